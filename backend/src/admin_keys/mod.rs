@@ -0,0 +1,241 @@
+//! Named admin API keys scoped to permission groups.
+//!
+//! Every admin route belongs to one [`AdminScope`] group (translations, gated
+//! config, cache, key management). A named key grants full access to the
+//! groups it is scoped to; `read_only` grants safe methods on every group.
+//! Keys are stored as SHA-256 digests — the plaintext is returned once, when a
+//! key is created or rotated, and never again.
+//!
+//! `ADMIN_API_KEY` stays valid as the bootstrap `root` identity holding every
+//! scope, so a fresh deploy can mint its first named keys. Durable state lives
+//! in [`store`].
+
+use axum::http::Method;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::AppError;
+
+mod store;
+
+pub use store::AdminKeyStore;
+
+/// Identity recorded for requests authenticated with `ADMIN_API_KEY`.
+pub const ROOT_KEY_NAME: &str = "root";
+
+/// Named keys held at once.
+pub const MAX_ADMIN_KEYS: usize = 64;
+
+/// Longest accepted key name.
+const MAX_KEY_NAME_LEN: usize = 64;
+
+/// Permission group an admin key is scoped to.
+///
+/// `translations`, `gated_config`, `cache` and `keys` each grant every method
+/// on their route group; `read_only` grants `GET`/`HEAD` on all groups.
+/// A `keys` identity can mint keys of any scope, so hand it out like root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminScope {
+    Translations,
+    GatedConfig,
+    Cache,
+    Keys,
+    ReadOnly,
+}
+
+impl AdminScope {
+    /// Every scope, as held by the root identity.
+    pub const ALL: [Self; 5] = [
+        Self::Translations,
+        Self::GatedConfig,
+        Self::Cache,
+        Self::Keys,
+        Self::ReadOnly,
+    ];
+}
+
+/// The authenticated caller, attached to admin requests as an extension by
+/// `admin_auth_middleware`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminIdentity {
+    pub name: String,
+    pub scopes: Vec<AdminScope>,
+}
+
+impl AdminIdentity {
+    /// The `ADMIN_API_KEY` identity.
+    pub fn root() -> Self {
+        Self {
+            name: ROOT_KEY_NAME.to_string(),
+            scopes: AdminScope::ALL.to_vec(),
+        }
+    }
+
+    /// Whether this identity may call a `method` route in `group`.
+    pub fn allows(&self, group: AdminScope, method: &Method) -> bool {
+        self.scopes.contains(&group)
+            || (self.scopes.contains(&AdminScope::ReadOnly)
+                && (method == Method::GET || method == Method::HEAD))
+    }
+}
+
+/// A named key as persisted: the digest, never the plaintext.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminKeyRecord {
+    pub name: String,
+    /// Hex SHA-256 of the key.
+    pub key_hash: String,
+    pub scopes: Vec<AdminScope>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotated_at: Option<DateTime<Utc>>,
+}
+
+/// A named key as listed by the admin API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminKeyInfo {
+    pub name: String,
+    pub scopes: Vec<AdminScope>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotated_at: Option<DateTime<Utc>>,
+}
+
+impl From<&AdminKeyRecord> for AdminKeyInfo {
+    fn from(record: &AdminKeyRecord) -> Self {
+        Self {
+            name: record.name.clone(),
+            scopes: record.scopes.clone(),
+            created_at: record.created_at,
+            rotated_at: record.rotated_at,
+        }
+    }
+}
+
+/// A freshly created or rotated key. `key` is the only copy of the plaintext.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuedAdminKey {
+    pub key: String,
+    #[serde(flatten)]
+    pub info: AdminKeyInfo,
+}
+
+/// Hex SHA-256 digest of a key, as stored and compared.
+pub fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Fresh admin key (256 bits from two v4 UUIDs).
+pub fn generate_key() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// Reject names that are empty, too long, reserved for the root identity, or
+/// not `[a-z0-9_-]`.
+pub fn validate_key_name(name: &str) -> Result<(), AppError> {
+    let invalid = |message: &str| AppError::Validation {
+        message: message.to_string(),
+        field: Some("name".to_string()),
+        details: None,
+    };
+    if name.is_empty() || name.len() > MAX_KEY_NAME_LEN {
+        return Err(invalid("Key name must be 1-64 characters"));
+    }
+    if name == ROOT_KEY_NAME {
+        return Err(invalid("Key name 'root' is reserved for ADMIN_API_KEY"));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        return Err(invalid(
+            "Key name may only contain lowercase letters, digits, '-' and '_'",
+        ));
+    }
+    Ok(())
+}
+
+/// Deduplicate `scopes`, rejecting an empty set.
+pub fn normalize_scopes(scopes: &[AdminScope]) -> Result<Vec<AdminScope>, AppError> {
+    let normalized: Vec<AdminScope> = AdminScope::ALL
+        .into_iter()
+        .filter(|scope| scopes.contains(scope))
+        .collect();
+    if normalized.is_empty() {
+        return Err(AppError::Validation {
+            message: "At least one scope is required".to_string(),
+            field: Some("scopes".to_string()),
+            details: None,
+        });
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(scopes: &[AdminScope]) -> AdminIdentity {
+        AdminIdentity {
+            name: "ops".to_string(),
+            scopes: scopes.to_vec(),
+        }
+    }
+
+    #[test]
+    fn scope_grants_every_method_on_its_group_only() {
+        let translator = identity(&[AdminScope::Translations]);
+        assert!(translator.allows(AdminScope::Translations, &Method::POST));
+        assert!(translator.allows(AdminScope::Translations, &Method::GET));
+        assert!(!translator.allows(AdminScope::GatedConfig, &Method::GET));
+        assert!(!translator.allows(AdminScope::Cache, &Method::POST));
+    }
+
+    #[test]
+    fn read_only_grants_safe_methods_everywhere() {
+        let viewer = identity(&[AdminScope::ReadOnly]);
+        assert!(viewer.allows(AdminScope::GatedConfig, &Method::GET));
+        assert!(viewer.allows(AdminScope::Cache, &Method::HEAD));
+        assert!(!viewer.allows(AdminScope::GatedConfig, &Method::PUT));
+        assert!(!viewer.allows(AdminScope::Keys, &Method::POST));
+    }
+
+    #[test]
+    fn root_allows_everything() {
+        let root = AdminIdentity::root();
+        for scope in AdminScope::ALL {
+            assert!(root.allows(scope, &Method::DELETE));
+        }
+    }
+
+    #[test]
+    fn key_names_are_validated() {
+        assert!(validate_key_name("translator-1").is_ok());
+        assert!(validate_key_name("").is_err());
+        assert!(validate_key_name("root").is_err());
+        assert!(validate_key_name("Ops Team").is_err());
+        assert!(validate_key_name(&"a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn scopes_are_deduplicated_and_must_be_non_empty() {
+        let scopes = normalize_scopes(&[AdminScope::Cache, AdminScope::Cache]).unwrap();
+        assert_eq!(scopes, vec![AdminScope::Cache]);
+        assert!(normalize_scopes(&[]).is_err());
+    }
+
+    #[test]
+    fn hash_is_stable_hex_sha256() {
+        assert_eq!(
+            hash_key("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(generate_key(), generate_key());
+    }
+}
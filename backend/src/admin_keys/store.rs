@@ -0,0 +1,246 @@
+//! Durable admin key store.
+//!
+//! A single locked [`KeyImage`] of named key records, persisted as a
+//! whole-image JSON file after every change with the same discipline as the
+//! lease ledger: unique temp file, `sync_all`, rename into place, parent
+//! directory fsync. A corrupt image on load falls back to `<path>.bak` and
+//! otherwise fails loudly — an empty store would silently revoke every key.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::image_file::ImageFile;
+
+use super::{
+    generate_key, hash_key, normalize_scopes, validate_key_name, AdminIdentity, AdminKeyInfo,
+    AdminKeyRecord, AdminScope, IssuedAdminKey, MAX_ADMIN_KEYS,
+};
+
+/// Everything the store persists, written as one image.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct KeyImage {
+    /// key name -> record
+    keys: HashMap<String, AdminKeyRecord>,
+}
+
+/// The durable admin key store.
+///
+/// The image is guarded by a single std [`Mutex`], never held across an
+/// `.await`. A separate async `write_gate` serializes persist calls so that
+/// the image written to disk is always the newest snapshot.
+pub struct AdminKeyStore {
+    file: ImageFile,
+    image: Mutex<KeyImage>,
+    write_gate: tokio::sync::Mutex<()>,
+}
+
+impl AdminKeyStore {
+    /// Bind a store to `path` with no named keys — the create path when no
+    /// prior image exists.
+    pub fn create(path: PathBuf) -> Self {
+        Self {
+            file: ImageFile::new(path, "admin key store"),
+            image: Mutex::new(KeyImage::default()),
+            write_gate: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Load an existing image from `path`. A corrupt primary image falls back
+    /// to `<path>.bak`; if neither loads, this returns `Err`.
+    pub async fn load(path: PathBuf) -> Result<Self, AppError> {
+        let file = ImageFile::new(path, "admin key store");
+        let image = file.load().await?;
+        Ok(Self {
+            file,
+            image: Mutex::new(image),
+            write_gate: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Durably write the current key set to the store's path.
+    pub async fn persist(&self) -> Result<(), AppError> {
+        // Snapshot INSIDE the write gate so snapshot order equals rename order.
+        let _write = self.write_gate.lock().await;
+        let snapshot = self.lock().clone();
+        self.file.write(&snapshot).await
+    }
+
+    /// The identity `key` authenticates as, if it matches a named key.
+    /// Digests are compared rather than plaintexts, so timing reveals nothing
+    /// about the stored key bytes.
+    pub fn identify(&self, key: &str) -> Option<AdminIdentity> {
+        let hash = hash_key(key);
+        self.lock()
+            .keys
+            .values()
+            .find(|record| record.key_hash == hash)
+            .map(|record| AdminIdentity {
+                name: record.name.clone(),
+                scopes: record.scopes.clone(),
+            })
+    }
+
+    /// Every named key, ordered by name.
+    pub fn list(&self) -> Vec<AdminKeyInfo> {
+        let mut keys: Vec<AdminKeyInfo> = self.lock().keys.values().map(Into::into).collect();
+        keys.sort_by(|a, b| a.name.cmp(&b.name));
+        keys
+    }
+
+    /// Mint a new named key with `scopes`.
+    pub async fn create_key(
+        &self,
+        name: &str,
+        scopes: &[AdminScope],
+    ) -> Result<IssuedAdminKey, AppError> {
+        validate_key_name(name)?;
+        let scopes = normalize_scopes(scopes)?;
+        let key = generate_key();
+        let record = AdminKeyRecord {
+            name: name.to_string(),
+            key_hash: hash_key(&key),
+            scopes,
+            created_at: Utc::now(),
+            rotated_at: None,
+        };
+        {
+            let mut image = self.lock();
+            if image.keys.contains_key(name) {
+                return Err(AppError::Validation {
+                    message: format!("Admin key '{name}' already exists"),
+                    field: Some("name".to_string()),
+                    details: None,
+                });
+            }
+            if image.keys.len() >= MAX_ADMIN_KEYS {
+                return Err(AppError::Validation {
+                    message: format!("At most {MAX_ADMIN_KEYS} admin keys may exist"),
+                    field: None,
+                    details: None,
+                });
+            }
+            image.keys.insert(name.to_string(), record.clone());
+        }
+        self.persist().await?;
+        Ok(IssuedAdminKey {
+            key,
+            info: (&record).into(),
+        })
+    }
+
+    /// Replace the key behind `name`, keeping its scopes. The old key stops
+    /// authenticating immediately.
+    pub async fn rotate_key(&self, name: &str) -> Result<IssuedAdminKey, AppError> {
+        let key = generate_key();
+        let info = {
+            let mut image = self.lock();
+            let record = image.keys.get_mut(name).ok_or_else(|| AppError::NotFound {
+                resource: format!("Admin key: {name}"),
+            })?;
+            record.key_hash = hash_key(&key);
+            record.rotated_at = Some(Utc::now());
+            AdminKeyInfo::from(&*record)
+        };
+        self.persist().await?;
+        Ok(IssuedAdminKey { key, info })
+    }
+
+    /// Remove the key behind `name`.
+    pub async fn revoke_key(&self, name: &str) -> Result<(), AppError> {
+        if self.lock().keys.remove(name).is_none() {
+            return Err(AppError::NotFound {
+                resource: format!("Admin key: {name}"),
+            });
+        }
+        self.persist().await
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, KeyImage> {
+        self.image.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Ephemeral store bound to a unique temp file, for tests only.
+    #[cfg(test)]
+    pub fn ephemeral() -> Self {
+        let path = crate::image_file::ephemeral_path("admin-keys");
+        Self::create(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn created_key_identifies_and_survives_reload_hashed() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("admin_keys.json");
+        let store = AdminKeyStore::create(path.clone());
+        let issued = store
+            .create_key("translator", &[AdminScope::Translations])
+            .await
+            .unwrap();
+
+        let on_disk = tokio::fs::read_to_string(&path).await.unwrap();
+        assert!(
+            !on_disk.contains(&issued.key),
+            "plaintext must not be stored"
+        );
+
+        let reloaded = AdminKeyStore::load(path).await.unwrap();
+        let identity = reloaded.identify(&issued.key).unwrap();
+        assert_eq!(identity.name, "translator");
+        assert_eq!(identity.scopes, vec![AdminScope::Translations]);
+        assert!(reloaded.identify("wrong").is_none());
+    }
+
+    #[tokio::test]
+    async fn duplicate_name_is_rejected() {
+        let store = AdminKeyStore::ephemeral();
+        store.create_key("ops", &[AdminScope::Cache]).await.unwrap();
+        assert!(store
+            .create_key("ops", &[AdminScope::ReadOnly])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn rotation_invalidates_old_key_and_keeps_scopes() {
+        let store = AdminKeyStore::ephemeral();
+        let old = store
+            .create_key("ops", &[AdminScope::GatedConfig])
+            .await
+            .unwrap();
+        let new = store.rotate_key("ops").await.unwrap();
+
+        assert!(store.identify(&old.key).is_none());
+        let identity = store.identify(&new.key).unwrap();
+        assert_eq!(identity.scopes, vec![AdminScope::GatedConfig]);
+        assert!(new.info.rotated_at.is_some());
+        assert!(store.rotate_key("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn revoked_key_stops_authenticating() {
+        let store = AdminKeyStore::ephemeral();
+        let issued = store.create_key("ops", &[AdminScope::Cache]).await.unwrap();
+        store.revoke_key("ops").await.unwrap();
+        assert!(store.identify(&issued.key).is_none());
+        assert!(store.list().is_empty());
+        assert!(store.revoke_key("ops").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn corrupt_image_without_backup_fails_loudly() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("admin_keys.json");
+        tokio::fs::write(&path, b"[").await.unwrap();
+        assert!(AdminKeyStore::load(path).await.is_err());
+    }
+}
@@ -0,0 +1,940 @@
+//! Append-only, hash-chained JSONL audit file set.
+//!
+//! Each line is `{"seq", "prev_hash", "hash", "entry"}` where `hash` is the
+//! HMAC-SHA256 of `seq|prev_hash|entry` under the [`AuditKey`]. The first line
+//! of a chain links to [`GENESIS_HASH`]. The active file `audit.jsonl` is
+//! appended and `sync_data`'d per entry; once it reaches the size or age limit
+//! it is renamed to `audit-<first seq>.jsonl` and only the newest segments are
+//! kept.
+//!
+//! `head.json` records the newest seq and hash and the oldest retained seq,
+//! itself MAC'd, and is rewritten after every append and before every prune.
+//! Lines can't be forged without the key, so the head only has to catch what
+//! the hashes can't: dropping the newest lines or deleting old segments.
+
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use tokio::fs;
+use tokio::io::AsyncWriteExt as _;
+use tracing::{error, info, warn};
+
+use super::{AuditKey, AuditRecord, AuditRetention, AuditRow};
+use crate::error::AppError;
+use crate::image_file::ImageFile;
+
+/// `prev_hash` of the first entry in a chain.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// File currently appended to.
+const ACTIVE_FILE: &str = "audit.jsonl";
+
+/// Prefix of rotated segment files.
+const SEGMENT_PREFIX: &str = "audit-";
+
+/// Keyed record of the chain's extent.
+const HEAD_FILE: &str = "head.json";
+
+/// One chained line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainedEntry<T> {
+    pub seq: u64,
+    pub prev_hash: String,
+    pub hash: String,
+    pub entry: T,
+}
+
+impl<T: AuditRecord> ChainedEntry<T> {
+    /// Flattened view for the unified audit query and export
+    pub fn to_row(&self) -> AuditRow {
+        self.entry.to_row(self.seq, &self.hash)
+    }
+}
+
+/// Result of walking every retained line of a chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChainVerification {
+    pub valid: bool,
+    /// Lines checked before the first failure (or in total)
+    pub entries: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_seq: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_seq: Option<u64>,
+    /// Where and why the chain broke
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Newest seq and hash; record them elsewhere to detect a later rewrite
+    /// by someone holding the key
+    pub head_seq: u64,
+    pub head_hash: String,
+}
+
+/// Extent of the chain as last written, MAC'd so it can't be rolled back to
+/// match a truncated chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Head {
+    seq: u64,
+    hash: String,
+    /// Oldest seq still retained
+    first_seq: u64,
+    mac: String,
+}
+
+/// Append position, recovered from disk at init.
+#[derive(Debug)]
+struct Tail {
+    seq: u64,
+    hash: String,
+    active_bytes: u64,
+    active_first_seq: Option<u64>,
+    active_started: Option<DateTime<Utc>>,
+    /// Oldest seq still retained (1 until something is pruned)
+    first_seq: u64,
+}
+
+impl Tail {
+    fn genesis() -> Self {
+        Self {
+            seq: 0,
+            hash: GENESIS_HASH.to_string(),
+            first_seq: 1,
+            active_bytes: 0,
+            active_first_seq: None,
+            active_started: None,
+        }
+    }
+}
+
+/// A hash-chained audit log for one entry type.
+///
+/// Appends and rotation take the `tail` lock. Readers hold it only long
+/// enough to list the segments and copy the active file, the one file that
+/// changes under appends, and parse outside it.
+#[derive(Debug)]
+pub struct AuditChain<T> {
+    dir: PathBuf,
+    retention: AuditRetention,
+    key: AuditKey,
+    head: ImageFile,
+    tail: tokio::sync::Mutex<Tail>,
+    _entry: PhantomData<fn() -> T>,
+}
+
+impl<T: AuditRecord> AuditChain<T> {
+    /// Bind a chain to `dir`. Nothing is read until [`Self::init`].
+    pub fn new(dir: PathBuf, retention: AuditRetention, key: AuditKey) -> Self {
+        Self {
+            head: ImageFile::new(dir.join(HEAD_FILE), "audit head"),
+            dir,
+            retention,
+            key,
+            tail: tokio::sync::Mutex::new(Tail::genesis()),
+            _entry: PhantomData,
+        }
+    }
+
+    /// Create the directory and recover the append position from the newest
+    /// file. A torn final line (crash mid-append) is cut off first, so the
+    /// next append starts on a fresh line instead of fusing with it.
+    ///
+    /// If the files end before the recorded head, the newest lines were
+    /// removed. Appending resumes after the head rather than reusing their
+    /// seqs, so the gap stays visible to [`Self::verify`].
+    pub async fn init(&self) -> Result<(), AppError> {
+        fs::create_dir_all(&self.dir).await.map_err(|e| {
+            AppError::Internal(format!(
+                "Failed to create audit directory {}: {e}",
+                self.dir.display()
+            ))
+        })?;
+
+        let mut tail = self.tail.lock().await;
+        *tail = self.recover_tail().await?;
+
+        if let Some(head) = self.load_head().await? {
+            if head.seq > tail.seq {
+                error!(
+                    "Audit log {} ends at seq {} but its head records seq {}; entries were removed",
+                    self.dir.display(),
+                    tail.seq,
+                    head.seq
+                );
+                tail.seq = head.seq;
+                tail.hash = head.hash;
+            }
+        }
+        Ok(())
+    }
+
+    /// Append position as the files on disk describe it.
+    async fn recover_tail(&self) -> Result<Tail, AppError> {
+        let mut tail = Tail::genesis();
+        let segments = self.segments().await?;
+        if let Some(first_segment) = segments.first() {
+            if let Some(first) = read_lines(first_segment).await?.first() {
+                tail.first_seq = first.seq;
+            }
+        }
+        if let Some(last_segment) = segments.last() {
+            if let Some(last) = read_lines(last_segment).await?.last() {
+                tail.seq = last.seq;
+                tail.hash.clone_from(&last.hash);
+            }
+        }
+
+        let active = self.active_path();
+        if active.exists() {
+            truncate_torn_line(&active).await?;
+            let lines = read_lines(&active).await?;
+            if let Some(first) = lines.first() {
+                tail.active_first_seq = Some(first.seq);
+                tail.active_started = entry_timestamp::<T>(&first.entry);
+                if segments.is_empty() {
+                    tail.first_seq = first.seq;
+                }
+            }
+            if let Some(last) = lines.last() {
+                tail.seq = last.seq;
+                tail.hash.clone_from(&last.hash);
+            }
+            tail.active_bytes = fs::metadata(&active).await.map(|m| m.len()).unwrap_or(0);
+        }
+        Ok(tail)
+    }
+
+    /// Append one entry, rotating first if the active file is due.
+    pub async fn append(&self, entry: &T) -> Result<ChainedEntry<T>, AppError> {
+        let value = serde_json::to_value(entry)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize audit entry: {e}")))?;
+
+        let mut tail = self.tail.lock().await;
+        if self.rotation_due(&tail, entry.timestamp()) {
+            self.rotate(&mut tail).await?;
+        }
+
+        let seq = tail.seq + 1;
+        let hash = chain_hash(&self.key, seq, &tail.hash, &value);
+        let line = ChainedEntry {
+            seq,
+            prev_hash: tail.hash.clone(),
+            hash: hash.clone(),
+            entry: value,
+        };
+        let mut bytes = serde_json::to_vec(&line)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize audit entry: {e}")))?;
+        bytes.push(b'\n');
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.active_path())
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to open audit log: {e}")))?;
+        file.write_all(&bytes)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to append audit log: {e}")))?;
+        file.sync_data()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to sync audit log: {e}")))?;
+
+        tail.seq = seq;
+        tail.hash.clone_from(&hash);
+        tail.active_bytes += u64::try_from(bytes.len()).unwrap_or(u64::MAX);
+        if tail.active_first_seq.is_none() {
+            tail.active_first_seq = Some(seq);
+            tail.active_started = Some(entry.timestamp());
+        }
+        self.write_head(&tail).await?;
+
+        Ok(ChainedEntry {
+            seq,
+            prev_hash: line.prev_hash,
+            hash,
+            entry: entry.clone(),
+        })
+    }
+
+    /// Every retained entry, oldest first. Lines that fail to parse are
+    /// skipped; [`Self::verify`] reports them.
+    pub async fn read_all(&self) -> Result<Vec<ChainedEntry<T>>, AppError> {
+        self.read_since(None).await
+    }
+
+    /// Retained entries, oldest first, skipping segments last written before
+    /// `from`. Callers still filter entries by time; this only avoids parsing
+    /// segments that can't match.
+    pub async fn read_since(
+        &self,
+        from: Option<DateTime<Utc>>,
+    ) -> Result<Vec<ChainedEntry<T>>, AppError> {
+        let (segments, active) = {
+            let _tail = self.tail.lock().await;
+            let active = self.active_path();
+            let content = if active.exists() {
+                Some(read_file(&active).await?)
+            } else {
+                None
+            };
+            (self.segments().await?, content)
+        };
+
+        let mut entries = Vec::new();
+        for path in segments {
+            if let Some(from) = from {
+                if written_before(&path, from).await {
+                    continue;
+                }
+            }
+            // Segments never change once rotated, but one may be pruned
+            // after it was listed.
+            match fs::read_to_string(&path).await {
+                Ok(content) => parse_entries(&content, &mut entries),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(AppError::Internal(format!(
+                        "Failed to read audit log {}: {e}",
+                        path.display()
+                    )))
+                }
+            }
+        }
+        if let Some(content) = active {
+            parse_entries(&content, &mut entries);
+        }
+        Ok(entries)
+    }
+
+    /// Walk every retained line and check sequence, linkage and hashes, then
+    /// check the lines against the head record.
+    ///
+    /// When old segments have been pruned the first retained line cannot be
+    /// linked back to genesis; its own hash is still checked, and the head
+    /// says how far pruning went.
+    pub async fn verify(&self) -> Result<ChainVerification, AppError> {
+        let tail = self.tail.lock().await;
+        let mut report = ChainVerification {
+            valid: true,
+            entries: 0,
+            first_seq: None,
+            last_seq: None,
+            error: None,
+            head_seq: tail.seq,
+            head_hash: tail.hash.clone(),
+        };
+        report.error = match self.load_head().await {
+            Ok(head) => match self.walk_lines(head.as_ref(), &mut report).await? {
+                Some(broken) => Some(broken),
+                None => check_extent(head.as_ref(), report.first_seq, report.last_seq),
+            },
+            Err(e) => Some(e.to_string()),
+        };
+        report.valid = report.error.is_none();
+        Ok(report)
+    }
+
+    /// Count every retained line into `report`, stopping at the first one
+    /// that fails its checks and returning where and why.
+    async fn walk_lines(
+        &self,
+        head: Option<&Head>,
+        report: &mut ChainVerification,
+    ) -> Result<Option<String>, AppError> {
+        let mut previous: Option<(u64, String)> = None;
+        for path in self.files().await? {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let content = read_file(&path).await?;
+            for (index, raw) in content.lines().enumerate() {
+                let checked = serde_json::from_str::<ChainedEntry<Value>>(raw)
+                    .map_err(|e| format!("unparseable ({e})"))
+                    .and_then(|line| {
+                        check_link(&self.key, previous.as_ref(), head, &line).map(|()| line)
+                    });
+                match checked {
+                    Ok(line) => {
+                        report.entries += 1;
+                        report.first_seq.get_or_insert(line.seq);
+                        report.last_seq = Some(line.seq);
+                        previous = Some((line.seq, line.hash));
+                    }
+                    Err(why) => return Ok(Some(format!("{name} line {}: {why}", index + 1))),
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Append `entries` from a pre-chain audit file at `path` if the chain is
+    /// still empty, then rename the file to `<path>.migrated`.
+    pub async fn import_legacy(&self, path: &Path, entries: Vec<T>) -> Result<(), AppError> {
+        if self.tail.lock().await.seq == 0 {
+            for entry in &entries {
+                self.append(entry).await?;
+            }
+            info!(
+                "Imported {} legacy audit entries from {}",
+                entries.len(),
+                path.display()
+            );
+        }
+        let mut migrated = path.as_os_str().to_os_string();
+        migrated.push(".migrated");
+        fs::rename(path, &migrated).await.map_err(|e| {
+            AppError::Internal(format!(
+                "Failed to retire legacy audit log {}: {e}",
+                path.display()
+            ))
+        })
+    }
+
+    fn active_path(&self) -> PathBuf {
+        self.dir.join(ACTIVE_FILE)
+    }
+
+    /// The head record, `None` before the first append. A head whose MAC
+    /// fails is an error.
+    async fn load_head(&self) -> Result<Option<Head>, AppError> {
+        if !self.dir.join(HEAD_FILE).exists() {
+            return Ok(None);
+        }
+        let head: Head = self.head.load().await?;
+        if head_mac(&self.key, head.seq, &head.hash, head.first_seq) != head.mac {
+            return Err(AppError::Internal(format!(
+                "Audit head in {} fails authentication",
+                self.dir.display()
+            )));
+        }
+        Ok(Some(head))
+    }
+
+    async fn write_head(&self, tail: &Tail) -> Result<(), AppError> {
+        self.head
+            .write(&Head {
+                seq: tail.seq,
+                hash: tail.hash.clone(),
+                first_seq: tail.first_seq,
+                mac: head_mac(&self.key, tail.seq, &tail.hash, tail.first_seq),
+            })
+            .await
+    }
+
+    fn rotation_due(&self, tail: &Tail, now: DateTime<Utc>) -> bool {
+        tail.active_bytes >= self.retention.max_file_bytes
+            || tail
+                .active_started
+                .is_some_and(|started| now - started >= self.retention.max_file_age)
+    }
+
+    /// Rename the active file to a segment and prune beyond `max_segments`.
+    async fn rotate(&self, tail: &mut Tail) -> Result<(), AppError> {
+        let Some(first_seq) = tail.active_first_seq else {
+            return Ok(());
+        };
+        let segment = self
+            .dir
+            .join(format!("{SEGMENT_PREFIX}{first_seq:012}.jsonl"));
+        fs::rename(self.active_path(), &segment)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to rotate audit log: {e}")))?;
+        info!("Rotated audit log to {}", segment.display());
+
+        tail.active_bytes = 0;
+        tail.active_first_seq = None;
+        tail.active_started = None;
+
+        let segments = self.segments().await?;
+        let excess = segments.len().saturating_sub(self.retention.max_segments);
+        if excess == 0 {
+            return Ok(());
+        }
+        // Record how far pruning goes before deleting anything, so a crash
+        // mid-prune leaves extra segments rather than an unexplained gap.
+        if let Some(first_seq) = segments
+            .get(excess)
+            .and_then(|kept| segment_first_seq(kept))
+        {
+            tail.first_seq = first_seq;
+            self.write_head(tail).await?;
+        }
+        for old in segments.iter().take(excess) {
+            match fs::remove_file(old).await {
+                Ok(()) => info!("Pruned audit segment {}", old.display()),
+                Err(e) => warn!("Failed to prune audit segment {}: {e}", old.display()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Rotated segments, oldest first.
+    async fn segments(&self) -> Result<Vec<PathBuf>, AppError> {
+        let mut dir = match fs::read_dir(&self.dir).await {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(AppError::Internal(format!("Failed to list audit log: {e}"))),
+        };
+        let mut segments = Vec::new();
+        while let Some(item) = dir
+            .next_entry()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to list audit log: {e}")))?
+        {
+            let name = item.file_name().to_string_lossy().into_owned();
+            if name.starts_with(SEGMENT_PREFIX) && name.ends_with(".jsonl") {
+                segments.push(item.path());
+            }
+        }
+        // Zero-padded first seq, so lexical order is chain order.
+        segments.sort();
+        Ok(segments)
+    }
+
+    /// Segments then the active file, in chain order.
+    async fn files(&self) -> Result<Vec<PathBuf>, AppError> {
+        let mut files = self.segments().await?;
+        let active = self.active_path();
+        if active.exists() {
+            files.push(active);
+        }
+        Ok(files)
+    }
+}
+
+/// Check one line against its predecessor (`None` for the first retained
+/// line) and, if it is the line the head points at, against the head.
+fn check_link(
+    key: &AuditKey,
+    previous: Option<&(u64, String)>,
+    head: Option<&Head>,
+    line: &ChainedEntry<Value>,
+) -> Result<(), String> {
+    match previous {
+        Some((seq, _)) if line.seq != seq + 1 => {
+            return Err(format!("seq {} follows {seq}", line.seq));
+        }
+        Some((_, hash)) if &line.prev_hash != hash => {
+            return Err(format!("seq {} does not link to its predecessor", line.seq));
+        }
+        None if line.seq == 1 && line.prev_hash != GENESIS_HASH => {
+            return Err("seq 1 does not link to genesis".to_string());
+        }
+        _ => {}
+    }
+    if chain_hash(key, line.seq, &line.prev_hash, &line.entry) != line.hash {
+        return Err(format!("seq {} hash mismatch", line.seq));
+    }
+    match head {
+        Some(head) if head.seq == line.seq && head.hash != line.hash => {
+            Err(format!("seq {} does not match the head", line.seq))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check the retained lines span what the head says they should: nothing
+/// newer than the head was dropped, and nothing older than its `first_seq`.
+fn check_extent(head: Option<&Head>, first: Option<u64>, last: Option<u64>) -> Option<String> {
+    let Some(head) = head else {
+        return last.map(|_| "head record is missing".to_string());
+    };
+    if last.unwrap_or(0) < head.seq {
+        return Some(format!(
+            "chain ends at seq {} but the head records seq {}",
+            last.unwrap_or(0),
+            head.seq
+        ));
+    }
+    match first {
+        Some(first) if first > head.first_seq => Some(format!(
+            "entries from seq {} to {} are missing",
+            head.first_seq,
+            first - 1
+        )),
+        _ => None,
+    }
+}
+
+/// Hex HMAC-SHA256 of `seq|prev_hash|entry`, with `entry` as compact JSON.
+fn chain_hash(key: &AuditKey, seq: u64, prev_hash: &str, entry: &Value) -> String {
+    let mut mac = hmac(key);
+    mac.update(format!("{seq}|{prev_hash}|").as_bytes());
+    mac.update(entry.to_string().as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Hex HMAC-SHA256 of `head|seq|hash|first_seq`.
+fn head_mac(key: &AuditKey, seq: u64, hash: &str, first_seq: u64) -> String {
+    let mut mac = hmac(key);
+    mac.update(format!("head|{seq}|{hash}|{first_seq}").as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn hmac(key: &AuditKey) -> Hmac<Sha256> {
+    <Hmac<Sha256> as Mac>::new_from_slice(key.as_bytes())
+        .unwrap_or_else(|_| unreachable!("HMAC accepts keys of any length"))
+}
+
+/// First seq of a rotated segment, from its `audit-<first seq>.jsonl` name.
+fn segment_first_seq(path: &Path) -> Option<u64> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix(SEGMENT_PREFIX)?
+        .strip_suffix(".jsonl")?
+        .parse()
+        .ok()
+}
+
+fn entry_timestamp<T: AuditRecord>(entry: &Value) -> Option<DateTime<Utc>> {
+    serde_json::from_value::<T>(entry.clone())
+        .ok()
+        .map(|entry| entry.timestamp())
+}
+
+async fn read_file(path: &Path) -> Result<String, AppError> {
+    fs::read_to_string(path).await.map_err(|e| {
+        AppError::Internal(format!("Failed to read audit log {}: {e}", path.display()))
+    })
+}
+
+/// Cut `path` back to its last newline. Every append ends with one, so any
+/// bytes after it are a line torn by a crash mid-append and were never
+/// acknowledged to the caller.
+async fn truncate_torn_line(path: &Path) -> Result<(), AppError> {
+    let content = fs::read(path).await.map_err(|e| {
+        AppError::Internal(format!("Failed to read audit log {}: {e}", path.display()))
+    })?;
+    if content.last().is_none_or(|&byte| byte == b'\n') {
+        return Ok(());
+    }
+    let keep = content
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |newline| newline + 1);
+    warn!(
+        "Dropping {} bytes of a torn audit line at the end of {}",
+        content.len() - keep,
+        path.display()
+    );
+
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to open audit log: {e}")))?;
+    file.set_len(u64::try_from(keep).unwrap_or(u64::MAX))
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to truncate audit log: {e}")))?;
+    file.sync_data()
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to sync audit log: {e}")))
+}
+
+/// Whether `path` was last modified before `from`. Unknown counts as not.
+async fn written_before(path: &Path, from: DateTime<Utc>) -> bool {
+    fs::metadata(path)
+        .await
+        .and_then(|meta| meta.modified())
+        .is_ok_and(|modified| DateTime::<Utc>::from(modified) < from)
+}
+
+/// Append the parseable entries of one file's `content` to `entries`.
+fn parse_entries<T: AuditRecord>(content: &str, entries: &mut Vec<ChainedEntry<T>>) {
+    for line in content
+        .lines()
+        .filter_map(|raw| serde_json::from_str::<ChainedEntry<Value>>(raw).ok())
+    {
+        match serde_json::from_value(line.entry) {
+            Ok(entry) => entries.push(ChainedEntry {
+                seq: line.seq,
+                prev_hash: line.prev_hash,
+                hash: line.hash,
+                entry,
+            }),
+            Err(e) => warn!("Skipping audit entry {}: {e}", line.seq),
+        }
+    }
+}
+
+/// Parseable lines of one file.
+async fn read_lines(path: &Path) -> Result<Vec<ChainedEntry<Value>>, AppError> {
+    let content = read_file(path).await?;
+    Ok(content
+        .lines()
+        .filter_map(|raw| serde_json::from_str(raw).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditSource;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct Note {
+        timestamp: DateTime<Utc>,
+        text: String,
+    }
+
+    impl AuditRecord for Note {
+        fn timestamp(&self) -> DateTime<Utc> {
+            self.timestamp
+        }
+
+        fn to_row(&self, seq: u64, hash: &str) -> AuditRow {
+            AuditRow {
+                source: AuditSource::Config,
+                seq,
+                hash: hash.to_string(),
+                id: seq.to_string(),
+                timestamp: self.timestamp,
+                actor: None,
+                action: "note".to_string(),
+                resource: self.text.clone(),
+                details: None,
+                before: None,
+                after: None,
+                ip: None,
+                request_id: None,
+            }
+        }
+    }
+
+    fn note(text: &str) -> Note {
+        Note {
+            timestamp: Utc::now(),
+            text: text.to_string(),
+        }
+    }
+
+    fn retention(max_file_bytes: u64, max_segments: usize) -> AuditRetention {
+        AuditRetention {
+            max_file_bytes,
+            max_file_age: chrono::Duration::days(1),
+            max_segments,
+        }
+    }
+
+    async fn chain(dir: &TempDir, retention: AuditRetention) -> AuditChain<Note> {
+        let chain = AuditChain::new(
+            dir.path().join("chain"),
+            retention,
+            AuditKey::new("test-key"),
+        );
+        chain.init().await.unwrap();
+        chain
+    }
+
+    #[tokio::test]
+    async fn entries_link_and_survive_restart() {
+        let dir = TempDir::new().unwrap();
+        let first = chain(&dir, AuditRetention::default()).await;
+        let a = first.append(&note("a")).await.unwrap();
+        assert_eq!(a.seq, 1);
+        assert_eq!(a.prev_hash, GENESIS_HASH);
+
+        let reopened = chain(&dir, AuditRetention::default()).await;
+        let b = reopened.append(&note("b")).await.unwrap();
+        assert_eq!(b.seq, 2);
+        assert_eq!(b.prev_hash, a.hash);
+
+        let all = reopened.read_all().await.unwrap();
+        assert_eq!(all.len(), 2);
+        let report = reopened.verify().await.unwrap();
+        assert!(report.valid, "{report:?}");
+        assert_eq!(report.entries, 2);
+    }
+
+    #[tokio::test]
+    async fn edited_line_is_detected() {
+        let dir = TempDir::new().unwrap();
+        let chain = chain(&dir, AuditRetention::default()).await;
+        chain.append(&note("grant")).await.unwrap();
+        chain.append(&note("later")).await.unwrap();
+
+        let path = dir.path().join("chain").join(ACTIVE_FILE);
+        let content = fs::read_to_string(&path).await.unwrap();
+        fs::write(&path, content.replacen("grant", "deny", 1))
+            .await
+            .unwrap();
+
+        let report = chain.verify().await.unwrap();
+        assert!(!report.valid);
+        assert!(report.error.unwrap().contains("seq 1 hash mismatch"));
+    }
+
+    #[tokio::test]
+    async fn rewrite_without_the_key_is_detected() {
+        let dir = TempDir::new().unwrap();
+        let chain = chain(&dir, AuditRetention::default()).await;
+        chain.append(&note("grant")).await.unwrap();
+
+        // Recompute the line's hash as an attacker without the key would.
+        let forged = AuditChain::<Note>::new(
+            dir.path().join("forged"),
+            AuditRetention::default(),
+            AuditKey::new("guess"),
+        );
+        forged.init().await.unwrap();
+        forged.append(&note("deny")).await.unwrap();
+        fs::copy(
+            dir.path().join("forged").join(ACTIVE_FILE),
+            dir.path().join("chain").join(ACTIVE_FILE),
+        )
+        .await
+        .unwrap();
+
+        let report = chain.verify().await.unwrap();
+        assert!(!report.valid);
+        assert!(report.error.unwrap().contains("seq 1 hash mismatch"));
+    }
+
+    #[tokio::test]
+    async fn truncated_tail_is_detected_and_not_reused() {
+        let dir = TempDir::new().unwrap();
+        let first = chain(&dir, AuditRetention::default()).await;
+        for text in ["a", "b", "c"] {
+            first.append(&note(text)).await.unwrap();
+        }
+
+        let path = dir.path().join("chain").join(ACTIVE_FILE);
+        let content = fs::read_to_string(&path).await.unwrap();
+        let kept: Vec<&str> = content.lines().take(1).collect();
+        fs::write(&path, kept.join("\n") + "\n").await.unwrap();
+
+        let report = first.verify().await.unwrap();
+        assert!(!report.valid);
+        assert!(report.error.unwrap().contains("head records seq 3"));
+
+        // After a restart the next entry still leaves the gap visible.
+        let reopened = chain(&dir, AuditRetention::default()).await;
+        assert_eq!(reopened.append(&note("d")).await.unwrap().seq, 4);
+        let report = reopened.verify().await.unwrap();
+        assert!(!report.valid);
+        assert!(report.error.unwrap().contains("seq 4 follows 1"));
+    }
+
+    #[tokio::test]
+    async fn deleted_line_is_detected() {
+        let dir = TempDir::new().unwrap();
+        let chain = chain(&dir, AuditRetention::default()).await;
+        for text in ["a", "b", "c"] {
+            chain.append(&note(text)).await.unwrap();
+        }
+
+        let path = dir.path().join("chain").join(ACTIVE_FILE);
+        let content = fs::read_to_string(&path).await.unwrap();
+        let kept: Vec<&str> = content
+            .lines()
+            .enumerate()
+            .filter(|(i, _)| *i != 1)
+            .map(|(_, line)| line)
+            .collect();
+        fs::write(&path, kept.join("\n") + "\n").await.unwrap();
+
+        let report = chain.verify().await.unwrap();
+        assert!(!report.valid);
+        assert!(report.error.unwrap().contains("seq 3 follows 1"));
+    }
+
+    #[tokio::test]
+    async fn torn_line_is_cut_off_before_the_next_append() {
+        let dir = TempDir::new().unwrap();
+        let first = chain(&dir, AuditRetention::default()).await;
+        first.append(&note("a")).await.unwrap();
+
+        // Crash mid-append: half a line with no trailing newline.
+        let path = dir.path().join("chain").join(ACTIVE_FILE);
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .unwrap();
+        file.write_all(br#"{"seq":2,"prev_ha"#).await.unwrap();
+        drop(file);
+
+        let reopened = chain(&dir, AuditRetention::default()).await;
+        let b = reopened.append(&note("b")).await.unwrap();
+        assert_eq!(b.seq, 2);
+
+        assert_eq!(reopened.read_all().await.unwrap().len(), 2);
+        let report = reopened.verify().await.unwrap();
+        assert!(report.valid, "{report:?}");
+        assert_eq!(report.last_seq, Some(2));
+    }
+
+    #[tokio::test]
+    async fn rotation_keeps_chain_and_prunes_old_segments() {
+        let dir = TempDir::new().unwrap();
+        // Every append finds the active file over the limit and rotates.
+        let chain = chain(&dir, retention(1, 2)).await;
+        for text in ["a", "b", "c", "d", "e"] {
+            chain.append(&note(text)).await.unwrap();
+        }
+
+        let entries = chain.read_all().await.unwrap();
+        let seqs: Vec<u64> = entries.iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![3, 4, 5]);
+
+        let report = chain.verify().await.unwrap();
+        assert!(report.valid, "{report:?}");
+        assert_eq!(report.first_seq, Some(3));
+        assert_eq!(report.head_seq, 5);
+
+        // Deleting a segment the head still counts as retained is caught.
+        let oldest = chain.segments().await.unwrap().remove(0);
+        fs::remove_file(oldest).await.unwrap();
+        let report = chain.verify().await.unwrap();
+        assert!(!report.valid);
+        assert!(report
+            .error
+            .unwrap()
+            .contains("from seq 3 to 3 are missing"));
+    }
+
+    #[tokio::test]
+    async fn read_since_skips_segments_written_before_the_range() {
+        let dir = TempDir::new().unwrap();
+        // Every append rotates, so each earlier entry sits in its own segment.
+        let chain = chain(&dir, retention(1, 10)).await;
+        for text in ["a", "b", "c"] {
+            chain.append(&note(text)).await.unwrap();
+        }
+        let oldest = chain.segments().await.unwrap().remove(0);
+        std::fs::File::options()
+            .write(true)
+            .open(&oldest)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(3600))
+            .unwrap();
+
+        let since = chain
+            .read_since(Some(Utc::now() - chrono::Duration::minutes(1)))
+            .await
+            .unwrap();
+        let seqs: Vec<u64> = since.iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![2, 3]);
+        assert_eq!(chain.read_all().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn legacy_entries_are_imported_once() {
+        let dir = TempDir::new().unwrap();
+        let legacy = dir.path().join("audit-log.json");
+        fs::write(&legacy, "[]").await.unwrap();
+
+        let chain = chain(&dir, AuditRetention::default()).await;
+        chain
+            .import_legacy(&legacy, vec![note("old-1"), note("old-2")])
+            .await
+            .unwrap();
+
+        assert!(!legacy.exists());
+        assert!(dir.path().join("audit-log.json.migrated").exists());
+        assert_eq!(chain.read_all().await.unwrap().len(), 2);
+    }
+}
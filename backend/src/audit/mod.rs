@@ -0,0 +1,265 @@
+//! Tamper-evident admin audit log.
+//!
+//! Config and translation changes are each appended to their own
+//! [`AuditChain`]: an append-only JSONL file set where every line carries an
+//! HMAC-SHA256 (keyed by `AUDIT_HMAC_KEY`) linking it to its predecessor, so
+//! editing, dropping or reordering a retained line is caught by
+//! [`AuditChain::verify`] even by someone who can write the files. A keyed
+//! head record catches truncation and pruning beyond retention. Active files
+//! rotate by size and age; only the newest segments are retained.
+//!
+//! `admin_auth_middleware` runs each admin request inside [`scope`], which
+//! makes the caller's IP and request ID visible to every audit write made
+//! while handling it via [`current_request`].
+
+use std::future::Future;
+
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+mod chain;
+
+pub use chain::{AuditChain, ChainVerification, ChainedEntry};
+
+/// Default size at which the active file rotates (5 MiB).
+const DEFAULT_MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Default age at which the active file rotates.
+const DEFAULT_MAX_FILE_AGE_HOURS: i64 = 24 * 7;
+
+/// Default number of rotated segments kept per chain.
+const DEFAULT_MAX_SEGMENTS: usize = 30;
+
+/// CSV export header, matching [`AuditRow::to_csv_line`].
+pub const CSV_HEADER: &str =
+    "source,seq,hash,id,timestamp,actor,action,resource,details,before,after,ip,request_id";
+
+tokio::task_local! {
+    static REQUEST_CONTEXT: RequestContext;
+}
+
+/// Request metadata attached to audit entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestContext {
+    pub ip: Option<String>,
+    pub request_id: Option<String>,
+}
+
+/// Run `future` with `context` as the current request for audit writes.
+pub async fn scope<F: Future>(context: RequestContext, future: F) -> F::Output {
+    REQUEST_CONTEXT.scope(context, future).await
+}
+
+/// The request being handled, or empty outside [`scope`] (background jobs,
+/// startup migration).
+pub fn current_request() -> RequestContext {
+    REQUEST_CONTEXT.try_with(Clone::clone).unwrap_or_default()
+}
+
+/// When an active file rotates and how many segments survive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditRetention {
+    pub max_file_bytes: u64,
+    pub max_file_age: chrono::Duration,
+    pub max_segments: usize,
+}
+
+impl Default for AuditRetention {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+            max_file_age: chrono::Duration::hours(DEFAULT_MAX_FILE_AGE_HOURS),
+            max_segments: DEFAULT_MAX_SEGMENTS,
+        }
+    }
+}
+
+impl AuditRetention {
+    /// Defaults overridden by `AUDIT_MAX_FILE_BYTES`,
+    /// `AUDIT_MAX_FILE_AGE_HOURS` and `AUDIT_MAX_SEGMENTS`.
+    pub fn from_env() -> Self {
+        fn env<T: std::str::FromStr>(key: &str) -> Option<T> {
+            std::env::var(key).ok().and_then(|v| v.parse().ok())
+        }
+        let defaults = Self::default();
+        Self {
+            max_file_bytes: env("AUDIT_MAX_FILE_BYTES").unwrap_or(defaults.max_file_bytes),
+            max_file_age: env("AUDIT_MAX_FILE_AGE_HOURS")
+                .map(chrono::Duration::hours)
+                .unwrap_or(defaults.max_file_age),
+            max_segments: env("AUDIT_MAX_SEGMENTS").unwrap_or(defaults.max_segments),
+        }
+    }
+}
+
+/// Secret keying the chain and head MACs.
+#[derive(Clone)]
+pub struct AuditKey(Vec<u8>);
+
+impl std::fmt::Debug for AuditKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AuditKey(..)")
+    }
+}
+
+impl AuditKey {
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Self(key.into())
+    }
+
+    /// `AUDIT_HMAC_KEY`. Without it the chain is keyed with an empty secret,
+    /// which anyone can recompute, so a warning is logged.
+    pub fn from_env() -> Self {
+        match std::env::var("AUDIT_HMAC_KEY") {
+            Ok(key) if !key.is_empty() => Self(key.into_bytes()),
+            _ => {
+                warn!("AUDIT_HMAC_KEY is not set; audit chains are not tamper-evident");
+                Self(Vec::new())
+            }
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Which log an entry came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditSource {
+    Config,
+    Translations,
+}
+
+impl AuditSource {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Config => "config",
+            Self::Translations => "translations",
+        }
+    }
+}
+
+/// An entry type stored in an [`AuditChain`].
+pub trait AuditRecord: Serialize + DeserializeOwned + Clone {
+    /// When the change happened
+    fn timestamp(&self) -> DateTime<Utc>;
+
+    /// Flattened view for the unified query and export
+    fn to_row(&self, seq: u64, hash: &str) -> AuditRow;
+}
+
+/// One entry from either log, flattened for query and export.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditRow {
+    pub source: AuditSource,
+    pub seq: u64,
+    pub hash: String,
+    pub id: String,
+    pub timestamp: DateTime<Utc>,
+    pub actor: Option<String>,
+    pub action: String,
+    pub resource: String,
+    pub details: Option<String>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub ip: Option<String>,
+    pub request_id: Option<String>,
+}
+
+impl AuditRow {
+    /// One CSV record (without line terminator), columns as [`CSV_HEADER`].
+    /// `before`/`after` are embedded as compact JSON.
+    pub fn to_csv_line(&self) -> String {
+        let json = |value: &Option<Value>| value.as_ref().map(Value::to_string).unwrap_or_default();
+        [
+            self.source.as_str().to_string(),
+            self.seq.to_string(),
+            self.hash.clone(),
+            self.id.clone(),
+            self.timestamp.to_rfc3339(),
+            self.actor.clone().unwrap_or_default(),
+            self.action.clone(),
+            self.resource.clone(),
+            self.details.clone().unwrap_or_default(),
+            json(&self.before),
+            json(&self.after),
+            self.ip.clone().unwrap_or_default(),
+            self.request_id.clone().unwrap_or_default(),
+        ]
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+/// Quote a CSV field when needed. Fields a spreadsheet would evaluate as a
+/// formula are prefixed with `'`.
+fn csv_field(field: &str) -> String {
+    let field = if field.starts_with(['=', '+', '-', '@']) {
+        format!("'{field}")
+    } else {
+        field.to_string()
+    };
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row() -> AuditRow {
+        AuditRow {
+            source: AuditSource::Config,
+            seq: 7,
+            hash: "abc".to_string(),
+            id: "id-1".to_string(),
+            timestamp: DateTime::parse_from_rfc3339("2026-01-02T03:04:05Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            actor: Some("ops".to_string()),
+            action: "update".to_string(),
+            resource: "gated/ui-settings".to_string(),
+            details: Some("version 2, \"quoted\"".to_string()),
+            before: None,
+            after: Some(serde_json::json!({ "a": 1 })),
+            ip: Some("10.0.0.1".to_string()),
+            request_id: None,
+        }
+    }
+
+    #[test]
+    fn csv_line_quotes_and_embeds_json() {
+        assert_eq!(
+            row().to_csv_line(),
+            "config,7,abc,id-1,2026-01-02T03:04:05+00:00,ops,update,gated/ui-settings,\
+             \"version 2, \"\"quoted\"\"\",,\"{\"\"a\"\":1}\",10.0.0.1,"
+        );
+        assert_eq!(CSV_HEADER.split(',').count(), 13);
+    }
+
+    #[test]
+    fn csv_neutralises_formulas() {
+        assert_eq!(csv_field("=HYPERLINK(1)"), "'=HYPERLINK(1)");
+        assert_eq!(csv_field("plain"), "plain");
+    }
+
+    #[tokio::test]
+    async fn request_context_is_visible_inside_scope_only() {
+        let context = RequestContext {
+            ip: Some("10.0.0.1".to_string()),
+            request_id: Some("req-1".to_string()),
+        };
+        let seen = scope(context.clone(), async { current_request() }).await;
+        assert_eq!(seen, context);
+        assert_eq!(current_request(), RequestContext::default());
+    }
+}
@@ -4,10 +4,13 @@
 //! `NewBlock` and `Tx` events, and dispatches them through broadcast channels
 //! to consumers (refresh tasks, lease/earn monitors).
 //!
-//! On disconnect: reconnects with exponential backoff (1s → 30s max).
+//! On disconnect: reconnects with exponential backoff (1s → 30s max), failing
+//! over to another configured RPC node once the current one opens its circuit
+//! or falls behind.
 //! No timer fallback — data goes stale visibly via `Cached<T>.age_secs()`.
 
 use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::Duration;
 
 use futures::{SinkExt, StreamExt};
@@ -15,6 +18,8 @@ use tokio::sync::broadcast;
 use tokio_tungstenite::tungstenite::Message as WsMessage;
 use tracing::{debug, error, info, warn};
 
+use crate::external::endpoints::{EndpointPool, MAX_HEIGHT_LAG};
+
 /// Max time to wait for the initial WebSocket handshake.
 /// Without this, a stuck TCP/TLS handshake hangs the task forever with no
 /// progress log (observed 2026-04-21: connect_async sat for 19h mid-incident).
@@ -45,11 +50,31 @@ fn block_silence_exceeded(elapsed: Duration) -> bool {
 // ============================================================================
 
 /// Contract execution event extracted from CometBFT Tx events.
+///
+/// Dispatched for the generic `wasm` event and for contract-emitted custom
+/// events (`wasm-ls-open`, `wasm-ls-repay`, ...), which carry their payload in
+/// `attributes`.
 #[derive(Debug, Clone)]
 pub struct ContractExecEvent {
     pub contract_address: String,
     pub action: Option<String>,
     pub tx_hash: String,
+    /// Height of the block that included the tx, if the node reported it
+    pub height: Option<u64>,
+    /// CometBFT event type (`wasm` or `wasm-<custom>`)
+    pub event_type: String,
+    /// All event attributes in emission order
+    pub attributes: Vec<(String, String)>,
+}
+
+impl ContractExecEvent {
+    /// First value of attribute `key`, if present.
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
 }
 
 /// Bank-transfer event extracted from CometBFT Tx events.
@@ -103,7 +128,8 @@ impl EventChannels {
 // ============================================================================
 
 struct ChainEventClient {
-    ws_url: String,
+    /// RPC nodes to subscribe through, shared with the chain client's scoring
+    endpoints: Arc<EndpointPool>,
     channels: EventChannels,
 }
 
@@ -120,11 +146,13 @@ pub fn ws_url_from_rpc(rpc_url: &str) -> String {
 
 /// Start the CometBFT WebSocket event client.
 ///
-/// Spawns a tokio task that connects, subscribes to events, and dispatches
-/// them through the provided channels. Reconnects automatically on failure.
-pub fn start(rpc_url: &str, channels: EventChannels) {
+/// Spawns a tokio task that connects to the pinned node of `endpoints`,
+/// subscribes to events, and dispatches them through the provided channels.
+/// Reconnects automatically on failure, moving to another node once the
+/// current one opens its circuit or falls behind the highest observed height.
+pub fn start(endpoints: Arc<EndpointPool>, channels: EventChannels) {
     let client = ChainEventClient {
-        ws_url: ws_url_from_rpc(rpc_url),
+        endpoints,
         channels,
     };
     tokio::spawn(async move {
@@ -138,14 +166,26 @@ impl ChainEventClient {
         let mut backoff_secs = 1u64;
 
         loop {
-            info!("Connecting to CometBFT WebSocket at {}", self.ws_url);
+            let Some(index) = self.endpoints.ordered().first().copied() else {
+                error!("No CometBFT RPC endpoints configured, event client stopped");
+                return;
+            };
+            let ws_url = ws_url_from_rpc(self.endpoints.url(index));
+            info!("Connecting to CometBFT WebSocket at {}", ws_url);
 
-            match self.connect_and_listen().await {
+            match self.connect_and_listen(index, &ws_url).await {
                 Ok(()) => {
                     info!("CometBFT WebSocket disconnected cleanly, reconnecting");
                     backoff_secs = 1;
                 }
                 Err(e) => {
+                    self.endpoints.record_failure(index, e.to_string());
+                    // Another node took over the pin: switch without waiting
+                    if self.endpoints.ordered().first() != Some(&index) {
+                        warn!("CometBFT WebSocket error: {}. Failing over", e);
+                        backoff_secs = 1;
+                        continue;
+                    }
                     error!(
                         "CometBFT WebSocket error: {}. Reconnecting in {}s",
                         e, backoff_secs
@@ -166,21 +206,26 @@ impl ChainEventClient {
     }
 
     /// Connect, subscribe, and process messages until disconnect or error.
-    async fn connect_and_listen(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let (ws_stream, _) = tokio::time::timeout(
-            CONNECT_TIMEOUT,
-            tokio_tungstenite::connect_async(&self.ws_url),
-        )
-        .await
-        .map_err(|_err| {
-            format!(
-                "WebSocket connect timed out after {}s",
-                CONNECT_TIMEOUT.as_secs()
-            )
-        })??;
+    async fn connect_and_listen(
+        &self,
+        index: usize,
+        ws_url: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let started = std::time::Instant::now();
+        let (ws_stream, _) =
+            tokio::time::timeout(CONNECT_TIMEOUT, tokio_tungstenite::connect_async(ws_url))
+                .await
+                .map_err(|_err| {
+                    format!(
+                        "WebSocket connect timed out after {}s",
+                        CONNECT_TIMEOUT.as_secs()
+                    )
+                })??;
         let (mut write, mut read) = ws_stream.split();
 
         info!("Connected to CometBFT WebSocket");
+        self.endpoints
+            .record_success(index, started.elapsed(), None);
 
         // Subscribe to NewBlock events
         let sub_new_block = serde_json::json!({
@@ -253,7 +298,11 @@ impl ChainEventClient {
                     // only Closed is a real failure, but that can't happen
                     // while `self.channels` keeps the sender alive.
                     match block_event {
-                        Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {
+                        Ok(height) => {
+                            last_block_at = tokio::time::Instant::now();
+                            self.endpoints.observe_height(index, height);
+                        }
+                        Err(broadcast::error::RecvError::Lagged(_)) => {
                             last_block_at = tokio::time::Instant::now();
                         }
                         Err(broadcast::error::RecvError::Closed) => {
@@ -270,6 +319,18 @@ impl ChainEventClient {
                             BLOCK_SILENCE_TIMEOUT.as_secs(),
                         ).into());
                     }
+                    // A node behind the rest of the pool would dispatch stale
+                    // events; hand over once another node has taken the pin
+                    if self.endpoints.is_lagging(index)
+                        && self.endpoints.ordered().first() != Some(&index)
+                    {
+                        info!(
+                            "CometBFT node {} is more than {} blocks behind, switching nodes",
+                            self.endpoints.url(index),
+                            MAX_HEIGHT_LAG
+                        );
+                        return Ok(());
+                    }
                 }
             }
         }
@@ -332,6 +393,11 @@ impl ChainEventClient {
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .to_string();
+        let height = msg["result"]["events"]["tx.height"]
+            .as_array()
+            .and_then(|arr| arr.first())
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.parse::<u64>().ok());
 
         // Extract events from the TxResult
         let events = match msg["result"]["data"]["value"]["TxResult"]["result"]["events"].as_array()
@@ -347,7 +413,7 @@ impl ChainEventClient {
         for event in events {
             let event_type = event["type"].as_str();
 
-            if event_type == Some("wasm") {
+            if let Some(wasm_type) = event_type.filter(|t| *t == "wasm" || t.starts_with("wasm-")) {
                 let attrs = match event["attributes"].as_array() {
                     Some(a) => a,
                     None => continue,
@@ -355,19 +421,23 @@ impl ChainEventClient {
 
                 let mut contract_address = String::new();
                 let mut action = None;
+                let mut attributes = Vec::with_capacity(attrs.len());
 
                 for attr in attrs {
-                    match attr["key"].as_str() {
-                        Some("contract_address") | Some("_contract_address") => {
-                            if let Some(v) = attr["value"].as_str() {
-                                contract_address = v.to_string();
-                            }
+                    let (Some(key), Some(value)) = (attr["key"].as_str(), attr["value"].as_str())
+                    else {
+                        continue;
+                    };
+                    match key {
+                        "contract_address" | "_contract_address" => {
+                            contract_address = value.to_string();
                         }
-                        Some("action") => {
-                            action = attr["value"].as_str().map(|s| s.to_string());
+                        "action" => {
+                            action = Some(value.to_string());
                         }
                         _ => {}
                     }
+                    attributes.push((key.to_string(), value.to_string()));
                 }
 
                 if !contract_address.is_empty() {
@@ -375,6 +445,9 @@ impl ChainEventClient {
                         contract_address,
                         action,
                         tx_hash: tx_hash.clone(),
+                        height,
+                        event_type: wasm_type.to_string(),
+                        attributes,
                     });
                 }
             } else if event_type == Some("transfer") {
@@ -412,6 +485,14 @@ impl ChainEventClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::external::endpoints::EndpointKind;
+
+    fn test_endpoints() -> Arc<EndpointPool> {
+        Arc::new(EndpointPool::new(
+            EndpointKind::Rpc,
+            ["https://test".to_string()],
+        ))
+    }
 
     #[test]
     fn block_silence_watchdog_fires_just_past_threshold() {
@@ -484,6 +565,9 @@ mod tests {
                 contract_address: "nolus1test".to_string(),
                 action: Some("feed_prices".to_string()),
                 tx_hash: "abc123".to_string(),
+                height: Some(42),
+                event_type: "wasm".to_string(),
+                attributes: Vec::new(),
             })
             .unwrap();
         let event = rx.recv().await.unwrap();
@@ -497,7 +581,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.new_block.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -526,7 +610,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.contract_exec.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -535,7 +619,7 @@ mod tests {
             "id": "tx_events",
             "result": {
                 "query": "tm.event='Tx'",
-                "events": { "tx.hash": ["DEADBEEF"] },
+                "events": { "tx.hash": ["DEADBEEF"], "tx.height": ["1234"] },
                 "data": {
                     "type": "tendermint/event/Tx",
                     "value": {
@@ -560,6 +644,39 @@ mod tests {
         assert_eq!(event.contract_address, "nolus1oracle");
         assert_eq!(event.action.as_deref(), Some("feed_prices"));
         assert_eq!(event.tx_hash, "DEADBEEF");
+        assert_eq!(event.height, Some(1234));
+    }
+
+    #[test]
+    fn test_parse_tx_event_with_custom_wasm_event() {
+        let channels = EventChannels::new();
+        let mut rx = channels.contract_exec.subscribe();
+        let client = ChainEventClient {
+            endpoints: test_endpoints(),
+            channels,
+        };
+
+        let msg = r#"{
+            "result": {
+                "query": "tm.event='Tx'",
+                "events": { "tx.hash": ["FEED"] },
+                "data": { "value": { "TxResult": { "result": { "events": [{
+                    "type": "wasm-ls-repay",
+                    "attributes": [
+                        { "key": "_contract_address", "value": "nolus1lease" },
+                        { "key": "payment-symbol", "value": "USDC_NOBLE" },
+                        { "key": "payment-amount", "value": "1000000" }
+                    ]
+                }] } } } }
+            }
+        }"#;
+
+        client.handle_message(msg);
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.contract_address, "nolus1lease");
+        assert_eq!(event.event_type, "wasm-ls-repay");
+        assert_eq!(event.attribute("payment-amount"), Some("1000000"));
+        assert_eq!(event.attribute("missing"), None);
     }
 
     #[test]
@@ -567,7 +684,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.new_block.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -584,7 +701,7 @@ mod tests {
         let mut new_block_rx = channels.new_block.subscribe();
         let mut contract_rx = channels.contract_exec.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -604,7 +721,7 @@ mod tests {
         let mut new_block_rx = channels.new_block.subscribe();
         let mut contract_rx = channels.contract_exec.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -628,7 +745,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.new_block.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -652,7 +769,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.new_block.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -690,7 +807,7 @@ mod tests {
         let mut rx1 = channels.contract_exec.subscribe();
         let mut rx2 = channels.contract_exec.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -724,7 +841,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.contract_exec.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -752,7 +869,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.contract_exec.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -793,7 +910,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.contract_exec.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -820,7 +937,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.contract_exec.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -858,7 +975,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.bank_transfer.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -896,7 +1013,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.bank_transfer.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -932,7 +1049,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.bank_transfer.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
@@ -968,7 +1085,7 @@ mod tests {
         let channels = EventChannels::new();
         let mut rx = channels.bank_transfer.subscribe();
         let client = ChainEventClient {
-            ws_url: "wss://test/websocket".to_string(),
+            endpoints: test_endpoints(),
             channels,
         };
 
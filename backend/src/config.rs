@@ -5,6 +5,9 @@ use thiserror::Error;
 /// Default TCP port the server binds to when `PORT` is unset.
 const DEFAULT_PORT: u16 = 3000;
 
+/// Default restake bot cadence (daily) when `RESTAKE_INTERVAL_SECS` is unset.
+const DEFAULT_RESTAKE_INTERVAL_SECS: u64 = 86_400;
+
 // ============================================================================
 // Configuration Errors
 // ============================================================================
@@ -23,6 +26,9 @@ pub enum ConfigError {
 
     #[error("Admin API is enabled but no API key is configured")]
     AdminEnabledNoKey,
+
+    #[error("Invalid restake configuration for {field}: {reason}")]
+    InvalidRestake { field: String, reason: String },
 }
 
 /// Result of configuration validation
@@ -53,6 +59,8 @@ pub struct AppConfig {
     pub external: ExternalApiConfig,
     pub admin: AdminConfig,
     pub protocols: ProtocolsConfig,
+    #[serde(default)]
+    pub restake: RestakeConfig,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -84,6 +92,11 @@ pub struct ExternalApiConfig {
     // Chain RPCs & REST (only Nolus required - all contracts are on Nolus)
     pub nolus_rpc_url: String,
     pub nolus_rest_url: String,
+    // Extra Nolus nodes the chain client fails over to when the primary degrades
+    #[serde(default)]
+    pub nolus_rpc_fallback_urls: Vec<String>,
+    #[serde(default)]
+    pub nolus_rest_fallback_urls: Vec<String>,
 
     // Solana RPC (optional): the operator's Solana JSON-RPC endpoint. Unset ->
     // Solana endpoints (balances, transfer-params) fail visibly with 503; no
@@ -103,12 +116,53 @@ pub struct ExternalApiConfig {
     pub intercom_secret_key: String,
 }
 
+impl ExternalApiConfig {
+    /// Nolus LCD nodes, primary first
+    pub fn nolus_rest_urls(&self) -> Vec<String> {
+        std::iter::once(self.nolus_rest_url.clone())
+            .chain(self.nolus_rest_fallback_urls.iter().cloned())
+            .collect()
+    }
+
+    /// Nolus RPC nodes, primary first
+    pub fn nolus_rpc_urls(&self) -> Vec<String> {
+        std::iter::once(self.nolus_rpc_url.clone())
+            .chain(self.nolus_rpc_fallback_urls.iter().cloned())
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct AdminConfig {
     #[serde(default)]
     pub enabled: bool,
     #[serde(default)]
     pub api_key: String,
+    /// Reverse proxies whose `X-Forwarded-For` is believed when recording
+    /// the caller's IP in the audit log
+    #[serde(default)]
+    pub trusted_proxies: Vec<std::net::IpAddr>,
+}
+
+/// Auto-compound (restake) bot the staking authz grants are issued to
+#[derive(Debug, Clone, Deserialize)]
+pub struct RestakeConfig {
+    /// Grantee address of the operator's restake bot. Unset disables the
+    /// auto-compound grant builder.
+    #[serde(default)]
+    pub bot_address: Option<String>,
+    /// How often the bot claims and re-delegates, used for the compounding
+    /// APR projection
+    pub interval_secs: u64,
+}
+
+impl Default for RestakeConfig {
+    fn default() -> Self {
+        Self {
+            bot_address: None,
+            interval_secs: DEFAULT_RESTAKE_INTERVAL_SECS,
+        }
+    }
 }
 
 /// Protocol configuration for the Nolus Admin contract
@@ -197,6 +251,25 @@ impl AppConfig {
             errors.push(ConfigError::AdminEnabledNoKey);
         }
 
+        // ====================================================================
+        // Restake Bot
+        // ====================================================================
+
+        if let Some(bot) = &self.restake.bot_address {
+            if !crate::validation::is_valid_nolus_address(bot) {
+                errors.push(ConfigError::InvalidRestake {
+                    field: "RESTAKE_BOT_ADDRESS".to_string(),
+                    reason: format!("{} is not a nolus account address", bot),
+                });
+            }
+        }
+        if self.restake.interval_secs == 0 {
+            errors.push(ConfigError::InvalidRestake {
+                field: "RESTAKE_INTERVAL_SECS".to_string(),
+                reason: "must be positive".to_string(),
+            });
+        }
+
         // ====================================================================
         // Warnings (non-critical)
         // ====================================================================
@@ -230,6 +303,13 @@ impl AppConfig {
                 .push("Skip API key not configured - swap routing may be rate limited".to_string());
         }
 
+        if self.restake.bot_address.is_none() {
+            warnings.push(
+                "Restake bot not configured - auto-compound grant endpoints will return 503"
+                    .to_string(),
+            );
+        }
+
         if self.external.solana_rpc_url.is_none() {
             warnings.push(
                 "Solana RPC not configured - Solana balance/transfer-params endpoints will return 503"
@@ -257,6 +337,18 @@ impl AppConfig {
         }
     }
 
+    /// Comma-separated list env var; unset or empty yields an empty list
+    fn get_list_env(env_var: &str) -> Vec<String> {
+        env::var(env_var)
+            .map(|v| {
+                v.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Load configuration from environment variables
     /// Only Nolus endpoints and ETL URL are required to start
     pub fn load() -> anyhow::Result<Self> {
@@ -276,6 +368,8 @@ impl AppConfig {
             // Chain RPCs & REST (only Nolus required)
             nolus_rpc_url,
             nolus_rest_url,
+            nolus_rpc_fallback_urls: Self::get_list_env("NOLUS_RPC_FALLBACK_URLS"),
+            nolus_rest_fallback_urls: Self::get_list_env("NOLUS_REST_FALLBACK_URLS"),
 
             // Solana RPC (optional — no default; absent means Solana disabled)
             solana_rpc_url: env::var("SOLANA_RPC_URL").ok().filter(|v| !v.is_empty()),
@@ -320,6 +414,9 @@ impl AppConfig {
                     .parse()
                     .unwrap_or(false),
                 api_key: env::var("ADMIN_API_KEY").unwrap_or_else(|_err| String::new()),
+                trusted_proxies: env::var("ADMIN_TRUSTED_PROXIES")
+                    .map(|v| v.split(',').filter_map(|s| s.trim().parse().ok()).collect())
+                    .unwrap_or_default(),
             },
             protocols: ProtocolsConfig {
                 admin_contract: Self::get_required_env("ADMIN_CONTRACT", "Admin contract address")?,
@@ -328,6 +425,17 @@ impl AppConfig {
                     "Dispatcher contract address",
                 )?,
             },
+            restake: RestakeConfig {
+                bot_address: env::var("RESTAKE_BOT_ADDRESS")
+                    .ok()
+                    .filter(|v| !v.is_empty()),
+                interval_secs: env::var("RESTAKE_INTERVAL_SECS")
+                    .unwrap_or_else(|_err| DEFAULT_RESTAKE_INTERVAL_SECS.to_string())
+                    .parse()
+                    .map_err(|e| {
+                        anyhow::anyhow!("RESTAKE_INTERVAL_SECS must be a whole number: {e}")
+                    })?,
+            },
         })
     }
 }
@@ -346,6 +454,8 @@ mod tests {
                 skip_api_key: None,
                 nolus_rpc_url: "https://rpc.nolus.network".to_string(),
                 nolus_rest_url: "https://lcd.nolus.network".to_string(),
+                nolus_rpc_fallback_urls: Vec::new(),
+                nolus_rest_fallback_urls: Vec::new(),
                 solana_rpc_url: Some("https://solana-rpc.example.com".to_string()),
                 referral_api_url: String::new(),
                 referral_api_token: String::new(),
@@ -356,6 +466,7 @@ mod tests {
             },
             admin: AdminConfig::default(),
             protocols: ProtocolsConfig::default(),
+            restake: RestakeConfig::default(),
         }
     }
 
@@ -475,6 +586,20 @@ mod tests {
             .any(|e| matches!(e, ConfigError::AdminEnabledNoKey)));
     }
 
+    #[test]
+    fn test_validate_invalid_restake_settings() {
+        let mut config = create_test_config();
+        config.restake.bot_address = Some("cosmos1notnolus".to_string());
+        config.restake.interval_secs = 0;
+        let result = config.validate();
+        assert!(!result.is_ok());
+        for expected in ["RESTAKE_BOT_ADDRESS", "RESTAKE_INTERVAL_SECS"] {
+            assert!(result.errors.iter().any(|e| {
+                matches!(e, ConfigError::InvalidRestake { field, .. } if field == expected)
+            }));
+        }
+    }
+
     #[test]
     fn test_validate_warnings_for_optional_apis() {
         let mut config = create_test_config();
@@ -319,12 +319,74 @@ pub struct LeaseRulesConfig {
     /// giving users a more accurate picture of what they'll owe at repayment time.
     #[serde(default = "default_due_projection_secs")]
     pub due_projection_secs: u64,
+    /// Thresholds for the `lease_alerts` WebSocket topic
+    #[serde(default)]
+    pub alerts: LeaseAlertsConfig,
 }
 
 fn default_due_projection_secs() -> u64 {
     400
 }
 
+/// Lease health alert thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaseAlertsConfig {
+    /// LTV warning thresholds in percent, strictly ascending and each within
+    /// (0, 100). Empty uses the leaser's `first/second/third_liq_warn` levels.
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_ltv_warning_thresholds"
+    )]
+    pub ltv_warning_thresholds: Vec<f64>,
+    /// Alert when the price is within this many percent of the liquidation price
+    #[serde(default = "default_liquidation_distance_percent")]
+    pub liquidation_distance_percent: f64,
+    /// Alert when overdue interest collection is due within this many seconds
+    #[serde(default = "default_overdue_collect_warning_secs")]
+    pub overdue_collect_warning_secs: u64,
+}
+
+impl Default for LeaseAlertsConfig {
+    fn default() -> Self {
+        Self {
+            ltv_warning_thresholds: Vec::new(),
+            liquidation_distance_percent: default_liquidation_distance_percent(),
+            overdue_collect_warning_secs: default_overdue_collect_warning_secs(),
+        }
+    }
+}
+
+/// Deserialize `ltv_warning_thresholds`, rejecting a list that would fire
+/// alerts out of order or more than once per level, so a bad file fails on
+/// load and a bad admin write fails before it is saved.
+fn deserialize_ltv_warning_thresholds<'de, D>(deserializer: D) -> Result<Vec<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let thresholds = Vec::<f64>::deserialize(deserializer)?;
+    if let Some(out_of_range) = thresholds.iter().find(|t| **t <= 0.0 || **t >= 100.0) {
+        return Err(serde::de::Error::custom(format!(
+            "LTV warning threshold {} is outside (0, 100) percent",
+            out_of_range
+        )));
+    }
+    if thresholds.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(serde::de::Error::custom(
+            "LTV warning thresholds must be strictly ascending",
+        ));
+    }
+    Ok(thresholds)
+}
+
+fn default_liquidation_distance_percent() -> f64 {
+    5.0
+}
+
+fn default_overdue_collect_warning_secs() -> u64 {
+    3600
+}
+
 /// Downpayment range for an asset
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct DownpaymentRange {
@@ -921,6 +983,7 @@ mod tests {
                 ignore_short: vec![],
             },
             due_projection_secs: 400,
+            alerts: LeaseAlertsConfig::default(),
         };
 
         let json = serde_json::to_string_pretty(&config).unwrap();
@@ -940,6 +1003,37 @@ mod tests {
         assert_eq!(config.due_projection_secs, 400);
     }
 
+    #[test]
+    fn test_lease_rules_config_default_alerts() {
+        let json = r#"{"alerts": {"ltv_warning_thresholds": [70.0, 80.0]}}"#;
+        let config: LeaseRulesConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.alerts.ltv_warning_thresholds, vec![70.0, 80.0]);
+        assert_eq!(config.alerts.liquidation_distance_percent, 5.0);
+        assert_eq!(config.alerts.overdue_collect_warning_secs, 3600);
+
+        let config: LeaseRulesConfig = serde_json::from_str("{}").unwrap();
+        assert!(config.alerts.ltv_warning_thresholds.is_empty());
+    }
+
+    #[test]
+    fn test_lease_alerts_reject_bad_ltv_thresholds() {
+        for thresholds in [
+            "[80.0, 70.0]",
+            "[70.0, 70.0]",
+            "[0.0, 70.0]",
+            "[70.0, 100.0]",
+        ] {
+            let json = format!(
+                r#"{{"alerts": {{"ltv_warning_thresholds": {}}}}}"#,
+                thresholds
+            );
+            assert!(
+                serde_json::from_str::<LeaseRulesConfig>(&json).is_err(),
+                "{thresholds} must be rejected"
+            );
+        }
+    }
+
     // Contract these tests pin for the svm ChainType + SOLANA network model.
     // `NetworkSettings` becomes
     // a tagged enum with a custom `Deserialize` that defaults a *missing*
@@ -8,6 +8,7 @@
 //! - In-memory caching with configurable TTL
 //! - Validation before saving
 //! - Admin API for CRUD operations
+//! - Immutable version history with diffs, rollback and staged drafts
 //!
 //! ## Gated Propagation Config Types
 //! - Currency Display (enrichment: icon, displayName, color)
@@ -21,5 +22,6 @@
 
 pub mod gated_types;
 pub mod storage;
+pub mod versions;
 
 pub use storage::ConfigStore;
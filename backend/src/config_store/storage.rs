@@ -2,39 +2,76 @@
 //!
 //! Provides file-based JSON storage with caching and atomic writes.
 
+use crate::audit::{AuditChain, AuditKey, AuditRecord, AuditRetention, AuditRow, AuditSource};
 use crate::error::AppError;
 use chrono::{DateTime, Utc};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
 use tokio::fs;
-use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
 use super::gated_types::*;
-
-/// Maximum audit log entries retained in memory; older entries are dropped.
-const MAX_AUDIT_LOG_ENTRIES: usize = 1000;
+use super::versions::{ConfigVersionSummary, GatedResource, VersionStore};
 
 /// Maximum number of audit log entries returned in a single query.
-const MAX_PAGE_LIMIT: usize = 100;
+pub const MAX_PAGE_LIMIT: usize = 100;
 
 /// Number of audit log entries returned when the query omits a limit.
-const DEFAULT_PAGE_LIMIT: usize = 50;
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
 
 /// Audit log entry for configuration changes
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditLogEntry {
     pub id: String,
     pub timestamp: DateTime<Utc>,
+    /// Name of the admin key that made the change (`None` for system writes)
+    #[serde(default)]
+    pub actor: Option<String>,
     pub action: String,
     pub resource: String,
     pub details: Option<String>,
+    /// Resource state before the change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<serde_json::Value>,
+    /// Resource state after the change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<serde_json::Value>,
+    /// Requester IP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    /// `x-request-id` of the admin request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl AuditRecord for AuditLogEntry {
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    fn to_row(&self, seq: u64, hash: &str) -> AuditRow {
+        AuditRow {
+            source: AuditSource::Config,
+            seq,
+            hash: hash.to_string(),
+            id: self.id.clone(),
+            timestamp: self.timestamp,
+            actor: self.actor.clone(),
+            action: self.action.clone(),
+            resource: self.resource.clone(),
+            details: self.details.clone(),
+            before: self.before.clone(),
+            after: self.after.clone(),
+            ip: self.ip.clone(),
+            request_id: self.request_id.clone(),
+        }
+    }
 }
 
 /// Query parameters for audit log
 #[derive(Debug, Clone, Default)]
 pub struct AuditLogQuery {
+    pub actor: Option<String>,
     pub action: Option<String>,
     pub resource: Option<String>,
     pub from: Option<DateTime<Utc>>,
@@ -57,8 +94,12 @@ pub struct AuditLogResponse {
 pub struct ConfigStore {
     /// Base directory for config files
     config_dir: PathBuf,
-    /// Audit log entries (in-memory, persisted to file)
-    audit_log: Arc<RwLock<Vec<AuditLogEntry>>>,
+    /// Hash-chained audit log of config changes
+    audit: AuditChain<AuditLogEntry>,
+    /// Version history and drafts for the gated config files
+    versions: VersionStore,
+    /// Serializes gated saves so each version diffs against its predecessor
+    gated_write: tokio::sync::Mutex<()>,
 }
 
 impl ConfigStore {
@@ -66,7 +107,13 @@ impl ConfigStore {
     pub fn new<P: AsRef<Path>>(config_dir: P) -> Self {
         Self {
             config_dir: config_dir.as_ref().to_path_buf(),
-            audit_log: Arc::new(RwLock::new(Vec::new())),
+            audit: AuditChain::new(
+                config_dir.as_ref().join("audit").join("config"),
+                AuditRetention::from_env(),
+                AuditKey::from_env(),
+            ),
+            versions: VersionStore::new(config_dir.as_ref().join("gated")),
+            gated_write: tokio::sync::Mutex::new(()),
         }
     }
 
@@ -91,11 +138,13 @@ impl ConfigStore {
             }
         }
 
-        // Load audit log
-        if let Err(e) = self.load_audit_log().await {
-            warn!("Could not load audit log (starting fresh): {}", e);
+        self.audit.init().await?;
+        if let Err(e) = self.migrate_legacy_audit_log().await {
+            warn!("Could not migrate legacy audit log: {}", e);
         }
 
+        self.versions.init().await?;
+
         Ok(())
     }
 
@@ -104,44 +153,98 @@ impl ConfigStore {
         info!("Configuration cache invalidated");
     }
 
+    /// Version history and drafts for the gated config files
+    pub const fn versions(&self) -> &VersionStore {
+        &self.versions
+    }
+
+    /// Hash-chained audit log of config changes
+    pub const fn audit(&self) -> &AuditChain<AuditLogEntry> {
+        &self.audit
+    }
+
     // =========================================================================
     // Audit Log
     // =========================================================================
 
-    /// Record an audit log entry
-    pub async fn record_audit(&self, action: &str, resource: &str, details: Option<String>) {
+    /// Record an audit log entry on behalf of `actor`
+    pub async fn record_audit(
+        &self,
+        actor: Option<&str>,
+        action: &str,
+        resource: &str,
+        details: Option<String>,
+    ) {
+        self.record_change(actor, action, resource, details, None, None)
+            .await;
+    }
+
+    /// Record an audit log entry with the resource's state before and after
+    /// the change. The requester IP and request ID come from the audit scope
+    /// of the admin request being handled.
+    ///
+    /// Entries are recorded after the change is applied, so a failed append
+    /// is logged rather than returned: failing the request would report an
+    /// applied change as not made.
+    pub async fn record_change(
+        &self,
+        actor: Option<&str>,
+        action: &str,
+        resource: &str,
+        details: Option<String>,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) {
+        let request = crate::audit::current_request();
         let entry = AuditLogEntry {
             id: uuid::Uuid::new_v4().to_string(),
             timestamp: Utc::now(),
+            actor: actor.map(String::from),
             action: action.to_string(),
             resource: resource.to_string(),
             details,
+            before,
+            after,
+            ip: request.ip,
+            request_id: request.request_id,
         };
 
-        {
-            let mut log = self.audit_log.write().await;
-            log.push(entry.clone());
-
-            // Keep only the most recent entries in memory
-            let len = log.len();
-            if len > MAX_AUDIT_LOG_ENTRIES {
-                log.drain(0..len - MAX_AUDIT_LOG_ENTRIES);
-            }
+        if let Err(e) = self.audit.append(&entry).await {
+            error!(
+                "Failed to record audit entry {} - {} by {}: {}",
+                action,
+                resource,
+                actor.unwrap_or("system"),
+                e
+            );
+            return;
         }
 
-        // Persist to file asynchronously (best effort)
-        let _ = self.persist_audit_log().await;
-
-        info!("Audit: {} - {}", action, resource);
+        info!(
+            "Audit: {} - {} by {}",
+            action,
+            resource,
+            actor.unwrap_or("system")
+        );
     }
 
     /// Query the audit log
-    pub async fn query_audit_log(&self, query: AuditLogQuery) -> AuditLogResponse {
-        let log = self.audit_log.read().await;
+    pub async fn query_audit_log(
+        &self,
+        query: AuditLogQuery,
+    ) -> Result<AuditLogResponse, AppError> {
+        let log = self.audit.read_since(query.from).await?;
 
         let filtered: Vec<_> = log
-            .iter()
+            .into_iter()
+            .map(|chained| chained.entry)
             .filter(|entry| {
+                // Filter by actor
+                if let Some(ref actor) = query.actor {
+                    if entry.actor.as_ref() != Some(actor) {
+                        return false;
+                    }
+                }
                 // Filter by action
                 if let Some(ref action) = query.action {
                     if !entry.action.contains(action) {
@@ -167,7 +270,6 @@ impl ConfigStore {
                 }
                 true
             })
-            .cloned()
             .collect();
 
         let total = filtered.len();
@@ -185,32 +287,16 @@ impl ConfigStore {
             .take(limit)
             .collect();
 
-        AuditLogResponse {
+        Ok(AuditLogResponse {
             entries,
             total,
             offset,
             limit,
-        }
-    }
-
-    /// Persist audit log to file
-    async fn persist_audit_log(&self) -> Result<(), AppError> {
-        let log = self.audit_log.read().await;
-        let path = self.config_dir.join("audit-log.json");
-
-        let content = serde_json::to_string_pretty(&*log)
-            .map_err(|e| AppError::Internal(format!("Failed to serialize audit log: {}", e)))?;
-
-        fs::write(&path, content).await.map_err(|e| {
-            error!("Failed to persist audit log: {}", e);
-            AppError::Internal(format!("Failed to persist audit log: {}", e))
-        })?;
-
-        Ok(())
+        })
     }
 
-    /// Load audit log from file (called during init)
-    async fn load_audit_log(&self) -> Result<(), AppError> {
+    /// Move a pre-chain `audit-log.json` into the audit chain (called during init)
+    async fn migrate_legacy_audit_log(&self) -> Result<(), AppError> {
         let path = self.config_dir.join("audit-log.json");
 
         if !path.exists() {
@@ -221,15 +307,10 @@ impl ConfigStore {
             .await
             .map_err(|e| AppError::Internal(format!("Failed to read audit log: {}", e)))?;
 
-        let entries: Vec<AuditLogEntry> = serde_json::from_str(&content).map_err(|e| {
-            warn!("Failed to parse audit log (starting fresh): {}", e);
-            AppError::Internal(format!("Failed to parse audit log: {}", e))
-        })?;
-
-        let mut log = self.audit_log.write().await;
-        *log = entries;
+        let entries: Vec<AuditLogEntry> = serde_json::from_str(&content)
+            .map_err(|e| AppError::Internal(format!("Failed to parse audit log: {}", e)))?;
 
-        Ok(())
+        self.audit.import_legacy(&path, entries).await
     }
 
     // =========================================================================
@@ -269,54 +350,151 @@ impl ConfigStore {
     pub async fn save_currency_display(
         &self,
         config: &CurrencyDisplayConfig,
-    ) -> Result<(), AppError> {
-        self.save_json_file("gated/currency-display.json", config)
-            .await?;
-        self.record_audit("update", "gated/currency-display", None)
-            .await;
-        self.invalidate_cache().await;
-        Ok(())
+        actor: Option<&str>,
+    ) -> Result<ConfigVersionSummary, AppError> {
+        self.save_gated(GatedResource::CurrencyDisplay, config, actor, None)
+            .await
     }
 
     /// Save gated network configuration
     pub async fn save_gated_network_config(
         &self,
         config: &GatedNetworkConfig,
-    ) -> Result<(), AppError> {
-        self.save_json_file("gated/network-config.json", config)
-            .await?;
-        self.record_audit("update", "gated/network-config", None)
-            .await;
-        self.invalidate_cache().await;
-        Ok(())
+        actor: Option<&str>,
+    ) -> Result<ConfigVersionSummary, AppError> {
+        self.save_gated(GatedResource::NetworkConfig, config, actor, None)
+            .await
     }
 
     /// Save lease rules configuration
-    pub async fn save_lease_rules(&self, config: &LeaseRulesConfig) -> Result<(), AppError> {
-        self.save_json_file("gated/lease-rules.json", config)
-            .await?;
-        self.record_audit("update", "gated/lease-rules", None).await;
-        self.invalidate_cache().await;
-        Ok(())
+    pub async fn save_lease_rules(
+        &self,
+        config: &LeaseRulesConfig,
+        actor: Option<&str>,
+    ) -> Result<ConfigVersionSummary, AppError> {
+        self.save_gated(GatedResource::LeaseRules, config, actor, None)
+            .await
     }
 
     /// Save swap settings configuration
-    pub async fn save_swap_settings(&self, config: &SwapSettingsConfig) -> Result<(), AppError> {
-        self.save_json_file("gated/swap-settings.json", config)
-            .await?;
-        self.record_audit("update", "gated/swap-settings", None)
-            .await;
-        self.invalidate_cache().await;
-        Ok(())
+    pub async fn save_swap_settings(
+        &self,
+        config: &SwapSettingsConfig,
+        actor: Option<&str>,
+    ) -> Result<ConfigVersionSummary, AppError> {
+        self.save_gated(GatedResource::SwapSettings, config, actor, None)
+            .await
     }
 
     /// Save UI settings configuration
-    pub async fn save_ui_settings(&self, config: &UiSettingsConfig) -> Result<(), AppError> {
-        self.save_json_file("gated/ui-settings.json", config)
+    pub async fn save_ui_settings(
+        &self,
+        config: &UiSettingsConfig,
+        actor: Option<&str>,
+    ) -> Result<ConfigVersionSummary, AppError> {
+        self.save_gated(GatedResource::UiSettings, config, actor, None)
+            .await
+    }
+
+    /// Save a gated config file and record it as a new immutable version
+    ///
+    /// The version's diff is taken against the file it replaces, so the
+    /// first save after upgrading still diffs against the pre-existing file.
+    /// `rollback_of` marks a save that restores an earlier version.
+    pub async fn save_gated<T: Serialize>(
+        &self,
+        resource: GatedResource,
+        config: &T,
+        actor: Option<&str>,
+        rollback_of: Option<u64>,
+    ) -> Result<ConfigVersionSummary, AppError> {
+        let _write = self.gated_write.lock().await;
+        self.write_gated(resource, config, actor, rollback_of).await
+    }
+
+    /// Save a gated config file only if its latest version is still
+    /// `base_version`
+    ///
+    /// The check runs under the same lock as the write, so a save that lands
+    /// between a caller's own check and this call can't be overwritten.
+    pub async fn publish_gated<T: Serialize>(
+        &self,
+        resource: GatedResource,
+        config: &T,
+        actor: Option<&str>,
+        base_version: Option<u64>,
+    ) -> Result<ConfigVersionSummary, AppError> {
+        let _write = self.gated_write.lock().await;
+        let latest = self.versions.latest_id(resource).await;
+        if base_version != latest {
+            return Err(AppError::Validation {
+                message: format!(
+                    "Live {} changed since this draft was staged; re-stage it against the current config",
+                    resource.as_str()
+                ),
+                field: None,
+                details: Some(serde_json::json!({
+                    "base_version": base_version,
+                    "latest_version": latest,
+                })),
+            });
+        }
+        self.write_gated(resource, config, actor, None).await
+    }
+
+    /// Version, write and audit a gated config file. Callers hold
+    /// `gated_write`.
+    ///
+    /// The version snapshot is written before the live file, so a live file
+    /// never exists without a version that can restore it; if the live write
+    /// then fails the version is retracted. Once the live file has changed
+    /// the cache is invalidated before anything else can fail.
+    async fn write_gated<T: Serialize>(
+        &self,
+        resource: GatedResource,
+        config: &T,
+        actor: Option<&str>,
+        rollback_of: Option<u64>,
+    ) -> Result<ConfigVersionSummary, AppError> {
+        let snapshot = serde_json::to_value(config)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize config: {}", e)))?;
+        let previous = self
+            .load_gated_value(resource)
+            .await
+            .unwrap_or(serde_json::Value::Null);
+        let version = self
+            .versions
+            .record(resource, &previous, snapshot.clone(), actor, rollback_of)
             .await?;
-        self.record_audit("update", "gated/ui-settings", None).await;
+        if let Err(e) = self.save_json_file(&resource.file(), config).await {
+            self.versions.retract(version.id).await;
+            return Err(e);
+        }
         self.invalidate_cache().await;
-        Ok(())
+
+        let action = if rollback_of.is_some() {
+            "rollback"
+        } else {
+            "update"
+        };
+        self.record_change(
+            actor,
+            action,
+            &format!("gated/{}", resource.as_str()),
+            Some(format!("version {}", version.id)),
+            (!previous.is_null()).then_some(previous),
+            Some(snapshot),
+        )
+        .await;
+        Ok(version)
+    }
+
+    /// Load a gated config file as untyped JSON
+    pub async fn load_gated_value(
+        &self,
+        resource: GatedResource,
+    ) -> Result<serde_json::Value, AppError> {
+        self.load_json_file(&resource.file()).await
     }
 
     // =========================================================================
@@ -417,14 +595,118 @@ mod tests {
         store.init().await.unwrap();
 
         store
-            .record_audit("create", "test-resource", Some("test details".to_string()))
+            .record_audit(
+                Some("ops"),
+                "create",
+                "test-resource",
+                Some("test details".to_string()),
+            )
             .await;
 
         let query = AuditLogQuery::default();
-        let response = store.query_audit_log(query).await;
+        let response = store.query_audit_log(query).await.unwrap();
 
         assert_eq!(response.total, 1);
         assert_eq!(response.entries[0].action, "create");
         assert_eq!(response.entries[0].resource, "test-resource");
+        assert_eq!(response.entries[0].actor.as_deref(), Some("ops"));
+
+        let by_other = store
+            .query_audit_log(AuditLogQuery {
+                actor: Some("someone-else".to_string()),
+                ..AuditLogQuery::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(by_other.total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_save_records_version_with_author_and_diff() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ConfigStore::new(temp_dir.path());
+        store.init().await.unwrap();
+
+        let mut config = UiSettingsConfig::default();
+        store.save_ui_settings(&config, None).await.unwrap();
+        config.hidden_proposals.push("42".to_string());
+        let version = store.save_ui_settings(&config, Some("ops")).await.unwrap();
+
+        assert_eq!(version.id, 2);
+        assert_eq!(version.author.as_deref(), Some("ops"));
+        let full = store.versions().get(version.id).await.unwrap();
+        assert_eq!(full.diff.len(), 1);
+        assert_eq!(full.diff[0].path, "/hidden_proposals");
+        assert_eq!(full.snapshot["hidden_proposals"][0], "42");
+
+        let audit = store
+            .query_audit_log(AuditLogQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(audit.entries[0].details.as_deref(), Some("version 2"));
+        assert_eq!(
+            audit.entries[0].before.as_ref().unwrap()["hidden_proposals"],
+            serde_json::json!([])
+        );
+        assert_eq!(
+            audit.entries[0].after.as_ref().unwrap()["hidden_proposals"][0],
+            "42"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_live_write_leaves_no_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ConfigStore::new(temp_dir.path());
+        store.init().await.unwrap();
+
+        let config = UiSettingsConfig::default();
+        let first = store.save_ui_settings(&config, None).await.unwrap();
+
+        // A non-empty directory at the live path makes the rename fail.
+        let live = temp_dir.path().join(GatedResource::UiSettings.file());
+        std::fs::remove_file(&live).unwrap();
+        std::fs::create_dir_all(live.join("blocker")).unwrap();
+
+        assert!(store.save_ui_settings(&config, None).await.is_err());
+        assert_eq!(
+            store.versions().latest_id(GatedResource::UiSettings).await,
+            Some(first.id)
+        );
+        assert!(store.versions().get(first.id + 1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_audit_failure_does_not_fail_an_applied_save() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ConfigStore::new(temp_dir.path());
+        store.init().await.unwrap();
+
+        // A directory where the active audit file belongs makes appends fail.
+        std::fs::create_dir_all(temp_dir.path().join("audit/config/audit.jsonl/blocker")).unwrap();
+
+        let mut config = UiSettingsConfig::default();
+        config.hidden_proposals.push("9".to_string());
+        let version = store.save_ui_settings(&config, None).await.unwrap();
+
+        assert_eq!(version.id, 1);
+        let live = store.load_ui_settings().await.unwrap();
+        assert_eq!(live.hidden_proposals, vec!["9".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_publish_refuses_a_stale_base_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = ConfigStore::new(temp_dir.path());
+        store.init().await.unwrap();
+
+        let config = UiSettingsConfig::default();
+        let first = store.save_ui_settings(&config, None).await.unwrap();
+        store.save_ui_settings(&config, None).await.unwrap();
+
+        let stale = store
+            .publish_gated(GatedResource::UiSettings, &config, None, Some(first.id))
+            .await;
+        assert!(matches!(stale, Err(AppError::Validation { .. })));
     }
 }
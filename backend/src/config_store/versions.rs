@@ -0,0 +1,639 @@
+//! Gated config version history and staged drafts
+//!
+//! Every gated save writes an immutable, numbered snapshot of the saved file
+//! to `gated/versions/`, with its author and a structured diff against the
+//! content it replaced. `gated/drafts/` holds at most one staged change per
+//! resource until it is published (or discarded).
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::fs;
+use tokio::io::AsyncWriteExt as _;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use super::gated_types::{
+    CurrencyDisplayConfig, GatedNetworkConfig, LeaseRulesConfig, SwapSettingsConfig,
+    UiSettingsConfig,
+};
+use crate::error::AppError;
+
+/// Maximum number of versions returned in a single query.
+const MAX_PAGE_LIMIT: usize = 100;
+
+/// Number of versions returned when the query omits a limit.
+const DEFAULT_PAGE_LIMIT: usize = 50;
+
+/// One of the five gated config files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GatedResource {
+    CurrencyDisplay,
+    NetworkConfig,
+    LeaseRules,
+    SwapSettings,
+    UiSettings,
+}
+
+impl GatedResource {
+    pub const ALL: [Self; 5] = [
+        Self::CurrencyDisplay,
+        Self::NetworkConfig,
+        Self::LeaseRules,
+        Self::SwapSettings,
+        Self::UiSettings,
+    ];
+
+    /// Resource name as used in routes and the audit log (e.g. "lease-rules")
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::CurrencyDisplay => "currency-display",
+            Self::NetworkConfig => "network-config",
+            Self::LeaseRules => "lease-rules",
+            Self::SwapSettings => "swap-settings",
+            Self::UiSettings => "ui-settings",
+        }
+    }
+
+    /// Config file path relative to the config directory
+    pub fn file(self) -> String {
+        format!("gated/{}.json", self.as_str())
+    }
+
+    /// Parse a route segment into a resource
+    pub fn parse(name: &str) -> Result<Self, AppError> {
+        Self::ALL
+            .into_iter()
+            .find(|resource| resource.as_str() == name)
+            .ok_or_else(|| AppError::NotFound {
+                resource: format!("Gated config resource: {name}"),
+            })
+    }
+
+    /// Check that `value` deserializes into this resource's config type
+    pub fn validate(self, value: &Value) -> Result<(), AppError> {
+        let result = match self {
+            Self::CurrencyDisplay => {
+                serde_json::from_value::<CurrencyDisplayConfig>(value.clone()).map(drop)
+            }
+            Self::NetworkConfig => {
+                serde_json::from_value::<GatedNetworkConfig>(value.clone()).map(drop)
+            }
+            Self::LeaseRules => serde_json::from_value::<LeaseRulesConfig>(value.clone()).map(drop),
+            Self::SwapSettings => {
+                serde_json::from_value::<SwapSettingsConfig>(value.clone()).map(drop)
+            }
+            Self::UiSettings => serde_json::from_value::<UiSettingsConfig>(value.clone()).map(drop),
+        };
+        result.map_err(|e| AppError::Validation {
+            message: format!("Invalid {} config: {}", self.as_str(), e),
+            field: None,
+            details: None,
+        })
+    }
+}
+
+/// Kind of change at a single JSON path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOp {
+    Add,
+    Remove,
+    Replace,
+}
+
+/// A single change between two JSON documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonChange {
+    /// JSON Pointer (RFC 6901) to the changed value; "" is the document root
+    pub path: String,
+    pub op: ChangeOp,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new: Option<Value>,
+}
+
+/// Structured diff from `old` to `new`
+///
+/// Objects are compared key by key (in sorted order); arrays and scalars are
+/// replaced as a whole. A `null` document is treated as absent.
+pub fn json_diff(old: &Value, new: &Value) -> Vec<JsonChange> {
+    let mut changes = Vec::new();
+    match (old, new) {
+        (Value::Null, Value::Null) => {}
+        (Value::Null, _) => changes.push(JsonChange {
+            path: String::new(),
+            op: ChangeOp::Add,
+            old: None,
+            new: Some(new.clone()),
+        }),
+        (_, Value::Null) => changes.push(JsonChange {
+            path: String::new(),
+            op: ChangeOp::Remove,
+            old: Some(old.clone()),
+            new: None,
+        }),
+        _ => diff_into(String::new(), old, new, &mut changes),
+    }
+    changes
+}
+
+fn diff_into(path: String, old: &Value, new: &Value, changes: &mut Vec<JsonChange>) {
+    let (Value::Object(old_map), Value::Object(new_map)) = (old, new) else {
+        if old != new {
+            changes.push(JsonChange {
+                path,
+                op: ChangeOp::Replace,
+                old: Some(old.clone()),
+                new: Some(new.clone()),
+            });
+        }
+        return;
+    };
+
+    let mut keys: Vec<&String> = old_map.keys().chain(new_map.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        let child = format!("{path}/{}", key.replace('~', "~0").replace('/', "~1"));
+        match (old_map.get(key), new_map.get(key)) {
+            (Some(old_value), Some(new_value)) => diff_into(child, old_value, new_value, changes),
+            (Some(old_value), None) => changes.push(JsonChange {
+                path: child,
+                op: ChangeOp::Remove,
+                old: Some(old_value.clone()),
+                new: None,
+            }),
+            (None, Some(new_value)) => changes.push(JsonChange {
+                path: child,
+                op: ChangeOp::Add,
+                old: None,
+                new: Some(new_value.clone()),
+            }),
+            (None, None) => {}
+        }
+    }
+}
+
+/// Version metadata, as listed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigVersionSummary {
+    pub id: u64,
+    pub resource: GatedResource,
+    /// Name of the admin key that saved this version (`None` for system writes)
+    pub author: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Number of changes against the content this version replaced
+    pub change_count: usize,
+    /// Version this one restored, when created by a rollback
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollback_of: Option<u64>,
+}
+
+/// An immutable version: metadata, diff against its predecessor, and the
+/// full saved document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigVersion {
+    #[serde(flatten)]
+    pub summary: ConfigVersionSummary,
+    pub diff: Vec<JsonChange>,
+    pub snapshot: Value,
+}
+
+/// Response for version list queries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionListResponse {
+    pub versions: Vec<ConfigVersionSummary>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+/// A staged, not-yet-live change to one resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigDraft {
+    pub resource: GatedResource,
+    pub author: Option<String>,
+    pub updated_at: DateTime<Utc>,
+    /// Latest version of the resource when the draft was staged. Publishing
+    /// is refused once the live config has moved past it.
+    pub base_version: Option<u64>,
+    pub config: Value,
+}
+
+/// Version history and drafts for the gated config files
+#[derive(Debug)]
+pub struct VersionStore {
+    versions_dir: PathBuf,
+    drafts_dir: PathBuf,
+    /// Summaries of every version, ascending by id
+    index: RwLock<Vec<ConfigVersionSummary>>,
+}
+
+impl VersionStore {
+    /// Create a store rooted at the gated config directory
+    pub fn new<P: AsRef<Path>>(gated_dir: P) -> Self {
+        Self {
+            versions_dir: gated_dir.as_ref().join("versions"),
+            drafts_dir: gated_dir.as_ref().join("drafts"),
+            index: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Create directories and index existing versions
+    pub async fn init(&self) -> Result<(), AppError> {
+        for dir in [&self.versions_dir, &self.drafts_dir] {
+            fs::create_dir_all(dir).await.map_err(|e| {
+                AppError::Internal(format!("Failed to create directory {:?}: {}", dir, e))
+            })?;
+        }
+
+        let mut entries = fs::read_dir(&self.versions_dir)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to read versions: {}", e)))?;
+        let mut summaries = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to read versions: {}", e)))?
+        {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            match read_json::<ConfigVersion>(&path).await {
+                Ok(version) => summaries.push(version.summary),
+                Err(e) => warn!("Skipping unreadable config version {:?}: {}", path, e),
+            }
+        }
+        summaries.sort_by_key(|summary| summary.id);
+
+        *self.index.write().await = summaries;
+        Ok(())
+    }
+
+    /// Latest version id of `resource`, if it has any
+    pub async fn latest_id(&self, resource: GatedResource) -> Option<u64> {
+        self.index
+            .read()
+            .await
+            .iter()
+            .rev()
+            .find(|summary| summary.resource == resource)
+            .map(|summary| summary.id)
+    }
+
+    /// Record a new immutable version of `resource`
+    pub async fn record(
+        &self,
+        resource: GatedResource,
+        previous: &Value,
+        snapshot: Value,
+        author: Option<&str>,
+        rollback_of: Option<u64>,
+    ) -> Result<ConfigVersionSummary, AppError> {
+        let diff = json_diff(previous, &snapshot);
+        // Hold the index lock across the write so ids are assigned in order.
+        let mut index = self.index.write().await;
+        let summary = ConfigVersionSummary {
+            id: index.last().map_or(1, |last| last.id + 1),
+            resource,
+            author: author.map(String::from),
+            created_at: Utc::now(),
+            change_count: diff.len(),
+            rollback_of,
+        };
+        let version = ConfigVersion {
+            summary: summary.clone(),
+            diff,
+            snapshot,
+        };
+        let content = serde_json::to_vec_pretty(&version)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize version: {}", e)))?;
+
+        let path = self.version_path(summary.id);
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to create version file: {}", e)))?;
+        file.write_all(&content)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to write version file: {}", e)))?;
+        file.sync_all()
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to sync version file: {}", e)))?;
+
+        index.push(summary.clone());
+        Ok(summary)
+    }
+
+    /// Undo a [`record`](Self::record) whose live write failed. Only the
+    /// newest version can be retracted; ids stay contiguous.
+    pub async fn retract(&self, id: u64) {
+        let mut index = self.index.write().await;
+        if index.last().is_some_and(|last| last.id == id) {
+            index.pop();
+            if let Err(e) = fs::remove_file(self.version_path(id)).await {
+                warn!("Failed to remove retracted config version {}: {}", id, e);
+            }
+        }
+    }
+
+    /// Load a full version
+    pub async fn get(&self, id: u64) -> Result<ConfigVersion, AppError> {
+        let known = self
+            .index
+            .read()
+            .await
+            .iter()
+            .any(|summary| summary.id == id);
+        if !known {
+            return Err(AppError::NotFound {
+                resource: format!("Config version: {id}"),
+            });
+        }
+        read_json(&self.version_path(id)).await
+    }
+
+    /// List versions, most recent first, optionally for one resource
+    pub async fn list(
+        &self,
+        resource: Option<GatedResource>,
+        offset: usize,
+        limit: usize,
+    ) -> VersionListResponse {
+        let index = self.index.read().await;
+        let filtered: Vec<&ConfigVersionSummary> = index
+            .iter()
+            .filter(|summary| resource.is_none_or(|r| summary.resource == r))
+            .collect();
+
+        let total = filtered.len();
+        let limit = if limit == 0 {
+            DEFAULT_PAGE_LIMIT
+        } else {
+            limit.min(MAX_PAGE_LIMIT)
+        };
+        let offset = offset.min(total);
+
+        VersionListResponse {
+            versions: filtered
+                .into_iter()
+                .rev()
+                .skip(offset)
+                .take(limit)
+                .cloned()
+                .collect(),
+            total,
+            offset,
+            limit,
+        }
+    }
+
+    /// Changes from version `from` to version `to` of the same resource
+    pub async fn diff(&self, from: u64, to: u64) -> Result<Vec<JsonChange>, AppError> {
+        let from = self.get(from).await?;
+        let to = self.get(to).await?;
+        if from.summary.resource != to.summary.resource {
+            return Err(AppError::Validation {
+                message: format!(
+                    "Versions {} and {} belong to different resources",
+                    from.summary.id, to.summary.id
+                ),
+                field: Some("against".to_string()),
+                details: None,
+            });
+        }
+        Ok(json_diff(&from.snapshot, &to.snapshot))
+    }
+
+    /// Stage (or replace) the draft for a resource
+    pub async fn save_draft(&self, draft: &ConfigDraft) -> Result<(), AppError> {
+        let path = self.draft_path(draft.resource);
+        let temp = path.with_extension("json.tmp");
+        let content = serde_json::to_vec_pretty(draft)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize draft: {}", e)))?;
+        fs::write(&temp, &content)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to write draft: {}", e)))?;
+        fs::rename(&temp, &path)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to save draft: {}", e)))
+    }
+
+    /// Load the staged draft for a resource
+    pub async fn load_draft(&self, resource: GatedResource) -> Result<ConfigDraft, AppError> {
+        let path = self.draft_path(resource);
+        if !path.exists() {
+            return Err(AppError::NotFound {
+                resource: format!("Draft: {}", resource.as_str()),
+            });
+        }
+        read_json(&path).await
+    }
+
+    /// Every staged draft
+    pub async fn list_drafts(&self) -> Result<Vec<ConfigDraft>, AppError> {
+        let mut drafts = Vec::new();
+        for resource in GatedResource::ALL {
+            match self.load_draft(resource).await {
+                Ok(draft) => drafts.push(draft),
+                Err(AppError::NotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(drafts)
+    }
+
+    /// Drop the staged draft for a resource
+    pub async fn discard_draft(&self, resource: GatedResource) -> Result<(), AppError> {
+        let path = self.draft_path(resource);
+        if !path.exists() {
+            return Err(AppError::NotFound {
+                resource: format!("Draft: {}", resource.as_str()),
+            });
+        }
+        fs::remove_file(&path)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to remove draft: {}", e)))
+    }
+
+    fn version_path(&self, id: u64) -> PathBuf {
+        self.versions_dir.join(format!("{id:08}.json"))
+    }
+
+    fn draft_path(&self, resource: GatedResource) -> PathBuf {
+        self.drafts_dir.join(format!("{}.json", resource.as_str()))
+    }
+}
+
+async fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, AppError> {
+    let content = fs::read(path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read {:?}: {}", path, e)))?;
+    serde_json::from_slice(&content)
+        .map_err(|e| AppError::Internal(format!("Failed to parse {:?}: {}", path, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    async fn store() -> (TempDir, VersionStore) {
+        let dir = TempDir::new().unwrap();
+        let store = VersionStore::new(dir.path());
+        store.init().await.unwrap();
+        (dir, store)
+    }
+
+    #[test]
+    fn json_diff_reports_nested_adds_removes_and_replacements() {
+        let old = json!({"ATOM": {"icon": "a.svg", "color": "#fff"}, "OSMO": {"icon": "o.svg"}});
+        let new = json!({"ATOM": {"icon": "b.svg", "color": "#fff"}, "a/b": 1});
+        let diff = json_diff(&old, &new);
+        assert_eq!(
+            diff,
+            vec![
+                JsonChange {
+                    path: "/ATOM/icon".to_string(),
+                    op: ChangeOp::Replace,
+                    old: Some(json!("a.svg")),
+                    new: Some(json!("b.svg")),
+                },
+                JsonChange {
+                    path: "/OSMO".to_string(),
+                    op: ChangeOp::Remove,
+                    old: Some(json!({"icon": "o.svg"})),
+                    new: None,
+                },
+                JsonChange {
+                    path: "/a~1b".to_string(),
+                    op: ChangeOp::Add,
+                    old: None,
+                    new: Some(json!(1)),
+                },
+            ]
+        );
+        assert!(json_diff(&new, &new).is_empty());
+        assert_eq!(json_diff(&Value::Null, &new)[0].op, ChangeOp::Add);
+    }
+
+    #[tokio::test]
+    async fn versions_are_numbered_and_survive_reindex() {
+        let (dir, store) = store().await;
+        let first = store
+            .record(
+                GatedResource::UiSettings,
+                &Value::Null,
+                json!({"hidden_proposals": ["1"]}),
+                Some("ops"),
+                None,
+            )
+            .await
+            .unwrap();
+        let second = store
+            .record(
+                GatedResource::UiSettings,
+                &json!({"hidden_proposals": ["1"]}),
+                json!({"hidden_proposals": ["1", "2"]}),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!((first.id, second.id), (1, 2));
+        assert_eq!(second.change_count, 1);
+
+        let reopened = VersionStore::new(dir.path());
+        reopened.init().await.unwrap();
+        let listed = reopened.list(None, 0, 0).await;
+        assert_eq!(listed.total, 2);
+        assert_eq!(listed.versions[0].id, 2, "most recent first");
+        assert_eq!(
+            reopened.get(1).await.unwrap().summary.author.as_deref(),
+            Some("ops")
+        );
+        assert_eq!(reopened.latest_id(GatedResource::UiSettings).await, Some(2));
+        assert_eq!(reopened.latest_id(GatedResource::LeaseRules).await, None);
+    }
+
+    #[tokio::test]
+    async fn diff_between_versions_requires_same_resource() {
+        let (_dir, store) = store().await;
+        for (resource, value) in [
+            (
+                GatedResource::UiSettings,
+                json!({"feature_flags": {"a": true}}),
+            ),
+            (
+                GatedResource::UiSettings,
+                json!({"feature_flags": {"a": false}}),
+            ),
+            (GatedResource::LeaseRules, json!({})),
+        ] {
+            store
+                .record(resource, &Value::Null, value, None, None)
+                .await
+                .unwrap();
+        }
+        let changes = store.diff(1, 2).await.unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "/feature_flags/a");
+        assert!(store.diff(1, 3).await.is_err());
+        assert!(store.get(99).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn drafts_round_trip_and_discard() {
+        let (_dir, store) = store().await;
+        let draft = ConfigDraft {
+            resource: GatedResource::SwapSettings,
+            author: Some("ops".to_string()),
+            updated_at: Utc::now(),
+            base_version: None,
+            config: json!({"api_url": "https://skip"}),
+        };
+        store.save_draft(&draft).await.unwrap();
+        assert_eq!(store.list_drafts().await.unwrap().len(), 1);
+        assert_eq!(
+            store
+                .load_draft(GatedResource::SwapSettings)
+                .await
+                .unwrap()
+                .config,
+            draft.config
+        );
+
+        store
+            .discard_draft(GatedResource::SwapSettings)
+            .await
+            .unwrap();
+        assert!(store.load_draft(GatedResource::SwapSettings).await.is_err());
+        assert!(store
+            .discard_draft(GatedResource::SwapSettings)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn resource_names_parse_and_validate() {
+        assert_eq!(
+            GatedResource::parse("lease-rules").unwrap(),
+            GatedResource::LeaseRules
+        );
+        assert!(GatedResource::parse("secrets").is_err());
+        assert!(GatedResource::UiSettings.validate(&json!({})).is_ok());
+        assert!(GatedResource::SwapSettings.validate(&json!({})).is_err());
+        assert!(GatedResource::LeaseRules
+            .validate(&json!({"alerts": {"ltv_warning_thresholds": [90.0, 80.0]}}))
+            .is_err());
+    }
+}
@@ -29,6 +29,7 @@ use crate::handlers::gated_networks::NetworksResponse;
 use crate::handlers::leases::LeaseConfigResponse;
 use crate::handlers::staking::Validator;
 use crate::handlers::swap::SwapConfigResponse;
+use crate::handlers::validator_scoring::ValidatorScoreState;
 use crate::propagation::user_data_filter::UserDataFilterContext;
 use std::collections::HashMap;
 
@@ -123,10 +124,15 @@ pub type ProtocolContractsMap = HashMap<String, crate::external::chain::Protocol
 /// each refresh: per-id failures retain the prior value, proposals that exit
 /// voting period are pruned. Finalized proposals' tally lives on
 /// `Proposal::final_tally_result` and does not need a separate map entry.
+///
+/// `tally_history` holds the tallies sampled while each proposal was voting.
+/// It outlives the voting period and is only dropped once the proposal falls
+/// out of the proposals window (or the process restarts).
 #[derive(Debug, Clone)]
 pub struct ProposalsWithTally {
     pub proposals: Vec<crate::external::chain::Proposal>,
     pub tallies: HashMap<String, crate::external::chain::TallyResult>,
+    pub tally_history: HashMap<String, Vec<crate::handlers::governance::TallySample>>,
 }
 
 /// All cached application data.
@@ -171,6 +177,9 @@ pub struct AppDataCache {
     /// Staking pool (bonded/not-bonded tokens)
     pub staking_pool: Cached<StakingPoolResponse>,
 
+    /// Validator scores plus the vote and commission history behind them
+    pub validator_scores: Cached<ValidatorScoreState>,
+
     /// Gated assets (deduplicated, enriched view)
     pub gated_assets: Cached<AssetsResponse>,
 
@@ -216,6 +225,7 @@ impl AppDataCache {
             annual_inflation: Cached::new(),
             proposals_with_tally: Cached::new(),
             staking_pool: Cached::new(),
+            validator_scores: Cached::new(),
             gated_assets: Cached::new(),
             gated_protocols: Cached::new(),
             gated_networks: Cached::new(),
@@ -242,6 +252,7 @@ impl AppDataCache {
             proposals_with_tally: self
                 .field_status("proposals_with_tally", &self.proposals_with_tally),
             staking_pool: self.field_status("staking_pool", &self.staking_pool),
+            validator_scores: self.field_status("validator_scores", &self.validator_scores),
             gated_assets: self.field_status("gated_assets", &self.gated_assets),
             gated_protocols: self.field_status("gated_protocols", &self.gated_protocols),
             gated_networks: self.field_status("gated_networks", &self.gated_networks),
@@ -290,6 +301,7 @@ pub struct CacheStatusSummary {
     pub annual_inflation: CacheFieldStatus,
     pub proposals_with_tally: CacheFieldStatus,
     pub staking_pool: CacheFieldStatus,
+    pub validator_scores: CacheFieldStatus,
     pub gated_assets: CacheFieldStatus,
     pub gated_protocols: CacheFieldStatus,
     pub gated_networks: CacheFieldStatus,
@@ -0,0 +1,697 @@
+//! Backend-recorded lender ledger for earn position history.
+//!
+//! [`start_earn_ledger_task`] listens to `contract_exec` events emitted by
+//! LPP contracts. `wasm-lp-deposit` records a deposit with the nLPN receipts
+//! it minted, `wasm-lp-close` a withdrawal with the receipts it burnt, and
+//! `wasm-lp-claim` a reward claim. Activity from before the backend was
+//! watching is merged in once per lender by [`backfill`], which replays the
+//! lender's LPP transactions from ETL and prices withdrawals via its
+//! `lp-withdraw` endpoint. If the event stream lags, lenders are re-backfilled
+//! from the start of the gap rather than forgotten. [`summarize`] turns the
+//! activity into cost basis, realized earnings and the nLPN prices
+//! time-weighted yield is measured between. Durable state lives in [`store`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, warn};
+use utoipa::ToSchema;
+
+use crate::chain_events::ContractExecEvent;
+use crate::error::AppError;
+use crate::external::etl::EtlTransaction;
+use crate::lease_ledger::usd_value;
+use crate::num_utils::{f64_ceil_to_u64, u128_to_f64};
+use crate::AppState;
+
+mod store;
+
+pub use store::EarnLedgerStore;
+
+/// Lenders tracked; those with the oldest latest activity are evicted first.
+pub const MAX_TRACKED_LENDERS: usize = 50_000;
+
+/// Activity kept per position; past this the oldest is dropped and the
+/// position's summary is reported as inexact.
+pub const MAX_ACTIVITY_PER_POSITION: usize = 1_000;
+
+/// Custom event the LPP emits for every deposit.
+const LPP_DEPOSIT_EVENT: &str = "wasm-lp-deposit";
+
+/// Custom event the LPP emits for every withdrawal.
+const LPP_CLOSE_EVENT: &str = "wasm-lp-close";
+
+/// Custom event the LPP emits when a lender claims rewards.
+const LPP_CLAIM_EVENT: &str = "wasm-lp-claim";
+
+/// ETL transactions requested per page during backfill.
+const BACKFILL_PAGE_SIZE: u32 = 100;
+
+/// Transactions scanned per lender before backfill gives up on older history.
+const MAX_BACKFILL_TXS: u32 = 2_000;
+
+/// Concurrent `lp-withdraw` lookups during backfill.
+const BACKFILL_FANOUT_CAP: usize = 4;
+
+/// How far before the last received event a lag re-backfill starts, covering
+/// the gap between block time and the time the event reached the backend.
+const LAG_RESYNC_MARGIN_MINUTES: i64 = 5;
+
+/// Kind of lender activity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LenderActivityKind {
+    Deposit,
+    Withdraw,
+    RewardClaim,
+}
+
+/// Where an activity was recorded from: the live event stream or the ETL
+/// backfill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivitySource {
+    Chain,
+    Etl,
+}
+
+/// One deposit, withdrawal or reward claim.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LenderActivity {
+    pub tx_hash: String,
+    pub at: DateTime<Utc>,
+    pub kind: LenderActivityKind,
+    /// LPN for deposits and withdrawals, the reward currency for claims
+    pub ticker: String,
+    /// Minor units of `ticker`
+    pub amount: u128,
+    /// nLPN minted or burnt; `None` for claims and backfilled deposits
+    pub receipts: Option<u128>,
+    /// USD value when recorded, if it could be priced; `None` for backfilled
+    /// activity, whose historical price isn't known
+    pub usd: Option<f64>,
+    pub source: ActivitySource,
+}
+
+/// Cost basis and earnings derived from a position's recorded activity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionSummary {
+    /// LPN deposited, minor units
+    pub total_deposited: u128,
+    /// LPN withdrawn, minor units
+    pub total_withdrawn: u128,
+    /// Average-cost LPN basis of the receipts still held
+    pub cost_basis: u128,
+    /// Withdrawn LPN minus the cost basis of the receipts burnt
+    pub realized_earnings: i128,
+    /// Reward currency claimed, minor units
+    pub rewards_claimed: u128,
+    /// `None` if any claim could not be priced
+    pub rewards_claimed_usd: Option<f64>,
+    /// nLPN the recorded activity leaves the lender holding
+    pub receipts_held: u128,
+    /// Every deposit and withdrawal carried its receipts and no withdrawal
+    /// burnt more than the recorded holdings
+    pub exact: bool,
+    /// First observed nLPN price (LPN per nLPN)
+    pub first_price: Option<(DateTime<Utc>, f64)>,
+    /// Latest observed nLPN price (LPN per nLPN)
+    pub last_price: Option<(DateTime<Utc>, f64)>,
+}
+
+/// Replay a position's activity (oldest first) with average-cost accounting.
+///
+/// Receipts missing from backfilled deposits are estimated at the last
+/// observed nLPN price, starting from the 1:1 issue price, and mark the
+/// summary inexact.
+pub fn summarize(activity: &[LenderActivity]) -> PositionSummary {
+    let mut summary = PositionSummary {
+        total_deposited: 0,
+        total_withdrawn: 0,
+        cost_basis: 0,
+        realized_earnings: 0,
+        rewards_claimed: 0,
+        rewards_claimed_usd: Some(0.0),
+        receipts_held: 0,
+        exact: true,
+        first_price: None,
+        last_price: None,
+    };
+    let mut price = 1.0;
+
+    for entry in activity {
+        if entry.kind == LenderActivityKind::RewardClaim {
+            summary.rewards_claimed = summary.rewards_claimed.saturating_add(entry.amount);
+            summary.rewards_claimed_usd = summary
+                .rewards_claimed_usd
+                .zip(entry.usd)
+                .map(|(total, usd)| total + usd);
+            continue;
+        }
+
+        let receipts = match entry.receipts {
+            Some(receipts) if receipts > 0 => {
+                price = u128_to_f64(entry.amount) / u128_to_f64(receipts);
+                if summary.first_price.is_none() {
+                    summary.first_price = Some((entry.at, price));
+                }
+                summary.last_price = Some((entry.at, price));
+                receipts
+            }
+            _ => {
+                summary.exact = false;
+                u128::from(f64_ceil_to_u64(u128_to_f64(entry.amount) / price))
+            }
+        };
+
+        if entry.kind == LenderActivityKind::Deposit {
+            summary.total_deposited = summary.total_deposited.saturating_add(entry.amount);
+            summary.cost_basis = summary.cost_basis.saturating_add(entry.amount);
+            summary.receipts_held = summary.receipts_held.saturating_add(receipts);
+            continue;
+        }
+
+        summary.total_withdrawn = summary.total_withdrawn.saturating_add(entry.amount);
+        if receipts > summary.receipts_held {
+            summary.exact = false;
+        }
+        let burnt = receipts.min(summary.receipts_held);
+        let cost_removed = if summary.receipts_held == 0 {
+            summary.cost_basis
+        } else {
+            summary.cost_basis.checked_mul(burnt).map_or_else(
+                || summary.cost_basis / summary.receipts_held * burnt,
+                |scaled| scaled / summary.receipts_held,
+            )
+        };
+        summary.realized_earnings = summary
+            .realized_earnings
+            .saturating_add(signed(entry.amount))
+            .saturating_sub(signed(cost_removed));
+        summary.cost_basis = summary.cost_basis.saturating_sub(cost_removed);
+        summary.receipts_held = summary.receipts_held.saturating_sub(burnt);
+    }
+
+    summary
+}
+
+/// Return on one nLPN between two observed prices, in percent, and its
+/// annualized equivalent once at least a day has passed.
+///
+/// The nLPN price already compounds everything the pool paid out, so its
+/// growth is the time-weighted yield regardless of when the lender added or
+/// removed funds.
+pub fn time_weighted_yield(
+    (start_at, start_price): (DateTime<Utc>, f64),
+    (end_at, end_price): (DateTime<Utc>, f64),
+) -> Option<(f64, Option<f64>)> {
+    if start_price <= 0.0 || end_at < start_at {
+        return None;
+    }
+    let growth = end_price / start_price;
+    let minutes = i32::try_from((end_at - start_at).num_minutes()).unwrap_or(i32::MAX);
+    let days = f64::from(minutes) / 1_440.0;
+    let annualized = (days >= 1.0).then(|| (growth.powf(365.0 / days) - 1.0) * 100.0);
+    Some(((growth - 1.0) * 100.0, annualized))
+}
+
+fn signed(value: u128) -> i128 {
+    i128::try_from(value).unwrap_or(i128::MAX)
+}
+
+/// Payload of an LPP deposit, withdrawal or claim event.
+#[derive(Debug, Clone, PartialEq)]
+struct LppObservation {
+    lender: String,
+    kind: LenderActivityKind,
+    ticker: String,
+    amount: u128,
+    receipts: Option<u128>,
+}
+
+fn parse_lpp_event(event: &ContractExecEvent) -> Option<LppObservation> {
+    let (kind, lender_key, coin) = match event.event_type.as_str() {
+        LPP_DEPOSIT_EVENT => (LenderActivityKind::Deposit, "from", "deposit"),
+        LPP_CLOSE_EVENT => (LenderActivityKind::Withdraw, "to", "withdraw"),
+        LPP_CLAIM_EVENT => (LenderActivityKind::RewardClaim, "to", "reward"),
+        _ => return None,
+    };
+    let receipts = match kind {
+        LenderActivityKind::RewardClaim => None,
+        _ => Some(event.attribute("receipts")?.parse().ok()?),
+    };
+    Some(LppObservation {
+        lender: event.attribute(lender_key)?.to_string(),
+        kind,
+        ticker: event.attribute(&format!("{}-symbol", coin))?.to_string(),
+        amount: event.attribute(&format!("{}-amount", coin))?.parse().ok()?,
+        receipts,
+    })
+}
+
+/// LPP action a lender sent in an ETL-indexed transaction.
+#[derive(Debug, Clone, PartialEq)]
+struct EtlLppAction {
+    protocol: String,
+    kind: LenderActivityKind,
+    /// Deposited funds; withdrawals are priced through `lp-withdraw`
+    amount: Option<u128>,
+    receipts: Option<u128>,
+}
+
+/// Recognize a lender's deposit or withdrawal execution against an LPP.
+///
+/// Reward claims are skipped: the message doesn't carry the claimed amount,
+/// and ETL's `earnings` total already covers them.
+fn parse_etl_lpp_tx(
+    tx: &EtlTransaction,
+    lender: &str,
+    pools: &HashMap<String, String>,
+) -> Option<EtlLppAction> {
+    if !tx
+        .r#type
+        .as_deref()
+        .is_some_and(|t| t.ends_with("MsgExecuteContract"))
+    {
+        return None;
+    }
+    let data = tx.data.as_ref()?;
+    if data["sender"].as_str() != Some(lender) {
+        return None;
+    }
+    let protocol = pools.get(data["contract"].as_str()?)?.clone();
+    // Some ETL versions store the message as its JSON string.
+    let msg = match &data["msg"] {
+        serde_json::Value::String(raw) => serde_json::from_str(raw).ok()?,
+        msg => msg.clone(),
+    };
+
+    if msg.get("deposit").is_some() {
+        let amount = data["funds"]
+            .as_array()?
+            .first()?
+            .get("amount")?
+            .as_str()?
+            .parse()
+            .ok()?;
+        return Some(EtlLppAction {
+            protocol,
+            kind: LenderActivityKind::Deposit,
+            amount: Some(amount),
+            receipts: None,
+        });
+    }
+    // `handlers::earn::withdraw` builds `burn_deposit`; older clients sent `burn`.
+    let burnt = msg
+        .get("burn_deposit")
+        .or_else(|| msg.get("burn"))?
+        .get("amount")?
+        .as_str()?
+        .parse()
+        .ok()?;
+    Some(EtlLppAction {
+        protocol,
+        kind: LenderActivityKind::Withdraw,
+        amount: None,
+        receipts: Some(burnt),
+    })
+}
+
+/// A lender's LPP actions from ETL at or after `since` (every scanned one if
+/// `None`), as `(tx hash, time, action)`.
+///
+/// ETL lists transactions newest first, so a window ends the scan at the first
+/// page reaching back past its start.
+async fn scan_lpp_actions(
+    state: &AppState,
+    lender: &str,
+    pools: &HashMap<String, String>,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<(String, DateTime<Utc>, EtlLppAction)>, AppError> {
+    let mut actions = Vec::new();
+    let mut skip = 0;
+    while skip < MAX_BACKFILL_TXS {
+        let page = state
+            .etl_client
+            .fetch_transactions(lender, skip, BACKFILL_PAGE_SIZE)
+            .await?;
+        let mut reached_window_start = false;
+        for tx in &page.data {
+            let Some(at) = tx
+                .timestamp
+                .as_deref()
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(|at| at.with_timezone(&Utc))
+            else {
+                continue;
+            };
+            if since.is_some_and(|since| at < since) {
+                reached_window_start = true;
+                continue;
+            }
+            if let Some(action) = parse_etl_lpp_tx(tx, lender, pools) {
+                actions.push((tx.hash.clone(), at, action));
+            }
+        }
+        let full_page = u32::try_from(page.data.len()).is_ok_and(|n| n >= BACKFILL_PAGE_SIZE);
+        if !full_page || reached_window_start {
+            break;
+        }
+        skip = skip.saturating_add(BACKFILL_PAGE_SIZE);
+    }
+    Ok(actions)
+}
+
+/// Merge the lender's LPP history from ETL into the ledger, once per lender,
+/// and again from the start of any window in which live events were missed.
+///
+/// Transactions the live stream already recorded are deduplicated by hash.
+/// Fails (and is retried on the next request) if ETL can't list the
+/// lender's transactions; withdrawals ETL can't price are left out.
+pub async fn backfill(state: &AppState, lender: &str) -> Result<(), AppError> {
+    let since = state.earn_ledger.stale_since(lender);
+    if state.earn_ledger.is_backfilled(lender) && since.is_none() {
+        return Ok(());
+    }
+    let contracts = state
+        .data_cache
+        .protocol_contracts
+        .load_or_unavailable("Protocol contracts")?;
+    let pools: HashMap<String, String> = contracts
+        .iter()
+        .map(|(protocol, info)| (info.lpp.clone(), protocol.clone()))
+        .collect();
+    let currencies_cache = state.data_cache.currencies.load();
+    let currencies = currencies_cache.as_ref();
+
+    let actions = scan_lpp_actions(state, lender, &pools, since).await?;
+
+    let lpn_ticker = |protocol: &str| {
+        currencies.and_then(|c| {
+            c.lpn
+                .iter()
+                .find(|lpn| lpn.protocol == protocol)
+                .map(|lpn| lpn.ticker.clone())
+        })
+    };
+    let recorded: Vec<(String, LenderActivity)> = futures::stream::iter(actions)
+        .map(|(tx_hash, at, action)| async move {
+            let (ticker, amount) = match action.amount {
+                Some(amount) => (lpn_ticker(&action.protocol)?, amount),
+                None => match state.etl_client.fetch_lp_withdraw(&tx_hash).await {
+                    Ok(withdraw) => (withdraw.asset, withdraw.amount.parse().ok()?),
+                    Err(e) => {
+                        debug!("No ETL withdrawal for {}: {}", tx_hash, e);
+                        return None;
+                    }
+                },
+            };
+            Some((
+                action.protocol,
+                LenderActivity {
+                    tx_hash,
+                    at,
+                    kind: action.kind,
+                    ticker,
+                    amount,
+                    receipts: action.receipts,
+                    usd: None,
+                    source: ActivitySource::Etl,
+                },
+            ))
+        })
+        .buffer_unordered(BACKFILL_FANOUT_CAP)
+        .filter_map(std::future::ready)
+        .collect()
+        .await;
+
+    state.earn_ledger.merge_backfill(lender, recorded).await
+}
+
+async fn on_lpp_event(state: &Arc<AppState>, event: &ContractExecEvent) {
+    let protocol = state
+        .data_cache
+        .protocol_contracts
+        .load()
+        .and_then(|contracts| {
+            contracts
+                .iter()
+                .find(|(_, info)| info.lpp == event.contract_address)
+                .map(|(protocol, _)| protocol.clone())
+        });
+    let Some(protocol) = protocol else {
+        return;
+    };
+    let Some(observed) = parse_lpp_event(event) else {
+        warn!(
+            "Malformed {} event in tx {}",
+            event.event_type, event.tx_hash
+        );
+        return;
+    };
+    let usd = match (
+        state.data_cache.prices.load(),
+        state.data_cache.currencies.load(),
+    ) {
+        (Some(prices), Some(currencies)) => usd_value(
+            &prices,
+            &currencies,
+            &observed.ticker,
+            &protocol,
+            &observed.amount.to_string(),
+        ),
+        _ => None,
+    };
+    let activity = LenderActivity {
+        tx_hash: event.tx_hash.clone(),
+        at: Utc::now(),
+        kind: observed.kind,
+        ticker: observed.ticker,
+        amount: observed.amount,
+        receipts: observed.receipts,
+        usd,
+        source: ActivitySource::Chain,
+    };
+    if let Err(e) = state
+        .earn_ledger
+        .record(&observed.lender, &protocol, activity)
+        .await
+    {
+        warn!(
+            "Failed to record LPP activity of {} in tx {}: {}",
+            observed.lender, event.tx_hash, e
+        );
+    }
+}
+
+/// Record LPP deposits, withdrawals and reward claims from contract events.
+pub async fn start_earn_ledger_task(
+    state: Arc<AppState>,
+    mut contract_rx: tokio::sync::broadcast::Receiver<ContractExecEvent>,
+) {
+    tokio::spawn(async move {
+        let mut last_received = Utc::now();
+        loop {
+            match contract_rx.recv().await {
+                Ok(event) => {
+                    last_received = Utc::now();
+                    match event.event_type.as_str() {
+                        LPP_DEPOSIT_EVENT | LPP_CLOSE_EVENT | LPP_CLAIM_EVENT => {
+                            on_lpp_event(&state, &event).await
+                        }
+                        _ => {}
+                    }
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                    // A missed event would skew cost basis; have each lender's
+                    // next request re-read the gap from ETL.
+                    let since = last_received - Duration::minutes(LAG_RESYNC_MARGIN_MINUTES);
+                    warn!(
+                        "Earn ledger lagged {} events, re-backfilling lenders from {}",
+                        n, since
+                    );
+                    if let Err(e) = state.earn_ledger.mark_stale(since).await {
+                        warn!("Failed to persist stale earn ledger lenders: {}", e);
+                    }
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                    error!("Contract event channel closed, earn ledger stopping");
+                    return;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn event(event_type: &str, attributes: &[(&str, &str)]) -> ContractExecEvent {
+        ContractExecEvent {
+            contract_address: "nolus1lpp".to_string(),
+            action: None,
+            tx_hash: "HASH".to_string(),
+            height: Some(100),
+            event_type: event_type.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+        }
+    }
+
+    fn activity(
+        kind: LenderActivityKind,
+        days: i64,
+        amount: u128,
+        receipts: Option<u128>,
+    ) -> LenderActivity {
+        LenderActivity {
+            tx_hash: format!("T{}", days),
+            at: DateTime::<Utc>::UNIX_EPOCH + Duration::days(days),
+            kind,
+            ticker: "USDC".to_string(),
+            amount,
+            receipts,
+            usd: None,
+            source: ActivitySource::Chain,
+        }
+    }
+
+    #[test]
+    fn parses_lpp_events() {
+        let deposit = parse_lpp_event(&event(
+            LPP_DEPOSIT_EVENT,
+            &[
+                ("from", "nolus1lender"),
+                ("deposit-amount", "1000000"),
+                ("deposit-symbol", "USDC_NOBLE"),
+                ("receipts", "950000"),
+            ],
+        ))
+        .unwrap();
+        assert_eq!(deposit.lender, "nolus1lender");
+        assert_eq!(deposit.kind, LenderActivityKind::Deposit);
+        assert_eq!(deposit.receipts, Some(950_000));
+
+        let claim = parse_lpp_event(&event(
+            LPP_CLAIM_EVENT,
+            &[
+                ("to", "nolus1lender"),
+                ("reward-amount", "42"),
+                ("reward-symbol", "NLS"),
+            ],
+        ))
+        .unwrap();
+        assert_eq!(claim.kind, LenderActivityKind::RewardClaim);
+        assert_eq!(claim.receipts, None);
+
+        // A withdrawal must say how many receipts it burnt.
+        assert!(parse_lpp_event(&event(
+            LPP_CLOSE_EVENT,
+            &[
+                ("to", "nolus1lender"),
+                ("withdraw-amount", "1"),
+                ("withdraw-symbol", "USDC_NOBLE"),
+            ],
+        ))
+        .is_none());
+    }
+
+    #[test]
+    fn recognizes_lender_lpp_transactions_from_etl() {
+        let pools = HashMap::from([("nolus1lpp".to_string(), "P".to_string())]);
+        let tx = |data: serde_json::Value| EtlTransaction {
+            hash: "H".to_string(),
+            height: None,
+            timestamp: Some("2026-01-01T00:00:00Z".to_string()),
+            r#type: Some("/cosmwasm.wasm.v1.MsgExecuteContract".to_string()),
+            data: Some(data),
+        };
+
+        let deposit = parse_etl_lpp_tx(
+            &tx(serde_json::json!({
+                "sender": "nolus1lender",
+                "contract": "nolus1lpp",
+                "msg": { "deposit": {} },
+                "funds": [{ "denom": "ibc/USDC", "amount": "500" }]
+            })),
+            "nolus1lender",
+            &pools,
+        )
+        .unwrap();
+        assert_eq!(deposit.amount, Some(500));
+
+        let burn = parse_etl_lpp_tx(
+            &tx(serde_json::json!({
+                "sender": "nolus1lender",
+                "contract": "nolus1lpp",
+                "msg": "{\"burn_deposit\":{\"amount\":\"480\"}}"
+            })),
+            "nolus1lender",
+            &pools,
+        )
+        .unwrap();
+        assert_eq!(burn.kind, LenderActivityKind::Withdraw);
+        assert_eq!(burn.receipts, Some(480));
+
+        // Someone else's deposit, or another contract, is not this lender's LPP activity.
+        let other = serde_json::json!({
+            "sender": "nolus1other",
+            "contract": "nolus1lpp",
+            "msg": { "deposit": {} },
+            "funds": [{ "denom": "ibc/USDC", "amount": "1" }]
+        });
+        assert!(parse_etl_lpp_tx(&tx(other), "nolus1lender", &pools).is_none());
+    }
+
+    #[test]
+    fn summary_uses_average_cost() {
+        let summary = summarize(&[
+            activity(LenderActivityKind::Deposit, 0, 1_000, Some(1_000)),
+            activity(LenderActivityKind::Deposit, 10, 1_100, Some(1_000)),
+            // Half the receipts at 1.2 LPN each
+            activity(LenderActivityKind::Withdraw, 20, 1_200, Some(1_000)),
+        ]);
+        assert!(summary.exact);
+        assert_eq!(summary.total_deposited, 2_100);
+        assert_eq!(summary.cost_basis, 1_050);
+        assert_eq!(summary.realized_earnings, 150);
+        assert_eq!(summary.receipts_held, 1_000);
+        assert_eq!(summary.first_price.unwrap().1, 1.0);
+        assert!((summary.last_price.unwrap().1 - 1.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn summary_estimates_missing_receipts_and_sums_claims() {
+        let mut claim = activity(LenderActivityKind::RewardClaim, 5, 42, None);
+        claim.usd = Some(0.5);
+        let summary = summarize(&[
+            activity(LenderActivityKind::Deposit, 0, 1_000, None),
+            claim,
+            activity(LenderActivityKind::Withdraw, 9, 1_100, Some(1_000)),
+        ]);
+        assert!(!summary.exact);
+        assert_eq!(summary.realized_earnings, 100);
+        assert_eq!(summary.rewards_claimed, 42);
+        assert_eq!(summary.rewards_claimed_usd, Some(0.5));
+    }
+
+    #[test]
+    fn time_weighted_yield_annualizes_price_growth() {
+        let start = DateTime::<Utc>::UNIX_EPOCH;
+        let (total, annualized) =
+            time_weighted_yield((start, 1.0), (start + Duration::days(365), 1.1)).unwrap();
+        assert!((total - 10.0).abs() < 1e-9);
+        assert!((annualized.unwrap() - 10.0).abs() < 1e-6);
+
+        let (_, short) =
+            time_weighted_yield((start, 1.0), (start + Duration::hours(6), 1.001)).unwrap();
+        assert!(short.is_none());
+    }
+}
@@ -0,0 +1,346 @@
+//! Durable earn ledger store.
+//!
+//! A single locked [`LedgerImage`] of per-lender activity, persisted as a
+//! whole-image JSON file after every change with the same discipline as the
+//! lease ledger: unique temp file, `sync_all`, rename into place, parent
+//! directory fsync. A corrupt image on load falls back to `<path>.bak` and
+//! otherwise fails loudly.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::image_file::ImageFile;
+
+use super::{LenderActivity, MAX_ACTIVITY_PER_POSITION, MAX_TRACKED_LENDERS};
+
+/// Everything the ledger persists, written as one image.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct LedgerImage {
+    /// lender address -> protocol -> activity, oldest first
+    lenders: HashMap<String, HashMap<String, Vec<LenderActivity>>>,
+    /// Lenders whose ETL history has been merged in
+    #[serde(default)]
+    backfilled: HashSet<String>,
+    /// Backfilled lenders that may have missed live events since the given
+    /// time, re-backfilled from then on their next request
+    #[serde(default)]
+    stale: HashMap<String, DateTime<Utc>>,
+    /// lender address -> protocols whose oldest activity was dropped at
+    /// [`MAX_ACTIVITY_PER_POSITION`]
+    #[serde(default)]
+    truncated: HashMap<String, HashSet<String>>,
+}
+
+impl LedgerImage {
+    /// Insert `activity` unless the same transaction already recorded it.
+    /// Returns whether the image changed.
+    fn insert(&mut self, lender: &str, protocol: &str, activity: LenderActivity) -> bool {
+        if !self.lenders.contains_key(lender) && self.lenders.len() >= MAX_TRACKED_LENDERS {
+            let stalest = self
+                .lenders
+                .iter()
+                .min_by_key(|(_, positions)| {
+                    positions
+                        .values()
+                        .filter_map(|activity| activity.last().map(|a| a.at))
+                        .max()
+                })
+                .map(|(address, _)| address.clone());
+            if let Some(stalest) = stalest {
+                self.lenders.remove(&stalest);
+                self.backfilled.remove(&stalest);
+                self.stale.remove(&stalest);
+                self.truncated.remove(&stalest);
+            }
+        }
+        let positions = self.lenders.entry(lender.to_string()).or_default();
+        let position = positions.entry(protocol.to_string()).or_default();
+        if position
+            .iter()
+            .any(|a| a.tx_hash == activity.tx_hash && a.kind == activity.kind)
+        {
+            return false;
+        }
+        position.push(activity);
+        position.sort_by_key(|a| a.at);
+        if position.len() > MAX_ACTIVITY_PER_POSITION {
+            // Keep the newest history; summaries over what remains are
+            // reported as inexact.
+            let excess = position.len() - MAX_ACTIVITY_PER_POSITION;
+            position.drain(..excess);
+            self.truncated
+                .entry(lender.to_string())
+                .or_default()
+                .insert(protocol.to_string());
+        }
+        true
+    }
+}
+
+/// The durable earn ledger.
+///
+/// The image is guarded by a single std [`Mutex`], never held across an
+/// `.await`. A separate async `write_gate` serializes persist calls so that
+/// the image written to disk is always the newest snapshot.
+pub struct EarnLedgerStore {
+    file: ImageFile,
+    image: Mutex<LedgerImage>,
+    write_gate: tokio::sync::Mutex<()>,
+}
+
+impl EarnLedgerStore {
+    /// Bind a store to `path` with an empty ledger — the create path when no
+    /// prior image exists.
+    pub fn create(path: PathBuf) -> Self {
+        Self {
+            file: ImageFile::new(path, "earn ledger"),
+            image: Mutex::new(LedgerImage::default()),
+            write_gate: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Load an existing image from `path`. A corrupt primary image falls back
+    /// to `<path>.bak`; if neither loads, this returns `Err`.
+    pub async fn load(path: PathBuf) -> Result<Self, AppError> {
+        let file = ImageFile::new(path, "earn ledger");
+        let image = file.load().await?;
+        Ok(Self {
+            file,
+            image: Mutex::new(image),
+            write_gate: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Durably write the current ledger to the store's path.
+    pub async fn persist(&self) -> Result<(), AppError> {
+        // Snapshot INSIDE the write gate so snapshot order equals rename order.
+        let _write = self.write_gate.lock().await;
+        let snapshot = self.lock().clone();
+        self.file.write(&snapshot).await
+    }
+
+    /// Recorded activity of `lender`, by protocol.
+    pub fn get(&self, lender: &str) -> HashMap<String, Vec<LenderActivity>> {
+        self.lock().lenders.get(lender).cloned().unwrap_or_default()
+    }
+
+    /// Recorded activity of `lender` in one pool.
+    pub fn position(&self, lender: &str, protocol: &str) -> Option<Vec<LenderActivity>> {
+        self.lock()
+            .lenders
+            .get(lender)
+            .and_then(|positions| positions.get(protocol))
+            .cloned()
+    }
+
+    /// Whether `lender`'s ETL history has already been merged in.
+    pub fn is_backfilled(&self, lender: &str) -> bool {
+        self.lock().backfilled.contains(lender)
+    }
+
+    /// Start of the window in which `lender` may have missed live events, if
+    /// its history needs a partial re-backfill.
+    pub fn stale_since(&self, lender: &str) -> Option<DateTime<Utc>> {
+        self.lock().stale.get(lender).copied()
+    }
+
+    /// Whether the oldest activity of `lender` in `protocol` was dropped at
+    /// the per-position cap.
+    pub fn is_truncated(&self, lender: &str, protocol: &str) -> bool {
+        self.lock()
+            .truncated
+            .get(lender)
+            .is_some_and(|protocols| protocols.contains(protocol))
+    }
+
+    /// Record one observed activity. A transaction already recorded for the
+    /// same kind is ignored.
+    pub async fn record(
+        &self,
+        lender: &str,
+        protocol: &str,
+        activity: LenderActivity,
+    ) -> Result<(), AppError> {
+        if !self.lock().insert(lender, protocol, activity) {
+            return Ok(());
+        }
+        self.persist().await
+    }
+
+    /// Merge backfilled activity and mark `lender` as backfilled and no
+    /// longer stale.
+    pub async fn merge_backfill(
+        &self,
+        lender: &str,
+        activity: Vec<(String, LenderActivity)>,
+    ) -> Result<(), AppError> {
+        {
+            let mut image = self.lock();
+            for (protocol, entry) in activity {
+                image.insert(lender, &protocol, entry);
+            }
+            image.backfilled.insert(lender.to_string());
+            image.stale.remove(lender);
+        }
+        self.persist().await
+    }
+
+    /// Mark every backfilled lender as possibly missing live events since
+    /// `since` (after missing events whose lenders are unknown). Recorded
+    /// history is kept; only the window is re-read from ETL.
+    pub async fn mark_stale(&self, since: DateTime<Utc>) -> Result<(), AppError> {
+        {
+            let mut image = self.lock();
+            let LedgerImage {
+                backfilled, stale, ..
+            } = &mut *image;
+            for lender in backfilled.iter() {
+                stale
+                    .entry(lender.clone())
+                    .and_modify(|at| *at = (*at).min(since))
+                    .or_insert(since);
+            }
+        }
+        self.persist().await
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LedgerImage> {
+        self.image.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Ephemeral store bound to a unique temp file, for tests only.
+    #[cfg(test)]
+    pub fn ephemeral() -> Self {
+        let path = crate::image_file::ephemeral_path("earn-ledger");
+        Self::create(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earn_ledger::{ActivitySource, LenderActivityKind};
+    use chrono::{Duration, Utc};
+    use tempfile::TempDir;
+
+    fn deposit(tx_hash: &str, minutes_ago: i64) -> LenderActivity {
+        LenderActivity {
+            tx_hash: tx_hash.to_string(),
+            at: Utc::now() - Duration::minutes(minutes_ago),
+            kind: LenderActivityKind::Deposit,
+            ticker: "USDC".to_string(),
+            amount: 1_000,
+            receipts: Some(1_000),
+            usd: Some(0.001),
+            source: ActivitySource::Chain,
+        }
+    }
+
+    #[tokio::test]
+    async fn activity_survives_reload() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("earn_ledger.json");
+        let store = EarnLedgerStore::create(path.clone());
+        store
+            .record("nolus1lender", "P", deposit("A", 0))
+            .await
+            .unwrap();
+        store
+            .merge_backfill("nolus1lender", Vec::new())
+            .await
+            .unwrap();
+
+        let reloaded = EarnLedgerStore::load(path).await.unwrap();
+        assert_eq!(reloaded.position("nolus1lender", "P").unwrap().len(), 1);
+        assert!(reloaded.is_backfilled("nolus1lender"));
+    }
+
+    #[tokio::test]
+    async fn backfill_dedupes_and_orders_by_time() {
+        let store = EarnLedgerStore::ephemeral();
+        store
+            .record("nolus1lender", "P", deposit("LIVE", 0))
+            .await
+            .unwrap();
+        let mut duplicate = deposit("LIVE", 5);
+        duplicate.source = ActivitySource::Etl;
+        store
+            .merge_backfill(
+                "nolus1lender",
+                vec![
+                    ("P".to_string(), duplicate),
+                    ("P".to_string(), deposit("OLD", 60)),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let position = store.position("nolus1lender", "P").unwrap();
+        let hashes: Vec<_> = position.iter().map(|a| a.tx_hash.as_str()).collect();
+        assert_eq!(hashes, ["OLD", "LIVE"]);
+        assert_eq!(position[1].source, ActivitySource::Chain);
+    }
+
+    #[tokio::test]
+    async fn mark_stale_keeps_history_until_the_window_is_merged() {
+        let store = EarnLedgerStore::ephemeral();
+        store
+            .merge_backfill("nolus1lender", vec![("P".to_string(), deposit("A", 0))])
+            .await
+            .unwrap();
+        let since = Utc::now() - Duration::minutes(10);
+        store.mark_stale(since).await.unwrap();
+        store
+            .mark_stale(since + Duration::minutes(5))
+            .await
+            .unwrap();
+        assert_eq!(store.stale_since("nolus1lender"), Some(since));
+        assert_eq!(store.position("nolus1lender", "P").unwrap().len(), 1);
+
+        store
+            .merge_backfill("nolus1lender", vec![("P".to_string(), deposit("B", 5))])
+            .await
+            .unwrap();
+        assert_eq!(store.stale_since("nolus1lender"), None);
+        assert_eq!(store.position("nolus1lender", "P").unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn full_position_drops_oldest_activity_and_is_marked_truncated() {
+        let store = EarnLedgerStore::ephemeral();
+        let activity = (0..MAX_ACTIVITY_PER_POSITION)
+            .map(|i| {
+                let minutes_ago = i64::try_from(MAX_ACTIVITY_PER_POSITION - i).unwrap();
+                ("P".to_string(), deposit(&format!("T{i}"), minutes_ago))
+            })
+            .collect();
+        store
+            .merge_backfill("nolus1lender", activity)
+            .await
+            .unwrap();
+        assert!(!store.is_truncated("nolus1lender", "P"));
+
+        store
+            .record("nolus1lender", "P", deposit("NEW", 0))
+            .await
+            .unwrap();
+        let position = store.position("nolus1lender", "P").unwrap();
+        assert_eq!(position.len(), MAX_ACTIVITY_PER_POSITION);
+        assert_eq!(position[0].tx_hash, "T1");
+        assert_eq!(position.last().unwrap().tx_hash, "NEW");
+        assert!(store.is_truncated("nolus1lender", "P"));
+    }
+
+    #[tokio::test]
+    async fn corrupt_image_without_backup_fails_loudly() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("earn_ledger.json");
+        tokio::fs::write(&path, b"[").await.unwrap();
+        assert!(EarnLedgerStore::load(path).await.is_err());
+    }
+}
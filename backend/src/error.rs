@@ -41,6 +41,25 @@ pub enum AppError {
     #[error("Service unavailable: {message}")]
     ServiceUnavailable { message: String },
 
+    #[error("Transaction out of gas: {message}")]
+    TxOutOfGas { message: String },
+
+    #[error("Transaction fee too low: {message}")]
+    TxInsufficientFee { message: String },
+
+    #[error("Account sequence mismatch: {message}")]
+    TxSequenceMismatch {
+        expected: Option<u64>,
+        message: String,
+    },
+
+    #[error("Transaction rejected ({codespace}/{code}): {message}")]
+    TxRejected {
+        code: u32,
+        codespace: String,
+        message: String,
+    },
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -148,6 +167,42 @@ impl IntoResponse for AppError {
                 None,
                 None,
             ),
+            AppError::TxOutOfGas { message } => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "TX_OUT_OF_GAS",
+                message.clone(),
+                None,
+                None,
+                None,
+            ),
+            AppError::TxInsufficientFee { message } => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "TX_INSUFFICIENT_FEE",
+                message.clone(),
+                None,
+                None,
+                None,
+            ),
+            AppError::TxSequenceMismatch { expected, message } => (
+                StatusCode::CONFLICT,
+                "TX_SEQUENCE_MISMATCH",
+                message.clone(),
+                None,
+                expected.map(|expected| serde_json::json!({ "expected_sequence": expected })),
+                None,
+            ),
+            AppError::TxRejected {
+                code,
+                codespace,
+                message,
+            } => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "TX_REJECTED",
+                message.clone(),
+                None,
+                Some(serde_json::json!({ "code": code, "codespace": codespace })),
+                None,
+            ),
             AppError::Internal(msg) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "INTERNAL_ERROR",
@@ -354,6 +409,53 @@ mod tests {
         assert!(msg.contains("some detail"), "message was: {msg}");
     }
 
+    #[tokio::test]
+    async fn app_error_tx_out_of_gas_and_fee_return_422() {
+        let resp = AppError::TxOutOfGas {
+            message: "gasWanted: 100, gasUsed: 200".to_string(),
+        }
+        .into_response();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = parse_json_body(resp).await;
+        assert_eq!(body["error"]["code"], "TX_OUT_OF_GAS");
+
+        let resp = AppError::TxInsufficientFee {
+            message: "got: 10unls required: 500unls".to_string(),
+        }
+        .into_response();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = parse_json_body(resp).await;
+        assert_eq!(body["error"]["code"], "TX_INSUFFICIENT_FEE");
+    }
+
+    #[tokio::test]
+    async fn app_error_tx_sequence_mismatch_returns_409_with_expected_sequence() {
+        let err = AppError::TxSequenceMismatch {
+            expected: Some(5),
+            message: "expected 5, got 4".to_string(),
+        };
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let body = parse_json_body(resp).await;
+        assert_eq!(body["error"]["code"], "TX_SEQUENCE_MISMATCH");
+        assert_eq!(body["error"]["details"]["expected_sequence"], 5);
+    }
+
+    #[tokio::test]
+    async fn app_error_tx_rejected_returns_422_with_abci_code() {
+        let err = AppError::TxRejected {
+            code: 5,
+            codespace: "sdk".to_string(),
+            message: "insufficient funds".to_string(),
+        };
+        let resp = err.into_response();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = parse_json_body(resp).await;
+        assert_eq!(body["error"]["code"], "TX_REJECTED");
+        assert_eq!(body["error"]["details"]["code"], 5);
+        assert_eq!(body["error"]["details"]["codespace"], "sdk");
+    }
+
     // ──────────────────────────────────────────────────────────────────
     // Conversion tests
     // ──────────────────────────────────────────────────────────────────
@@ -12,6 +12,7 @@ use tracing::{debug, error, warn};
 use utoipa::ToSchema;
 
 use crate::error::AppError;
+use crate::external::endpoints::{self, EndpointKind, EndpointPool};
 
 /// Maximum concurrent LCD/REST requests to the chain node.
 /// Prevents burst overload on cold start and during cache refresh cycles.
@@ -38,6 +39,9 @@ const RETRYABLE_STATUS_CODES: [u16; 2] = [429, 503];
 /// Milliseconds per second, for `Retry-After` (seconds) → millisecond conversion.
 const MS_PER_SEC: u64 = 1000;
 
+/// Request header pinning an LCD query to the state at a block height
+const QUERY_HEIGHT_HEADER: &str = "x-cosmos-block-height";
+
 /// gRPC-gateway status code the Cosmos LCD returns for a missing gov vote —
 /// the generic `InvalidArgument`, which it also returns for malformed input,
 /// so the code alone cannot classify a "hasn't voted" response.
@@ -95,10 +99,24 @@ fn rand_jitter(max: u64) -> u64 {
     nanos % max
 }
 
+/// Whether an HTTP status counts against the node that returned it: rate
+/// limiting or any server error. A node answering 500 to everything (broken
+/// app, pruned state) must lose the pin like one refusing connections.
+const fn is_node_failure(status: u16) -> bool {
+    status == 429 || status >= 500
+}
+
+/// Derive a node's CometBFT RPC URL from its LCD URL, for deployments that
+/// only configure the latter.
+fn rpc_url_from_rest(rest_url: &str) -> String {
+    rest_url.replace("/rest", "").replace("lcd", "rpc")
+}
+
 /// Client for querying Cosmos chains via RPC/REST
 #[derive(Clone)]
 pub struct ChainClient {
-    rest_url: String,
+    rest: Arc<EndpointPool>,
+    rpc: Arc<EndpointPool>,
     pub client: Client,
     query_semaphore: Arc<Semaphore>,
 }
@@ -130,26 +148,66 @@ pub struct OraclePricesResponse {
 }
 
 impl ChainClient {
+    /// Client for a single LCD node, with its RPC URL derived from it
     pub fn new(rest_url: String, client: Client) -> Self {
+        let rpc_url = rpc_url_from_rest(&rest_url);
+        Self::with_endpoints(vec![rest_url], vec![rpc_url], client)
+    }
+
+    /// Client failing over across several LCD and RPC nodes
+    pub fn with_endpoints(rest_urls: Vec<String>, rpc_urls: Vec<String>, client: Client) -> Self {
         Self {
-            rest_url,
+            rest: Arc::new(EndpointPool::new(EndpointKind::Lcd, rest_urls)),
+            rpc: Arc::new(EndpointPool::new(EndpointKind::Rpc, rpc_urls)),
             client,
             query_semaphore: Arc::new(Semaphore::new(chain_query_concurrency())),
         }
     }
 
+    /// LCD nodes behind this client
+    pub fn rest_endpoints(&self) -> &Arc<EndpointPool> {
+        &self.rest
+    }
+
+    /// RPC nodes behind this client, shared with the CometBFT event client
+    pub fn rpc_endpoints(&self) -> &Arc<EndpointPool> {
+        &self.rpc
+    }
+
+    /// Start probing every LCD and RPC node for height and latency
+    pub fn start_endpoint_probes(&self) {
+        endpoints::start_probe_task(self.rest.clone(), self.client.clone());
+        endpoints::start_probe_task(self.rpc.clone(), self.client.clone());
+    }
+
     /// Get a reference to the HTTP client for health checks
     pub fn http_client(&self) -> &Client {
         &self.client
     }
 
-    /// Execute an HTTP GET with semaphore gating, 429/503 retry, and exponential backoff.
+    /// GET `path` from the LCD nodes; see [`Self::pool_get`].
+    async fn chain_get(&self, path: &str) -> Result<reqwest::Response, AppError> {
+        self.pool_get(&self.rest, path, None).await
+    }
+
+    /// Execute an HTTP GET of `path` with semaphore gating, node failover,
+    /// 429/503 retry, and exponential backoff.
     ///
     /// - Acquires a semaphore permit (held for duration including retries)
-    /// - On 429/503: retries up to MAX_RETRIES times with exponential backoff + jitter
+    /// - Tries the pool's nodes in health order, moving on after a transport
+    ///   error, a 429 or any 5xx, and scores every outcome
+    /// - When every node answered 429/503: retries up to MAX_RETRIES times
+    ///   with exponential backoff + jitter, respecting Retry-After
     /// - On other status codes: returns the response for caller to handle
-    /// - Respects Retry-After header when present
-    async fn chain_get(&self, url: &str) -> Result<reqwest::Response, AppError> {
+    ///
+    /// With `height`, the query reads the state at that block instead of the
+    /// latest one.
+    async fn pool_get(
+        &self,
+        pool: &EndpointPool,
+        path: &str,
+        height: Option<u64>,
+    ) -> Result<reqwest::Response, AppError> {
         let _permit = self
             .query_semaphore
             .acquire()
@@ -159,18 +217,50 @@ impl ChainClient {
         let mut backoff_ms = INITIAL_BACKOFF_MS;
 
         for attempt in 0..=MAX_RETRIES {
-            let response = self
-                .client
-                .get(url)
-                .send()
-                .await
-                .map_err(|e| AppError::ChainRpc {
+            let mut failed_response = None;
+            let mut transport_error = None;
+
+            for index in pool.ordered() {
+                let url = format!("{}{}", pool.url(index), path);
+                let started = std::time::Instant::now();
+                let mut request = self.client.get(&url);
+                if let Some(height) = height {
+                    request = request.header(QUERY_HEIGHT_HEADER, height);
+                }
+                let response = match request.send().await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        pool.record_failure(index, format!("Request failed: {}", e));
+                        transport_error = Some(e);
+                        continue;
+                    }
+                };
+
+                let status = response.status().as_u16();
+                if is_node_failure(status) {
+                    pool.record_failure(index, format!("HTTP {}", status));
+                    failed_response = Some(response);
+                    continue;
+                }
+                pool.record_success(
+                    index,
+                    started.elapsed(),
+                    endpoints::response_height(response.headers()),
+                );
+                return Ok(response);
+            }
+
+            let Some(response) = failed_response else {
+                return Err(AppError::ChainRpc {
                     chain: "nolus".to_string(),
-                    message: format!("Request failed: {}", e),
-                })?;
+                    message: transport_error.map_or_else(
+                        || "No chain endpoints configured".to_string(),
+                        |e| format!("Request failed: {}", e),
+                    ),
+                });
+            };
 
             let status = response.status().as_u16();
-
             if !RETRYABLE_STATUS_CODES.contains(&status) {
                 return Ok(response);
             }
@@ -202,7 +292,7 @@ impl ChainClient {
                 attempt + 1,
                 MAX_RETRIES,
                 actual_wait,
-                url
+                path
             );
 
             tokio::time::sleep(std::time::Duration::from_millis(actual_wait)).await;
@@ -219,25 +309,96 @@ impl ChainClient {
         })
     }
 
+    /// POST a JSON `body` to `path` on the LCD nodes, in health order.
+    ///
+    /// A node that refused the connection is always skipped. Other transport
+    /// errors and 5xx responses move on to the next node only for
+    /// `idempotent` requests, since the failed node may still have processed
+    /// the request. The last HTTP response is returned for the caller to
+    /// handle.
+    async fn rest_post(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+        idempotent: bool,
+    ) -> Result<reqwest::Response, AppError> {
+        let mut transport_error = None;
+        let mut failed_response = None;
+
+        for index in self.rest.ordered() {
+            let url = format!("{}{}", self.rest.url(index), path);
+            let started = std::time::Instant::now();
+            match self.client.post(&url).json(body).send().await {
+                Ok(response) if is_node_failure(response.status().as_u16()) => {
+                    self.rest
+                        .record_failure(index, format!("HTTP {}", response.status().as_u16()));
+                    failed_response = Some(response);
+                    if !idempotent {
+                        break;
+                    }
+                }
+                Ok(response) => {
+                    self.rest.record_success(
+                        index,
+                        started.elapsed(),
+                        endpoints::response_height(response.headers()),
+                    );
+                    return Ok(response);
+                }
+                Err(e) => {
+                    self.rest
+                        .record_failure(index, format!("Request failed: {}", e));
+                    let retry_elsewhere = idempotent || e.is_connect();
+                    transport_error = Some(e);
+                    if !retry_elsewhere {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if let Some(response) = failed_response {
+            return Ok(response);
+        }
+        Err(AppError::ChainRpc {
+            chain: "nolus".to_string(),
+            message: transport_error.map_or_else(
+                || "No chain endpoints configured".to_string(),
+                |e| format!("Request failed: {}", e),
+            ),
+        })
+    }
+
     /// Query a CosmWasm contract
     async fn query_contract<T: for<'de> Deserialize<'de>>(
         &self,
         contract_address: &str,
         query_msg: serde_json::Value,
+    ) -> Result<T, AppError> {
+        self.query_contract_at(contract_address, query_msg, None)
+            .await
+    }
+
+    /// Query a CosmWasm contract, at block `height` when given
+    async fn query_contract_at<T: for<'de> Deserialize<'de>>(
+        &self,
+        contract_address: &str,
+        query_msg: serde_json::Value,
+        height: Option<u64>,
     ) -> Result<T, AppError> {
         let query_b64 = base64::Engine::encode(
             &base64::engine::general_purpose::STANDARD,
             serde_json::to_vec(&query_msg).map_err(|e| AppError::Internal(e.to_string()))?,
         );
 
-        let url = format!(
-            "{}/cosmwasm/wasm/v1/contract/{}/smart/{}",
-            self.rest_url, contract_address, query_b64
+        let path = format!(
+            "/cosmwasm/wasm/v1/contract/{}/smart/{}",
+            contract_address, query_b64
         );
 
-        debug!("Querying contract: {}", url);
+        debug!("Querying contract: {}", path);
 
-        let response = self.chain_get(&url).await?;
+        let response = self.pool_get(&self.rest, &path, height).await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -287,6 +448,19 @@ impl ChainClient {
         self.query_contract(oracle_address, query).await
     }
 
+    /// Get the stable price of `currency` as the Oracle reported it at block
+    /// `height`
+    pub async fn get_stable_price_at(
+        &self,
+        oracle_address: &str,
+        currency: &str,
+        height: u64,
+    ) -> Result<BaseCurrencyPrice, AppError> {
+        let query = json!({ "stable_price": { "currency": currency } });
+        self.query_contract_at(oracle_address, query, Some(height))
+            .await
+    }
+
     /// Get LPN (Liquidity Provider Note) ticker from LPP contract
     pub async fn get_lpn(&self, lpp_address: &str) -> Result<String, AppError> {
         // LPP contract expects empty array for parameterless queries
@@ -317,12 +491,9 @@ impl ChainClient {
     pub async fn get_all_balances(&self, address: &str) -> Result<Vec<BankBalance>, AppError> {
         // Valid bech32 is unaffected; prevents path-steering if any caller ever passes an unvalidated string.
         let encoded_address = urlencoding::encode(address);
-        let url = format!(
-            "{}/cosmos/bank/v1beta1/balances/{}",
-            self.rest_url, encoded_address
-        );
+        let path = format!("/cosmos/bank/v1beta1/balances/{}", encoded_address);
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -346,12 +517,12 @@ impl ChainClient {
 
     /// Get validators by status
     async fn get_validators_by_status(&self, status: &str) -> Result<Vec<ValidatorInfo>, AppError> {
-        let url = format!(
-            "{}/cosmos/staking/v1beta1/validators?status={}&pagination.limit=200",
-            self.rest_url, status
+        let path = format!(
+            "/cosmos/staking/v1beta1/validators?status={}&pagination.limit=200",
+            status
         );
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -388,14 +559,98 @@ impl ChainClient {
         Ok(all)
     }
 
+    /// Get the liveness record of every validator that has signed blocks
+    pub async fn get_signing_infos(&self) -> Result<Vec<SigningInfo>, AppError> {
+        let path = "/cosmos/slashing/v1beta1/signing_infos?pagination.limit=1000";
+
+        let response = self.chain_get(&path).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("HTTP {}", response.status()),
+            });
+        }
+
+        #[derive(Deserialize)]
+        struct SigningInfosResponse {
+            info: Vec<SigningInfo>,
+        }
+
+        let result: SigningInfosResponse =
+            response.json().await.map_err(|e| AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("Failed to parse signing infos: {}", e),
+            })?;
+
+        Ok(result.info)
+    }
+
+    /// Get slashing module params (liveness window)
+    pub async fn get_slashing_params(&self) -> Result<SlashingParams, AppError> {
+        let path = "/cosmos/slashing/v1beta1/params";
+
+        let response = self.chain_get(&path).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("HTTP {}", response.status()),
+            });
+        }
+
+        #[derive(Deserialize)]
+        struct SlashingParamsResponse {
+            params: SlashingParams,
+        }
+
+        let result: SlashingParamsResponse =
+            response.json().await.map_err(|e| AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("Failed to parse slashing params: {}", e),
+            })?;
+
+        Ok(result.params)
+    }
+
+    /// Get in-flight redelegations of a delegator
+    pub async fn get_redelegations(
+        &self,
+        delegator: &str,
+    ) -> Result<Vec<RedelegationResponse>, AppError> {
+        let path = format!(
+            "/cosmos/staking/v1beta1/delegators/{}/redelegations",
+            delegator
+        );
+
+        let response = self.chain_get(&path).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("HTTP {}", response.status()),
+            });
+        }
+
+        #[derive(Deserialize)]
+        struct RedelegationsResponse {
+            redelegation_responses: Vec<RedelegationResponse>,
+        }
+
+        let result: RedelegationsResponse =
+            response.json().await.map_err(|e| AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("Failed to parse redelegations: {}", e),
+            })?;
+
+        Ok(result.redelegation_responses)
+    }
+
     /// Get delegations for an address
     pub async fn get_delegations(&self, delegator: &str) -> Result<Vec<DelegationInfo>, AppError> {
-        let url = format!(
-            "{}/cosmos/staking/v1beta1/delegations/{}",
-            self.rest_url, delegator
-        );
+        let path = format!("/cosmos/staking/v1beta1/delegations/{}", delegator);
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -420,12 +675,12 @@ impl ChainClient {
 
     /// Get rewards for a delegator
     pub async fn get_rewards(&self, delegator: &str) -> Result<RewardsResponse, AppError> {
-        let url = format!(
-            "{}/cosmos/distribution/v1beta1/delegators/{}/rewards",
-            self.rest_url, delegator
+        let path = format!(
+            "/cosmos/distribution/v1beta1/delegators/{}/rewards",
+            delegator
         );
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -440,17 +695,48 @@ impl ChainClient {
         })
     }
 
+    /// Get the authz grants issued by `granter`
+    pub async fn get_granter_grants(
+        &self,
+        granter: &str,
+    ) -> Result<Vec<GrantAuthorization>, AppError> {
+        let path = format!("/cosmos/authz/v1beta1/grants/granter/{}", granter);
+
+        let response = self.chain_get(&path).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("HTTP {}", response.status()),
+            });
+        }
+
+        #[derive(Deserialize)]
+        struct GranterGrantsResponse {
+            #[serde(default)]
+            grants: Vec<GrantAuthorization>,
+        }
+
+        let result: GranterGrantsResponse =
+            response.json().await.map_err(|e| AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("Failed to parse authz grants: {}", e),
+            })?;
+
+        Ok(result.grants)
+    }
+
     /// Get unbonding delegations for a delegator
     pub async fn get_unbonding_delegations(
         &self,
         delegator: &str,
     ) -> Result<Vec<UnbondingDelegation>, AppError> {
-        let url = format!(
-            "{}/cosmos/staking/v1beta1/delegators/{}/unbonding_delegations",
-            self.rest_url, delegator
+        let path = format!(
+            "/cosmos/staking/v1beta1/delegators/{}/unbonding_delegations",
+            delegator
         );
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -516,6 +802,7 @@ impl ChainClient {
         leaser_address: &str,
         downpayment: &str,
         downpayment_ticker: &str,
+        lease_asset: Option<&str>,
         max_ltd: Option<u32>,
     ) -> Result<LeaseQuoteResponse, AppError> {
         let mut query = json!({
@@ -526,6 +813,9 @@ impl ChainClient {
                 }
             }
         });
+        if let Some(asset) = lease_asset {
+            query["quote"]["lease_asset"] = serde_json::Value::String(asset.to_string());
+        }
         if let Some(ltd) = max_ltd {
             query["quote"]["max_ltd"] = serde_json::Value::Number(ltd.into());
         }
@@ -597,12 +887,12 @@ impl ChainClient {
         limit: u32,
         reverse: bool,
     ) -> Result<ProposalsResponse, AppError> {
-        let url = format!(
-            "{}/cosmos/gov/v1/proposals?pagination.limit={}&pagination.reverse={}&pagination.countTotal=true",
-            self.rest_url, limit, reverse
+        let path = format!(
+            "/cosmos/gov/v1/proposals?pagination.limit={}&pagination.reverse={}&pagination.countTotal=true",
+            limit, reverse
         );
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -619,12 +909,9 @@ impl ChainClient {
 
     /// Get proposal tally
     pub async fn get_proposal_tally(&self, proposal_id: &str) -> Result<TallyResponse, AppError> {
-        let url = format!(
-            "{}/cosmos/gov/v1/proposals/{}/tally",
-            self.rest_url, proposal_id
-        );
+        let path = format!("/cosmos/gov/v1/proposals/{}/tally", proposal_id);
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -645,12 +932,9 @@ impl ChainClient {
         proposal_id: &str,
         voter: &str,
     ) -> Result<Option<VoteResponse>, AppError> {
-        let url = format!(
-            "{}/cosmos/gov/v1/proposals/{}/votes/{}",
-            self.rest_url, proposal_id, voter
-        );
+        let path = format!("/cosmos/gov/v1/proposals/{}/votes/{}", proposal_id, voter);
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if response.status() == reqwest::StatusCode::NOT_FOUND {
             return Ok(None);
@@ -681,9 +965,9 @@ impl ChainClient {
 
     /// Get tallying params
     pub async fn get_tallying_params(&self) -> Result<TallyingParamsResponse, AppError> {
-        let url = format!("{}/cosmos/gov/v1/params/tallying", self.rest_url);
+        let path = "/cosmos/gov/v1/params/tallying";
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -700,9 +984,9 @@ impl ChainClient {
 
     /// Get staking pool (bonded tokens)
     pub async fn get_staking_pool(&self) -> Result<StakingPoolResponse, AppError> {
-        let url = format!("{}/cosmos/staking/v1beta1/pool", self.rest_url);
+        let path = "/cosmos/staking/v1beta1/pool";
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -719,9 +1003,9 @@ impl ChainClient {
 
     /// Get annual inflation
     pub async fn get_annual_inflation(&self) -> Result<AnnualInflationResponse, AppError> {
-        let url = format!("{}/nolus/mint/v1beta1/annual_inflation", self.rest_url);
+        let path = "/nolus/mint/v1beta1/annual_inflation";
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -738,9 +1022,9 @@ impl ChainClient {
 
     /// Get staking params
     pub async fn get_staking_params(&self) -> Result<StakingParamsResponse, AppError> {
-        let url = format!("{}/cosmos/staking/v1beta1/params", self.rest_url);
+        let path = "/cosmos/staking/v1beta1/params";
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -757,9 +1041,9 @@ impl ChainClient {
 
     /// Get account info (for vesting)
     pub async fn get_account(&self, address: &str) -> Result<AccountResponse, AppError> {
-        let url = format!("{}/cosmos/auth/v1beta1/accounts/{}", self.rest_url, address);
+        let path = format!("/cosmos/auth/v1beta1/accounts/{}", address);
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -776,12 +1060,9 @@ impl ChainClient {
 
     /// Get denom metadata
     pub async fn get_denom_metadata(&self, denom: &str) -> Result<Option<DenomMetadata>, AppError> {
-        let url = format!(
-            "{}/cosmos/bank/v1beta1/denoms_metadata/{}",
-            self.rest_url, denom
-        );
+        let path = format!("/cosmos/bank/v1beta1/denoms_metadata/{}", denom);
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if response.status() == reqwest::StatusCode::NOT_FOUND {
             return Ok(None);
@@ -811,11 +1092,8 @@ impl ChainClient {
     pub async fn get_node_info(
         &self,
     ) -> Result<crate::handlers::governance::NodeInfoResponse, AppError> {
-        // Use the RPC URL for ABCI info
-        let rpc_url = self.rest_url.replace("/rest", "").replace("lcd", "rpc");
-        let url = format!("{}/abci_info", rpc_url);
-
-        let response = self.chain_get(&url).await?;
+        // ABCI info is served by the RPC nodes
+        let response = self.pool_get(&self.rpc, "/abci_info", None).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -854,11 +1132,8 @@ impl ChainClient {
     pub async fn get_network_status(
         &self,
     ) -> Result<crate::handlers::governance::NetworkStatusResponse, AppError> {
-        // Use the RPC URL for status
-        let rpc_url = self.rest_url.replace("/rest", "").replace("lcd", "rpc");
-        let url = format!("{}/status", rpc_url);
-
-        let response = self.chain_get(&url).await?;
+        // Status is served by the RPC nodes
+        let response = self.pool_get(&self.rpc, "/status", None).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -909,9 +1184,9 @@ impl ChainClient {
 
     /// Get tax module params (gas fee denoms and min prices)
     pub async fn get_tax_params(&self) -> Result<TaxParamsResponse, AppError> {
-        let url = format!("{}/nolus/tax/v2/params", self.rest_url);
+        let path = "/nolus/tax/v2/params";
 
-        let response = self.chain_get(&url).await?;
+        let response = self.chain_get(&path).await?;
 
         if !response.status().is_success() {
             return Err(AppError::ChainRpc {
@@ -925,6 +1200,186 @@ impl ChainClient {
             message: format!("Failed to parse tax params: {}", e),
         })
     }
+
+    // ========================================================================
+    // Transaction Service
+    // ========================================================================
+
+    /// Get the current sequence of an account (0 for accounts without one
+    /// yet, which the auth module reports as 404 until first funded)
+    pub async fn get_account_sequence(&self, address: &str) -> Result<u64, AppError> {
+        let path = format!("/cosmos/auth/v1beta1/accounts/{}", address);
+
+        let response = self.chain_get(&path).await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(0);
+        }
+
+        if !response.status().is_success() {
+            return Err(AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("HTTP {}", response.status()),
+            });
+        }
+
+        let account: AccountResponse = response.json().await.map_err(|e| AppError::ChainRpc {
+            chain: "nolus".to_string(),
+            message: format!("Failed to parse account: {}", e),
+        })?;
+        Ok(parse_account_sequence(&account.account).unwrap_or(0))
+    }
+
+    /// Simulate a transaction via `/cosmos/tx/v1beta1/simulate`.
+    ///
+    /// A rejected simulation is an expected outcome and comes back as
+    /// `TxSimulationOutcome::Failed` with the node's error message; only
+    /// transport and parse failures are errors.
+    pub async fn simulate_tx(&self, tx_bytes: &[u8]) -> Result<TxSimulationOutcome, AppError> {
+        use base64::Engine as _;
+
+        let _permit = self
+            .query_semaphore
+            .acquire()
+            .await
+            .map_err(|e| AppError::Internal(format!("Semaphore closed: {}", e)))?;
+
+        let body = json!({
+            "tx_bytes": base64::engine::general_purpose::STANDARD.encode(tx_bytes)
+        });
+
+        // Simulation has no side effects, so any failed node can be retried
+        let response = self
+            .rest_post("/cosmos/tx/v1beta1/simulate", &body, true)
+            .await?;
+
+        let status = response.status();
+        let payload: serde_json::Value = response.json().await.map_err(|e| AppError::ChainRpc {
+            chain: "nolus".to_string(),
+            message: format!("Failed to parse simulation response: {}", e),
+        })?;
+
+        if status.is_success() {
+            let gas_info: SimulateGasInfo = payload
+                .get("gas_info")
+                .cloned()
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| AppError::ChainRpc {
+                    chain: "nolus".to_string(),
+                    message: format!("Failed to parse gas info: {}", e),
+                })?
+                .unwrap_or_default();
+            return Ok(TxSimulationOutcome::Success {
+                gas_used: gas_info.gas_used.parse().unwrap_or(0),
+            });
+        }
+
+        // The node rejects a failing tx with `{ code, message, details }`
+        match payload.get("message").and_then(|m| m.as_str()) {
+            Some(message) => Ok(TxSimulationOutcome::Failed {
+                message: message.to_string(),
+            }),
+            None => Err(AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("HTTP {}", status),
+            }),
+        }
+    }
+
+    /// Broadcast signed tx bytes via `/cosmos/tx/v1beta1/txs` in sync mode.
+    ///
+    /// Sync mode returns once the tx has passed `CheckTx`; a non-zero `code`
+    /// in the result means the mempool rejected it. Not retried: a resubmit
+    /// of the same bytes would only fail the sequence check.
+    pub async fn broadcast_tx_sync(&self, tx_bytes: &[u8]) -> Result<TxBroadcastResult, AppError> {
+        use base64::Engine as _;
+
+        let _permit = self
+            .query_semaphore
+            .acquire()
+            .await
+            .map_err(|e| AppError::Internal(format!("Semaphore closed: {}", e)))?;
+
+        let body = json!({
+            "tx_bytes": base64::engine::general_purpose::STANDARD.encode(tx_bytes),
+            "mode": "BROADCAST_MODE_SYNC"
+        });
+
+        // Only a node that refused the connection is skipped: any other
+        // failure may have delivered the tx
+        let response = self
+            .rest_post("/cosmos/tx/v1beta1/txs", &body, false)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("HTTP {}: {}", status, body),
+            });
+        }
+
+        #[derive(Deserialize)]
+        struct BroadcastResponse {
+            tx_response: TxBroadcastResult,
+        }
+
+        let result: BroadcastResponse = response.json().await.map_err(|e| AppError::ChainRpc {
+            chain: "nolus".to_string(),
+            message: format!("Failed to parse broadcast response: {}", e),
+        })?;
+
+        Ok(result.tx_response)
+    }
+
+    /// Look up a transaction by hash. Returns `None` while it is not yet
+    /// included in a block.
+    pub async fn get_tx_result(&self, tx_hash: &str) -> Result<Option<TxResult>, AppError> {
+        let path = format!("/cosmos/tx/v1beta1/txs/{}", tx_hash);
+
+        let response = self.chain_get(&path).await?;
+
+        // Unknown hashes come back as 404 (or 400 "tx not found" on older nodes)
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            if body.contains("not found") {
+                return Ok(None);
+            }
+            return Err(AppError::ChainRpc {
+                chain: "nolus".to_string(),
+                message: format!("HTTP {}: {}", status, body),
+            });
+        }
+
+        #[derive(Deserialize)]
+        struct GetTxResponse {
+            tx_response: TxResult,
+        }
+
+        let result: GetTxResponse = response.json().await.map_err(|e| AppError::ChainRpc {
+            chain: "nolus".to_string(),
+            message: format!("Failed to parse tx: {}", e),
+        })?;
+
+        Ok(Some(result.tx_response))
+    }
+}
+
+/// Extract the sequence from an auth account, including vesting accounts
+/// whose base account is nested under `base_vesting_account`.
+pub fn parse_account_sequence(account: &serde_json::Value) -> Option<u64> {
+    account
+        .get("sequence")
+        .or_else(|| account.pointer("/base_vesting_account/base_account/sequence"))
+        .or_else(|| account.pointer("/base_account/sequence"))
+        .and_then(|v| v.as_str())
+        .and_then(|v| v.parse().ok())
 }
 
 // Additional response types
@@ -995,6 +1450,24 @@ pub struct CommissionRates {
     pub max_change_rate: String,
 }
 
+/// Liveness record of a validator, keyed by its consensus (`valcons`) address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningInfo {
+    pub address: String,
+    #[serde(default)]
+    pub missed_blocks_counter: String,
+    #[serde(default)]
+    pub tombstoned: bool,
+    #[serde(default)]
+    pub jailed_until: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashingParams {
+    pub signed_blocks_window: String,
+    pub min_signed_per_window: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DelegationInfo {
     pub delegation: Delegation,
@@ -1037,6 +1510,34 @@ pub struct UnbondingEntry {
     pub balance: String,
 }
 
+/// Redelegation from one validator to another with its entry balances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedelegationResponse {
+    pub redelegation: Redelegation,
+    pub entries: Vec<RedelegationEntryResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Redelegation {
+    pub delegator_address: String,
+    pub validator_src_address: String,
+    pub validator_dst_address: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedelegationEntryResponse {
+    pub redelegation_entry: RedelegationEntry,
+    pub balance: String,
+}
+
+/// Single redelegation entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedelegationEntry {
+    pub creation_height: String,
+    pub completion_time: String,
+    pub initial_balance: String,
+}
+
 /// Admin contract protocol response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminProtocolResponse {
@@ -1078,7 +1579,8 @@ pub struct LeasePositionSpec {
     pub min_transaction: AmountSpec,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Leaser liability thresholds, all in permille of the position value
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct LiabilitySpec {
     pub initial: u32,
     pub healthy: u32,
@@ -1401,6 +1903,70 @@ pub struct DenomUnit {
     pub aliases: Vec<String>,
 }
 
+// ============================================================================
+// Authz Types
+// ============================================================================
+
+/// One authz grant as listed by `grants/granter/{granter}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrantAuthorization {
+    pub granter: String,
+    pub grantee: String,
+    /// `Any`-encoded authorization; `GenericAuthorization` carries `msg`
+    #[serde(default)]
+    pub authorization: serde_json::Value,
+    /// RFC 3339; absent for grants without expiry
+    #[serde(default)]
+    pub expiration: Option<String>,
+}
+
+// ============================================================================
+// Transaction Service Types
+// ============================================================================
+
+/// Outcome of a transaction simulation
+#[derive(Debug, Clone, PartialEq)]
+pub enum TxSimulationOutcome {
+    Success { gas_used: u64 },
+    Failed { message: String },
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct SimulateGasInfo {
+    #[serde(default)]
+    gas_used: String,
+}
+
+/// `CheckTx` result of a sync broadcast
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TxBroadcastResult {
+    #[serde(rename = "txhash")]
+    pub tx_hash: String,
+    /// ABCI code; 0 means accepted into the mempool
+    #[serde(default)]
+    pub code: u32,
+    #[serde(default)]
+    pub codespace: String,
+    #[serde(default)]
+    pub raw_log: String,
+}
+
+/// Result of a transaction included in a block
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TxResult {
+    #[serde(rename = "txhash")]
+    pub tx_hash: String,
+    #[serde(default)]
+    pub height: String,
+    /// ABCI code; 0 means the tx executed successfully
+    #[serde(default)]
+    pub code: u32,
+    #[serde(default)]
+    pub codespace: String,
+    #[serde(default)]
+    pub raw_log: String,
+}
+
 // ============================================================================
 // Tax Module Types
 // ============================================================================
@@ -1437,7 +2003,7 @@ pub struct TaxDenomPrice {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use wiremock::matchers::{method, path, query_param};
+    use wiremock::matchers::{header, method, path, path_regex, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     async fn setup_mock_server() -> MockServer {
@@ -1462,8 +2028,7 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let url = format!("{}/always-503", mock_server.uri());
-        let result = client.chain_get(&url).await;
+        let result = client.chain_get("/always-503").await;
 
         match result {
             Err(AppError::ChainRpc { chain, message }) => {
@@ -1477,6 +2042,77 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn chain_get_fails_over_to_next_node() {
+        // The first node refuses connections and the second is overloaded;
+        // the third serves the request within the same attempt.
+        let overloaded = setup_mock_server().await;
+        let healthy = setup_mock_server().await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/bank/v1beta1/balances/nolus1testaddr"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&overloaded)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/bank/v1beta1/balances/nolus1testaddr"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("grpc-metadata-x-cosmos-block-height", "500")
+                    .set_body_json(serde_json::json!({ "balances": [] })),
+            )
+            .mount(&healthy)
+            .await;
+
+        let client = ChainClient::with_endpoints(
+            vec![
+                "http://127.0.0.1:1".to_string(),
+                overloaded.uri(),
+                healthy.uri(),
+            ],
+            Vec::new(),
+            Client::new(),
+        );
+        let balances = client.get_all_balances("nolus1testaddr").await.unwrap();
+        assert!(balances.is_empty());
+
+        let health = client.rest_endpoints().health();
+        assert_eq!(health[0].consecutive_failures, 1);
+        assert_eq!(health[1].consecutive_failures, 1);
+        assert_eq!(health[2].consecutive_failures, 0);
+        assert_eq!(health[2].height, Some(500));
+    }
+
+    #[tokio::test]
+    async fn chain_get_scores_500_as_failure_and_fails_over() {
+        let broken = setup_mock_server().await;
+        let healthy = setup_mock_server().await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/bank/v1beta1/balances/nolus1testaddr"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&broken)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/bank/v1beta1/balances/nolus1testaddr"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "balances": [] })),
+            )
+            .mount(&healthy)
+            .await;
+
+        let client = ChainClient::with_endpoints(
+            vec![broken.uri(), healthy.uri()],
+            Vec::new(),
+            Client::new(),
+        );
+        assert!(client.get_all_balances("nolus1testaddr").await.is_ok());
+
+        let health = client.rest_endpoints().health();
+        assert_eq!(health[0].consecutive_failures, 1);
+        assert_eq!(health[0].last_error.as_deref(), Some("HTTP 500"));
+    }
+
     #[tokio::test]
     async fn test_get_all_balances() {
         let mock_server = setup_mock_server().await;
@@ -1504,6 +2140,33 @@ mod tests {
         assert_eq!(balances[0].amount, "1000000");
     }
 
+    #[tokio::test]
+    async fn stable_price_at_height_pins_the_query_height() {
+        let mock_server = setup_mock_server().await;
+        let client = create_test_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path_regex(
+                r"^/cosmwasm/wasm/v1/contract/nolus1oracle/smart/",
+            ))
+            .and(header("x-cosmos-block-height", "1234"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "amount": {"ticker": "ATOM", "amount": "1000000"},
+                    "amount_quote": {"ticker": "USDC", "amount": "8000000"}
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let price = client
+            .get_stable_price_at("nolus1oracle", "ATOM", 1234)
+            .await
+            .unwrap();
+        assert_eq!(price.amount_quote.amount, "8000000");
+    }
+
     // The chain sends the new terminal state as `{"open_failed":{"reason":<str>}}`
     // (externally-tagged, snake_case). It must round-trip through the untagged
     // LeaseStatusResponse via a wrapper-struct variant carrying `open_failed.reason`
@@ -1721,6 +2384,53 @@ mod tests {
         assert_eq!(delegations[0].balance.amount, "1000000");
     }
 
+    #[tokio::test]
+    async fn test_get_redelegations() {
+        let mock_server = setup_mock_server().await;
+        let client = create_test_client(&mock_server.uri());
+
+        let response_body = serde_json::json!({
+            "redelegation_responses": [
+                {
+                    "redelegation": {
+                        "delegator_address": "nolus1testaddr",
+                        "validator_src_address": "nolusvaloper1src",
+                        "validator_dst_address": "nolusvaloper1dst",
+                        "entries": []
+                    },
+                    "entries": [
+                        {
+                            "redelegation_entry": {
+                                "creation_height": "1200",
+                                "completion_time": "2026-02-01T00:00:00Z",
+                                "initial_balance": "500000",
+                                "shares_dst": "500000.000000000000000000"
+                            },
+                            "balance": "500000"
+                        }
+                    ]
+                }
+            ],
+            "pagination": { "next_key": null, "total": "1" }
+        });
+
+        Mock::given(method("GET"))
+            .and(path(
+                "/cosmos/staking/v1beta1/delegators/nolus1testaddr/redelegations",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&response_body))
+            .mount(&mock_server)
+            .await;
+
+        let redelegations = client.get_redelegations("nolus1testaddr").await.unwrap();
+        assert_eq!(redelegations.len(), 1);
+        assert_eq!(
+            redelegations[0].redelegation.validator_dst_address,
+            "nolusvaloper1dst"
+        );
+        assert_eq!(redelegations[0].entries[0].balance, "500000");
+    }
+
     #[tokio::test]
     async fn test_balance_query_error_handling() {
         let mock_server = setup_mock_server().await;
@@ -1736,6 +2446,23 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_get_account_sequence_is_zero_for_unknown_account() {
+        let mock_server = setup_mock_server().await;
+        let client = create_test_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/cosmos/auth/v1beta1/accounts/nolus1fresh"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "code": 5,
+                "message": "account nolus1fresh not found"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        assert_eq!(client.get_account_sequence("nolus1fresh").await.unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn test_get_rewards() {
         let mock_server = setup_mock_server().await;
@@ -1872,4 +2599,144 @@ mod tests {
             other => panic!("expected ChainRpc error for oversized 400 body, got {other:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn simulate_tx_returns_gas_used() {
+        let mock_server = setup_mock_server().await;
+        let client = create_test_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .and(path("/cosmos/tx/v1beta1/simulate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "gas_info": {"gas_wanted": "0", "gas_used": "123456"},
+                "result": {"data": "", "log": "", "events": []}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.simulate_tx(b"tx").await.expect("simulation");
+        assert_eq!(outcome, TxSimulationOutcome::Success { gas_used: 123_456 });
+    }
+
+    #[tokio::test]
+    async fn broadcast_tx_sync_returns_check_tx_result() {
+        let mock_server = setup_mock_server().await;
+        let client = create_test_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .and(path("/cosmos/tx/v1beta1/txs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tx_response": {
+                    "height": "0",
+                    "txhash": "ABCDEF",
+                    "codespace": "sdk",
+                    "code": 32,
+                    "raw_log": "account sequence mismatch, expected 5, got 4: incorrect account sequence"
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let result = client.broadcast_tx_sync(b"tx").await.expect("broadcast");
+        assert_eq!(result.tx_hash, "ABCDEF");
+        assert_eq!(result.code, 32);
+        assert_eq!(result.codespace, "sdk");
+    }
+
+    #[tokio::test]
+    async fn get_tx_result_not_found_is_none() {
+        let mock_server = setup_mock_server().await;
+        let client = create_test_client(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/cosmos/tx/v1beta1/txs/MISSING"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "code": 5,
+                "message": "tx not found: MISSING",
+                "details": []
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/tx/v1beta1/txs/FOUND"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tx": {},
+                "tx_response": {"height": "42", "txhash": "FOUND", "code": 0, "raw_log": ""}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        assert!(client
+            .get_tx_result("MISSING")
+            .await
+            .expect("lookup")
+            .is_none());
+        let found = client
+            .get_tx_result("FOUND")
+            .await
+            .expect("lookup")
+            .expect("included");
+        assert_eq!(found.height, "42");
+        assert_eq!(found.code, 0);
+    }
+
+    #[tokio::test]
+    async fn simulate_tx_rejection_is_failed_outcome_not_error() {
+        let mock_server = setup_mock_server().await;
+        let client = create_test_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .and(path("/cosmos/tx/v1beta1/simulate"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "code": 2,
+                "message": "failed to execute message; message index: 0: insufficient funds",
+                "details": []
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let outcome = client.simulate_tx(b"tx").await.expect("simulation");
+        assert_eq!(
+            outcome,
+            TxSimulationOutcome::Failed {
+                message: "failed to execute message; message index: 0: insufficient funds"
+                    .to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn simulate_tx_unparseable_error_body_is_chain_error() {
+        let mock_server = setup_mock_server().await;
+        let client = create_test_client(&mock_server.uri());
+
+        Mock::given(method("POST"))
+            .and(path("/cosmos/tx/v1beta1/simulate"))
+            .respond_with(ResponseTemplate::new(502).set_body_string("bad gateway"))
+            .mount(&mock_server)
+            .await;
+
+        let result = client.simulate_tx(b"tx").await;
+        assert!(matches!(result, Err(AppError::ChainRpc { .. })));
+    }
+
+    #[test]
+    fn parse_account_sequence_handles_base_and_vesting_accounts() {
+        let base = serde_json::json!({
+            "@type": "/cosmos.auth.v1beta1.BaseAccount",
+            "account_number": "12",
+            "sequence": "7"
+        });
+        assert_eq!(parse_account_sequence(&base), Some(7));
+
+        let vesting = serde_json::json!({
+            "@type": "/cosmos.vesting.v1beta1.ContinuousVestingAccount",
+            "base_vesting_account": {
+                "base_account": {"account_number": "3", "sequence": "42"}
+            }
+        });
+        assert_eq!(parse_account_sequence(&vesting), Some(42));
+
+        assert_eq!(parse_account_sequence(&serde_json::json!({})), None);
+    }
 }
@@ -0,0 +1,582 @@
+//! Multi-endpoint failover for Nolus LCD and RPC nodes
+//!
+//! An [`EndpointPool`] holds every configured node of one kind with a rolling
+//! latency/error score, a circuit breaker and the last block height the node
+//! reported. Requests go to the pinned node while it stays healthy and within
+//! [`MAX_HEIGHT_LAG`] blocks of the best node; a failing or lagging node hands
+//! the pin to the best-scoring alternative, so a node that falls behind
+//! cannot serve stale lease state.
+//!
+//! Lag is measured by a background probe that queries every node's latest
+//! height in one round and compares only heights from that round, so an idle
+//! node is never measured against the pinned node's newer heights. The probe
+//! also keeps the scores of idle nodes current. Between rounds, the
+//! `grpc-metadata-x-cosmos-block-height` header of LCD responses and
+//! `NewBlock` events on the RPC WebSocket keep each node's reported height
+//! (and when it was seen) up to date for `/api/health/detailed`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use utoipa::ToSchema;
+
+use crate::num_utils::f64_ceil_to_u64;
+
+/// Blocks a node may trail the highest observed height before requests skip it.
+/// Nolus produces a block every ~3s, so this tolerates ~10s of propagation lag.
+pub const MAX_HEIGHT_LAG: u64 = 3;
+
+/// Consecutive failures that open a node's circuit.
+const CIRCUIT_FAILURE_THRESHOLD: u32 = 3;
+
+/// How long an open circuit keeps a node at the back of the rotation. Once it
+/// elapses the node is tried again; one failure reopens the circuit.
+const CIRCUIT_OPEN_DURATION: Duration = Duration::from_secs(30);
+
+/// Weight of the newest sample in the rolling latency and error averages.
+const EWMA_ALPHA: f64 = 0.2;
+
+/// Score multiplier for a node failing every request, relative to its latency.
+const ERROR_RATE_PENALTY: f64 = 4.0;
+
+/// How often every node is probed for height and latency.
+const PROBE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Max time a single probe may take before it counts as a failure.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Header the Cosmos LCD sets to the height a query was served at.
+const BLOCK_HEIGHT_HEADER: &str = "grpc-metadata-x-cosmos-block-height";
+
+/// Which node API a pool talks to; decides how its nodes are probed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointKind {
+    /// Cosmos REST (LCD) API
+    Lcd,
+    /// CometBFT RPC
+    Rpc,
+}
+
+impl EndpointKind {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Lcd => "LCD",
+            Self::Rpc => "RPC",
+        }
+    }
+
+    /// Cheapest query that reports the node's latest height
+    const fn probe_path(self) -> &'static str {
+        match self {
+            Self::Lcd => "/cosmos/base/tendermint/v1beta1/blocks/latest",
+            Self::Rpc => "/status",
+        }
+    }
+
+    fn probe_height(self, body: &serde_json::Value) -> Option<u64> {
+        let height = match self {
+            Self::Lcd => &body["block"]["header"]["height"],
+            Self::Rpc => &body["result"]["sync_info"]["latest_block_height"],
+        };
+        height.as_str().and_then(|h| h.parse().ok())
+    }
+}
+
+/// Health of one node as seen by the pool: `lagging` nodes trail the highest
+/// observed height by more than `MAX_HEIGHT_LAG` blocks, `circuit_open` nodes
+/// failed too often in a row and are tried only when no other node is left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointStatus {
+    Healthy,
+    Lagging,
+    CircuitOpen,
+}
+
+/// One node's entry in `/api/health/detailed`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EndpointHealth {
+    pub url: String,
+    pub status: EndpointStatus,
+    /// Whether requests currently go to this node first
+    pub pinned: bool,
+    /// Rolling average response time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Rolling share of failed requests (0-1)
+    pub error_rate: f64,
+    pub consecutive_failures: u32,
+    /// Last block height the node reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u64>,
+    /// Seconds since `height` was reported
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height_age_secs: Option<u64>,
+    /// Blocks behind the best node in the last probe round
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocks_behind: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    latency_ms: Option<f64>,
+    error_rate: f64,
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    height: Option<u64>,
+    height_at: Option<Instant>,
+    /// Set by the last probe round; `None` if the node did not answer it
+    blocks_behind: Option<u64>,
+    last_error: Option<String>,
+}
+
+impl EndpointState {
+    fn circuit_open(&self, now: Instant) -> bool {
+        self.open_until.is_some_and(|until| now < until)
+    }
+
+    fn lagging(&self) -> bool {
+        self.blocks_behind
+            .is_some_and(|behind| behind > MAX_HEIGHT_LAG)
+    }
+
+    fn status(&self, now: Instant) -> EndpointStatus {
+        if self.circuit_open(now) {
+            EndpointStatus::CircuitOpen
+        } else if self.lagging() {
+            EndpointStatus::Lagging
+        } else {
+            EndpointStatus::Healthy
+        }
+    }
+
+    /// Lower is better. Nodes without a latency sample yet score 0 so they
+    /// get tried.
+    fn score(&self) -> f64 {
+        self.latency_ms.unwrap_or(0.0) * (1.0 + ERROR_RATE_PENALTY * self.error_rate)
+    }
+}
+
+/// Scored, circuit-broken set of interchangeable nodes.
+///
+/// Each node's state sits behind its own std [`Mutex`], never held across an
+/// `.await`.
+pub struct EndpointPool {
+    kind: EndpointKind,
+    urls: Vec<String>,
+    states: Vec<Mutex<EndpointState>>,
+    pinned: AtomicUsize,
+}
+
+impl EndpointPool {
+    /// A pool over `urls` with the first one pinned. Trailing slashes are
+    /// trimmed; empty and duplicate entries are dropped.
+    pub fn new(kind: EndpointKind, urls: impl IntoIterator<Item = String>) -> Self {
+        let mut unique: Vec<String> = Vec::new();
+        for url in urls {
+            let url = url.trim().trim_end_matches('/').to_string();
+            if !url.is_empty() && !unique.contains(&url) {
+                unique.push(url);
+            }
+        }
+        let states = unique
+            .iter()
+            .map(|_| Mutex::new(EndpointState::default()))
+            .collect();
+        Self {
+            kind,
+            urls: unique,
+            states,
+            pinned: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+
+    /// Base URL of node `index`, without a trailing slash
+    pub fn url(&self, index: usize) -> &str {
+        self.urls.get(index).map_or("", String::as_str)
+    }
+
+    /// Node indices in the order a request should try them: healthy nodes,
+    /// then lagging ones, then those with an open circuit as a last resort.
+    /// Within a tier the pinned node leads and the rest follow by score. When
+    /// the pinned node drops out of the healthy tier the pin moves to the
+    /// first node of the order.
+    pub fn ordered(&self) -> Vec<usize> {
+        let pinned = self.pinned.load(Ordering::Relaxed);
+        let ranked = self.rank();
+
+        if let Some(&(status, first)) = ranked.first() {
+            if first != pinned {
+                self.pinned.store(first, Ordering::Relaxed);
+                info!(
+                    "Nolus {} failover: pinned {} ({:?}) in place of {}",
+                    self.kind.label(),
+                    self.url(first),
+                    status,
+                    self.url(pinned)
+                );
+            }
+        }
+        ranked.into_iter().map(|(_, index)| index).collect()
+    }
+
+    /// The order [`Self::ordered`] would return, without moving the pin
+    fn rank(&self) -> Vec<(EndpointStatus, usize)> {
+        let now = Instant::now();
+        let pinned = self.pinned.load(Ordering::Relaxed);
+        let mut ranked: Vec<(EndpointStatus, bool, f64, usize)> = self
+            .states
+            .iter()
+            .enumerate()
+            .map(|(index, state)| {
+                let state = lock(state);
+                (state.status(now), index != pinned, state.score(), index)
+            })
+            .collect();
+        ranked.sort_by(|a, b| {
+            tier(a.0)
+                .cmp(&tier(b.0))
+                .then(a.1.cmp(&b.1))
+                .then(a.2.total_cmp(&b.2))
+                .then(a.3.cmp(&b.3))
+        });
+        ranked
+            .into_iter()
+            .map(|(status, _, _, index)| (status, index))
+            .collect()
+    }
+
+    /// The node the next request will go to first. Read-only: the pin
+    /// itself only moves when a request is routed.
+    pub fn pinned_url(&self) -> Option<&str> {
+        self.rank().first().map(|&(_, index)| self.url(index))
+    }
+
+    /// Whether node `index` has fallen too far behind to serve requests
+    pub fn is_lagging(&self, index: usize) -> bool {
+        self.states
+            .get(index)
+            .is_some_and(|state| lock(state).lagging())
+    }
+
+    /// Record a request node `index` answered, closing its circuit
+    pub fn record_success(&self, index: usize, latency: Duration, height: Option<u64>) {
+        let Some(state) = self.states.get(index) else {
+            return;
+        };
+        {
+            let mut state = lock(state);
+            let sample = latency.as_secs_f64() * 1000.0;
+            state.latency_ms = Some(
+                state
+                    .latency_ms
+                    .map_or(sample, |avg| avg + EWMA_ALPHA * (sample - avg)),
+            );
+            state.error_rate *= 1.0 - EWMA_ALPHA;
+            state.consecutive_failures = 0;
+            state.open_until = None;
+        }
+        if let Some(height) = height {
+            self.observe_height(index, height);
+        }
+    }
+
+    /// Record a request node `index` failed, opening its circuit after
+    /// `CIRCUIT_FAILURE_THRESHOLD` failures in a row
+    pub fn record_failure(&self, index: usize, error: String) {
+        let Some(state) = self.states.get(index) else {
+            return;
+        };
+        let now = Instant::now();
+        let mut state = lock(state);
+        state.error_rate += EWMA_ALPHA * (1.0 - state.error_rate);
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.consecutive_failures >= CIRCUIT_FAILURE_THRESHOLD {
+            if !state.circuit_open(now) {
+                warn!(
+                    "Nolus {} circuit opened for {} after {} consecutive failures: {}",
+                    self.kind.label(),
+                    self.url(index),
+                    state.consecutive_failures,
+                    error
+                );
+            }
+            state.open_until = Some(now + CIRCUIT_OPEN_DURATION);
+        }
+        state.last_error = Some(error);
+    }
+
+    /// Record the latest block height node `index` reported
+    pub fn observe_height(&self, index: usize, height: u64) {
+        if let Some(state) = self.states.get(index) {
+            let mut state = lock(state);
+            state.height = Some(height);
+            state.height_at = Some(Instant::now());
+        }
+    }
+
+    /// Compare the heights one probe round collected, indexed by node.
+    /// Nodes that did not answer the round have no lag until the next one;
+    /// their failures are scored separately.
+    fn record_round(&self, heights: &[Option<u64>]) {
+        let Some(best) = heights.iter().flatten().max().copied() else {
+            return;
+        };
+        for (state, height) in self.states.iter().zip(heights) {
+            lock(state).blocks_behind = height.map(|height| best.saturating_sub(height));
+        }
+    }
+
+    /// Per-node health for `/api/health/detailed`
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        let pinned = self.rank().first().map(|&(_, index)| index);
+        let now = Instant::now();
+        self.states
+            .iter()
+            .enumerate()
+            .map(|(index, state)| {
+                let state = lock(state);
+                EndpointHealth {
+                    url: self.url(index).to_string(),
+                    status: state.status(now),
+                    pinned: pinned == Some(index),
+                    latency_ms: state.latency_ms.map(f64_ceil_to_u64),
+                    error_rate: state.error_rate,
+                    consecutive_failures: state.consecutive_failures,
+                    height: state.height,
+                    height_age_secs: state.height_at.map(|at| now.duration_since(at).as_secs()),
+                    blocks_behind: state.blocks_behind,
+                    last_error: state.last_error.clone(),
+                }
+            })
+            .collect()
+    }
+
+    /// Query every node's latest height, scoring the probe like a request,
+    /// and measure lag within the round
+    async fn probe_all(&self, client: &Client) {
+        let probes = (0..self.len()).map(|index| self.probe(client, index));
+        let heights = futures::future::join_all(probes).await;
+        self.record_round(&heights);
+    }
+
+    async fn probe(&self, client: &Client, index: usize) -> Option<u64> {
+        let url = format!("{}{}", self.url(index), self.kind.probe_path());
+        let started = Instant::now();
+        let outcome = tokio::time::timeout(PROBE_TIMEOUT, async {
+            client
+                .get(&url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<serde_json::Value>()
+                .await
+        })
+        .await;
+        let error = match outcome {
+            Ok(Ok(body)) => match self.kind.probe_height(&body) {
+                Some(height) => {
+                    debug!("Nolus {} probe: {} at {}", self.kind.label(), url, height);
+                    self.record_success(index, started.elapsed(), Some(height));
+                    return Some(height);
+                }
+                None => "probe returned no block height".to_string(),
+            },
+            Ok(Err(e)) => format!("probe failed: {}", e),
+            Err(_) => format!("probe timed out after {}s", PROBE_TIMEOUT.as_secs()),
+        };
+        self.record_failure(index, error);
+        None
+    }
+}
+
+/// Spawn the background task that probes every node of `pool` each
+/// `PROBE_INTERVAL`. A single-node pool is not probed: there is nothing to
+/// fail over to.
+pub fn start_probe_task(pool: Arc<EndpointPool>, client: Client) {
+    if pool.len() < 2 {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PROBE_INTERVAL);
+        loop {
+            interval.tick().await;
+            pool.probe_all(&client).await;
+        }
+    });
+}
+
+/// Height an LCD response was served at, from its block-height header
+pub fn response_height(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(BLOCK_HEIGHT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+const fn tier(status: EndpointStatus) -> u8 {
+    match status {
+        EndpointStatus::Healthy => 0,
+        EndpointStatus::Lagging => 1,
+        EndpointStatus::CircuitOpen => 2,
+    }
+}
+
+fn lock(state: &Mutex<EndpointState>) -> MutexGuard<'_, EndpointState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(urls: &[&str]) -> EndpointPool {
+        EndpointPool::new(EndpointKind::Lcd, urls.iter().map(|u| u.to_string()))
+    }
+
+    #[test]
+    fn new_trims_and_dedupes_urls() {
+        let pool = pool(&["https://a/", "https://a", " ", "https://b"]);
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.url(0), "https://a");
+        assert_eq!(pool.url(1), "https://b");
+    }
+
+    #[test]
+    fn pinned_node_leads_while_healthy() {
+        let pool = pool(&["https://a", "https://b"]);
+        // b is faster, but a keeps the pin while it stays healthy
+        pool.record_success(0, Duration::from_millis(200), None);
+        pool.record_success(1, Duration::from_millis(20), None);
+        assert_eq!(pool.ordered(), [0, 1]);
+    }
+
+    #[test]
+    fn open_circuit_moves_pin_to_best_scoring_node() {
+        let pool = pool(&["https://a", "https://b", "https://c"]);
+        pool.record_success(1, Duration::from_millis(200), None);
+        pool.record_success(2, Duration::from_millis(20), None);
+        for _ in 0..CIRCUIT_FAILURE_THRESHOLD {
+            pool.record_failure(0, "HTTP 503".to_string());
+        }
+        assert_eq!(pool.ordered(), [2, 1, 0]);
+        assert_eq!(pool.pinned_url(), Some("https://c"));
+
+        let health = pool.health();
+        assert_eq!(health[0].status, EndpointStatus::CircuitOpen);
+        assert!(health[2].pinned);
+    }
+
+    #[test]
+    fn health_does_not_move_the_pin() {
+        let pool = pool(&["https://a", "https://b"]);
+        for _ in 0..CIRCUIT_FAILURE_THRESHOLD {
+            pool.record_failure(0, "HTTP 500".to_string());
+        }
+        // Reporting shows where the next request goes without routing one
+        assert!(pool.health()[1].pinned);
+        assert_eq!(pool.pinned_url(), Some("https://b"));
+        assert_eq!(pool.pinned.load(Ordering::Relaxed), 0);
+
+        assert_eq!(pool.ordered(), [1, 0]);
+        assert_eq!(pool.pinned.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn success_closes_circuit() {
+        let pool = pool(&["https://a"]);
+        for _ in 0..CIRCUIT_FAILURE_THRESHOLD {
+            pool.record_failure(0, "timeout".to_string());
+        }
+        // A lone node is still offered as a last resort
+        assert_eq!(pool.ordered(), [0]);
+        pool.record_success(0, Duration::from_millis(50), None);
+        let health = pool.health();
+        assert_eq!(health[0].status, EndpointStatus::Healthy);
+        assert_eq!(health[0].consecutive_failures, 0);
+    }
+
+    #[test]
+    fn lagging_node_loses_pin() {
+        let pool = pool(&["https://a", "https://b"]);
+        pool.record_round(&[Some(100), Some(100 + MAX_HEIGHT_LAG)]);
+        assert_eq!(pool.ordered(), [0, 1]);
+
+        pool.record_round(&[Some(100), Some(101 + MAX_HEIGHT_LAG)]);
+        assert!(pool.is_lagging(0));
+        assert_eq!(pool.ordered(), [1, 0]);
+        assert_eq!(pool.health()[0].blocks_behind, Some(MAX_HEIGHT_LAG + 1));
+    }
+
+    #[test]
+    fn idle_node_is_not_measured_against_per_response_heights() {
+        let pool = pool(&["https://pinned", "https://idle"]);
+        pool.record_round(&[Some(100), Some(100)]);
+        // The pinned node keeps reporting newer heights between probes
+        pool.observe_height(0, 100 + 10 * MAX_HEIGHT_LAG);
+        assert!(!pool.is_lagging(1));
+
+        let health = pool.health();
+        assert_eq!(health[1].status, EndpointStatus::Healthy);
+        assert_eq!(health[0].height, Some(100 + 10 * MAX_HEIGHT_LAG));
+        assert_eq!(health[0].height_age_secs, Some(0));
+    }
+
+    #[test]
+    fn node_missing_from_round_has_no_lag() {
+        let pool = pool(&["https://a", "https://b"]);
+        pool.record_round(&[Some(100), Some(50)]);
+        assert!(pool.is_lagging(1));
+        pool.record_round(&[Some(110), None]);
+        assert!(!pool.is_lagging(1));
+        assert_eq!(pool.health()[1].blocks_behind, None);
+    }
+
+    #[test]
+    fn errors_outweigh_latency_in_score() {
+        let pool = pool(&["https://pinned", "https://a", "https://b"]);
+        for _ in 0..CIRCUIT_FAILURE_THRESHOLD {
+            pool.record_failure(0, "down".to_string());
+        }
+        pool.record_success(1, Duration::from_millis(40), None);
+        pool.record_failure(1, "HTTP 502".to_string());
+        pool.record_success(2, Duration::from_millis(60), None);
+        assert_eq!(pool.ordered()[0], 2);
+    }
+
+    #[test]
+    fn probe_heights_parse_per_kind() {
+        let lcd = serde_json::json!({ "block": { "header": { "height": "4242" } } });
+        let rpc =
+            serde_json::json!({ "result": { "sync_info": { "latest_block_height": "4243" } } });
+        assert_eq!(EndpointKind::Lcd.probe_height(&lcd), Some(4242));
+        assert_eq!(EndpointKind::Rpc.probe_height(&rpc), Some(4243));
+        assert_eq!(EndpointKind::Rpc.probe_height(&lcd), None);
+    }
+
+    #[test]
+    fn response_height_reads_lcd_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(response_height(&headers), None);
+        headers.insert(
+            "Grpc-Metadata-X-Cosmos-Block-Height",
+            "777".parse().unwrap(),
+        );
+        assert_eq!(response_height(&headers), Some(777));
+    }
+}
@@ -67,10 +67,21 @@ impl EtlClient {
             .await
     }
 
-    /// Fetch historical prices for charts
+    /// Fetch historical prices for one currency over the last `interval_days`
     /// Note: Current prices should be fetched from Oracle contracts on-chain
-    pub async fn fetch_price_history(&self, interval: u32) -> Result<Vec<EtlPrice>, AppError> {
-        let url = format!("{}?interval={}", self.url().endpoint("prices"), interval);
+    pub async fn fetch_price_history(
+        &self,
+        ticker: &str,
+        protocol: &str,
+        interval_days: u32,
+    ) -> Result<Vec<EtlPricePoint>, AppError> {
+        let url = format!(
+            "{}?key={}&protocol={}&interval={}",
+            self.url().endpoint("prices"),
+            urlencoding::encode(ticker),
+            urlencoding::encode(protocol),
+            interval_days
+        );
         debug!("Fetching price history from {}", url);
 
         self.client
@@ -105,6 +116,28 @@ impl EtlClient {
         Ok(response.protocols)
     }
 
+    /// Fetch supplied/borrowed history of a pool
+    pub async fn fetch_supplied_borrowed_history(
+        &self,
+        protocol: &str,
+    ) -> Result<Vec<EtlSuppliedBorrowedPoint>, AppError> {
+        let url = self
+            .url()
+            .with_query("supplied-borrowed-history", &[("protocol", protocol)]);
+        debug!("Fetching supplied/borrowed history from {}", url);
+
+        self.client
+            .get(&url)
+            .send()
+            .await
+            .with_context(API_NAME, "fetch supplied/borrowed history")
+            .await?
+            .check_status(API_NAME, "supplied/borrowed history")
+            .await?
+            .parse_json(API_NAME, "supplied/borrowed history")
+            .await
+    }
+
     /// Fetch lease opening data
     pub async fn fetch_lease_opening(
         &self,
@@ -213,6 +246,40 @@ impl EtlClient {
             .await
     }
 
+    /// Fetch lifetime lender earnings (USD) for an address
+    pub async fn fetch_earnings(&self, address: &str) -> Result<EtlEarnings, AppError> {
+        let url = self.url().with_query("earnings", &[("address", address)]);
+        debug!("Fetching earnings from {}", url);
+
+        self.client
+            .get(&url)
+            .send()
+            .await
+            .with_context(API_NAME, "fetch earnings")
+            .await?
+            .check_status(API_NAME, "earnings")
+            .await?
+            .parse_json(API_NAME, "earnings")
+            .await
+    }
+
+    /// Fetch the LPN amount paid out by an LPP withdrawal transaction
+    pub async fn fetch_lp_withdraw(&self, tx_hash: &str) -> Result<EtlLpWithdraw, AppError> {
+        let url = self.url().with_query("lp-withdraw", &[("tx", tx_hash)]);
+        debug!("Fetching LP withdraw from {}", url);
+
+        self.client
+            .get(&url)
+            .send()
+            .await
+            .with_context(API_NAME, "fetch LP withdraw")
+            .await?
+            .check_status(API_NAME, "LP withdraw")
+            .await?
+            .parse_json(API_NAME, "LP withdraw")
+            .await
+    }
+
     /// Fetch TVL
     pub async fn fetch_tvl(&self) -> Result<EtlTvlResponse, AppError> {
         let url = self.url().endpoint("total-value-locked");
@@ -297,13 +364,9 @@ pub struct EtlCurrenciesResponse {
     pub deprecated_count: u32,
 }
 
-/// Price from ETL API
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EtlPrice {
-    pub ticker: String,
-    pub amount: String,
-    // Add more fields as needed
-}
+/// Historical price point from ETL API: `[unix_millis, price_usd]`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EtlPricePoint(pub i64, pub f64);
 
 /// Pool data from ETL API
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -395,6 +458,15 @@ pub struct EtlLeaseHistoryEntry {
     pub liquidation_price: Option<String>,
 }
 
+/// Pool supplied/borrowed sample from ETL API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EtlSuppliedBorrowedPoint {
+    #[serde(alias = "lp_pool_timestamp")]
+    pub timestamp: String,
+    pub supplied: String,
+    pub borrowed: String,
+}
+
 /// PnL data point from ETL API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EtlPnlPoint {
@@ -433,6 +505,31 @@ pub struct EtlTransaction {
     pub data: Option<serde_json::Value>,
 }
 
+/// Lender earnings from ETL API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EtlEarnings {
+    /// Lifetime earnings across all pools (USD)
+    pub earnings: String,
+    #[serde(default)]
+    pub by_protocol: Vec<EtlProtocolEarnings>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EtlProtocolEarnings {
+    pub protocol: String,
+    pub earnings: String,
+}
+
+/// LPP withdrawal from ETL API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EtlLpWithdraw {
+    pub tx: String,
+    /// LPN paid out (minor units)
+    pub amount: String,
+    pub asset: String,
+    pub amount_usd: Option<String>,
+}
+
 /// TVL response from ETL API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EtlTvlResponse {
@@ -705,6 +802,58 @@ mod tests {
         assert_etl_error(&err);
     }
 
+    #[tokio::test]
+    async fn etl_fetch_supplied_borrowed_history_passes_protocol() {
+        let server = MockServer::start().await;
+        let body = serde_json::json!([
+            { "lp_pool_timestamp": "2026-01-01T00:00:00Z", "supplied": "100", "borrowed": "40" }
+        ]);
+        Mock::given(method("GET"))
+            .and(path("/api/supplied-borrowed-history"))
+            .and(query_param("protocol", "OSMOSIS-OSMOSIS-USDC_NOBLE"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&server)
+            .await;
+
+        let client = test_client(&server.uri());
+        let history = client
+            .fetch_supplied_borrowed_history("OSMOSIS-OSMOSIS-USDC_NOBLE")
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].timestamp, "2026-01-01T00:00:00Z");
+        assert_eq!(history[0].borrowed, "40");
+    }
+
+    #[tokio::test]
+    async fn etl_fetch_earnings_defaults_missing_breakdown() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/earnings"))
+            .and(query_param("address", "nolus1lender"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({ "earnings": "12.5" })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/lp-withdraw"))
+            .and(query_param("tx", "HASH"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "tx": "HASH", "amount": "1050000", "asset": "USDC_NOBLE"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = test_client(&server.uri());
+        let earnings = client.fetch_earnings("nolus1lender").await.unwrap();
+        assert_eq!(earnings.earnings, "12.5");
+        assert!(earnings.by_protocol.is_empty());
+        let withdraw = client.fetch_lp_withdraw("HASH").await.unwrap();
+        assert_eq!(withdraw.amount, "1050000");
+        assert!(withdraw.amount_usd.is_none());
+    }
+
     // ---- lease history entry: stepped-liquidation fields (#196 / #236) ----
 
     #[test]
@@ -915,19 +1064,22 @@ mod tests {
     #[tokio::test]
     async fn etl_price_history_interval_query() {
         let server = MockServer::start().await;
-        let body = serde_json::json!([
-            { "ticker": "USDC", "amount": "1.0" }
-        ]);
+        let body = serde_json::json!([[1_700_000_000_000_i64, 1.0], [1_700_000_060_000_i64, 1.01]]);
         Mock::given(method("GET"))
             .and(path("/api/prices"))
-            .and(query_param("interval", "60"))
+            .and(query_param("key", "USDC"))
+            .and(query_param("protocol", "OSMOSIS-OSMOSIS-USDC_NOBLE"))
+            .and(query_param("interval", "7"))
             .respond_with(ResponseTemplate::new(200).set_body_json(body))
             .mount(&server)
             .await;
 
         let client = test_client(&server.uri());
-        let prices = client.fetch_price_history(60).await.unwrap();
-        assert_eq!(prices.len(), 1);
-        assert_eq!(prices[0].ticker, "USDC");
+        let prices = client
+            .fetch_price_history("USDC", "OSMOSIS-OSMOSIS-USDC_NOBLE", 7)
+            .await
+            .unwrap();
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices[0], EtlPricePoint(1_700_000_000_000, 1.0));
     }
 }
@@ -1,5 +1,6 @@
 pub mod base_client;
 pub mod chain;
+pub mod endpoints;
 pub mod etl;
 pub mod intercom;
 pub mod referral;
@@ -6,6 +6,7 @@ use tracing::{debug, warn};
 use utoipa::ToSchema;
 
 use crate::error::AppError;
+use crate::external::endpoints::EndpointHealth;
 use crate::num_utils::u128_to_f64;
 use crate::AppState;
 
@@ -35,8 +36,14 @@ pub struct DetailedHealthResponse {
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ServiceHealth {
     pub etl_api: ServiceStatus,
+    /// Pinned Nolus RPC node
     pub nolus_rpc: ServiceStatus,
+    /// Pinned Nolus LCD node
     pub nolus_rest: ServiceStatus,
+    /// Every configured Nolus RPC node with its failover score
+    pub nolus_rpc_endpoints: Vec<EndpointHealth>,
+    /// Every configured Nolus LCD node with its failover score
+    pub nolus_rest_endpoints: Vec<EndpointHealth>,
     pub skip_api: ServiceStatus,
     pub referral_api: ServiceStatus,
     pub zero_interest_api: ServiceStatus,
@@ -160,6 +167,8 @@ pub async fn health_check(State(state): State<Arc<AppState>>) -> Json<HealthResp
 ///
 /// Performs live connectivity checks to upstream services (ETL, Nolus RPC/REST,
 /// Skip, referral, zero-interest) in parallel and reports cache-warm status.
+/// Nolus checks hit the pinned node; every configured node is listed with its
+/// failover status, latency, error rate and block height.
 /// Slower than `/api/health` — intended for monitoring rather than load
 /// balancer probes.
 #[utoipa::path(
@@ -248,6 +257,8 @@ pub async fn detailed_health_check(
             etl_api: etl_status,
             nolus_rpc: nolus_rpc_status,
             nolus_rest: nolus_rest_status,
+            nolus_rpc_endpoints: state.chain_client.rpc_endpoints().health(),
+            nolus_rest_endpoints: state.chain_client.rest_endpoints().health(),
             skip_api: skip_status,
             referral_api: referral_status,
             zero_interest_api: zero_interest_status,
@@ -297,12 +308,11 @@ async fn check_etl_health(state: &AppState, timeout_duration: Duration) -> Servi
     }
 }
 
-/// Check Nolus RPC health by fetching status endpoint
+/// Check Nolus RPC health by fetching the pinned node's status endpoint
 async fn check_nolus_rpc_health(state: &AppState, timeout_duration: Duration) -> ServiceStatus {
-    let url = &state.config.external.nolus_rpc_url;
-    if url.is_empty() {
+    let Some(url) = state.chain_client.rpc_endpoints().pinned_url() else {
         return ServiceStatus::not_configured();
-    }
+    };
 
     let check_url = format!("{}/status", url);
     debug!("Health check: Nolus RPC at {}", check_url);
@@ -330,12 +340,11 @@ async fn check_nolus_rpc_health(state: &AppState, timeout_duration: Duration) ->
     }
 }
 
-/// Check Nolus REST health by fetching node info
+/// Check Nolus REST health by fetching the pinned node's node info
 async fn check_nolus_rest_health(state: &AppState, timeout_duration: Duration) -> ServiceStatus {
-    let url = &state.config.external.nolus_rest_url;
-    if url.is_empty() {
+    let Some(url) = state.chain_client.rest_endpoints().pinned_url() else {
         return ServiceStatus::not_configured();
-    }
+    };
 
     let check_url = format!("{}/cosmos/base/tendermint/v1beta1/node_info", url);
     debug!("Health check: Nolus REST at {}", check_url);
@@ -592,6 +601,9 @@ pub async fn invalidate_cache(
                 "staking_pool" => {
                     tokio::spawn(async move { crate::refresh::refresh_staking_pool(&s).await });
                 }
+                "validator_scores" => {
+                    tokio::spawn(async move { crate::refresh::refresh_validator_scores(&s).await });
+                }
                 other => {
                     return Err(AppError::Validation {
                         message: format!("Unknown cache field: {}", other),
@@ -0,0 +1,238 @@
+//! Admin Key Handler
+//!
+//! Lists, mints, rotates and revokes the named admin API keys. Created and
+//! rotated keys are returned in plaintext exactly once; every change is
+//! recorded in the config audit log under the caller's identity.
+
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::admin_keys::{AdminIdentity, AdminKeyInfo, AdminScope, IssuedAdminKey};
+use crate::error::AppError;
+use crate::AppState;
+
+/// Body of `POST /api/admin/keys`
+#[derive(Debug, Deserialize)]
+pub struct CreateAdminKeyRequest {
+    pub name: String,
+    pub scopes: Vec<AdminScope>,
+}
+
+/// GET /api/admin/keys
+/// List named admin keys (never their hashes)
+pub async fn list_keys(State(state): State<Arc<AppState>>) -> Json<Vec<AdminKeyInfo>> {
+    Json(state.admin_keys.list())
+}
+
+/// POST /api/admin/keys
+/// Mint a named key with the requested scopes
+pub async fn create_key(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
+    Json(request): Json<CreateAdminKeyRequest>,
+) -> Result<Json<IssuedAdminKey>, AppError> {
+    let issued = state
+        .admin_keys
+        .create_key(&request.name, &request.scopes)
+        .await?;
+    info!(
+        "Admin: {} created admin key {}",
+        identity.name, request.name
+    );
+    state
+        .config_store
+        .record_change(
+            Some(&identity.name),
+            "create",
+            &format!("admin-keys/{}", request.name),
+            Some(format!("scopes: {:?}", issued.info.scopes)),
+            None,
+            serde_json::to_value(&issued.info).ok(),
+        )
+        .await;
+    Ok(Json(issued))
+}
+
+/// POST /api/admin/keys/:name/rotate
+/// Replace a key's secret; the old secret stops working immediately
+pub async fn rotate_key(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
+    Path(name): Path<String>,
+) -> Result<Json<IssuedAdminKey>, AppError> {
+    let before = key_snapshot(&state, &name);
+    let issued = state.admin_keys.rotate_key(&name).await?;
+    info!("Admin: {} rotated admin key {}", identity.name, name);
+    state
+        .config_store
+        .record_change(
+            Some(&identity.name),
+            "rotate",
+            &format!("admin-keys/{name}"),
+            None,
+            before,
+            serde_json::to_value(&issued.info).ok(),
+        )
+        .await;
+    Ok(Json(issued))
+}
+
+/// DELETE /api/admin/keys/:name
+/// Revoke a named key
+pub async fn revoke_key(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
+    Path(name): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let before = key_snapshot(&state, &name);
+    state.admin_keys.revoke_key(&name).await?;
+    info!("Admin: {} revoked admin key {}", identity.name, name);
+    state
+        .config_store
+        .record_change(
+            Some(&identity.name),
+            "revoke",
+            &format!("admin-keys/{name}"),
+            None,
+            before,
+            None,
+        )
+        .await;
+    Ok(Json(serde_json::json!({ "revoked": name })))
+}
+
+/// A key's listing (never its hash) as the audit `before` payload
+fn key_snapshot(state: &AppState, name: &str) -> Option<serde_json::Value> {
+    state
+        .admin_keys
+        .list()
+        .into_iter()
+        .find(|info| info.name == name)
+        .and_then(|info| serde_json::to_value(info).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_store::storage::AuditLogQuery;
+    use crate::middleware::{admin_auth_middleware, require_admin_scope};
+    use crate::test_utils::{collect_body_str, test_app_state_with_config, test_config_with_admin};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        middleware::from_fn_with_state,
+        routing::post,
+        Router,
+    };
+    use tower::ServiceExt;
+
+    async fn keys_router() -> (Router, Arc<AppState>) {
+        let state = test_app_state_with_config(test_config_with_admin(true, "root-key")).await;
+        let router = Router::new()
+            .route("/api/admin/keys", post(create_key).get(list_keys))
+            .route("/api/admin/keys/{name}/rotate", post(rotate_key))
+            .route_layer(from_fn_with_state(AdminScope::Keys, require_admin_scope))
+            .layer(from_fn_with_state(state.clone(), admin_auth_middleware))
+            .with_state(state.clone());
+        (router, state)
+    }
+
+    fn request(uri: &str, token: &str, body: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn root_mints_key_and_audit_records_identity() {
+        let (app, state) = keys_router().await;
+        let resp = app
+            .oneshot(request(
+                "/api/admin/keys",
+                "root-key",
+                r#"{"name":"translator","scopes":["translations"]}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let issued: serde_json::Value =
+            serde_json::from_str(&collect_body_str(resp).await).unwrap();
+        let key = issued["key"].as_str().unwrap();
+        assert_eq!(issued["name"], "translator");
+        assert_eq!(
+            state.admin_keys.identify(key).unwrap().scopes,
+            vec![AdminScope::Translations]
+        );
+
+        let audit = state
+            .config_store
+            .query_audit_log(AuditLogQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(audit.entries[0].actor.as_deref(), Some("root"));
+        assert_eq!(audit.entries[0].resource, "admin-keys/translator");
+        assert_eq!(
+            audit.entries[0].after.as_ref().unwrap()["name"],
+            "translator"
+        );
+    }
+
+    #[tokio::test]
+    async fn rotation_takes_effect_without_restart() {
+        let (app, state) = keys_router().await;
+        let old = state
+            .admin_keys
+            .create_key("ops", &[AdminScope::Keys])
+            .await
+            .unwrap();
+
+        // A key-scoped identity may rotate its own key.
+        let resp = app
+            .clone()
+            .oneshot(request("/api/admin/keys/ops/rotate", &old.key, ""))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let rotated: serde_json::Value =
+            serde_json::from_str(&collect_body_str(resp).await).unwrap();
+
+        let stale = app
+            .clone()
+            .oneshot(request("/api/admin/keys/ops/rotate", &old.key, ""))
+            .await
+            .unwrap();
+        assert_eq!(stale.status(), StatusCode::UNAUTHORIZED);
+
+        let fresh = rotated["key"].as_str().unwrap();
+        assert!(state.admin_keys.identify(fresh).is_some());
+    }
+
+    #[tokio::test]
+    async fn key_without_keys_scope_cannot_mint() {
+        let (app, state) = keys_router().await;
+        let issued = state
+            .admin_keys
+            .create_key("translator", &[AdminScope::Translations])
+            .await
+            .unwrap();
+        let resp = app
+            .oneshot(request(
+                "/api/admin/keys",
+                &issued.key,
+                r#"{"name":"escalated","scopes":["gated_config"]}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        assert_eq!(state.admin_keys.list().len(), 1);
+    }
+}
@@ -0,0 +1,396 @@
+//! Admin Audit Handler
+//!
+//! A single query endpoint over both hash-chained audit logs (config and
+//! translations), with JSONL and CSV export, and an integrity check that
+//! walks both chains.
+
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::audit::{AuditRow, AuditSource, ChainVerification, CSV_HEADER};
+use crate::config_store::storage::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT};
+use crate::error::AppError;
+use crate::AppState;
+
+/// Response format of `GET /api/admin/audit`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditFormat {
+    /// Paginated JSON, newest first
+    #[default]
+    Json,
+    /// Every matching entry as a JSONL attachment, oldest first
+    Jsonl,
+    /// Every matching entry as a CSV attachment, oldest first
+    Csv,
+}
+
+/// Query parameters for `GET /api/admin/audit`
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditQuery {
+    /// Restrict to one log; both when omitted
+    pub source: Option<AuditSource>,
+    pub actor: Option<String>,
+    /// Substring match
+    pub action: Option<String>,
+    /// Substring match
+    pub resource: Option<String>,
+    pub request_id: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub limit: usize,
+    #[serde(default)]
+    pub format: AuditFormat,
+}
+
+impl AuditQuery {
+    fn matches(&self, row: &AuditRow) -> bool {
+        self.source.is_none_or(|source| row.source == source)
+            && self
+                .actor
+                .as_ref()
+                .is_none_or(|actor| row.actor.as_ref() == Some(actor))
+            && self
+                .action
+                .as_ref()
+                .is_none_or(|action| row.action.contains(action.as_str()))
+            && self
+                .resource
+                .as_ref()
+                .is_none_or(|resource| row.resource.contains(resource.as_str()))
+            && self
+                .request_id
+                .as_ref()
+                .is_none_or(|id| row.request_id.as_ref() == Some(id))
+            && self.from.is_none_or(|from| row.timestamp >= from)
+            && self.to.is_none_or(|to| row.timestamp <= to)
+    }
+}
+
+/// Paginated audit entries from both logs
+#[derive(Debug, Serialize)]
+pub struct AuditQueryResponse {
+    pub entries: Vec<AuditRow>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+/// Integrity of both audit chains
+#[derive(Debug, Serialize)]
+pub struct AuditVerifyResponse {
+    pub valid: bool,
+    pub config: ChainVerification,
+    pub translations: ChainVerification,
+}
+
+/// GET /api/admin/audit
+/// Query or export the config and translation audit logs
+pub async fn query_audit(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AuditQuery>,
+) -> Result<Response, AppError> {
+    let mut rows: Vec<AuditRow> = collect_rows(&state, query.from)
+        .await?
+        .into_iter()
+        .filter(|row| query.matches(row))
+        .collect();
+
+    match query.format {
+        AuditFormat::Json => {
+            rows.reverse();
+            let total = rows.len();
+            let limit = if query.limit == 0 {
+                DEFAULT_PAGE_LIMIT
+            } else {
+                query.limit.min(MAX_PAGE_LIMIT)
+            };
+            let offset = query.offset.min(total);
+            let entries = rows.into_iter().skip(offset).take(limit).collect();
+            Ok(Json(AuditQueryResponse {
+                entries,
+                total,
+                offset,
+                limit,
+            })
+            .into_response())
+        }
+        AuditFormat::Jsonl => export_jsonl(&rows),
+        AuditFormat::Csv => Ok(export_csv(&rows)),
+    }
+}
+
+fn export_jsonl(rows: &[AuditRow]) -> Result<Response, AppError> {
+    let mut body = String::new();
+    for row in rows {
+        let line = serde_json::to_string(row)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize audit entry: {}", e)))?;
+        body.push_str(&line);
+        body.push('\n');
+    }
+    Ok(attachment("application/x-ndjson", "audit.jsonl", body))
+}
+
+fn export_csv(rows: &[AuditRow]) -> Response {
+    let mut body = format!("{CSV_HEADER}\n");
+    for row in rows {
+        body.push_str(&row.to_csv_line());
+        body.push('\n');
+    }
+    attachment("text/csv; charset=utf-8", "audit.csv", body)
+}
+
+/// GET /api/admin/audit/verify
+/// Walk both audit chains and report the first broken link, if any
+pub async fn verify_audit(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<AuditVerifyResponse>, AppError> {
+    let config = state.config_store.audit().verify().await?;
+    let translations = state
+        .translation_storage
+        .audit_log()
+        .chain()
+        .verify()
+        .await?;
+    Ok(Json(AuditVerifyResponse {
+        valid: config.valid && translations.valid,
+        config,
+        translations,
+    }))
+}
+
+/// Entries from both chains, oldest first. Segments written before `from`
+/// are not read.
+async fn collect_rows(
+    state: &AppState,
+    from: Option<DateTime<Utc>>,
+) -> Result<Vec<AuditRow>, AppError> {
+    let mut rows: Vec<AuditRow> = state
+        .config_store
+        .audit()
+        .read_since(from)
+        .await?
+        .iter()
+        .map(|chained| chained.to_row())
+        .collect();
+    rows.extend(
+        state
+            .translation_storage
+            .audit_log()
+            .chain()
+            .read_since(from)
+            .await?
+            .iter()
+            .map(|chained| chained.to_row()),
+    );
+    rows.sort_by(|a, b| {
+        a.timestamp
+            .cmp(&b.timestamp)
+            .then_with(|| a.source.as_str().cmp(b.source.as_str()))
+            .then_with(|| a.seq.cmp(&b.seq))
+    });
+    Ok(rows)
+}
+
+fn attachment(content_type: &'static str, filename: &str, body: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::{admin_auth_middleware, request_id_middleware, REQUEST_ID_HEADER};
+    use crate::test_utils::{collect_body_str, test_app_state_with_config, test_config_with_admin};
+    use crate::translations::TranslationSource;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        middleware::{from_fn, from_fn_with_state},
+        routing::get,
+        Router,
+    };
+    use tower::ServiceExt;
+
+    async fn audit_router() -> (Router, Arc<AppState>) {
+        let state = test_app_state_with_config(test_config_with_admin(true, "root-key")).await;
+        let router = Router::new()
+            .route("/api/admin/audit", get(query_audit))
+            .route("/api/admin/audit/verify", get(verify_audit))
+            .layer(from_fn_with_state(state.clone(), admin_auth_middleware))
+            .layer(from_fn(request_id_middleware))
+            .with_state(state.clone());
+        (router, state)
+    }
+
+    async fn get_body(app: Router, uri: &str) -> (StatusCode, String) {
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .header("Authorization", "Bearer root-key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        (resp.status(), collect_body_str(resp).await)
+    }
+
+    async fn seed(state: &AppState) {
+        state
+            .config_store
+            .record_change(
+                Some("root"),
+                "update",
+                "gated/ui-settings",
+                Some("version 1".to_string()),
+                None,
+                Some(serde_json::json!({ "hidden_proposals": ["7"] })),
+            )
+            .await;
+        state
+            .translation_storage
+            .audit_log()
+            .record_approve(
+                Some("translator".to_string()),
+                "ru",
+                "message.hello",
+                "Привет",
+                TranslationSource::AiGenerated,
+                None,
+            )
+            .await;
+    }
+
+    #[tokio::test]
+    async fn query_merges_both_logs_newest_first() {
+        let (app, state) = audit_router().await;
+        seed(&state).await;
+
+        let (status, body) = get_body(app.clone(), "/api/admin/audit").await;
+        assert_eq!(status, StatusCode::OK);
+        let response: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(response["total"], 2);
+        assert_eq!(response["entries"][0]["source"], "translations");
+        assert_eq!(
+            response["entries"][0]["resource"],
+            "translations/ru/message.hello"
+        );
+        assert_eq!(response["entries"][1]["source"], "config");
+
+        let (_, body) = get_body(app, "/api/admin/audit?source=config&actor=root").await;
+        let response: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(response["total"], 1);
+        assert_eq!(response["entries"][0]["after"]["hidden_proposals"][0], "7");
+    }
+
+    #[tokio::test]
+    async fn admin_writes_carry_request_id_and_ip() {
+        let mut config = test_config_with_admin(true, "root-key");
+        config.admin.trusted_proxies = vec!["10.0.0.2".parse().unwrap()];
+        let state = test_app_state_with_config(config).await;
+        let app = Router::new()
+            .route(
+                "/api/admin/touch",
+                axum::routing::post(|State(state): State<Arc<AppState>>| async move {
+                    state
+                        .config_store
+                        .record_audit(Some("root"), "touch", "test", None)
+                        .await
+                }),
+            )
+            .layer(from_fn_with_state(state.clone(), admin_auth_middleware))
+            .layer(from_fn(request_id_middleware))
+            .with_state(state.clone());
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/admin/touch")
+                    .header("Authorization", "Bearer root-key")
+                    .header(REQUEST_ID_HEADER, "req-123")
+                    .header("x-forwarded-for", "198.51.100.1, 203.0.113.9")
+                    .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+                        [10, 0, 0, 2],
+                        443,
+                    ))))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let rows = collect_rows(&state, None).await.unwrap();
+        assert_eq!(rows[0].request_id.as_deref(), Some("req-123"));
+        assert_eq!(rows[0].ip.as_deref(), Some("203.0.113.9"));
+    }
+
+    #[tokio::test]
+    async fn exports_csv_and_jsonl_attachments() {
+        let (app, state) = audit_router().await;
+        seed(&state).await;
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/admin/audit?format=csv")
+                    .header("Authorization", "Bearer root-key")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            resp.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"audit.csv\""
+        );
+        let csv = collect_body_str(resp).await;
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with("config,1,"));
+        assert!(lines[2].starts_with("translations,1,"));
+
+        let (_, jsonl) = get_body(app, "/api/admin/audit?format=jsonl&source=translations").await;
+        let rows: Vec<serde_json::Value> = jsonl
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["action"], "approve");
+    }
+
+    #[tokio::test]
+    async fn verify_reports_both_chains() {
+        let (app, state) = audit_router().await;
+        seed(&state).await;
+
+        let (status, body) = get_body(app, "/api/admin/audit/verify").await;
+        assert_eq!(status, StatusCode::OK);
+        let report: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(report["valid"], true);
+        assert_eq!(report["config"]["entries"], 1);
+        assert_eq!(report["translations"]["last_seq"], 1);
+    }
+}
@@ -0,0 +1,1384 @@
+//! Cosmos transaction simulation and broadcast relay
+//!
+//! Endpoints:
+//! - POST /api/tx/simulate - Simulate unsigned Nolus messages and estimate gas
+//! - POST /api/tx/broadcast - Relay signed tx bytes to the configured node
+//!
+//! Accepts the JSON messages produced by the lease/earn/staking/governance
+//! builders, encodes them to protobuf with `cosmrs`, and runs them through the LCD
+//! `cosmos/tx/v1beta1/simulate` endpoint. A failing simulation is returned as a
+//! decoded error rather than an HTTP failure, so the wallet preview can show
+//! the reason next to the transaction.
+//!
+//! Broadcasts use sync mode; mempool rejections are mapped to typed
+//! `AppError`s and accepted hashes are tracked by the `WebSocketManager`, so
+//! `tx_status` subscribers get the confirmation without polling.
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::Json;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use cosmrs::proto::cosmos::authz::v1beta1::{GenericAuthorization, Grant, MsgGrant, MsgRevoke};
+use cosmrs::proto::cosmos::base::v1beta1::Coin;
+use cosmrs::proto::cosmos::crypto::secp256k1::PubKey;
+use cosmrs::proto::cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward;
+use cosmrs::proto::cosmos::staking::v1beta1::{MsgBeginRedelegate, MsgDelegate, MsgUndelegate};
+use cosmrs::proto::cosmos::tx::signing::v1beta1::SignMode;
+use cosmrs::proto::cosmos::tx::v1beta1::{
+    mode_info, AuthInfo, Fee, ModeInfo, SignerInfo, Tx, TxBody,
+};
+use cosmrs::proto::cosmwasm::wasm::v1::MsgExecuteContract;
+use cosmrs::proto::tendermint::google::protobuf::Timestamp;
+use cosmrs::Any;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use utoipa::ToSchema;
+
+use crate::error::AppError;
+use crate::external::chain::TxSimulationOutcome;
+use crate::handlers::tx_builder::{self, GasEstimate};
+use crate::num_utils::{f64_ceil_to_u64, u128_to_f64};
+use crate::AppState;
+
+/// Gas multiplier applied to simulated gas when no gas config is cached yet
+const DEFAULT_GAS_MULTIPLIER: f64 = 1.5;
+
+const SECP256K1_PUBKEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+
+const GENERIC_AUTHORIZATION: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
+
+/// Cosmos SDK ABCI error codes (codespace `sdk`) mapped to dedicated errors
+const SDK_CODESPACE: &str = "sdk";
+const ABCI_CODE_OUT_OF_GAS: u32 = 11;
+const ABCI_CODE_INSUFFICIENT_FEE: u32 = 13;
+const ABCI_CODE_WRONG_SEQUENCE: u32 = 32;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SimulateTxRequest {
+    /// Address that will sign the transaction
+    pub signer: String,
+    /// Unsigned messages as returned by the transaction builders
+    #[schema(value_type = Vec<Object>)]
+    pub messages: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub memo: String,
+    /// Base64 secp256k1 public key of the signer; the node substitutes a
+    /// placeholder key when omitted
+    #[serde(default)]
+    pub public_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimulateTxResponse {
+    pub success: bool,
+    /// Gas consumed by the simulation
+    pub gas_used: Option<u64>,
+    /// Recommended gas limit (gas used × multiplier) and the matching fees
+    pub gas: Option<GasEstimate>,
+    /// Decoded failure reason when the simulation was rejected
+    pub error: Option<TxError>,
+}
+
+/// Decoded transaction failure
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct TxError {
+    pub kind: TxErrorKind,
+    /// Index of the failing message, when the node reports one
+    pub message_index: Option<u32>,
+    /// Failure reason with the SDK boilerplate stripped
+    pub reason: String,
+    /// Raw error message returned by the node
+    pub raw: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BroadcastTxRequest {
+    /// Base64-encoded signed `TxRaw` bytes
+    pub tx_bytes: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BroadcastTxResponse {
+    pub tx_hash: String,
+    /// Chain the tx was submitted to; subscribe to the `tx_status` topic with
+    /// `hash` and `chain_id` to receive the confirmation
+    pub chain_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TxErrorKind {
+    OutOfGas,
+    InsufficientFunds,
+    InsufficientFee,
+    SequenceMismatch,
+    ContractError,
+    Unknown,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Simulate a transaction
+///
+/// Encodes the given unsigned messages into a transaction for `signer`,
+/// simulates it against the Nolus node and returns the gas used with a fee
+/// recommendation from the cached gas config. A rejected simulation returns
+/// `success: false` with the decoded error.
+#[utoipa::path(
+    post,
+    path = "/api/tx/simulate",
+    tag = "tx",
+    request_body = SimulateTxRequest,
+    responses(
+        (status = 200, description = "Simulation result", body = SimulateTxResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 502, description = "Chain node unreachable", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn simulate_tx(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SimulateTxRequest>,
+) -> Result<Json<SimulateTxResponse>, AppError> {
+    debug!(
+        "Simulating {} message(s) for {}",
+        request.messages.len(),
+        request.signer
+    );
+
+    crate::validation::validate_nolus_address(&request.signer, "signer")?;
+    let messages = encode_messages(&request.messages, &request.signer)?;
+    let public_key = request
+        .public_key
+        .as_deref()
+        .map(decode_public_key)
+        .transpose()?;
+
+    let sequence = state
+        .chain_client
+        .get_account_sequence(&request.signer)
+        .await?;
+    let tx_bytes = build_unsigned_tx(messages, &request.memo, sequence, public_key, None);
+
+    let response = match state.chain_client.simulate_tx(&tx_bytes).await? {
+        TxSimulationOutcome::Success { gas_used } => {
+            let fee_config = state.data_cache.gas_fee_config.load();
+            let multiplier = fee_config
+                .as_ref()
+                .map(|c| c.gas_multiplier)
+                .filter(|m| *m > 0.0)
+                .unwrap_or(DEFAULT_GAS_MULTIPLIER);
+            let gas_limit = f64_ceil_to_u64(u128_to_f64(u128::from(gas_used)) * multiplier);
+
+            SimulateTxResponse {
+                success: true,
+                gas_used: Some(gas_used),
+                gas: Some(GasEstimate {
+                    gas_limit,
+                    fee_options: tx_builder::fee_options(gas_limit, fee_config.as_ref()),
+                }),
+                error: None,
+            }
+        }
+        TxSimulationOutcome::Failed { message } => SimulateTxResponse {
+            success: false,
+            gas_used: None,
+            gas: None,
+            error: Some(decode_tx_error(&message)),
+        },
+    };
+
+    Ok(Json(response))
+}
+
+/// Broadcast a signed transaction
+///
+/// Submits the signed tx bytes to the configured Nolus node in sync mode and
+/// registers the resulting hash for `tx_status` WebSocket updates. Mempool
+/// rejections for out of gas, insufficient fees and sequence mismatches come
+/// back as dedicated error codes.
+#[utoipa::path(
+    post,
+    path = "/api/tx/broadcast",
+    tag = "tx",
+    request_body = BroadcastTxRequest,
+    responses(
+        (status = 200, description = "Transaction accepted into the mempool", body = BroadcastTxResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 409, description = "Account sequence mismatch", body = crate::error::ErrorResponse),
+        (status = 422, description = "Transaction rejected by the node", body = crate::error::ErrorResponse),
+        (status = 502, description = "Chain node unreachable", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn broadcast_tx(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<BroadcastTxRequest>,
+) -> Result<Json<BroadcastTxResponse>, AppError> {
+    let tx_bytes = BASE64
+        .decode(request.tx_bytes.trim())
+        .ok()
+        .filter(|bytes| !bytes.is_empty())
+        .ok_or_else(|| AppError::Validation {
+            message: "tx_bytes must be non-empty base64".to_string(),
+            field: Some("tx_bytes".to_string()),
+            details: None,
+        })?;
+
+    // Resolved before submitting so a lookup failure never hides an accepted tx
+    let chain_id = nolus_chain_id(&state).await?;
+
+    let result = state.chain_client.broadcast_tx_sync(&tx_bytes).await?;
+    if result.code != 0 {
+        warn!(
+            "Broadcast tx {} rejected ({}/{}): {}",
+            result.tx_hash, result.codespace, result.code, result.raw_log
+        );
+        return Err(abci_error(result.code, &result.codespace, &result.raw_log));
+    }
+
+    info!("Broadcast tx {} accepted on {}", result.tx_hash, chain_id);
+    state
+        .ws_manager
+        .register_broadcast_tx(&result.tx_hash, &chain_id);
+
+    Ok(Json(BroadcastTxResponse {
+        tx_hash: result.tx_hash,
+        chain_id,
+    }))
+}
+
+/// Chain id of the native network, from the cached app config or the node
+async fn nolus_chain_id(state: &AppState) -> Result<String, AppError> {
+    let cached = state.data_cache.app_config.load().and_then(|config| {
+        config
+            .networks
+            .into_iter()
+            .find(|network| network.native)
+            .map(|network| network.chain_id)
+    });
+
+    match cached {
+        Some(chain_id) => Ok(chain_id),
+        None => Ok(state.chain_client.get_network_status().await?.network),
+    }
+}
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+/// Encode builder messages to protobuf `Any`, checking each is signed by `signer`
+pub fn encode_messages(messages: &[serde_json::Value], signer: &str) -> Result<Vec<Any>, AppError> {
+    if messages.is_empty() {
+        return Err(AppError::Validation {
+            message: "At least one message is required".to_string(),
+            field: Some("messages".to_string()),
+            details: None,
+        });
+    }
+
+    messages
+        .iter()
+        .enumerate()
+        .map(|(index, msg)| encode_message(msg, Some(signer), index))
+        .collect()
+}
+
+/// One builder message being encoded, for field lookup and error context
+struct MsgFields<'a> {
+    msg: &'a serde_json::Value,
+    /// Address every signer field must match. `None` for the messages inside
+    /// a proposal, which the gov module executes rather than the signer.
+    signer: Option<&'a str>,
+    index: usize,
+}
+
+impl MsgFields<'_> {
+    fn invalid(&self, message: String) -> AppError {
+        AppError::Validation {
+            message: format!("messages[{}]: {}", self.index, message),
+            field: Some("messages".to_string()),
+            details: None,
+        }
+    }
+
+    fn field(&self, name: &str) -> Result<String, AppError> {
+        self.msg
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| self.invalid(format!("missing `{}`", name)))
+    }
+
+    fn signed_by(&self, name: &str) -> Result<String, AppError> {
+        let value = self.field(name)?;
+        match self.signer {
+            Some(signer) if value != signer => Err(self.invalid(format!(
+                "`{}` is {}, expected signer {}",
+                name, value, signer
+            ))),
+            _ => Ok(value),
+        }
+    }
+
+    fn coin(&self, name: &str) -> Result<Coin, AppError> {
+        parse_coin(self.msg.get(name), name).map_err(|e| self.invalid(e))
+    }
+
+    fn coins(&self, name: &str) -> Result<Vec<Coin>, AppError> {
+        parse_coins(self.msg.get(name), name).map_err(|e| self.invalid(e))
+    }
+
+    /// Optional `metadata`, empty when absent
+    fn metadata(&self) -> String {
+        self.msg
+            .get("metadata")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn proposal_id(&self) -> Result<u64, AppError> {
+        self.field("proposal_id")?
+            .parse()
+            .map_err(|_| self.invalid("`proposal_id` must be an unsigned integer".to_string()))
+    }
+}
+
+fn encode_message(
+    msg: &serde_json::Value,
+    signer: Option<&str>,
+    index: usize,
+) -> Result<Any, AppError> {
+    let fields = MsgFields { msg, signer, index };
+    let type_url = msg
+        .get("@type")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| fields.invalid("missing `@type`".to_string()))?;
+
+    let encoded = if type_url.starts_with("/cosmos.gov.v1.") {
+        encode_gov_message(&fields, type_url)?
+    } else if type_url.starts_with("/cosmos.authz.v1beta1.") {
+        encode_authz_message(&fields, type_url)?
+    } else {
+        encode_core_message(&fields, type_url)?
+    };
+    let value =
+        encoded.ok_or_else(|| fields.invalid(format!("unsupported message type {}", type_url)))?;
+
+    Ok(Any {
+        type_url: type_url.to_string(),
+        value,
+    })
+}
+
+/// Contract execution, staking and distribution messages
+fn encode_core_message(
+    fields: &MsgFields<'_>,
+    type_url: &str,
+) -> Result<Option<Vec<u8>>, AppError> {
+    let value = match type_url {
+        "/cosmwasm.wasm.v1.MsgExecuteContract" => MsgExecuteContract {
+            sender: fields.signed_by("sender")?,
+            contract: fields.field("contract")?,
+            msg: serde_json::to_vec(fields.msg.get("msg").unwrap_or(&serde_json::Value::Null))
+                .map_err(|e| fields.invalid(format!("unserializable `msg`: {}", e)))?,
+            funds: fields.coins("funds")?,
+        }
+        .encode_to_vec(),
+        "/cosmos.staking.v1beta1.MsgDelegate" => MsgDelegate {
+            delegator_address: fields.signed_by("delegator_address")?,
+            validator_address: fields.field("validator_address")?,
+            amount: Some(fields.coin("amount")?),
+        }
+        .encode_to_vec(),
+        "/cosmos.staking.v1beta1.MsgUndelegate" => MsgUndelegate {
+            delegator_address: fields.signed_by("delegator_address")?,
+            validator_address: fields.field("validator_address")?,
+            amount: Some(fields.coin("amount")?),
+        }
+        .encode_to_vec(),
+        "/cosmos.staking.v1beta1.MsgBeginRedelegate" => MsgBeginRedelegate {
+            delegator_address: fields.signed_by("delegator_address")?,
+            validator_src_address: fields.field("validator_src_address")?,
+            validator_dst_address: fields.field("validator_dst_address")?,
+            amount: Some(fields.coin("amount")?),
+        }
+        .encode_to_vec(),
+        "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward" => MsgWithdrawDelegatorReward {
+            delegator_address: fields.signed_by("delegator_address")?,
+            validator_address: fields.field("validator_address")?,
+        }
+        .encode_to_vec(),
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+/// `cosmos.gov.v1` votes, deposits and proposal submissions
+fn encode_gov_message(fields: &MsgFields<'_>, type_url: &str) -> Result<Option<Vec<u8>>, AppError> {
+    let value = match type_url {
+        "/cosmos.gov.v1.MsgVote" => gov_v1::MsgVote {
+            proposal_id: fields.proposal_id()?,
+            voter: fields.signed_by("voter")?,
+            option: vote_option(fields, fields.msg.get("option"))?,
+            metadata: fields.metadata(),
+        }
+        .encode_to_vec(),
+        "/cosmos.gov.v1.MsgVoteWeighted" => gov_v1::MsgVoteWeighted {
+            proposal_id: fields.proposal_id()?,
+            voter: fields.signed_by("voter")?,
+            options: weighted_vote_options(fields)?,
+            metadata: fields.metadata(),
+        }
+        .encode_to_vec(),
+        "/cosmos.gov.v1.MsgDeposit" => gov_v1::MsgDeposit {
+            proposal_id: fields.proposal_id()?,
+            depositor: fields.signed_by("depositor")?,
+            amount: fields.coins("amount")?,
+        }
+        .encode_to_vec(),
+        "/cosmos.gov.v1.MsgSubmitProposal" => gov_v1::MsgSubmitProposal {
+            messages: proposal_messages(fields)?,
+            initial_deposit: fields.coins("initial_deposit")?,
+            proposer: fields.signed_by("proposer")?,
+            metadata: fields.metadata(),
+            title: fields.field("title")?,
+            summary: fields.field("summary")?,
+            expedited: fields
+                .msg
+                .get("expedited")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+        }
+        .encode_to_vec(),
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+/// A `cosmos.gov.v1.VoteOption` given by its enum name
+fn vote_option(fields: &MsgFields<'_>, value: Option<&serde_json::Value>) -> Result<i32, AppError> {
+    match value.and_then(|v| v.as_str()) {
+        Some("VOTE_OPTION_YES") => Ok(1),
+        Some("VOTE_OPTION_ABSTAIN") => Ok(2),
+        Some("VOTE_OPTION_NO") => Ok(3),
+        Some("VOTE_OPTION_NO_WITH_VETO") => Ok(4),
+        other => Err(fields.invalid(format!("invalid vote option {:?}", other))),
+    }
+}
+
+fn weighted_vote_options(
+    fields: &MsgFields<'_>,
+) -> Result<Vec<gov_v1::WeightedVoteOption>, AppError> {
+    let Some(serde_json::Value::Array(options)) = fields.msg.get("options") else {
+        return Err(fields.invalid("`options` must be an array".to_string()));
+    };
+    options
+        .iter()
+        .map(|option| {
+            Ok(gov_v1::WeightedVoteOption {
+                option: vote_option(fields, option.get("option"))?,
+                weight: option
+                    .get("weight")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| fields.invalid("vote option without `weight`".to_string()))?
+                    .to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Encode the messages a proposal executes once passed. They run as the gov
+/// module, so their signer fields are not checked against the proposer.
+fn proposal_messages(fields: &MsgFields<'_>) -> Result<Vec<Any>, AppError> {
+    let messages = match fields.msg.get("messages") {
+        None | Some(serde_json::Value::Null) => return Ok(Vec::new()),
+        Some(serde_json::Value::Array(messages)) => messages,
+        Some(_) => return Err(fields.invalid("`messages` must be an array".to_string())),
+    };
+    messages
+        .iter()
+        .enumerate()
+        .map(|(index, msg)| {
+            encode_message(msg, None, index).map_err(|e| match e {
+                AppError::Validation { message, .. } => {
+                    fields.invalid(format!("proposal {}", message))
+                }
+                other => other,
+            })
+        })
+        .collect()
+}
+
+/// `cosmos.authz.v1beta1` grants and revocations
+fn encode_authz_message(
+    fields: &MsgFields<'_>,
+    type_url: &str,
+) -> Result<Option<Vec<u8>>, AppError> {
+    let value = match type_url {
+        "/cosmos.authz.v1beta1.MsgGrant" => MsgGrant {
+            granter: fields.signed_by("granter")?,
+            grantee: fields.field("grantee")?,
+            grant: Some(authz_grant(fields)?),
+        }
+        .encode_to_vec(),
+        "/cosmos.authz.v1beta1.MsgRevoke" => MsgRevoke {
+            granter: fields.signed_by("granter")?,
+            grantee: fields.field("grantee")?,
+            msg_type_url: fields.field("msg_type_url")?,
+        }
+        .encode_to_vec(),
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+/// The `grant` of a `MsgGrant`. Only `GenericAuthorization` is built by the
+/// staking handlers, so other authorization types are rejected.
+fn authz_grant(fields: &MsgFields<'_>) -> Result<Grant, AppError> {
+    let grant = fields
+        .msg
+        .get("grant")
+        .ok_or_else(|| fields.invalid("missing `grant`".to_string()))?;
+    let authorization = grant
+        .get("authorization")
+        .ok_or_else(|| fields.invalid("missing `grant.authorization`".to_string()))?;
+    let authorization_type = authorization.get("@type").and_then(|v| v.as_str());
+    if authorization_type != Some(GENERIC_AUTHORIZATION) {
+        return Err(fields.invalid(format!(
+            "unsupported authorization type {:?}",
+            authorization_type
+        )));
+    }
+    let msg = authorization
+        .get("msg")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| fields.invalid("missing `grant.authorization.msg`".to_string()))?;
+
+    Ok(Grant {
+        authorization: Some(Any {
+            type_url: GENERIC_AUTHORIZATION.to_string(),
+            value: GenericAuthorization {
+                msg: msg.to_string(),
+            }
+            .encode_to_vec(),
+        }),
+        expiration: grant_expiration(fields, grant.get("expiration"))?,
+    })
+}
+
+/// An optional RFC 3339 grant expiry as a protobuf timestamp
+fn grant_expiration(
+    fields: &MsgFields<'_>,
+    value: Option<&serde_json::Value>,
+) -> Result<Option<Timestamp>, AppError> {
+    let Some(raw) = value.and_then(|v| v.as_str()) else {
+        return Ok(None);
+    };
+    let at = chrono::DateTime::parse_from_rfc3339(raw)
+        .map_err(|_| fields.invalid(format!("invalid grant expiration {}", raw)))?;
+    Ok(Some(Timestamp {
+        seconds: at.timestamp(),
+        nanos: i32::try_from(at.timestamp_subsec_nanos()).unwrap_or(0),
+    }))
+}
+
+fn parse_coin(value: Option<&serde_json::Value>, name: &str) -> Result<Coin, String> {
+    let value = value.ok_or_else(|| format!("missing `{}`", name))?;
+    let denom = value
+        .get("denom")
+        .and_then(|v| v.as_str())
+        .filter(|d| !d.is_empty())
+        .ok_or_else(|| "coin without `denom`".to_string())?;
+    let amount = value
+        .get("amount")
+        .and_then(|v| v.as_str())
+        .filter(|a| a.parse::<u128>().is_ok())
+        .ok_or_else(|| format!("invalid amount for {}", denom))?;
+    Ok(Coin {
+        denom: denom.to_string(),
+        amount: amount.to_string(),
+    })
+}
+
+fn parse_coins(value: Option<&serde_json::Value>, name: &str) -> Result<Vec<Coin>, String> {
+    match value {
+        None | Some(serde_json::Value::Null) => Ok(Vec::new()),
+        Some(serde_json::Value::Array(coins)) => coins
+            .iter()
+            .map(|coin| parse_coin(Some(coin), name))
+            .collect(),
+        Some(_) => Err(format!("`{}` must be an array", name)),
+    }
+}
+
+fn decode_public_key(encoded: &str) -> Result<Any, AppError> {
+    let key = BASE64
+        .decode(encoded)
+        .map_err(|_err| AppError::Validation {
+            message: "public_key must be base64".to_string(),
+            field: Some("public_key".to_string()),
+            details: None,
+        })?;
+    Ok(Any {
+        type_url: SECP256K1_PUBKEY_TYPE_URL.to_string(),
+        value: PubKey { key }.encode_to_vec(),
+    })
+}
+
+/// Assemble a single-signer `SIGN_MODE_DIRECT` transaction with an empty
+/// signature slot. Without a fee the node treats it as a simulation candidate.
+pub fn build_unsigned_tx(
+    messages: Vec<Any>,
+    memo: &str,
+    sequence: u64,
+    public_key: Option<Any>,
+    fee: Option<Fee>,
+) -> Vec<u8> {
+    let signer_info = SignerInfo {
+        public_key,
+        mode_info: Some(ModeInfo {
+            sum: Some(mode_info::Sum::Single(mode_info::Single {
+                mode: i32::from(SignMode::Direct),
+            })),
+        }),
+        sequence,
+    };
+
+    Tx {
+        body: Some(TxBody {
+            messages,
+            memo: memo.to_string(),
+            ..Default::default()
+        }),
+        auth_info: Some(AuthInfo {
+            signer_infos: vec![signer_info],
+            fee: Some(fee.unwrap_or_default()),
+            ..Default::default()
+        }),
+        signatures: vec![Vec::new()],
+    }
+    .encode_to_vec()
+}
+
+// ============================================================================
+// Error decoding
+// ============================================================================
+
+/// Decode an SDK error message such as
+/// `failed to execute message; message index: 0: Insufficient balance: execute wasm contract failed`
+pub fn decode_tx_error(raw: &str) -> TxError {
+    const INDEX_MARKER: &str = "message index: ";
+
+    let (message_index, remainder) = match raw.find(INDEX_MARKER) {
+        Some(pos) => {
+            let after = &raw[pos + INDEX_MARKER.len()..];
+            let digits: String = after.chars().take_while(char::is_ascii_digit).collect();
+            let rest = after[digits.len()..].trim_start_matches(':').trim();
+            (digits.parse().ok(), rest)
+        }
+        None => (None, raw.trim()),
+    };
+
+    let reason = remainder
+        .trim_end_matches(": execute wasm contract failed")
+        .trim()
+        .to_string();
+
+    TxError {
+        kind: classify_tx_error(raw),
+        message_index,
+        reason,
+        raw: raw.to_string(),
+    }
+}
+
+/// Map a non-zero ABCI result of a broadcast to a typed error
+pub fn abci_error(code: u32, codespace: &str, raw_log: &str) -> AppError {
+    let message = decode_tx_error(raw_log).reason;
+    match (codespace, code) {
+        (SDK_CODESPACE, ABCI_CODE_OUT_OF_GAS) => AppError::TxOutOfGas { message },
+        (SDK_CODESPACE, ABCI_CODE_INSUFFICIENT_FEE) => AppError::TxInsufficientFee { message },
+        (SDK_CODESPACE, ABCI_CODE_WRONG_SEQUENCE) => AppError::TxSequenceMismatch {
+            expected: parse_expected_sequence(raw_log),
+            message,
+        },
+        _ => AppError::TxRejected {
+            code,
+            codespace: codespace.to_string(),
+            message,
+        },
+    }
+}
+
+/// Extract `N` from `account sequence mismatch, expected N, got M`
+fn parse_expected_sequence(raw_log: &str) -> Option<u64> {
+    let after = raw_log.split("expected ").nth(1)?;
+    after
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>()
+        .parse()
+        .ok()
+}
+
+fn classify_tx_error(raw: &str) -> TxErrorKind {
+    let lower = raw.to_lowercase();
+    if lower.contains("out of gas") {
+        TxErrorKind::OutOfGas
+    } else if lower.contains("insufficient fee") {
+        TxErrorKind::InsufficientFee
+    } else if lower.contains("account sequence mismatch")
+        || lower.contains("incorrect account sequence")
+    {
+        TxErrorKind::SequenceMismatch
+    } else if lower.contains("insufficient funds") {
+        TxErrorKind::InsufficientFunds
+    } else if lower.contains("execute wasm contract failed") {
+        TxErrorKind::ContractError
+    } else {
+        TxErrorKind::Unknown
+    }
+}
+
+/// `cosmos.gov.v1` messages, declared from the SDK protos because the
+/// `cosmos-sdk-proto` bundled with `cosmrs` predates `expedited`, which the
+/// governance builders emit.
+mod gov_v1 {
+    use cosmrs::proto::cosmos::base::v1beta1::Coin;
+    use cosmrs::Any;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgVote {
+        #[prost(uint64, tag = "1")]
+        pub proposal_id: u64,
+        #[prost(string, tag = "2")]
+        pub voter: String,
+        #[prost(int32, tag = "3")]
+        pub option: i32,
+        #[prost(string, tag = "4")]
+        pub metadata: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct WeightedVoteOption {
+        #[prost(int32, tag = "1")]
+        pub option: i32,
+        /// Decimal string, e.g. `0.500000000000000000`
+        #[prost(string, tag = "2")]
+        pub weight: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgVoteWeighted {
+        #[prost(uint64, tag = "1")]
+        pub proposal_id: u64,
+        #[prost(string, tag = "2")]
+        pub voter: String,
+        #[prost(message, repeated, tag = "3")]
+        pub options: Vec<WeightedVoteOption>,
+        #[prost(string, tag = "4")]
+        pub metadata: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgDeposit {
+        #[prost(uint64, tag = "1")]
+        pub proposal_id: u64,
+        #[prost(string, tag = "2")]
+        pub depositor: String,
+        #[prost(message, repeated, tag = "3")]
+        pub amount: Vec<Coin>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgSubmitProposal {
+        #[prost(message, repeated, tag = "1")]
+        pub messages: Vec<Any>,
+        #[prost(message, repeated, tag = "2")]
+        pub initial_deposit: Vec<Coin>,
+        #[prost(string, tag = "3")]
+        pub proposer: String,
+        #[prost(string, tag = "4")]
+        pub metadata: String,
+        #[prost(string, tag = "5")]
+        pub title: String,
+        #[prost(string, tag = "6")]
+        pub summary: String,
+        #[prost(bool, tag = "7")]
+        pub expedited: bool,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{collect_body_str, test_app_state_with_config_and_client, test_config};
+    use axum::{body::Body, http::Request, http::StatusCode, routing::post, Router};
+    use serde_json::json;
+    use tower::ServiceExt;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const SIGNER: &str = "nolus1qg5ega6dykkxc307y25pecuufrjkxkaggkkxh7nad0vhyhtuhw3sqaa3c5";
+
+    fn execute_msg(sender: &str) -> serde_json::Value {
+        json!({
+            "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
+            "sender": sender,
+            "contract": "nolus1contract",
+            "msg": {"repay": {}},
+            "funds": [{"denom": "ibc/USDC", "amount": "1000"}]
+        })
+    }
+
+    #[test]
+    fn encode_execute_contract_round_trips() {
+        let encoded = encode_messages(&[execute_msg(SIGNER)], SIGNER).expect("encode");
+        assert_eq!(encoded.len(), 1);
+        assert_eq!(encoded[0].type_url, "/cosmwasm.wasm.v1.MsgExecuteContract");
+
+        let decoded = MsgExecuteContract::decode(encoded[0].value.as_slice()).expect("decode");
+        assert_eq!(decoded.sender, SIGNER);
+        assert_eq!(decoded.contract, "nolus1contract");
+        assert_eq!(decoded.msg, br#"{"repay":{}}"#.to_vec());
+        assert_eq!(decoded.funds[0].denom, "ibc/USDC");
+        assert_eq!(decoded.funds[0].amount, "1000");
+    }
+
+    #[test]
+    fn encode_staking_messages() {
+        let messages = vec![
+            json!({
+                "@type": "/cosmos.staking.v1beta1.MsgDelegate",
+                "delegator_address": SIGNER,
+                "validator_address": "nolusvaloper1abc",
+                "amount": {"denom": "unls", "amount": "5"}
+            }),
+            json!({
+                "@type": "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+                "delegator_address": SIGNER,
+                "validator_address": "nolusvaloper1abc"
+            }),
+        ];
+        let encoded = encode_messages(&messages, SIGNER).expect("encode");
+        let delegate = MsgDelegate::decode(encoded[0].value.as_slice()).expect("decode");
+        assert_eq!(delegate.amount.expect("amount").amount, "5");
+        assert_eq!(
+            encoded[1].type_url,
+            "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward"
+        );
+    }
+
+    #[test]
+    fn encode_rejects_foreign_signer_and_unknown_types() {
+        let err = encode_messages(&[execute_msg("nolus1someoneelse")], SIGNER)
+            .expect_err("foreign signer");
+        assert!(
+            matches!(err, AppError::Validation { ref message, .. } if message.contains("messages[0]"))
+        );
+
+        let err = encode_messages(
+            &[json!({"@type": "/cosmos.bank.v1beta1.MsgMultiSend"})],
+            SIGNER,
+        )
+        .expect_err("unsupported type");
+        assert!(
+            matches!(err, AppError::Validation { ref message, .. } if message.contains("unsupported"))
+        );
+
+        assert!(encode_messages(&[], SIGNER).is_err());
+    }
+
+    #[test]
+    fn encode_governance_votes_and_deposits() {
+        let messages = vec![
+            json!({
+                "@type": "/cosmos.gov.v1.MsgVote",
+                "proposal_id": "7",
+                "voter": SIGNER,
+                "option": "VOTE_OPTION_NO_WITH_VETO",
+                "metadata": ""
+            }),
+            json!({
+                "@type": "/cosmos.gov.v1.MsgVoteWeighted",
+                "proposal_id": "7",
+                "voter": SIGNER,
+                "options": [
+                    {"option": "VOTE_OPTION_YES", "weight": "0.700000000000000000"},
+                    {"option": "VOTE_OPTION_ABSTAIN", "weight": "0.300000000000000000"}
+                ],
+                "metadata": "note"
+            }),
+            json!({
+                "@type": "/cosmos.gov.v1.MsgDeposit",
+                "proposal_id": "7",
+                "depositor": SIGNER,
+                "amount": [{"denom": "unls", "amount": "1000"}]
+            }),
+        ];
+        let encoded = encode_messages(&messages, SIGNER).expect("encode");
+
+        let vote = gov_v1::MsgVote::decode(encoded[0].value.as_slice()).expect("decode");
+        assert_eq!(vote.proposal_id, 7);
+        assert_eq!(vote.option, 4);
+
+        let weighted =
+            gov_v1::MsgVoteWeighted::decode(encoded[1].value.as_slice()).expect("decode");
+        assert_eq!(weighted.options.len(), 2);
+        assert_eq!(weighted.options[0].option, 1);
+        assert_eq!(weighted.options[1].weight, "0.300000000000000000");
+        assert_eq!(weighted.metadata, "note");
+
+        let deposit = gov_v1::MsgDeposit::decode(encoded[2].value.as_slice()).expect("decode");
+        assert_eq!(deposit.depositor, SIGNER);
+        assert_eq!(deposit.amount[0].amount, "1000");
+    }
+
+    #[test]
+    fn encode_submit_proposal_with_nested_messages() {
+        let proposal = json!({
+            "@type": "/cosmos.gov.v1.MsgSubmitProposal",
+            "messages": [execute_msg("nolus10d07y265gmmuvt4z0w9aw880jnsr700jmq3jzm")],
+            "initial_deposit": [{"denom": "unls", "amount": "5000"}],
+            "proposer": SIGNER,
+            "metadata": "",
+            "title": "Upgrade",
+            "summary": "Upgrade the contract",
+            "expedited": true
+        });
+        let encoded = encode_messages(&[proposal], SIGNER).expect("encode");
+
+        let submit =
+            gov_v1::MsgSubmitProposal::decode(encoded[0].value.as_slice()).expect("decode");
+        assert_eq!(submit.proposer, SIGNER);
+        assert_eq!(submit.title, "Upgrade");
+        assert!(submit.expedited);
+        assert_eq!(submit.initial_deposit[0].amount, "5000");
+        assert_eq!(
+            submit.messages[0].type_url,
+            "/cosmwasm.wasm.v1.MsgExecuteContract"
+        );
+        let inner =
+            MsgExecuteContract::decode(submit.messages[0].value.as_slice()).expect("decode");
+        assert_eq!(inner.contract, "nolus1contract");
+    }
+
+    #[test]
+    fn encode_governance_rejects_bad_fields() {
+        let vote = |proposal_id: &str, option: &str| {
+            json!({
+                "@type": "/cosmos.gov.v1.MsgVote",
+                "proposal_id": proposal_id,
+                "voter": SIGNER,
+                "option": option
+            })
+        };
+        assert!(encode_messages(&[vote("seven", "VOTE_OPTION_YES")], SIGNER).is_err());
+        assert!(encode_messages(&[vote("7", "VOTE_OPTION_MAYBE")], SIGNER).is_err());
+
+        let err = encode_messages(
+            &[json!({
+                "@type": "/cosmos.gov.v1.MsgSubmitProposal",
+                "messages": [{"@type": "/cosmos.bank.v1beta1.MsgMultiSend"}],
+                "initial_deposit": [],
+                "proposer": SIGNER,
+                "title": "t",
+                "summary": "s"
+            })],
+            SIGNER,
+        )
+        .expect_err("unsupported nested message");
+        assert!(
+            matches!(err, AppError::Validation { ref message, .. } if message.contains("proposal messages[0]"))
+        );
+    }
+
+    #[test]
+    fn encode_authz_grant_and_revoke() {
+        let messages = vec![
+            json!({
+                "@type": "/cosmos.authz.v1beta1.MsgGrant",
+                "granter": SIGNER,
+                "grantee": "nolus1bot",
+                "grant": {
+                    "authorization": {
+                        "@type": GENERIC_AUTHORIZATION,
+                        "msg": "/cosmos.staking.v1beta1.MsgDelegate"
+                    },
+                    "expiration": "2027-01-01T00:00:00Z"
+                }
+            }),
+            json!({
+                "@type": "/cosmos.authz.v1beta1.MsgRevoke",
+                "granter": SIGNER,
+                "grantee": "nolus1bot",
+                "msg_type_url": "/cosmos.staking.v1beta1.MsgDelegate"
+            }),
+        ];
+        let encoded = encode_messages(&messages, SIGNER).expect("encode");
+
+        let grant = MsgGrant::decode(encoded[0].value.as_slice()).expect("decode");
+        assert_eq!(grant.grantee, "nolus1bot");
+        let grant = grant.grant.expect("grant");
+        assert_eq!(grant.expiration.expect("expiration").seconds, 1_798_761_600);
+        let authorization = grant.authorization.expect("authorization");
+        assert_eq!(authorization.type_url, GENERIC_AUTHORIZATION);
+        let generic = GenericAuthorization::decode(authorization.value.as_slice()).expect("decode");
+        assert_eq!(generic.msg, "/cosmos.staking.v1beta1.MsgDelegate");
+
+        let revoke = MsgRevoke::decode(encoded[1].value.as_slice()).expect("decode");
+        assert_eq!(revoke.msg_type_url, "/cosmos.staking.v1beta1.MsgDelegate");
+    }
+
+    #[test]
+    fn encode_authz_rejects_other_authorizations() {
+        let grant = json!({
+            "@type": "/cosmos.authz.v1beta1.MsgGrant",
+            "granter": SIGNER,
+            "grantee": "nolus1bot",
+            "grant": {
+                "authorization": {
+                    "@type": "/cosmos.bank.v1beta1.SendAuthorization",
+                    "spend_limit": []
+                }
+            }
+        });
+        let err = encode_messages(&[grant], SIGNER).expect_err("send authorization");
+        assert!(
+            matches!(err, AppError::Validation { ref message, .. } if message.contains("unsupported authorization"))
+        );
+    }
+
+    #[test]
+    fn build_unsigned_tx_has_one_empty_signature_and_sequence() {
+        let messages = encode_messages(&[execute_msg(SIGNER)], SIGNER).expect("encode");
+        let bytes = build_unsigned_tx(messages, "memo", 9, None, None);
+
+        let tx = Tx::decode(bytes.as_slice()).expect("decode tx");
+        assert_eq!(tx.signatures, vec![Vec::<u8>::new()]);
+        assert_eq!(tx.body.expect("body").memo, "memo");
+        let auth = tx.auth_info.expect("auth info");
+        assert_eq!(auth.signer_infos[0].sequence, 9);
+        assert!(auth.signer_infos[0].public_key.is_none());
+    }
+
+    #[test]
+    fn decode_tx_error_extracts_index_and_reason() {
+        let err = decode_tx_error(
+            "failed to execute message; message index: 0: Insufficient balance: execute wasm contract failed",
+        );
+        assert_eq!(err.kind, TxErrorKind::ContractError);
+        assert_eq!(err.message_index, Some(0));
+        assert_eq!(err.reason, "Insufficient balance");
+    }
+
+    #[test]
+    fn decode_tx_error_classifies_sdk_errors() {
+        assert_eq!(
+            decode_tx_error(
+                "out of gas in location: WriteFlat; gasWanted: 100, gasUsed: 200: out of gas"
+            )
+            .kind,
+            TxErrorKind::OutOfGas
+        );
+        assert_eq!(
+            decode_tx_error(
+                "account sequence mismatch, expected 5, got 4: incorrect account sequence"
+            )
+            .kind,
+            TxErrorKind::SequenceMismatch
+        );
+        assert_eq!(
+            decode_tx_error("insufficient fees; got: 10unls required: 500unls: insufficient fee")
+                .kind,
+            TxErrorKind::InsufficientFee
+        );
+        assert_eq!(
+            decode_tx_error("failed to execute message; message index: 1: 5unls is smaller than 10unls: insufficient funds").kind,
+            TxErrorKind::InsufficientFunds
+        );
+        let unknown = decode_tx_error("something else");
+        assert_eq!(unknown.kind, TxErrorKind::Unknown);
+        assert_eq!(unknown.message_index, None);
+        assert_eq!(unknown.reason, "something else");
+    }
+
+    #[test]
+    fn abci_error_maps_common_sdk_codes() {
+        assert!(matches!(
+            abci_error(
+                11,
+                "sdk",
+                "out of gas in location: ReadFlat; gasWanted: 10, gasUsed: 20: out of gas"
+            ),
+            AppError::TxOutOfGas { .. }
+        ));
+        assert!(matches!(
+            abci_error(
+                13,
+                "sdk",
+                "insufficient fees; got: 1unls required: 5unls: insufficient fee"
+            ),
+            AppError::TxInsufficientFee { .. }
+        ));
+        assert!(matches!(
+            abci_error(
+                32,
+                "sdk",
+                "account sequence mismatch, expected 7, got 6: incorrect account sequence"
+            ),
+            AppError::TxSequenceMismatch {
+                expected: Some(7),
+                ..
+            }
+        ));
+        match abci_error(
+            5,
+            "wasm",
+            "failed to execute message; message index: 0: boom: execute wasm contract failed",
+        ) {
+            AppError::TxRejected {
+                code,
+                codespace,
+                message,
+            } => {
+                assert_eq!(code, 5);
+                assert_eq!(codespace, "wasm");
+                assert_eq!(message, "boom");
+            }
+            other => panic!("expected TxRejected, got {other:?}"),
+        }
+    }
+
+    async fn state_with_chain(server: &MockServer) -> Arc<AppState> {
+        let mut config = test_config();
+        config.external.nolus_rest_url = server.uri();
+        test_app_state_with_config_and_client(config, reqwest::Client::new()).await
+    }
+
+    async fn mount_account(server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path(format!("/cosmos/auth/v1beta1/accounts/{}", SIGNER)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "account": {
+                    "@type": "/cosmos.auth.v1beta1.BaseAccount",
+                    "address": SIGNER,
+                    "account_number": "1",
+                    "sequence": "3"
+                }
+            })))
+            .mount(server)
+            .await;
+    }
+
+    async fn mount_node_status(server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/status"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "result": {
+                    "node_info": {"network": "pirin-1"},
+                    "sync_info": {
+                        "latest_block_height": "100",
+                        "latest_block_time": "2026-01-01T00:00:00Z",
+                        "catching_up": false
+                    }
+                }
+            })))
+            .mount(server)
+            .await;
+    }
+
+    async fn mount_broadcast(server: &MockServer, code: u32, raw_log: &str) {
+        Mock::given(method("POST"))
+            .and(path("/cosmos/tx/v1beta1/txs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "tx_response": {
+                    "height": "0",
+                    "txhash": "A1B2C3",
+                    "codespace": if code == 0 { "" } else { "sdk" },
+                    "code": code,
+                    "raw_log": raw_log
+                }
+            })))
+            .mount(server)
+            .await;
+    }
+
+    fn app(state: Arc<AppState>) -> Router {
+        Router::new()
+            .route("/api/tx/simulate", post(simulate_tx))
+            .route("/api/tx/broadcast", post(broadcast_tx))
+            .with_state(state)
+    }
+
+    fn broadcast_request(tx_bytes: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/api/tx/broadcast")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "tx_bytes": tx_bytes }).to_string()))
+            .unwrap()
+    }
+
+    fn simulate_request(body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/api/tx/simulate")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn simulate_tx_success_returns_gas_and_fee() {
+        let server = MockServer::start().await;
+        mount_account(&server).await;
+        Mock::given(method("POST"))
+            .and(path("/cosmos/tx/v1beta1/simulate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "gas_info": {"gas_wanted": "0", "gas_used": "100000"}
+            })))
+            .mount(&server)
+            .await;
+
+        let state = state_with_chain(&server).await;
+        let mut gas_prices = std::collections::HashMap::new();
+        gas_prices.insert("unls".to_string(), "0.5".to_string());
+        state
+            .data_cache
+            .gas_fee_config
+            .store(crate::handlers::fees::GasFeeConfigResponse {
+                gas_prices,
+                gas_multiplier: 2.0,
+            });
+
+        let resp = app(state)
+            .oneshot(simulate_request(json!({
+                "signer": SIGNER,
+                "messages": [execute_msg(SIGNER)]
+            })))
+            .await
+            .expect("router call");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value =
+            serde_json::from_str(&collect_body_str(resp).await).expect("json");
+        assert_eq!(body["success"], true);
+        assert_eq!(body["gas_used"], 100_000);
+        assert_eq!(body["gas"]["gas_limit"], 200_000);
+        assert_eq!(body["gas"]["fee_options"][0]["amount"], "100000");
+    }
+
+    #[tokio::test]
+    async fn simulate_tx_failure_returns_decoded_error() {
+        let server = MockServer::start().await;
+        mount_account(&server).await;
+        Mock::given(method("POST"))
+            .and(path("/cosmos/tx/v1beta1/simulate"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "code": 2,
+                "message": "failed to execute message; message index: 0: The lease is closed: execute wasm contract failed",
+                "details": []
+            })))
+            .mount(&server)
+            .await;
+
+        let resp = app(state_with_chain(&server).await)
+            .oneshot(simulate_request(json!({
+                "signer": SIGNER,
+                "messages": [execute_msg(SIGNER)]
+            })))
+            .await
+            .expect("router call");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value =
+            serde_json::from_str(&collect_body_str(resp).await).expect("json");
+        assert_eq!(body["success"], false);
+        assert_eq!(body["error"]["kind"], "contract_error");
+        assert_eq!(body["error"]["reason"], "The lease is closed");
+    }
+
+    #[tokio::test]
+    async fn simulate_tx_invalid_signer_returns_400() {
+        let server = MockServer::start().await;
+        let resp = app(state_with_chain(&server).await)
+            .oneshot(simulate_request(json!({
+                "signer": "cosmos1notnolus",
+                "messages": [execute_msg(SIGNER)]
+            })))
+            .await
+            .expect("router call");
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn broadcast_tx_accepted_registers_hash_for_tx_status() {
+        let server = MockServer::start().await;
+        mount_node_status(&server).await;
+        mount_broadcast(&server, 0, "").await;
+        let state = state_with_chain(&server).await;
+
+        let resp = app(state.clone())
+            .oneshot(broadcast_request(&BASE64.encode(b"signed-tx")))
+            .await
+            .expect("router call");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value =
+            serde_json::from_str(&collect_body_str(resp).await).expect("json");
+        assert_eq!(body["tx_hash"], "A1B2C3");
+        assert_eq!(body["chain_id"], "pirin-1");
+        assert_eq!(
+            state.ws_manager.get_pending_broadcast_txs(),
+            vec!["A1B2C3".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn broadcast_tx_sequence_mismatch_returns_409() {
+        let server = MockServer::start().await;
+        mount_node_status(&server).await;
+        mount_broadcast(
+            &server,
+            32,
+            "account sequence mismatch, expected 12, got 11: incorrect account sequence",
+        )
+        .await;
+        let state = state_with_chain(&server).await;
+
+        let resp = app(state.clone())
+            .oneshot(broadcast_request(&BASE64.encode(b"signed-tx")))
+            .await
+            .expect("router call");
+
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let body: serde_json::Value =
+            serde_json::from_str(&collect_body_str(resp).await).expect("json");
+        assert_eq!(body["error"]["code"], "TX_SEQUENCE_MISMATCH");
+        assert_eq!(body["error"]["details"]["expected_sequence"], 12);
+        assert!(state.ws_manager.get_pending_broadcast_txs().is_empty());
+    }
+
+    #[tokio::test]
+    async fn broadcast_tx_invalid_base64_returns_400() {
+        let server = MockServer::start().await;
+        let resp = app(state_with_chain(&server).await)
+            .oneshot(broadcast_request("not base64!"))
+            .await
+            .expect("router call");
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -1,6 +1,7 @@
 //! Currency and Price Handlers
 //!
-//! Provides currency information, prices from Oracle contracts, and balance queries.
+//! Provides currency information, prices from Oracle contracts, backend-recorded
+//! price candles, and balance queries.
 
 use axum::{
     extract::{Path, Query, State},
@@ -10,9 +11,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, warn};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::error::AppError;
+use crate::price_history::{Candle, Resolution};
 use crate::query_types::AddressQuery;
 use crate::AppState;
 
@@ -66,6 +68,35 @@ pub struct PriceInfo {
     pub price_usd: String,
 }
 
+/// Default number of candles returned.
+const DEFAULT_CANDLE_LIMIT: usize = 500;
+
+/// Maximum number of candles returned.
+const MAX_CANDLE_LIMIT: usize = 2_000;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CandlesQuery {
+    /// Candle width (default `1h`)
+    pub resolution: Option<Resolution>,
+    /// Protocol to read the price from when `ticker` is a bare ticker
+    pub protocol: Option<String>,
+    /// Earliest candle start, unix seconds
+    pub from: Option<i64>,
+    /// Latest candle start, unix seconds
+    pub to: Option<i64>,
+    /// Maximum candles, newest kept (default 500, max 2000)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CandlesResponse {
+    /// Resolved currency key (TICKER@PROTOCOL)
+    pub key: String,
+    pub resolution: Resolution,
+    /// Oldest first
+    pub candles: Vec<Candle>,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct BalancesResponse {
     pub balances: Vec<BalanceInfo>,
@@ -180,6 +211,73 @@ pub async fn get_prices(
     Ok(Json(response))
 }
 
+/// Get OHLC price candles
+///
+/// Candles are built by the backend from the oracle prices of every block and
+/// backfilled from ETL history, so they stay available during ETL outages.
+/// `ticker` is either a full `TICKER@PROTOCOL` key or a bare ticker; a bare
+/// ticker uses `protocol` if given, otherwise the first tracked protocol.
+#[utoipa::path(
+    get,
+    path = "/api/prices/{ticker}/candles",
+    tag = "prices",
+    params(
+        ("ticker" = String, Path, description = "Ticker or TICKER@PROTOCOL key"),
+        CandlesQuery,
+    ),
+    responses(
+        (status = 200, description = "Price candles", body = CandlesResponse),
+        (status = 404, description = "No price history for this currency", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn get_price_candles(
+    State(state): State<Arc<AppState>>,
+    Path(ticker): Path<String>,
+    Query(query): Query<CandlesQuery>,
+) -> Result<Json<CandlesResponse>, AppError> {
+    let key = resolve_history_key(
+        &state.price_history.keys(),
+        &ticker,
+        query.protocol.as_deref(),
+    )
+    .ok_or_else(|| AppError::NotFound {
+        resource: format!("Price history for {}", ticker),
+    })?;
+    let resolution = query.resolution.unwrap_or(Resolution::OneHour);
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_CANDLE_LIMIT)
+        .clamp(1, MAX_CANDLE_LIMIT);
+
+    let candles = state
+        .price_history
+        .candles(&key, resolution, query.from, query.to, limit)
+        .ok_or_else(|| AppError::NotFound {
+            resource: format!("Price history for {}", key),
+        })?;
+
+    Ok(Json(CandlesResponse {
+        key,
+        resolution,
+        candles,
+    }))
+}
+
+/// Map a request ticker (bare or `TICKER@PROTOCOL`) onto a tracked key.
+fn resolve_history_key(keys: &[String], ticker: &str, protocol: Option<&str>) -> Option<String> {
+    let wanted = match (ticker.contains('@'), protocol) {
+        (true, _) => ticker.to_string(),
+        (false, Some(protocol)) => format!("{}@{}", ticker, protocol),
+        (false, None) => {
+            return keys
+                .iter()
+                .find(|key| key.split_once('@').is_some_and(|(t, _)| t == ticker))
+                .cloned();
+        }
+    };
+    keys.contains(&wanted).then_some(wanted)
+}
+
 /// Get wallet balances
 ///
 /// Returns visible balances (configured, non-ignored currencies) for a Nolus
@@ -325,7 +423,7 @@ const PRICE_KEY_DELIMITER: char = '@';
 /// first (arbitrary order); pinning the smallest key keeps the rendered USD
 /// value reproducible run-to-run for the same feed. Returns `0.0` when the
 /// ticker has no price at all.
-fn resolve_price_usd(prices: &HashMap<String, PriceInfo>, key: &str, ticker: &str) -> f64 {
+pub fn resolve_price_usd(prices: &HashMap<String, PriceInfo>, key: &str, ticker: &str) -> f64 {
     prices
         .get(key)
         .or_else(|| {
@@ -377,7 +475,7 @@ pub fn calculate_price_with_decimals(
 mod tests {
     use std::collections::HashMap;
 
-    use super::{calculate_price_with_decimals, PriceInfo};
+    use super::{calculate_price_with_decimals, resolve_history_key, PriceInfo};
 
     #[test]
     fn test_calculate_price_with_decimals() {
@@ -402,6 +500,69 @@ mod tests {
         assert_eq!(calculate_price_with_decimals("100", "0", "1.0", 8, 6), "0");
     }
 
+    #[test]
+    fn test_resolve_history_key() {
+        let keys = vec![
+            "ATOM@A".to_string(),
+            "ATOM@B".to_string(),
+            "OSMO@A".to_string(),
+        ];
+        assert_eq!(
+            resolve_history_key(&keys, "ATOM@B", None),
+            Some("ATOM@B".to_string())
+        );
+        assert_eq!(
+            resolve_history_key(&keys, "ATOM", Some("B")),
+            Some("ATOM@B".to_string())
+        );
+        assert_eq!(
+            resolve_history_key(&keys, "ATOM", None),
+            Some("ATOM@A".to_string())
+        );
+        assert_eq!(resolve_history_key(&keys, "OSMO", Some("B")), None);
+        assert_eq!(resolve_history_key(&keys, "NLS", None), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_price_candles_serves_recorded_history() {
+        use axum::extract::{Path, Query, State};
+
+        let state = crate::test_utils::test_app_state().await;
+        state
+            .price_history
+            .record(&[("ATOM@OSMOSIS", 7.0)], 1_700_000_000);
+        state
+            .price_history
+            .record(&[("ATOM@OSMOSIS", 8.0)], 1_700_000_030);
+
+        let query = super::CandlesQuery {
+            resolution: Some(crate::price_history::Resolution::OneDay),
+            protocol: None,
+            from: None,
+            to: None,
+            limit: None,
+        };
+        let axum::Json(response) =
+            super::get_price_candles(State(state.clone()), Path("ATOM".to_string()), Query(query))
+                .await
+                .unwrap();
+        assert_eq!(response.key, "ATOM@OSMOSIS");
+        assert_eq!(response.candles.len(), 1);
+        assert_eq!(response.candles[0].high, 8.0);
+
+        let missing = super::CandlesQuery {
+            resolution: None,
+            protocol: None,
+            from: None,
+            to: None,
+            limit: None,
+        };
+        let err = super::get_price_candles(State(state), Path("NLS".to_string()), Query(missing))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::error::AppError::NotFound { .. }));
+    }
+
     /// An invalid address is rejected before any cache or chain access, as the
     /// 400-mapped `Validation` error the REST balances handler already returns.
     #[tokio::test]
@@ -3,21 +3,27 @@
 //! Endpoints:
 //! - GET /api/earn/pools - Get all earn pools with APY and utilization
 //! - GET /api/earn/pools/:pool_id - Get details for a specific pool
+//! - GET /api/earn/pools/:pool_id/analytics - Utilization, borrow-rate curve and APY projection
 //! - GET /api/earn/positions?owner=... - Get all earn positions for an owner
+//! - GET /api/earn/positions/history?address=... - Ledger, cost basis and yield per position
 //! - POST /api/earn/deposit - Build transaction to deposit into a pool
 //! - POST /api/earn/withdraw - Build transaction to withdraw from a pool
+//! - POST /api/earn/withdraw/preview - Check a withdrawal against pool liquidity
 //! - GET /api/earn/stats - Get overall earn statistics
 
 use axum::{
     extract::{Path, Query, State},
     Json,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{debug, warn};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
+use crate::earn_ledger::{self, ActivitySource, LenderActivity, LenderActivityKind};
 use crate::error::AppError;
+use crate::handlers::tx_builder::{self, GasEstimate, MsgKind};
 use crate::num_utils::u128_to_f64;
 use crate::query_types::AddressQuery;
 use crate::AppState;
@@ -79,12 +85,16 @@ pub struct EarnPositionsResponse {
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct DepositRequest {
+    /// Lender address signing the deposit
+    pub sender: String,
     pub protocol: String,
     pub amount: String,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct WithdrawRequest {
+    /// Lender address signing the withdrawal
+    pub sender: String,
     pub protocol: String,
     /// Amount in nLPN to withdraw (receipt tokens)
     pub amount: String,
@@ -96,6 +106,7 @@ pub struct EarnTransactionResponse {
     #[schema(value_type = Vec<Object>)]
     pub messages: Vec<serde_json::Value>,
     pub memo: String,
+    pub gas: GasEstimate,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -110,12 +121,174 @@ pub struct EarnStats {
     pub dispatcher_rewards: Option<f64>,
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PoolAnalyticsQuery {
+    /// Hypothetical extra deposit (LPN base units) to project the APY for
+    pub deposit: Option<String>,
+}
+
+/// LPP borrow-rate parameters, in percent
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BorrowRateModel {
+    pub base_interest_rate: f64,
+    pub utilization_optimal: f64,
+    pub addon_optimal_interest_rate: f64,
+    /// Utilization below which the pool stops accepting deposits
+    pub min_utilization: f64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RateCurvePoint {
+    /// Utilization (0-100)
+    pub utilization: f64,
+    /// Borrow rate charged on new leases (percent)
+    pub borrow_rate: f64,
+    /// Supply rate earned by lenders before protocol fees (percent)
+    pub supply_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UtilizationPoint {
+    pub timestamp: String,
+    pub supplied: String,
+    pub borrowed: String,
+    /// Utilization (0-100)
+    pub utilization: f64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DepositProjection {
+    /// Extra deposit (LPN base units)
+    pub deposit: String,
+    /// Utilization after the deposit (0-100)
+    pub projected_utilization: f64,
+    /// Current APY spread over the larger pool (percent)
+    pub projected_apy: f64,
+    /// Borrow rate new leases would pay after the deposit (percent)
+    pub projected_borrow_rate: f64,
+    /// The deposit fits the remaining deposit capacity
+    pub within_capacity: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EarnPoolAnalytics {
+    pub protocol: String,
+    pub lpp_address: String,
+    /// LPN currency ticker
+    pub currency: String,
+    /// Pool balance (LPN base units)
+    pub total_deposited: String,
+    /// Outstanding lease principal (LPN base units)
+    pub total_borrowed: String,
+    /// Current utilization (0-100)
+    pub utilization: f64,
+    /// Current APY as percentage
+    pub current_apy: f64,
+    /// Borrow rate new leases pay at the current utilization (percent)
+    pub current_borrow_rate: f64,
+    pub borrow_rate_model: BorrowRateModel,
+    /// Borrow and supply rate across utilization, in 5% steps
+    pub borrow_rate_curve: Vec<RateCurvePoint>,
+    /// Utilization over time from ETL (empty when ETL is unavailable)
+    pub utilization_history: Vec<UtilizationPoint>,
+    /// Remaining deposit capacity; `None` when the pool has no limit
+    pub deposit_capacity: Option<String>,
+    /// Present when `deposit` was given
+    pub projection: Option<DepositProjection>,
+}
+
+/// One recorded deposit, withdrawal or reward claim
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EarnActivity {
+    pub tx_hash: String,
+    pub at: DateTime<Utc>,
+    pub kind: LenderActivityKind,
+    pub ticker: String,
+    /// Minor units of `ticker`
+    pub amount: String,
+    /// nLPN minted or burnt
+    pub receipts: Option<String>,
+    /// USD value when recorded
+    pub usd: Option<f64>,
+    pub source: ActivitySource,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EarnPositionHistory {
+    pub protocol: String,
+    /// Oldest first
+    pub activity: Vec<EarnActivity>,
+    /// LPN deposited (minor units)
+    pub total_deposited: String,
+    /// LPN withdrawn (minor units)
+    pub total_withdrawn: String,
+    /// Average-cost LPN basis of the nLPN still held
+    pub cost_basis: String,
+    /// Current LPN value of the nLPN held on-chain
+    pub current_value: String,
+    /// Withdrawn LPN minus the cost basis of the nLPN burnt
+    pub realized_earnings: String,
+    /// Current value minus cost basis
+    pub unrealized_earnings: String,
+    /// Reward currency claimed (minor units)
+    pub rewards_claimed: String,
+    /// `None` if a claim could not be priced
+    pub rewards_claimed_usd: Option<f64>,
+    /// nLPN price growth since the first recorded price (percent)
+    pub time_weighted_yield: Option<f64>,
+    /// `time_weighted_yield` annualized; `None` with under a day of history
+    pub annualized_yield: Option<f64>,
+    /// ETL's lifetime earnings in this pool (USD), covering history the
+    /// ledger lacks
+    pub etl_earnings_usd: Option<String>,
+    /// The recorded activity fully accounts for the nLPN held on-chain
+    pub complete: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EarnPositionsHistoryResponse {
+    pub address: String,
+    pub positions: Vec<EarnPositionHistory>,
+    /// ETL's lifetime earnings across all pools (USD)
+    pub etl_total_earnings_usd: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WithdrawPreview {
+    pub protocol: String,
+    pub lpp_address: String,
+    /// LPN currency ticker
+    pub currency: String,
+    /// Requested amount in nLPN
+    pub requested_nlpn: String,
+    /// Requested amount converted to LPN at the current LPP price
+    pub requested_lpn: String,
+    /// nLPN the lender holds
+    pub lender_nlpn: String,
+    /// LPN the pool can pay out now: balance minus outstanding borrows
+    pub available_liquidity: String,
+    /// Most nLPN that can be withdrawn now
+    pub max_withdrawable_nlpn: String,
+    /// `max_withdrawable_nlpn` in LPN
+    pub max_withdrawable_lpn: String,
+    /// The pool can pay out the full requested amount
+    pub can_withdraw_in_full: bool,
+    /// Partial amount (nLPN) to withdraw instead when the pool can't pay in full
+    pub recommended_nlpn: Option<String>,
+    /// Unsigned withdrawal of the requested amount, or of the recommended
+    /// partial amount; `None` when nothing can be withdrawn now
+    pub transaction: Option<EarnTransactionResponse>,
+}
+
 // ============================================================================
 // Constants
 // ============================================================================
 
 const INTEREST_DECIMALS: i32 = 7;
 
+/// Utilization step of the borrow-rate curve (percent)
+const RATE_CURVE_STEP: usize = 5;
+
 // ============================================================================
 // Handlers
 // ============================================================================
@@ -318,8 +491,8 @@ pub async fn get_positions(
 
 /// Build a deposit transaction
 ///
-/// Returns unsigned CosmWasm execute messages to deposit funds into an earn
-/// pool. The client fills in the sender address and denom before signing.
+/// Returns a ready-to-sign CosmWasm execute message depositing the pool's LPN
+/// (resolved to its bank denom) on behalf of the sender, with a gas estimate.
 #[utoipa::path(
     post,
     path = "/api/earn/deposit",
@@ -327,6 +500,7 @@ pub async fn get_positions(
     request_body = DepositRequest,
     responses(
         (status = 200, description = "Unsigned deposit transaction", body = EarnTransactionResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
         (status = 404, description = "Protocol not found", body = crate::error::ErrorResponse),
         (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
     ),
@@ -340,6 +514,9 @@ pub async fn deposit(
         request.protocol
     );
 
+    tx_builder::validate_sender(&request.sender)?;
+    tx_builder::parse_positive_amount(&request.amount, "amount")?;
+
     let contracts_map = state
         .data_cache
         .protocol_contracts
@@ -350,6 +527,12 @@ pub async fn deposit(
             resource: format!("Protocol {}", request.protocol),
         })?;
 
+    let currencies = state
+        .data_cache
+        .currencies
+        .load_or_unavailable("Currencies")?;
+    let denom = tx_builder::resolve_lpn_denom(&currencies, &request.protocol)?;
+
     // Build deposit message
     // Deposit to LPP is done by sending funds to the contract
     let deposit_msg = serde_json::json!({
@@ -358,18 +541,20 @@ pub async fn deposit(
 
     let execute_msg = serde_json::json!({
         "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
-        "sender": "", // Will be filled by frontend with wallet address
+        "sender": request.sender,
         "contract": contract_info.lpp,
         "msg": deposit_msg,
         "funds": [{
-            "denom": "", // Will be filled with LPN IBC denom
+            "denom": denom,
             "amount": request.amount
         }]
     });
 
+    let gas_fee_config = state.data_cache.gas_fee_config.load();
     Ok(Json(EarnTransactionResponse {
         messages: vec![execute_msg],
         memo: "Deposit to Nolus Earn".to_string(),
+        gas: tx_builder::estimate_gas(&[MsgKind::WasmExecute], gas_fee_config.as_ref()),
     }))
 }
 
@@ -384,6 +569,7 @@ pub async fn deposit(
     request_body = WithdrawRequest,
     responses(
         (status = 200, description = "Unsigned withdraw transaction", body = EarnTransactionResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
         (status = 404, description = "Protocol not found", body = crate::error::ErrorResponse),
         (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
     ),
@@ -397,6 +583,9 @@ pub async fn withdraw(
         request.protocol
     );
 
+    tx_builder::validate_sender(&request.sender)?;
+    tx_builder::parse_positive_amount(&request.amount, "amount")?;
+
     let contracts_map = state
         .data_cache
         .protocol_contracts
@@ -407,25 +596,116 @@ pub async fn withdraw(
             resource: format!("Protocol {}", request.protocol),
         })?;
 
-    // Build withdraw message
-    // Withdraw from LPP burns nLPN and returns LPN
-    let withdraw_msg = serde_json::json!({
-        "burn_deposit": {
-            "amount": request.amount
-        }
-    });
+    Ok(Json(withdraw_transaction(
+        &state,
+        &request.sender,
+        &contract_info.lpp,
+        &request.amount,
+    )))
+}
 
-    let execute_msg = serde_json::json!({
-        "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
-        "sender": "", // Will be filled by frontend with wallet address
-        "contract": contract_info.lpp,
-        "msg": withdraw_msg,
-        "funds": []
-    });
+/// Preview a withdrawal
+///
+/// Converts the requested nLPN to LPN at the current LPP price and checks it
+/// against the lender's deposit and the pool's available liquidity (balance
+/// minus outstanding borrows). When the pool can't pay out in full, the
+/// preview recommends the largest partial withdrawal that would succeed and
+/// returns its transaction instead of one the contract would reject.
+#[utoipa::path(
+    post,
+    path = "/api/earn/withdraw/preview",
+    tag = "earn",
+    request_body = WithdrawRequest,
+    responses(
+        (status = 200, description = "Withdrawal preview", body = WithdrawPreview),
+        (status = 400, description = "Invalid request or amount above the lender's deposit", body = crate::error::ErrorResponse),
+        (status = 404, description = "Protocol not found", body = crate::error::ErrorResponse),
+        (status = 502, description = "Upstream chain RPC error", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn preview_withdraw(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<WithdrawRequest>,
+) -> Result<Json<WithdrawPreview>, AppError> {
+    debug!("Previewing withdrawal for protocol: {}", request.protocol);
 
-    Ok(Json(EarnTransactionResponse {
-        messages: vec![execute_msg],
-        memo: "Withdraw from Nolus Earn".to_string(),
+    tx_builder::validate_sender(&request.sender)?;
+    let requested = tx_builder::parse_positive_amount(&request.amount, "amount")?;
+
+    let contracts_map = state
+        .data_cache
+        .protocol_contracts
+        .load_or_unavailable("Protocol contracts")?;
+    let lpp_address = contracts_map
+        .get(&request.protocol)
+        .map(|c| c.lpp.clone())
+        .ok_or_else(|| AppError::NotFound {
+            resource: format!("Protocol {}", request.protocol),
+        })?;
+
+    let (lpp_price, lpp_balance, deposit) = tokio::try_join!(
+        state.chain_client.get_lpp_price(&lpp_address),
+        state.chain_client.get_lpp_balance(&lpp_address),
+        state
+            .chain_client
+            .get_lender_deposit(&lpp_address, &request.sender),
+    )?;
+
+    let parse = |value: &str, what: &str| -> Result<u128, AppError> {
+        value.parse().map_err(|e| {
+            AppError::Internal(format!(
+                "Unparseable {} for {}: '{}': {e}",
+                what, request.protocol, value
+            ))
+        })
+    };
+    let price = (
+        parse(&lpp_price.amount.amount, "LPP price_amount")?,
+        parse(&lpp_price.amount_quote.amount, "LPP price_quote")?,
+    );
+    let held = parse(&deposit.amount, "lender deposit")?;
+    let balance = parse(&lpp_balance.balance.amount, "LPP balance")?;
+    let borrowed = parse(
+        &lpp_balance.total_principal_due.amount,
+        "LPP total_principal_due",
+    )?;
+
+    if requested > held {
+        return Err(AppError::Validation {
+            message: format!(
+                "amount {} exceeds the lender's deposit of {} nLPN",
+                requested, held
+            ),
+            field: Some("amount".to_string()),
+            details: None,
+        });
+    }
+
+    let available = balance.saturating_sub(borrowed);
+    let max_nlpn = held.min(lpn_to_nlpn(available, price));
+    let can_withdraw_in_full = requested <= max_nlpn;
+    let payable = if can_withdraw_in_full {
+        requested
+    } else {
+        max_nlpn
+    };
+    let transaction = (payable > 0)
+        .then(|| withdraw_transaction(&state, &request.sender, &lpp_address, &payable.to_string()));
+
+    Ok(Json(WithdrawPreview {
+        protocol: request.protocol,
+        lpp_address,
+        currency: lpp_balance.balance.ticker,
+        requested_nlpn: requested.to_string(),
+        requested_lpn: nlpn_to_lpn(requested, price).to_string(),
+        lender_nlpn: held.to_string(),
+        available_liquidity: available.to_string(),
+        max_withdrawable_nlpn: max_nlpn.to_string(),
+        max_withdrawable_lpn: nlpn_to_lpn(max_nlpn, price).to_string(),
+        can_withdraw_in_full,
+        recommended_nlpn: (!can_withdraw_in_full && max_nlpn > 0).then(|| max_nlpn.to_string()),
+        transaction,
     }))
 }
 
@@ -490,10 +770,340 @@ pub async fn get_earn_stats(
     }))
 }
 
+/// Get earn pool analytics
+///
+/// Returns a pool's utilization history, the borrow-rate curve of its LPP
+/// `borrow_rate` config, the remaining deposit capacity and, when `deposit`
+/// is given, the APY and utilization projected after that extra deposit.
+#[utoipa::path(
+    get,
+    path = "/api/earn/pools/{pool_id}/analytics",
+    tag = "earn",
+    params(
+        ("pool_id" = String, Path, description = "Protocol key (e.g. `OSMOSIS-OSMOSIS-USDC_NOBLE`)"),
+        PoolAnalyticsQuery,
+    ),
+    responses(
+        (status = 200, description = "Pool analytics", body = EarnPoolAnalytics),
+        (status = 400, description = "Invalid deposit amount", body = crate::error::ErrorResponse),
+        (status = 404, description = "Pool not found", body = crate::error::ErrorResponse),
+        (status = 502, description = "Upstream chain RPC error", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn get_pool_analytics(
+    State(state): State<Arc<AppState>>,
+    Path(protocol): Path<String>,
+    Query(query): Query<PoolAnalyticsQuery>,
+) -> Result<Json<EarnPoolAnalytics>, AppError> {
+    debug!("Getting pool analytics for protocol: {}", protocol);
+
+    let deposit = query
+        .deposit
+        .as_deref()
+        .map(|d| tx_builder::parse_positive_amount(d, "deposit"))
+        .transpose()?;
+
+    let filter_ctx = state
+        .data_cache
+        .filter_context
+        .load_or_unavailable("Filter context")?;
+    if !filter_ctx.is_earn_position_visible(&protocol) {
+        return Err(AppError::NotFound {
+            resource: format!("Pool for protocol: {}", protocol),
+        });
+    }
+    let contracts_map = state
+        .data_cache
+        .protocol_contracts
+        .load_or_unavailable("Protocol contracts")?;
+    let lpp_address = contracts_map
+        .get(&protocol)
+        .map(|c| c.lpp.clone())
+        .ok_or_else(|| AppError::NotFound {
+            resource: format!("Protocol {}", protocol),
+        })?;
+
+    // Chain state is required; the ETL history is optional
+    let (chain_result, history_result) = tokio::join!(
+        async {
+            tokio::try_join!(
+                state.chain_client.get_lpp_config(&lpp_address),
+                state.chain_client.get_lpp_balance(&lpp_address),
+                state.chain_client.get_deposit_capacity(&lpp_address),
+                state.chain_client.get_lpn(&lpp_address),
+            )
+        },
+        state.etl_client.fetch_supplied_borrowed_history(&protocol),
+    );
+    let (lpp_config, lpp_balance, deposit_capacity, lpn_ticker) = chain_result?;
+    let history = history_or_empty(history_result, &protocol);
+
+    let balance: u128 = lpp_balance.balance.amount.parse().map_err(|e| {
+        AppError::Internal(format!(
+            "Unparseable LPP balance for {}: '{}': {e}",
+            protocol, lpp_balance.balance.amount
+        ))
+    })?;
+    let borrowed: u128 = lpp_balance
+        .total_principal_due
+        .amount
+        .parse()
+        .map_err(|e| {
+            AppError::Internal(format!(
+                "Unparseable LPP total_principal_due for {}: '{}': {e}",
+                protocol, lpp_balance.total_principal_due.amount
+            ))
+        })?;
+
+    let current_apy = state
+        .data_cache
+        .pools
+        .load()
+        .and_then(|pools| pools.into_iter().find(|p| p.protocol == protocol))
+        .map(|p| p.apy)
+        .unwrap_or(0.0);
+    let rate = &lpp_config.borrow_rate;
+    let utilization = utilization_ratio(borrowed, balance);
+    let capacity: Option<u128> = deposit_capacity
+        .as_ref()
+        .and_then(|dc| dc.amount.parse().ok());
+
+    let projection = deposit.map(|deposit| {
+        let projected_utilization = utilization_ratio(borrowed, balance.saturating_add(deposit));
+        let dilution = if balance > 0 {
+            u128_to_f64(balance) / u128_to_f64(balance.saturating_add(deposit))
+        } else {
+            1.0
+        };
+        DepositProjection {
+            deposit: deposit.to_string(),
+            projected_utilization: projected_utilization * 100.0,
+            projected_apy: current_apy * dilution,
+            projected_borrow_rate: borrow_rate_at(rate, projected_utilization),
+            within_capacity: capacity.is_none_or(|c| deposit <= c),
+        }
+    });
+
+    Ok(Json(EarnPoolAnalytics {
+        protocol,
+        lpp_address,
+        currency: lpn_ticker,
+        total_deposited: balance.to_string(),
+        total_borrowed: borrowed.to_string(),
+        utilization: utilization * 100.0,
+        current_apy,
+        current_borrow_rate: borrow_rate_at(rate, utilization),
+        borrow_rate_model: BorrowRateModel {
+            base_interest_rate: permille_to_percent(rate.base_interest_rate),
+            utilization_optimal: permille_to_percent(rate.utilization_optimal),
+            addon_optimal_interest_rate: permille_to_percent(rate.addon_optimal_interest_rate),
+            min_utilization: permille_to_percent(lpp_config.min_utilization),
+        },
+        borrow_rate_curve: borrow_rate_curve(rate),
+        utilization_history: history,
+        deposit_capacity: deposit_capacity.map(|dc| dc.amount),
+        projection,
+    }))
+}
+
+/// Get earn position history for an address
+///
+/// Returns every deposit, withdrawal and reward claim recorded for the
+/// lender, with the cost basis, realized and unrealized earnings and
+/// time-weighted yield of each position. History from before the backend
+/// was watching is backfilled from ETL on first request; `complete` is
+/// false where the ledger still can't account for the nLPN held on-chain,
+/// and `etl_earnings_usd` is the fallback figure there.
+#[utoipa::path(
+    get,
+    path = "/api/earn/positions/history",
+    tag = "earn",
+    params(AddressQuery),
+    responses(
+        (status = 200, description = "Earn position history", body = EarnPositionsHistoryResponse),
+        (status = 400, description = "Invalid address", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn get_positions_history(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AddressQuery>,
+) -> Result<Json<EarnPositionsHistoryResponse>, AppError> {
+    crate::validation::validate_bech32_address(&query.address, "address")?;
+    debug!("Getting earn position history for: {}", query.address);
+
+    let filter_ctx = state
+        .data_cache
+        .filter_context
+        .load_or_unavailable("Filter context")?;
+
+    if let Err(e) = earn_ledger::backfill(&state, &query.address).await {
+        warn!(
+            "Failed to backfill earn ledger for {}: {}",
+            query.address, e
+        );
+    }
+
+    let (current, earnings) = tokio::join!(
+        fetch_earn_positions_for_monitoring(&state, &query.address),
+        state.etl_client.fetch_earnings(&query.address),
+    );
+    let (current, _) = current?;
+    let earnings = match earnings {
+        Ok(earnings) => Some(earnings),
+        Err(e) => {
+            warn!("Failed to fetch ETL earnings for {}: {}", query.address, e);
+            None
+        }
+    };
+
+    let mut ledger = state.earn_ledger.get(&query.address);
+    let mut protocols: Vec<String> = ledger
+        .keys()
+        .cloned()
+        .chain(current.iter().map(|p| p.protocol.clone()))
+        .filter(|p| filter_ctx.is_earn_position_visible(p))
+        .collect();
+    protocols.sort();
+    protocols.dedup();
+
+    let now = Utc::now();
+    let positions = protocols
+        .into_iter()
+        .map(|protocol| {
+            let held = current
+                .iter()
+                .find(|p| p.protocol == protocol)
+                .map(|p| {
+                    (
+                        p.deposited_lpn.parse().unwrap_or_default(),
+                        p.deposited_asset.parse().unwrap_or_default(),
+                    )
+                })
+                .unwrap_or_default();
+            let etl_earnings_usd = earnings.as_ref().and_then(|e| {
+                e.by_protocol
+                    .iter()
+                    .find(|p| p.protocol == protocol)
+                    .map(|p| p.earnings.clone())
+            });
+            let activity = ledger.remove(&protocol).unwrap_or_default();
+            let truncated = state.earn_ledger.is_truncated(&query.address, &protocol);
+            position_history(protocol, activity, truncated, held, etl_earnings_usd, now)
+        })
+        .collect();
+
+    Ok(Json(EarnPositionsHistoryResponse {
+        address: query.address,
+        positions,
+        etl_total_earnings_usd: earnings.map(|e| e.earnings),
+    }))
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// Unsigned LPP execution burning `amount` nLPN for its LPN value
+fn withdraw_transaction(
+    state: &AppState,
+    sender: &str,
+    lpp_address: &str,
+    amount: &str,
+) -> EarnTransactionResponse {
+    let execute_msg = serde_json::json!({
+        "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
+        "sender": sender,
+        "contract": lpp_address,
+        "msg": {
+            "burn_deposit": {
+                "amount": amount
+            }
+        },
+        "funds": []
+    });
+
+    let gas_fee_config = state.data_cache.gas_fee_config.load();
+    EarnTransactionResponse {
+        messages: vec![execute_msg],
+        memo: "Withdraw from Nolus Earn".to_string(),
+        gas: tx_builder::estimate_gas(&[MsgKind::WasmExecute], gas_fee_config.as_ref()),
+    }
+}
+
+/// nLPN to LPN at an LPP `(amount, amount_quote)` price, rounding down.
+/// A zero price leaves the amount unscaled.
+fn nlpn_to_lpn(nlpn: u128, (price_amount, price_quote): (u128, u128)) -> u128 {
+    if price_amount == 0 {
+        return nlpn;
+    }
+    nlpn.checked_mul(price_quote)
+        .map_or(u128::MAX, |scaled| scaled / price_amount)
+}
+
+/// LPN to the nLPN it buys at an LPP `(amount, amount_quote)` price, rounding
+/// down so the result never pays out more than `lpn`.
+fn lpn_to_nlpn(lpn: u128, (price_amount, price_quote): (u128, u128)) -> u128 {
+    if price_quote == 0 {
+        return lpn;
+    }
+    lpn.checked_mul(price_amount)
+        .map_or(u128::MAX, |scaled| scaled / price_quote)
+}
+
+/// Summarize one position's ledger against the `(LPN, nLPN)` it holds
+/// on-chain now. A `truncated` ledger lost its oldest activity and is never
+/// complete.
+fn position_history(
+    protocol: String,
+    activity: Vec<LenderActivity>,
+    truncated: bool,
+    (held_lpn, held_nlpn): (u128, u128),
+    etl_earnings_usd: Option<String>,
+    now: DateTime<Utc>,
+) -> EarnPositionHistory {
+    let summary = earn_ledger::summarize(&activity);
+    let current_price =
+        (held_nlpn > 0).then(|| (now, u128_to_f64(held_lpn) / u128_to_f64(held_nlpn)));
+    let yields = summary
+        .first_price
+        .zip(current_price.or(summary.last_price))
+        .and_then(|(start, end)| earn_ledger::time_weighted_yield(start, end));
+    let unrealized = i128::try_from(held_lpn)
+        .unwrap_or(i128::MAX)
+        .saturating_sub(i128::try_from(summary.cost_basis).unwrap_or(i128::MAX));
+
+    EarnPositionHistory {
+        protocol,
+        activity: activity
+            .into_iter()
+            .map(|a| EarnActivity {
+                tx_hash: a.tx_hash,
+                at: a.at,
+                kind: a.kind,
+                ticker: a.ticker,
+                amount: a.amount.to_string(),
+                receipts: a.receipts.map(|r| r.to_string()),
+                usd: a.usd,
+                source: a.source,
+            })
+            .collect(),
+        total_deposited: summary.total_deposited.to_string(),
+        total_withdrawn: summary.total_withdrawn.to_string(),
+        cost_basis: summary.cost_basis.to_string(),
+        current_value: held_lpn.to_string(),
+        realized_earnings: summary.realized_earnings.to_string(),
+        unrealized_earnings: unrealized.to_string(),
+        rewards_claimed: summary.rewards_claimed.to_string(),
+        rewards_claimed_usd: summary.rewards_claimed_usd,
+        time_weighted_yield: yields.map(|(total, _)| total),
+        annualized_yield: yields.and_then(|(_, annualized)| annualized),
+        etl_earnings_usd,
+        complete: !truncated && summary.exact && summary.receipts_held == held_nlpn,
+    }
+}
+
 pub async fn fetch_pool_info(
     state: &AppState,
     protocol: &str,
@@ -575,6 +1185,84 @@ pub async fn fetch_pool_info(
     })
 }
 
+/// LPP rates and utilizations are permilles
+fn permille_to_percent(permille: u32) -> f64 {
+    f64::from(permille) / 10.0
+}
+
+/// Borrowed share of the pool balance (0-1)
+fn utilization_ratio(borrowed: u128, balance: u128) -> f64 {
+    if balance == 0 {
+        return 0.0;
+    }
+    (u128_to_f64(borrowed) / u128_to_f64(balance)).clamp(0.0, 1.0)
+}
+
+/// Borrow rate (percent) at `utilization` (0-1) under the LPP `borrow_rate`
+/// model: the add-on grows with liabilities over available funds and reaches
+/// `addon_optimal_interest_rate` at the optimal utilization, where it caps.
+fn borrow_rate_at(model: &crate::external::chain::BorrowRate, utilization: f64) -> f64 {
+    let base = permille_to_percent(model.base_interest_rate);
+    let addon = permille_to_percent(model.addon_optimal_interest_rate);
+    let optimal = (f64::from(model.utilization_optimal) / 1000.0).clamp(0.0, 0.999);
+    if optimal <= 0.0 {
+        return base + addon;
+    }
+    let liabilities_over_available = |u: f64| u / (1.0 - u);
+    let u = utilization.clamp(0.0, 0.999);
+    base + addon * (liabilities_over_available(u) / liabilities_over_available(optimal)).min(1.0)
+}
+
+fn borrow_rate_curve(model: &crate::external::chain::BorrowRate) -> Vec<RateCurvePoint> {
+    (0..100_u32)
+        .step_by(RATE_CURVE_STEP)
+        .map(|percent| {
+            let utilization = f64::from(percent) / 100.0;
+            let borrow_rate = borrow_rate_at(model, utilization);
+            RateCurvePoint {
+                utilization: f64::from(percent),
+                borrow_rate,
+                supply_rate: borrow_rate * utilization,
+            }
+        })
+        .collect()
+}
+
+/// Utilization samples from ETL; the history is optional, so an ETL failure
+/// yields an empty series
+fn history_or_empty(
+    history: Result<Vec<crate::external::etl::EtlSuppliedBorrowedPoint>, AppError>,
+    protocol: &str,
+) -> Vec<UtilizationPoint> {
+    match history {
+        Ok(points) => points
+            .into_iter()
+            .map(|p| {
+                let supplied: f64 = p.supplied.parse().unwrap_or(0.0);
+                let borrowed: f64 = p.borrowed.parse().unwrap_or(0.0);
+                let utilization = if supplied > 0.0 {
+                    (borrowed / supplied * 100.0).clamp(0.0, 100.0)
+                } else {
+                    0.0
+                };
+                UtilizationPoint {
+                    timestamp: p.timestamp,
+                    supplied: p.supplied,
+                    borrowed: p.borrowed,
+                    utilization,
+                }
+            })
+            .collect(),
+        Err(e) => {
+            warn!(
+                "Failed to fetch utilization history for {}: {}",
+                protocol, e
+            );
+            Vec::new()
+        }
+    }
+}
+
 async fn fetch_position_info(
     state: &AppState,
     protocol: &str,
@@ -786,9 +1474,18 @@ async fn fetch_position_for_monitoring(
         deposit_amount
     };
 
-    // Calculate rewards (difference between current value and deposited value)
-    // For now, rewards tracking would require historical data
-    let rewards = "0".to_string();
+    // Rewards are the value above cost basis, known once the ledger accounts
+    // for every nLPN held
+    let rewards = state
+        .earn_ledger
+        .position(owner, protocol)
+        .filter(|_| !state.earn_ledger.is_truncated(owner, protocol))
+        .map(|activity| earn_ledger::summarize(&activity))
+        .filter(|summary| summary.exact && summary.receipts_held == deposit_amount)
+        .map_or(0, |summary| {
+            deposited_lpn.saturating_sub(summary.cost_basis)
+        })
+        .to_string();
 
     Ok(Some(EarnPositionInfo {
         protocol: protocol.to_string(),
@@ -815,7 +1512,14 @@ mod tests {
         Router::new()
             .route("/api/earn/pools", get(get_pools))
             .route("/api/earn/positions", get(get_positions))
+            .route("/api/earn/positions/history", get(get_positions_history))
             .route("/api/earn/deposit", post(deposit))
+            .route("/api/earn/withdraw", post(withdraw))
+            .route("/api/earn/withdraw/preview", post(preview_withdraw))
+            .route(
+                "/api/earn/pools/{pool_id}/analytics",
+                get(get_pool_analytics),
+            )
             .with_state(state)
     }
 
@@ -875,11 +1579,358 @@ mod tests {
                     .method("POST")
                     .uri("/api/earn/deposit")
                     .header("content-type", "application/json")
-                    .body(Body::from(r#"{"protocol":"P","amount":"100"}"#.to_string()))
+                    .body(Body::from(
+                        r#"{"sender":"nolus1qg5ega6dykkxc307y25pecuufrjkxkaggkkxh7nad0vhyhtuhw3sqaa3c5","protocol":"P","amount":"100"}"#
+                            .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn earn_deposit_invalid_sender_returns_400() {
+        let app = build_app(test_app_state().await);
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/earn/deposit")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"sender":"osmo1notnolus","protocol":"P","amount":"100"}"#.to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body = collect_body_str(resp).await;
+        assert!(body.contains("sender"), "body: {body}");
+    }
+
+    async fn assert_amounts_rejected(uri: &str) {
+        for amount in ["abc", "0", "-1"] {
+            let body = serde_json::json!({
+                "sender": "nolus1qg5ega6dykkxc307y25pecuufrjkxkaggkkxh7nad0vhyhtuhw3sqaa3c5",
+                "protocol": "P",
+                "amount": amount
+            });
+            let resp = build_app(test_app_state().await)
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri(uri)
+                        .header("content-type", "application/json")
+                        .body(Body::from(body.to_string()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "amount {amount}");
+            let body = collect_body_str(resp).await;
+            assert!(body.contains("amount"), "body: {body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn earn_deposit_rejects_non_positive_amount() {
+        assert_amounts_rejected("/api/earn/deposit").await;
+    }
+
+    #[tokio::test]
+    async fn earn_withdraw_rejects_non_positive_amount() {
+        assert_amounts_rejected("/api/earn/withdraw").await;
+    }
+
+    #[tokio::test]
+    async fn earn_pool_analytics_rejects_invalid_deposit() {
+        let app = build_app(test_app_state().await);
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/earn/pools/P/analytics?deposit=-5")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body = collect_body_str(resp).await;
+        assert!(body.contains("deposit"), "body: {body}");
+    }
+
+    #[tokio::test]
+    async fn earn_pool_analytics_cold_cache_returns_503() {
+        let app = build_app(test_app_state().await);
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/earn/pools/P/analytics?deposit=1000")
+                    .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
         assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
     }
+
+    #[test]
+    fn borrow_rate_follows_lpp_model() {
+        let model = crate::external::chain::BorrowRate {
+            base_interest_rate: 100,
+            utilization_optimal: 700,
+            addon_optimal_interest_rate: 20,
+        };
+        assert!((borrow_rate_at(&model, 0.0) - 10.0).abs() < 1e-9);
+        assert!((borrow_rate_at(&model, 0.7) - 12.0).abs() < 1e-9);
+        // Capped past the optimal utilization
+        assert!((borrow_rate_at(&model, 0.95) - 12.0).abs() < 1e-9);
+        // Half the optimal liabilities-to-available ratio earns half the add-on
+        let r = 0.7 / 0.3 / 2.0;
+        let u = r / (1.0 + r);
+        assert!((borrow_rate_at(&model, u) - 11.0).abs() < 1e-9);
+
+        let curve = borrow_rate_curve(&model);
+        assert_eq!(curve.len(), 20);
+        assert_eq!(curve[0].supply_rate, 0.0);
+        assert!((curve[10].supply_rate - curve[10].borrow_rate * 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn utilization_history_tolerates_etl_failure() {
+        let history = history_or_empty(
+            Ok(vec![crate::external::etl::EtlSuppliedBorrowedPoint {
+                timestamp: "2026-01-01T00:00:00Z".to_string(),
+                supplied: "200".to_string(),
+                borrowed: "50".to_string(),
+            }]),
+            "P",
+        );
+        assert!((history[0].utilization - 25.0).abs() < 1e-9);
+        assert!(history_or_empty(Err(AppError::Internal("down".to_string())), "P").is_empty());
+    }
+
+    #[tokio::test]
+    async fn earn_positions_history_invalid_address_returns_400() {
+        let app = build_app(test_app_state().await);
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/earn/positions/history?address=not-bech32")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn position_history_measures_yield_against_chain_holdings() {
+        let start = Utc::now() - chrono::Duration::days(365);
+        let activity = vec![LenderActivity {
+            tx_hash: "D".to_string(),
+            at: start,
+            kind: LenderActivityKind::Deposit,
+            ticker: "USDC".to_string(),
+            amount: 1_000_000,
+            receipts: Some(1_000_000),
+            usd: Some(1.0),
+            source: ActivitySource::Chain,
+        }];
+        let history = position_history(
+            "P".to_string(),
+            activity.clone(),
+            false,
+            (1_100_000, 1_000_000),
+            None,
+            start + chrono::Duration::days(365),
+        );
+        assert!(history.complete);
+        assert_eq!(history.cost_basis, "1000000");
+        assert_eq!(history.unrealized_earnings, "100000");
+        assert!((history.time_weighted_yield.unwrap() - 10.0).abs() < 1e-6);
+        assert!((history.annualized_yield.unwrap() - 10.0).abs() < 1e-6);
+
+        // nLPN the ledger never saw minted leaves the position incomplete
+        let partial = position_history(
+            "P".to_string(),
+            activity.clone(),
+            false,
+            (2_200_000, 2_000_000),
+            None,
+            Utc::now(),
+        );
+        assert!(!partial.complete);
+
+        // A ledger that dropped its oldest activity is never complete
+        let truncated = position_history(
+            "P".to_string(),
+            activity,
+            true,
+            (1_100_000, 1_000_000),
+            None,
+            Utc::now(),
+        );
+        assert!(!truncated.complete);
+    }
+
+    #[tokio::test]
+    async fn earn_withdraw_preview_rejects_zero_amount() {
+        let app = build_app(test_app_state().await);
+        let body = serde_json::json!({
+            "sender": "nolus1qg5ega6dykkxc307y25pecuufrjkxkaggkkxh7nad0vhyhtuhw3sqaa3c5",
+            "protocol": "P",
+            "amount": "0"
+        });
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/earn/withdraw/preview")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body = collect_body_str(resp).await;
+        assert!(body.contains("amount"), "body: {body}");
+    }
+
+    const LENDER: &str = "nolus1qg5ega6dykkxc307y25pecuufrjkxkaggkkxh7nad0vhyhtuhw3sqaa3c5";
+
+    /// State whose LPP prices 1 nLPN at 1.05 LPN and holds a 1_000_000 nLPN
+    /// deposit for `LENDER`, with `balance` LPN of which `borrowed` is lent out.
+    async fn withdraw_preview_state(
+        chain: &wiremock::MockServer,
+        balance: &str,
+        borrowed: &str,
+    ) -> Arc<AppState> {
+        use base64::Engine as _;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let contract_queries = [
+            (
+                serde_json::json!({ "price": [] }),
+                serde_json::json!({
+                    "amount": { "amount": "100" },
+                    "amount_quote": { "amount": "105" }
+                }),
+            ),
+            (
+                serde_json::json!({ "lpp_balance": [] }),
+                serde_json::json!({
+                    "balance": { "amount": balance, "ticker": "USDC" },
+                    "total_principal_due": { "amount": borrowed, "ticker": "USDC" },
+                    "total_interest_due": { "amount": "0", "ticker": "USDC" },
+                    "balance_nlpn": { "amount": "1000000" }
+                }),
+            ),
+            (
+                serde_json::json!({ "balance": { "address": LENDER } }),
+                serde_json::json!({ "amount": "1000000" }),
+            ),
+        ];
+        for (query, data) in contract_queries {
+            let encoded = base64::engine::general_purpose::STANDARD
+                .encode(serde_json::to_vec(&query).unwrap());
+            Mock::given(method("GET"))
+                .and(path(format!(
+                    "/cosmwasm/wasm/v1/contract/nolus1lpp/smart/{}",
+                    encoded
+                )))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": data })),
+                )
+                .mount(chain)
+                .await;
+        }
+
+        let mut config = crate::test_utils::test_config();
+        config.external.nolus_rest_url = chain.uri();
+        let state = crate::test_utils::test_app_state_with_config_and_client(
+            config,
+            reqwest::Client::new(),
+        )
+        .await;
+        state
+            .data_cache
+            .protocol_contracts
+            .store(std::collections::HashMap::from([(
+                "P".to_string(),
+                crate::external::chain::ProtocolContractsInfo {
+                    oracle: "nolus1oracle".to_string(),
+                    lpp: "nolus1lpp".to_string(),
+                    leaser: "nolus1leaser".to_string(),
+                    profit: "nolus1profit".to_string(),
+                    reserve: None,
+                },
+            )]));
+        state
+    }
+
+    async fn preview_withdraw_of(state: Arc<AppState>, amount: &str) -> serde_json::Value {
+        let body = serde_json::json!({ "sender": LENDER, "protocol": "P", "amount": amount });
+        let resp = build_app(state)
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/earn/withdraw/preview")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        serde_json::from_str(&collect_body_str(resp).await).unwrap()
+    }
+
+    #[tokio::test]
+    async fn earn_withdraw_preview_recommends_partial_withdraw() {
+        let chain = wiremock::MockServer::start().await;
+        // 210_000 LPN free is 200_000 nLPN at 1.05 LPN each
+        let state = withdraw_preview_state(&chain, "600000", "390000").await;
+
+        let preview = preview_withdraw_of(state, "500000").await;
+        assert_eq!(preview["can_withdraw_in_full"], false);
+        assert_eq!(preview["available_liquidity"], "210000");
+        assert_eq!(preview["max_withdrawable_nlpn"], "200000");
+        assert_eq!(preview["max_withdrawable_lpn"], "210000");
+        assert_eq!(preview["recommended_nlpn"], "200000");
+        assert_eq!(
+            preview["transaction"]["messages"][0]["msg"]["burn_deposit"]["amount"],
+            "200000"
+        );
+    }
+
+    #[tokio::test]
+    async fn earn_withdraw_preview_without_liquidity_has_no_transaction() {
+        let chain = wiremock::MockServer::start().await;
+        let state = withdraw_preview_state(&chain, "600000", "600000").await;
+
+        let preview = preview_withdraw_of(state, "500000").await;
+        assert_eq!(preview["can_withdraw_in_full"], false);
+        assert_eq!(preview["available_liquidity"], "0");
+        assert_eq!(preview["max_withdrawable_nlpn"], "0");
+        assert!(preview["recommended_nlpn"].is_null());
+        assert!(preview["transaction"].is_null());
+    }
+
+    #[test]
+    fn lpp_price_conversions_round_down() {
+        // 1 nLPN = 1.05 LPN
+        let price = (100, 105);
+        assert_eq!(nlpn_to_lpn(1_000, price), 1_050);
+        assert_eq!(lpn_to_nlpn(1_050, price), 1_000);
+        assert_eq!(lpn_to_nlpn(1_049, price), 999);
+        assert!(nlpn_to_lpn(lpn_to_nlpn(1_049, price), price) <= 1_049);
+        assert_eq!(nlpn_to_lpn(7, (0, 0)), 7);
+    }
 }
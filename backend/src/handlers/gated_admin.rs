@@ -4,20 +4,35 @@
 //! Admin endpoints show ETL data + enrichment status.
 
 use axum::{
-    extract::{Path, State},
-    Json,
+    extract::{Path, Query, State},
+    Extension, Json,
 };
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+use crate::admin_keys::AdminIdentity;
 use crate::config_store::gated_types::{
     AdminCurrencyResponse, AdminNetworkResponse, AdminProtocolResponse, CurrencyDisplayConfig,
     CurrencyDisplayInput, CurrencyEnrichmentStatus, DownpaymentRangesInput, GatedNetworkConfig,
     LeaseRulesConfig, NetworkConfigStatus, NetworkSettingsInput, ProtocolReadinessStatus,
     SwapSettingsConfig, UiSettingsConfig, UnconfiguredSummary,
 };
+use crate::config_store::versions::{
+    json_diff, ConfigDraft, ConfigVersion, ConfigVersionSummary, GatedResource, JsonChange,
+    VersionListResponse,
+};
+use crate::data_cache::{GatedConfigBundle, GatedProtocolsResponse};
 use crate::error::AppError;
+use crate::handlers::gated_assets::AssetsResponse;
+use crate::handlers::gated_networks::NetworksResponse;
+use crate::handlers::swap::SwapConfigResponse;
+use crate::propagation::user_data_filter::UserDataFilterContext;
 use crate::propagation::PropagationValidator;
+use crate::refresh::{
+    build_gated_assets, build_gated_networks, build_gated_protocols, build_swap_config,
+};
 use crate::AppState;
 
 // ============================================================================
@@ -302,10 +317,14 @@ fn trigger_gated_refresh(state: &Arc<AppState>) {
 /// Replace all currency display configs
 pub async fn replace_currency_display(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Json(config): Json<CurrencyDisplayConfig>,
 ) -> Result<Json<CurrencyDisplayConfig>, AppError> {
     info!("Admin: replacing all currency display configs");
-    state.config_store.save_currency_display(&config).await?;
+    state
+        .config_store
+        .save_currency_display(&config, Some(&identity.name))
+        .await?;
     trigger_gated_refresh(&state);
     Ok(Json(config))
 }
@@ -314,6 +333,7 @@ pub async fn replace_currency_display(
 /// Upsert a single currency display config
 pub async fn upsert_currency_display(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Path(ticker): Path<String>,
     Json(input): Json<CurrencyDisplayInput>,
 ) -> Result<Json<AdminCurrencyResponse>, AppError> {
@@ -330,7 +350,10 @@ pub async fn upsert_currency_display(
     let display = input.into();
     config.currencies.insert(ticker.clone(), display);
 
-    state.config_store.save_currency_display(&config).await?;
+    state
+        .config_store
+        .save_currency_display(&config, Some(&identity.name))
+        .await?;
     trigger_gated_refresh(&state);
 
     // Return the updated entry — present because it was just inserted above.
@@ -360,6 +383,7 @@ pub async fn upsert_currency_display(
 /// Delete a currency display config (hides currency)
 pub async fn delete_currency_display(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Path(ticker): Path<String>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     info!("Admin: deleting currency display for {}", ticker);
@@ -372,7 +396,10 @@ pub async fn delete_currency_display(
         });
     }
 
-    state.config_store.save_currency_display(&config).await?;
+    state
+        .config_store
+        .save_currency_display(&config, Some(&identity.name))
+        .await?;
     trigger_gated_refresh(&state);
 
     Ok(Json(serde_json::json!({
@@ -398,12 +425,13 @@ pub async fn get_network_config(
 /// Replace all network configs
 pub async fn replace_network_config(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Json(config): Json<GatedNetworkConfig>,
 ) -> Result<Json<GatedNetworkConfig>, AppError> {
     info!("Admin: replacing all network configs");
     state
         .config_store
-        .save_gated_network_config(&config)
+        .save_gated_network_config(&config, Some(&identity.name))
         .await?;
     trigger_gated_refresh(&state);
     Ok(Json(config))
@@ -413,6 +441,7 @@ pub async fn replace_network_config(
 /// Upsert a single network config
 pub async fn upsert_network_config(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Path(network): Path<String>,
     Json(input): Json<NetworkSettingsInput>,
 ) -> Result<Json<AdminNetworkResponse>, AppError> {
@@ -431,7 +460,7 @@ pub async fn upsert_network_config(
 
     state
         .config_store
-        .save_gated_network_config(&config)
+        .save_gated_network_config(&config, Some(&identity.name))
         .await?;
     trigger_gated_refresh(&state);
 
@@ -453,6 +482,7 @@ pub async fn upsert_network_config(
 /// Delete a network config
 pub async fn delete_network_config(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Path(network): Path<String>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     info!("Admin: deleting network config for {}", network);
@@ -467,7 +497,7 @@ pub async fn delete_network_config(
 
     state
         .config_store
-        .save_gated_network_config(&config)
+        .save_gated_network_config(&config, Some(&identity.name))
         .await?;
     trigger_gated_refresh(&state);
 
@@ -494,10 +524,14 @@ pub async fn get_lease_rules(
 /// Replace all lease rules
 pub async fn replace_lease_rules(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Json(config): Json<LeaseRulesConfig>,
 ) -> Result<Json<LeaseRulesConfig>, AppError> {
     info!("Admin: replacing all lease rules");
-    state.config_store.save_lease_rules(&config).await?;
+    state
+        .config_store
+        .save_lease_rules(&config, Some(&identity.name))
+        .await?;
     trigger_gated_refresh(&state);
     Ok(Json(config))
 }
@@ -506,6 +540,7 @@ pub async fn replace_lease_rules(
 /// Upsert downpayment ranges for a protocol
 pub async fn upsert_downpayment_ranges(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Path(protocol): Path<String>,
     Json(input): Json<DownpaymentRangesInput>,
 ) -> Result<Json<serde_json::Value>, AppError> {
@@ -515,7 +550,10 @@ pub async fn upsert_downpayment_ranges(
     config
         .downpayment_ranges
         .insert(protocol.clone(), input.ranges);
-    state.config_store.save_lease_rules(&config).await?;
+    state
+        .config_store
+        .save_lease_rules(&config, Some(&identity.name))
+        .await?;
     trigger_gated_refresh(&state);
 
     Ok(Json(serde_json::json!({
@@ -541,10 +579,14 @@ pub async fn get_swap_settings(
 /// Replace swap settings
 pub async fn replace_swap_settings(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Json(config): Json<SwapSettingsConfig>,
 ) -> Result<Json<SwapSettingsConfig>, AppError> {
     info!("Admin: replacing swap settings");
-    state.config_store.save_swap_settings(&config).await?;
+    state
+        .config_store
+        .save_swap_settings(&config, Some(&identity.name))
+        .await?;
     trigger_gated_refresh(&state);
     Ok(Json(config))
 }
@@ -566,10 +608,14 @@ pub async fn get_ui_settings(
 /// Replace UI settings
 pub async fn replace_ui_settings(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Json(config): Json<UiSettingsConfig>,
 ) -> Result<Json<UiSettingsConfig>, AppError> {
     info!("Admin: replacing UI settings");
-    state.config_store.save_ui_settings(&config).await?;
+    state
+        .config_store
+        .save_ui_settings(&config, Some(&identity.name))
+        .await?;
     trigger_gated_refresh(&state);
     Ok(Json(config))
 }
@@ -578,6 +624,7 @@ pub async fn replace_ui_settings(
 /// Add a hidden proposal
 pub async fn add_hidden_proposal(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Path(proposal_id): Path<String>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     info!("Admin: adding hidden proposal {}", proposal_id);
@@ -586,7 +633,10 @@ pub async fn add_hidden_proposal(
 
     if !config.hidden_proposals.contains(&proposal_id) {
         config.hidden_proposals.push(proposal_id.clone());
-        state.config_store.save_ui_settings(&config).await?;
+        state
+            .config_store
+            .save_ui_settings(&config, Some(&identity.name))
+            .await?;
         trigger_gated_refresh(&state);
     }
 
@@ -600,6 +650,7 @@ pub async fn add_hidden_proposal(
 /// Remove a hidden proposal
 pub async fn remove_hidden_proposal(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Path(proposal_id): Path<String>,
 ) -> Result<Json<serde_json::Value>, AppError> {
     info!("Admin: removing hidden proposal {}", proposal_id);
@@ -615,7 +666,10 @@ pub async fn remove_hidden_proposal(
         });
     }
 
-    state.config_store.save_ui_settings(&config).await?;
+    state
+        .config_store
+        .save_ui_settings(&config, Some(&identity.name))
+        .await?;
     trigger_gated_refresh(&state);
 
     Ok(Json(serde_json::json!({
@@ -624,11 +678,556 @@ pub async fn remove_hidden_proposal(
     })))
 }
 
+// ============================================================================
+// Version History
+// ============================================================================
+
+/// Query parameters for `GET /api/admin/gated/versions`
+#[derive(Debug, Deserialize)]
+pub struct VersionListQuery {
+    pub resource: Option<String>,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub limit: usize,
+}
+
+/// Query parameters for `GET /api/admin/gated/versions/:id/diff`
+#[derive(Debug, Deserialize)]
+pub struct VersionDiffQuery {
+    /// Version to diff from; defaults to the version's predecessor
+    pub against: Option<u64>,
+}
+
+/// Diff between two versions of one resource
+#[derive(Debug, Serialize)]
+pub struct VersionDiffResponse {
+    pub id: u64,
+    pub against: Option<u64>,
+    pub resource: GatedResource,
+    pub changes: Vec<JsonChange>,
+}
+
+/// GET /api/admin/gated/versions
+/// List config versions, most recent first
+pub async fn list_versions(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<VersionListQuery>,
+) -> Result<Json<VersionListResponse>, AppError> {
+    let resource = query
+        .resource
+        .as_deref()
+        .map(GatedResource::parse)
+        .transpose()?;
+    let versions = state
+        .config_store
+        .versions()
+        .list(resource, query.offset, query.limit)
+        .await;
+    Ok(Json(versions))
+}
+
+/// GET /api/admin/gated/versions/:id
+/// Get a version with its diff and full snapshot
+pub async fn get_version(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u64>,
+) -> Result<Json<ConfigVersion>, AppError> {
+    let version = state.config_store.versions().get(id).await?;
+    Ok(Json(version))
+}
+
+/// GET /api/admin/gated/versions/:id/diff
+/// Diff a version against another version of the same resource
+/// (or, without `against`, against the content it replaced)
+pub async fn diff_version(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u64>,
+    Query(query): Query<VersionDiffQuery>,
+) -> Result<Json<VersionDiffResponse>, AppError> {
+    let versions = state.config_store.versions();
+    let version = versions.get(id).await?;
+    let changes = match query.against {
+        Some(against) => versions.diff(against, id).await?,
+        None => version.diff,
+    };
+    Ok(Json(VersionDiffResponse {
+        id,
+        against: query.against,
+        resource: version.summary.resource,
+        changes,
+    }))
+}
+
+/// POST /api/admin/gated/versions/:id/rollback
+/// Restore a version's snapshot as a new version
+pub async fn rollback_version(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
+    Path(id): Path<u64>,
+) -> Result<Json<ConfigVersionSummary>, AppError> {
+    let version = state.config_store.versions().get(id).await?;
+    let resource = version.summary.resource;
+    info!(
+        "Admin: rolling back {} to version {}",
+        resource.as_str(),
+        id
+    );
+
+    resource.validate(&version.snapshot)?;
+    let restored = state
+        .config_store
+        .save_gated(resource, &version.snapshot, Some(&identity.name), Some(id))
+        .await?;
+    trigger_gated_refresh(&state);
+    Ok(Json(restored))
+}
+
+// ============================================================================
+// Drafts & Publish
+// ============================================================================
+
+/// Query parameters for `POST /api/admin/gated/drafts/:resource/publish`
+#[derive(Debug, Deserialize)]
+pub struct PublishQuery {
+    /// Publish even if the draft would take currencies, networks or
+    /// protocols offline
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// A staged draft with its changes against the live config
+#[derive(Debug, Serialize)]
+pub struct DraftResponse {
+    #[serde(flatten)]
+    pub draft: ConfigDraft,
+    pub changes: Vec<JsonChange>,
+}
+
+/// Result of publishing a draft
+#[derive(Debug, Serialize)]
+pub struct PublishResponse {
+    pub version: ConfigVersionSummary,
+    /// Regressions published anyway under `force`
+    pub regressions: Vec<String>,
+}
+
+/// GET /api/admin/gated/drafts
+/// List staged drafts
+pub async fn list_drafts(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<ConfigDraft>>, AppError> {
+    let drafts = state.config_store.versions().list_drafts().await?;
+    Ok(Json(drafts))
+}
+
+/// GET /api/admin/gated/drafts/:resource
+/// Get a staged draft and its changes against the live config
+pub async fn get_draft(
+    State(state): State<Arc<AppState>>,
+    Path(resource): Path<String>,
+) -> Result<Json<DraftResponse>, AppError> {
+    let resource = GatedResource::parse(&resource)?;
+    let draft = state.config_store.versions().load_draft(resource).await?;
+    let live = state
+        .config_store
+        .load_gated_value(resource)
+        .await
+        .unwrap_or(serde_json::Value::Null);
+    let changes = json_diff(&live, &draft.config);
+    Ok(Json(DraftResponse { draft, changes }))
+}
+
+/// PUT /api/admin/gated/drafts/:resource
+/// Stage a full replacement of a resource without making it live
+pub async fn stage_draft(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
+    Path(resource): Path<String>,
+    Json(config): Json<serde_json::Value>,
+) -> Result<Json<ConfigDraft>, AppError> {
+    let resource = GatedResource::parse(&resource)?;
+    resource.validate(&config)?;
+    info!("Admin: staging {} draft", resource.as_str());
+
+    let versions = state.config_store.versions();
+    let draft = ConfigDraft {
+        resource,
+        author: Some(identity.name),
+        updated_at: chrono::Utc::now(),
+        base_version: versions.latest_id(resource).await,
+        config,
+    };
+    versions.save_draft(&draft).await?;
+    Ok(Json(draft))
+}
+
+/// DELETE /api/admin/gated/drafts/:resource
+/// Discard a staged draft
+pub async fn discard_draft(
+    State(state): State<Arc<AppState>>,
+    Path(resource): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let resource = GatedResource::parse(&resource)?;
+    state
+        .config_store
+        .versions()
+        .discard_draft(resource)
+        .await?;
+    Ok(Json(serde_json::json!({ "discarded": resource.as_str() })))
+}
+
+/// POST /api/admin/gated/drafts/:resource/publish
+/// Validate a staged draft and make it live as a new version
+///
+/// Refused when the live config changed since the draft was staged, or when
+/// `PropagationValidator` finds the draft would take currencies, networks or
+/// protocols offline (unless `force=true`).
+pub async fn publish_draft(
+    State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
+    Path(resource): Path<String>,
+    Query(query): Query<PublishQuery>,
+) -> Result<Json<PublishResponse>, AppError> {
+    let resource = GatedResource::parse(&resource)?;
+    let versions = state.config_store.versions();
+    let draft = versions.load_draft(resource).await?;
+
+    resource.validate(&draft.config)?;
+    let regressions = publish_regressions(&state, resource, &draft.config).await?;
+    if !regressions.is_empty() && !query.force {
+        return Err(AppError::Validation {
+            message: format!(
+                "Draft would take {} item(s) offline; pass force=true to publish anyway",
+                regressions.len()
+            ),
+            field: None,
+            details: Some(serde_json::json!({ "regressions": regressions })),
+        });
+    }
+
+    info!("Admin: publishing {} draft", resource.as_str());
+    let version = state
+        .config_store
+        .publish_gated(
+            resource,
+            &draft.config,
+            Some(&identity.name),
+            draft.base_version,
+        )
+        .await?;
+    versions.discard_draft(resource).await?;
+    trigger_gated_refresh(&state);
+
+    Ok(Json(PublishResponse {
+        version,
+        regressions,
+    }))
+}
+
+/// Run `PropagationValidator` over a candidate currency-display or
+/// network-config document. Other resources don't feed propagation readiness.
+async fn publish_regressions(
+    state: &Arc<AppState>,
+    resource: GatedResource,
+    candidate: &serde_json::Value,
+) -> Result<Vec<String>, AppError> {
+    let parse_error = |e: serde_json::Error| AppError::Validation {
+        message: format!("Invalid {} config: {}", resource.as_str(), e),
+        field: None,
+        details: None,
+    };
+
+    // A live file that was never written is empty, but one that exists and
+    // can't be read is an error: treating it as empty would make every
+    // candidate item look new and hide real regressions.
+    let live_currencies = match state.config_store.load_currency_display().await {
+        Err(AppError::NotFound { .. }) => CurrencyDisplayConfig {
+            currencies: std::collections::HashMap::new(),
+        },
+        loaded => loaded?,
+    };
+    let live_networks = match state.config_store.load_gated_network_config().await {
+        Err(AppError::NotFound { .. }) => GatedNetworkConfig {
+            networks: std::collections::HashMap::new(),
+        },
+        loaded => loaded?,
+    };
+
+    let (candidate_currencies, candidate_networks) = match resource {
+        GatedResource::CurrencyDisplay => (
+            serde_json::from_value(candidate.clone()).map_err(parse_error)?,
+            live_networks.clone(),
+        ),
+        GatedResource::NetworkConfig => (
+            live_currencies.clone(),
+            serde_json::from_value(candidate.clone()).map_err(parse_error)?,
+        ),
+        GatedResource::LeaseRules | GatedResource::SwapSettings | GatedResource::UiSettings => {
+            return Ok(Vec::new());
+        }
+    };
+
+    let etl_currencies = state.etl_client.fetch_currencies().await?;
+    let etl_protocols = state.etl_client.fetch_protocols().await?;
+
+    Ok(PropagationValidator::publish_regressions(
+        &live_currencies,
+        &live_networks,
+        &candidate_currencies,
+        &candidate_networks,
+        &etl_protocols,
+        &etl_currencies,
+    ))
+}
+
+// ============================================================================
+// Dry-run Preview
+// ============================================================================
+
+/// Body of `POST /api/admin/gated/preview`
+///
+/// Each present field replaces that part of the live bundle in memory.
+/// Nothing is written to disk.
+#[derive(Debug, Default, Deserialize)]
+pub struct GatedPreviewRequest {
+    #[serde(default)]
+    pub currency_display: Option<serde_json::Value>,
+    #[serde(default)]
+    pub network_config: Option<serde_json::Value>,
+    #[serde(default)]
+    pub lease_rules: Option<serde_json::Value>,
+    #[serde(default)]
+    pub swap_settings: Option<serde_json::Value>,
+    #[serde(default)]
+    pub ui_settings: Option<serde_json::Value>,
+    /// Use staged drafts for resources not given in the body
+    #[serde(default)]
+    pub drafts: bool,
+}
+
+/// Items that would appear or disappear for users, sorted
+#[derive(Debug, Default, Serialize, PartialEq, Eq)]
+pub struct VisibilityChange {
+    pub appear: Vec<String>,
+    pub disappear: Vec<String>,
+}
+
+impl VisibilityChange {
+    fn between(live: &BTreeSet<String>, candidate: &BTreeSet<String>) -> Self {
+        Self {
+            appear: candidate.difference(live).cloned().collect(),
+            disappear: live.difference(candidate).cloned().collect(),
+        }
+    }
+}
+
+/// Impact of a proposed gated config change against the live cache
+#[derive(Debug, Serialize)]
+pub struct GatedPreviewResponse {
+    /// Resources replaced by the body or a staged draft
+    pub resources: Vec<GatedResource>,
+    pub protocols: VisibilityChange,
+    pub currencies: VisibilityChange,
+    pub networks: VisibilityChange,
+    pub pools: VisibilityChange,
+    /// Transfer routes (`NETWORK: from -> to`) and default swap targets
+    /// (`swap_to_currency: denom`, `swap_currency_<network>: denom`)
+    pub swap_routes: VisibilityChange,
+}
+
+/// POST /api/admin/gated/preview
+/// Dry-run a proposed gated config change against current ETL data
+///
+/// Rebuilds the gated protocol, asset, network and swap views plus the user
+/// data filter context from the candidate bundle, exactly as the background
+/// refresh would, and reports what appears or disappears relative to what is
+/// served now.
+pub async fn preview_gated_change(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<GatedPreviewRequest>,
+) -> Result<Json<GatedPreviewResponse>, AppError> {
+    let cache = &state.data_cache;
+    let mut candidate = cache.gated_config.load_or_unavailable("Gated config")?;
+
+    let overrides = [
+        (GatedResource::CurrencyDisplay, request.currency_display),
+        (GatedResource::NetworkConfig, request.network_config),
+        (GatedResource::LeaseRules, request.lease_rules),
+        (GatedResource::SwapSettings, request.swap_settings),
+        (GatedResource::UiSettings, request.ui_settings),
+    ];
+    let mut resources = Vec::new();
+    for (resource, value) in overrides {
+        let value = match value {
+            Some(value) => value,
+            None if request.drafts => {
+                match state.config_store.versions().load_draft(resource).await {
+                    Ok(draft) => draft.config,
+                    Err(AppError::NotFound { .. }) => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+            None => continue,
+        };
+        apply_override(&mut candidate, resource, value)?;
+        resources.push(resource);
+    }
+    debug!("Admin: previewing gated change to {:?}", resources);
+
+    let live_protocols = cache
+        .gated_protocols
+        .load_or_unavailable("Gated protocols")?;
+    let live_assets = cache.gated_assets.load_or_unavailable("Gated assets")?;
+    let live_networks = cache.gated_networks.load_or_unavailable("Gated networks")?;
+    let live_swap = cache.swap_config.load_or_unavailable("Swap config")?;
+    let live_filter = cache.filter_context.load_or_unavailable("Filter context")?;
+    let pools = cache.pools.load().unwrap_or_default();
+    let prices = cache.prices.load().map(|p| p.prices).unwrap_or_default();
+
+    let (etl_protocols, etl_currencies) = tokio::try_join!(
+        state.etl_client.fetch_protocols(),
+        state.etl_client.fetch_currencies(),
+    )?;
+
+    let protocols = build_gated_protocols(&candidate, &etl_protocols);
+    let assets = build_gated_assets(&candidate, &etl_currencies, &etl_protocols, &prices);
+    let networks = build_gated_networks(&candidate.network_config);
+    let swap = build_swap_config(&candidate.swap_settings, &etl_protocols, &etl_currencies);
+    let filter = UserDataFilterContext::from_config(
+        &etl_protocols,
+        &candidate.currency_display,
+        &candidate.network_config,
+        &candidate.lease_rules,
+    );
+
+    let protocol_names = |response: &GatedProtocolsResponse| -> BTreeSet<String> {
+        response
+            .protocols
+            .iter()
+            .map(|p| p.protocol.clone())
+            .collect()
+    };
+    let tickers = |response: &AssetsResponse| -> BTreeSet<String> {
+        response.assets.iter().map(|a| a.ticker.clone()).collect()
+    };
+    let network_keys = |response: &NetworksResponse| -> BTreeSet<String> {
+        response
+            .networks
+            .iter()
+            .map(|n| n.network.clone())
+            .collect()
+    };
+    let visible_pools = |filter: &UserDataFilterContext| -> BTreeSet<String> {
+        pools
+            .iter()
+            .filter(|pool| filter.is_earn_position_visible(&pool.protocol))
+            .map(|pool| pool.protocol.clone())
+            .collect()
+    };
+    let denom_tickers: HashMap<&str, &str> = etl_currencies
+        .currencies
+        .iter()
+        .flat_map(|c| {
+            c.protocols
+                .iter()
+                .map(|p| (p.bank_symbol.as_str(), c.ticker.as_str()))
+        })
+        .collect();
+
+    Ok(Json(GatedPreviewResponse {
+        resources,
+        protocols: VisibilityChange::between(
+            &protocol_names(&live_protocols),
+            &protocol_names(&protocols),
+        ),
+        currencies: VisibilityChange::between(&tickers(&live_assets), &tickers(&assets)),
+        networks: VisibilityChange::between(
+            &network_keys(&live_networks),
+            &network_keys(&networks),
+        ),
+        pools: VisibilityChange::between(&visible_pools(&live_filter), &visible_pools(&filter)),
+        swap_routes: VisibilityChange::between(
+            &swap_routes(&live_swap, &denom_tickers),
+            &swap_routes(&swap, &denom_tickers),
+        ),
+    }))
+}
+
+/// Replace one part of `bundle` with a candidate document
+fn apply_override(
+    bundle: &mut GatedConfigBundle,
+    resource: GatedResource,
+    value: serde_json::Value,
+) -> Result<(), AppError> {
+    let parse_error = |e: serde_json::Error| AppError::Validation {
+        message: format!("Invalid {} config: {}", resource.as_str(), e),
+        field: Some(resource.as_str().to_string()),
+        details: None,
+    };
+    match resource {
+        GatedResource::CurrencyDisplay => {
+            bundle.currency_display = serde_json::from_value(value).map_err(parse_error)?;
+        }
+        GatedResource::NetworkConfig => {
+            bundle.network_config = serde_json::from_value(value).map_err(parse_error)?;
+        }
+        GatedResource::LeaseRules => {
+            bundle.lease_rules = serde_json::from_value(value).map_err(parse_error)?;
+        }
+        GatedResource::SwapSettings => {
+            bundle.swap_settings = serde_json::from_value(value).map_err(parse_error)?;
+        }
+        GatedResource::UiSettings => {
+            bundle.ui_settings = serde_json::from_value(value).map_err(parse_error)?;
+        }
+    }
+    Ok(())
+}
+
+/// Swap routes offered by a swap config. Transfers of blacklisted tickers
+/// and unresolved default targets are not routes.
+fn swap_routes(
+    config: &SwapConfigResponse,
+    denom_tickers: &HashMap<&str, &str>,
+) -> BTreeSet<String> {
+    let blacklisted = |denom: &str| {
+        denom_tickers
+            .get(denom)
+            .is_some_and(|ticker| config.blacklist.iter().any(|b| b == ticker))
+    };
+    let mut routes: BTreeSet<String> = config
+        .transfers
+        .iter()
+        .flat_map(|(network, transfers)| {
+            transfers
+                .currencies
+                .iter()
+                .filter(|c| !blacklisted(&c.from))
+                .map(move |c| format!("{network}: {} -> {}", c.from, c.to))
+        })
+        .collect();
+    if !config.swap_to_currency.is_empty() {
+        routes.insert(format!("swap_to_currency: {}", config.swap_to_currency));
+    }
+    routes.extend(
+        config
+            .swap_currencies
+            .iter()
+            .filter(|(_, denom)| !denom.is_empty())
+            .map(|(key, denom)| format!("{key}: {denom}")),
+    );
+    routes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::middleware::admin_auth_middleware;
-    use crate::test_utils::{test_app_state_with_config, test_config_with_admin};
+    use crate::test_utils::{
+        collect_body_str, test_app_state_with_config, test_app_state_with_config_and_client,
+        test_config_with_admin,
+    };
     use axum::{
         body::Body,
         http::{Request, StatusCode},
@@ -700,4 +1299,368 @@ mod tests {
             .unwrap();
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
+
+    async fn versions_router() -> (Router, Arc<AppState>) {
+        let state = test_app_state_with_config(test_config_with_admin(true, "s3cret")).await;
+        let router = Router::new()
+            .route(
+                "/api/admin/gated/versions/{id}/rollback",
+                axum::routing::post(rollback_version),
+            )
+            .route(
+                "/api/admin/gated/drafts/{resource}",
+                axum::routing::put(stage_draft),
+            )
+            .route(
+                "/api/admin/gated/drafts/{resource}/publish",
+                axum::routing::post(publish_draft),
+            )
+            .layer(from_fn_with_state(state.clone(), admin_auth_middleware))
+            .with_state(state.clone());
+        (router, state)
+    }
+
+    fn admin_request(method: &str, uri: &str, body: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Authorization", "Bearer s3cret")
+            .header("Content-Type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn hidden(ids: &[&str]) -> UiSettingsConfig {
+        UiSettingsConfig {
+            hidden_proposals: ids.iter().map(|id| id.to_string()).collect(),
+            ..UiSettingsConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn rollback_restores_snapshot_as_new_version() {
+        let (app, state) = versions_router().await;
+        state
+            .config_store
+            .save_ui_settings(&hidden(&["1"]), None)
+            .await
+            .unwrap();
+        state
+            .config_store
+            .save_ui_settings(&hidden(&["1", "2"]), None)
+            .await
+            .unwrap();
+
+        let resp = app
+            .oneshot(admin_request(
+                "POST",
+                "/api/admin/gated/versions/1/rollback",
+                "",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let live = state.config_store.load_ui_settings().await.unwrap();
+        assert_eq!(live.hidden_proposals, vec!["1".to_string()]);
+        let restored = state.config_store.versions().get(3).await.unwrap();
+        assert_eq!(restored.summary.rollback_of, Some(1));
+        assert_eq!(restored.summary.author.as_deref(), Some("root"));
+    }
+
+    #[tokio::test]
+    async fn staged_draft_is_not_live_until_published() {
+        let (app, state) = versions_router().await;
+        let resp = app
+            .clone()
+            .oneshot(admin_request(
+                "PUT",
+                "/api/admin/gated/drafts/ui-settings",
+                r#"{"hidden_proposals":["7"]}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(state.config_store.load_ui_settings().await.is_err());
+
+        let resp = app
+            .oneshot(admin_request(
+                "POST",
+                "/api/admin/gated/drafts/ui-settings/publish",
+                "",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let live = state.config_store.load_ui_settings().await.unwrap();
+        assert_eq!(live.hidden_proposals, vec!["7".to_string()]);
+        assert!(state
+            .config_store
+            .versions()
+            .load_draft(GatedResource::UiSettings)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn stale_draft_is_refused() {
+        let (app, state) = versions_router().await;
+        let resp = app
+            .clone()
+            .oneshot(admin_request(
+                "PUT",
+                "/api/admin/gated/drafts/ui-settings",
+                r#"{"hidden_proposals":["7"]}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Live config moves on after the draft was staged.
+        state
+            .config_store
+            .save_ui_settings(&hidden(&["8"]), None)
+            .await
+            .unwrap();
+
+        let resp = app
+            .oneshot(admin_request(
+                "POST",
+                "/api/admin/gated/drafts/ui-settings/publish",
+                "",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let live = state.config_store.load_ui_settings().await.unwrap();
+        assert_eq!(live.hidden_proposals, vec!["8".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn invalid_draft_is_rejected_at_staging() {
+        let (app, _state) = versions_router().await;
+        let resp = app
+            .oneshot(admin_request(
+                "PUT",
+                "/api/admin/gated/drafts/swap-settings",
+                r#"{"blacklist":[]}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    const PREVIEW_PROTOCOL: &str = "OSMOSIS-OSMOSIS-USDC_NOBLE";
+
+    fn preview_bundle() -> GatedConfigBundle {
+        GatedConfigBundle {
+            currency_display: serde_json::from_value(serde_json::json!({
+                "USDC": { "icon": "/icons/usdc.svg", "displayName": "USD Coin" }
+            }))
+            .unwrap(),
+            network_config: serde_json::from_value(serde_json::json!({
+                "OSMOSIS": {
+                    "name": "Osmosis", "chain_id": "osmosis-1", "prefix": "osmo",
+                    "rpc": "https://rpc.osmosis.zone", "lcd": "https://lcd.osmosis.zone",
+                    "gas_price": "0.025uosmo", "gas_multiplier": 3.5
+                }
+            }))
+            .unwrap(),
+            lease_rules: serde_json::from_value(serde_json::json!({})).unwrap(),
+            swap_settings: serde_json::from_value(serde_json::json!({
+                "api_url": "https://skip.example",
+                "swap_to_currency": "USDC"
+            }))
+            .unwrap(),
+            ui_settings: UiSettingsConfig::default(),
+        }
+    }
+
+    /// Live caches populated by the real refresh tasks from a mocked ETL
+    /// serving one protocol and its LPN.
+    async fn preview_router() -> (Router, Arc<AppState>, wiremock::MockServer) {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let etl = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/protocols"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "protocols": [{
+                    "name": PREVIEW_PROTOCOL,
+                    "network": "osmosis",
+                    "dex": "Osmosis",
+                    "position_type": "long",
+                    "lpn_symbol": "USDC",
+                    "is_active": true,
+                    "contracts": {
+                        "leaser": "nolus1leaser", "lpp": "nolus1lpp",
+                        "oracle": "nolus1oracle", "profit": "nolus1profit",
+                        "reserve": null
+                    }
+                }],
+                "count": 1, "active_count": 1, "deprecated_count": 0
+            })))
+            .mount(&etl)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/currencies"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "currencies": [{
+                    "ticker": "USDC",
+                    "decimal_digits": 6,
+                    "is_active": true,
+                    "protocols": [{
+                        "protocol": PREVIEW_PROTOCOL,
+                        "group": "lpn",
+                        "bank_symbol": "ibc/USDC-NOLUS",
+                        "dex_symbol": "ibc/USDC-DEX"
+                    }]
+                }],
+                "count": 1, "active_count": 1, "deprecated_count": 0
+            })))
+            .mount(&etl)
+            .await;
+
+        let mut config = test_config_with_admin(true, "s3cret");
+        config.external.etl_api_url = etl.uri();
+        let state = test_app_state_with_config_and_client(config, reqwest::Client::new()).await;
+        let cache = &state.data_cache;
+        cache.gated_config.store(preview_bundle());
+        cache
+            .prices
+            .store(crate::handlers::currencies::PricesResponse {
+                prices: HashMap::new(),
+                updated_at: String::new(),
+            });
+        cache.pools.store(vec![crate::handlers::earn::EarnPool {
+            protocol: PREVIEW_PROTOCOL.to_string(),
+            lpp_address: "nolus1lpp".to_string(),
+            currency: "USDC".to_string(),
+            total_deposited: "0".to_string(),
+            total_deposited_usd: None,
+            apy: 0.0,
+            utilization: 0.0,
+            available_liquidity: "0".to_string(),
+            deposit_capacity: None,
+            icon: None,
+        }]);
+        crate::refresh::refresh_filter_context(&state).await;
+        crate::refresh::refresh_gated_protocols(&state).await;
+        crate::refresh::refresh_gated_assets(&state).await;
+        crate::refresh::refresh_gated_networks(&state).await;
+        crate::refresh::refresh_swap_config(&state).await;
+
+        let router = Router::new()
+            .route(
+                "/api/admin/gated/preview",
+                axum::routing::post(preview_gated_change),
+            )
+            .layer(from_fn_with_state(state.clone(), admin_auth_middleware))
+            .with_state(state.clone());
+        (router, state, etl)
+    }
+
+    async fn preview(app: Router, body: &str) -> (StatusCode, serde_json::Value) {
+        let resp = app
+            .oneshot(admin_request("POST", "/api/admin/gated/preview", body))
+            .await
+            .unwrap();
+        let status = resp.status();
+        let body = collect_body_str(resp).await;
+        (status, serde_json::from_str(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn preview_without_changes_reports_nothing() {
+        let (app, _state, _etl) = preview_router().await;
+        let (status, body) = preview(app, "{}").await;
+        assert_eq!(status, StatusCode::OK);
+        let empty = serde_json::json!({ "appear": [], "disappear": [] });
+        for category in [
+            "protocols",
+            "currencies",
+            "networks",
+            "pools",
+            "swap_routes",
+        ] {
+            assert_eq!(body[category], empty, "{category}");
+        }
+    }
+
+    #[tokio::test]
+    async fn preview_hiding_lpn_reports_everything_it_takes_down() {
+        let (app, state, _etl) = preview_router().await;
+        let (status, body) = preview(app, r#"{"currency_display":{}}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["resources"], serde_json::json!(["currency-display"]));
+        assert_eq!(
+            body["protocols"]["disappear"],
+            serde_json::json!([PREVIEW_PROTOCOL])
+        );
+        assert_eq!(body["currencies"]["disappear"], serde_json::json!(["USDC"]));
+        assert_eq!(
+            body["pools"]["disappear"],
+            serde_json::json!([PREVIEW_PROTOCOL])
+        );
+        // Networks come from network config alone.
+        assert_eq!(body["networks"]["disappear"], serde_json::json!([]));
+
+        // Nothing was applied.
+        let live = state.data_cache.gated_config.load().unwrap();
+        assert!(live.currency_display.currencies.contains_key("USDC"));
+    }
+
+    #[tokio::test]
+    async fn preview_reports_network_and_swap_route_changes() {
+        let (app, _state, _etl) = preview_router().await;
+        let (status, body) = preview(
+            app,
+            r#"{"network_config":{},"swap_settings":{"api_url":"https://skip.example","blacklist":["USDC"]}}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body["networks"]["disappear"],
+            serde_json::json!(["OSMOSIS"])
+        );
+        assert_eq!(
+            body["swap_routes"]["disappear"],
+            serde_json::json!([
+                "OSMOSIS: ibc/USDC-NOLUS -> ibc/USDC-DEX",
+                "swap_to_currency: ibc/USDC-NOLUS"
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn preview_rejects_invalid_override() {
+        let (app, _state, _etl) = preview_router().await;
+        let (status, _body) = preview(app, r#"{"swap_settings":{"blacklist":[]}}"#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn preview_can_overlay_staged_drafts() {
+        let (app, state, _etl) = preview_router().await;
+        state
+            .config_store
+            .versions()
+            .save_draft(&ConfigDraft {
+                resource: GatedResource::NetworkConfig,
+                author: None,
+                updated_at: chrono::Utc::now(),
+                base_version: None,
+                config: serde_json::json!({}),
+            })
+            .await
+            .unwrap();
+        let (status, body) = preview(app, r#"{"drafts":true}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["resources"], serde_json::json!(["network-config"]));
+        assert_eq!(
+            body["networks"]["disappear"],
+            serde_json::json!(["OSMOSIS"])
+        );
+    }
 }
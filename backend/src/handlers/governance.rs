@@ -1,6 +1,8 @@
 //! Governance handlers
 //!
-//! Provides endpoints for governance proposals, voting, and params.
+//! Provides endpoints for governance proposals, voting, and params, plus
+//! unsigned `MsgVote` / `MsgVoteWeighted` / `MsgDeposit` / `MsgSubmitProposal`
+//! builders that check the proposal's period against the cached proposal list.
 
 use std::sync::Arc;
 
@@ -9,16 +11,26 @@ use axum::{
     http::{HeaderMap, HeaderValue},
     Json,
 };
+use chrono::{DateTime, Utc};
 use futures::future;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 use utoipa::{IntoParams, ToSchema};
 
+use crate::handlers::proposal_messages::{self, ProposalMessageSummary};
+use crate::handlers::tx_builder::{self, GasEstimate, MsgKind};
+use crate::num_utils::u128_to_f64;
 use crate::{error::AppError, external::chain, AppState};
 
 /// Status string the chain reports for proposals currently accepting votes.
 const PROPOSAL_STATUS_VOTING_PERIOD: &str = "PROPOSAL_STATUS_VOTING_PERIOD";
 
+/// Status string the chain reports for proposals still collecting deposits.
+const PROPOSAL_STATUS_DEPOSIT_PERIOD: &str = "PROPOSAL_STATUS_DEPOSIT_PERIOD";
+
+/// Scale of a cosmos `Dec` (18 fractional digits).
+const DEC_PRECISION: u32 = 18;
+
 // ============================================================================
 // Hidden Proposals
 // ============================================================================
@@ -90,6 +102,30 @@ pub struct ProposalResponse {
     pub voted: Option<bool>,
 }
 
+impl ProposalResponse {
+    fn from_cached(
+        p: chain::Proposal,
+        tally: Option<chain::TallyResult>,
+        voted: Option<bool>,
+    ) -> Self {
+        Self {
+            id: p.id,
+            status: p.status,
+            final_tally_result: p.final_tally_result,
+            submit_time: p.submit_time,
+            deposit_end_time: p.deposit_end_time,
+            voting_start_time: p.voting_start_time,
+            voting_end_time: p.voting_end_time,
+            title: p.title,
+            summary: p.summary,
+            messages: p.messages,
+            metadata: p.metadata,
+            tally,
+            voted,
+        }
+    }
+}
+
 /// Proposals list response
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ProposalsListResponse {
@@ -231,28 +267,13 @@ pub async fn get_proposals(
         .into_iter()
         .map(|p| {
             let id = p.id.clone();
-            let status = p.status.clone();
-            let tally = if status == PROPOSAL_STATUS_VOTING_PERIOD {
+            let tally = if p.status == PROPOSAL_STATUS_VOTING_PERIOD {
                 snapshot.tallies.get(&id).cloned()
             } else {
                 None
             };
             let voted = voted_map.get(&id).copied();
-            ProposalResponse {
-                id,
-                status,
-                final_tally_result: p.final_tally_result,
-                submit_time: p.submit_time,
-                deposit_end_time: p.deposit_end_time,
-                voting_start_time: p.voting_start_time,
-                voting_end_time: p.voting_end_time,
-                title: p.title,
-                summary: p.summary,
-                messages: p.messages,
-                metadata: p.metadata,
-                tally,
-                voted,
-            }
+            ProposalResponse::from_cached(p, tally, voted)
         })
         .collect();
 
@@ -275,6 +296,215 @@ pub async fn get_proposals(
     ))
 }
 
+// ============================================================================
+// Proposal Detail
+// ============================================================================
+
+/// A tally observed by the refresh task while the proposal was voting
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TallySample {
+    pub at: DateTime<Utc>,
+    pub tally: chain::TallyResult,
+}
+
+/// How the proposal would end if voting closed with the current tally
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectedOutcome {
+    Passes,
+    Rejected,
+    Vetoed,
+    NoQuorum,
+}
+
+/// Tally measured against the chain's tallying parameters. Ratios are
+/// fractions (0..1) like the parameters themselves.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TallyProgress {
+    /// Bonded stake the turnout is measured against (current, not historical)
+    pub bonded_tokens: String,
+    pub total_voted: String,
+    /// Share of the bonded stake that voted
+    pub turnout: f64,
+    pub quorum: f64,
+    pub quorum_reached: bool,
+    /// Yes share of the non-abstain votes
+    pub yes_ratio: f64,
+    pub threshold: f64,
+    pub threshold_reached: bool,
+    /// NoWithVeto share of all votes
+    pub veto_ratio: f64,
+    pub veto_threshold: f64,
+    pub vetoed: bool,
+    pub outcome: ProjectedOutcome,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProposalDetailResponse {
+    pub proposal: ProposalResponse,
+    /// `proposal.messages` decoded into typed, human-readable summaries
+    pub decoded_messages: Vec<ProposalMessageSummary>,
+    /// Absent when there is no tally yet or the tallying parameters or
+    /// staking pool are unavailable
+    pub progress: Option<TallyProgress>,
+    /// Oldest first
+    pub tally_history: Vec<TallySample>,
+}
+
+/// Get a governance proposal
+///
+/// Returns one cached proposal with its messages decoded (parameter changes,
+/// software upgrades, protocol contract migrations, community pool spends),
+/// quorum and threshold progress of its current tally against the live
+/// tallying parameters and bonded stake, and the tally history sampled by the
+/// refresh task during voting. Hidden proposals are reported as not found.
+#[utoipa::path(
+    get,
+    path = "/api/governance/proposals/{proposal_id}",
+    tag = "governance",
+    params(
+        ("proposal_id" = String, Path, description = "Governance proposal ID"),
+    ),
+    responses(
+        (status = 200, description = "Proposal detail", body = ProposalDetailResponse),
+        (status = 404, description = "Proposal not found", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn get_proposal_detail(
+    State(state): State<Arc<AppState>>,
+    Path(proposal_id): Path<String>,
+) -> Result<Json<ProposalDetailResponse>, AppError> {
+    debug!("Fetching proposal detail: {}", proposal_id);
+
+    let snapshot = state
+        .data_cache
+        .proposals_with_tally
+        .load_or_unavailable("Proposals")?;
+    let hidden = state
+        .data_cache
+        .gated_config
+        .load()
+        .is_some_and(|g| g.ui_settings.hidden_proposals.contains(&proposal_id));
+    let proposal = snapshot
+        .proposals
+        .into_iter()
+        .find(|p| p.id == proposal_id)
+        .filter(|_| !hidden)
+        .ok_or_else(|| AppError::NotFound {
+            resource: format!("Proposal {}", proposal_id),
+        })?;
+
+    let tally = if proposal.status == PROPOSAL_STATUS_VOTING_PERIOD {
+        snapshot.tallies.get(&proposal_id).cloned()
+    } else {
+        None
+    };
+    let progress = match tally.as_ref().or(proposal.final_tally_result.as_ref()) {
+        Some(current) => fetch_tally_progress(&state, current).await,
+        None => None,
+    };
+    let decoded_messages = proposal_messages::summarize_messages(
+        &proposal.messages,
+        state.data_cache.protocol_contracts.load().as_ref(),
+    );
+    let tally_history = snapshot
+        .tally_history
+        .get(&proposal_id)
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(Json(ProposalDetailResponse {
+        proposal: ProposalResponse::from_cached(proposal, tally, None),
+        decoded_messages,
+        progress,
+        tally_history,
+    }))
+}
+
+/// Progress of `tally` against the live tallying params and cached bonded
+/// stake; `None` (logged) when either input is unavailable.
+async fn fetch_tally_progress(
+    state: &AppState,
+    tally: &chain::TallyResult,
+) -> Option<TallyProgress> {
+    let params = match state.chain_client.get_tallying_params().await {
+        Ok(response) => response.params,
+        Err(e) => {
+            warn!(
+                "Failed to fetch tallying params for proposal progress: {}",
+                e
+            );
+            return None;
+        }
+    };
+    let pool = state.data_cache.staking_pool.load()?;
+    tally_progress(tally, &params, &pool.pool.bonded_tokens)
+}
+
+/// Apply the gov module's tally rules: quorum on turnout, then veto over all
+/// votes, then the yes threshold over non-abstain votes.
+fn tally_progress(
+    tally: &chain::TallyResult,
+    params: &chain::TallyingParams,
+    bonded_tokens: &str,
+) -> Option<TallyProgress> {
+    let yes = tally.yes_count.parse::<u128>().ok()?;
+    let abstain = tally.abstain_count.parse::<u128>().ok()?;
+    let no = tally.no_count.parse::<u128>().ok()?;
+    let veto = tally.no_with_veto_count.parse::<u128>().ok()?;
+    let bonded = bonded_tokens.parse::<u128>().ok()?;
+    let quorum = params.quorum.parse::<f64>().ok()?;
+    let threshold = params.threshold.parse::<f64>().ok()?;
+    let veto_threshold = params.veto_threshold.parse::<f64>().ok()?;
+
+    let ratio = |part: u128, whole: u128| {
+        if whole == 0 {
+            0.0
+        } else {
+            u128_to_f64(part) / u128_to_f64(whole)
+        }
+    };
+    let total = yes
+        .saturating_add(abstain)
+        .saturating_add(no)
+        .saturating_add(veto);
+    let non_abstain = total.saturating_sub(abstain);
+    let turnout = ratio(total, bonded);
+    let yes_ratio = ratio(yes, non_abstain);
+    let veto_ratio = ratio(veto, total);
+
+    let quorum_reached = bonded > 0 && turnout >= quorum;
+    let vetoed = veto_ratio > veto_threshold;
+    let threshold_reached = yes_ratio > threshold;
+    let outcome = if !quorum_reached {
+        ProjectedOutcome::NoQuorum
+    } else if non_abstain == 0 {
+        ProjectedOutcome::Rejected
+    } else if vetoed {
+        ProjectedOutcome::Vetoed
+    } else if threshold_reached {
+        ProjectedOutcome::Passes
+    } else {
+        ProjectedOutcome::Rejected
+    };
+
+    Some(TallyProgress {
+        bonded_tokens: bonded_tokens.to_string(),
+        total_voted: total.to_string(),
+        turnout,
+        quorum,
+        quorum_reached,
+        yes_ratio,
+        threshold,
+        threshold_reached,
+        veto_ratio,
+        veto_threshold,
+        vetoed,
+        outcome,
+    })
+}
+
 /// Get proposal tally
 ///
 /// Returns the current (live) tally for a proposal.
@@ -523,12 +753,458 @@ pub async fn get_network_status(
     Ok(Json(status))
 }
 
+// ============================================================================
+// Transaction Builders
+// ============================================================================
+
+/// A vote option
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteOption {
+    Yes,
+    Abstain,
+    No,
+    NoWithVeto,
+}
+
+impl VoteOption {
+    /// The `cosmos.gov.v1.VoteOption` enum name
+    const fn proto_name(self) -> &'static str {
+        match self {
+            Self::Yes => "VOTE_OPTION_YES",
+            Self::Abstain => "VOTE_OPTION_ABSTAIN",
+            Self::No => "VOTE_OPTION_NO",
+            Self::NoWithVeto => "VOTE_OPTION_NO_WITH_VETO",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VoteRequest {
+    /// Voter address signing the transaction
+    pub voter: String,
+    pub proposal_id: String,
+    pub option: VoteOption,
+    /// Optional vote metadata
+    #[serde(default)]
+    pub metadata: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WeightedVoteOption {
+    pub option: VoteOption,
+    /// Decimal weight (e.g. `0.25`); the weights of a vote must sum to 1
+    pub weight: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WeightedVoteRequest {
+    /// Voter address signing the transaction
+    pub voter: String,
+    pub proposal_id: String,
+    pub options: Vec<WeightedVoteOption>,
+    /// Optional vote metadata
+    #[serde(default)]
+    pub metadata: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ProposalDepositRequest {
+    /// Depositor address signing the transaction
+    pub depositor: String,
+    pub proposal_id: String,
+    pub amount: String,
+    /// Bank denom to deposit; defaults to the native denom
+    #[serde(default)]
+    pub denom: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SubmitProposalRequest {
+    /// Proposer address signing the transaction
+    pub proposer: String,
+    pub title: String,
+    pub summary: String,
+    /// Optional proposal metadata (usually an IPFS link)
+    #[serde(default)]
+    pub metadata: Option<String>,
+    /// Messages executed by the governance module if the proposal passes;
+    /// empty for a text proposal. Each must carry an `@type`.
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub messages: Vec<serde_json::Value>,
+    /// Initial deposit amount (minimal denomination)
+    pub initial_deposit: String,
+    /// Bank denom of the initial deposit; defaults to the native denom
+    #[serde(default)]
+    pub denom: Option<String>,
+    #[serde(default)]
+    pub expedited: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GovernanceTransactionResponse {
+    /// Unsigned Cosmos SDK messages for client-side signing
+    #[schema(value_type = Vec<Object>)]
+    pub messages: Vec<serde_json::Value>,
+    pub memo: String,
+    pub gas: GasEstimate,
+}
+
+/// Build a vote transaction
+///
+/// Returns a ready-to-sign `MsgVote`. The proposal must be in its voting
+/// period according to the cached proposal list.
+#[utoipa::path(
+    post,
+    path = "/api/governance/vote",
+    tag = "governance",
+    request_body = VoteRequest,
+    responses(
+        (status = 200, description = "Unsigned vote transaction", body = GovernanceTransactionResponse),
+        (status = 400, description = "Invalid request or proposal not in voting period", body = crate::error::ErrorResponse),
+        (status = 404, description = "Proposal not found", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn vote(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<VoteRequest>,
+) -> Result<Json<GovernanceTransactionResponse>, AppError> {
+    debug!(
+        "Building vote transaction for proposal {}",
+        request.proposal_id
+    );
+
+    crate::validation::validate_nolus_address(&request.voter, "voter")?;
+    require_proposal_status(
+        &state,
+        &request.proposal_id,
+        &[PROPOSAL_STATUS_VOTING_PERIOD],
+    )?;
+
+    let vote_msg = serde_json::json!({
+        "@type": "/cosmos.gov.v1.MsgVote",
+        "proposal_id": request.proposal_id,
+        "voter": request.voter,
+        "option": request.option.proto_name(),
+        "metadata": request.metadata.unwrap_or_default()
+    });
+
+    Ok(Json(GovernanceTransactionResponse {
+        messages: vec![vote_msg],
+        memo: format!("Vote on proposal #{}", request.proposal_id),
+        gas: governance_gas(&state, MsgKind::Vote),
+    }))
+}
+
+/// Build a weighted vote transaction
+///
+/// Returns a ready-to-sign `MsgVoteWeighted` splitting the voting power across
+/// options. Weights are decimals that must be positive, name each option at
+/// most once and sum to exactly 1. The proposal must be in its voting period.
+#[utoipa::path(
+    post,
+    path = "/api/governance/vote-weighted",
+    tag = "governance",
+    request_body = WeightedVoteRequest,
+    responses(
+        (status = 200, description = "Unsigned weighted vote transaction", body = GovernanceTransactionResponse),
+        (status = 400, description = "Invalid request or proposal not in voting period", body = crate::error::ErrorResponse),
+        (status = 404, description = "Proposal not found", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn vote_weighted(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<WeightedVoteRequest>,
+) -> Result<Json<GovernanceTransactionResponse>, AppError> {
+    debug!(
+        "Building weighted vote transaction for proposal {}",
+        request.proposal_id
+    );
+
+    crate::validation::validate_nolus_address(&request.voter, "voter")?;
+    let options = validate_vote_weights(&request.options)?;
+    require_proposal_status(
+        &state,
+        &request.proposal_id,
+        &[PROPOSAL_STATUS_VOTING_PERIOD],
+    )?;
+
+    let vote_msg = serde_json::json!({
+        "@type": "/cosmos.gov.v1.MsgVoteWeighted",
+        "proposal_id": request.proposal_id,
+        "voter": request.voter,
+        "options": options,
+        "metadata": request.metadata.unwrap_or_default()
+    });
+
+    Ok(Json(GovernanceTransactionResponse {
+        messages: vec![vote_msg],
+        memo: format!("Weighted vote on proposal #{}", request.proposal_id),
+        gas: governance_gas(&state, MsgKind::Vote),
+    }))
+}
+
+/// Build a proposal deposit transaction
+///
+/// Returns a ready-to-sign `MsgDeposit`. The chain accepts deposits during the
+/// deposit and the voting period; any other status is rejected.
+#[utoipa::path(
+    post,
+    path = "/api/governance/deposit",
+    tag = "governance",
+    request_body = ProposalDepositRequest,
+    responses(
+        (status = 200, description = "Unsigned deposit transaction", body = GovernanceTransactionResponse),
+        (status = 400, description = "Invalid request or proposal not accepting deposits", body = crate::error::ErrorResponse),
+        (status = 404, description = "Proposal not found", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn deposit(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ProposalDepositRequest>,
+) -> Result<Json<GovernanceTransactionResponse>, AppError> {
+    debug!(
+        "Building deposit transaction for proposal {}",
+        request.proposal_id
+    );
+
+    crate::validation::validate_nolus_address(&request.depositor, "depositor")?;
+    tx_builder::parse_positive_amount(&request.amount, "amount")?;
+    require_proposal_status(
+        &state,
+        &request.proposal_id,
+        &[
+            PROPOSAL_STATUS_DEPOSIT_PERIOD,
+            PROPOSAL_STATUS_VOTING_PERIOD,
+        ],
+    )?;
+    let denom = resolve_deposit_denom(&state, request.denom)?;
+
+    let deposit_msg = serde_json::json!({
+        "@type": "/cosmos.gov.v1.MsgDeposit",
+        "proposal_id": request.proposal_id,
+        "depositor": request.depositor,
+        "amount": [{
+            "denom": denom,
+            "amount": request.amount
+        }]
+    });
+
+    Ok(Json(GovernanceTransactionResponse {
+        messages: vec![deposit_msg],
+        memo: format!("Deposit on proposal #{}", request.proposal_id),
+        gas: governance_gas(&state, MsgKind::Deposit),
+    }))
+}
+
+/// Build a proposal submission transaction
+///
+/// Returns a ready-to-sign `MsgSubmitProposal` with the given title, summary,
+/// executable messages (none for a text proposal) and initial deposit.
+#[utoipa::path(
+    post,
+    path = "/api/governance/submit-proposal",
+    tag = "governance",
+    request_body = SubmitProposalRequest,
+    responses(
+        (status = 200, description = "Unsigned proposal submission", body = GovernanceTransactionResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn submit_proposal(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SubmitProposalRequest>,
+) -> Result<Json<GovernanceTransactionResponse>, AppError> {
+    debug!("Building proposal submission: {}", request.title);
+
+    crate::validation::validate_nolus_address(&request.proposer, "proposer")?;
+    for (value, field) in [(&request.title, "title"), (&request.summary, "summary")] {
+        if value.trim().is_empty() {
+            return Err(AppError::Validation {
+                message: format!("{} is required", field),
+                field: Some(field.to_string()),
+                details: None,
+            });
+        }
+    }
+    if request
+        .messages
+        .iter()
+        .any(|m| m.get("@type").and_then(|t| t.as_str()).is_none())
+    {
+        return Err(AppError::Validation {
+            message: "Every proposal message must carry an @type".to_string(),
+            field: Some("messages".to_string()),
+            details: None,
+        });
+    }
+    tx_builder::parse_positive_amount(&request.initial_deposit, "initial_deposit")?;
+    let denom = resolve_deposit_denom(&state, request.denom)?;
+
+    let submit_msg = serde_json::json!({
+        "@type": "/cosmos.gov.v1.MsgSubmitProposal",
+        "messages": request.messages,
+        "initial_deposit": [{
+            "denom": denom,
+            "amount": request.initial_deposit
+        }],
+        "proposer": request.proposer,
+        "metadata": request.metadata.unwrap_or_default(),
+        "title": request.title,
+        "summary": request.summary,
+        "expedited": request.expedited
+    });
+
+    Ok(Json(GovernanceTransactionResponse {
+        messages: vec![submit_msg],
+        memo: "Submit governance proposal".to_string(),
+        gas: governance_gas(&state, MsgKind::SubmitProposal),
+    }))
+}
+
+/// Reject a proposal that is unknown or not in one of `allowed` statuses.
+fn require_proposal_status(
+    state: &AppState,
+    proposal_id: &str,
+    allowed: &[&str],
+) -> Result<(), AppError> {
+    let snapshot = state
+        .data_cache
+        .proposals_with_tally
+        .load_or_unavailable("Proposals")?;
+    let proposal = snapshot
+        .proposals
+        .iter()
+        .find(|p| p.id == proposal_id)
+        .ok_or_else(|| AppError::NotFound {
+            resource: format!("Proposal {}", proposal_id),
+        })?;
+    if allowed.contains(&proposal.status.as_str()) {
+        return Ok(());
+    }
+    Err(AppError::Validation {
+        message: format!(
+            "Proposal {} is in {}, expected {}",
+            proposal_id,
+            proposal.status,
+            allowed.join(" or ")
+        ),
+        field: Some("proposal_id".to_string()),
+        details: None,
+    })
+}
+
+/// Validate weighted vote options and render them as `cosmos.gov.v1`
+/// `WeightedVoteOption`s with 18-decimal weights.
+fn validate_vote_weights(
+    options: &[WeightedVoteOption],
+) -> Result<Vec<serde_json::Value>, AppError> {
+    let invalid = |message: String| AppError::Validation {
+        message,
+        field: Some("options".to_string()),
+        details: None,
+    };
+    if options.is_empty() {
+        return Err(invalid("At least one vote option is required".to_string()));
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let mut total: u128 = 0;
+    let mut rendered = Vec::with_capacity(options.len());
+    for option in options {
+        if !seen.insert(option.option) {
+            return Err(invalid(format!(
+                "Vote option {} appears more than once",
+                option.option.proto_name()
+            )));
+        }
+        let weight = parse_dec(&option.weight)
+            .filter(|w| *w > 0)
+            .ok_or_else(|| invalid(format!("Invalid vote weight '{}'", option.weight)))?;
+        total = total.saturating_add(weight);
+        rendered.push(serde_json::json!({
+            "option": option.option.proto_name(),
+            "weight": format_dec(weight)
+        }));
+    }
+
+    if total != 10_u128.pow(DEC_PRECISION) {
+        return Err(invalid("Vote weights must sum to 1".to_string()));
+    }
+    Ok(rendered)
+}
+
+/// Parse a non-negative decimal string into a cosmos `Dec` scaled integer.
+fn parse_dec(raw: &str) -> Option<u128> {
+    let (whole, fraction) = raw.split_once('.').unwrap_or((raw, ""));
+    let digits = u32::try_from(fraction.len())
+        .ok()
+        .filter(|len| *len <= DEC_PRECISION)?;
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let whole: u128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let fraction: u128 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse().ok()?
+    };
+    whole
+        .checked_mul(10_u128.pow(DEC_PRECISION))?
+        .checked_add(fraction.checked_mul(10_u128.pow(DEC_PRECISION - digits))?)
+}
+
+/// Render a cosmos `Dec` scaled integer with all 18 fractional digits.
+fn format_dec(value: u128) -> String {
+    let scale = 10_u128.pow(DEC_PRECISION);
+    format!("{}.{:018}", value / scale, value % scale)
+}
+
+/// Use the requested denom, or the native denom from cached currencies
+fn resolve_deposit_denom(state: &AppState, requested: Option<String>) -> Result<String, AppError> {
+    if let Some(denom) = requested.filter(|d| !d.is_empty()) {
+        return Ok(denom);
+    }
+    let currencies = state
+        .data_cache
+        .currencies
+        .load_or_unavailable("Currencies")?;
+    tx_builder::resolve_native_denom(&currencies)
+}
+
+fn governance_gas(state: &AppState, kind: MsgKind) -> GasEstimate {
+    let gas_fee_config = state.data_cache.gas_fee_config.load();
+    tx_builder::estimate_gas(&[kind], gas_fee_config.as_ref())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::data_cache::GatedConfigBundle;
     use crate::test_utils::{collect_body_str, test_app_state};
-    use axum::{body::Body, http::Request, http::StatusCode, routing::get, Router};
+    use axum::{
+        body::Body,
+        http::Request,
+        http::StatusCode,
+        routing::{get, post},
+        Router,
+    };
     use tower::ServiceExt;
 
     fn build_app(state: Arc<AppState>) -> Router {
@@ -542,6 +1218,14 @@ mod tests {
                 "/api/governance/proposals/{proposal_id}/tally",
                 get(get_proposal_tally),
             )
+            .route(
+                "/api/governance/proposals/{proposal_id}",
+                get(get_proposal_detail),
+            )
+            .route("/api/governance/vote", post(vote))
+            .route("/api/governance/vote-weighted", post(vote_weighted))
+            .route("/api/governance/deposit", post(deposit))
+            .route("/api/governance/submit-proposal", post(submit_proposal))
             .with_state(state)
     }
 
@@ -729,6 +1413,7 @@ mod tests {
             .store(crate::data_cache::ProposalsWithTally {
                 proposals,
                 tallies: HashMap::new(),
+                tally_history: HashMap::new(),
             });
     }
 
@@ -1059,4 +1744,281 @@ mod tests {
             "successful lookup must report proposal 2 as voted=true, body: {body}"
         );
     }
+
+    const VOTER: &str = "nolus1ncc58ptqrkd7r7uk60dx4eufvvqf2edhtktv0q";
+
+    async fn post_json(app: Router, uri: &str, body: serde_json::Value) -> (StatusCode, String) {
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = resp.status();
+        (status, collect_body_str(resp).await)
+    }
+
+    #[tokio::test]
+    async fn vote_builds_msg_vote_for_proposal_in_voting_period() {
+        let state = test_app_state().await;
+        populate_proposals_cache(
+            &state,
+            vec![sample_proposal("7", "PROPOSAL_STATUS_VOTING_PERIOD")],
+        );
+        let (status, body) = post_json(
+            build_app(state),
+            "/api/governance/vote",
+            serde_json::json!({"voter": VOTER, "proposal_id": "7", "option": "no_with_veto"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "body: {body}");
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["messages"][0]["@type"], "/cosmos.gov.v1.MsgVote");
+        assert_eq!(json["messages"][0]["option"], "VOTE_OPTION_NO_WITH_VETO");
+    }
+
+    #[tokio::test]
+    async fn vote_rejects_proposal_outside_voting_period() {
+        let state = test_app_state().await;
+        populate_proposals_cache(
+            &state,
+            vec![sample_proposal("7", "PROPOSAL_STATUS_DEPOSIT_PERIOD")],
+        );
+        let (status, body) = post_json(
+            build_app(state),
+            "/api/governance/vote",
+            serde_json::json!({"voter": VOTER, "proposal_id": "7", "option": "yes"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "body: {body}");
+    }
+
+    #[tokio::test]
+    async fn vote_unknown_proposal_returns_404() {
+        let state = test_app_state().await;
+        populate_proposals_cache(&state, vec![]);
+        let (status, _) = post_json(
+            build_app(state),
+            "/api/governance/vote",
+            serde_json::json!({"voter": VOTER, "proposal_id": "7", "option": "yes"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn deposit_is_accepted_during_voting_period() {
+        let state = test_app_state().await;
+        populate_proposals_cache(
+            &state,
+            vec![sample_proposal("7", "PROPOSAL_STATUS_VOTING_PERIOD")],
+        );
+        let (status, body) = post_json(
+            build_app(state),
+            "/api/governance/deposit",
+            serde_json::json!({"depositor": VOTER, "proposal_id": "7", "amount": "1000", "denom": "unls"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "body: {body}");
+        assert!(body.contains("/cosmos.gov.v1.MsgDeposit"), "body: {body}");
+    }
+
+    #[tokio::test]
+    async fn governance_builder_messages_encode_for_simulation() {
+        let state = test_app_state().await;
+        populate_proposals_cache(
+            &state,
+            vec![sample_proposal("7", "PROPOSAL_STATUS_VOTING_PERIOD")],
+        );
+        let requests = [
+            (
+                "/api/governance/vote",
+                serde_json::json!({"voter": VOTER, "proposal_id": "7", "option": "yes"}),
+            ),
+            (
+                "/api/governance/vote-weighted",
+                serde_json::json!({"voter": VOTER, "proposal_id": "7", "options": [
+                    {"option": "yes", "weight": "0.5"},
+                    {"option": "no", "weight": "0.5"}
+                ]}),
+            ),
+            (
+                "/api/governance/deposit",
+                serde_json::json!({"depositor": VOTER, "proposal_id": "7", "amount": "1000", "denom": "unls"}),
+            ),
+            (
+                "/api/governance/submit-proposal",
+                serde_json::json!({
+                    "proposer": VOTER,
+                    "title": "Text proposal",
+                    "summary": "No messages",
+                    "initial_deposit": "1000",
+                    "denom": "unls"
+                }),
+            ),
+        ];
+        for (uri, request) in requests {
+            let (status, body) = post_json(build_app(state.clone()), uri, request).await;
+            assert_eq!(status, StatusCode::OK, "{uri}: {body}");
+            let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+            let messages = json["messages"].as_array().unwrap();
+            let encoded = crate::handlers::cosmos_tx::encode_messages(messages, VOTER)
+                .unwrap_or_else(|e| panic!("{uri} builds unencodable messages: {e}"));
+            assert_eq!(encoded.len(), 1);
+        }
+    }
+
+    #[test]
+    fn vote_weights_must_sum_to_one() {
+        let option = |option, weight: &str| WeightedVoteOption {
+            option,
+            weight: weight.to_string(),
+        };
+        let rendered = validate_vote_weights(&[
+            option(VoteOption::Yes, "0.7"),
+            option(VoteOption::Abstain, ".3"),
+        ])
+        .unwrap();
+        assert_eq!(rendered[0]["weight"], "0.700000000000000000");
+        assert_eq!(rendered[1]["weight"], "0.300000000000000000");
+
+        assert!(validate_vote_weights(&[option(VoteOption::Yes, "0.5")]).is_err());
+        assert!(validate_vote_weights(&[
+            option(VoteOption::Yes, "0.5"),
+            option(VoteOption::Yes, "0.5"),
+        ])
+        .is_err());
+        assert!(validate_vote_weights(&[
+            option(VoteOption::Yes, "1.0"),
+            option(VoteOption::No, "0"),
+        ])
+        .is_err());
+        assert!(validate_vote_weights(&[option(VoteOption::Yes, "1e0")]).is_err());
+    }
+
+    fn tally(yes: &str, abstain: &str, no: &str, veto: &str) -> chain::TallyResult {
+        chain::TallyResult {
+            yes_count: yes.to_string(),
+            abstain_count: abstain.to_string(),
+            no_count: no.to_string(),
+            no_with_veto_count: veto.to_string(),
+        }
+    }
+
+    fn tallying_params() -> chain::TallyingParams {
+        chain::TallyingParams {
+            quorum: "0.334000000000000000".to_string(),
+            threshold: "0.500000000000000000".to_string(),
+            veto_threshold: "0.334000000000000000".to_string(),
+        }
+    }
+
+    #[test]
+    fn tally_progress_applies_quorum_veto_and_threshold() {
+        let params = tallying_params();
+
+        let no_quorum = tally_progress(&tally("100", "0", "0", "0"), &params, "1000").unwrap();
+        assert_eq!(no_quorum.outcome, ProjectedOutcome::NoQuorum);
+        assert!((no_quorum.turnout - 0.1).abs() < 1e-9);
+
+        // Abstain counts toward quorum but not toward the yes threshold.
+        let passes = tally_progress(&tally("200", "300", "100", "0"), &params, "1000").unwrap();
+        assert!(passes.quorum_reached);
+        assert!((passes.yes_ratio - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(passes.outcome, ProjectedOutcome::Passes);
+
+        let vetoed = tally_progress(&tally("300", "0", "0", "200"), &params, "1000").unwrap();
+        assert_eq!(vetoed.outcome, ProjectedOutcome::Vetoed);
+
+        let all_abstain = tally_progress(&tally("0", "500", "0", "0"), &params, "1000").unwrap();
+        assert_eq!(all_abstain.outcome, ProjectedOutcome::Rejected);
+    }
+
+    #[tokio::test]
+    async fn proposal_detail_decodes_messages_and_reports_progress() {
+        let mock = MockServer::start().await;
+        let state = state_with_chain_url(&mock.uri()).await;
+        let mut proposal = sample_proposal("5", "PROPOSAL_STATUS_VOTING_PERIOD");
+        proposal.messages = vec![serde_json::json!({
+            "@type": "/cosmos.upgrade.v1beta1.MsgSoftwareUpgrade",
+            "authority": "nolus10d07y265gmmuvt4z0w9aw880jnsr700jf5qd9h",
+            "plan": {"name": "v0.9.0", "height": "1000", "info": ""}
+        })];
+        let mut tallies = HashMap::new();
+        tallies.insert("5".to_string(), tally("400", "0", "100", "0"));
+        let mut tally_history = HashMap::new();
+        tally_history.insert(
+            "5".to_string(),
+            vec![TallySample {
+                at: Utc::now(),
+                tally: tally("100", "0", "0", "0"),
+            }],
+        );
+        state
+            .data_cache
+            .proposals_with_tally
+            .store(crate::data_cache::ProposalsWithTally {
+                proposals: vec![proposal],
+                tallies,
+                tally_history,
+            });
+        state
+            .data_cache
+            .staking_pool
+            .store(chain::StakingPoolResponse {
+                pool: chain::StakingPool {
+                    not_bonded_tokens: "0".to_string(),
+                    bonded_tokens: "1000".to_string(),
+                },
+            });
+        Mock::given(wm_method("GET"))
+            .and(wm_path("/cosmos/gov/v1/params/tallying"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "params": tallying_params() })),
+            )
+            .mount(&mock)
+            .await;
+
+        let resp = build_app(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/api/governance/proposals/5")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = collect_body_str(resp).await;
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(
+            json["decoded_messages"][0]["summary"],
+            "Software upgrade v0.9.0 at height 1000"
+        );
+        assert_eq!(json["progress"]["outcome"], "passes");
+        assert_eq!(json["proposal"]["tally"]["yes_count"], "400");
+        assert_eq!(json["tally_history"].as_array().map(Vec::len), Some(1));
+    }
+
+    #[tokio::test]
+    async fn proposal_detail_unknown_id_returns_404() {
+        let state = test_app_state().await;
+        populate_proposals_cache(&state, vec![sample_proposal("1", "PROPOSAL_STATUS_PASSED")]);
+        let resp = build_app(state)
+            .oneshot(
+                Request::builder()
+                    .uri("/api/governance/proposals/2")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
 }
@@ -5,10 +5,14 @@
 //! - GET /api/leases/:address - Get details for a specific lease
 //! - GET /api/leases/:address/history - Get lease history
 //! - POST /api/leases/quote - Get a quote for opening a lease
+//! - POST /api/leases/simulate - Preview the position a lease opening would create
 //! - POST /api/leases/open - Build transaction to open a lease
 //! - POST /api/leases/repay - Build transaction to repay a lease
 //! - POST /api/leases/close - Build transaction to close a lease
 //! - POST /api/leases/market-close - Build transaction for market close
+//! - POST /api/leases/close-policy - Build transaction to change stop-loss / take-profit
+//! - POST /api/leases/partial-close - Build transaction to sell part of the position
+//! - POST /api/leases/repay-with-collateral - Build transaction to repay debt from the position
 
 use axum::{
     extract::{Path, Query, State},
@@ -21,7 +25,12 @@ use tracing::{debug, error, warn};
 use utoipa::ToSchema;
 
 use crate::error::AppError;
-use crate::external::chain::{AmountSpec, ClosingLeaseInfo, LeaseStatusResponse, OpenedLeaseInfo};
+use crate::external::chain::{
+    AmountSpec, ClosingLeaseInfo, LeaseAmount, LeaseStatusResponse, LiabilitySpec, OpenedLeaseInfo,
+};
+use crate::handlers::tx_builder::{self, GasEstimate, MsgKind};
+use crate::lease_ledger::LeaseLedgerEntry;
+use crate::num_utils::{f64_ceil_to_u64, u128_to_f64};
 use crate::query_types::{AddressWithProtocolQuery, OptionalProtocolQuery};
 use crate::AppState;
 
@@ -120,6 +129,27 @@ pub struct LeasePnlInfo {
     pub percent: String,
     pub downpayment: String,
     pub pnl_positive: bool,
+    /// Component breakdown (present when computed from the backend's own
+    /// lease ledger rather than ETL)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub breakdown: Option<LeasePnlBreakdown>,
+}
+
+/// PnL components in USD: `amount = price_pnl - interest - fees - swap_costs`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LeasePnlBreakdown {
+    /// Gain or loss from the position's price moving since opening
+    pub price_pnl: String,
+    /// Loan interest paid plus currently accrued
+    pub interest: String,
+    /// Margin interest (protocol fee) paid plus currently accrued
+    pub fees: String,
+    /// Value lost in the opening swap; absent if the post-swap position
+    /// was not observed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub swap_costs: Option<String>,
+    /// Total USD repaid into the lease after opening
+    pub repaid: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -194,6 +224,9 @@ pub struct LeaseQuoteRequest {
     pub protocol: String,
     pub downpayment_ticker: String,
     pub downpayment_amount: String,
+    /// Ticker of the asset to lease (required to price a long position)
+    #[serde(default)]
+    pub lease_asset: Option<String>,
     #[serde(default)]
     pub max_ltd: Option<u32>,
 }
@@ -203,11 +236,101 @@ pub struct LeaseQuoteResponse {
     pub borrow_ticker: String,
     pub borrow_amount: String,
     pub annual_interest_rate: f64,
+    /// USD price of the position asset at which the lease reaches the liquidation LTV
     pub estimated_liquidation_price: Option<String>,
+    /// Loan-to-value at opening, in percent
+    pub initial_ltv: Option<f64>,
+    /// Loan-to-value at which the leaser liquidates, in percent
+    pub liquidation_ltv: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SimulateLeaseRequest {
+    pub protocol: String,
+    pub downpayment_ticker: String,
+    pub downpayment_amount: String,
+    /// Ticker of the asset to lease
+    pub lease_asset: String,
+    #[serde(default)]
+    pub max_ltd: Option<u32>,
+}
+
+/// Full preview of the position a lease opening would create
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimulateLeaseResponse {
+    pub protocol: String,
+    /// "long" or "short"
+    pub position_type: String,
+    pub downpayment: SimulatedAmount,
+    pub borrow: SimulatedAmount,
+    /// Expected position after swapping, in the lease asset
+    pub position: SimulatedAmount,
+    pub swap: SimulatedSwap,
+    /// Annual interest rate (loan + margin) in percent
+    pub annual_interest_rate: f64,
+    /// Interest accrued on the borrowed amount per day, in USD
+    pub interest_per_day_usd: f64,
+    /// Loan-to-value at opening, in percent
+    pub initial_ltv: Option<f64>,
+    /// Loan-to-value at which the leaser liquidates, in percent
+    pub liquidation_ltv: Option<f64>,
+    /// USD price of the position asset at which the lease is liquidated
+    pub liquidation_price: Option<String>,
+    /// Warning zones announced by the leaser before liquidation
+    pub warnings: Vec<SimulatedWarningLevel>,
+    /// Downpayment range check for the lease asset; `None` when unconfigured
+    pub downpayment_range: Option<DownpaymentRangeCheck>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimulatedAmount {
+    pub ticker: String,
+    /// Amount in minimal denomination
+    pub amount: String,
+    /// Value in USD at current oracle prices
+    pub amount_usd: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimulatedSwap {
+    /// USD value routed through the DEX (the part not already in the lease asset)
+    pub input_usd: f64,
+    /// Swap fee in USD
+    pub fee_usd: f64,
+    /// Swap fee in basis points
+    pub fee_bps: u32,
+    /// Slippage tolerance in percent
+    pub slippage_percent: f64,
+    /// Expected lease asset received, in minimal denomination
+    pub expected_output: String,
+    /// Minimum lease asset received once the slippage tolerance is applied
+    pub min_output: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimulatedWarningLevel {
+    /// Warning level (1 = first warning, 3 = last before liquidation)
+    pub level: u8,
+    /// Loan-to-value that triggers this warning, in percent
+    pub ltv: f64,
+    /// USD price of the position asset at which the warning triggers
+    pub price: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DownpaymentRangeCheck {
+    /// Minimum downpayment value in USD
+    pub min_usd: f64,
+    /// Maximum downpayment value in USD
+    pub max_usd: f64,
+    /// Whether the requested downpayment falls within the range
+    pub within_range: bool,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct OpenLeaseRequest {
+    /// Address that will sign and own the lease
+    pub sender: String,
     pub protocol: String,
     pub downpayment_ticker: String,
     pub downpayment_amount: String,
@@ -221,24 +344,131 @@ pub struct LeaseTransactionResponse {
     #[schema(value_type = Vec<Object>)]
     pub messages: Vec<serde_json::Value>,
     pub memo: String,
+    pub gas: GasEstimate,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct RepayLeaseRequest {
+    /// Lease owner signing the repayment
+    pub sender: String,
     pub lease_address: String,
+    /// Protocol the lease belongs to (used to resolve the payment denom)
+    pub protocol: String,
+    /// Ticker of the currency used to repay
+    pub ticker: String,
     pub amount: String,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CloseLeaseRequest {
+    /// Lease owner signing the close
+    pub sender: String,
     pub lease_address: String,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct MarketCloseRequest {
+    /// Lease owner signing the close
+    pub sender: String,
     pub lease_address: String,
 }
 
+/// A stop-loss or take-profit change: `{"set": <LTV permille>}`, or `"reset"`
+/// to remove it, as the lease contract's `change_close_policy` takes it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClosePolicyChange {
+    Set(u32),
+    Reset,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ClosePolicyRequest {
+    /// Lease owner signing the change
+    pub sender: String,
+    pub lease_address: String,
+    /// Protocol the lease belongs to
+    pub protocol: String,
+    /// Close once the LTV rises to this level; omit to keep the current one
+    pub stop_loss: Option<ClosePolicyChange>,
+    /// Close once the LTV falls to this level; omit to keep the current one
+    pub take_profit: Option<ClosePolicyChange>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClosePolicyResponse {
+    pub transaction: LeaseTransactionResponse,
+    /// Policy in effect once the change executes
+    pub close_policy: LeaseClosePolicy,
+    /// Ticker whose price drives the triggers (the lease asset for longs, the
+    /// borrowed asset for shorts)
+    pub price_ticker: String,
+    pub current_price: String,
+    /// Current LTV (percent)
+    pub current_ltv: f64,
+    /// LTV at which the lease is liquidated (percent)
+    pub liquidation_ltv: f64,
+    pub liquidation_price: String,
+    /// Price at which the stop-loss closes the lease
+    pub stop_loss_price: Option<String>,
+    /// Price at which the take-profit closes the lease
+    pub take_profit_price: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PartialCloseRequest {
+    /// Lease owner signing the close
+    pub sender: String,
+    pub lease_address: String,
+    /// Protocol the lease belongs to
+    pub protocol: String,
+    /// Units of the lease asset to sell (minor units)
+    pub amount: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RepayWithCollateralRequest {
+    /// Lease owner signing the repayment
+    pub sender: String,
+    pub lease_address: String,
+    /// Protocol the lease belongs to
+    pub protocol: String,
+    /// Debt to repay, in the debt currency (minor units)
+    pub amount: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PartialCloseResponse {
+    pub transaction: LeaseTransactionResponse,
+    /// Lease asset units sold by the close
+    pub amount: AmountSpec,
+    pub preview: PartialClosePreview,
+}
+
+/// Lease risk before and after a partial close, at cached oracle prices.
+/// Swap fees and slippage are ignored.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PartialClosePreview {
+    /// Lease asset units left in the position
+    pub remaining_amount: AmountSpec,
+    /// Outstanding debt once the proceeds are repaid
+    pub debt_after: AmountSpec,
+    pub debt_after_usd: String,
+    /// Proceeds beyond the outstanding debt, paid out to the owner
+    pub surplus_usd: String,
+    /// Ticker whose price drives liquidation (the lease asset for longs, the
+    /// borrowed asset for shorts)
+    pub price_ticker: String,
+    /// LTV before the close (percent)
+    pub ltv_before: f64,
+    /// LTV after the close (percent)
+    pub ltv_after: f64,
+    /// `None` without outstanding debt
+    pub liquidation_price_before: Option<String>,
+    /// `None` once the close repays the whole debt
+    pub liquidation_price_after: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LeaseHistoryEntry {
     pub tx_hash: Option<String>,
@@ -271,6 +501,8 @@ pub struct LeaseConfigResponse {
         std::collections::HashMap<String, crate::config_store::gated_types::DownpaymentRange>,
     pub min_asset: AmountSpec,
     pub min_transaction: AmountSpec,
+    /// Leaser liability thresholds (permille)
+    pub liability: LiabilitySpec,
 }
 
 /// Get lease configuration for a protocol
@@ -607,7 +839,10 @@ pub async fn get_lease_history(
 /// Quote a prospective lease
 ///
 /// Queries the Leaser contract for the borrow amount and annual interest rate
-/// that would result from opening a lease with the given downpayment.
+/// that would result from opening a lease with the given downpayment. The
+/// initial LTV, liquidation LTV and estimated liquidation price are derived
+/// from the leaser liability spec and cached oracle prices; they are `null`
+/// when that data is not yet available.
 #[utoipa::path(
     post,
     path = "/api/leases/quote",
@@ -642,6 +877,7 @@ pub async fn get_lease_quote(
             &contract_info.leaser,
             &request.downpayment_amount,
             &request.downpayment_ticker,
+            request.lease_asset.as_deref(),
             request.max_ltd,
         )
         .await?;
@@ -651,18 +887,119 @@ pub async fn get_lease_quote(
         / 10f64.powi(INTEREST_DECIMALS)
         * 100.0;
 
+    let is_short = is_short_protocol(&state, &request.protocol);
+    let lease_configs = state.data_cache.lease_configs.load();
+    let liability = lease_configs
+        .as_ref()
+        .and_then(|configs| configs.get(&request.protocol))
+        .map(|config| &config.liability);
+    let prices = state.data_cache.prices.load();
+    let currencies = state.data_cache.currencies.load();
+
+    let risk = estimate_quote_risk(
+        &request,
+        &quote.borrow,
+        is_short,
+        liability,
+        prices.as_ref(),
+        currencies.as_ref(),
+    );
+
     Ok(Json(LeaseQuoteResponse {
         borrow_ticker: quote.borrow.ticker,
         borrow_amount: quote.borrow.amount,
         annual_interest_rate: annual_interest,
-        estimated_liquidation_price: None, // Would need price data to calculate
+        estimated_liquidation_price: risk.liquidation_price,
+        initial_ltv: risk.initial_ltv,
+        liquidation_ltv: risk.liquidation_ltv,
     }))
 }
 
+/// Simulate opening a lease
+///
+/// Combines the Leaser quote with cached prices, the leaser liability spec and
+/// the gated swap settings into a full position preview: position size, swap
+/// output after slippage, fees, daily interest, liquidation and warning-zone
+/// prices, and the downpayment range check for the lease asset.
+#[utoipa::path(
+    post,
+    path = "/api/leases/simulate",
+    tag = "leases",
+    request_body = SimulateLeaseRequest,
+    responses(
+        (status = 200, description = "Position preview", body = SimulateLeaseResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 404, description = "Protocol not configured", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn simulate_lease(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SimulateLeaseRequest>,
+) -> Result<Json<SimulateLeaseResponse>, AppError> {
+    debug!(
+        "Simulating lease of {} for protocol: {}",
+        request.lease_asset, request.protocol
+    );
+
+    tx_builder::parse_positive_amount(&request.downpayment_amount, "downpayment_amount")?;
+
+    let contracts_map = state
+        .data_cache
+        .protocol_contracts
+        .load_or_unavailable("Protocol contracts")?;
+    let contract_info = contracts_map
+        .get(&request.protocol)
+        .ok_or_else(|| AppError::NotFound {
+            resource: format!("Protocol {}", request.protocol),
+        })?;
+    let prices = state.data_cache.prices.load_or_unavailable("Prices")?;
+    let currencies = state
+        .data_cache
+        .currencies
+        .load_or_unavailable("Currencies")?;
+    let gated = state
+        .data_cache
+        .gated_config
+        .load_or_unavailable("Gated config")?;
+
+    let quote = state
+        .chain_client
+        .get_lease_quote(
+            &contract_info.leaser,
+            &request.downpayment_amount,
+            &request.downpayment_ticker,
+            Some(&request.lease_asset),
+            request.max_ltd,
+        )
+        .await?;
+
+    let is_short = is_short_protocol(&state, &request.protocol);
+    let liability = state
+        .data_cache
+        .lease_configs
+        .load()
+        .and_then(|configs| configs.get(&request.protocol).map(|c| c.liability.clone()));
+
+    let inputs = LeaseSimulationInputs {
+        request: &request,
+        quote: &quote,
+        is_short,
+        liability: liability.as_ref(),
+        downpayment_ranges: gated.lease_rules.downpayment_ranges.get(&request.protocol),
+        swap_fee_bps: gated.swap_settings.fee,
+        slippage_percent: f64::from(gated.swap_settings.slippage),
+        prices: &prices,
+        currencies: &currencies,
+    };
+
+    Ok(Json(simulate_lease_position(&inputs)?))
+}
+
 /// Build an open-lease transaction
 ///
-/// Returns unsigned `MsgExecuteContract` messages that the frontend wallet will
-/// complete (sender, IBC denom) and sign to open a new lease.
+/// Returns a ready-to-sign `MsgExecuteContract` for the given sender, with the
+/// downpayment ticker resolved to its bank denom and a gas estimate attached.
 #[utoipa::path(
     post,
     path = "/api/leases/open",
@@ -684,6 +1021,9 @@ pub async fn open_lease(
         request.protocol
     );
 
+    tx_builder::validate_sender(&request.sender)?;
+    tx_builder::parse_positive_amount(&request.downpayment_amount, "downpayment_amount")?;
+
     let contracts_map = state
         .data_cache
         .protocol_contracts
@@ -694,6 +1034,17 @@ pub async fn open_lease(
             resource: format!("Protocol {}", request.protocol),
         })?;
 
+    let currencies = state
+        .data_cache
+        .currencies
+        .load_or_unavailable("Currencies")?;
+    let denom = tx_builder::resolve_denom(
+        &currencies,
+        &request.protocol,
+        &request.downpayment_ticker,
+        "downpayment_ticker",
+    )?;
+
     // Build the open lease message
     // The actual transaction needs to be signed by the user's wallet
     let open_msg = serde_json::json!({
@@ -705,25 +1056,27 @@ pub async fn open_lease(
 
     let execute_msg = serde_json::json!({
         "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
-        "sender": "", // Will be filled by frontend with wallet address
+        "sender": request.sender,
         "contract": contract_info.leaser,
         "msg": open_msg,
         "funds": [{
-            "denom": "", // Will be filled with IBC denom
+            "denom": denom,
             "amount": request.downpayment_amount
         }]
     });
 
+    let gas_fee_config = state.data_cache.gas_fee_config.load();
     Ok(Json(LeaseTransactionResponse {
         messages: vec![execute_msg],
         memo: "Open Nolus Lease".to_string(),
+        gas: tx_builder::estimate_gas(&[MsgKind::OpenLease], gas_fee_config.as_ref()),
     }))
 }
 
 /// Build a repay-lease transaction
 ///
-/// Returns unsigned `MsgExecuteContract` messages to repay the specified amount
-/// toward an existing lease.
+/// Returns a ready-to-sign `MsgExecuteContract` repaying the specified amount
+/// toward an existing lease, paid in the bank denom of the given ticker.
 #[utoipa::path(
     post,
     path = "/api/leases/repay",
@@ -732,10 +1085,11 @@ pub async fn open_lease(
     responses(
         (status = 200, description = "Unsigned transaction messages", body = LeaseTransactionResponse),
         (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
     ),
 )]
 pub async fn repay_lease(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<RepayLeaseRequest>,
 ) -> Result<Json<LeaseTransactionResponse>, AppError> {
     debug!(
@@ -743,30 +1097,43 @@ pub async fn repay_lease(
         request.lease_address
     );
 
+    tx_builder::validate_sender(&request.sender)?;
+    crate::validation::validate_nolus_address(&request.lease_address, "lease_address")?;
+    tx_builder::parse_positive_amount(&request.amount, "amount")?;
+
+    let currencies = state
+        .data_cache
+        .currencies
+        .load_or_unavailable("Currencies")?;
+    let denom =
+        tx_builder::resolve_denom(&currencies, &request.protocol, &request.ticker, "ticker")?;
+
     let repay_msg = serde_json::json!({
         "repay": {}
     });
 
     let execute_msg = serde_json::json!({
         "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
-        "sender": "", // Will be filled by frontend
+        "sender": request.sender,
         "contract": request.lease_address,
         "msg": repay_msg,
         "funds": [{
-            "denom": "", // Will be filled with IBC denom based on currency
+            "denom": denom,
             "amount": request.amount
         }]
     });
 
+    let gas_fee_config = state.data_cache.gas_fee_config.load();
     Ok(Json(LeaseTransactionResponse {
         messages: vec![execute_msg],
         memo: "Repay Nolus Lease".to_string(),
+        gas: tx_builder::estimate_gas(&[MsgKind::WasmExecute], gas_fee_config.as_ref()),
     }))
 }
 
 /// Build a close-lease transaction
 ///
-/// Returns unsigned `MsgExecuteContract` messages to fully close the specified
+/// Returns a ready-to-sign `MsgExecuteContract` that fully closes the specified
 /// lease position (full close of collateral).
 #[utoipa::path(
     post,
@@ -779,7 +1146,7 @@ pub async fn repay_lease(
     ),
 )]
 pub async fn close_lease(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<CloseLeaseRequest>,
 ) -> Result<Json<LeaseTransactionResponse>, AppError> {
     debug!(
@@ -787,6 +1154,9 @@ pub async fn close_lease(
         request.lease_address
     );
 
+    tx_builder::validate_sender(&request.sender)?;
+    crate::validation::validate_nolus_address(&request.lease_address, "lease_address")?;
+
     let close_msg = serde_json::json!({
         "close_position": {
             "full_close": {}
@@ -795,21 +1165,23 @@ pub async fn close_lease(
 
     let execute_msg = serde_json::json!({
         "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
-        "sender": "", // Will be filled by frontend
+        "sender": request.sender,
         "contract": request.lease_address,
         "msg": close_msg,
         "funds": []
     });
 
+    let gas_fee_config = state.data_cache.gas_fee_config.load();
     Ok(Json(LeaseTransactionResponse {
         messages: vec![execute_msg],
         memo: "Close Nolus Lease".to_string(),
+        gas: tx_builder::estimate_gas(&[MsgKind::WasmExecute], gas_fee_config.as_ref()),
     }))
 }
 
 /// Build a market-close transaction
 ///
-/// Returns unsigned `MsgExecuteContract` messages that market-close the lease
+/// Returns a ready-to-sign `MsgExecuteContract` that market-closes the lease
 /// by selling collateral to repay the outstanding debt.
 #[utoipa::path(
     post,
@@ -822,7 +1194,7 @@ pub async fn close_lease(
     ),
 )]
 pub async fn market_close_lease(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<MarketCloseRequest>,
 ) -> Result<Json<LeaseTransactionResponse>, AppError> {
     debug!(
@@ -830,6 +1202,9 @@ pub async fn market_close_lease(
         request.lease_address
     );
 
+    tx_builder::validate_sender(&request.sender)?;
+    crate::validation::validate_nolus_address(&request.lease_address, "lease_address")?;
+
     let market_close_msg = serde_json::json!({
         "close_position": {
             "partial_close": {
@@ -840,22 +1215,453 @@ pub async fn market_close_lease(
 
     let execute_msg = serde_json::json!({
         "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
-        "sender": "", // Will be filled by frontend
+        "sender": request.sender,
         "contract": request.lease_address,
         "msg": market_close_msg,
         "funds": []
     });
 
+    let gas_fee_config = state.data_cache.gas_fee_config.load();
     Ok(Json(LeaseTransactionResponse {
         messages: vec![execute_msg],
         memo: "Market Close Nolus Lease".to_string(),
+        gas: tx_builder::estimate_gas(&[MsgKind::WasmExecute], gas_fee_config.as_ref()),
+    }))
+}
+
+/// Build a change-close-policy transaction
+///
+/// Returns a ready-to-sign `change_close_policy` execution setting or
+/// resetting the lease's stop-loss and take-profit. The resulting policy is
+/// checked against the current LTV and the liquidation threshold so it
+/// neither triggers immediately nor sits past liquidation, and the response
+/// carries the prices at which each trigger fires at cached oracle prices.
+#[utoipa::path(
+    post,
+    path = "/api/leases/close-policy",
+    tag = "leases",
+    request_body = ClosePolicyRequest,
+    responses(
+        (status = 200, description = "Unsigned transaction messages with trigger prices", body = ClosePolicyResponse),
+        (status = 400, description = "Invalid request or close policy", body = crate::error::ErrorResponse),
+        (status = 404, description = "Protocol not found", body = crate::error::ErrorResponse),
+        (status = 502, description = "Upstream chain RPC error", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn change_close_policy(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ClosePolicyRequest>,
+) -> Result<Json<ClosePolicyResponse>, AppError> {
+    debug!(
+        "Building close policy transaction for: {}",
+        request.lease_address
+    );
+
+    tx_builder::validate_sender(&request.sender)?;
+    crate::validation::validate_nolus_address(&request.lease_address, "lease_address")?;
+    if request.stop_loss.is_none() && request.take_profit.is_none() {
+        return Err(AppError::Validation {
+            message: "stop_loss or take_profit is required".to_string(),
+            field: None,
+            details: None,
+        });
+    }
+
+    let lease = load_priced_lease(&state, &request.lease_address, &request.protocol).await?;
+    if lease.position_usd <= 0.0 || lease.debt_usd <= 0.0 {
+        return Err(AppError::Validation {
+            message: "Lease has no outstanding debt to protect".to_string(),
+            field: Some("lease_address".to_string()),
+            details: None,
+        });
+    }
+
+    let liability = &lease.config.liability;
+    let (price, is_short) = (lease.price, lease.is_short);
+    let ratio = lease.debt_usd / lease.position_usd;
+    let current = lease.opened.close_policy.as_ref();
+    let close_policy = LeaseClosePolicy {
+        stop_loss: apply_close_policy_change(
+            current.and_then(|cp| cp.stop_loss),
+            request.stop_loss,
+        ),
+        take_profit: apply_close_policy_change(
+            current.and_then(|cp| cp.take_profit),
+            request.take_profit,
+        ),
+    };
+    validate_close_policy(&close_policy, ratio * PERMILLE, liability.max)?;
+
+    let trigger_price = |permille: u32| {
+        format!(
+            "{:.6}",
+            ltv_trigger_price(price, ratio, f64::from(permille) / PERMILLE, is_short)
+        )
+    };
+    let execute_msg = serde_json::json!({
+        "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
+        "sender": request.sender,
+        "contract": request.lease_address,
+        "msg": {
+            "change_close_policy": {
+                "stop_loss": request.stop_loss,
+                "take_profit": request.take_profit
+            }
+        },
+        "funds": []
+    });
+
+    let gas_fee_config = state.data_cache.gas_fee_config.load();
+    Ok(Json(ClosePolicyResponse {
+        transaction: LeaseTransactionResponse {
+            messages: vec![execute_msg],
+            memo: "Change Nolus Lease Close Policy".to_string(),
+            gas: tx_builder::estimate_gas(&[MsgKind::WasmExecute], gas_fee_config.as_ref()),
+        },
+        stop_loss_price: close_policy.stop_loss.map(trigger_price),
+        take_profit_price: close_policy.take_profit.map(trigger_price),
+        close_policy,
+        current_price: format!("{:.6}", price),
+        current_ltv: ratio * 100.0,
+        liquidation_ltv: f64::from(liability.max) / PERMILLE * 100.0,
+        liquidation_price: trigger_price(liability.max),
+        price_ticker: lease.price_ticker,
     }))
 }
 
+/// Build a partial-close transaction
+///
+/// Returns a ready-to-sign `close_position` execution selling `amount` units
+/// of the lease asset to repay debt, with a preview of the debt, LTV and
+/// liquidation price left behind. The sale must meet the leaser's minimum
+/// transaction and leave at least its minimum asset in the position.
+#[utoipa::path(
+    post,
+    path = "/api/leases/partial-close",
+    tag = "leases",
+    request_body = PartialCloseRequest,
+    responses(
+        (status = 200, description = "Unsigned transaction messages with risk preview", body = PartialCloseResponse),
+        (status = 400, description = "Invalid request or amount", body = crate::error::ErrorResponse),
+        (status = 404, description = "Protocol not found", body = crate::error::ErrorResponse),
+        (status = 502, description = "Upstream chain RPC error", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn partial_close_lease(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<PartialCloseRequest>,
+) -> Result<Json<PartialCloseResponse>, AppError> {
+    debug!(
+        "Building partial close transaction for: {}",
+        request.lease_address
+    );
+
+    tx_builder::validate_sender(&request.sender)?;
+    crate::validation::validate_nolus_address(&request.lease_address, "lease_address")?;
+    let units = tx_builder::parse_positive_amount(&request.amount, "amount")?;
+
+    let lease = load_priced_lease(&state, &request.lease_address, &request.protocol).await?;
+    build_partial_close(
+        &state,
+        &request.sender,
+        &request.lease_address,
+        &lease,
+        units,
+        "Partial Close Nolus Lease",
+    )
+    .map(Json)
+}
+
+/// Build a repay-with-collateral transaction
+///
+/// Converts `amount` of debt into the lease asset units that cover it at
+/// cached oracle prices (rounded up) and returns the partial close selling
+/// them, with the same preview and limits as `/api/leases/partial-close`.
+#[utoipa::path(
+    post,
+    path = "/api/leases/repay-with-collateral",
+    tag = "leases",
+    request_body = RepayWithCollateralRequest,
+    responses(
+        (status = 200, description = "Unsigned transaction messages with risk preview", body = PartialCloseResponse),
+        (status = 400, description = "Invalid request or amount", body = crate::error::ErrorResponse),
+        (status = 404, description = "Protocol not found", body = crate::error::ErrorResponse),
+        (status = 502, description = "Upstream chain RPC error", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn repay_with_collateral(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<RepayWithCollateralRequest>,
+) -> Result<Json<PartialCloseResponse>, AppError> {
+    debug!(
+        "Building repay-with-collateral transaction for: {}",
+        request.lease_address
+    );
+
+    tx_builder::validate_sender(&request.sender)?;
+    crate::validation::validate_nolus_address(&request.lease_address, "lease_address")?;
+    let repay = tx_builder::parse_positive_amount(&request.amount, "amount")?;
+
+    let lease = load_priced_lease(&state, &request.lease_address, &request.protocol).await?;
+    let debt = parse_amount(&lease.debt.amount, "debt")?;
+    if repay > debt {
+        return Err(AppError::Validation {
+            message: format!(
+                "amount exceeds the outstanding debt of {} {}",
+                lease.debt.amount, lease.debt.ticker
+            ),
+            field: Some("amount".to_string()),
+            details: None,
+        });
+    }
+
+    let unpriced = lease.unpriced();
+    let repay_usd = lease
+        .amount_usd(&lease.debt.ticker, &request.amount)
+        .ok_or_else(&unpriced)?;
+    let unit_usd = lease
+        .amount_usd(&lease.opened.amount.ticker, "1")
+        .ok_or_else(&unpriced)?;
+    let units = u128::from(f64_ceil_to_u64(repay_usd / unit_usd));
+
+    build_partial_close(
+        &state,
+        &request.sender,
+        &request.lease_address,
+        &lease,
+        units,
+        "Repay Nolus Lease With Collateral",
+    )
+    .map(Json)
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// An open lease valued at cached oracle prices
+struct PricedLease {
+    protocol: String,
+    lease_address: String,
+    opened: OpenedLeaseInfo,
+    config: LeaseConfigResponse,
+    prices: crate::handlers::currencies::PricesResponse,
+    currencies: crate::handlers::currencies::CurrenciesResponse,
+    /// Total debt: principal plus due and overdue interest and margin
+    debt: LeaseAmount,
+    is_short: bool,
+    /// Ticker whose price drives liquidation and the close policy
+    price_ticker: String,
+    price: f64,
+    position_usd: f64,
+    debt_usd: f64,
+}
+
+impl PricedLease {
+    fn amount_usd(&self, ticker: &str, amount: &str) -> Option<f64> {
+        quote_amount_usd(
+            &self.protocol,
+            ticker,
+            amount,
+            &self.prices,
+            &self.currencies,
+        )
+    }
+
+    fn unpriced(&self) -> impl Fn() -> AppError + '_ {
+        move || AppError::ServiceUnavailable {
+            message: format!("Prices for lease {} not available", self.lease_address),
+        }
+    }
+}
+
+/// Fetch an open lease with the cached prices, currencies and leaser config
+/// of its protocol, and value its position and debt.
+async fn load_priced_lease(
+    state: &AppState,
+    lease_address: &str,
+    protocol: &str,
+) -> Result<PricedLease, AppError> {
+    let prices = state.data_cache.prices.load_or_unavailable("Prices")?;
+    let currencies = state
+        .data_cache
+        .currencies
+        .load_or_unavailable("Currencies")?;
+    let config = state
+        .data_cache
+        .lease_configs
+        .load_or_unavailable("Lease configs")?
+        .get(protocol)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound {
+            resource: format!("Protocol {}", protocol),
+        })?;
+
+    let opened = match state
+        .chain_client
+        .get_lease_status(lease_address, 0)
+        .await?
+    {
+        LeaseStatusResponse::Opened(lease) => lease.opened,
+        _ => {
+            return Err(AppError::Validation {
+                message: "Lease is not open".to_string(),
+                field: Some("lease_address".to_string()),
+                details: None,
+            })
+        }
+    };
+
+    let debt = LeaseAmount {
+        ticker: opened.principal_due.ticker.clone(),
+        amount: calculate_total_debt(&opened)?,
+    };
+    let is_short = is_short_protocol(state, protocol);
+    let price_ticker = if is_short {
+        debt.ticker.clone()
+    } else {
+        opened.amount.ticker.clone()
+    };
+
+    let mut lease = PricedLease {
+        protocol: protocol.to_string(),
+        lease_address: lease_address.to_string(),
+        opened,
+        config,
+        prices,
+        currencies,
+        debt,
+        is_short,
+        price_ticker,
+        price: 0.0,
+        position_usd: 0.0,
+        debt_usd: 0.0,
+    };
+    let unpriced = lease.unpriced();
+    let position_usd = lease
+        .amount_usd(&lease.opened.amount.ticker, &lease.opened.amount.amount)
+        .ok_or_else(&unpriced)?;
+    let debt_usd = lease
+        .amount_usd(&lease.debt.ticker, &lease.debt.amount)
+        .ok_or_else(&unpriced)?;
+    let price = quote_price_usd(&lease.protocol, &lease.price_ticker, &lease.prices)
+        .ok_or_else(&unpriced)?;
+    lease.position_usd = position_usd;
+    lease.debt_usd = debt_usd;
+    lease.price = price;
+    Ok(lease)
+}
+
+/// Validate and preview selling `units` of the lease asset, and build the
+/// `close_position` message doing it.
+fn build_partial_close(
+    state: &AppState,
+    sender: &str,
+    lease_address: &str,
+    lease: &PricedLease,
+    units: u128,
+    memo: &str,
+) -> Result<PartialCloseResponse, AppError> {
+    let position = parse_amount(&lease.opened.amount.amount, "lease amount")?;
+    if units >= position {
+        return Err(AppError::Validation {
+            message: format!(
+                "amount must be below the position of {} {}; close the lease to sell all of it",
+                lease.opened.amount.amount, lease.opened.amount.ticker
+            ),
+            field: Some("amount".to_string()),
+            details: None,
+        });
+    }
+
+    let unpriced = lease.unpriced();
+    let asset = &lease.opened.amount.ticker;
+    let sold_usd = lease
+        .amount_usd(asset, &units.to_string())
+        .ok_or_else(&unpriced)?;
+    let min_transaction = &lease.config.min_transaction;
+    let min_asset = &lease.config.min_asset;
+    let min_transaction_usd = lease
+        .amount_usd(&min_transaction.ticker, &min_transaction.amount)
+        .ok_or_else(&unpriced)?;
+    let min_asset_usd = lease
+        .amount_usd(&min_asset.ticker, &min_asset.amount)
+        .ok_or_else(&unpriced)?;
+    validate_partial_close(
+        sold_usd,
+        lease.position_usd - sold_usd,
+        min_transaction_usd,
+        min_asset_usd,
+    )?;
+
+    let figures = partial_close_figures(
+        lease.position_usd,
+        lease.debt_usd,
+        sold_usd,
+        lease.price,
+        f64::from(lease.config.liability.max) / PERMILLE,
+        lease.is_short,
+    );
+    let debt = parse_amount(&lease.debt.amount, "debt")?;
+    let debt_after = if lease.debt_usd > 0.0 {
+        u128::from(f64_ceil_to_u64(
+            u128_to_f64(debt) * figures.debt_after_usd / lease.debt_usd,
+        ))
+        .min(debt)
+    } else {
+        0
+    };
+    let amount = AmountSpec {
+        amount: units.to_string(),
+        ticker: asset.clone(),
+    };
+    let price = |price: f64| format!("{:.6}", price);
+
+    let execute_msg = serde_json::json!({
+        "@type": "/cosmwasm.wasm.v1.MsgExecuteContract",
+        "sender": sender,
+        "contract": lease_address,
+        "msg": {
+            "close_position": {
+                "partial_close": {
+                    "amount": amount
+                }
+            }
+        },
+        "funds": []
+    });
+
+    let gas_fee_config = state.data_cache.gas_fee_config.load();
+    Ok(PartialCloseResponse {
+        transaction: LeaseTransactionResponse {
+            messages: vec![execute_msg],
+            memo: memo.to_string(),
+            gas: tx_builder::estimate_gas(&[MsgKind::WasmExecute], gas_fee_config.as_ref()),
+        },
+        amount,
+        preview: PartialClosePreview {
+            remaining_amount: AmountSpec {
+                amount: (position - units).to_string(),
+                ticker: asset.clone(),
+            },
+            debt_after: AmountSpec {
+                amount: debt_after.to_string(),
+                ticker: lease.debt.ticker.clone(),
+            },
+            debt_after_usd: format!("{:.2}", figures.debt_after_usd),
+            surplus_usd: format!("{:.2}", figures.surplus_usd),
+            price_ticker: lease.price_ticker.clone(),
+            ltv_before: figures.ltv_before * 100.0,
+            ltv_after: figures.ltv_after * 100.0,
+            liquidation_price_before: figures.liquidation_price_before.map(price),
+            liquidation_price_after: figures.liquidation_price_after.map(price),
+        },
+    })
+}
+
 async fn fetch_lease_info(
     state: &AppState,
     lease_address: &str,
@@ -875,9 +1681,10 @@ async fn fetch_lease_info(
         .await
         .ok();
 
-    // Load prices and currencies from cache (for PnL calculation)
+    // Load prices, currencies and the recorded ledger (for PnL calculation)
     let prices = state.data_cache.prices.load();
     let currencies = state.data_cache.currencies.load();
+    let ledger = state.lease_ledger.get(lease_address);
 
     // Build ETL data struct if available
     // Note: Some fields are at the top level of EtlLeaseOpening, not inside lease
@@ -961,8 +1768,11 @@ async fn fetch_lease_info(
             &opened.opened,
             etl_data,
             etl_info,
-            &prices,
-            &currencies,
+            &LeasePnlSources {
+                ledger: ledger.as_ref(),
+                prices: prices.as_ref(),
+                currencies: currencies.as_ref(),
+            },
         )?,
         LeaseStatusResponse::Closing(closing) => build_closing_lease_info(
             lease_address,
@@ -1010,14 +1820,20 @@ async fn fetch_lease_info(
     Ok(lease_info)
 }
 
+/// Cached inputs for an opened lease's PnL, loaded once per lease fetch
+struct LeasePnlSources<'a> {
+    ledger: Option<&'a LeaseLedgerEntry>,
+    prices: Option<&'a crate::handlers::currencies::PricesResponse>,
+    currencies: Option<&'a crate::handlers::currencies::CurrenciesResponse>,
+}
+
 fn build_opened_lease_info(
     lease_address: &str,
     protocol: &str,
     opened: &OpenedLeaseInfo,
     etl_data: Option<crate::external::etl::EtlLeaseOpening>,
     etl_info: Option<LeaseEtlData>,
-    prices: &Option<crate::handlers::currencies::PricesResponse>,
-    currencies: &Option<crate::handlers::currencies::CurrenciesResponse>,
+    pnl_sources: &LeasePnlSources<'_>,
 ) -> Result<LeaseInfo, AppError> {
     let total_debt = calculate_total_debt(opened)?;
     let interest_info = calculate_interest_info(opened);
@@ -1031,15 +1847,30 @@ fn build_opened_lease_info(
         take_profit: cp.take_profit,
     });
 
-    // Calculate PnL if we have prices, currencies, and ETL data
-    let pnl = calculate_pnl(
-        protocol,
-        opened,
-        &total_debt,
-        etl_data.as_ref(),
-        prices.as_ref(),
-        currencies.as_ref(),
-    );
+    // Prefer the backend's own ledger (available from the opening block on);
+    // fall back to ETL for leases opened before the backend was watching.
+    let pnl = pnl_sources
+        .ledger
+        .and_then(|ledger| {
+            calculate_ledger_pnl(
+                protocol,
+                opened,
+                &total_debt,
+                ledger,
+                pnl_sources.prices,
+                pnl_sources.currencies,
+            )
+        })
+        .or_else(|| {
+            calculate_pnl(
+                protocol,
+                opened,
+                &total_debt,
+                etl_data.as_ref(),
+                pnl_sources.prices,
+                pnl_sources.currencies,
+            )
+        });
 
     Ok(LeaseInfo {
         address: lease_address.to_string(),
@@ -1240,6 +2071,145 @@ fn calculate_total_debt(opened: &OpenedLeaseInfo) -> Result<String, AppError> {
     Ok(total.to_string())
 }
 
+/// The stop-loss or take-profit once `change` applies to `current`
+fn apply_close_policy_change(
+    current: Option<u32>,
+    change: Option<ClosePolicyChange>,
+) -> Option<u32> {
+    match change {
+        None => current,
+        Some(ClosePolicyChange::Set(permille)) => Some(permille),
+        Some(ClosePolicyChange::Reset) => None,
+    }
+}
+
+/// Check a close policy against the current LTV and the liquidation LTV (all
+/// permille): the stop-loss must sit between the two, the take-profit below
+/// the current LTV, so neither fires the moment it is set.
+fn validate_close_policy(
+    policy: &LeaseClosePolicy,
+    ltv_permille: f64,
+    liquidation_permille: u32,
+) -> Result<(), AppError> {
+    let invalid = |field: &str, message: String| AppError::Validation {
+        message,
+        field: Some(field.to_string()),
+        details: None,
+    };
+    if let Some(stop_loss) = policy.stop_loss {
+        if stop_loss >= liquidation_permille {
+            return Err(invalid(
+                "stop_loss",
+                format!(
+                    "stop_loss of {} permille must be below the liquidation LTV of {} permille",
+                    stop_loss, liquidation_permille
+                ),
+            ));
+        }
+        if f64::from(stop_loss) <= ltv_permille {
+            return Err(invalid(
+                "stop_loss",
+                format!(
+                    "stop_loss of {} permille would trigger at the current LTV of {:.1} permille",
+                    stop_loss, ltv_permille
+                ),
+            ));
+        }
+    }
+    if let Some(take_profit) = policy.take_profit {
+        if take_profit == 0 || f64::from(take_profit) >= ltv_permille {
+            return Err(invalid(
+                "take_profit",
+                format!(
+                    "take_profit of {} permille must be above zero and below the current LTV of {:.1} permille",
+                    take_profit, ltv_permille
+                ),
+            ));
+        }
+    }
+    if let (Some(stop_loss), Some(take_profit)) = (policy.stop_loss, policy.take_profit) {
+        if take_profit >= stop_loss {
+            return Err(invalid(
+                "take_profit",
+                format!(
+                    "take_profit of {} permille must be below stop_loss of {} permille",
+                    take_profit, stop_loss
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check a partial close against the leaser limits (all USD): the sale must
+/// reach the minimum transaction and leave at least the minimum asset.
+fn validate_partial_close(
+    sold_usd: f64,
+    remaining_usd: f64,
+    min_transaction_usd: f64,
+    min_asset_usd: f64,
+) -> Result<(), AppError> {
+    let invalid = |message: String| AppError::Validation {
+        message,
+        field: Some("amount".to_string()),
+        details: None,
+    };
+    if sold_usd < min_transaction_usd {
+        return Err(invalid(format!(
+            "closing ${:.2} is below the minimum transaction of ${:.2}",
+            sold_usd, min_transaction_usd
+        )));
+    }
+    if remaining_usd < min_asset_usd {
+        return Err(invalid(format!(
+            "the remaining ${:.2} position would be below the minimum of ${:.2}",
+            remaining_usd, min_asset_usd
+        )));
+    }
+    Ok(())
+}
+
+/// Lease risk around a partial close, with LTVs as ratios
+#[derive(Debug, PartialEq)]
+struct PartialCloseFigures {
+    debt_after_usd: f64,
+    surplus_usd: f64,
+    ltv_before: f64,
+    ltv_after: f64,
+    liquidation_price_before: Option<f64>,
+    liquidation_price_after: Option<f64>,
+}
+
+/// Sell `sold_usd` of the position and repay the debt with the proceeds;
+/// whatever exceeds the debt is paid out. Liquidation prices follow
+/// [`ltv_trigger_price`] at `liquidation_ratio`.
+fn partial_close_figures(
+    position_usd: f64,
+    debt_usd: f64,
+    sold_usd: f64,
+    price: f64,
+    liquidation_ratio: f64,
+    is_short: bool,
+) -> PartialCloseFigures {
+    let repaid_usd = sold_usd.min(debt_usd);
+    let debt_after_usd = debt_usd - repaid_usd;
+    let remaining_usd = position_usd - sold_usd;
+    let ltv = |debt: f64, position: f64| if position > 0.0 { debt / position } else { 0.0 };
+    let liquidation_price = |ratio: f64| {
+        (ratio > 0.0).then(|| ltv_trigger_price(price, ratio, liquidation_ratio, is_short))
+    };
+    let ltv_before = ltv(debt_usd, position_usd);
+    let ltv_after = ltv(debt_after_usd, remaining_usd);
+    PartialCloseFigures {
+        debt_after_usd,
+        surplus_usd: sold_usd - repaid_usd,
+        ltv_before,
+        ltv_after,
+        liquidation_price_before: liquidation_price(ltv_before),
+        liquidation_price_after: liquidation_price(ltv_after),
+    }
+}
+
 fn calculate_total_debt_from_closing(closing: &ClosingLeaseInfo) -> Result<String, AppError> {
     let principal = parse_amount(&closing.principal_due.amount, "principal_due")?;
     let overdue_margin = parse_amount(&closing.overdue_margin.amount, "overdue_margin")?;
@@ -1416,25 +2386,401 @@ fn calculate_pnl(
         None => 0.0,
     };
 
-    // PnL formula
-    let pnl_amount = asset_value_usd - total_debt_usd - downpayment + fee - repayment_value;
-    let total_invested = downpayment + repayment_value;
-    let pnl_percent = if total_invested > 0.0 {
-        (pnl_amount / total_invested) * 100.0
+    // PnL formula
+    let pnl_amount = asset_value_usd - total_debt_usd - downpayment + fee - repayment_value;
+    let total_invested = downpayment + repayment_value;
+    let pnl_percent = if total_invested > 0.0 {
+        (pnl_amount / total_invested) * 100.0
+    } else {
+        0.0
+    };
+    let pnl_positive = pnl_amount >= 0.0;
+
+    Some(LeasePnlInfo {
+        amount: format!("{:.6}", pnl_amount),
+        percent: format!("{:.2}", pnl_percent),
+        downpayment: etl
+            .lease
+            .downpayment_amount
+            .clone()
+            .unwrap_or_else(|| "0".to_string()),
+        pnl_positive,
+        breakdown: None,
+    })
+}
+
+/// Calculate PnL for an opened lease from the backend's own lease ledger.
+///
+/// Everything paid in is valued at the time it was paid:
+///   invested = downpayment_usd + Σ repayment_usd
+///   amount   = assetValueUsd - totalDebtUsd - invested
+///   percent  = amount / invested * 100
+///
+/// Interest and fees cover both what repayments already settled and what is
+/// accrued in the current debt, valued at today's LPN price. Price PnL is the
+/// remainder, so the components always add up to `amount`.
+fn calculate_ledger_pnl(
+    protocol: &str,
+    opened: &OpenedLeaseInfo,
+    total_debt: &str,
+    ledger: &LeaseLedgerEntry,
+    prices: Option<&crate::handlers::currencies::PricesResponse>,
+    currencies: Option<&crate::handlers::currencies::CurrenciesResponse>,
+) -> Option<LeasePnlInfo> {
+    let prices = prices?;
+    let currencies = currencies?;
+    let usd = |ticker: &str, amount: &str| {
+        crate::lease_ledger::usd_value(prices, currencies, ticker, protocol, amount)
+    };
+
+    let asset_value_usd = usd(&opened.amount.ticker, &opened.amount.amount)?;
+    let lpn_ticker = &opened.principal_due.ticker;
+    let total_debt_usd = usd(lpn_ticker, total_debt)?;
+
+    let repaid_usd = ledger.repaid_usd()?;
+    let invested = ledger.downpayment.usd + repaid_usd;
+    let pnl_amount = asset_value_usd - total_debt_usd - invested;
+    let pnl_percent = if invested > 0.0 {
+        (pnl_amount / invested) * 100.0
+    } else {
+        0.0
+    };
+
+    let (loan_interest_repaid, margin_interest_repaid) = ledger.interest_repaid();
+    let accrued = |parts: [&LeaseAmount; 2], repaid: u128| -> Option<f64> {
+        let mut total = repaid;
+        for part in parts {
+            total = total.saturating_add(part.amount.parse::<u128>().ok()?);
+        }
+        usd(lpn_ticker, &total.to_string())
+    };
+    let interest = accrued(
+        [&opened.due_interest, &opened.overdue_interest],
+        loan_interest_repaid,
+    )?;
+    let fees = accrued(
+        [&opened.due_margin, &opened.overdue_margin],
+        margin_interest_repaid,
+    )?;
+    let swap_costs = ledger.swap_cost_usd();
+    let price_pnl = pnl_amount + interest + fees + swap_costs.unwrap_or(0.0);
+
+    // Same scale as ETL's LS_cltr_amnt_stable: USD × 10^(downpayment decimals)
+    let downpayment_decimals = currencies
+        .currencies
+        .get(&format!("{}@{}", ledger.downpayment.ticker, protocol))
+        .map_or(6, |c| i32::from(c.decimal_digits));
+    let downpayment = ledger.downpayment.usd * 10_f64.powi(downpayment_decimals);
+
+    Some(LeasePnlInfo {
+        amount: format!("{:.6}", pnl_amount),
+        percent: format!("{:.2}", pnl_percent),
+        downpayment: format!("{:.0}", downpayment),
+        pnl_positive: pnl_amount >= 0.0,
+        breakdown: Some(LeasePnlBreakdown {
+            price_pnl: format!("{:.6}", price_pnl),
+            interest: format!("{:.6}", interest),
+            fees: format!("{:.6}", fees),
+            swap_costs: swap_costs.map(|cost| format!("{:.6}", cost)),
+            repaid: format!("{:.6}", repaid_usd),
+        }),
+    })
+}
+
+/// Whether the protocol holds short positions, per the cached app config
+pub fn is_short_protocol(state: &AppState, protocol: &str) -> bool {
+    state
+        .data_cache
+        .app_config
+        .load()
+        .and_then(|config| {
+            config
+                .protocols
+                .get(protocol)
+                .map(|info| info.position_type == "short")
+        })
+        .unwrap_or(false)
+}
+
+/// Price of the position asset at which the lease reaches `ratio` LTV.
+///
+/// A long position holds the lease asset against an LPN debt, so the trigger
+/// is reached when the asset price falls to `price * initial_ratio / ratio`.
+/// A short position holds stable value against a debt in the borrowed asset,
+/// so the trigger is reached when that asset rises to `price * ratio / initial_ratio`.
+fn ltv_trigger_price(price: f64, initial_ratio: f64, ratio: f64, is_short: bool) -> f64 {
+    if is_short {
+        price * ratio / initial_ratio
+    } else {
+        price * initial_ratio / ratio
+    }
+}
+
+/// LTV figures and liquidation price derived for a lease quote
+#[derive(Debug, Default, PartialEq)]
+struct QuoteRiskEstimate {
+    initial_ltv: Option<f64>,
+    liquidation_ltv: Option<f64>,
+    liquidation_price: Option<String>,
+}
+
+/// Convert a chain amount of `ticker` to USD using cached prices and decimals
+fn quote_amount_usd(
+    protocol: &str,
+    ticker: &str,
+    amount: &str,
+    prices: &crate::handlers::currencies::PricesResponse,
+    currencies: &crate::handlers::currencies::CurrenciesResponse,
+) -> Option<f64> {
+    let key = format!("{}@{}", ticker, protocol);
+    let decimals = i32::from(currencies.currencies.get(&key)?.decimal_digits);
+    let price = quote_price_usd(protocol, ticker, prices)?;
+    let raw: f64 = amount.parse().ok()?;
+    Some(raw / 10_f64.powi(decimals) * price)
+}
+
+fn quote_price_usd(
+    protocol: &str,
+    ticker: &str,
+    prices: &crate::handlers::currencies::PricesResponse,
+) -> Option<f64> {
+    prices
+        .prices
+        .get(&format!("{}@{}", ticker, protocol))
+        .and_then(|p| p.price_usd.parse().ok())
+        .filter(|price: &f64| *price > 0.0)
+}
+
+/// Estimate the opening LTV and the liquidation point of a quoted lease.
+///
+/// The liquidation price follows [`ltv_trigger_price`] at the liability `max`.
+/// Swap fees are ignored, which makes the estimate slightly optimistic.
+fn estimate_quote_risk(
+    request: &LeaseQuoteRequest,
+    borrow: &LeaseAmount,
+    is_short: bool,
+    liability: Option<&LiabilitySpec>,
+    prices: Option<&crate::handlers::currencies::PricesResponse>,
+    currencies: Option<&crate::handlers::currencies::CurrenciesResponse>,
+) -> QuoteRiskEstimate {
+    let liquidation_ratio = liability.map(|spec| f64::from(spec.max) / PERMILLE);
+    let mut estimate = QuoteRiskEstimate {
+        liquidation_ltv: liquidation_ratio.map(|ratio| ratio * 100.0),
+        ..Default::default()
+    };
+
+    let (Some(prices), Some(currencies)) = (prices, currencies) else {
+        return estimate;
+    };
+    let protocol = &request.protocol;
+
+    let Some(downpayment_usd) = quote_amount_usd(
+        protocol,
+        &request.downpayment_ticker,
+        &request.downpayment_amount,
+        prices,
+        currencies,
+    ) else {
+        return estimate;
+    };
+    let Some(borrow_usd) =
+        quote_amount_usd(protocol, &borrow.ticker, &borrow.amount, prices, currencies)
+    else {
+        return estimate;
+    };
+
+    let position_usd = downpayment_usd + borrow_usd;
+    if position_usd <= 0.0 || borrow_usd <= 0.0 {
+        return estimate;
+    }
+    let initial_ratio = borrow_usd / position_usd;
+    estimate.initial_ltv = Some(initial_ratio * 100.0);
+
+    let Some(liquidation_ratio) = liquidation_ratio.filter(|ratio| *ratio > 0.0) else {
+        return estimate;
+    };
+
+    let price_ticker = if is_short {
+        Some(borrow.ticker.as_str())
+    } else {
+        request.lease_asset.as_deref()
+    };
+    estimate.liquidation_price = price_ticker
+        .and_then(|ticker| quote_price_usd(protocol, ticker, prices))
+        .map(|price| {
+            format!(
+                "{:.6}",
+                ltv_trigger_price(price, initial_ratio, liquidation_ratio, is_short)
+            )
+        });
+
+    estimate
+}
+
+/// Everything [`simulate_lease_position`] needs, gathered from the quote and caches
+struct LeaseSimulationInputs<'a> {
+    request: &'a SimulateLeaseRequest,
+    quote: &'a crate::external::chain::LeaseQuoteResponse,
+    is_short: bool,
+    liability: Option<&'a LiabilitySpec>,
+    downpayment_ranges: Option<
+        &'a std::collections::HashMap<String, crate::config_store::gated_types::DownpaymentRange>,
+    >,
+    swap_fee_bps: u32,
+    slippage_percent: f64,
+    prices: &'a crate::handlers::currencies::PricesResponse,
+    currencies: &'a crate::handlers::currencies::CurrenciesResponse,
+}
+
+/// Build the position preview for a quoted lease.
+///
+/// The part of the position not already held in the lease asset is swapped,
+/// paying the configured swap fee; the minimum output applies the slippage
+/// tolerance on top. LTVs and trigger prices use pre-swap values, matching
+/// [`estimate_quote_risk`].
+fn simulate_lease_position(
+    inputs: &LeaseSimulationInputs<'_>,
+) -> Result<SimulateLeaseResponse, AppError> {
+    let request = inputs.request;
+    let protocol = &request.protocol;
+    let borrow = &inputs.quote.borrow;
+
+    let value_of = |ticker: &str, amount: &str| -> Result<f64, AppError> {
+        quote_amount_usd(protocol, ticker, amount, inputs.prices, inputs.currencies).ok_or_else(
+            || AppError::ServiceUnavailable {
+                message: format!("No price or currency data for {}@{}", ticker, protocol),
+            },
+        )
+    };
+    let downpayment_usd = value_of(&request.downpayment_ticker, &request.downpayment_amount)?;
+    let borrow_usd = value_of(&borrow.ticker, &borrow.amount)?;
+
+    let asset_key = format!("{}@{}", request.lease_asset, protocol);
+    let asset_decimals = inputs
+        .currencies
+        .currencies
+        .get(&asset_key)
+        .map(|c| i32::from(c.decimal_digits))
+        .ok_or_else(|| AppError::Validation {
+            message: format!("Unknown lease asset {}", request.lease_asset),
+            field: Some("lease_asset".to_string()),
+            details: None,
+        })?;
+    let asset_price =
+        quote_price_usd(protocol, &request.lease_asset, inputs.prices).ok_or_else(|| {
+            AppError::ServiceUnavailable {
+                message: format!("No price for {}", asset_key),
+            }
+        })?;
+
+    // Only the part of the position not already in the lease asset is swapped
+    let mut swap_input_usd = borrow_usd;
+    if request.downpayment_ticker != request.lease_asset {
+        swap_input_usd += downpayment_usd;
+    }
+    if borrow.ticker == request.lease_asset {
+        swap_input_usd -= borrow_usd;
+    }
+    let fee_usd = swap_input_usd * f64::from(inputs.swap_fee_bps) / 10_000.0;
+
+    let position_usd = downpayment_usd + borrow_usd;
+    let to_asset_units = |usd: f64| {
+        format!(
+            "{:.0}",
+            (usd / asset_price * 10_f64.powi(asset_decimals)).floor()
+        )
+    };
+    let expected_output = to_asset_units(position_usd - fee_usd);
+    let min_output =
+        to_asset_units((position_usd - fee_usd) * (1.0 - inputs.slippage_percent / 100.0));
+
+    let annual_interest_rate =
+        f64::from(inputs.quote.annual_interest_rate + inputs.quote.annual_interest_rate_margin)
+            / 10f64.powi(INTEREST_DECIMALS)
+            * 100.0;
+    let interest_per_day_usd = borrow_usd * annual_interest_rate / 100.0 / 365.0;
+
+    let initial_ratio = (position_usd > 0.0).then_some(borrow_usd / position_usd);
+    let price_ticker = if inputs.is_short {
+        &borrow.ticker
     } else {
-        0.0
+        &request.lease_asset
+    };
+    let trigger_price = |permille: u32| -> Option<String> {
+        let initial_ratio = initial_ratio.filter(|r| *r > 0.0)?;
+        let ratio = f64::from(permille) / PERMILLE;
+        if ratio <= 0.0 {
+            return None;
+        }
+        let price = quote_price_usd(protocol, price_ticker, inputs.prices)?;
+        Some(format!(
+            "{:.6}",
+            ltv_trigger_price(price, initial_ratio, ratio, inputs.is_short)
+        ))
     };
-    let pnl_positive = pnl_amount >= 0.0;
 
-    Some(LeasePnlInfo {
-        amount: format!("{:.6}", pnl_amount),
-        percent: format!("{:.2}", pnl_percent),
-        downpayment: etl
-            .lease
-            .downpayment_amount
-            .clone()
-            .unwrap_or_else(|| "0".to_string()),
-        pnl_positive,
+    let warnings = inputs
+        .liability
+        .map(|spec| {
+            [
+                (1, spec.first_liq_warn),
+                (2, spec.second_liq_warn),
+                (3, spec.third_liq_warn),
+            ]
+            .into_iter()
+            .map(|(level, permille)| SimulatedWarningLevel {
+                level,
+                ltv: f64::from(permille) / PERMILLE * 100.0,
+                price: trigger_price(permille),
+            })
+            .collect()
+        })
+        .unwrap_or_default();
+
+    let downpayment_range = inputs
+        .downpayment_ranges
+        .and_then(|ranges| ranges.get(&request.lease_asset))
+        .map(|range| DownpaymentRangeCheck {
+            min_usd: range.min,
+            max_usd: range.max,
+            within_range: downpayment_usd >= range.min && downpayment_usd <= range.max,
+        });
+
+    Ok(SimulateLeaseResponse {
+        protocol: protocol.clone(),
+        position_type: if inputs.is_short { "short" } else { "long" }.to_string(),
+        downpayment: SimulatedAmount {
+            ticker: request.downpayment_ticker.clone(),
+            amount: request.downpayment_amount.clone(),
+            amount_usd: downpayment_usd,
+        },
+        borrow: SimulatedAmount {
+            ticker: borrow.ticker.clone(),
+            amount: borrow.amount.clone(),
+            amount_usd: borrow_usd,
+        },
+        position: SimulatedAmount {
+            ticker: request.lease_asset.clone(),
+            amount: expected_output.clone(),
+            amount_usd: position_usd - fee_usd,
+        },
+        swap: SimulatedSwap {
+            input_usd: swap_input_usd,
+            fee_usd,
+            fee_bps: inputs.swap_fee_bps,
+            slippage_percent: inputs.slippage_percent,
+            expected_output,
+            min_output,
+        },
+        annual_interest_rate,
+        interest_per_day_usd,
+        initial_ltv: initial_ratio.map(|r| r * 100.0),
+        liquidation_ltv: inputs
+            .liability
+            .map(|spec| f64::from(spec.max) / PERMILLE * 100.0),
+        liquidation_price: inputs.liability.and_then(|spec| trigger_price(spec.max)),
+        warnings,
+        downpayment_range,
     })
 }
 
@@ -1481,6 +2827,73 @@ pub struct LeaseMonitorInfo {
     pub close_policy: Option<LeaseClosePolicy>,
     pub in_progress: Option<LeaseInProgress>,
     pub reason: Option<String>,
+    /// Time until overdue interest collection (nanoseconds, opened leases only)
+    pub overdue_collect_in: Option<u64>,
+}
+
+/// How far a lease is from liquidation at current prices
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LiquidationDistance {
+    /// Ticker whose price drives liquidation (the lease asset for longs, the
+    /// borrowed asset for shorts)
+    pub price_ticker: String,
+    pub current_price: String,
+    pub liquidation_price: String,
+    /// Price move, in percent of the current price, that triggers liquidation
+    pub distance_percent: f64,
+    /// Current LTV (percent)
+    pub ltv: f64,
+    /// LTV at which the lease is liquidated (percent)
+    pub liquidation_ltv: f64,
+}
+
+/// Compute the current LTV and the distance to the liquidation price of a
+/// monitored lease. `None` when the lease has no debt or prices are missing.
+pub fn compute_liquidation_distance(
+    lease: &LeaseMonitorInfo,
+    liability: &LiabilitySpec,
+    is_short: bool,
+    prices: &crate::handlers::currencies::PricesResponse,
+    currencies: &crate::handlers::currencies::CurrenciesResponse,
+) -> Option<LiquidationDistance> {
+    let amount = lease.amount.as_ref()?;
+    let debt = lease.debt.as_ref()?;
+    let protocol = &lease.protocol;
+
+    let position_usd =
+        quote_amount_usd(protocol, &amount.ticker, &amount.amount, prices, currencies)?;
+    let debt_usd = quote_amount_usd(protocol, &debt.ticker, &debt.total, prices, currencies)?;
+    if position_usd <= 0.0 || debt_usd <= 0.0 {
+        return None;
+    }
+
+    let ratio = debt_usd / position_usd;
+    let liquidation_ratio = f64::from(liability.max) / PERMILLE;
+    if liquidation_ratio <= 0.0 {
+        return None;
+    }
+
+    let price_ticker = if is_short {
+        &debt.ticker
+    } else {
+        &amount.ticker
+    };
+    let price = quote_price_usd(protocol, price_ticker, prices)?;
+    let liquidation_price = ltv_trigger_price(price, ratio, liquidation_ratio, is_short);
+    let move_to_liquidation = if is_short {
+        liquidation_price - price
+    } else {
+        price - liquidation_price
+    };
+
+    Some(LiquidationDistance {
+        price_ticker: price_ticker.clone(),
+        current_price: format!("{:.6}", price),
+        liquidation_price: format!("{:.6}", liquidation_price),
+        distance_percent: (move_to_liquidation / price * 100.0).max(0.0),
+        ltv: ratio * 100.0,
+        liquidation_ltv: liquidation_ratio * 100.0,
+    })
 }
 
 /// Fetch all leases for an owner (for WebSocket monitoring)
@@ -1627,6 +3040,7 @@ fn build_lease_monitor_info(
                 stage: parse_opening_stage(&opening.opening.in_progress),
             }),
             reason: None,
+            overdue_collect_in: None,
         },
         LeaseStatusResponse::Opened(opened) => {
             let info = &opened.opened;
@@ -1666,6 +3080,7 @@ fn build_lease_monitor_info(
                 }),
                 in_progress: parse_opened_status(&info.status),
                 reason: None,
+                overdue_collect_in: info.overdue_collect_in,
             }
         }
         LeaseStatusResponse::Closing(closing) => {
@@ -1703,6 +3118,7 @@ fn build_lease_monitor_info(
                 close_policy: None,
                 in_progress: Some(LeaseInProgress::Close {}),
                 reason: None,
+                overdue_collect_in: None,
             }
         }
         LeaseStatusResponse::PaidOff(paid_off) => LeaseMonitorInfo {
@@ -1721,6 +3137,7 @@ fn build_lease_monitor_info(
             close_policy: None,
             in_progress: None,
             reason: None,
+            overdue_collect_in: None,
         },
         LeaseStatusResponse::Closed(_) => LeaseMonitorInfo {
             address: lease_address.to_string(),
@@ -1734,6 +3151,7 @@ fn build_lease_monitor_info(
             close_policy: None,
             in_progress: None,
             reason: None,
+            overdue_collect_in: None,
         },
         LeaseStatusResponse::Liquidated(_) => LeaseMonitorInfo {
             address: lease_address.to_string(),
@@ -1747,6 +3165,7 @@ fn build_lease_monitor_info(
             close_policy: None,
             in_progress: None,
             reason: None,
+            overdue_collect_in: None,
         },
         LeaseStatusResponse::OpenFailed(failed) => LeaseMonitorInfo {
             address: lease_address.to_string(),
@@ -1760,6 +3179,7 @@ fn build_lease_monitor_info(
             close_policy: None,
             in_progress: None,
             reason: Some(failed.open_failed.reason.clone()),
+            overdue_collect_in: None,
         },
     };
 
@@ -2056,8 +3476,10 @@ mod tests {
 
     // ── PnL calculation tests ────────────────────────────────────
 
+    use crate::config_store::gated_types::DownpaymentRange;
     use crate::external::chain::{
         LeaseAmount, LeaseClosing, LeaseOpened, LeaseOpening, LeaseOpeningInfo,
+        LeaseQuoteResponse as ChainLeaseQuote,
     };
     use crate::external::etl::{EtlLeaseInfo, EtlLeaseOpening};
     use crate::handlers::currencies::{
@@ -2372,6 +3794,440 @@ mod tests {
         assert!(pnl.pnl_positive);
     }
 
+    #[test]
+    fn test_calculate_ledger_pnl_breakdown() {
+        // 0.01 BTC at $100,000 = $1000; debt 500 principal + 1.5 interest
+        // + 0.5 margin = $502. Paid in: $400 downpayment + $20 repayment
+        // (which settled $1 of loan interest). Opening swap turned $900 into
+        // $895 of BTC.
+        // PnL = 1000 - 502 - 420 = $78; 78 / 420 = 18.57%
+        // interest = 1 + 1.5 = 2.5, fees = 0.5, swap = 5
+        // price PnL = 78 + 2.5 + 0.5 + 5 = $86
+        let mut opened = make_opened("ALL_BTC", "1000000", "USDC_NOBLE");
+        opened.due_interest.amount = "1500000".to_string();
+        opened.due_margin.amount = "500000".to_string();
+        let (prices, currencies) = make_prices_and_currencies("100000", "1.0");
+        let usdc = |usd: f64| crate::lease_ledger::LedgerAmount {
+            ticker: "USDC_NOBLE".to_string(),
+            amount: "0".to_string(),
+            usd,
+        };
+        let ledger = LeaseLedgerEntry {
+            protocol: "TEST-PROTOCOL".to_string(),
+            opened_at: chrono::Utc::now(),
+            downpayment: usdc(400.0),
+            loan: usdc(500.0),
+            opened_position: Some(usdc(895.0)),
+            repayments: vec![crate::lease_ledger::LeaseRepayment {
+                tx_hash: "REPAY".to_string(),
+                at: chrono::Utc::now(),
+                payment: usdc(20.0),
+                unpriced: false,
+                principal: 19_000_000,
+                loan_interest: 1_000_000,
+                margin_interest: 0,
+            }],
+        };
+
+        let pnl = calculate_ledger_pnl(
+            "TEST-PROTOCOL",
+            &opened,
+            "502000000",
+            &ledger,
+            Some(&prices),
+            Some(&currencies),
+        )
+        .unwrap();
+
+        let num = |s: &str| s.parse::<f64>().unwrap();
+        assert!(
+            (num(&pnl.amount) - 78.0).abs() < 1e-6,
+            "PnL: {}",
+            pnl.amount
+        );
+        assert!((num(&pnl.percent) - 18.57).abs() < 0.01);
+        assert_eq!(pnl.downpayment, "400000000");
+        let breakdown = pnl.breakdown.unwrap();
+        assert!((num(&breakdown.interest) - 2.5).abs() < 1e-6);
+        assert!((num(&breakdown.fees) - 0.5).abs() < 1e-6);
+        assert!((num(breakdown.swap_costs.as_deref().unwrap()) - 5.0).abs() < 1e-6);
+        assert!((num(&breakdown.price_pnl) - 86.0).abs() < 1e-6);
+        assert!((num(&breakdown.repaid) - 20.0).abs() < 1e-6);
+
+        // No prices, no PnL (the caller then falls back to ETL)
+        assert!(calculate_ledger_pnl(
+            "TEST-PROTOCOL",
+            &opened,
+            "502000000",
+            &ledger,
+            None,
+            Some(&currencies),
+        )
+        .is_none());
+
+        // Nor with a repayment the ledger could not price
+        let mut unpriced = ledger.clone();
+        unpriced.repayments[0].unpriced = true;
+        assert!(calculate_ledger_pnl(
+            "TEST-PROTOCOL",
+            &opened,
+            "502000000",
+            &unpriced,
+            Some(&prices),
+            Some(&currencies),
+        )
+        .is_none());
+    }
+
+    fn make_liability(max: u32) -> LiabilitySpec {
+        LiabilitySpec {
+            initial: 600,
+            healthy: 830,
+            first_liq_warn: 850,
+            second_liq_warn: 865,
+            third_liq_warn: 880,
+            max,
+            recalc_time: 7_200_000_000_000,
+        }
+    }
+
+    fn make_monitor_lease(
+        amount: (&str, &str),
+        debt: (&str, &str),
+        overdue_collect_in: Option<u64>,
+    ) -> LeaseMonitorInfo {
+        LeaseMonitorInfo {
+            address: "nolus1lease".to_string(),
+            protocol: "TEST-PROTOCOL".to_string(),
+            status: "opened".to_string(),
+            amount: Some(LeaseAssetInfo {
+                ticker: amount.0.to_string(),
+                amount: amount.1.to_string(),
+                amount_usd: None,
+            }),
+            debt: Some(LeaseDebtInfo {
+                ticker: debt.0.to_string(),
+                principal: debt.1.to_string(),
+                overdue_margin: "0".to_string(),
+                overdue_interest: "0".to_string(),
+                due_margin: "0".to_string(),
+                due_interest: "0".to_string(),
+                total: debt.1.to_string(),
+                total_usd: None,
+            }),
+            interest: None,
+            liquidation_price: None,
+            pnl: None,
+            close_policy: None,
+            in_progress: None,
+            reason: None,
+            overdue_collect_in,
+        }
+    }
+
+    #[test]
+    fn test_compute_liquidation_distance_long() {
+        // 0.01 BTC at $100,000 against 700 USDC → LTV 70%, liquidation at 90%
+        // → 100,000 * 0.7 / 0.9 = $77,777.78, a 22.2% drop away
+        let (prices, currencies) = make_prices_and_currencies("100000", "1.0");
+        let lease = make_monitor_lease(("ALL_BTC", "1000000"), ("USDC_NOBLE", "700000000"), None);
+
+        let distance =
+            compute_liquidation_distance(&lease, &make_liability(900), false, &prices, &currencies)
+                .expect("distance");
+
+        assert_eq!(distance.price_ticker, "ALL_BTC");
+        assert_eq!(distance.liquidation_price, "77777.777778");
+        assert!((distance.ltv - 70.0).abs() < 1e-9);
+        assert!((distance.liquidation_ltv - 90.0).abs() < 1e-9);
+        assert!((distance.distance_percent - 22.222_222).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_compute_liquidation_distance_short() {
+        // 1000 USDC held against 0.007 BTC borrowed ($700) → liquidation when
+        // BTC rises to 100,000 * 0.9 / 0.7 = $128,571.43 (+28.6%)
+        let (prices, currencies) = make_prices_and_currencies("100000", "1.0");
+        let lease = make_monitor_lease(("USDC_NOBLE", "1000000000"), ("ALL_BTC", "700000"), None);
+
+        let distance =
+            compute_liquidation_distance(&lease, &make_liability(900), true, &prices, &currencies)
+                .expect("distance");
+
+        assert_eq!(distance.price_ticker, "ALL_BTC");
+        assert_eq!(distance.liquidation_price, "128571.428571");
+        assert!((distance.distance_percent - 28.571_428).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_compute_liquidation_distance_none_without_debt_or_price() {
+        let (prices, currencies) = make_prices_and_currencies("100000", "1.0");
+        let repaid = make_monitor_lease(("ALL_BTC", "1000000"), ("USDC_NOBLE", "0"), None);
+        assert!(compute_liquidation_distance(
+            &repaid,
+            &make_liability(900),
+            false,
+            &prices,
+            &currencies
+        )
+        .is_none());
+
+        let unpriced = make_monitor_lease(("ATOM", "1000000"), ("USDC_NOBLE", "700000000"), None);
+        assert!(compute_liquidation_distance(
+            &unpriced,
+            &make_liability(900),
+            false,
+            &prices,
+            &currencies
+        )
+        .is_none());
+    }
+
+    fn make_quote_request(lease_asset: Option<&str>) -> LeaseQuoteRequest {
+        LeaseQuoteRequest {
+            protocol: "TEST-PROTOCOL".to_string(),
+            downpayment_ticker: "USDC_NOBLE".to_string(),
+            downpayment_amount: "400000000".to_string(), // 400 USDC
+            lease_asset: lease_asset.map(str::to_string),
+            max_ltd: None,
+        }
+    }
+
+    #[test]
+    fn test_estimate_quote_risk_long() {
+        // $400 down + $600 borrowed → initial LTV 60%, liquidation at 90%.
+        // BTC at $100,000 → liquidation at 100,000 * 0.6 / 0.9 = $66,666.67
+        let (prices, currencies) = make_prices_and_currencies("100000", "1.0");
+        let request = make_quote_request(Some("ALL_BTC"));
+        let borrow = LeaseAmount {
+            ticker: "USDC_NOBLE".to_string(),
+            amount: "600000000".to_string(),
+        };
+
+        let risk = estimate_quote_risk(
+            &request,
+            &borrow,
+            false,
+            Some(&make_liability(900)),
+            Some(&prices),
+            Some(&currencies),
+        );
+
+        assert!((risk.initial_ltv.unwrap() - 60.0).abs() < 0.001);
+        assert!((risk.liquidation_ltv.unwrap() - 90.0).abs() < 0.001);
+        let price: f64 = risk.liquidation_price.unwrap().parse().unwrap();
+        assert!((price - 66_666.666_667).abs() < 0.01, "price: {}", price);
+    }
+
+    #[test]
+    fn test_estimate_quote_risk_short() {
+        // $400 down + 0.006 BTC borrowed at $100,000 ($600) → initial LTV 60%.
+        // Debt grows with BTC: liquidation at 100,000 * 0.9 / 0.6 = $150,000
+        let (prices, currencies) = make_prices_and_currencies("100000", "1.0");
+        let request = make_quote_request(None);
+        let borrow = LeaseAmount {
+            ticker: "ALL_BTC".to_string(),
+            amount: "600000".to_string(),
+        };
+
+        let risk = estimate_quote_risk(
+            &request,
+            &borrow,
+            true,
+            Some(&make_liability(900)),
+            Some(&prices),
+            Some(&currencies),
+        );
+
+        assert!((risk.initial_ltv.unwrap() - 60.0).abs() < 0.001);
+        let price: f64 = risk.liquidation_price.unwrap().parse().unwrap();
+        assert!((price - 150_000.0).abs() < 0.01, "price: {}", price);
+    }
+
+    #[test]
+    fn test_estimate_quote_risk_long_without_lease_asset_has_no_price() {
+        let (prices, currencies) = make_prices_and_currencies("100000", "1.0");
+        let request = make_quote_request(None);
+        let borrow = LeaseAmount {
+            ticker: "USDC_NOBLE".to_string(),
+            amount: "600000000".to_string(),
+        };
+
+        let risk = estimate_quote_risk(
+            &request,
+            &borrow,
+            false,
+            Some(&make_liability(900)),
+            Some(&prices),
+            Some(&currencies),
+        );
+
+        assert!(risk.initial_ltv.is_some());
+        assert!(risk.liquidation_price.is_none());
+    }
+
+    #[test]
+    fn test_estimate_quote_risk_without_cache_data() {
+        let request = make_quote_request(Some("ALL_BTC"));
+        let borrow = LeaseAmount {
+            ticker: "USDC_NOBLE".to_string(),
+            amount: "600000000".to_string(),
+        };
+
+        // No liability spec and no prices: nothing can be derived
+        assert_eq!(
+            estimate_quote_risk(&request, &borrow, false, None, None, None),
+            QuoteRiskEstimate::default()
+        );
+
+        // Liability spec alone still yields the liquidation LTV
+        let risk = estimate_quote_risk(
+            &request,
+            &borrow,
+            false,
+            Some(&make_liability(900)),
+            None,
+            None,
+        );
+        assert!((risk.liquidation_ltv.unwrap() - 90.0).abs() < 0.001);
+        assert!(risk.initial_ltv.is_none());
+        assert!(risk.liquidation_price.is_none());
+    }
+
+    fn make_chain_quote(borrow_ticker: &str, borrow_amount: &str) -> ChainLeaseQuote {
+        ChainLeaseQuote {
+            borrow: LeaseAmount {
+                ticker: borrow_ticker.to_string(),
+                amount: borrow_amount.to_string(),
+            },
+            annual_interest_rate: 1_000_000, // 10%
+            annual_interest_rate_margin: 0,
+        }
+    }
+
+    fn make_simulate_request() -> SimulateLeaseRequest {
+        SimulateLeaseRequest {
+            protocol: "TEST-PROTOCOL".to_string(),
+            downpayment_ticker: "USDC_NOBLE".to_string(),
+            downpayment_amount: "400000000".to_string(), // 400 USDC
+            lease_asset: "ALL_BTC".to_string(),
+            max_ltd: None,
+        }
+    }
+
+    #[test]
+    fn test_simulate_lease_position_long() {
+        // $400 down + $600 borrowed, all swapped into BTC at $100,000.
+        // Fee 10 bps on $1000 = $1 → expected 999 / 100,000 BTC = 999_000 sats,
+        // 1% slippage → min 989_010 sats.
+        let (prices, currencies) = make_prices_and_currencies("100000", "1.0");
+        let request = make_simulate_request();
+        let quote = make_chain_quote("USDC_NOBLE", "600000000");
+        let liability = make_liability(900);
+        let mut ranges = HashMap::new();
+        ranges.insert(
+            "ALL_BTC".to_string(),
+            DownpaymentRange {
+                min: 50.0,
+                max: 300.0,
+            },
+        );
+
+        let preview = simulate_lease_position(&LeaseSimulationInputs {
+            request: &request,
+            quote: &quote,
+            is_short: false,
+            liability: Some(&liability),
+            downpayment_ranges: Some(&ranges),
+            swap_fee_bps: 10,
+            slippage_percent: 1.0,
+            prices: &prices,
+            currencies: &currencies,
+        })
+        .expect("preview");
+
+        assert_eq!(preview.position_type, "long");
+        assert!((preview.swap.input_usd - 1000.0).abs() < 1e-9);
+        assert!((preview.swap.fee_usd - 1.0).abs() < 1e-9);
+        assert_eq!(preview.swap.expected_output, "999000");
+        let min_output: f64 = preview.swap.min_output.parse().unwrap();
+        assert!((min_output - 989_010.0).abs() <= 1.0, "min: {}", min_output);
+        assert_eq!(preview.position.amount, preview.swap.expected_output);
+
+        assert!((preview.interest_per_day_usd - 600.0 * 0.1 / 365.0).abs() < 1e-9);
+        assert!((preview.initial_ltv.unwrap() - 60.0).abs() < 1e-9);
+
+        // Warning prices sit between the current and the liquidation price
+        let liquidation: f64 = preview.liquidation_price.unwrap().parse().unwrap();
+        assert!((liquidation - 66_666.666_667).abs() < 0.01);
+        assert_eq!(preview.warnings.len(), 3);
+        let first: f64 = preview.warnings[0]
+            .price
+            .as_deref()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((first - 70_588.235_294).abs() < 0.01, "first: {}", first);
+        assert!((preview.warnings[2].ltv - 88.0).abs() < 1e-9);
+
+        // $400 exceeds the $300 cap for ALL_BTC
+        let range = preview.downpayment_range.unwrap();
+        assert!(!range.within_range);
+        assert_eq!(range.max_usd, 300.0);
+    }
+
+    #[test]
+    fn test_simulate_lease_position_skips_swap_for_in_kind_downpayment() {
+        // Downpayment already in BTC: only the borrowed $600 goes through the DEX
+        let (prices, currencies) = make_prices_and_currencies("100000", "1.0");
+        let mut request = make_simulate_request();
+        request.downpayment_ticker = "ALL_BTC".to_string();
+        request.downpayment_amount = "400000".to_string(); // 0.004 BTC = $400
+        let quote = make_chain_quote("USDC_NOBLE", "600000000");
+
+        let preview = simulate_lease_position(&LeaseSimulationInputs {
+            request: &request,
+            quote: &quote,
+            is_short: false,
+            liability: None,
+            downpayment_ranges: None,
+            swap_fee_bps: 100,
+            slippage_percent: 0.0,
+            prices: &prices,
+            currencies: &currencies,
+        })
+        .expect("preview");
+
+        assert!((preview.swap.input_usd - 600.0).abs() < 1e-9);
+        assert!((preview.swap.fee_usd - 6.0).abs() < 1e-9);
+        assert!(preview.liquidation_price.is_none());
+        assert!(preview.warnings.is_empty());
+        assert!(preview.downpayment_range.is_none());
+    }
+
+    #[test]
+    fn test_simulate_lease_position_unknown_asset_is_validation_error() {
+        let (prices, currencies) = make_prices_and_currencies("100000", "1.0");
+        let mut request = make_simulate_request();
+        request.lease_asset = "NOPE".to_string();
+        let quote = make_chain_quote("USDC_NOBLE", "600000000");
+
+        let err = simulate_lease_position(&LeaseSimulationInputs {
+            request: &request,
+            quote: &quote,
+            is_short: false,
+            liability: None,
+            downpayment_ranges: None,
+            swap_fee_bps: 0,
+            slippage_percent: 0.0,
+            prices: &prices,
+            currencies: &currencies,
+        })
+        .expect_err("unknown asset");
+
+        assert!(matches!(err, AppError::Validation { field: Some(f), .. } if f == "lease_asset"));
+    }
+
     #[test]
     fn test_enrich_history_action_liquidation_with_cause() {
         assert_eq!(
@@ -2408,4 +4264,162 @@ mod tests {
         );
         assert_eq!(enrich_history_action("market-close", None), "market-close");
     }
+
+    #[test]
+    fn test_close_policy_change_applies_to_current() {
+        assert_eq!(apply_close_policy_change(Some(800), None), Some(800));
+        assert_eq!(
+            apply_close_policy_change(Some(800), Some(ClosePolicyChange::Set(850))),
+            Some(850)
+        );
+        assert_eq!(
+            apply_close_policy_change(Some(800), Some(ClosePolicyChange::Reset)),
+            None
+        );
+        // Serialized exactly as the lease contract expects
+        assert_eq!(
+            serde_json::to_value(ClosePolicyChange::Set(850)).unwrap(),
+            serde_json::json!({ "set": 850 })
+        );
+        assert_eq!(
+            serde_json::to_value(ClosePolicyChange::Reset).unwrap(),
+            serde_json::json!("reset")
+        );
+    }
+
+    #[test]
+    fn test_validate_close_policy_bounds() {
+        let policy = |stop_loss, take_profit| LeaseClosePolicy {
+            stop_loss,
+            take_profit,
+        };
+        assert!(validate_close_policy(&policy(Some(850), Some(500)), 700.0, 900).is_ok());
+        // Past liquidation
+        assert!(validate_close_policy(&policy(Some(900), None), 700.0, 900).is_err());
+        // Would fire immediately
+        assert!(validate_close_policy(&policy(Some(650), None), 700.0, 900).is_err());
+        assert!(validate_close_policy(&policy(None, Some(750)), 700.0, 900).is_err());
+        assert!(validate_close_policy(&policy(None, Some(0)), 700.0, 900).is_err());
+        // Both cleared is a valid policy
+        assert!(validate_close_policy(&policy(None, None), 700.0, 900).is_ok());
+    }
+
+    #[test]
+    fn test_close_policy_trigger_prices_bracket_current_price() {
+        // Long at 70% LTV: stop-loss at 85% fires on a price drop, take-profit
+        // at 50% on a rise
+        let stop = ltv_trigger_price(10.0, 0.7, 0.85, false);
+        let take = ltv_trigger_price(10.0, 0.7, 0.5, false);
+        assert!(stop < 10.0 && take > 10.0);
+        assert!((take - 14.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_partial_close_figures_long() {
+        // $1000 position at 60% LTV, asset at $10; sell $200
+        let figures = partial_close_figures(1000.0, 600.0, 200.0, 10.0, 0.9, false);
+        assert!((figures.debt_after_usd - 400.0).abs() < 1e-9);
+        assert_eq!(figures.surplus_usd, 0.0);
+        assert!((figures.ltv_before - 0.6).abs() < 1e-9);
+        assert!((figures.ltv_after - 0.5).abs() < 1e-9);
+        // Deleveraging moves the liquidation price further below the price
+        let before = figures.liquidation_price_before.unwrap();
+        let after = figures.liquidation_price_after.unwrap();
+        assert!((before - 10.0 * 0.6 / 0.9).abs() < 1e-9);
+        assert!(after < before);
+    }
+
+    #[test]
+    fn test_partial_close_figures_short_moves_liquidation_up() {
+        let figures = partial_close_figures(1000.0, 600.0, 200.0, 2.0, 0.9, true);
+        let before = figures.liquidation_price_before.unwrap();
+        let after = figures.liquidation_price_after.unwrap();
+        assert!(before > 2.0 && after > before);
+    }
+
+    #[test]
+    fn test_partial_close_figures_surplus_clears_debt() {
+        let figures = partial_close_figures(1000.0, 100.0, 300.0, 10.0, 0.9, false);
+        assert_eq!(figures.debt_after_usd, 0.0);
+        assert!((figures.surplus_usd - 200.0).abs() < 1e-9);
+        assert_eq!(figures.ltv_after, 0.0);
+        assert_eq!(figures.liquidation_price_after, None);
+    }
+
+    #[test]
+    fn test_validate_partial_close_limits() {
+        assert!(validate_partial_close(50.0, 500.0, 10.0, 100.0).is_ok());
+        // Below the minimum transaction
+        assert!(validate_partial_close(5.0, 500.0, 10.0, 100.0).is_err());
+        // Leaves too little behind
+        assert!(validate_partial_close(450.0, 50.0, 10.0, 100.0).is_err());
+    }
+
+    const OWNER: &str = "nolus1ncc58ptqrkd7r7uk60dx4eufvvqf2edhtktv0q";
+
+    fn assert_amount_rejected<T: std::fmt::Debug>(
+        result: Result<T, AppError>,
+        expected_field: &str,
+    ) {
+        match result {
+            Err(AppError::Validation { field, .. }) => {
+                assert_eq!(field.as_deref(), Some(expected_field));
+            }
+            other => panic!("expected a {expected_field} validation error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_simulate_lease_rejects_non_positive_downpayment() {
+        let state = crate::test_utils::test_app_state().await;
+        for amount in ["abc", "0", "-1"] {
+            let request = SimulateLeaseRequest {
+                protocol: "OSMOSIS-OSMOSIS-USDC_NOBLE".to_string(),
+                downpayment_ticker: "USDC_NOBLE".to_string(),
+                downpayment_amount: amount.to_string(),
+                lease_asset: "ATOM".to_string(),
+                max_ltd: None,
+            };
+            assert_amount_rejected(
+                simulate_lease(State(state.clone()), Json(request)).await,
+                "downpayment_amount",
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_open_lease_rejects_non_positive_downpayment() {
+        let state = crate::test_utils::test_app_state().await;
+        for amount in ["abc", "0", "-1"] {
+            let request = OpenLeaseRequest {
+                sender: OWNER.to_string(),
+                protocol: "OSMOSIS-OSMOSIS-USDC_NOBLE".to_string(),
+                downpayment_ticker: "USDC_NOBLE".to_string(),
+                downpayment_amount: amount.to_string(),
+                max_ltd: None,
+            };
+            assert_amount_rejected(
+                open_lease(State(state.clone()), Json(request)).await,
+                "downpayment_amount",
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repay_lease_rejects_non_positive_amount() {
+        let state = crate::test_utils::test_app_state().await;
+        for amount in ["abc", "0", "-1"] {
+            let request = RepayLeaseRequest {
+                sender: OWNER.to_string(),
+                lease_address: OWNER.to_string(),
+                protocol: "OSMOSIS-OSMOSIS-USDC_NOBLE".to_string(),
+                ticker: "USDC_NOBLE".to_string(),
+                amount: amount.to_string(),
+            };
+            assert_amount_rejected(
+                repay_lease(State(state.clone()), Json(request)).await,
+                "amount",
+            );
+        }
+    }
 }
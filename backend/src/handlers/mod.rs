@@ -1,6 +1,9 @@
 pub mod admin;
+pub mod admin_keys;
+pub mod audit;
 pub mod common_types;
 pub mod config;
+pub mod cosmos_tx;
 pub mod currencies;
 pub mod earn;
 pub mod etl_proxy;
@@ -12,7 +15,10 @@ pub mod gated_protocols;
 pub mod governance;
 pub mod leases;
 pub mod locales;
+pub mod notifications;
 pub mod openapi;
+pub mod portfolio;
+pub mod proposal_messages;
 pub mod protocols;
 pub mod referral;
 pub mod solana;
@@ -23,5 +29,7 @@ pub mod swap;
 pub mod transactions;
 pub mod transfer;
 pub mod translations;
+pub mod tx_builder;
+pub mod validator_scoring;
 pub mod websocket;
 pub mod zero_interest;
@@ -0,0 +1,603 @@
+//! HTTP surface for the notification outbox.
+//!
+//! `GET /api/notifications/{address}` pages through an address's recorded
+//! lease, earn, transfer and governance events. Webhooks are registered per
+//! address with an ADR-036 proof that the caller controls it; the signing
+//! secret is returned once at registration and must be presented
+//! (`X-Webhook-Secret`) to remove the webhook. Deliveries that exhausted their
+//! retries are listed under `dead-letters`. Listings are public, so they show
+//! only each webhook's host.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::notifications::ownership::{
+    verify_ownership, webhook_registration_text, OwnershipProof,
+};
+use crate::notifications::{
+    generate_secret, redact_url, validate_webhook_url, DeadLetter, NotificationEvent,
+    NotificationKind, WebhookRegistration,
+};
+use crate::validation::validate_nolus_address;
+use crate::AppState;
+
+/// Default page size for history queries.
+const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+/// Maximum page size for history queries.
+const MAX_HISTORY_LIMIT: usize = 200;
+
+/// Header carrying the webhook secret on removal.
+const WEBHOOK_SECRET_HEADER: &str = "x-webhook-secret";
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct NotificationHistoryQuery {
+    /// Only return events of this kind
+    pub kind: Option<NotificationKind>,
+    /// Return events older than this sequence number (the previous page's `next_before`)
+    pub before: Option<u64>,
+    /// Page size (default 50, max 200)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationHistoryResponse {
+    pub address: String,
+    /// Newest first
+    pub events: Vec<NotificationEvent>,
+    /// Cursor for the next (older) page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_before: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterWebhookRequest {
+    /// HTTPS endpoint receiving signed event POSTs
+    pub url: String,
+    /// Kinds to deliver; omitted or empty means every kind
+    #[serde(default)]
+    pub kinds: Vec<NotificationKind>,
+    /// ADR-036 signature by the address over
+    /// `Register Nolus webhook <url> for <address> at <timestamp>`
+    pub proof: OwnershipProof,
+}
+
+/// A registered webhook (the secret is never returned after registration)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookInfo {
+    pub id: String,
+    /// Host of the webhook URL; the full URL is only returned at registration
+    pub host: String,
+    pub kinds: Vec<NotificationKind>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookRegistration> for WebhookInfo {
+    fn from(hook: WebhookRegistration) -> Self {
+        Self {
+            id: hook.id,
+            host: redact_url(&hook.url),
+            kinds: hook.kinds,
+            created_at: hook.created_at,
+        }
+    }
+}
+
+/// A delivery that exhausted its retries
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeadLetterInfo {
+    pub delivery_id: String,
+    pub webhook_id: String,
+    /// Host of the webhook URL
+    pub host: String,
+    pub event: NotificationEvent,
+    pub attempts: u32,
+    pub last_error: String,
+    pub dead_at: DateTime<Utc>,
+}
+
+impl From<DeadLetter> for DeadLetterInfo {
+    fn from(letter: DeadLetter) -> Self {
+        Self {
+            delivery_id: letter.delivery_id,
+            webhook_id: letter.webhook_id,
+            host: redact_url(&letter.url),
+            event: letter.event,
+            attempts: letter.attempts,
+            last_error: letter.last_error,
+            dead_at: letter.dead_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookCreatedResponse {
+    pub id: String,
+    pub url: String,
+    pub kinds: Vec<NotificationKind>,
+    pub created_at: DateTime<Utc>,
+    /// HMAC-SHA256 signing secret. Deliveries carry
+    /// `X-Nolus-Signature: t=<unix>,v1=<hex hmac of "<t>.<body>">`. Shown only once.
+    pub secret: String,
+}
+
+/// Page through an address's notification history
+#[utoipa::path(
+    get,
+    path = "/api/notifications/{address}",
+    tag = "notifications",
+    params(
+        ("address" = String, Path, description = "Nolus wallet address"),
+        NotificationHistoryQuery,
+    ),
+    responses(
+        (status = 200, description = "Recorded events, newest first", body = NotificationHistoryResponse),
+        (status = 400, description = "Invalid address", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn get_notifications(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(query): Query<NotificationHistoryQuery>,
+) -> Result<Json<NotificationHistoryResponse>, AppError> {
+    validate_nolus_address(&address, "address")?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .clamp(1, MAX_HISTORY_LIMIT);
+
+    let page = state
+        .notification_store
+        .history(&address, query.kind, query.before, limit);
+
+    Ok(Json(NotificationHistoryResponse {
+        address,
+        events: page.events,
+        next_before: page.next_before,
+    }))
+}
+
+/// List an address's registered webhooks
+#[utoipa::path(
+    get,
+    path = "/api/notifications/{address}/webhooks",
+    tag = "notifications",
+    params(("address" = String, Path, description = "Nolus wallet address")),
+    responses(
+        (status = 200, description = "Registered webhooks", body = Vec<WebhookInfo>),
+        (status = 400, description = "Invalid address", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn list_webhooks(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Result<Json<Vec<WebhookInfo>>, AppError> {
+    validate_nolus_address(&address, "address")?;
+    let hooks = state
+        .notification_store
+        .webhooks(&address)
+        .into_iter()
+        .map(WebhookInfo::from)
+        .collect();
+    Ok(Json(hooks))
+}
+
+/// Register a webhook for an address's events
+#[utoipa::path(
+    post,
+    path = "/api/notifications/{address}/webhooks",
+    tag = "notifications",
+    params(("address" = String, Path, description = "Nolus wallet address")),
+    request_body = RegisterWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook registered", body = WebhookCreatedResponse),
+        (status = 400, description = "Invalid address, URL or proof, or webhook limit reached", body = crate::error::ErrorResponse),
+        (status = 403, description = "Proof not signed by the address", body = crate::error::ErrorResponse),
+        (status = 503, description = "Webhook registrations at capacity", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn register_webhook(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Json(request): Json<RegisterWebhookRequest>,
+) -> Result<(StatusCode, Json<WebhookCreatedResponse>), AppError> {
+    validate_nolus_address(&address, "address")?;
+    let url = validate_webhook_url(&request.url)?;
+    verify_ownership(
+        &address,
+        &webhook_registration_text(&address, &request.url, request.proof.timestamp),
+        &request.proof,
+        Utc::now().timestamp(),
+    )?;
+
+    let mut kinds = request.kinds;
+    kinds.sort();
+    kinds.dedup();
+
+    let registration = WebhookRegistration {
+        id: Uuid::new_v4().to_string(),
+        address,
+        url: url.to_string(),
+        secret: generate_secret(),
+        kinds,
+        created_at: Utc::now(),
+    };
+    state
+        .notification_store
+        .register_webhook(registration.clone())
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(WebhookCreatedResponse {
+            id: registration.id,
+            url: registration.url,
+            kinds: registration.kinds,
+            created_at: registration.created_at,
+            secret: registration.secret,
+        }),
+    ))
+}
+
+/// Remove a webhook (requires its secret in `X-Webhook-Secret`)
+#[utoipa::path(
+    delete,
+    path = "/api/notifications/{address}/webhooks/{webhook_id}",
+    tag = "notifications",
+    params(
+        ("address" = String, Path, description = "Nolus wallet address"),
+        ("webhook_id" = String, Path, description = "Webhook id"),
+        ("X-Webhook-Secret" = String, Header, description = "Secret returned at registration"),
+    ),
+    responses(
+        (status = 204, description = "Webhook removed"),
+        (status = 403, description = "Missing or wrong secret", body = crate::error::ErrorResponse),
+        (status = 404, description = "Unknown webhook", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn delete_webhook(
+    State(state): State<Arc<AppState>>,
+    Path((address, webhook_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<StatusCode, AppError> {
+    validate_nolus_address(&address, "address")?;
+    let secret = headers
+        .get(WEBHOOK_SECRET_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or(AppError::Forbidden)?;
+
+    state
+        .notification_store
+        .remove_webhook(&address, &webhook_id, secret)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List deliveries that exhausted their retries
+#[utoipa::path(
+    get,
+    path = "/api/notifications/{address}/dead-letters",
+    tag = "notifications",
+    params(("address" = String, Path, description = "Nolus wallet address")),
+    responses(
+        (status = 200, description = "Dead-lettered deliveries, newest first", body = Vec<DeadLetterInfo>),
+        (status = 400, description = "Invalid address", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn get_dead_letters(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Result<Json<Vec<DeadLetterInfo>>, AppError> {
+    validate_nolus_address(&address, "address")?;
+    let letters = state
+        .notification_store
+        .dead_letters(&address)
+        .into_iter()
+        .map(DeadLetterInfo::from)
+        .collect();
+    Ok(Json(letters))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::ownership::sign_for_test;
+    use crate::notifications::{deliver, NotificationDraft, SIGNATURE_HEADER};
+    use crate::test_utils::{collect_body_str, test_app_state};
+    use axum::{
+        body::Body,
+        http::Request,
+        routing::{delete, get},
+        Router,
+    };
+    use tower::ServiceExt;
+    use wiremock::matchers::{header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const ADDRESS: &str = "nolus17xpfvakm2amg962yls6f84z3kell8c5lxfnlfc";
+
+    fn build_app(state: Arc<AppState>) -> Router {
+        Router::new()
+            .route("/api/notifications/{address}", get(get_notifications))
+            .route(
+                "/api/notifications/{address}/webhooks",
+                get(list_webhooks).post(register_webhook),
+            )
+            .route(
+                "/api/notifications/{address}/webhooks/{webhook_id}",
+                delete(delete_webhook),
+            )
+            .route(
+                "/api/notifications/{address}/dead-letters",
+                get(get_dead_letters),
+            )
+            .with_state(state)
+    }
+
+    /// A registration body for `url`, signed by the test key `seed` on behalf
+    /// of `address`. Returns the body and the key's own address.
+    fn signed_registration(
+        seed: u8,
+        address: Option<&str>,
+        url: &str,
+    ) -> (serde_json::Value, String) {
+        let now = Utc::now().timestamp();
+        let (own_address, _) = sign_for_test(seed, "", now);
+        let address = address.unwrap_or(&own_address).to_string();
+        let text = webhook_registration_text(&address, url, now);
+        let (_, proof) = sign_for_test(seed, &text, now);
+        let body = serde_json::json!({
+            "url": url,
+            "kinds": ["lease"],
+            "proof": {
+                "pub_key": proof.pub_key,
+                "signature": proof.signature,
+                "timestamp": proof.timestamp,
+            },
+        });
+        (body, own_address)
+    }
+
+    fn post_json(uri: &str, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn register_list_and_delete_webhook() {
+        let state = test_app_state().await;
+        let app = build_app(state.clone());
+        let (body, address) = signed_registration(7, None, "https://hooks.example.com/nolus");
+
+        let resp = app
+            .clone()
+            .oneshot(post_json(
+                &format!("/api/notifications/{address}/webhooks"),
+                body,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let created: serde_json::Value =
+            serde_json::from_str(&collect_body_str(resp).await).unwrap();
+        let id = created["id"].as_str().unwrap().to_string();
+        let secret = created["secret"].as_str().unwrap().to_string();
+        assert_eq!(created["kinds"], serde_json::json!(["lease"]));
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/notifications/{address}/webhooks"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = collect_body_str(resp).await;
+        assert!(body.contains(&id));
+        assert!(!body.contains(&secret), "the secret is never listed");
+        assert!(body.contains("hooks.example.com"));
+        assert!(!body.contains("/nolus"), "listings show only the host");
+
+        let delete_req = |secret: Option<&str>| {
+            let mut builder = Request::builder()
+                .method("DELETE")
+                .uri(format!("/api/notifications/{address}/webhooks/{id}"));
+            if let Some(secret) = secret {
+                builder = builder.header(WEBHOOK_SECRET_HEADER, secret);
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+        let resp = app.clone().oneshot(delete_req(None)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        let resp = app
+            .clone()
+            .oneshot(delete_req(Some(&secret)))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(!state.notification_store.is_registered(&address));
+    }
+
+    #[tokio::test]
+    async fn register_requires_proof_from_the_address_key() {
+        let state = test_app_state().await;
+        let app = build_app(state.clone());
+        let (_, victim) = signed_registration(7, None, "https://hooks.example.com/nolus");
+        let (body, _) = signed_registration(8, Some(&victim), "https://hooks.example.com/nolus");
+
+        let resp = app
+            .oneshot(post_json(
+                &format!("/api/notifications/{victim}/webhooks"),
+                body,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        assert!(!state.notification_store.is_registered(&victim));
+    }
+
+    #[tokio::test]
+    async fn register_rejects_non_https_url_and_bad_address() {
+        let app = build_app(test_app_state().await);
+        let (body, address) = signed_registration(7, None, "http://hooks.example.com/nolus");
+
+        let resp = app
+            .clone()
+            .oneshot(post_json(
+                &format!("/api/notifications/{address}/webhooks"),
+                body,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let (body, _) =
+            signed_registration(7, Some("cosmos1xyz"), "https://hooks.example.com/nolus");
+        let resp = app
+            .oneshot(post_json("/api/notifications/cosmos1xyz/webhooks", body))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn history_endpoint_pages_with_cursor() {
+        let state = test_app_state().await;
+        state.notification_store.record(
+            (0..3)
+                .map(|i| {
+                    NotificationDraft::new(
+                        ADDRESS,
+                        NotificationKind::Lease,
+                        &format!("e{i}"),
+                        serde_json::Value::Null,
+                    )
+                })
+                .collect(),
+        );
+        let app = build_app(state);
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/api/notifications/{ADDRESS}?limit=2"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let page: serde_json::Value = serde_json::from_str(&collect_body_str(resp).await).unwrap();
+        assert_eq!(page["events"].as_array().unwrap().len(), 2);
+        assert_eq!(page["events"][0]["event"], "e2");
+        let before = page["next_before"].as_u64().unwrap();
+
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/api/notifications/{ADDRESS}?limit=2&before={before}&kind=lease"
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let page: serde_json::Value = serde_json::from_str(&collect_body_str(resp).await).unwrap();
+        assert_eq!(page["events"][0]["event"], "e0");
+        assert!(page.get("next_before").is_none());
+    }
+
+    #[tokio::test]
+    async fn delivery_posts_signed_payload() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header_exists(SIGNATURE_HEADER))
+            .and(header_exists("x-nolus-event-id"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let state = test_app_state().await;
+        // Registered directly: the HTTP surface only accepts public https URLs.
+        state
+            .notification_store
+            .register_webhook(WebhookRegistration {
+                id: "w1".to_string(),
+                address: ADDRESS.to_string(),
+                url: format!("{}/hook", server.uri()),
+                secret: "secret".to_string(),
+                kinds: vec![],
+                created_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        state.notification_store.record(vec![NotificationDraft::new(
+            ADDRESS,
+            NotificationKind::Earn,
+            "deposited",
+            serde_json::json!({"amount": "1"}),
+        )]);
+
+        let due = state.notification_store.due_deliveries(Utc::now(), 10);
+        assert_eq!(due.len(), 1);
+        deliver(&reqwest::Client::new(), &due[0])
+            .await
+            .expect("2xx is a successful delivery");
+    }
+
+    #[tokio::test]
+    async fn delivery_reports_non_2xx_as_failure() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let state = test_app_state().await;
+        state
+            .notification_store
+            .register_webhook(WebhookRegistration {
+                id: "w1".to_string(),
+                address: ADDRESS.to_string(),
+                url: format!("{}/hook", server.uri()),
+                secret: "secret".to_string(),
+                kinds: vec![],
+                created_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        state.notification_store.record(vec![NotificationDraft::new(
+            ADDRESS,
+            NotificationKind::Transfer,
+            "bank_transfer",
+            serde_json::Value::Null,
+        )]);
+
+        crate::notifications::run_delivery_pass(&state, &reqwest::Client::new()).await;
+
+        // Rescheduled with backoff, not dead-lettered after one failure
+        assert!(state
+            .notification_store
+            .due_deliveries(Utc::now(), 10)
+            .is_empty());
+        assert!(state.notification_store.dead_letters(ADDRESS).is_empty());
+    }
+}
@@ -18,9 +18,10 @@ use utoipa::OpenApi;
 use crate::error::{ErrorBody, ErrorResponse};
 use crate::external;
 use crate::handlers::{
-    admin, common_types, config, currencies, earn, etl_proxy, fees, gated_assets, gated_networks,
-    gated_protocols, governance, leases, locales, protocols, referral, solana, solana_tx, staking,
-    swap, transactions, transfer, zero_interest,
+    admin, common_types, config, cosmos_tx, currencies, earn, etl_proxy, fees, gated_assets,
+    gated_networks, gated_protocols, governance, leases, locales, notifications, portfolio,
+    proposal_messages, protocols, referral, solana, solana_tx, staking, swap, transactions,
+    transfer, tx_builder, validator_scoring, zero_interest,
 };
 use crate::transfer_tracker;
 
@@ -51,6 +52,7 @@ use crate::transfer_tracker;
         currencies::get_currencies,
         currencies::get_currency,
         currencies::get_prices,
+        currencies::get_price_candles,
         currencies::get_balances,
         // Solana (balances + transfer-timeout params)
         solana::get_solana_balances,
@@ -59,6 +61,8 @@ use crate::transfer_tracker;
         solana_tx::build_create_ata,
         solana_tx::build_send_source,
         solana_tx::build_send_sink,
+        cosmos_tx::simulate_tx,
+        cosmos_tx::broadcast_tx,
         // Protocols
         protocols::get_protocols,
         protocols::get_active_protocols,
@@ -72,32 +76,52 @@ use crate::transfer_tracker;
         leases::get_lease_history,
         leases::get_lease_config,
         leases::get_lease_quote,
+        leases::simulate_lease,
         leases::open_lease,
         leases::repay_lease,
         leases::close_lease,
         leases::market_close_lease,
+        leases::change_close_policy,
+        leases::partial_close_lease,
+        leases::repay_with_collateral,
         // Earn
         earn::get_pools,
         earn::get_pool,
+        earn::get_pool_analytics,
         earn::get_positions,
+        earn::get_positions_history,
         earn::get_earn_stats,
         earn::deposit,
         earn::withdraw,
+        earn::preview_withdraw,
         // Staking
         staking::get_validators,
         staking::get_validator,
         staking::get_positions,
         staking::get_staking_params,
+        staking::get_schedule,
         staking::delegate,
         staking::undelegate,
         staking::redelegate,
         staking::claim_rewards,
+        staking::build_auto_compound,
+        staking::get_auto_compound_grants,
+        staking::revoke_auto_compound,
+        validator_scoring::get_validator_scores,
+        validator_scoring::recommend_delegations,
+        // Portfolio
+        portfolio::get_portfolio,
         // Governance
         governance::get_hidden_proposals,
         governance::get_proposals,
+        governance::get_proposal_detail,
         governance::get_proposal_tally,
         governance::get_proposal_vote,
         governance::get_tallying_params,
+        governance::vote,
+        governance::vote_weighted,
+        governance::deposit,
+        governance::submit_proposal,
         governance::get_staking_pool,
         governance::get_apr,
         governance::get_account,
@@ -141,6 +165,12 @@ use crate::transfer_tracker;
         // Transfer tracker (Nolus<->Solana route status)
         transfer::track_transfer,
         transfer::get_transfer_status,
+        // Notification outbox (history + webhooks)
+        notifications::get_notifications,
+        notifications::list_webhooks,
+        notifications::register_webhook,
+        notifications::delete_webhook,
+        notifications::get_dead_letters,
         // ETL proxy (opaque passthrough)
         etl_proxy::proxy_subscribe,
         etl_proxy::batch_stats_overview,
@@ -169,6 +199,9 @@ use crate::transfer_tracker;
         currencies::CurrenciesResponse,
         currencies::PricesResponse,
         currencies::PriceInfo,
+        currencies::CandlesResponse,
+        crate::price_history::Candle,
+        crate::price_history::Resolution,
         currencies::BalancesResponse,
         currencies::BalanceInfo,
         // Solana
@@ -188,6 +221,14 @@ use crate::transfer_tracker;
         protocols::ProtocolsResponse,
         // Fees
         fees::GasFeeConfigResponse,
+        tx_builder::GasEstimate,
+        tx_builder::FeeCoin,
+        cosmos_tx::SimulateTxRequest,
+        cosmos_tx::SimulateTxResponse,
+        cosmos_tx::TxError,
+        cosmos_tx::TxErrorKind,
+        cosmos_tx::BroadcastTxRequest,
+        cosmos_tx::BroadcastTxResponse,
         // Leases
         leases::LeasesResponse,
         leases::LeaseInfo,
@@ -196,13 +237,27 @@ use crate::transfer_tracker;
         leases::LeaseDebtInfo,
         leases::LeaseInterestInfo,
         leases::LeasePnlInfo,
+        leases::LeasePnlBreakdown,
         leases::LeaseClosePolicy,
+        leases::ClosePolicyChange,
+        leases::ClosePolicyRequest,
+        leases::ClosePolicyResponse,
+        leases::PartialCloseRequest,
+        leases::RepayWithCollateralRequest,
+        leases::PartialCloseResponse,
+        leases::PartialClosePreview,
         leases::LeaseInProgress,
         leases::LeaseOpeningStateInfo,
         leases::LeaseEtlData,
         leases::LeaseHistoryEntry,
         leases::LeaseQuoteRequest,
         leases::LeaseQuoteResponse,
+        leases::SimulateLeaseRequest,
+        leases::SimulateLeaseResponse,
+        leases::SimulatedAmount,
+        leases::SimulatedSwap,
+        leases::SimulatedWarningLevel,
+        leases::DownpaymentRangeCheck,
         leases::OpenLeaseRequest,
         leases::LeaseTransactionResponse,
         leases::RepayLeaseRequest,
@@ -217,6 +272,17 @@ use crate::transfer_tracker;
         earn::WithdrawRequest,
         earn::EarnTransactionResponse,
         earn::EarnStats,
+        earn::BorrowRateModel,
+        earn::RateCurvePoint,
+        earn::UtilizationPoint,
+        earn::DepositProjection,
+        earn::EarnPoolAnalytics,
+        earn::EarnActivity,
+        earn::EarnPositionHistory,
+        earn::EarnPositionsHistoryResponse,
+        earn::WithdrawPreview,
+        crate::earn_ledger::LenderActivityKind,
+        crate::earn_ledger::ActivitySource,
         // Staking
         staking::Validator,
         staking::ValidatorStatus,
@@ -232,14 +298,47 @@ use crate::transfer_tracker;
         staking::ClaimRewardsRequest,
         staking::StakingTransactionResponse,
         staking::StakingParams,
+        staking::ScheduleEntryKind,
+        staking::ScheduleEntry,
+        staking::StakingScheduleResponse,
+        staking::AutoCompoundRequest,
+        staking::CompoundingProjection,
+        staking::AutoCompoundResponse,
+        staking::AuthzGrant,
+        staking::AutoCompoundGrantsResponse,
+        staking::RevokeGrantsRequest,
+        validator_scoring::CommissionChange,
+        validator_scoring::ValidatorScore,
+        validator_scoring::ValidatorScoresResponse,
+        validator_scoring::DelegationAllocation,
+        validator_scoring::DelegationRecommendation,
+        // Portfolio
+        portfolio::PortfolioResponse,
+        portfolio::PortfolioHolding,
+        portfolio::PortfolioSlice,
+        portfolio::PortfolioCategory,
+        portfolio::UnavailableSource,
         // Governance
         governance::HiddenProposalsResponse,
         governance::ProposalResponse,
         governance::ProposalsListResponse,
         governance::PaginationInfo,
+        governance::TallySample,
+        governance::ProjectedOutcome,
+        governance::TallyProgress,
+        governance::ProposalDetailResponse,
+        proposal_messages::ProposalMessageKind,
+        proposal_messages::ProposalMessageSummary,
         governance::AprResponse,
         governance::NodeInfoResponse,
         governance::NetworkStatusResponse,
+        governance::VoteOption,
+        governance::VoteRequest,
+        governance::WeightedVoteOption,
+        governance::WeightedVoteRequest,
+        governance::ProposalDepositRequest,
+        governance::SubmitProposalRequest,
+        governance::GovernanceTransactionResponse,
         external::chain::TallyResult,
         external::chain::TallyResponse,
         external::chain::VoteResponse,
@@ -253,6 +352,7 @@ use crate::transfer_tracker;
         external::chain::DenomMetadata,
         external::chain::DenomUnit,
         external::chain::AmountSpec,
+        external::chain::LiabilitySpec,
         // Referral
         referral::ValidateCodeResponse,
         referral::RegisterRequest,
@@ -317,6 +417,15 @@ use crate::transfer_tracker;
         transfer_tracker::Chain,
         transfer_tracker::Direction,
         transfer_tracker::IbcHeight,
+        // Notification outbox
+        notifications::NotificationHistoryResponse,
+        notifications::RegisterWebhookRequest,
+        notifications::WebhookInfo,
+        notifications::WebhookCreatedResponse,
+        notifications::DeadLetterInfo,
+        crate::notifications::NotificationEvent,
+        crate::notifications::NotificationKind,
+        crate::notifications::ownership::OwnershipProof,
         // Skip ingress responses validated at the boundary
         external::skip::SkipRouteResponse,
         external::skip::SkipMessagesResponse,
@@ -338,6 +447,8 @@ use crate::transfer_tracker;
         admin::DetailedHealthResponse,
         admin::ServiceHealth,
         admin::ServiceStatus,
+        external::endpoints::EndpointHealth,
+        external::endpoints::EndpointStatus,
         admin::CacheHealth,
         admin::IntercomTokenRequest,
         admin::IntercomTokenResponse,
@@ -355,6 +466,7 @@ use crate::transfer_tracker;
         (name = "leases", description = "Leverage lease positions (read + write)"),
         (name = "earn", description = "Liquidity-pool deposits and yields"),
         (name = "staking", description = "NLS staking delegations"),
+        (name = "portfolio", description = "Net worth across balances, leases, earn and staking"),
         (name = "governance", description = "Proposals, tallies, voting parameters"),
         (name = "node", description = "Node info and network status"),
         (name = "referral", description = "Referral codes and rewards"),
@@ -364,8 +476,10 @@ use crate::transfer_tracker;
         (name = "networks", description = "Gated networks catalog"),
         (name = "swap", description = "Cross-chain swap (Skip passthrough, opaque bodies)"),
         (name = "transfer", description = "Nolus<->Solana transfer tracking and status"),
+        (name = "notifications", description = "Per-address event history and signed webhook delivery"),
         (name = "etl", description = "ETL proxy endpoints (opaque passthrough)"),
         (name = "transactions", description = "Enriched transactions (opaque passthrough)"),
+        (name = "tx", description = "Cosmos transaction simulation and broadcast relay"),
         (name = "intercom", description = "Intercom user-hash issuance"),
     ),
 )]
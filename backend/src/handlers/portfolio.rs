@@ -0,0 +1,698 @@
+//! Aggregated portfolio of a Nolus address.
+//!
+//! `GET /api/portfolio/{address}` fans out to the internals behind the
+//! balance, lease, earn and staking endpoints, plus the Solana wallets the
+//! client links via `?solana=`, and folds every holding into one net-worth
+//! breakdown by category, asset and chain. Lease debt is a negative holding,
+//! so the leases category reports equity. A source that fails is listed under
+//! `unavailable` instead of failing the whole portfolio. The `portfolio`
+//! WebSocket topic pushes the same payload.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::error::AppError;
+use crate::handlers::currencies::{
+    compute_balances, resolve_price_usd, BalancesError, BalancesResponse, CurrenciesResponse,
+    CurrencyInfo, PricesResponse,
+};
+use crate::handlers::earn::fetch_earn_positions_for_monitoring;
+use crate::handlers::leases::{fetch_leases_for_monitoring, LeaseMonitorInfo};
+use crate::handlers::solana::{fetch_solana_balances, SolanaBalancesResponse};
+use crate::handlers::staking::{fetch_staking_positions, StakingPositionsResponse};
+use crate::handlers::tx_builder;
+use crate::handlers::websocket::EarnPositionInfo;
+use crate::propagation::user_data_filter::UserDataFilterContext;
+use crate::AppState;
+
+/// Solana wallets that may be linked to one portfolio.
+pub const MAX_LINKED_SOLANA_ADDRESSES: usize = 4;
+
+/// Chain label of Nolus-held positions.
+const NOLUS_CHAIN: &str = "nolus";
+
+/// Chain label of linked Solana wallets.
+const SOLANA_CHAIN: &str = "solana";
+
+/// Symbol priced for native SOL, which has no currency key.
+const SOL_TICKER: &str = "SOL";
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PortfolioQuery {
+    /// Comma-separated Solana wallets to include (max 4)
+    pub solana: Option<String>,
+}
+
+/// Where a holding sits.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PortfolioCategory {
+    Wallet,
+    Leases,
+    Earn,
+    Staking,
+}
+
+impl PortfolioCategory {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Wallet => "wallet",
+            Self::Leases => "leases",
+            Self::Earn => "earn",
+            Self::Staking => "staking",
+        }
+    }
+}
+
+/// One priced position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PortfolioHolding {
+    pub category: PortfolioCategory,
+    /// `nolus` or `solana`
+    pub chain: String,
+    /// Holder: wallet, lease, LPP contract or validator address
+    pub source: String,
+    pub symbol: String,
+    /// Currency key (`TICKER@PROTOCOL`), when the asset maps to one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Amount in the asset's minimal denomination
+    pub amount: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimal_digits: Option<u8>,
+    /// USD value, negative for lease debt; absent (and left out of every
+    /// total) when the asset has no price
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_usd: Option<String>,
+}
+
+/// Net worth attributed to one category, asset or chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PortfolioSlice {
+    pub name: String,
+    pub value_usd: String,
+    /// Share of the total net worth (percent)
+    pub share: f64,
+}
+
+/// A source that could not be read; its holdings are missing from the totals.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct UnavailableSource {
+    pub category: PortfolioCategory,
+    pub chain: String,
+    pub address: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PortfolioResponse {
+    pub address: String,
+    /// Linked Solana wallets included in the totals
+    pub solana_addresses: Vec<String>,
+    /// Net worth in USD (lease debt subtracted)
+    pub total_usd: String,
+    /// Largest first
+    pub by_category: Vec<PortfolioSlice>,
+    /// Largest first; lease debt nets against the borrowed asset
+    pub by_asset: Vec<PortfolioSlice>,
+    /// Largest first
+    pub by_chain: Vec<PortfolioSlice>,
+    pub holdings: Vec<PortfolioHolding>,
+    pub unavailable: Vec<UnavailableSource>,
+}
+
+/// Get a portfolio
+///
+/// Aggregates wallet balances, leases (position minus debt), earn deposits
+/// and staking (delegations, unbonding and pending rewards) of a Nolus
+/// address, plus the balances of linked Solana wallets, into a net-worth
+/// breakdown by category, asset and chain.
+#[utoipa::path(
+    get,
+    path = "/api/portfolio/{address}",
+    tag = "portfolio",
+    params(
+        ("address" = String, Path, description = "Nolus wallet address"),
+        PortfolioQuery,
+    ),
+    responses(
+        (status = 200, description = "Portfolio breakdown", body = PortfolioResponse),
+        (status = 400, description = "Invalid address", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn get_portfolio(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(query): Query<PortfolioQuery>,
+) -> Result<Json<PortfolioResponse>, AppError> {
+    let solana_addresses: Vec<String> = query
+        .solana
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(String::from)
+        .collect();
+
+    compute_portfolio(&state, &address, &solana_addresses)
+        .await
+        .map(Json)
+}
+
+/// Validate the Solana wallets linked to a portfolio.
+pub fn validate_linked_solana(addresses: &[String]) -> Result<(), AppError> {
+    if addresses.len() > MAX_LINKED_SOLANA_ADDRESSES {
+        return Err(AppError::Validation {
+            message: format!(
+                "At most {} Solana addresses can be linked",
+                MAX_LINKED_SOLANA_ADDRESSES
+            ),
+            field: Some("solana".to_string()),
+            details: None,
+        });
+    }
+    addresses
+        .iter()
+        .try_for_each(|a| crate::validation::validate_solana_address(a, "solana"))
+}
+
+/// Build the portfolio of `address` and its linked Solana wallets.
+///
+/// Shared by the REST handler and the `portfolio` WebSocket monitor.
+pub async fn compute_portfolio(
+    state: &AppState,
+    address: &str,
+    solana_addresses: &[String],
+) -> Result<PortfolioResponse, AppError> {
+    crate::validation::validate_nolus_address(address, "address")?;
+    validate_linked_solana(solana_addresses)?;
+
+    let filter_ctx = state
+        .data_cache
+        .filter_context
+        .load_or_unavailable("Filter context")?;
+    let currencies = state
+        .data_cache
+        .currencies
+        .load_or_unavailable("Currencies")?;
+    let prices = state.data_cache.prices.load_or_unavailable("Prices")?;
+
+    debug!("Computing portfolio for {}", address);
+    let (wallet, leases, earn, staking, solana) = tokio::join!(
+        compute_balances(state, address),
+        fetch_leases_for_monitoring(state, address),
+        fetch_earn_positions_for_monitoring(state, address),
+        fetch_staking_positions(state, address),
+        futures::future::join_all(
+            solana_addresses
+                .iter()
+                .map(|a| fetch_solana_balances(state, a.clone()))
+        ),
+    );
+
+    let pricing = Pricing {
+        currencies: &currencies,
+        prices: &prices,
+    };
+    let mut holdings = Vec::new();
+    let mut unavailable = Vec::new();
+
+    match wallet {
+        Ok(balances) => holdings.extend(wallet_holdings(address, &balances, &pricing)),
+        Err(
+            BalancesError::Unavailable(e) | BalancesError::Validation(e) | BalancesError::Chain(e),
+        ) => unavailable.push(UnavailableSource::new(
+            PortfolioCategory::Wallet,
+            NOLUS_CHAIN,
+            address,
+            &e,
+        )),
+    }
+    match leases {
+        Ok(leases) => holdings.extend(lease_holdings(&leases, &filter_ctx, &pricing)),
+        Err(e) => unavailable.push(UnavailableSource::new(
+            PortfolioCategory::Leases,
+            NOLUS_CHAIN,
+            address,
+            &e,
+        )),
+    }
+    match earn {
+        Ok((positions, _)) => holdings.extend(earn_holdings(&positions, &filter_ctx, &pricing)),
+        Err(e) => unavailable.push(UnavailableSource::new(
+            PortfolioCategory::Earn,
+            NOLUS_CHAIN,
+            address,
+            &e,
+        )),
+    }
+    let native_denom = tx_builder::resolve_native_denom(&currencies).ok();
+    match staking {
+        Ok(staking) => holdings.extend(staking_holdings(
+            &staking,
+            native_denom.as_deref(),
+            &pricing,
+        )),
+        Err(e) => unavailable.push(UnavailableSource::new(
+            PortfolioCategory::Staking,
+            NOLUS_CHAIN,
+            address,
+            &e,
+        )),
+    }
+    for (wallet, result) in solana_addresses.iter().zip(solana) {
+        match result {
+            Ok(balances) => holdings.extend(solana_holdings(&balances, &pricing)),
+            Err(e) => unavailable.push(UnavailableSource::new(
+                PortfolioCategory::Wallet,
+                SOLANA_CHAIN,
+                wallet,
+                &e,
+            )),
+        }
+    }
+
+    Ok(summarize(
+        address,
+        solana_addresses.to_vec(),
+        holdings,
+        unavailable,
+    ))
+}
+
+impl UnavailableSource {
+    fn new(category: PortfolioCategory, chain: &str, address: &str, error: &AppError) -> Self {
+        Self {
+            category,
+            chain: chain.to_string(),
+            address: address.to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Cached prices and currency metadata used to value holdings.
+struct Pricing<'a> {
+    currencies: &'a CurrenciesResponse,
+    prices: &'a PricesResponse,
+}
+
+impl Pricing<'_> {
+    /// The currency held on Nolus under `denom`.
+    fn by_denom(&self, denom: &str) -> Option<&CurrencyInfo> {
+        self.currencies
+            .currencies
+            .values()
+            .find(|c| c.bank_symbol == denom)
+    }
+
+    /// The currency `ticker` of `protocol`.
+    fn by_ticker(&self, ticker: &str, protocol: &str) -> Option<&CurrencyInfo> {
+        self.currencies
+            .currencies
+            .get(&format!("{}@{}", ticker, protocol))
+    }
+
+    /// USD value of `amount` minimal units; `None` when unpriced. Decimal
+    /// amounts (pending rewards) are truncated to whole units.
+    fn usd(&self, key: &str, ticker: &str, decimals: u8, amount: &str) -> Option<f64> {
+        let price = resolve_price_usd(&self.prices.prices, key, ticker);
+        let raw: f64 = amount.split('.').next()?.parse().ok()?;
+        (price > 0.0).then(|| raw / 10_f64.powi(i32::from(decimals)) * price)
+    }
+
+    /// A Nolus holding of a known currency.
+    fn holding(
+        &self,
+        category: PortfolioCategory,
+        source: &str,
+        currency: &CurrencyInfo,
+        amount: &str,
+        sign: f64,
+    ) -> PortfolioHolding {
+        let value = self
+            .usd(
+                &currency.key,
+                &currency.ticker,
+                currency.decimal_digits,
+                amount,
+            )
+            .map(|v| v * sign);
+        PortfolioHolding {
+            category,
+            chain: NOLUS_CHAIN.to_string(),
+            source: source.to_string(),
+            symbol: currency.symbol.clone(),
+            key: Some(currency.key.clone()),
+            amount: amount.to_string(),
+            decimal_digits: Some(currency.decimal_digits),
+            value_usd: value.map(format_usd),
+        }
+    }
+}
+
+fn format_usd(value: f64) -> String {
+    format!("{:.2}", value)
+}
+
+/// Whether a (possibly decimal) amount has no whole units.
+fn is_zero(amount: &str) -> bool {
+    !amount
+        .split('.')
+        .next()
+        .and_then(|whole| whole.parse::<u128>().ok())
+        .is_some_and(|units| units > 0)
+}
+
+fn wallet_holdings(
+    address: &str,
+    balances: &BalancesResponse,
+    pricing: &Pricing<'_>,
+) -> Vec<PortfolioHolding> {
+    balances
+        .balances
+        .iter()
+        .filter(|b| !is_zero(&b.amount))
+        .filter_map(|b| {
+            let currency = pricing.currencies.currencies.get(&b.key)?;
+            Some(pricing.holding(PortfolioCategory::Wallet, address, currency, &b.amount, 1.0))
+        })
+        .collect()
+}
+
+/// Each visible lease contributes its position and, negated, its debt.
+fn lease_holdings(
+    leases: &[LeaseMonitorInfo],
+    filter_ctx: &UserDataFilterContext,
+    pricing: &Pricing<'_>,
+) -> Vec<PortfolioHolding> {
+    let mut holdings = Vec::new();
+    for lease in leases {
+        if !filter_ctx.is_protocol_visible(&lease.protocol) {
+            continue;
+        }
+        if let Some(amount) = &lease.amount {
+            if !filter_ctx.is_lease_visible(&lease.protocol, &amount.ticker) {
+                continue;
+            }
+            if let Some(currency) = pricing.by_ticker(&amount.ticker, &lease.protocol) {
+                holdings.push(pricing.holding(
+                    PortfolioCategory::Leases,
+                    &lease.address,
+                    currency,
+                    &amount.amount,
+                    1.0,
+                ));
+            }
+        }
+        if let Some(debt) = lease.debt.as_ref().filter(|d| !is_zero(&d.total)) {
+            if let Some(currency) = pricing.by_ticker(&debt.ticker, &lease.protocol) {
+                holdings.push(pricing.holding(
+                    PortfolioCategory::Leases,
+                    &lease.address,
+                    currency,
+                    &debt.total,
+                    -1.0,
+                ));
+            }
+        }
+    }
+    holdings
+}
+
+/// Earn deposits valued in the pool's LPN.
+fn earn_holdings(
+    positions: &[EarnPositionInfo],
+    filter_ctx: &UserDataFilterContext,
+    pricing: &Pricing<'_>,
+) -> Vec<PortfolioHolding> {
+    positions
+        .iter()
+        .filter(|p| filter_ctx.is_earn_position_visible(&p.protocol))
+        .filter_map(|p| {
+            let lpn = pricing
+                .currencies
+                .lpn
+                .iter()
+                .find(|c| c.protocol == p.protocol)?;
+            Some(pricing.holding(
+                PortfolioCategory::Earn,
+                &p.lpp_address,
+                lpn,
+                &p.deposited_lpn,
+                1.0,
+            ))
+        })
+        .collect()
+}
+
+/// Delegations, unbonding entries and pending rewards, per validator.
+fn staking_holdings(
+    staking: &StakingPositionsResponse,
+    native_denom: Option<&str>,
+    pricing: &Pricing<'_>,
+) -> Vec<PortfolioHolding> {
+    let delegations = staking.delegations.iter().map(|d| {
+        (
+            &d.validator_address,
+            d.balance.denom.as_str(),
+            &d.balance.amount,
+        )
+    });
+    let unbonding = native_denom.into_iter().flat_map(|denom| {
+        staking.unbonding.iter().flat_map(move |u| {
+            u.entries
+                .iter()
+                .map(move |e| (&u.validator_address, denom, &e.balance))
+        })
+    });
+    let rewards = staking.rewards.iter().flat_map(|r| {
+        r.rewards
+            .iter()
+            .map(move |b| (&r.validator_address, b.denom.as_str(), &b.amount))
+    });
+
+    delegations
+        .chain(unbonding)
+        .chain(rewards)
+        .filter(|(_, _, amount)| !is_zero(amount))
+        .filter_map(|(validator, denom, amount)| {
+            let currency = pricing.by_denom(denom)?;
+            let whole = amount.split('.').next().unwrap_or("0");
+            Some(pricing.holding(PortfolioCategory::Staking, validator, currency, whole, 1.0))
+        })
+        .collect()
+}
+
+fn solana_holdings(
+    balances: &SolanaBalancesResponse,
+    pricing: &Pricing<'_>,
+) -> Vec<PortfolioHolding> {
+    std::iter::once(&balances.sol)
+        .chain(&balances.tokens)
+        .filter(|b| !is_zero(&b.amount))
+        .map(|b| {
+            let ticker = b
+                .key
+                .as_deref()
+                .and_then(|key| pricing.currencies.currencies.get(key))
+                .map_or(SOL_TICKER, |c| c.ticker.as_str());
+            let value = pricing.usd(
+                b.key.as_deref().unwrap_or_default(),
+                ticker,
+                b.decimal_digits,
+                &b.amount,
+            );
+            PortfolioHolding {
+                category: PortfolioCategory::Wallet,
+                chain: SOLANA_CHAIN.to_string(),
+                source: balances.address.clone(),
+                symbol: b.symbol.clone(),
+                key: b.key.clone(),
+                amount: b.amount.clone(),
+                decimal_digits: Some(b.decimal_digits),
+                value_usd: value.map(format_usd),
+            }
+        })
+        .collect()
+}
+
+/// Fold holdings into the totals and per-category/asset/chain slices.
+fn summarize(
+    address: &str,
+    solana_addresses: Vec<String>,
+    holdings: Vec<PortfolioHolding>,
+    unavailable: Vec<UnavailableSource>,
+) -> PortfolioResponse {
+    let mut total = 0.0_f64;
+    let mut by_category: HashMap<&str, f64> = HashMap::new();
+    let mut by_asset: HashMap<&str, f64> = HashMap::new();
+    let mut by_chain: HashMap<&str, f64> = HashMap::new();
+
+    for holding in &holdings {
+        let Some(value) = holding
+            .value_usd
+            .as_deref()
+            .and_then(|v| v.parse::<f64>().ok())
+        else {
+            continue;
+        };
+        total += value;
+        *by_category.entry(holding.category.as_str()).or_default() += value;
+        *by_asset.entry(holding.symbol.as_str()).or_default() += value;
+        *by_chain.entry(holding.chain.as_str()).or_default() += value;
+    }
+
+    let slices = |totals: HashMap<&str, f64>| {
+        let mut totals: Vec<(&str, f64)> = totals.into_iter().collect();
+        totals.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        totals
+            .into_iter()
+            .map(|(name, value)| PortfolioSlice {
+                name: name.to_string(),
+                value_usd: format_usd(value),
+                share: if total > 0.0 {
+                    value / total * 100.0
+                } else {
+                    0.0
+                },
+            })
+            .collect::<Vec<_>>()
+    };
+    let by_category = slices(by_category);
+    let by_asset = slices(by_asset);
+    let by_chain = slices(by_chain);
+
+    PortfolioResponse {
+        address: address.to_string(),
+        solana_addresses,
+        total_usd: format_usd(total),
+        by_category,
+        by_asset,
+        by_chain,
+        holdings,
+        unavailable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_app_state;
+
+    const OWNER: &str = "nolus1qg5ega6dykkxc307y25pecuufrjkxkaggkkxh7nad0vhyhtuhw3sqaa3c5";
+
+    fn holding(
+        category: PortfolioCategory,
+        chain: &str,
+        symbol: &str,
+        value_usd: Option<&str>,
+    ) -> PortfolioHolding {
+        PortfolioHolding {
+            category,
+            chain: chain.to_string(),
+            source: OWNER.to_string(),
+            symbol: symbol.to_string(),
+            key: None,
+            amount: "1".to_string(),
+            decimal_digits: None,
+            value_usd: value_usd.map(String::from),
+        }
+    }
+
+    #[test]
+    fn summarize_nets_lease_debt_and_skips_unpriced() {
+        let holdings = vec![
+            holding(
+                PortfolioCategory::Wallet,
+                NOLUS_CHAIN,
+                "USDC",
+                Some("100.00"),
+            ),
+            holding(
+                PortfolioCategory::Leases,
+                NOLUS_CHAIN,
+                "ATOM",
+                Some("300.00"),
+            ),
+            holding(
+                PortfolioCategory::Leases,
+                NOLUS_CHAIN,
+                "USDC",
+                Some("-200.00"),
+            ),
+            holding(
+                PortfolioCategory::Wallet,
+                SOLANA_CHAIN,
+                "SOL",
+                Some("100.00"),
+            ),
+            holding(PortfolioCategory::Wallet, SOLANA_CHAIN, "BONK", None),
+        ];
+        let portfolio = summarize(OWNER, Vec::new(), holdings, Vec::new());
+
+        assert_eq!(portfolio.total_usd, "300.00");
+        let slice = |slices: &[PortfolioSlice], name: &str| {
+            slices
+                .iter()
+                .find(|s| s.name == name)
+                .map(|s| s.value_usd.clone())
+        };
+        assert_eq!(
+            slice(&portfolio.by_category, "leases").as_deref(),
+            Some("100.00")
+        );
+        assert_eq!(
+            slice(&portfolio.by_category, "wallet").as_deref(),
+            Some("200.00")
+        );
+        assert_eq!(
+            slice(&portfolio.by_asset, "USDC").as_deref(),
+            Some("-100.00")
+        );
+        assert_eq!(slice(&portfolio.by_asset, "BONK"), None);
+        assert_eq!(portfolio.by_asset[0].name, "ATOM");
+        assert_eq!(portfolio.by_chain[0].name, NOLUS_CHAIN);
+        assert!((portfolio.by_chain[0].share - 200.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn is_zero_reads_whole_units() {
+        assert!(is_zero("0"));
+        assert!(is_zero("0.75"));
+        assert!(is_zero(""));
+        assert!(!is_zero("12"));
+        assert!(!is_zero("3.5"));
+    }
+
+    #[tokio::test]
+    async fn compute_portfolio_rejects_invalid_addresses() {
+        let state = test_app_state().await;
+
+        let err = compute_portfolio(&state, "cosmos1bad", &[])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation { .. }));
+
+        let err = compute_portfolio(&state, OWNER, &["not-base58!".to_string()])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation { field: Some(ref f), .. } if f == "solana"));
+
+        let linked = vec!["11111111111111111111111111111111".to_string(); 5];
+        assert!(validate_linked_solana(&linked).is_err());
+        assert!(validate_linked_solana(&linked[..4]).is_ok());
+    }
+}
@@ -0,0 +1,388 @@
+//! Governance proposal message summaries
+//!
+//! The gov v1 REST API returns proposal messages as JSON `Any`s with
+//! snake_case fields. A message the node could not resolve arrives as
+//! `{"@type", "value"}` with base64 protobuf bytes instead and is decoded
+//! through [`transactions::decode_message`], which renders camelCase fields —
+//! so every field lookup here accepts both spellings.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::data_cache::ProtocolContractsMap;
+use crate::handlers::transactions;
+
+/// Wrapper the gov v1 module uses for pre-v1 `Content` proposals.
+const EXEC_LEGACY_CONTENT: &str = "/cosmos.gov.v1.MsgExecLegacyContent";
+
+/// What a proposal message does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalMessageKind {
+    ParameterChange,
+    ParamsUpdate,
+    SoftwareUpgrade,
+    CancelUpgrade,
+    ContractMigration,
+    ContractExecution,
+    CommunityPoolSpend,
+    Other,
+}
+
+/// A decoded proposal message
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProposalMessageSummary {
+    pub type_url: String,
+    pub kind: ProposalMessageKind,
+    /// One-line human-readable description
+    pub summary: String,
+    /// Protocol owning the targeted contract (migrations and executions)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    /// Normalized message fields; the shape depends on `kind`
+    #[schema(value_type = Object)]
+    pub details: serde_json::Value,
+}
+
+/// Summarize every message of a proposal, in order. `contracts` resolves
+/// contract addresses to the protocol they belong to.
+pub fn summarize_messages(
+    messages: &[serde_json::Value],
+    contracts: Option<&ProtocolContractsMap>,
+) -> Vec<ProposalMessageSummary> {
+    messages
+        .iter()
+        .map(|message| summarize_message(message, contracts))
+        .collect()
+}
+
+fn summarize_message(
+    message: &serde_json::Value,
+    contracts: Option<&ProtocolContractsMap>,
+) -> ProposalMessageSummary {
+    let type_url = text(message, &["@type"]);
+    if type_url == EXEC_LEGACY_CONTENT {
+        if let Some(content) = message.get("content") {
+            return summarize_message(content, contracts);
+        }
+    }
+
+    let decoded = message
+        .get("value")
+        .and_then(|v| v.as_str())
+        .and_then(|value| transactions::decode_message(&type_url, value));
+    let body = decoded.as_ref().unwrap_or(message);
+
+    let (kind, summary, protocol, details) = match type_url.as_str() {
+        "/cosmos.params.v1beta1.ParameterChangeProposal" => {
+            let changes: Vec<serde_json::Value> = field(body, &["changes"])
+                .and_then(|v| v.as_array())
+                .map(|changes| {
+                    changes
+                        .iter()
+                        .map(|c| {
+                            serde_json::json!({
+                                "subspace": text(c, &["subspace"]),
+                                "key": text(c, &["key"]),
+                                "value": text(c, &["value"])
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let keys: Vec<String> = changes
+                .iter()
+                .map(|c| format!("{}.{}", text(c, &["subspace"]), text(c, &["key"])))
+                .collect();
+            (
+                ProposalMessageKind::ParameterChange,
+                format!("Change parameters: {}", keys.join(", ")),
+                None,
+                serde_json::json!({ "changes": changes }),
+            )
+        }
+        t if t.ends_with(".MsgUpdateParams") => {
+            let module = module_of(t);
+            (
+                ProposalMessageKind::ParamsUpdate,
+                format!("Update {} module parameters", module),
+                None,
+                serde_json::json!({
+                    "module": module,
+                    "params": field(body, &["params"]).cloned().unwrap_or_default()
+                }),
+            )
+        }
+        "/cosmos.upgrade.v1beta1.MsgSoftwareUpgrade"
+        | "/cosmos.upgrade.v1beta1.SoftwareUpgradeProposal" => {
+            let plan = field(body, &["plan"]).cloned().unwrap_or_default();
+            let name = text(&plan, &["name"]);
+            let height = text(&plan, &["height"]);
+            (
+                ProposalMessageKind::SoftwareUpgrade,
+                format!("Software upgrade {} at height {}", name, height),
+                None,
+                serde_json::json!({
+                    "name": name,
+                    "height": height,
+                    "info": text(&plan, &["info"])
+                }),
+            )
+        }
+        "/cosmos.upgrade.v1beta1.MsgCancelUpgrade"
+        | "/cosmos.upgrade.v1beta1.CancelSoftwareUpgradeProposal" => (
+            ProposalMessageKind::CancelUpgrade,
+            "Cancel the pending software upgrade".to_string(),
+            None,
+            serde_json::json!({}),
+        ),
+        "/cosmwasm.wasm.v1.MsgMigrateContract" | "/cosmwasm.wasm.v1.MigrateContractProposal" => {
+            let contract = text(body, &["contract"]);
+            let code_id = text(body, &["code_id", "codeId"]);
+            let owner = contract_owner(&contract, contracts);
+            let target = match &owner {
+                Some((protocol, role)) => format!("{} {} contract", protocol, role),
+                None => format!("contract {}", contract),
+            };
+            (
+                ProposalMessageKind::ContractMigration,
+                format!("Migrate {} to code {}", target, code_id),
+                owner.map(|(protocol, _)| protocol),
+                serde_json::json!({
+                    "contract": contract,
+                    "code_id": code_id,
+                    "msg": contract_msg(body)
+                }),
+            )
+        }
+        "/cosmwasm.wasm.v1.MsgExecuteContract" | "/cosmwasm.wasm.v1.ExecuteContractProposal" => {
+            let contract = text(body, &["contract"]);
+            let msg = contract_msg(body);
+            let action = msg
+                .as_object()
+                .and_then(|m| m.keys().next().cloned())
+                .unwrap_or_else(|| "message".to_string());
+            let owner = contract_owner(&contract, contracts);
+            let target = match &owner {
+                Some((protocol, role)) => format!("the {} {} contract", protocol, role),
+                None => format!("contract {}", contract),
+            };
+            (
+                ProposalMessageKind::ContractExecution,
+                format!("Execute `{}` on {}", action, target),
+                owner.map(|(protocol, _)| protocol),
+                serde_json::json!({ "contract": contract, "msg": msg }),
+            )
+        }
+        "/cosmos.distribution.v1beta1.MsgCommunityPoolSpend"
+        | "/cosmos.distribution.v1beta1.CommunityPoolSpendProposal" => {
+            let recipient = text(body, &["recipient"]);
+            let amount = field(body, &["amount"]).cloned().unwrap_or_default();
+            let coins: Vec<String> = amount
+                .as_array()
+                .map(|coins| {
+                    coins
+                        .iter()
+                        .map(|c| format!("{}{}", text(c, &["amount"]), text(c, &["denom"])))
+                        .collect()
+                })
+                .unwrap_or_default();
+            (
+                ProposalMessageKind::CommunityPoolSpend,
+                format!(
+                    "Spend {} from the community pool to {}",
+                    coins.join(", "),
+                    recipient
+                ),
+                None,
+                serde_json::json!({ "recipient": recipient, "amount": amount }),
+            )
+        }
+        _ => (
+            ProposalMessageKind::Other,
+            type_url.clone(),
+            None,
+            body.clone(),
+        ),
+    };
+
+    ProposalMessageSummary {
+        type_url,
+        kind,
+        summary,
+        protocol,
+        details,
+    }
+}
+
+/// First present field among the given spellings
+fn field<'a>(value: &'a serde_json::Value, keys: &[&str]) -> Option<&'a serde_json::Value> {
+    keys.iter().find_map(|key| value.get(*key))
+}
+
+/// Field rendered as text; numbers (protobuf int64s) are stringified
+fn text(value: &serde_json::Value, keys: &[&str]) -> String {
+    match field(value, keys) {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Module name from a type URL, e.g. `/cosmos.staking.v1beta1.X` -> `staking`
+fn module_of(type_url: &str) -> String {
+    type_url
+        .trim_start_matches('/')
+        .split('.')
+        .nth(1)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// The contract message as JSON. REST renders it as an object, as base64
+/// bytes, or (after protobuf decoding) as a JSON string.
+fn contract_msg(body: &serde_json::Value) -> serde_json::Value {
+    match body.get("msg") {
+        Some(serde_json::Value::String(raw)) => serde_json::from_str::<serde_json::Value>(raw)
+            .ok()
+            .filter(|msg| msg.is_object() || msg.is_array())
+            .or_else(|| {
+                BASE64
+                    .decode(raw)
+                    .ok()
+                    .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            })
+            .unwrap_or_else(|| serde_json::Value::String(raw.clone())),
+        Some(other) => other.clone(),
+        None => serde_json::Value::Null,
+    }
+}
+
+/// Protocol and contract role owning `contract`, if it is a protocol contract
+fn contract_owner(
+    contract: &str,
+    contracts: Option<&ProtocolContractsMap>,
+) -> Option<(String, &'static str)> {
+    contracts?.iter().find_map(|(protocol, info)| {
+        let role = if info.leaser == contract {
+            "leaser"
+        } else if info.lpp == contract {
+            "lpp"
+        } else if info.oracle == contract {
+            "oracle"
+        } else if info.profit == contract {
+            "profit"
+        } else if info.reserve.as_deref() == Some(contract) {
+            "reserve"
+        } else {
+            return None;
+        };
+        Some((protocol.clone(), role))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::chain::ProtocolContractsInfo;
+
+    fn contracts() -> ProtocolContractsMap {
+        let mut map = ProtocolContractsMap::new();
+        map.insert(
+            "OSMOSIS-OSMOSIS-USDC".to_string(),
+            ProtocolContractsInfo {
+                oracle: "nolus1oracle".to_string(),
+                lpp: "nolus1lpp".to_string(),
+                leaser: "nolus1leaser".to_string(),
+                profit: "nolus1profit".to_string(),
+                reserve: None,
+            },
+        );
+        map
+    }
+
+    #[test]
+    fn legacy_parameter_change_is_unwrapped() {
+        let message = serde_json::json!({
+            "@type": EXEC_LEGACY_CONTENT,
+            "authority": "nolus10d07y265gmmuvt4z0w9aw880jnsr700jf5qd9h",
+            "content": {
+                "@type": "/cosmos.params.v1beta1.ParameterChangeProposal",
+                "title": "t",
+                "changes": [{"subspace": "staking", "key": "MaxValidators", "value": "\"60\""}]
+            }
+        });
+        let summaries = summarize_messages(&[message], None);
+        assert_eq!(summaries[0].kind, ProposalMessageKind::ParameterChange);
+        assert_eq!(
+            summaries[0].summary,
+            "Change parameters: staking.MaxValidators"
+        );
+    }
+
+    #[test]
+    fn migration_of_protocol_contract_names_the_protocol() {
+        let message = serde_json::json!({
+            "@type": "/cosmwasm.wasm.v1.MsgMigrateContract",
+            "sender": "nolus1gov",
+            "contract": "nolus1leaser",
+            "code_id": "42",
+            "msg": BASE64.encode(br#"{"migrate":{}}"#)
+        });
+        let summaries = summarize_messages(&[message], Some(&contracts()));
+        let summary = &summaries[0];
+        assert_eq!(summary.kind, ProposalMessageKind::ContractMigration);
+        assert_eq!(summary.protocol.as_deref(), Some("OSMOSIS-OSMOSIS-USDC"));
+        assert_eq!(
+            summary.summary,
+            "Migrate OSMOSIS-OSMOSIS-USDC leaser contract to code 42"
+        );
+        assert_eq!(summary.details["msg"], serde_json::json!({"migrate": {}}));
+    }
+
+    #[test]
+    fn unresolved_any_is_decoded_from_protobuf() {
+        use cosmrs::proto::cosmos::base::v1beta1::Coin;
+        use cosmrs::proto::cosmos::distribution::v1beta1::MsgCommunityPoolSpend;
+        use prost::Message;
+
+        let msg = MsgCommunityPoolSpend {
+            authority: "nolus1gov".to_string(),
+            recipient: "nolus1recipient".to_string(),
+            amount: vec![Coin {
+                denom: "unls".to_string(),
+                amount: "1000".to_string(),
+            }],
+        };
+        let message = serde_json::json!({
+            "@type": "/cosmos.distribution.v1beta1.MsgCommunityPoolSpend",
+            "value": BASE64.encode(msg.encode_to_vec())
+        });
+        let summaries = summarize_messages(&[message], None);
+        assert_eq!(summaries[0].kind, ProposalMessageKind::CommunityPoolSpend);
+        assert_eq!(
+            summaries[0].summary,
+            "Spend 1000unls from the community pool to nolus1recipient"
+        );
+    }
+
+    #[test]
+    fn params_update_and_unknown_types() {
+        let summaries = summarize_messages(
+            &[
+                serde_json::json!({
+                    "@type": "/cosmos.staking.v1beta1.MsgUpdateParams",
+                    "params": {"max_validators": 60}
+                }),
+                serde_json::json!({"@type": "/nolus.custom.v1.MsgThing", "x": 1}),
+            ],
+            None,
+        );
+        assert_eq!(summaries[0].kind, ProposalMessageKind::ParamsUpdate);
+        assert_eq!(summaries[0].summary, "Update staking module parameters");
+        assert_eq!(summaries[1].kind, ProposalMessageKind::Other);
+        assert_eq!(summaries[1].details["x"], 1);
+    }
+}
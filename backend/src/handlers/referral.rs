@@ -618,7 +618,9 @@ mod tests {
     use super::*;
     use crate::test_utils::{collect_body_str, test_app_state, test_app_state_with_config};
     use crate::{
-        config::{AdminConfig, AppConfig, ExternalApiConfig, ProtocolsConfig, ServerConfig},
+        config::{
+            AdminConfig, AppConfig, ExternalApiConfig, ProtocolsConfig, RestakeConfig, ServerConfig,
+        },
         test_utils::test_config,
     };
     use axum::{
@@ -651,6 +653,8 @@ mod tests {
                 skip_api_key: None,
                 nolus_rpc_url: "http://127.0.0.1:1/".to_string(),
                 nolus_rest_url: "http://127.0.0.1:1/".to_string(),
+                nolus_rpc_fallback_urls: Vec::new(),
+                nolus_rest_fallback_urls: Vec::new(),
                 solana_rpc_url: Some("http://127.0.0.1:1/".to_string()),
                 referral_api_url: "http://127.0.0.1:1/".to_string(),
                 referral_api_token: "".to_string(),
@@ -662,8 +666,10 @@ mod tests {
             admin: AdminConfig {
                 enabled: false,
                 api_key: String::new(),
+                trusted_proxies: Vec::new(),
             },
             protocols: ProtocolsConfig::default(),
+            restake: RestakeConfig::default(),
         }
     }
 
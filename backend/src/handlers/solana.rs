@@ -120,6 +120,16 @@ pub async fn get_solana_balances(
     // Validate first: a malformed address is the only typed-error path.
     crate::validation::validate_solana_address(&address, "address")?;
 
+    fetch_solana_balances(&state, address).await.map(Json)
+}
+
+/// SOL and SOLANA-protocol SPL balances of an already validated wallet.
+///
+/// Shared by the balances handler and the portfolio aggregate.
+pub async fn fetch_solana_balances(
+    state: &AppState,
+    address: String,
+) -> Result<SolanaBalancesResponse, AppError> {
     // Native SOL. 503 when Solana RPC is unconfigured; 502 on an RPC failure.
     let lamports = state.solana_client.get_balance(&address).await?;
     let sol = SolanaBalanceInfo {
@@ -160,11 +170,11 @@ pub async fn get_solana_balances(
         .flatten()
         .collect();
 
-    Ok(Json(SolanaBalancesResponse {
+    Ok(SolanaBalancesResponse {
         address,
         sol,
         tokens,
-    }))
+    })
 }
 
 /// Get Solana transfer-timeout parameters
@@ -5,10 +5,14 @@
 //! - GET /api/staking/validators/:address - Get specific validator
 //! - GET /api/staking/positions?delegator=... - Get staking positions
 //! - GET /api/staking/params - Get staking parameters
+//! - GET /api/staking/schedule?address=... - Unbonding and redelegation timeline
 //! - POST /api/staking/delegate - Build delegate transaction
 //! - POST /api/staking/undelegate - Build undelegate transaction
 //! - POST /api/staking/redelegate - Build redelegate transaction
 //! - POST /api/staking/claim-rewards - Build claim rewards transaction
+//! - POST /api/staking/auto-compound - Build authz grants for the restake bot
+//! - GET /api/staking/auto-compound/grants?address=... - List authz grants
+//! - POST /api/staking/auto-compound/revoke - Build authz grant revocation
 
 use axum::{
     extract::{Path, Query, State},
@@ -16,10 +20,11 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tracing::{debug, warn};
+use tracing::debug;
 use utoipa::ToSchema;
 
 use crate::error::AppError;
+use crate::handlers::tx_builder::{self, GasEstimate, MsgKind};
 use crate::query_types::AddressQuery;
 use crate::AppState;
 
@@ -100,28 +105,42 @@ pub struct ValidatorReward {
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct DelegateRequest {
+    /// Delegator address signing the transaction
+    pub delegator_address: String,
     pub validator_address: String,
     pub amount: String,
-    pub denom: String,
+    /// Bank denom to stake; defaults to the native staking denom
+    #[serde(default)]
+    pub denom: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct UndelegateRequest {
+    /// Delegator address signing the transaction
+    pub delegator_address: String,
     pub validator_address: String,
     pub amount: String,
-    pub denom: String,
+    /// Bank denom to unstake; defaults to the native staking denom
+    #[serde(default)]
+    pub denom: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct RedelegateRequest {
+    /// Delegator address signing the transaction
+    pub delegator_address: String,
     pub src_validator_address: String,
     pub dst_validator_address: String,
     pub amount: String,
-    pub denom: String,
+    /// Bank denom to move; defaults to the native staking denom
+    #[serde(default)]
+    pub denom: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct ClaimRewardsRequest {
+    /// Delegator address signing the transaction
+    pub delegator_address: String,
     pub validator_addresses: Vec<String>,
 }
 
@@ -131,6 +150,7 @@ pub struct StakingTransactionResponse {
     #[schema(value_type = Vec<Object>)]
     pub messages: Vec<serde_json::Value>,
     pub memo: String,
+    pub gas: GasEstimate,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -229,26 +249,32 @@ pub async fn get_positions(
     crate::validation::validate_bech32_address(&query.address, "address")?;
     debug!("Getting staking positions for: {}", query.address);
 
+    Ok(Json(fetch_staking_positions(&state, &query.address).await?))
+}
+
+/// Delegations, unbonding entries and pending rewards of a delegator.
+///
+/// Shared by the positions handler and the portfolio aggregate. A failed
+/// chain query is an error, so an outage never reads as having nothing
+/// staked.
+pub async fn fetch_staking_positions(
+    state: &AppState,
+    address: &str,
+) -> Result<StakingPositionsResponse, AppError> {
     // Fetch user-specific data in parallel; validators come from cache
     debug!("Fetching delegations, rewards, and unbonding...");
     let (delegations_result, rewards_result, unbonding_result) = tokio::join!(
-        state.chain_client.get_delegations(&query.address),
-        state.chain_client.get_rewards(&query.address),
-        state.chain_client.get_unbonding_delegations(&query.address),
+        state.chain_client.get_delegations(address),
+        state.chain_client.get_rewards(address),
+        state.chain_client.get_unbonding_delegations(address),
     );
     debug!("Delegations result: {:?}", delegations_result.is_ok());
     debug!("Rewards result: {:?}", rewards_result.is_ok());
     debug!("Unbonding result: {:?}", unbonding_result.is_ok());
 
-    let delegations = delegations_result.unwrap_or_default();
-    let rewards_response = match rewards_result {
-        Ok(r) => Some(r),
-        Err(e) => {
-            warn!("Failed to fetch staking rewards: {}", e);
-            None
-        }
-    };
-    let unbonding_delegations = unbonding_result.unwrap_or_default();
+    let delegations = delegations_result?;
+    let rewards_response = rewards_result?;
+    let unbonding_delegations = unbonding_result?;
     let validators = state.data_cache.validators.load();
 
     // Build delegations list
@@ -281,40 +307,32 @@ pub async fn get_positions(
 
     // Build rewards list
     let rewards: Vec<ValidatorReward> = rewards_response
-        .as_ref()
-        .map(|r| {
-            r.rewards
+        .rewards
+        .iter()
+        .map(|vr| ValidatorReward {
+            validator_address: vr.validator_address.clone(),
+            rewards: vr
+                .reward
                 .iter()
-                .map(|vr| ValidatorReward {
-                    validator_address: vr.validator_address.clone(),
-                    rewards: vr
-                        .reward
-                        .iter()
-                        .map(|b| BalanceInfo {
-                            denom: b.denom.clone(),
-                            amount: b.amount.clone(),
-                        })
-                        .collect(),
+                .map(|b| BalanceInfo {
+                    denom: b.denom.clone(),
+                    amount: b.amount.clone(),
                 })
-                .collect()
+                .collect(),
         })
-        .unwrap_or_default();
+        .collect();
 
     // Calculate total rewards
     // Chain returns decimal strings like "1234567.890000000000000000"
     // Extract integer part to avoid f64 precision issues
     let total_rewards: u128 = rewards_response
-        .as_ref()
-        .map(|r| {
-            r.total
-                .iter()
-                .filter_map(|b| {
-                    let integer_part = b.amount.split('.').next().unwrap_or("0");
-                    integer_part.parse::<u128>().ok()
-                })
-                .sum()
+        .total
+        .iter()
+        .filter_map(|b| {
+            let integer_part = b.amount.split('.').next().unwrap_or("0");
+            integer_part.parse::<u128>().ok()
         })
-        .unwrap_or(0);
+        .sum();
 
     // Build unbonding positions
     let unbonding_positions: Vec<UnbondingPosition> = unbonding_delegations
@@ -343,13 +361,13 @@ pub async fn get_positions(
         })
         .collect();
 
-    Ok(Json(StakingPositionsResponse {
+    Ok(StakingPositionsResponse {
         delegations: delegation_positions,
         unbonding: unbonding_positions,
         rewards,
         total_staked: total_staked.to_string(),
         total_rewards: total_rewards.to_string(),
-    }))
+    })
 }
 
 /// Get staking parameters
@@ -382,8 +400,8 @@ pub async fn get_staking_params(
 
 /// Build a delegate transaction
 ///
-/// Returns an unsigned `MsgDelegate` for client-side signing. The delegator
-/// address is filled in by the frontend before broadcast.
+/// Returns a ready-to-sign `MsgDelegate` for the given delegator, staking the
+/// native denom unless another is specified, with a gas estimate attached.
 #[utoipa::path(
     post,
     path = "/api/staking/delegate",
@@ -391,10 +409,12 @@ pub async fn get_staking_params(
     request_body = DelegateRequest,
     responses(
         (status = 200, description = "Unsigned delegate transaction", body = StakingTransactionResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
     ),
 )]
 pub async fn delegate(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<DelegateRequest>,
 ) -> Result<Json<StakingTransactionResponse>, AppError> {
     debug!(
@@ -402,12 +422,17 @@ pub async fn delegate(
         request.validator_address
     );
 
+    validate_delegator(&request.delegator_address)?;
+    crate::validation::validate_bech32_address(&request.validator_address, "validator_address")?;
+    tx_builder::parse_positive_amount(&request.amount, "amount")?;
+    let denom = resolve_staking_denom(&state, request.denom)?;
+
     let delegate_msg = serde_json::json!({
         "@type": "/cosmos.staking.v1beta1.MsgDelegate",
-        "delegator_address": "", // Will be filled by frontend
+        "delegator_address": request.delegator_address,
         "validator_address": request.validator_address,
         "amount": {
-            "denom": request.denom,
+            "denom": denom,
             "amount": request.amount
         }
     });
@@ -415,12 +440,13 @@ pub async fn delegate(
     Ok(Json(StakingTransactionResponse {
         messages: vec![delegate_msg],
         memo: "Delegate to Nolus validator".to_string(),
+        gas: staking_gas(&state, &[MsgKind::Delegate]),
     }))
 }
 
 /// Build an undelegate transaction
 ///
-/// Returns an unsigned `MsgUndelegate` for client-side signing.
+/// Returns a ready-to-sign `MsgUndelegate` for the given delegator.
 #[utoipa::path(
     post,
     path = "/api/staking/undelegate",
@@ -428,10 +454,12 @@ pub async fn delegate(
     request_body = UndelegateRequest,
     responses(
         (status = 200, description = "Unsigned undelegate transaction", body = StakingTransactionResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
     ),
 )]
 pub async fn undelegate(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<UndelegateRequest>,
 ) -> Result<Json<StakingTransactionResponse>, AppError> {
     debug!(
@@ -439,12 +467,17 @@ pub async fn undelegate(
         request.validator_address
     );
 
+    validate_delegator(&request.delegator_address)?;
+    crate::validation::validate_bech32_address(&request.validator_address, "validator_address")?;
+    tx_builder::parse_positive_amount(&request.amount, "amount")?;
+    let denom = resolve_staking_denom(&state, request.denom)?;
+
     let undelegate_msg = serde_json::json!({
         "@type": "/cosmos.staking.v1beta1.MsgUndelegate",
-        "delegator_address": "", // Will be filled by frontend
+        "delegator_address": request.delegator_address,
         "validator_address": request.validator_address,
         "amount": {
-            "denom": request.denom,
+            "denom": denom,
             "amount": request.amount
         }
     });
@@ -452,12 +485,13 @@ pub async fn undelegate(
     Ok(Json(StakingTransactionResponse {
         messages: vec![undelegate_msg],
         memo: "Undelegate from Nolus validator".to_string(),
+        gas: staking_gas(&state, &[MsgKind::Undelegate]),
     }))
 }
 
 /// Build a redelegate transaction
 ///
-/// Returns an unsigned `MsgBeginRedelegate` for moving stake between validators.
+/// Returns a ready-to-sign `MsgBeginRedelegate` for moving stake between validators.
 #[utoipa::path(
     post,
     path = "/api/staking/redelegate",
@@ -465,10 +499,12 @@ pub async fn undelegate(
     request_body = RedelegateRequest,
     responses(
         (status = 200, description = "Unsigned redelegate transaction", body = StakingTransactionResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
     ),
 )]
 pub async fn redelegate(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<RedelegateRequest>,
 ) -> Result<Json<StakingTransactionResponse>, AppError> {
     debug!(
@@ -476,13 +512,25 @@ pub async fn redelegate(
         request.src_validator_address, request.dst_validator_address
     );
 
+    validate_delegator(&request.delegator_address)?;
+    crate::validation::validate_bech32_address(
+        &request.src_validator_address,
+        "src_validator_address",
+    )?;
+    crate::validation::validate_bech32_address(
+        &request.dst_validator_address,
+        "dst_validator_address",
+    )?;
+    tx_builder::parse_positive_amount(&request.amount, "amount")?;
+    let denom = resolve_staking_denom(&state, request.denom)?;
+
     let redelegate_msg = serde_json::json!({
         "@type": "/cosmos.staking.v1beta1.MsgBeginRedelegate",
-        "delegator_address": "", // Will be filled by frontend
+        "delegator_address": request.delegator_address,
         "validator_src_address": request.src_validator_address,
         "validator_dst_address": request.dst_validator_address,
         "amount": {
-            "denom": request.denom,
+            "denom": denom,
             "amount": request.amount
         }
     });
@@ -490,13 +538,14 @@ pub async fn redelegate(
     Ok(Json(StakingTransactionResponse {
         messages: vec![redelegate_msg],
         memo: "Redelegate on Nolus".to_string(),
+        gas: staking_gas(&state, &[MsgKind::Redelegate]),
     }))
 }
 
 /// Build a claim-rewards transaction
 ///
-/// Returns unsigned `MsgWithdrawDelegatorReward` messages, one per validator
-/// address supplied.
+/// Returns ready-to-sign `MsgWithdrawDelegatorReward` messages, one per
+/// validator address supplied.
 #[utoipa::path(
     post,
     path = "/api/staking/claim-rewards",
@@ -504,10 +553,11 @@ pub async fn redelegate(
     request_body = ClaimRewardsRequest,
     responses(
         (status = 200, description = "Unsigned claim-rewards transaction", body = StakingTransactionResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
     ),
 )]
 pub async fn claim_rewards(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<ClaimRewardsRequest>,
 ) -> Result<Json<StakingTransactionResponse>, AppError> {
     debug!(
@@ -515,28 +565,656 @@ pub async fn claim_rewards(
         request.validator_addresses.len()
     );
 
+    validate_delegator(&request.delegator_address)?;
+    for validator_address in &request.validator_addresses {
+        crate::validation::validate_bech32_address(validator_address, "validator_addresses")?;
+    }
+
     let messages: Vec<serde_json::Value> = request
         .validator_addresses
         .iter()
         .map(|validator_address| {
             serde_json::json!({
                 "@type": "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
-                "delegator_address": "", // Will be filled by frontend
+                "delegator_address": request.delegator_address,
                 "validator_address": validator_address
             })
         })
         .collect();
 
+    let kinds = vec![MsgKind::WithdrawReward; messages.len()];
     Ok(Json(StakingTransactionResponse {
         messages,
         memo: "Claim staking rewards".to_string(),
+        gas: staking_gas(&state, &kinds),
+    }))
+}
+
+// ============================================================================
+// Unbonding Schedule
+// ============================================================================
+
+/// Unbonding tokens become liquid at completion; a completed redelegation
+/// leaves the source validator's slashing window and can be moved again
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleEntryKind {
+    Unbonding,
+    Redelegation,
+}
+
+/// An unbonding or redelegation entry on the delegator's timeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ScheduleEntry {
+    pub kind: ScheduleEntryKind,
+    /// Unbonding validator, or redelegation source
+    pub validator_address: String,
+    pub validator_moniker: Option<String>,
+    /// Redelegation destination
+    pub destination_validator_address: Option<String>,
+    pub destination_moniker: Option<String>,
+    pub creation_height: String,
+    /// RFC 3339 completion timestamp
+    pub completion_time: String,
+    pub initial_balance: String,
+    /// Current balance (lower than `initial_balance` after a slash)
+    pub amount: String,
+    /// Seconds until completion (0 once due)
+    pub seconds_remaining: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StakingScheduleResponse {
+    pub address: String,
+    /// Entries ordered by completion time, soonest first
+    pub entries: Vec<ScheduleEntry>,
+    /// Sum of unbonding balances (minimal denomination)
+    pub total_unbonding: String,
+    /// Sum of in-flight redelegation balances (minimal denomination)
+    pub total_redelegating: String,
+    /// Completion time of the next unbonding entry
+    pub next_liquid_at: Option<String>,
+}
+
+/// Get unbonding and redelegation schedule
+///
+/// Merges a delegator's unbonding entries and in-flight redelegations into
+/// one timeline ordered by completion time, with the amount each entry
+/// carries and when unbonding funds become liquid.
+#[utoipa::path(
+    get,
+    path = "/api/staking/schedule",
+    tag = "staking",
+    params(AddressQuery),
+    responses(
+        (status = 200, description = "Unbonding and redelegation timeline", body = StakingScheduleResponse),
+        (status = 400, description = "Invalid address", body = crate::error::ErrorResponse),
+        (status = 502, description = "Upstream chain RPC error", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn get_schedule(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AddressQuery>,
+) -> Result<Json<StakingScheduleResponse>, AppError> {
+    crate::validation::validate_bech32_address(&query.address, "address")?;
+    debug!("Getting staking schedule for: {}", query.address);
+
+    Ok(Json(fetch_staking_schedule(&state, &query.address).await?))
+}
+
+/// Unbonding and redelegation timeline of a delegator.
+///
+/// Unlike [`fetch_staking_positions`] a failed chain query is an error: the
+/// WebSocket maturity monitor must not mistake a missing section for entries
+/// that completed.
+pub async fn fetch_staking_schedule(
+    state: &AppState,
+    address: &str,
+) -> Result<StakingScheduleResponse, AppError> {
+    let (unbonding, redelegations) = tokio::try_join!(
+        state.chain_client.get_unbonding_delegations(address),
+        state.chain_client.get_redelegations(address),
+    )?;
+    let validators = state.data_cache.validators.load();
+
+    Ok(build_schedule(
+        address,
+        &unbonding,
+        &redelegations,
+        validators.as_deref().unwrap_or_default(),
+        chrono::Utc::now(),
+    ))
+}
+
+fn build_schedule(
+    address: &str,
+    unbonding: &[crate::external::chain::UnbondingDelegation],
+    redelegations: &[crate::external::chain::RedelegationResponse],
+    validators: &[Validator],
+    now: chrono::DateTime<chrono::Utc>,
+) -> StakingScheduleResponse {
+    let moniker = |address: &str| {
+        validators
+            .iter()
+            .find(|v| v.operator_address == address)
+            .map(|v| v.moniker.clone())
+    };
+    let seconds_remaining = |completion_time: &str| {
+        chrono::DateTime::parse_from_rfc3339(completion_time)
+            .map(|at| (at.with_timezone(&chrono::Utc) - now).num_seconds().max(0))
+            .unwrap_or(0)
+    };
+
+    let unbonding_entries = unbonding.iter().flat_map(|u| {
+        u.entries.iter().map(|e| ScheduleEntry {
+            kind: ScheduleEntryKind::Unbonding,
+            validator_address: u.validator_address.clone(),
+            validator_moniker: moniker(&u.validator_address),
+            destination_validator_address: None,
+            destination_moniker: None,
+            creation_height: e.creation_height.clone(),
+            completion_time: e.completion_time.clone(),
+            initial_balance: e.initial_balance.clone(),
+            amount: e.balance.clone(),
+            seconds_remaining: seconds_remaining(&e.completion_time),
+        })
+    });
+    let redelegation_entries = redelegations.iter().flat_map(|r| {
+        r.entries.iter().map(|e| ScheduleEntry {
+            kind: ScheduleEntryKind::Redelegation,
+            validator_address: r.redelegation.validator_src_address.clone(),
+            validator_moniker: moniker(&r.redelegation.validator_src_address),
+            destination_validator_address: Some(r.redelegation.validator_dst_address.clone()),
+            destination_moniker: moniker(&r.redelegation.validator_dst_address),
+            creation_height: e.redelegation_entry.creation_height.clone(),
+            completion_time: e.redelegation_entry.completion_time.clone(),
+            initial_balance: e.redelegation_entry.initial_balance.clone(),
+            amount: e.balance.clone(),
+            seconds_remaining: seconds_remaining(&e.redelegation_entry.completion_time),
+        })
+    });
+
+    let mut entries: Vec<ScheduleEntry> = unbonding_entries.chain(redelegation_entries).collect();
+    entries.sort_by(|a, b| {
+        a.seconds_remaining
+            .cmp(&b.seconds_remaining)
+            .then_with(|| a.completion_time.cmp(&b.completion_time))
+    });
+
+    let total = |kind: ScheduleEntryKind| -> u128 {
+        entries
+            .iter()
+            .filter(|e| e.kind == kind)
+            .filter_map(|e| e.amount.parse::<u128>().ok())
+            .sum()
+    };
+    let total_unbonding = total(ScheduleEntryKind::Unbonding);
+    let total_redelegating = total(ScheduleEntryKind::Redelegation);
+    let next_liquid_at = entries
+        .iter()
+        .find(|e| e.kind == ScheduleEntryKind::Unbonding)
+        .map(|e| e.completion_time.clone());
+
+    StakingScheduleResponse {
+        address: address.to_string(),
+        entries,
+        total_unbonding: total_unbonding.to_string(),
+        total_redelegating: total_redelegating.to_string(),
+        next_liquid_at,
+    }
+}
+
+/// Entries of `previous` that the chain no longer reports and whose
+/// completion time has passed. An entry that vanishes early was cancelled
+/// (`MsgCancelUnbondingDelegation`), not matured.
+pub fn matured_entries(
+    previous: &[ScheduleEntry],
+    current: &[ScheduleEntry],
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<ScheduleEntry> {
+    previous
+        .iter()
+        .filter(|e| !current.iter().any(|c| same_entry(c, e)))
+        .filter(|e| {
+            chrono::DateTime::parse_from_rfc3339(&e.completion_time)
+                .is_ok_and(|at| at.with_timezone(&chrono::Utc) <= now)
+        })
+        .cloned()
+        .collect()
+}
+
+/// Identity of an entry across polls (balances and countdowns change)
+fn same_entry(a: &ScheduleEntry, b: &ScheduleEntry) -> bool {
+    a.kind == b.kind
+        && a.validator_address == b.validator_address
+        && a.destination_validator_address == b.destination_validator_address
+        && a.creation_height == b.creation_height
+        && a.completion_time == b.completion_time
+        && a.initial_balance == b.initial_balance
+}
+
+// ============================================================================
+// Auto-Compound (authz)
+// ============================================================================
+
+/// Messages the restake bot needs to execute on the delegator's behalf
+const RESTAKE_MSG_TYPES: [&str; 2] = [
+    "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+    "/cosmos.staking.v1beta1.MsgDelegate",
+];
+
+const GENERIC_AUTHORIZATION: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
+
+/// Grant lifetime when the request does not specify one
+const DEFAULT_GRANT_DAYS: u32 = 365;
+
+/// Longest grant the builder issues
+const MAX_GRANT_DAYS: u32 = 730;
+
+const SECONDS_PER_YEAR: u64 = 365 * 86_400;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AutoCompoundRequest {
+    /// Delegator address signing the grant
+    pub delegator_address: String,
+    /// Validators the delegator stakes with, for the APR projection
+    pub validator_addresses: Vec<String>,
+    /// Grant lifetime in days (default 365, max 730)
+    #[serde(default)]
+    pub expiration_days: Option<u32>,
+}
+
+/// Projected yield of letting the bot compound rewards at one validator
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CompoundingProjection {
+    pub validator_address: String,
+    pub moniker: String,
+    pub commission_rate: String,
+    /// Staking APR net of commission (percent)
+    pub apr: f64,
+    /// APR with rewards re-delegated every restake interval (percent)
+    pub compounding_apr: f64,
+    pub compounds_per_year: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AutoCompoundResponse {
+    /// Restake bot receiving the grants
+    pub grantee: String,
+    /// RFC 3339 expiry written into the grants
+    pub expiration: String,
+    /// Unsigned Cosmos SDK messages for client-side signing
+    #[schema(value_type = Vec<Object>)]
+    pub messages: Vec<serde_json::Value>,
+    pub memo: String,
+    pub gas: GasEstimate,
+    pub projections: Vec<CompoundingProjection>,
+}
+
+/// An authz grant issued by the delegator
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AuthzGrant {
+    pub grantee: String,
+    pub authorization_type: String,
+    /// Message type a `GenericAuthorization` allows
+    pub msg_type_url: Option<String>,
+    pub expiration: Option<String>,
+    /// Granted to the configured restake bot for one of its message types
+    pub restake: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AutoCompoundGrantsResponse {
+    pub restake_bot: Option<String>,
+    /// The bot holds unexpired grants for every message it needs
+    pub enabled: bool,
+    /// Earliest expiry among the bot's grants
+    pub expires_at: Option<String>,
+    pub grants: Vec<AuthzGrant>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RevokeGrantsRequest {
+    /// Delegator (granter) address signing the transaction
+    pub delegator_address: String,
+    /// Grantee to revoke; defaults to the restake bot
+    #[serde(default)]
+    pub grantee: Option<String>,
+    /// Message types to revoke; defaults to the restake bot's
+    #[serde(default)]
+    pub msg_type_urls: Option<Vec<String>>,
+}
+
+/// Build an auto-compound grant
+///
+/// Returns ready-to-sign authz `MsgGrant`s giving the operator's restake bot
+/// a `GenericAuthorization` for `MsgWithdrawDelegatorReward` and
+/// `MsgDelegate`, plus the compounding APR projected for each listed
+/// validator from the staking APR, its commission and the bot's interval.
+#[utoipa::path(
+    post,
+    path = "/api/staking/auto-compound",
+    tag = "staking",
+    request_body = AutoCompoundRequest,
+    responses(
+        (status = 200, description = "Unsigned grant transaction", body = AutoCompoundResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 503, description = "Restake bot not configured or cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn build_auto_compound(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AutoCompoundRequest>,
+) -> Result<Json<AutoCompoundResponse>, AppError> {
+    debug!(
+        "Building auto-compound grant for {}",
+        request.delegator_address
+    );
+
+    validate_delegator(&request.delegator_address)?;
+    let grantee = restake_bot(&state)?;
+    let days = request.expiration_days.unwrap_or(DEFAULT_GRANT_DAYS);
+    if days == 0 || days > MAX_GRANT_DAYS {
+        return Err(AppError::Validation {
+            message: format!("expiration_days must be between 1 and {}", MAX_GRANT_DAYS),
+            field: Some("expiration_days".to_string()),
+            details: None,
+        });
+    }
+    if request.validator_addresses.is_empty() {
+        return Err(AppError::Validation {
+            message: "At least one validator is required".to_string(),
+            field: Some("validator_addresses".to_string()),
+            details: None,
+        });
+    }
+    let projections = compounding_projections(&state, &request.validator_addresses)?;
+
+    let expiration = (chrono::Utc::now() + chrono::Duration::days(i64::from(days)))
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let messages: Vec<serde_json::Value> = RESTAKE_MSG_TYPES
+        .iter()
+        .map(|msg_type_url| {
+            serde_json::json!({
+                "@type": "/cosmos.authz.v1beta1.MsgGrant",
+                "granter": request.delegator_address,
+                "grantee": grantee,
+                "grant": {
+                    "authorization": {
+                        "@type": GENERIC_AUTHORIZATION,
+                        "msg": msg_type_url
+                    },
+                    "expiration": expiration
+                }
+            })
+        })
+        .collect();
+
+    let kinds = vec![MsgKind::Grant; messages.len()];
+    Ok(Json(AutoCompoundResponse {
+        grantee,
+        expiration,
+        messages,
+        memo: "Enable staking auto-compound".to_string(),
+        gas: staking_gas(&state, &kinds),
+        projections,
     }))
 }
 
+/// List authz grants
+///
+/// Returns the authz grants issued by a delegator, flagging those held by the
+/// restake bot and whether auto-compound is currently active.
+#[utoipa::path(
+    get,
+    path = "/api/staking/auto-compound/grants",
+    tag = "staking",
+    params(AddressQuery),
+    responses(
+        (status = 200, description = "Grants issued by the delegator", body = AutoCompoundGrantsResponse),
+        (status = 400, description = "Invalid address", body = crate::error::ErrorResponse),
+        (status = 502, description = "Upstream chain RPC error", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn get_auto_compound_grants(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AddressQuery>,
+) -> Result<Json<AutoCompoundGrantsResponse>, AppError> {
+    validate_delegator(&query.address)?;
+    debug!("Listing authz grants of {}", query.address);
+
+    let grants = state
+        .chain_client
+        .get_granter_grants(&query.address)
+        .await?;
+    let restake_bot = state.config.restake.bot_address.clone();
+    Ok(Json(summarize_grants(
+        grants,
+        restake_bot,
+        chrono::Utc::now(),
+    )))
+}
+
+/// Build a grant revocation
+///
+/// Returns ready-to-sign authz `MsgRevoke`s, by default for the restake bot's
+/// two grants (disabling auto-compound).
+#[utoipa::path(
+    post,
+    path = "/api/staking/auto-compound/revoke",
+    tag = "staking",
+    request_body = RevokeGrantsRequest,
+    responses(
+        (status = 200, description = "Unsigned revoke transaction", body = StakingTransactionResponse),
+        (status = 400, description = "Invalid request", body = crate::error::ErrorResponse),
+        (status = 503, description = "Restake bot not configured", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn revoke_auto_compound(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<RevokeGrantsRequest>,
+) -> Result<Json<StakingTransactionResponse>, AppError> {
+    debug!(
+        "Building grant revocation for {}",
+        request.delegator_address
+    );
+
+    validate_delegator(&request.delegator_address)?;
+    let grantee = match request.grantee {
+        Some(grantee) => {
+            crate::validation::validate_nolus_address(&grantee, "grantee")?;
+            grantee
+        }
+        None => restake_bot(&state)?,
+    };
+    let msg_type_urls = request
+        .msg_type_urls
+        .unwrap_or_else(|| RESTAKE_MSG_TYPES.iter().map(|t| t.to_string()).collect());
+    if msg_type_urls.is_empty() || msg_type_urls.iter().any(|t| !t.starts_with('/')) {
+        return Err(AppError::Validation {
+            message: "msg_type_urls must be non-empty type URLs".to_string(),
+            field: Some("msg_type_urls".to_string()),
+            details: None,
+        });
+    }
+
+    let messages: Vec<serde_json::Value> = msg_type_urls
+        .iter()
+        .map(|msg_type_url| {
+            serde_json::json!({
+                "@type": "/cosmos.authz.v1beta1.MsgRevoke",
+                "granter": request.delegator_address,
+                "grantee": grantee,
+                "msg_type_url": msg_type_url
+            })
+        })
+        .collect();
+
+    let kinds = vec![MsgKind::Revoke; messages.len()];
+    Ok(Json(StakingTransactionResponse {
+        messages,
+        memo: "Revoke staking grants".to_string(),
+        gas: staking_gas(&state, &kinds),
+    }))
+}
+
+/// The configured restake bot, or 503 when the operator has not set one
+fn restake_bot(state: &AppState) -> Result<String, AppError> {
+    state
+        .config
+        .restake
+        .bot_address
+        .clone()
+        .ok_or_else(|| AppError::ServiceUnavailable {
+            message: "Auto-compound is not available: no restake bot configured".to_string(),
+        })
+}
+
+/// Project the compounding APR for each validator from the cached staking
+/// APR (annual inflation over bonded stake, as the frontend computes it).
+fn compounding_projections(
+    state: &AppState,
+    validator_addresses: &[String],
+) -> Result<Vec<CompoundingProjection>, AppError> {
+    let inflation = state
+        .data_cache
+        .annual_inflation
+        .load_or_unavailable("Annual inflation")?;
+    let pool = state
+        .data_cache
+        .staking_pool
+        .load_or_unavailable("Staking pool")?;
+    let validators = state
+        .data_cache
+        .validators
+        .load_or_unavailable("Validators")?;
+
+    let annual_inflation: f64 = inflation.annual_inflation.parse().unwrap_or(0.0);
+    let bonded: f64 = pool.pool.bonded_tokens.parse().unwrap_or(0.0);
+    let staking_apr = if bonded > 0.0 {
+        annual_inflation / bonded * 100.0
+    } else {
+        0.0
+    };
+    let compounds_per_year = (SECONDS_PER_YEAR / state.config.restake.interval_secs.max(1)).max(1);
+
+    validator_addresses
+        .iter()
+        .map(|address| {
+            let validator = validators
+                .iter()
+                .find(|v| &v.operator_address == address)
+                .ok_or_else(|| AppError::Validation {
+                    message: format!("Unknown validator {}", address),
+                    field: Some("validator_addresses".to_string()),
+                    details: None,
+                })?;
+            let commission: f64 = validator.commission_rate.parse().unwrap_or(0.0);
+            let apr = staking_apr * (1.0 - commission);
+            Ok(CompoundingProjection {
+                validator_address: validator.operator_address.clone(),
+                moniker: validator.moniker.clone(),
+                commission_rate: validator.commission_rate.clone(),
+                apr,
+                compounding_apr: compound_apr(apr, compounds_per_year),
+                compounds_per_year,
+            })
+        })
+        .collect()
+}
+
+/// Effective annual yield (percent) of `apr` (percent) compounded `n` times
+fn compound_apr(apr: f64, n: u64) -> f64 {
+    let periods = crate::num_utils::u128_to_f64(u128::from(n));
+    ((1.0 + apr / 100.0 / periods).powf(periods) - 1.0) * 100.0
+}
+
+fn summarize_grants(
+    grants: Vec<crate::external::chain::GrantAuthorization>,
+    restake_bot: Option<String>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> AutoCompoundGrantsResponse {
+    let grants: Vec<AuthzGrant> = grants
+        .into_iter()
+        .map(|grant| {
+            let authorization_type = grant
+                .authorization
+                .get("@type")
+                .and_then(|t| t.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let msg_type_url = grant
+                .authorization
+                .get("msg")
+                .and_then(|m| m.as_str())
+                .map(str::to_string);
+            let restake = restake_bot.as_deref() == Some(grant.grantee.as_str())
+                && authorization_type == GENERIC_AUTHORIZATION
+                && msg_type_url
+                    .as_deref()
+                    .is_some_and(|t| RESTAKE_MSG_TYPES.contains(&t));
+            AuthzGrant {
+                grantee: grant.grantee,
+                authorization_type,
+                msg_type_url,
+                expiration: grant.expiration,
+                restake,
+            }
+        })
+        .collect();
+
+    let live_restake: Vec<&AuthzGrant> = grants
+        .iter()
+        .filter(|g| g.restake)
+        .filter(|g| {
+            g.expiration.as_deref().is_none_or(|expiry| {
+                chrono::DateTime::parse_from_rfc3339(expiry).is_ok_and(|at| at > now)
+            })
+        })
+        .collect();
+    let enabled = RESTAKE_MSG_TYPES.iter().all(|msg_type| {
+        live_restake
+            .iter()
+            .any(|g| g.msg_type_url.as_deref() == Some(*msg_type))
+    });
+    let expires_at = live_restake
+        .iter()
+        .filter_map(|g| g.expiration.clone())
+        .min();
+
+    AutoCompoundGrantsResponse {
+        restake_bot,
+        enabled,
+        expires_at,
+        grants,
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+fn validate_delegator(delegator_address: &str) -> Result<(), AppError> {
+    crate::validation::validate_nolus_address(delegator_address, "delegator_address")
+}
+
+/// Use the requested denom, or the native staking denom from cached currencies
+fn resolve_staking_denom(state: &AppState, requested: Option<String>) -> Result<String, AppError> {
+    if let Some(denom) = requested.filter(|d| !d.is_empty()) {
+        return Ok(denom);
+    }
+    let currencies = state
+        .data_cache
+        .currencies
+        .load_or_unavailable("Currencies")?;
+    tx_builder::resolve_native_denom(&currencies)
+}
+
+fn staking_gas(state: &AppState, kinds: &[MsgKind]) -> GasEstimate {
+    let gas_fee_config = state.data_cache.gas_fee_config.load();
+    tx_builder::estimate_gas(kinds, gas_fee_config.as_ref())
+}
+
 pub fn parse_validator_status(status: &str) -> ValidatorStatus {
     match status {
         "BOND_STATUS_BONDED" => ValidatorStatus::Bonded,
@@ -568,4 +1246,290 @@ mod tests {
             ValidatorStatus::Unbonded
         ));
     }
+
+    #[test]
+    fn test_build_schedule_merges_and_orders() {
+        use crate::external::chain::{
+            Redelegation, RedelegationEntry, RedelegationEntryResponse, RedelegationResponse,
+            UnbondingDelegation, UnbondingEntry as ChainUnbondingEntry,
+        };
+
+        let now = chrono::DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let unbonding = vec![UnbondingDelegation {
+            delegator_address: "nolus1owner".to_string(),
+            validator_address: "nolusvaloper1a".to_string(),
+            entries: vec![ChainUnbondingEntry {
+                creation_height: "10".to_string(),
+                completion_time: "2026-01-20T00:00:00Z".to_string(),
+                initial_balance: "700".to_string(),
+                balance: "700".to_string(),
+            }],
+        }];
+        let redelegations = vec![RedelegationResponse {
+            redelegation: Redelegation {
+                delegator_address: "nolus1owner".to_string(),
+                validator_src_address: "nolusvaloper1a".to_string(),
+                validator_dst_address: "nolusvaloper1b".to_string(),
+            },
+            entries: vec![RedelegationEntryResponse {
+                redelegation_entry: RedelegationEntry {
+                    creation_height: "12".to_string(),
+                    completion_time: "2026-01-02T00:00:00Z".to_string(),
+                    initial_balance: "300".to_string(),
+                },
+                balance: "300".to_string(),
+            }],
+        }];
+
+        let schedule = build_schedule("nolus1owner", &unbonding, &redelegations, &[], now);
+        assert_eq!(schedule.entries.len(), 2);
+        assert_eq!(schedule.entries[0].kind, ScheduleEntryKind::Redelegation);
+        assert_eq!(schedule.entries[0].seconds_remaining, 86_400);
+        assert_eq!(
+            schedule.entries[0].destination_validator_address.as_deref(),
+            Some("nolusvaloper1b")
+        );
+        assert_eq!(schedule.total_unbonding, "700");
+        assert_eq!(schedule.total_redelegating, "300");
+        assert_eq!(
+            schedule.next_liquid_at.as_deref(),
+            Some("2026-01-20T00:00:00Z")
+        );
+
+        // Vanished before completion: cancelled, not matured
+        let later = now + chrono::Duration::days(2);
+        let matured = matured_entries(&schedule.entries, &[], later);
+        assert_eq!(matured.len(), 1);
+        assert_eq!(matured[0].kind, ScheduleEntryKind::Redelegation);
+    }
+
+    #[test]
+    fn test_compound_apr() {
+        assert!((compound_apr(10.0, 1) - 10.0).abs() < 1e-9);
+        let daily = compound_apr(10.0, 365);
+        assert!((daily - 10.5156).abs() < 1e-3);
+        assert_eq!(compound_apr(0.0, 365), 0.0);
+    }
+
+    #[test]
+    fn test_summarize_grants() {
+        use crate::external::chain::GrantAuthorization;
+
+        let bot = "nolus1bot".to_string();
+        let grant = |grantee: &str, msg: &str, expiration: &str| GrantAuthorization {
+            granter: "nolus1delegator".to_string(),
+            grantee: grantee.to_string(),
+            authorization: serde_json::json!({
+                "@type": GENERIC_AUTHORIZATION,
+                "msg": msg
+            }),
+            expiration: Some(expiration.to_string()),
+        };
+        let now = chrono::DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        let summary = summarize_grants(
+            vec![
+                grant(&bot, RESTAKE_MSG_TYPES[0], "2026-06-01T00:00:00Z"),
+                grant(&bot, RESTAKE_MSG_TYPES[1], "2026-03-01T00:00:00Z"),
+                grant("nolus1other", RESTAKE_MSG_TYPES[1], "2027-01-01T00:00:00Z"),
+            ],
+            Some(bot.clone()),
+            now,
+        );
+        assert!(summary.enabled);
+        assert_eq!(summary.expires_at.as_deref(), Some("2026-03-01T00:00:00Z"));
+        assert_eq!(summary.grants.iter().filter(|g| g.restake).count(), 2);
+
+        // One of the bot's grants has expired
+        let summary = summarize_grants(
+            vec![
+                grant(&bot, RESTAKE_MSG_TYPES[0], "2026-06-01T00:00:00Z"),
+                grant(&bot, RESTAKE_MSG_TYPES[1], "2025-12-01T00:00:00Z"),
+            ],
+            Some(bot),
+            now,
+        );
+        assert!(!summary.enabled);
+    }
+
+    const DELEGATOR: &str = "nolus1ncc58ptqrkd7r7uk60dx4eufvvqf2edhtktv0q";
+    const BOT: &str = "nolus1qg5ega6dykkxc307y25pecuufrjkxkaggkkxh7nad0vhyhtuhw3sqaa3c5";
+
+    async fn restake_state(bot: Option<&str>) -> Arc<AppState> {
+        use crate::external::chain::{AnnualInflationResponse, StakingPool, StakingPoolResponse};
+
+        let mut config = crate::test_utils::test_config();
+        config.restake.bot_address = bot.map(str::to_string);
+        let state = crate::test_utils::test_app_state_with_config(config).await;
+        state
+            .data_cache
+            .annual_inflation
+            .store(AnnualInflationResponse {
+                annual_inflation: "100".to_string(),
+            });
+        state.data_cache.staking_pool.store(StakingPoolResponse {
+            pool: StakingPool {
+                not_bonded_tokens: "0".to_string(),
+                bonded_tokens: "1000".to_string(),
+            },
+        });
+        state.data_cache.validators.store(vec![Validator {
+            operator_address: "nolusvaloper1a".to_string(),
+            moniker: "A".to_string(),
+            identity: None,
+            website: None,
+            details: None,
+            commission_rate: "0.1".to_string(),
+            max_commission_rate: "0.2".to_string(),
+            max_commission_change_rate: "0.01".to_string(),
+            tokens: "1000".to_string(),
+            delegator_shares: "1000".to_string(),
+            unbonding_height: "0".to_string(),
+            unbonding_time: "1970-01-01T00:00:00Z".to_string(),
+            status: ValidatorStatus::Bonded,
+            jailed: false,
+        }]);
+        state
+    }
+
+    const VALIDATOR: &str = "nolusvaloper1ncc58ptqrkd7r7uk60dx4eufvvqf2edhjx7fva";
+    const BAD_AMOUNTS: [&str; 3] = ["abc", "0", "-1"];
+
+    fn assert_amount_rejected(result: Result<Json<StakingTransactionResponse>, AppError>) {
+        match result {
+            Err(AppError::Validation { field, .. }) => {
+                assert_eq!(field.as_deref(), Some("amount"));
+            }
+            other => panic!("expected an amount validation error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delegate_rejects_non_positive_amount() {
+        let state = crate::test_utils::test_app_state().await;
+        for amount in BAD_AMOUNTS {
+            let request = DelegateRequest {
+                delegator_address: DELEGATOR.to_string(),
+                validator_address: VALIDATOR.to_string(),
+                amount: amount.to_string(),
+                denom: Some("unls".to_string()),
+            };
+            assert_amount_rejected(delegate(State(state.clone()), Json(request)).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_undelegate_rejects_non_positive_amount() {
+        let state = crate::test_utils::test_app_state().await;
+        for amount in BAD_AMOUNTS {
+            let request = UndelegateRequest {
+                delegator_address: DELEGATOR.to_string(),
+                validator_address: VALIDATOR.to_string(),
+                amount: amount.to_string(),
+                denom: Some("unls".to_string()),
+            };
+            assert_amount_rejected(undelegate(State(state.clone()), Json(request)).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redelegate_rejects_non_positive_amount() {
+        let state = crate::test_utils::test_app_state().await;
+        for amount in BAD_AMOUNTS {
+            let request = RedelegateRequest {
+                delegator_address: DELEGATOR.to_string(),
+                src_validator_address: VALIDATOR.to_string(),
+                dst_validator_address: VALIDATOR.to_string(),
+                amount: amount.to_string(),
+                denom: Some("unls".to_string()),
+            };
+            assert_amount_rejected(redelegate(State(state.clone()), Json(request)).await);
+        }
+    }
+
+    fn auto_compound_request(expiration_days: Option<u32>) -> AutoCompoundRequest {
+        AutoCompoundRequest {
+            delegator_address: DELEGATOR.to_string(),
+            validator_addresses: vec!["nolusvaloper1a".to_string()],
+            expiration_days,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_build_auto_compound_grants_encode() {
+        let state = restake_state(Some(BOT)).await;
+        let Json(response) = build_auto_compound(State(state), Json(auto_compound_request(None)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.grantee, BOT);
+        assert_eq!(response.messages.len(), RESTAKE_MSG_TYPES.len());
+        assert_eq!(response.projections.len(), 1);
+        // 10% staking APR less 10% commission
+        assert!((response.projections[0].apr - 9.0).abs() < 1e-9);
+        assert!(response.projections[0].compounding_apr > response.projections[0].apr);
+
+        let encoded =
+            crate::handlers::cosmos_tx::encode_messages(&response.messages, DELEGATOR).unwrap();
+        assert!(encoded
+            .iter()
+            .all(|any| any.type_url == "/cosmos.authz.v1beta1.MsgGrant"));
+    }
+
+    #[tokio::test]
+    async fn test_build_auto_compound_rejects_bad_requests() {
+        let state = restake_state(None).await;
+        let err = build_auto_compound(State(state), Json(auto_compound_request(None)))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::ServiceUnavailable { .. }));
+
+        let state = restake_state(Some(BOT)).await;
+        let err = build_auto_compound(
+            State(state.clone()),
+            Json(auto_compound_request(Some(MAX_GRANT_DAYS + 1))),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::Validation { .. }));
+
+        let mut request = auto_compound_request(None);
+        request.validator_addresses = vec!["nolusvaloper1unknown".to_string()];
+        let err = build_auto_compound(State(state), Json(request))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_auto_compound_defaults_to_bot_grants() {
+        let state = restake_state(Some(BOT)).await;
+        let request = RevokeGrantsRequest {
+            delegator_address: DELEGATOR.to_string(),
+            grantee: None,
+            msg_type_urls: None,
+        };
+        let Json(response) = revoke_auto_compound(State(state.clone()), Json(request))
+            .await
+            .unwrap();
+        assert_eq!(response.messages.len(), RESTAKE_MSG_TYPES.len());
+        assert_eq!(response.messages[0]["grantee"], BOT);
+        let encoded =
+            crate::handlers::cosmos_tx::encode_messages(&response.messages, DELEGATOR).unwrap();
+        assert_eq!(encoded[0].type_url, "/cosmos.authz.v1beta1.MsgRevoke");
+
+        let request = RevokeGrantsRequest {
+            delegator_address: DELEGATOR.to_string(),
+            grantee: None,
+            msg_type_urls: Some(vec!["MsgDelegate".to_string()]),
+        };
+        let err = revoke_auto_compound(State(state), Json(request))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation { .. }));
+    }
 }
@@ -27,10 +27,14 @@ lazy_static! {
 }
 
 use cosmrs::proto::cosmos::bank::v1beta1::MsgSend;
-use cosmrs::proto::cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward;
+use cosmrs::proto::cosmos::distribution::v1beta1::{
+    MsgCommunityPoolSpend, MsgWithdrawDelegatorReward,
+};
 use cosmrs::proto::cosmos::gov::v1beta1::MsgVote;
+use cosmrs::proto::cosmos::params::v1beta1::ParameterChangeProposal;
 use cosmrs::proto::cosmos::staking::v1beta1::{MsgBeginRedelegate, MsgDelegate, MsgUndelegate};
-use cosmrs::proto::cosmwasm::wasm::v1::MsgExecuteContract;
+use cosmrs::proto::cosmos::upgrade::v1beta1::MsgSoftwareUpgrade;
+use cosmrs::proto::cosmwasm::wasm::v1::{MsgExecuteContract, MsgMigrateContract};
 use cosmrs::proto::ibc::applications::transfer::v1::MsgTransfer;
 use cosmrs::proto::ibc::core::channel::v1::MsgRecvPacket;
 
@@ -239,7 +243,11 @@ fn bech32_data_part(addr: &str) -> Option<&str> {
 /// Decode a protobuf message based on its type URL.
 /// Returns a JSON object with camelCase field names matching what the frontend expects.
 /// Returns None if the type is unknown or decoding fails.
-fn decode_message(type_url: &str, value_b64: &str) -> Option<serde_json::Value> {
+///
+/// Besides the user transaction types this also decodes the governance
+/// payloads proposals carry (upgrades, migrations, pool spends, legacy
+/// parameter changes) for the proposal detail view.
+pub fn decode_message(type_url: &str, value_b64: &str) -> Option<serde_json::Value> {
     let bytes = BASE64.decode(value_b64).ok()?;
 
     match type_url {
@@ -325,6 +333,47 @@ fn decode_message(type_url: &str, value_b64: &str) -> Option<serde_json::Value>
                 }))
             }))
         }
+        "/cosmos.upgrade.v1beta1.MsgSoftwareUpgrade" => {
+            let msg = MsgSoftwareUpgrade::decode(bytes.as_slice()).ok()?;
+            Some(serde_json::json!({
+                "authority": msg.authority,
+                "plan": msg.plan.map(|plan| serde_json::json!({
+                    "name": plan.name,
+                    "height": plan.height.to_string(),
+                    "info": plan.info
+                }))
+            }))
+        }
+        "/cosmwasm.wasm.v1.MsgMigrateContract" => {
+            let msg = MsgMigrateContract::decode(bytes.as_slice()).ok()?;
+            let msg_str = String::from_utf8(msg.msg).ok()?;
+            Some(serde_json::json!({
+                "sender": msg.sender,
+                "contract": msg.contract,
+                "codeId": msg.code_id.to_string(),
+                "msg": msg_str
+            }))
+        }
+        "/cosmos.distribution.v1beta1.MsgCommunityPoolSpend" => {
+            let msg = MsgCommunityPoolSpend::decode(bytes.as_slice()).ok()?;
+            Some(serde_json::json!({
+                "authority": msg.authority,
+                "recipient": msg.recipient,
+                "amount": coin_list(&msg.amount)
+            }))
+        }
+        "/cosmos.params.v1beta1.ParameterChangeProposal" => {
+            let msg = ParameterChangeProposal::decode(bytes.as_slice()).ok()?;
+            Some(serde_json::json!({
+                "title": msg.title,
+                "description": msg.description,
+                "changes": msg.changes.iter().map(|c| serde_json::json!({
+                    "subspace": c.subspace,
+                    "key": c.key,
+                    "value": c.value
+                })).collect::<Vec<_>>()
+            }))
+        }
         "/ibc.core.channel.v1.MsgRecvPacket" => {
             let msg = MsgRecvPacket::decode(bytes.as_slice()).ok()?;
             msg.packet.and_then(|pkt| {
@@ -430,6 +479,24 @@ mod tests {
         assert_eq!(data["validatorAddress"], "nolusvaloper1validator");
     }
 
+    #[test]
+    fn test_decode_msg_migrate_contract() {
+        let msg = MsgMigrateContract {
+            sender: "nolus1gov".to_string(),
+            contract: "nolus1leaser".to_string(),
+            code_id: 42,
+            msg: br#"{"migrate":{}}"#.to_vec(),
+        };
+        let mut buf = Vec::new();
+        msg.encode(&mut buf).unwrap();
+        let b64 = BASE64.encode(&buf);
+
+        let data = decode_message("/cosmwasm.wasm.v1.MsgMigrateContract", &b64).unwrap();
+        assert_eq!(data["contract"], "nolus1leaser");
+        assert_eq!(data["codeId"], "42");
+        assert_eq!(data["msg"], r#"{"migrate":{}}"#);
+    }
+
     #[test]
     fn test_decode_msg_execute_contract() {
         let msg = MsgExecuteContract {
@@ -13,13 +13,14 @@ use std::sync::Arc;
 use axum::{
     extract::{Path, Query, State},
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 use uuid::Uuid;
 
+use crate::admin_keys::AdminIdentity;
 use crate::error::AppError;
 use crate::translations::audit::AuditAction;
 use crate::translations::llm::{LlmClient, TranslationInput};
@@ -179,6 +180,7 @@ pub async fn list_missing(
 /// Generate AI translations for missing keys
 pub async fn generate_translations(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Json(request): Json<GenerateQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let storage = get_translation_storage(&state)?;
@@ -266,7 +268,13 @@ pub async fn generate_translations(
     // Record in audit log
     storage
         .audit_log()
-        .record_generate(None, &lang, total_keys, &batch_id, llm.model())
+        .record_generate(
+            Some(identity.name),
+            &lang,
+            total_keys,
+            &batch_id,
+            llm.model(),
+        )
         .await;
 
     info!(
@@ -332,12 +340,12 @@ pub async fn get_pending(
 /// Approve a pending translation
 pub async fn approve_pending(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, AppError> {
     let storage = get_translation_storage(&state)?;
 
-    // Admin auth uses API key only - no user identity available
-    let admin_user = None;
+    let admin_user = Some(identity.name);
 
     storage.approve_pending(&id, admin_user).await?;
 
@@ -348,13 +356,13 @@ pub async fn approve_pending(
 /// Reject a pending translation
 pub async fn reject_pending(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Path(id): Path<String>,
     Json(request): Json<RejectRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let storage = get_translation_storage(&state)?;
 
-    // Admin auth uses API key only - no user identity available
-    let admin_user = None;
+    let admin_user = Some(identity.name);
 
     storage
         .reject_pending(&id, &request.reason, admin_user)
@@ -367,13 +375,13 @@ pub async fn reject_pending(
 /// Edit and approve a pending translation
 pub async fn edit_pending(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Path(id): Path<String>,
     Json(request): Json<EditRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let storage = get_translation_storage(&state)?;
 
-    // Admin auth uses API key only - no user identity available
-    let admin_user = None;
+    let admin_user = Some(identity.name);
 
     storage
         .edit_pending(&id, &request.value, admin_user)
@@ -388,12 +396,12 @@ pub async fn edit_pending(
 /// Bulk approve multiple pending translations
 pub async fn approve_batch(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Json(request): Json<ApproveRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let storage = get_translation_storage(&state)?;
 
-    // Admin auth uses API key only - no user identity available
-    let admin_user = None;
+    let admin_user = Some(identity.name);
 
     let approved_count = storage
         .approve_pending_batch(&request.ids, admin_user)
@@ -428,13 +436,13 @@ pub async fn get_active(
 /// Directly edit an active translation
 pub async fn update_active(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Path((lang, key)): Path<(String, String)>,
     Json(request): Json<EditRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let storage = get_translation_storage(&state)?;
 
-    // Admin auth uses API key only - no user identity available
-    let admin_user = None;
+    let admin_user = Some(identity.name);
 
     // Decode the key (it may be URL encoded with dots as %2E)
     let decoded_key = urlencoding::decode(&key)
@@ -474,12 +482,12 @@ pub async fn list_languages(
 /// Add a new language
 pub async fn add_language(
     State(state): State<Arc<AppState>>,
+    Extension(identity): Extension<AdminIdentity>,
     Json(request): Json<AddLanguageRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let storage = get_translation_storage(&state)?;
 
-    // Admin auth uses API key only - no user identity available
-    let admin_user = None;
+    let admin_user = Some(identity.name);
 
     storage
         .add_language(
@@ -527,7 +535,7 @@ pub async fn get_audit_log(
     let entries = storage
         .audit_log()
         .query(query.lang.as_deref(), action, None, None, limit, 0)
-        .await;
+        .await?;
 
     Ok(Json(entries))
 }
@@ -551,7 +559,7 @@ pub async fn get_key_history(
     let history = storage
         .audit_log()
         .get_key_history(&lang, &decoded_key)
-        .await;
+        .await?;
 
     Ok(Json(history))
 }
@@ -0,0 +1,306 @@
+//! Shared helpers for server-built unsigned transactions
+//!
+//! Lease, earn, staking and governance builders return ready-to-sign Cosmos messages: the
+//! signer address is validated, tickers are resolved to their on-chain bank
+//! denom through the cached currencies, and a gas estimate is attached so the
+//! wallet does not have to guess.
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::error::AppError;
+use crate::handlers::currencies::CurrenciesResponse;
+use crate::handlers::fees::GasFeeConfigResponse;
+use crate::num_utils::u128_to_f64;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Gas estimate attached to an unsigned transaction
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GasEstimate {
+    /// Gas limit covering every message in the transaction
+    pub gas_limit: u64,
+    /// Fee for `gas_limit` in each accepted fee denom (minimal denomination)
+    pub fee_options: Vec<FeeCoin>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FeeCoin {
+    pub denom: String,
+    pub amount: String,
+}
+
+/// Message kinds with a known gas footprint.
+///
+/// The limits are conservative static values measured on mainnet; they leave
+/// headroom for the contract-side swaps a lease open or close triggers.
+#[derive(Debug, Clone, Copy)]
+pub enum MsgKind {
+    OpenLease,
+    WasmExecute,
+    Delegate,
+    Undelegate,
+    Redelegate,
+    WithdrawReward,
+    Vote,
+    Deposit,
+    SubmitProposal,
+    Grant,
+    Revoke,
+}
+
+impl MsgKind {
+    pub const fn gas_limit(self) -> u64 {
+        match self {
+            Self::OpenLease => 1_500_000,
+            Self::WasmExecute => 800_000,
+            Self::Delegate => 250_000,
+            Self::Undelegate => 300_000,
+            Self::Redelegate => 350_000,
+            Self::WithdrawReward => 150_000,
+            Self::Vote => 150_000,
+            Self::Deposit => 200_000,
+            Self::SubmitProposal => 500_000,
+            Self::Grant => 150_000,
+            Self::Revoke => 120_000,
+        }
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Validate the signer of a transaction (must be a `nolus1…` address)
+pub fn validate_sender(sender: &str) -> Result<(), AppError> {
+    crate::validation::validate_nolus_address(sender, "sender")
+}
+
+/// Parse a user-supplied base-unit amount, rejecting zero, negatives and
+/// anything that is not an integer.
+pub fn parse_positive_amount(amount: &str, field_name: &str) -> Result<u128, AppError> {
+    match amount.parse::<u128>() {
+        Ok(value) if value > 0 => Ok(value),
+        _ => Err(AppError::Validation {
+            message: format!("{} must be a positive base-unit integer", field_name),
+            field: Some(field_name.to_string()),
+            details: None,
+        }),
+    }
+}
+
+/// Resolve `TICKER@PROTOCOL` to the bank denom held on Nolus
+pub fn resolve_denom(
+    currencies: &CurrenciesResponse,
+    protocol: &str,
+    ticker: &str,
+    field_name: &str,
+) -> Result<String, AppError> {
+    currencies
+        .currencies
+        .get(&format!("{}@{}", ticker, protocol))
+        .map(|c| c.bank_symbol.clone())
+        .ok_or_else(|| AppError::Validation {
+            message: format!("Unknown currency {} for protocol {}", ticker, protocol),
+            field: Some(field_name.to_string()),
+            details: None,
+        })
+}
+
+/// Resolve the LPN bank denom of a protocol
+pub fn resolve_lpn_denom(
+    currencies: &CurrenciesResponse,
+    protocol: &str,
+) -> Result<String, AppError> {
+    currencies
+        .lpn
+        .iter()
+        .find(|c| c.protocol == protocol)
+        .map(|c| c.bank_symbol.clone())
+        .ok_or_else(|| AppError::NotFound {
+            resource: format!("LPN for protocol {}", protocol),
+        })
+}
+
+/// Resolve the native (staking) bank denom
+pub fn resolve_native_denom(currencies: &CurrenciesResponse) -> Result<String, AppError> {
+    currencies
+        .currencies
+        .values()
+        .find(|c| c.native)
+        .map(|c| c.bank_symbol.clone())
+        .ok_or_else(|| AppError::NotFound {
+            resource: "Native currency".to_string(),
+        })
+}
+
+/// Build the gas estimate for a transaction made of `kinds`.
+///
+/// Fees are quoted for every denom accepted by the tax module; without a
+/// cached gas config only the gas limit is returned.
+pub fn estimate_gas(kinds: &[MsgKind], fee_config: Option<&GasFeeConfigResponse>) -> GasEstimate {
+    let gas_limit: u64 = kinds.iter().map(|kind| kind.gas_limit()).sum();
+    GasEstimate {
+        gas_limit,
+        fee_options: fee_options(gas_limit, fee_config),
+    }
+}
+
+/// Fee for `gas_limit` in each accepted fee denom, sorted by denom
+pub fn fee_options(gas_limit: u64, fee_config: Option<&GasFeeConfigResponse>) -> Vec<FeeCoin> {
+    let gas = u128_to_f64(u128::from(gas_limit));
+
+    let mut options: Vec<FeeCoin> = fee_config
+        .map(|config| {
+            config
+                .gas_prices
+                .iter()
+                .filter_map(|(denom, price)| {
+                    let price: f64 = price.parse().ok()?;
+                    Some(FeeCoin {
+                        denom: denom.clone(),
+                        amount: format!("{:.0}", (gas * price).ceil()),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    options.sort_by(|a, b| a.denom.cmp(&b.denom));
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::currencies::CurrencyInfo;
+    use std::collections::HashMap;
+
+    fn make_currency(
+        ticker: &str,
+        protocol: &str,
+        bank_symbol: &str,
+        native: bool,
+    ) -> CurrencyInfo {
+        CurrencyInfo {
+            key: format!("{}@{}", ticker, protocol),
+            ticker: ticker.to_string(),
+            symbol: ticker.to_string(),
+            name: ticker.to_string(),
+            short_name: ticker.to_string(),
+            decimal_digits: 6,
+            bank_symbol: bank_symbol.to_string(),
+            dex_symbol: bank_symbol.to_string(),
+            icon: String::new(),
+            native,
+            coingecko_id: None,
+            protocol: protocol.to_string(),
+            group: if native { "native" } else { "lpn" }.to_string(),
+            is_active: true,
+        }
+    }
+
+    fn make_currencies() -> CurrenciesResponse {
+        let usdc = make_currency(
+            "USDC_NOBLE",
+            "OSMOSIS-OSMOSIS-USDC_NOBLE",
+            "ibc/USDC",
+            false,
+        );
+        let nls = make_currency("NLS", "OSMOSIS-OSMOSIS-USDC_NOBLE", "unls", true);
+        let mut currencies = HashMap::new();
+        currencies.insert(usdc.key.clone(), usdc.clone());
+        currencies.insert(nls.key.clone(), nls);
+        CurrenciesResponse {
+            currencies,
+            lpn: vec![usdc],
+            lease_currencies: vec![],
+            map: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn resolve_denom_uses_bank_symbol() {
+        let currencies = make_currencies();
+        assert_eq!(
+            resolve_denom(
+                &currencies,
+                "OSMOSIS-OSMOSIS-USDC_NOBLE",
+                "USDC_NOBLE",
+                "ticker"
+            )
+            .unwrap(),
+            "ibc/USDC"
+        );
+    }
+
+    #[test]
+    fn resolve_denom_unknown_ticker_is_validation_error() {
+        let currencies = make_currencies();
+        let err = resolve_denom(&currencies, "OSMOSIS-OSMOSIS-USDC_NOBLE", "ATOM", "ticker")
+            .expect_err("unknown ticker");
+        assert!(matches!(err, AppError::Validation { field: Some(f), .. } if f == "ticker"));
+    }
+
+    #[test]
+    fn resolve_lpn_and_native_denoms() {
+        let currencies = make_currencies();
+        assert_eq!(
+            resolve_lpn_denom(&currencies, "OSMOSIS-OSMOSIS-USDC_NOBLE").unwrap(),
+            "ibc/USDC"
+        );
+        assert!(matches!(
+            resolve_lpn_denom(&currencies, "NEUTRON-ASTROPORT-USDC_NOBLE"),
+            Err(AppError::NotFound { .. })
+        ));
+        assert_eq!(resolve_native_denom(&currencies).unwrap(), "unls");
+    }
+
+    #[test]
+    fn estimate_gas_sums_limits_and_prices_each_denom() {
+        let mut gas_prices = HashMap::new();
+        gas_prices.insert("unls".to_string(), "0.25".to_string());
+        gas_prices.insert("ibc/USDC".to_string(), "0.5".to_string());
+        gas_prices.insert("ibc/BROKEN".to_string(), "n/a".to_string());
+        let config = GasFeeConfigResponse {
+            gas_prices,
+            gas_multiplier: 3.5,
+        };
+
+        let estimate = estimate_gas(
+            &[MsgKind::WithdrawReward, MsgKind::WithdrawReward],
+            Some(&config),
+        );
+
+        assert_eq!(estimate.gas_limit, 300_000);
+        assert_eq!(
+            estimate.fee_options,
+            vec![
+                FeeCoin {
+                    denom: "ibc/USDC".to_string(),
+                    amount: "150000".to_string(),
+                },
+                FeeCoin {
+                    denom: "unls".to_string(),
+                    amount: "75000".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn estimate_gas_without_fee_config_has_no_fee_options() {
+        let estimate = estimate_gas(&[MsgKind::OpenLease], None);
+        assert_eq!(estimate.gas_limit, MsgKind::OpenLease.gas_limit());
+        assert!(estimate.fee_options.is_empty());
+    }
+
+    #[test]
+    fn parse_positive_amount_rejects_zero_negative_and_fractions() {
+        assert_eq!(parse_positive_amount("150", "amount").unwrap(), 150);
+        assert!(parse_positive_amount("0", "amount").is_err());
+        assert!(parse_positive_amount("-1", "amount").is_err());
+        assert!(parse_positive_amount("1.5", "amount").is_err());
+    }
+}
@@ -0,0 +1,696 @@
+//! Validator scoring and delegation recommendations
+//!
+//! `refresh::refresh_validator_scores` derives per-validator metrics the
+//! staking module does not report directly: uptime over the slashing liveness
+//! window, participation in governance votes observed during their voting
+//! periods, commission changes seen across refreshes, and each validator's
+//! share of bonded voting power. Participation and commission history build up
+//! in the cache, so they start empty after a restart.
+//!
+//! Endpoints:
+//! - GET /api/staking/validators/scores - Per-validator scores
+//! - GET /api/staking/validators/recommend?amount=... - Suggested delegation split
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::error::AppError;
+use crate::handlers::staking::{Validator, ValidatorStatus};
+use crate::handlers::tx_builder;
+use crate::num_utils::{f64_ceil_to_u64, u128_to_f64};
+use crate::AppState;
+
+/// Proposals whose votes count towards participation (most recent first out)
+pub const MAX_TRACKED_PROPOSALS: usize = 10;
+
+/// Commission changes kept per validator
+pub const MAX_COMMISSION_CHANGES: usize = 20;
+
+/// Uptime below which a validator is never recommended
+const MIN_RECOMMENDED_UPTIME: f64 = 0.95;
+
+/// Commission at or above which the commission component scores zero
+const COMMISSION_SCORE_CAP: f64 = 0.2;
+
+/// Validators in a recommendation when the request does not say
+const DEFAULT_RECOMMENDATION_COUNT: usize = 5;
+
+const MAX_RECOMMENDATION_COUNT: usize = 20;
+
+/// Score weights; a component without data drops out and the rest rescale
+const UPTIME_WEIGHT: f64 = 0.4;
+const PARTICIPATION_WEIGHT: f64 = 0.25;
+const COMMISSION_WEIGHT: f64 = 0.2;
+const STABILITY_WEIGHT: f64 = 0.15;
+
+// ============================================================================
+// Cached State
+// ============================================================================
+
+/// Scores plus the history they are derived from, kept across refreshes.
+#[derive(Debug, Clone, Default)]
+pub struct ValidatorScoreState {
+    pub scores: Vec<ValidatorScore>,
+    pub signed_blocks_window: u64,
+    pub nakamoto_coefficient: u32,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Proposals whose voting period was observed, oldest first
+    pub tracked_proposals: Vec<String>,
+    /// Operator addresses seen voting, per tracked proposal
+    pub votes: HashMap<String, HashSet<String>>,
+    /// Commission changes per operator address, oldest first
+    pub commission_history: HashMap<String, Vec<CommissionChange>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CommissionChange {
+    pub at: DateTime<Utc>,
+    pub rate: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ValidatorScore {
+    pub operator_address: String,
+    pub moniker: String,
+    pub status: ValidatorStatus,
+    pub jailed: bool,
+    pub tombstoned: bool,
+    pub commission_rate: String,
+    /// Commission changes observed by this backend, oldest first
+    pub commission_history: Vec<CommissionChange>,
+    /// Signed share of the liveness window (0-1); `None` without a signing record
+    pub uptime: Option<f64>,
+    pub missed_blocks: Option<u64>,
+    /// Share of tracked proposals the validator voted on (0-1); `None` before
+    /// any proposal has been tracked
+    pub governance_participation: Option<f64>,
+    pub proposals_voted: u32,
+    pub proposals_tracked: u32,
+    /// Share of bonded tokens (0-1)
+    pub voting_power_share: f64,
+    /// Share held by this validator and every larger one (0-1)
+    pub cumulative_share: f64,
+    /// Among the largest validators that together exceed 1/3 of voting power
+    pub superminority: bool,
+    /// Composite score (0-100)
+    pub score: f64,
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ValidatorScoresResponse {
+    /// Blocks in the slashing liveness window uptime is measured over
+    pub signed_blocks_window: u64,
+    /// Validators needed to exceed 1/3 of bonded voting power
+    pub nakamoto_coefficient: u32,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Highest score first
+    pub validators: Vec<ValidatorScore>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RecommendQuery {
+    /// Amount to delegate (minimal denomination)
+    pub amount: String,
+    /// Validators to split across (default 5, max 20)
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DelegationAllocation {
+    pub validator_address: String,
+    pub moniker: String,
+    pub score: f64,
+    pub commission_rate: String,
+    pub voting_power_share: f64,
+    /// Amount to delegate to this validator (minimal denomination)
+    pub amount: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DelegationRecommendation {
+    pub amount: String,
+    pub denom: String,
+    /// Split weighted by score; the amounts sum to `amount`
+    pub allocations: Vec<DelegationAllocation>,
+    /// Superminority validators left out to spread voting power
+    pub excluded_superminority: u32,
+    pub nakamoto_coefficient: u32,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Get validator scores
+///
+/// Returns every validator's uptime, governance participation, commission
+/// history and voting-power concentration with the composite score, highest
+/// first.
+#[utoipa::path(
+    get,
+    path = "/api/staking/validators/scores",
+    tag = "staking",
+    responses(
+        (status = 200, description = "Validator scores", body = ValidatorScoresResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn get_validator_scores(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<ValidatorScoresResponse>, AppError> {
+    let scores = state
+        .data_cache
+        .validator_scores
+        .load_or_unavailable("Validator scores")?;
+
+    Ok(Json(ValidatorScoresResponse {
+        signed_blocks_window: scores.signed_blocks_window,
+        nakamoto_coefficient: scores.nakamoto_coefficient,
+        updated_at: scores.updated_at,
+        validators: scores.scores,
+    }))
+}
+
+/// Recommend a delegation split
+///
+/// Splits `amount` across the best-scoring active validators outside the
+/// superminority, weighted by score, so new stake spreads voting power
+/// instead of piling onto the largest validators. Validators that are
+/// jailed, tombstoned or below 95% uptime are never recommended.
+#[utoipa::path(
+    get,
+    path = "/api/staking/validators/recommend",
+    tag = "staking",
+    params(RecommendQuery),
+    responses(
+        (status = 200, description = "Suggested delegation split", body = DelegationRecommendation),
+        (status = 400, description = "Invalid amount or count", body = crate::error::ErrorResponse),
+        (status = 503, description = "Cache not yet populated", body = crate::error::ErrorResponse),
+    ),
+)]
+pub async fn recommend_delegations(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<RecommendQuery>,
+) -> Result<Json<DelegationRecommendation>, AppError> {
+    debug!("Recommending delegation split for {}", query.amount);
+
+    let amount = tx_builder::parse_positive_amount(&query.amount, "amount")?;
+    let count = query.count.unwrap_or(DEFAULT_RECOMMENDATION_COUNT);
+    if count == 0 || count > MAX_RECOMMENDATION_COUNT {
+        return Err(AppError::Validation {
+            message: format!("count must be between 1 and {}", MAX_RECOMMENDATION_COUNT),
+            field: Some("count".to_string()),
+            details: None,
+        });
+    }
+
+    let scores = state
+        .data_cache
+        .validator_scores
+        .load_or_unavailable("Validator scores")?;
+    let currencies = state
+        .data_cache
+        .currencies
+        .load_or_unavailable("Currencies")?;
+    let denom = tx_builder::resolve_native_denom(&currencies)?;
+
+    let allocations = recommend(&scores.scores, amount, count);
+    if allocations.is_empty() {
+        return Err(AppError::ServiceUnavailable {
+            message: "No validator currently qualifies for a recommendation".to_string(),
+        });
+    }
+    let excluded_superminority = u32::try_from(
+        scores
+            .scores
+            .iter()
+            .filter(|s| s.superminority && is_active(s))
+            .count(),
+    )
+    .unwrap_or(u32::MAX);
+
+    Ok(Json(DelegationRecommendation {
+        amount: amount.to_string(),
+        denom,
+        allocations,
+        excluded_superminority,
+        nakamoto_coefficient: scores.nakamoto_coefficient,
+    }))
+}
+
+// ============================================================================
+// Scoring
+// ============================================================================
+
+/// Per-refresh chain inputs for [`compute_scores`]
+pub struct ScoringInputs<'a> {
+    pub validators: &'a [Validator],
+    /// Consensus (`valcons`) address per operator address
+    pub consensus_addresses: &'a HashMap<String, String>,
+    pub signing_infos: &'a [crate::external::chain::SigningInfo],
+    pub signed_blocks_window: u64,
+}
+
+/// Score every validator from this refresh's chain data plus the history
+/// accumulated in `state`. Returns the scores (highest first) and the
+/// Nakamoto coefficient.
+pub fn compute_scores(
+    inputs: &ScoringInputs<'_>,
+    state: &ValidatorScoreState,
+) -> (Vec<ValidatorScore>, u32) {
+    let signing: HashMap<&str, &crate::external::chain::SigningInfo> = inputs
+        .signing_infos
+        .iter()
+        .map(|info| (info.address.as_str(), info))
+        .collect();
+    let shares = voting_power_shares(inputs.validators);
+    let nakamoto_coefficient =
+        u32::try_from(shares.values().filter(|s| s.superminority).count()).unwrap_or(u32::MAX);
+    let proposals_tracked = u32::try_from(state.tracked_proposals.len()).unwrap_or(u32::MAX);
+
+    let mut scores: Vec<ValidatorScore> = inputs
+        .validators
+        .iter()
+        .map(|v| {
+            let info = inputs
+                .consensus_addresses
+                .get(&v.operator_address)
+                .and_then(|address| signing.get(address.as_str()));
+            let missed_blocks = info.map(|i| i.missed_blocks_counter.parse().unwrap_or(0));
+            let uptime = missed_blocks
+                .filter(|_| inputs.signed_blocks_window > 0)
+                .map(|missed| {
+                    let missed = u128_to_f64(u128::from(missed));
+                    let window = u128_to_f64(u128::from(inputs.signed_blocks_window));
+                    (1.0 - missed / window).clamp(0.0, 1.0)
+                });
+            let tombstoned = info.is_some_and(|i| i.tombstoned);
+
+            let proposals_voted = u32::try_from(
+                state
+                    .tracked_proposals
+                    .iter()
+                    .filter(|id| {
+                        state
+                            .votes
+                            .get(*id)
+                            .is_some_and(|voters| voters.contains(&v.operator_address))
+                    })
+                    .count(),
+            )
+            .unwrap_or(u32::MAX);
+            let governance_participation = (proposals_tracked > 0)
+                .then(|| f64::from(proposals_voted) / f64::from(proposals_tracked));
+
+            let commission_history = state
+                .commission_history
+                .get(&v.operator_address)
+                .cloned()
+                .unwrap_or_default();
+            let share = shares.get(&v.operator_address).copied().unwrap_or_default();
+
+            let score = if v.jailed || tombstoned {
+                0.0
+            } else {
+                composite_score(
+                    uptime,
+                    governance_participation,
+                    v.commission_rate.parse().unwrap_or(0.0),
+                    commission_increased(&commission_history),
+                )
+            };
+
+            ValidatorScore {
+                operator_address: v.operator_address.clone(),
+                moniker: v.moniker.clone(),
+                status: v.status.clone(),
+                jailed: v.jailed,
+                tombstoned,
+                commission_rate: v.commission_rate.clone(),
+                commission_history,
+                uptime,
+                missed_blocks,
+                governance_participation,
+                proposals_voted,
+                proposals_tracked,
+                voting_power_share: share.share,
+                cumulative_share: share.cumulative,
+                superminority: share.superminority,
+                score,
+            }
+        })
+        .collect();
+
+    scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+    (scores, nakamoto_coefficient)
+}
+
+/// Weighted 0-100 score. Missing uptime or participation drops out of the
+/// weighting instead of counting as zero.
+fn composite_score(
+    uptime: Option<f64>,
+    participation: Option<f64>,
+    commission: f64,
+    commission_increased: bool,
+) -> f64 {
+    let commission_component = 1.0 - (commission / COMMISSION_SCORE_CAP).clamp(0.0, 1.0);
+    let stability_component = if commission_increased { 0.0 } else { 1.0 };
+    let components = [
+        (uptime, UPTIME_WEIGHT),
+        (participation, PARTICIPATION_WEIGHT),
+        (Some(commission_component), COMMISSION_WEIGHT),
+        (Some(stability_component), STABILITY_WEIGHT),
+    ];
+
+    let (weighted, total_weight) = components
+        .iter()
+        .filter_map(|(value, weight)| value.map(|v| (v * weight, *weight)))
+        .fold((0.0, 0.0), |(sum, total), (v, w)| (sum + v, total + w));
+    if total_weight > 0.0 {
+        weighted / total_weight * 100.0
+    } else {
+        0.0
+    }
+}
+
+fn commission_increased(history: &[CommissionChange]) -> bool {
+    history.windows(2).any(|pair| {
+        let before: f64 = pair[0].rate.parse().unwrap_or(0.0);
+        let after: f64 = pair[1].rate.parse().unwrap_or(0.0);
+        after > before
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct PowerShare {
+    share: f64,
+    cumulative: f64,
+    superminority: bool,
+}
+
+/// Voting-power share of each bonded validator, largest first. Validators
+/// outside the active set hold no voting power.
+fn voting_power_shares(validators: &[Validator]) -> HashMap<String, PowerShare> {
+    let mut bonded: Vec<(&str, u128)> = validators
+        .iter()
+        .filter(|v| matches!(v.status, ValidatorStatus::Bonded))
+        .map(|v| (v.operator_address.as_str(), v.tokens.parse().unwrap_or(0)))
+        .collect();
+    bonded.sort_by(|a, b| b.1.cmp(&a.1));
+    let total = u128_to_f64(bonded.iter().map(|(_, tokens)| tokens).sum());
+
+    let mut shares = HashMap::new();
+    if total <= 0.0 {
+        return shares;
+    }
+    let mut cumulative = 0.0;
+    for (address, tokens) in bonded {
+        let share = u128_to_f64(tokens) / total;
+        let superminority = cumulative < 1.0 / 3.0;
+        cumulative += share;
+        shares.insert(
+            address.to_string(),
+            PowerShare {
+                share,
+                cumulative,
+                superminority,
+            },
+        );
+    }
+    shares
+}
+
+fn is_active(score: &ValidatorScore) -> bool {
+    matches!(score.status, ValidatorStatus::Bonded) && !score.jailed && !score.tombstoned
+}
+
+/// Split `amount` across the top `count` eligible validators by score.
+/// Rounding dust goes to the best-scoring validator.
+fn recommend(scores: &[ValidatorScore], amount: u128, count: usize) -> Vec<DelegationAllocation> {
+    let mut eligible: Vec<&ValidatorScore> = scores
+        .iter()
+        .filter(|s| is_active(s) && !s.superminority)
+        .filter(|s| s.uptime.is_some_and(|u| u >= MIN_RECOMMENDED_UPTIME))
+        .filter(|s| s.score > 0.0)
+        .collect();
+    eligible.sort_by(|a, b| b.score.total_cmp(&a.score));
+    eligible.truncate(count);
+
+    // Weights in hundredths of a point keep the split in integer math
+    let weights: Vec<u128> = eligible
+        .iter()
+        .map(|s| u128::from(f64_ceil_to_u64(s.score * 100.0)))
+        .collect();
+    let total_weight: u128 = weights.iter().sum();
+    if total_weight == 0 {
+        return Vec::new();
+    }
+
+    let mut amounts: Vec<u128> = weights
+        .iter()
+        .map(|weight| {
+            amount
+                .checked_mul(*weight)
+                .map_or(amount / total_weight * weight, |v| v / total_weight)
+        })
+        .collect();
+    let allocated: u128 = amounts.iter().sum();
+    if let Some(first) = amounts.first_mut() {
+        *first += amount.saturating_sub(allocated);
+    }
+
+    eligible
+        .into_iter()
+        .zip(amounts)
+        .filter(|(_, amount)| *amount > 0)
+        .map(|(s, amount)| DelegationAllocation {
+            validator_address: s.operator_address.clone(),
+            moniker: s.moniker.clone(),
+            score: s.score,
+            commission_rate: s.commission_rate.clone(),
+            voting_power_share: s.voting_power_share,
+            amount: amount.to_string(),
+        })
+        .collect()
+}
+
+// ============================================================================
+// History Tracking
+// ============================================================================
+
+/// Append `rate` when it differs from the last recorded one
+pub fn record_commission(history: &mut Vec<CommissionChange>, rate: &str, now: DateTime<Utc>) {
+    if history.last().is_some_and(|last| last.rate == rate) {
+        return;
+    }
+    history.push(CommissionChange {
+        at: now,
+        rate: rate.to_string(),
+    });
+    if history.len() > MAX_COMMISSION_CHANGES {
+        history.remove(0);
+    }
+}
+
+/// Start tracking proposals that entered their voting period, dropping the
+/// oldest (and their votes) past [`MAX_TRACKED_PROPOSALS`].
+pub fn track_proposals(state: &mut ValidatorScoreState, voting_ids: &[String]) {
+    for id in voting_ids {
+        if !state.tracked_proposals.contains(id) {
+            state.tracked_proposals.push(id.clone());
+        }
+    }
+    while state.tracked_proposals.len() > MAX_TRACKED_PROPOSALS {
+        let dropped = state.tracked_proposals.remove(0);
+        state.votes.remove(&dropped);
+    }
+}
+
+// ============================================================================
+// Address Derivation
+// ============================================================================
+
+/// Consensus address (`nolusvalcons1...`) of an ed25519 consensus pubkey
+/// (`{"@type": ".../PubKey", "key": "<base64>"}`): the first 20 bytes of the
+/// key's SHA-256.
+pub fn consensus_address(pubkey: &serde_json::Value) -> Option<String> {
+    let key = pubkey.get("key")?.as_str()?;
+    let bytes = base64::engine::general_purpose::STANDARD.decode(key).ok()?;
+    let digest = Sha256::digest(&bytes);
+    let hrp = bech32::Hrp::parse("nolusvalcons").ok()?;
+    bech32::encode::<bech32::Bech32>(hrp, digest.get(..20)?).ok()
+}
+
+/// Account address (`nolus1...`) that votes on behalf of a validator operator
+pub fn operator_account_address(operator_address: &str) -> Option<String> {
+    let (_, data) = bech32::decode(operator_address).ok()?;
+    let hrp = bech32::Hrp::parse("nolus").ok()?;
+    bech32::encode::<bech32::Bech32>(hrp, &data).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::chain::SigningInfo;
+
+    fn validator(address: &str, tokens: &str, commission: &str) -> Validator {
+        Validator {
+            operator_address: address.to_string(),
+            moniker: address.to_uppercase(),
+            identity: None,
+            website: None,
+            details: None,
+            commission_rate: commission.to_string(),
+            max_commission_rate: "0.2".to_string(),
+            max_commission_change_rate: "0.01".to_string(),
+            tokens: tokens.to_string(),
+            delegator_shares: tokens.to_string(),
+            unbonding_height: "0".to_string(),
+            unbonding_time: "1970-01-01T00:00:00Z".to_string(),
+            status: ValidatorStatus::Bonded,
+            jailed: false,
+        }
+    }
+
+    fn signing(address: &str, missed: &str) -> SigningInfo {
+        SigningInfo {
+            address: format!("cons-{}", address),
+            missed_blocks_counter: missed.to_string(),
+            tombstoned: false,
+            jailed_until: None,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_voting_power_superminority() {
+        let validators = vec![
+            validator("a", "500", "0.05"),
+            validator("b", "300", "0.05"),
+            validator("c", "200", "0.05"),
+        ];
+        let shares = voting_power_shares(&validators);
+        // "a" alone exceeds 1/3, so it is the whole superminority
+        assert!(shares["a"].superminority);
+        assert!(!shares["b"].superminority);
+        assert!((shares["b"].cumulative - 0.8).abs() < 1e-9);
+        assert!((shares["c"].share - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_composite_score_renormalizes_missing_components() {
+        // Perfect uptime and zero commission without participation data
+        assert!((composite_score(Some(1.0), None, 0.0, false) - 100.0).abs() < 1e-9);
+        // Commission at the cap and a past increase zero their components
+        let score = composite_score(Some(1.0), Some(1.0), 0.2, true);
+        assert!((score - 65.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compute_scores_and_recommend() {
+        let validators = vec![
+            validator("a", "500", "0.05"),
+            validator("b", "300", "0.05"),
+            validator("c", "200", "0.10"),
+        ];
+        let consensus: HashMap<String, String> = ["a", "b", "c"]
+            .iter()
+            .map(|v| (v.to_string(), format!("cons-{}", v)))
+            .collect();
+        let infos = vec![signing("a", "0"), signing("b", "100"), signing("c", "0")];
+
+        let mut state = ValidatorScoreState::default();
+        track_proposals(&mut state, &["1".to_string(), "2".to_string()]);
+        state.votes.insert(
+            "1".to_string(),
+            HashSet::from(["b".to_string(), "c".to_string()]),
+        );
+        state
+            .votes
+            .insert("2".to_string(), HashSet::from(["c".to_string()]));
+
+        let (scores, nakamoto) = compute_scores(
+            &ScoringInputs {
+                validators: &validators,
+                consensus_addresses: &consensus,
+                signing_infos: &infos,
+                signed_blocks_window: 10_000,
+            },
+            &state,
+        );
+        assert_eq!(nakamoto, 1);
+        let b = scores.iter().find(|s| s.operator_address == "b").unwrap();
+        assert!((b.uptime.unwrap() - 0.99).abs() < 1e-9);
+        assert_eq!(b.governance_participation, Some(0.5));
+        assert_eq!(b.proposals_voted, 1);
+
+        let allocations = recommend(&scores, 1_000_001, 5);
+        // "a" is in the superminority and is left out
+        assert_eq!(allocations.len(), 2);
+        assert!(allocations.iter().all(|a| a.validator_address != "a"));
+        let total: u128 = allocations
+            .iter()
+            .map(|a| a.amount.parse::<u128>().unwrap())
+            .sum();
+        assert_eq!(total, 1_000_001);
+    }
+
+    #[test]
+    fn test_record_commission_only_on_change() {
+        let mut history = Vec::new();
+        record_commission(&mut history, "0.05", now());
+        record_commission(&mut history, "0.05", now());
+        record_commission(&mut history, "0.07", now());
+        assert_eq!(history.len(), 2);
+        assert!(commission_increased(&history));
+    }
+
+    #[test]
+    fn test_track_proposals_drops_oldest() {
+        let mut state = ValidatorScoreState::default();
+        let ids: Vec<String> = (0..=MAX_TRACKED_PROPOSALS).map(|i| i.to_string()).collect();
+        state.votes.insert("0".to_string(), HashSet::new());
+        track_proposals(&mut state, &ids);
+        assert_eq!(state.tracked_proposals.len(), MAX_TRACKED_PROPOSALS);
+        assert_eq!(state.tracked_proposals[0], "1");
+        assert!(!state.votes.contains_key("0"));
+    }
+
+    #[test]
+    fn test_address_derivation() {
+        let account = "nolus1ncc58ptqrkd7r7uk60dx4eufvvqf2edhtktv0q";
+        let (_, data) = bech32::decode(account).unwrap();
+        let valoper =
+            bech32::encode::<bech32::Bech32>(bech32::Hrp::parse("nolusvaloper").unwrap(), &data)
+                .unwrap();
+        assert_eq!(operator_account_address(&valoper).as_deref(), Some(account));
+
+        let pubkey = serde_json::json!({
+            "@type": "/cosmos.crypto.ed25519.PubKey",
+            "key": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="
+        });
+        let address = consensus_address(&pubkey).unwrap();
+        assert!(address.starts_with("nolusvalcons1"));
+        assert!(consensus_address(&serde_json::json!({})).is_none());
+    }
+}
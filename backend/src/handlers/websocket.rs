@@ -4,9 +4,15 @@
 //! - prices: Real-time price updates for all currencies
 //! - balances: Balance updates for specific addresses
 //! - leases: Lease state changes for a user
-//! - tx_status: Transaction confirmation status
+//! - tx_status: Transaction confirmation status (hashes relayed through
+//!   `/api/tx/broadcast` are tracked automatically)
 //! - skip_tx: Cross-chain transaction tracking
 //! - earn: Earn position updates for a user
+//! - lease_alerts: Lease health alerts (LTV warnings, approaching liquidation,
+//!   partial liquidation, imminent overdue collection) for a user
+//! - portfolio: Net-worth breakdown for a user and its linked Solana wallets
+//! - governance: Proposal status changes (all proposals, or the given ids)
+//! - staking: Unbonding and redelegation entries of a user reaching completion
 
 use axum::{
     extract::{
@@ -26,7 +32,12 @@ use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
+use crate::config_store::gated_types::LeaseAlertsConfig;
 use crate::handlers::currencies;
+use crate::handlers::leases::{LeaseConfigResponse, LeaseMonitorInfo, LiquidationDistance};
+use crate::handlers::portfolio::{self, PortfolioResponse};
+use crate::handlers::staking::{self, ScheduleEntry};
+use crate::notifications::{NotificationDraft, NotificationKind};
 use crate::AppState;
 
 // ============================================================================
@@ -98,6 +109,66 @@ pub enum ServerMessage {
         positions: Vec<EarnPositionInfo>,
         total_deposited_usd: String,
     },
+    /// Lease LTV crossed a warning threshold
+    LeaseLtvWarning {
+        owner: String,
+        lease_address: String,
+        protocol: String,
+        /// 1-based index of the highest threshold crossed
+        level: u8,
+        /// Threshold crossed (LTV percent)
+        threshold: f64,
+        distance: LiquidationDistance,
+    },
+    /// Price moved within the configured distance of the liquidation price
+    LeaseLiquidationApproaching {
+        owner: String,
+        lease_address: String,
+        protocol: String,
+        distance: LiquidationDistance,
+    },
+    /// A liquidation started on a lease that stays open
+    LeasePartialLiquidation {
+        owner: String,
+        lease_address: String,
+        protocol: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cause: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        distance: Option<LiquidationDistance>,
+    },
+    /// Overdue interest and margin are about to be collected from the position
+    LeaseOverdueCollection {
+        owner: String,
+        lease_address: String,
+        protocol: String,
+        collect_in_secs: u64,
+        /// Overdue amount to be collected (minimal denomination of `ticker`)
+        amount: String,
+        ticker: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        distance: Option<LiquidationDistance>,
+    },
+    /// Portfolio net worth changed
+    PortfolioUpdate {
+        portfolio: PortfolioResponse,
+        timestamp: String,
+    },
+    /// A governance proposal entered a new status
+    ProposalStatusChanged {
+        proposal_id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// `None` when the proposal is new since the previous refresh
+        previous_status: Option<String>,
+        status: String,
+    },
+    /// An unbonding (funds now liquid) or redelegation entry completed
+    StakingEntryMatured {
+        address: String,
+        entry: ScheduleEntry,
+        timestamp: String,
+    },
 }
 
 /// Earn position info for WebSocket updates
@@ -117,6 +188,9 @@ pub enum TxStatusValue {
     Pending,
     Success,
     Failed,
+    /// Not seen in a block within the confirmation window. The tx may still
+    /// land; a final `success`/`failed` follows if it does.
+    Timeout,
 }
 
 // ============================================================================
@@ -140,6 +214,17 @@ pub enum Subscription {
     },
     /// Subscribe to earn position updates
     Earn { address: String },
+    /// Subscribe to lease health alerts for an address
+    LeaseAlerts { address: String },
+    /// Subscribe to the portfolio of an address and its linked Solana wallets
+    Portfolio {
+        address: String,
+        solana_addresses: Vec<String>,
+    },
+    /// Subscribe to status changes of the given proposals (all when empty)
+    Governance { proposal_ids: Vec<String> },
+    /// Subscribe to unbonding/redelegation maturity for an address
+    Staking { address: String },
 }
 
 impl Subscription {
@@ -231,6 +316,88 @@ impl Subscription {
                     .to_string();
                 Ok(Subscription::Earn { address })
             }
+            "lease_alerts" => {
+                let address = params
+                    .get("address")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing 'address' parameter")?
+                    .to_string();
+                if !crate::validation::is_valid_nolus_address(&address) {
+                    return Err(
+                        "Lease alerts subscription requires a valid Nolus address".to_string()
+                    );
+                }
+                Ok(Subscription::LeaseAlerts { address })
+            }
+            "portfolio" => {
+                let address = params
+                    .get("address")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing 'address' parameter")?
+                    .to_string();
+                if !crate::validation::is_valid_nolus_address(&address) {
+                    return Err("Portfolio subscription requires a valid Nolus address".to_string());
+                }
+                let mut solana_addresses: Vec<String> = params
+                    .get("solana_addresses")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(|v| v.as_str().map(String::from))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                // Order-insensitive so unsubscribe matches the subscribe
+                solana_addresses.sort();
+                solana_addresses.dedup();
+                portfolio::validate_linked_solana(&solana_addresses).map_err(|e| e.to_string())?;
+                Ok(Subscription::Portfolio {
+                    address,
+                    solana_addresses,
+                })
+            }
+            "governance" => {
+                const MAX_PROPOSALS_PER_GOVERNANCE_SUBSCRIPTION: usize = 32;
+
+                let mut proposal_ids: Vec<String> = params
+                    .get("proposal_ids")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(|v| v.as_str().map(String::from))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if proposal_ids.len() > MAX_PROPOSALS_PER_GOVERNANCE_SUBSCRIPTION {
+                    return Err(format!(
+                        "Governance subscription exceeds the maximum of {} proposals",
+                        MAX_PROPOSALS_PER_GOVERNANCE_SUBSCRIPTION
+                    ));
+                }
+                if proposal_ids
+                    .iter()
+                    .any(|id| id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()))
+                {
+                    return Err(
+                        "Governance subscription contains an invalid proposal id".to_string()
+                    );
+                }
+                // Order-insensitive so unsubscribe matches the subscribe
+                proposal_ids.sort();
+                proposal_ids.dedup();
+                Ok(Subscription::Governance { proposal_ids })
+            }
+            "staking" => {
+                let address = params
+                    .get("address")
+                    .and_then(|v| v.as_str())
+                    .ok_or("Missing 'address' parameter")?
+                    .to_string();
+                if !crate::validation::is_valid_nolus_address(&address) {
+                    return Err("Staking subscription requires a valid Nolus address".to_string());
+                }
+                Ok(Subscription::Staking { address })
+            }
             _ => Err(format!("Unknown topic: {}", topic)),
         }
     }
@@ -243,6 +410,10 @@ impl Subscription {
             Subscription::TxStatus { .. } => "tx_status",
             Subscription::SkipTx { .. } => "skip_tx",
             Subscription::Earn { .. } => "earn",
+            Subscription::LeaseAlerts { .. } => "lease_alerts",
+            Subscription::Portfolio { .. } => "portfolio",
+            Subscription::Governance { .. } => "governance",
+            Subscription::Staking { .. } => "staking",
         }
     }
 }
@@ -279,6 +450,16 @@ pub struct CachedLeaseState {
     pub in_progress: Option<String>,
 }
 
+/// Alert conditions last reported for a lease (edge detection for `lease_alerts`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LeaseAlertState {
+    /// Highest LTV warning level reached (0 = below every threshold)
+    pub warning_level: u8,
+    pub approaching_liquidation: bool,
+    pub liquidation_in_progress: bool,
+    pub overdue_collect_imminent: bool,
+}
+
 /// Cached Skip transaction state for tracking
 #[derive(Debug, Clone, PartialEq)]
 pub struct CachedSkipTxState {
@@ -287,6 +468,19 @@ pub struct CachedSkipTxState {
     pub total_hops: u32,
 }
 
+/// Transaction relayed through `/api/tx/broadcast`, tracked until it lands
+#[derive(Debug, Clone)]
+pub struct TrackedTx {
+    pub chain_id: String,
+    pub registered_at: Instant,
+    /// Final status once the tx is found in a block; kept so a client that
+    /// subscribes after confirmation still receives it
+    pub outcome: Option<(TxStatusValue, Option<String>)>,
+    /// Set once the confirmation window passes without the tx being seen;
+    /// the tx keeps being looked up until it is included or pruned
+    pub timeout_error: Option<String>,
+}
+
 /// Cached earn position state for change detection
 #[derive(Debug, Clone, PartialEq)]
 pub struct CachedEarnState {
@@ -324,6 +518,14 @@ pub struct WebSocketManager {
     lease_address_to_owner: DashMap<String, String>,
     /// Known LPP contract addresses (for filtering earn-relevant contract events)
     lpp_contract_addresses: DashSet<String>,
+    /// Broadcast transactions awaiting (or recently reached) confirmation (tx_hash -> state)
+    tracked_txs: DashMap<String, TrackedTx>,
+    /// Last reported alert conditions (owner_address -> (lease_address -> state))
+    lease_alert_states: DashMap<String, HashMap<String, LeaseAlertState>>,
+    /// Last pushed portfolios for change detection ((address, linked Solana wallets) -> portfolio)
+    portfolio_states: DashMap<(String, Vec<String>), PortfolioResponse>,
+    /// Last seen unbonding/redelegation entries for maturity detection (address -> entries)
+    staking_schedules: DashMap<String, Vec<ScheduleEntry>>,
 }
 
 impl WebSocketManager {
@@ -338,6 +540,10 @@ impl WebSocketManager {
             balance_states: DashMap::new(),
             lease_address_to_owner: DashMap::new(),
             lpp_contract_addresses: DashSet::new(),
+            tracked_txs: DashMap::new(),
+            lease_alert_states: DashMap::new(),
+            portfolio_states: DashMap::new(),
+            staking_schedules: DashMap::new(),
         }
     }
 
@@ -490,12 +696,18 @@ impl WebSocketManager {
             // Clean up caches for subscriptions with no remaining subscribers
             for sub in &conn.subscriptions {
                 match sub {
-                    Subscription::Leases { address }
+                    Subscription::Leases { address } | Subscription::LeaseAlerts { address } => {
+                        // Both topics are served by the lease monitor's cache
+                        if !self.has_other_subscriber(|s| {
+                            matches!(s, Subscription::Leases { address: a } | Subscription::LeaseAlerts { address: a } if a == address)
+                        }) {
+                            self.clear_lease_cache(address);
+                        }
                         if !self.has_other_subscriber(
-                            |s| matches!(s, Subscription::Leases { address: a } if a == address),
-                        ) =>
-                    {
-                        self.clear_lease_cache(address);
+                            |s| matches!(s, Subscription::LeaseAlerts { address: a } if a == address),
+                        ) {
+                            self.clear_lease_alert_states(address);
+                        }
                     }
                     Subscription::Earn { address }
                         if !self.has_other_subscriber(
@@ -520,6 +732,15 @@ impl WebSocketManager {
                     {
                         self.skip_tx_states.remove(tx_hash);
                     }
+                    Subscription::Portfolio {
+                        address,
+                        solana_addresses,
+                    } if !self.has_other_subscriber(|s| s == sub) => {
+                        self.clear_portfolio_cache(address, solana_addresses);
+                    }
+                    Subscription::Staking { address } if !self.has_other_subscriber(|s| s == sub) => {
+                        self.clear_staking_schedule(address);
+                    }
                     _ => {}
                 }
             }
@@ -564,12 +785,14 @@ impl WebSocketManager {
         self.connection_count.load(Ordering::Relaxed)
     }
 
-    /// Get all unique owner addresses that have lease subscriptions
+    /// Get all unique owner addresses that have lease or lease alert subscriptions
     pub fn get_subscribed_lease_owners(&self) -> Vec<String> {
         let mut owners = HashSet::new();
         for entry in self.connections.iter() {
             for sub in &entry.value().subscriptions {
-                if let Subscription::Leases { address } = sub {
+                if let Subscription::Leases { address } | Subscription::LeaseAlerts { address } =
+                    sub
+                {
                     owners.insert(address.clone());
                 }
             }
@@ -620,6 +843,61 @@ impl WebSocketManager {
             .retain(|_, v| v.as_str() != owner);
     }
 
+    // =========================================================================
+    // Lease Alerts
+    // =========================================================================
+
+    /// Whether any connection subscribed to lease alerts for `owner`
+    pub fn has_lease_alert_subscriber(&self, owner: &str) -> bool {
+        self.has_other_subscriber(
+            |s| matches!(s, Subscription::LeaseAlerts { address } if address == owner),
+        )
+    }
+
+    /// Alert conditions last reported for a lease (default when never evaluated)
+    pub fn lease_alert_state(&self, owner: &str, lease_address: &str) -> LeaseAlertState {
+        self.lease_alert_states
+            .get(owner)
+            .and_then(|leases| leases.get(lease_address).cloned())
+            .unwrap_or_default()
+    }
+
+    pub fn set_lease_alert_state(&self, owner: &str, lease_address: &str, state: LeaseAlertState) {
+        self.lease_alert_states
+            .entry(owner.to_string())
+            .or_default()
+            .insert(lease_address.to_string(), state);
+    }
+
+    /// Forget a lease's alert state (when closed/liquidated)
+    pub fn remove_lease_alert_state(&self, owner: &str, lease_address: &str) {
+        if let Some(mut leases) = self.lease_alert_states.get_mut(owner) {
+            leases.remove(lease_address);
+        }
+    }
+
+    /// Clear alert states for an owner (when they unsubscribe)
+    pub fn clear_lease_alert_states(&self, owner: &str) {
+        self.lease_alert_states.remove(owner);
+    }
+
+    /// Send a lease alert to the owner's `lease_alerts` subscribers
+    pub fn send_lease_alert(&self, owner: &str, alert: ServerMessage) {
+        let msg = Arc::new(alert);
+
+        for entry in self.connections.iter() {
+            let conn = entry.value();
+            for sub in &conn.subscriptions {
+                if let Subscription::LeaseAlerts { address } = sub {
+                    if address == owner {
+                        let _ = conn.message_tx.try_send(Arc::clone(&msg));
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
     // =========================================================================
     // Skip Transaction Tracking
     // =========================================================================
@@ -702,6 +980,103 @@ impl WebSocketManager {
         }
     }
 
+    // =========================================================================
+    // Broadcast Transaction Tracking
+    // =========================================================================
+
+    /// Register a broadcast tx hash so `tx_status` subscribers get its
+    /// confirmation without any client-side polling
+    pub fn register_broadcast_tx(&self, tx_hash: &str, chain_id: &str) {
+        self.tracked_txs.insert(
+            tx_hash.to_string(),
+            TrackedTx {
+                chain_id: chain_id.to_string(),
+                registered_at: Instant::now(),
+                outcome: None,
+                timeout_error: None,
+            },
+        );
+    }
+
+    /// Hashes of tracked transactions that have not been confirmed yet
+    pub fn get_pending_broadcast_txs(&self) -> Vec<String> {
+        self.tracked_txs
+            .iter()
+            .filter(|entry| entry.value().outcome.is_none())
+            .map(|entry| entry.key().clone())
+            .collect()
+    }
+
+    /// Record the final status of a tracked tx and notify `tx_status` subscribers
+    pub fn resolve_broadcast_tx(
+        &self,
+        tx_hash: &str,
+        status: TxStatusValue,
+        error: Option<String>,
+    ) {
+        let chain_id = match self.tracked_txs.get_mut(tx_hash) {
+            Some(mut tracked) if tracked.outcome.is_none() => {
+                tracked.outcome = Some((status.clone(), error.clone()));
+                tracked.chain_id.clone()
+            }
+            _ => return,
+        };
+        self.send_tx_status(tx_hash, &chain_id, status, error);
+    }
+
+    /// Final status of a tracked tx if it has been resolved, or `Timeout`
+    /// while a tx past the confirmation window is still being looked up
+    pub fn broadcast_tx_outcome(
+        &self,
+        tx_hash: &str,
+        chain_id: &str,
+    ) -> Option<(TxStatusValue, Option<String>)> {
+        self.tracked_txs
+            .get(tx_hash)
+            .filter(|tracked| tracked.chain_id == chain_id)
+            .and_then(|tracked| {
+                tracked.outcome.clone().or_else(|| {
+                    tracked
+                        .timeout_error
+                        .clone()
+                        .map(|error| (TxStatusValue::Timeout, Some(error)))
+                })
+            })
+    }
+
+    /// Report `Timeout` once for transactions still unconfirmed after
+    /// `timeout`, and drop entries older than `retention`. Timed-out txs stay
+    /// pending, so a late inclusion still resolves them until they are pruned.
+    pub fn sweep_broadcast_txs(&self, timeout: Duration, retention: Duration) {
+        let error = format!(
+            "Transaction not included in a block within {}s",
+            timeout.as_secs()
+        );
+        let mut timed_out = Vec::new();
+        for mut entry in self.tracked_txs.iter_mut() {
+            let tracked = entry.value_mut();
+            if tracked.outcome.is_none()
+                && tracked.timeout_error.is_none()
+                && tracked.registered_at.elapsed() > timeout
+            {
+                tracked.timeout_error = Some(error.clone());
+                timed_out.push((entry.key().clone(), tracked.chain_id.clone()));
+            }
+        }
+
+        for (tx_hash, chain_id) in timed_out {
+            self.send_tx_status(
+                &tx_hash,
+                &chain_id,
+                TxStatusValue::Timeout,
+                Some(error.clone()),
+            );
+        }
+
+        self.tracked_txs
+            .retain(|_, tracked| tracked.registered_at.elapsed() <= retention);
+    }
+
     // =========================================================================
     // Earn Position Tracking
     // =========================================================================
@@ -816,6 +1191,156 @@ impl WebSocketManager {
         self.balance_states.remove(address);
     }
 
+    // =========================================================================
+    // Portfolio Tracking
+    // =========================================================================
+
+    /// All distinct (address, linked Solana wallets) portfolio subscriptions
+    pub fn get_subscribed_portfolios(&self) -> Vec<(String, Vec<String>)> {
+        let mut portfolios = HashSet::new();
+        for entry in self.connections.iter() {
+            for sub in &entry.value().subscriptions {
+                if let Subscription::Portfolio {
+                    address,
+                    solana_addresses,
+                } = sub
+                {
+                    portfolios.insert((address.clone(), solana_addresses.clone()));
+                }
+            }
+        }
+        portfolios.into_iter().collect()
+    }
+
+    /// Update the cached portfolio and return true if it changed
+    pub fn update_portfolio_state(&self, portfolio: &PortfolioResponse) -> bool {
+        let key = (
+            portfolio.address.clone(),
+            portfolio.solana_addresses.clone(),
+        );
+        let changed = self
+            .portfolio_states
+            .get(&key)
+            .is_none_or(|old| *old != *portfolio);
+
+        if changed {
+            self.portfolio_states.insert(key, portfolio.clone());
+        }
+
+        changed
+    }
+
+    /// Clear a cached portfolio (when its last subscriber leaves)
+    pub fn clear_portfolio_cache(&self, address: &str, solana_addresses: &[String]) {
+        self.portfolio_states
+            .remove(&(address.to_string(), solana_addresses.to_vec()));
+    }
+
+    /// Send a portfolio update to the matching subscribers
+    pub fn send_portfolio_update(&self, portfolio: PortfolioResponse) {
+        let target = Subscription::Portfolio {
+            address: portfolio.address.clone(),
+            solana_addresses: portfolio.solana_addresses.clone(),
+        };
+        let msg = Arc::new(ServerMessage::PortfolioUpdate {
+            portfolio,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        });
+
+        for entry in self.connections.iter() {
+            let conn = entry.value();
+            if conn.subscriptions.contains(&target) {
+                let _ = conn.message_tx.try_send(Arc::clone(&msg));
+            }
+        }
+    }
+
+    // =========================================================================
+    // Governance
+    // =========================================================================
+
+    /// Push proposal status changes to governance subscribers watching them
+    pub fn send_proposal_status_changes(&self, changes: &[crate::refresh::ProposalStatusChange]) {
+        for change in changes {
+            let msg = Arc::new(ServerMessage::ProposalStatusChanged {
+                proposal_id: change.proposal_id.clone(),
+                title: change.title.clone(),
+                previous_status: change.previous_status.clone(),
+                status: change.status.clone(),
+            });
+
+            for entry in self.connections.iter() {
+                let conn = entry.value();
+                let watched = conn.subscriptions.iter().any(|sub| {
+                    matches!(sub, Subscription::Governance { proposal_ids }
+                        if proposal_ids.is_empty() || proposal_ids.contains(&change.proposal_id))
+                });
+                if watched {
+                    let _ = conn.message_tx.try_send(Arc::clone(&msg));
+                }
+            }
+        }
+    }
+
+    // =========================================================================
+    // Staking Schedule
+    // =========================================================================
+
+    /// All distinct addresses with a staking subscription
+    pub fn get_subscribed_staking_addresses(&self) -> Vec<String> {
+        let mut addresses = HashSet::new();
+        for entry in self.connections.iter() {
+            for sub in &entry.value().subscriptions {
+                if let Subscription::Staking { address } = sub {
+                    addresses.insert(address.clone());
+                }
+            }
+        }
+        addresses.into_iter().collect()
+    }
+
+    /// Replace the cached schedule and return the entries that matured since
+    /// the previous one (none on the first call for an address)
+    pub fn update_staking_schedule(
+        &self,
+        address: &str,
+        entries: Vec<ScheduleEntry>,
+    ) -> Vec<ScheduleEntry> {
+        let matured = self
+            .staking_schedules
+            .get(address)
+            .map(|previous| staking::matured_entries(&previous, &entries, chrono::Utc::now()))
+            .unwrap_or_default();
+        self.staking_schedules.insert(address.to_string(), entries);
+        matured
+    }
+
+    /// Clear a cached schedule (when its last subscriber leaves)
+    pub fn clear_staking_schedule(&self, address: &str) {
+        self.staking_schedules.remove(address);
+    }
+
+    /// Push matured entries to the address's staking subscribers
+    pub fn send_staking_matured(&self, address: &str, matured: Vec<ScheduleEntry>) {
+        let target = Subscription::Staking {
+            address: address.to_string(),
+        };
+        for entry in matured {
+            let msg = Arc::new(ServerMessage::StakingEntryMatured {
+                address: address.to_string(),
+                entry,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            });
+
+            for conn in self.connections.iter() {
+                let conn = conn.value();
+                if conn.subscriptions.contains(&target) {
+                    let _ = conn.message_tx.try_send(Arc::clone(&msg));
+                }
+            }
+        }
+    }
+
     // =========================================================================
     // LPP Address Management (for earn event filtering)
     // =========================================================================
@@ -1009,12 +1534,46 @@ async fn handle_subscribe(
             let topic_name = sub.topic_name().to_string();
 
             // Capture the owner address before moving sub into add_subscription
-            let lease_owner = if let Subscription::Leases { address } = &sub {
+            let lease_owner = if let Subscription::Leases { address }
+            | Subscription::LeaseAlerts { address } = &sub
+            {
                 Some(address.clone())
             } else {
                 None
             };
 
+            // A new portfolio subscriber gets a snapshot even when it is unchanged
+            let portfolio_key = if let Subscription::Portfolio {
+                address,
+                solana_addresses,
+            } = &sub
+            {
+                Some((address.clone(), solana_addresses.clone()))
+            } else {
+                None
+            };
+
+            // Maturity is detected against a baseline taken at subscribe time
+            let staking_address = if let Subscription::Staking { address } = &sub {
+                Some(address.clone())
+            } else {
+                None
+            };
+
+            // A relayed tx may already be confirmed by the time the client subscribes
+            let tx_outcome = if let Subscription::TxStatus { hash, chain_id } = &sub {
+                state
+                    .ws_manager
+                    .broadcast_tx_outcome(hash, chain_id)
+                    .map(|(status, error)| ServerMessage::TxStatus {
+                        tx_hash: hash.clone(),
+                        status,
+                        error,
+                    })
+            } else {
+                None
+            };
+
             match state.ws_manager.add_subscription(conn_id, sub) {
                 Ok(true) => {
                     send_message(
@@ -1023,7 +1582,11 @@ async fn handle_subscribe(
                         ServerMessage::Subscribed { topic: topic_name },
                     );
 
-                    // For lease subscriptions, trigger an initial check to populate
+                    if let Some(msg) = tx_outcome {
+                        send_message(conn_id, state, msg);
+                    }
+
+                    // For lease (and lease alert) subscriptions, trigger an initial check to populate
                     // the reverse index (lease_address → owner). Without this, the
                     // first contract event after subscription can't be routed to the
                     // correct owner, delaying updates by up to 60 seconds.
@@ -1035,6 +1598,43 @@ async fn handle_subscribe(
                             }
                         });
                     }
+
+                    if let Some((address, solana_addresses)) = portfolio_key {
+                        let state = state.clone();
+                        let conn_id = conn_id.to_string();
+                        tokio::spawn(async move {
+                            match portfolio::compute_portfolio(&state, &address, &solana_addresses)
+                                .await
+                            {
+                                Ok(snapshot) => {
+                                    state.ws_manager.update_portfolio_state(&snapshot);
+                                    send_message(
+                                        &conn_id,
+                                        &state,
+                                        ServerMessage::PortfolioUpdate {
+                                            portfolio: snapshot,
+                                            timestamp: chrono::Utc::now().to_rfc3339(),
+                                        },
+                                    );
+                                }
+                                Err(e) => debug!("Initial portfolio for {}: {}", address, e),
+                            }
+                        });
+                    }
+
+                    if let Some(address) = staking_address {
+                        let state = state.clone();
+                        tokio::spawn(async move {
+                            match staking::fetch_staking_schedule(&state, &address).await {
+                                Ok(schedule) => {
+                                    state
+                                        .ws_manager
+                                        .update_staking_schedule(&address, schedule.entries);
+                                }
+                                Err(e) => debug!("Initial staking schedule for {}: {}", address, e),
+                            }
+                        });
+                    }
                 }
                 Ok(false) => {} // Connection not found, will be cleaned up
                 Err(()) => {
@@ -1252,9 +1852,18 @@ pub async fn start_lease_monitor_task(
     });
 }
 
-/// Check all subscribed lease owners (full sweep)
+/// Check all subscribed and webhook-registered lease owners (full sweep)
 async fn check_all_lease_owners(state: &Arc<AppState>) {
-    let owners = state.ws_manager.get_subscribed_lease_owners();
+    let mut owners: HashSet<String> = state
+        .ws_manager
+        .get_subscribed_lease_owners()
+        .into_iter()
+        .collect();
+    owners.extend(
+        state
+            .notification_store
+            .registered_addresses(NotificationKind::Lease),
+    );
     if owners.is_empty() {
         return;
     }
@@ -1291,6 +1900,12 @@ async fn check_owner_leases(state: &AppState, owner: &str) -> Result<(), String>
         .await
         .map_err(|e| e.to_string())?;
 
+    // Webhook-registered owners get alerts recorded even without a live subscriber
+    let notify = state.notification_store.is_registered(owner);
+    let alert_inputs = (notify || state.ws_manager.has_lease_alert_subscriber(owner))
+        .then(|| LeaseAlertInputs::load(state));
+    let mut drafts = Vec::new();
+
     for lease in leases {
         // Filter by gated configuration (protocol visibility and asset restrictions)
         let asset_ticker = lease
@@ -1309,6 +1924,12 @@ async fn check_owner_leases(state: &AppState, owner: &str) -> Result<(), String>
 
         let lease_address = &lease.address;
 
+        if let Some(inputs) = &alert_inputs {
+            for alert in check_lease_alerts(state, owner, &lease, inputs) {
+                drafts.push(lease_alert_draft(owner, &alert));
+            }
+        }
+
         // Update reverse index (lease contract addr -> owner) for targeted event handling
         state
             .ws_manager
@@ -1352,6 +1973,16 @@ async fn check_owner_leases(state: &AppState, owner: &str) -> Result<(), String>
 
             let lease_data = build_lease_update_payload(&lease);
 
+            // First sight of an existing lease (e.g. after a restart) is not an event
+            if !is_new || lease.status == "opening" {
+                drafts.push(NotificationDraft::new(
+                    owner,
+                    NotificationKind::Lease,
+                    change_type,
+                    lease_data.clone(),
+                ));
+            }
+
             // Send update to subscribers
             state.ws_manager.send_lease_update(owner, lease_data);
 
@@ -1365,6 +1996,9 @@ async fn check_owner_leases(state: &AppState, owner: &str) -> Result<(), String>
                 state
                     .ws_manager
                     .remove_lease_from_cache(owner, lease_address);
+                state
+                    .ws_manager
+                    .remove_lease_alert_state(owner, lease_address);
                 state
                     .ws_manager
                     .lease_address_to_owner
@@ -1373,9 +2007,298 @@ async fn check_owner_leases(state: &AppState, owner: &str) -> Result<(), String>
         }
     }
 
+    crate::notifications::record(state, drafts);
+
+    Ok(())
+}
+
+// ============================================================================
+// Broadcast Transaction Confirmation Task
+// ============================================================================
+
+/// How long a relayed tx may stay out of a block before it is reported as timed out (seconds)
+const TX_CONFIRMATION_TIMEOUT_SECS: u64 = 120;
+
+/// How long a tx is tracked: a timed-out tx is still looked up until then, and
+/// a resolved outcome is kept for late subscribers (seconds)
+const TX_OUTCOME_RETENTION_SECS: u64 = 600;
+
+/// Start background task confirming transactions relayed via `/api/tx/broadcast`.
+///
+/// Runs on every NewBlock: each pending hash is looked up on the LCD and, once
+/// included, its result is pushed to `tx_status` subscribers.
+pub async fn start_tx_confirmation_task(
+    state: Arc<AppState>,
+    mut new_block_rx: tokio::sync::broadcast::Receiver<u64>,
+) {
+    tokio::spawn(async move {
+        let timeout = Duration::from_secs(TX_CONFIRMATION_TIMEOUT_SECS);
+        let retention = Duration::from_secs(TX_OUTCOME_RETENTION_SECS);
+
+        loop {
+            match new_block_rx.recv().await {
+                Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                    error!("NewBlock channel closed, tx confirmation task stopping");
+                    return;
+                }
+            }
+
+            let pending = state.ws_manager.get_pending_broadcast_txs();
+            if !pending.is_empty() {
+                let futures: Vec<_> = pending
+                    .iter()
+                    .map(|tx_hash| {
+                        let state = state.clone();
+                        let tx_hash = tx_hash.clone();
+                        async move {
+                            if let Err(e) = check_broadcast_tx(&state, &tx_hash).await {
+                                debug!("Failed to check broadcast tx {}: {}", tx_hash, e);
+                            }
+                        }
+                    })
+                    .collect();
+
+                futures::future::join_all(futures).await;
+            }
+
+            state.ws_manager.sweep_broadcast_txs(timeout, retention);
+        }
+    });
+}
+
+/// Look up a relayed tx and resolve it once it is included in a block
+async fn check_broadcast_tx(state: &AppState, tx_hash: &str) -> Result<(), String> {
+    let Some(result) = state
+        .chain_client
+        .get_tx_result(tx_hash)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(());
+    };
+
+    if result.code == 0 {
+        state
+            .ws_manager
+            .resolve_broadcast_tx(tx_hash, TxStatusValue::Success, None);
+    } else {
+        let reason = super::cosmos_tx::decode_tx_error(&result.raw_log).reason;
+        info!(
+            "Broadcast tx {} failed at height {} ({}/{}): {}",
+            tx_hash, result.height, result.codespace, result.code, reason
+        );
+        state
+            .ws_manager
+            .resolve_broadcast_tx(tx_hash, TxStatusValue::Failed, Some(reason));
+    }
+
     Ok(())
 }
 
+// ============================================================================
+// Lease Alerts
+// ============================================================================
+
+/// Cached data needed to evaluate lease alerts, loaded once per owner check
+struct LeaseAlertInputs {
+    prices: Option<currencies::PricesResponse>,
+    currencies: Option<currencies::CurrenciesResponse>,
+    lease_configs: Option<HashMap<String, LeaseConfigResponse>>,
+    config: LeaseAlertsConfig,
+}
+
+impl LeaseAlertInputs {
+    fn load(state: &AppState) -> Self {
+        Self {
+            prices: state.data_cache.prices.load(),
+            currencies: state.data_cache.currencies.load(),
+            lease_configs: state.data_cache.lease_configs.load(),
+            config: state
+                .data_cache
+                .gated_config
+                .load()
+                .map(|gated| gated.lease_rules.alerts.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Thresholds a lease is checked against
+pub struct LeaseAlertThresholds {
+    /// LTV warning thresholds (percent, ascending)
+    pub ltv_warnings: Vec<f64>,
+    pub liquidation_distance_percent: f64,
+    pub overdue_collect_warning_secs: u64,
+}
+
+/// Evaluate a lease's alerts, push the ones that newly fired and return them
+fn check_lease_alerts(
+    state: &AppState,
+    owner: &str,
+    lease: &LeaseMonitorInfo,
+    inputs: &LeaseAlertInputs,
+) -> Vec<ServerMessage> {
+    let liability = inputs
+        .lease_configs
+        .as_ref()
+        .and_then(|configs| configs.get(&lease.protocol))
+        .map(|config| &config.liability);
+
+    let distance = match (liability, &inputs.prices, &inputs.currencies) {
+        (Some(liability), Some(prices), Some(currencies)) => {
+            crate::handlers::leases::compute_liquidation_distance(
+                lease,
+                liability,
+                crate::handlers::leases::is_short_protocol(state, &lease.protocol),
+                prices,
+                currencies,
+            )
+        }
+        _ => None,
+    };
+
+    // Without configured thresholds, warn at the leaser's own warning levels
+    let ltv_warnings = if inputs.config.ltv_warning_thresholds.is_empty() {
+        liability
+            .map(|spec| {
+                [
+                    spec.first_liq_warn,
+                    spec.second_liq_warn,
+                    spec.third_liq_warn,
+                ]
+                .iter()
+                .map(|permille| f64::from(*permille) / 10.0)
+                .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        inputs.config.ltv_warning_thresholds.clone()
+    };
+    let thresholds = LeaseAlertThresholds {
+        ltv_warnings,
+        liquidation_distance_percent: inputs.config.liquidation_distance_percent,
+        overdue_collect_warning_secs: inputs.config.overdue_collect_warning_secs,
+    };
+
+    let previous = state.ws_manager.lease_alert_state(owner, &lease.address);
+    let (current, alerts) =
+        evaluate_lease_alerts(owner, lease, distance.as_ref(), &thresholds, &previous);
+
+    if current != previous {
+        state
+            .ws_manager
+            .set_lease_alert_state(owner, &lease.address, current);
+    }
+    for alert in &alerts {
+        debug!("Lease alert for {} on {}", owner, lease.address);
+        state.ws_manager.send_lease_alert(owner, alert.clone());
+    }
+    alerts
+}
+
+/// Notification draft for a fired lease alert, named after its message type
+fn lease_alert_draft(owner: &str, alert: &ServerMessage) -> NotificationDraft {
+    let payload = serde_json::to_value(alert).unwrap_or_default();
+    let event = payload
+        .get("type")
+        .and_then(|t| t.as_str())
+        .unwrap_or("lease_alert")
+        .to_string();
+    NotificationDraft::new(owner, NotificationKind::Lease, &event, payload)
+}
+
+/// Compare a lease against the alert thresholds.
+///
+/// Alerts fire on the rising edge only: a condition that already held at the
+/// previous check stays quiet until it clears and crosses again. LTV warnings
+/// fire again for each higher level reached.
+pub fn evaluate_lease_alerts(
+    owner: &str,
+    lease: &LeaseMonitorInfo,
+    distance: Option<&LiquidationDistance>,
+    thresholds: &LeaseAlertThresholds,
+    previous: &LeaseAlertState,
+) -> (LeaseAlertState, Vec<ServerMessage>) {
+    let mut alerts = Vec::new();
+    let mut current = LeaseAlertState::default();
+
+    if let Some(distance) = distance {
+        let crossed = thresholds
+            .ltv_warnings
+            .iter()
+            .filter(|threshold| distance.ltv >= **threshold)
+            .count();
+        current.warning_level = u8::try_from(crossed).unwrap_or(u8::MAX);
+        if current.warning_level > previous.warning_level {
+            alerts.push(ServerMessage::LeaseLtvWarning {
+                owner: owner.to_string(),
+                lease_address: lease.address.clone(),
+                protocol: lease.protocol.clone(),
+                level: current.warning_level,
+                threshold: thresholds
+                    .ltv_warnings
+                    .get(crossed.saturating_sub(1))
+                    .copied()
+                    .unwrap_or_default(),
+                distance: distance.clone(),
+            });
+        }
+
+        current.approaching_liquidation =
+            distance.distance_percent <= thresholds.liquidation_distance_percent;
+        if current.approaching_liquidation && !previous.approaching_liquidation {
+            alerts.push(ServerMessage::LeaseLiquidationApproaching {
+                owner: owner.to_string(),
+                lease_address: lease.address.clone(),
+                protocol: lease.protocol.clone(),
+                distance: distance.clone(),
+            });
+        }
+    }
+
+    // A liquidation on a lease that is still open only sells part of the position
+    if let Some(crate::handlers::leases::LeaseInProgress::Liquidation { cause }) =
+        &lease.in_progress
+    {
+        current.liquidation_in_progress = lease.status == "opened";
+        if current.liquidation_in_progress && !previous.liquidation_in_progress {
+            alerts.push(ServerMessage::LeasePartialLiquidation {
+                owner: owner.to_string(),
+                lease_address: lease.address.clone(),
+                protocol: lease.protocol.clone(),
+                cause: cause.clone(),
+                distance: distance.cloned(),
+            });
+        }
+    }
+
+    // Only worth an alert when there is something overdue to collect
+    let overdue = lease.debt.as_ref().map(|debt| {
+        let amount = debt.overdue_interest.parse::<u128>().unwrap_or(0)
+            + debt.overdue_margin.parse::<u128>().unwrap_or(0);
+        (amount, debt.ticker.clone())
+    });
+    if let (Some(collect_in_ns), Some((amount, ticker))) = (lease.overdue_collect_in, overdue) {
+        let collect_in_secs = collect_in_ns / 1_000_000_000;
+        current.overdue_collect_imminent =
+            amount > 0 && collect_in_secs <= thresholds.overdue_collect_warning_secs;
+        if current.overdue_collect_imminent && !previous.overdue_collect_imminent {
+            alerts.push(ServerMessage::LeaseOverdueCollection {
+                owner: owner.to_string(),
+                lease_address: lease.address.clone(),
+                protocol: lease.protocol.clone(),
+                collect_in_secs,
+                amount: amount.to_string(),
+                ticker,
+                distance: distance.cloned(),
+            });
+        }
+    }
+
+    (current, alerts)
+}
+
 // ============================================================================
 // Skip Transaction Tracking Task
 // ============================================================================
@@ -1575,8 +2498,17 @@ pub async fn start_earn_monitor_task(
                 }
             }
 
-            // Get all addresses with earn subscriptions
-            let addresses = state.ws_manager.get_subscribed_earn_addresses();
+            // Get all addresses with earn subscriptions or earn webhooks
+            let mut addresses: HashSet<String> = state
+                .ws_manager
+                .get_subscribed_earn_addresses()
+                .into_iter()
+                .collect();
+            addresses.extend(
+                state
+                    .notification_store
+                    .registered_addresses(NotificationKind::Earn),
+            );
             if addresses.is_empty() {
                 continue;
             }
@@ -1641,9 +2573,23 @@ async fn check_earn_positions(state: &AppState, address: &str) -> Result<(), Str
     };
 
     // Check if state changed
+    let first_seen = !state.ws_manager.earn_states.contains_key(address);
     let changed = state.ws_manager.update_earn_state(address, current_state);
 
     if changed {
+        // The baseline snapshot (e.g. after a restart) is not an event
+        let drafts = if first_seen {
+            Vec::new()
+        } else {
+            vec![NotificationDraft::new(
+                address,
+                NotificationKind::Earn,
+                "positions_updated",
+                serde_json::json!({ "positions": positions }),
+            )]
+        };
+        crate::notifications::record(state, drafts);
+
         // Send update to subscribers
         state
             .ws_manager
@@ -1719,6 +2665,27 @@ fn build_lease_update_payload(lease: &super::leases::LeaseMonitorInfo) -> serde_
     payload
 }
 
+/// Transfer notifications for the webhook-registered parties of a bank
+/// transfer, each naming the other parties involved.
+fn bank_transfer_drafts(state: &AppState, addresses: &[String]) -> Vec<NotificationDraft> {
+    let registered = state
+        .notification_store
+        .registered_addresses(NotificationKind::Transfer);
+    addresses
+        .iter()
+        .filter(|address| registered.contains(address))
+        .map(|address| {
+            let counterparties: Vec<&String> = addresses.iter().filter(|a| *a != address).collect();
+            NotificationDraft::new(
+                address,
+                NotificationKind::Transfer,
+                "bank_transfer",
+                serde_json::json!({ "counterparties": counterparties }),
+            )
+        })
+        .collect()
+}
+
 /// Outcome of evaluating one address in a balance-monitor flush.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum BalanceFlushOutcome {
@@ -1775,10 +2742,14 @@ pub async fn start_balance_monitor_task(
 
         loop {
             let mut candidate_addresses: HashSet<String> = HashSet::new();
+            let mut transfer_drafts: Vec<NotificationDraft> = Vec::new();
             let mut do_full_recheck = false;
 
             match bank_transfer_rx.recv().await {
-                Ok(event) => candidate_addresses.extend(event.addresses),
+                Ok(event) => {
+                    transfer_drafts.extend(bank_transfer_drafts(&state, &event.addresses));
+                    candidate_addresses.extend(event.addresses);
+                }
                 Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
                     let now = Instant::now();
                     if lag_recheck_allowed(last_full_recheck, now) {
@@ -1811,6 +2782,7 @@ pub async fn start_balance_monitor_task(
                         result = bank_transfer_rx.recv() => {
                             match result {
                                 Ok(event) => {
+                                    transfer_drafts.extend(bank_transfer_drafts(&state, &event.addresses));
                                     candidate_addresses.extend(event.addresses);
                                     continue;
                                 }
@@ -1834,6 +2806,8 @@ pub async fn start_balance_monitor_task(
                 }
             }
 
+            crate::notifications::record(&state, transfer_drafts);
+
             let subscribed = state.ws_manager.get_subscribed_balance_addresses();
             let addresses_to_check: Vec<String> = if do_full_recheck {
                 subscribed
@@ -1858,31 +2832,130 @@ pub async fn start_balance_monitor_task(
                         process_balance_compute(&state.ws_manager, &address, computed)
                     }
                 })
-                .buffer_unordered(BALANCE_MONITOR_FANOUT_CAP)
-                .collect()
+                .buffer_unordered(BALANCE_MONITOR_FANOUT_CAP)
+                .collect()
+                .await;
+
+            if do_full_recheck {
+                last_full_recheck = Some(Instant::now());
+            }
+
+            let pushed = outcomes
+                .iter()
+                .filter(|&&o| o == BalanceFlushOutcome::Pushed)
+                .count();
+            let unchanged = outcomes
+                .iter()
+                .filter(|&&o| o == BalanceFlushOutcome::Unchanged)
+                .count();
+            let unavailable = outcomes
+                .iter()
+                .filter(|&&o| o == BalanceFlushOutcome::SkippedUnavailable)
+                .count();
+
+            info!(
+                "Balance monitor flush: pending={} pushed={} unchanged={} unavailable={}",
+                pending, pushed, unchanged, unavailable
+            );
+        }
+    });
+}
+
+// ============================================================================
+// Portfolio Monitoring Task
+// ============================================================================
+
+/// How often subscribed portfolios are recomputed. Portfolios move with every
+/// price refresh, so a timer bounds pushes instead of chain events.
+const PORTFOLIO_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Concurrency cap for the portfolio monitor's per-subscription fan-out.
+const PORTFOLIO_MONITOR_FANOUT_CAP: usize = 4;
+
+/// Start background task recomputing subscribed portfolios on a timer and
+/// pushing those that changed.
+pub async fn start_portfolio_monitor_task(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PORTFOLIO_REFRESH_INTERVAL);
+        interval.tick().await; // Consume the immediate first tick
+
+        loop {
+            interval.tick().await;
+
+            let portfolios = state.ws_manager.get_subscribed_portfolios();
+            if portfolios.is_empty() {
+                continue;
+            }
+
+            futures::stream::iter(portfolios)
+                .for_each_concurrent(
+                    PORTFOLIO_MONITOR_FANOUT_CAP,
+                    |(address, solana_addresses)| {
+                        let state = state.clone();
+                        async move {
+                            match portfolio::compute_portfolio(&state, &address, &solana_addresses)
+                                .await
+                            {
+                                Ok(snapshot) => {
+                                    if state.ws_manager.update_portfolio_state(&snapshot) {
+                                        state.ws_manager.send_portfolio_update(snapshot);
+                                    }
+                                }
+                                Err(e) => {
+                                    debug!("Failed to compute portfolio for {}: {}", address, e)
+                                }
+                            }
+                        }
+                    },
+                )
+                .await;
+        }
+    });
+}
+
+/// How often subscribed staking schedules are re-read. The chain removes an
+/// entry in the first block past its completion time.
+const STAKING_SCHEDULE_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Concurrency cap for the staking schedule monitor's per-address fan-out.
+const STAKING_MONITOR_FANOUT_CAP: usize = 4;
+
+/// Start background task re-reading subscribed staking schedules on a timer
+/// and pushing the entries that matured.
+pub async fn start_staking_schedule_monitor_task(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(STAKING_SCHEDULE_REFRESH_INTERVAL);
+        interval.tick().await; // Consume the immediate first tick
+
+        loop {
+            interval.tick().await;
+
+            let addresses = state.ws_manager.get_subscribed_staking_addresses();
+            if addresses.is_empty() {
+                continue;
+            }
+
+            futures::stream::iter(addresses)
+                .for_each_concurrent(STAKING_MONITOR_FANOUT_CAP, |address| {
+                    let state = state.clone();
+                    async move {
+                        // A failed read keeps the previous baseline
+                        match staking::fetch_staking_schedule(&state, &address).await {
+                            Ok(schedule) => {
+                                let matured = state
+                                    .ws_manager
+                                    .update_staking_schedule(&address, schedule.entries);
+                                if !matured.is_empty() {
+                                    state.ws_manager.send_staking_matured(&address, matured);
+                                }
+                            }
+                            Err(e) => {
+                                debug!("Failed to read staking schedule for {}: {}", address, e)
+                            }
+                        }
+                    }
+                })
                 .await;
-
-            if do_full_recheck {
-                last_full_recheck = Some(Instant::now());
-            }
-
-            let pushed = outcomes
-                .iter()
-                .filter(|&&o| o == BalanceFlushOutcome::Pushed)
-                .count();
-            let unchanged = outcomes
-                .iter()
-                .filter(|&&o| o == BalanceFlushOutcome::Unchanged)
-                .count();
-            let unavailable = outcomes
-                .iter()
-                .filter(|&&o| o == BalanceFlushOutcome::SkippedUnavailable)
-                .count();
-
-            info!(
-                "Balance monitor flush: pending={} pushed={} unchanged={} unavailable={}",
-                pending, pushed, unchanged, unavailable
-            );
         }
     });
 }
@@ -2049,6 +3122,7 @@ mod tests {
             close_policy: None,
             in_progress: None,
             reason: None,
+            overdue_collect_in: None,
         };
         let payload = build_lease_update_payload(&lease);
         let obj = payload
@@ -2450,6 +3524,115 @@ mod tests {
         assert!(rx_wrong_hash.try_recv().is_err());
     }
 
+    /// Resolving a relayed tx notifies its subscribers once and keeps the
+    /// outcome for late subscribers.
+    #[tokio::test]
+    async fn test_resolve_broadcast_tx_notifies_once_and_keeps_outcome() {
+        let m = WebSocketManager::new(16);
+        let mut rx = register_conn(&m, "c1");
+        m.add_subscription(
+            "c1",
+            Subscription::TxStatus {
+                hash: "ABC".to_string(),
+                chain_id: "pirin-1".to_string(),
+            },
+        )
+        .unwrap();
+
+        m.register_broadcast_tx("ABC", "pirin-1");
+        assert_eq!(m.get_pending_broadcast_txs(), vec!["ABC".to_string()]);
+        assert!(m.broadcast_tx_outcome("ABC", "pirin-1").is_none());
+
+        m.resolve_broadcast_tx("ABC", TxStatusValue::Success, None);
+        m.resolve_broadcast_tx("ABC", TxStatusValue::Failed, Some("late".to_string()));
+
+        let msg = rx.try_recv().unwrap();
+        assert!(matches!(
+            &*msg,
+            ServerMessage::TxStatus {
+                status: TxStatusValue::Success,
+                ..
+            }
+        ));
+        assert!(rx.try_recv().is_err());
+        assert!(m.get_pending_broadcast_txs().is_empty());
+        assert!(matches!(
+            m.broadcast_tx_outcome("ABC", "pirin-1"),
+            Some((TxStatusValue::Success, None))
+        ));
+        assert!(m.broadcast_tx_outcome("ABC", "osmosis-1").is_none());
+    }
+
+    /// Unconfirmed txs past the timeout are reported as timed out, not
+    /// failed, and stay pending; everything past the retention window is dropped.
+    #[tokio::test]
+    async fn test_sweep_broadcast_txs_times_out_and_prunes() {
+        let m = WebSocketManager::new(16);
+        m.register_broadcast_tx("SLOW", "pirin-1");
+
+        m.sweep_broadcast_txs(Duration::from_secs(60), Duration::from_secs(600));
+        assert_eq!(m.get_pending_broadcast_txs(), vec!["SLOW".to_string()]);
+        assert!(m.broadcast_tx_outcome("SLOW", "pirin-1").is_none());
+
+        m.sweep_broadcast_txs(Duration::ZERO, Duration::from_secs(600));
+        match m.broadcast_tx_outcome("SLOW", "pirin-1") {
+            Some((TxStatusValue::Timeout, Some(error))) => {
+                assert!(error.contains("not included"), "error was: {error}")
+            }
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+        assert_eq!(m.get_pending_broadcast_txs(), vec!["SLOW".to_string()]);
+
+        m.sweep_broadcast_txs(Duration::ZERO, Duration::ZERO);
+        assert!(m.broadcast_tx_outcome("SLOW", "pirin-1").is_none());
+        assert!(m.get_pending_broadcast_txs().is_empty());
+    }
+
+    /// A tx included after the confirmation window still gets its final
+    /// status, and the timeout is pushed only once.
+    #[tokio::test]
+    async fn test_timed_out_broadcast_tx_resolves_on_late_inclusion() {
+        let m = WebSocketManager::new(16);
+        let mut rx = register_conn(&m, "c1");
+        m.add_subscription(
+            "c1",
+            Subscription::TxStatus {
+                hash: "LATE".to_string(),
+                chain_id: "pirin-1".to_string(),
+            },
+        )
+        .unwrap();
+        m.register_broadcast_tx("LATE", "pirin-1");
+
+        m.sweep_broadcast_txs(Duration::ZERO, Duration::from_secs(600));
+        m.sweep_broadcast_txs(Duration::ZERO, Duration::from_secs(600));
+        let msg = rx.try_recv().unwrap();
+        assert!(matches!(
+            &*msg,
+            ServerMessage::TxStatus {
+                status: TxStatusValue::Timeout,
+                ..
+            }
+        ));
+        assert!(rx.try_recv().is_err());
+
+        m.resolve_broadcast_tx("LATE", TxStatusValue::Success, None);
+        let msg = rx.try_recv().unwrap();
+        assert!(matches!(
+            &*msg,
+            ServerMessage::TxStatus {
+                status: TxStatusValue::Success,
+                error: None,
+                ..
+            }
+        ));
+        assert!(m.get_pending_broadcast_txs().is_empty());
+        assert!(matches!(
+            m.broadcast_tx_outcome("LATE", "pirin-1"),
+            Some((TxStatusValue::Success, None))
+        ));
+    }
+
     /// Skip tx update targets only subscribers for the specific tx_hash.
     #[tokio::test]
     async fn test_send_skip_tx_update_routes_by_tx_hash() {
@@ -3251,4 +4434,481 @@ mod tests {
         state.ws_manager.remove_connection("seed-0");
         assert!(state.ws_manager.can_accept_connection());
     }
+
+    // ------------------------------------------------------------------
+    // Lease alerts
+    // ------------------------------------------------------------------
+
+    fn alert_lease(status: &str) -> LeaseMonitorInfo {
+        LeaseMonitorInfo {
+            address: "lease1".to_string(),
+            protocol: "OSMOSIS-OSMOSIS-USDC_NOBLE".to_string(),
+            status: status.to_string(),
+            amount: None,
+            debt: Some(crate::handlers::leases::LeaseDebtInfo {
+                ticker: "USDC_NOBLE".to_string(),
+                principal: "700".to_string(),
+                overdue_margin: "0".to_string(),
+                overdue_interest: "0".to_string(),
+                due_margin: "0".to_string(),
+                due_interest: "0".to_string(),
+                total: "700".to_string(),
+                total_usd: None,
+            }),
+            interest: None,
+            liquidation_price: None,
+            pnl: None,
+            close_policy: None,
+            in_progress: None,
+            reason: None,
+            overdue_collect_in: None,
+        }
+    }
+
+    fn alert_distance(ltv: f64, distance_percent: f64) -> LiquidationDistance {
+        LiquidationDistance {
+            price_ticker: "ATOM".to_string(),
+            current_price: "10.000000".to_string(),
+            liquidation_price: "8.000000".to_string(),
+            distance_percent,
+            ltv,
+            liquidation_ltv: 90.0,
+        }
+    }
+
+    fn alert_thresholds() -> LeaseAlertThresholds {
+        LeaseAlertThresholds {
+            ltv_warnings: vec![72.0, 75.0, 78.0],
+            liquidation_distance_percent: 5.0,
+            overdue_collect_warning_secs: 3600,
+        }
+    }
+
+    #[test]
+    fn test_subscription_parsing_lease_alerts() {
+        let owner = "nolus1qg5ega6dykkxc307y25pecuufrjkxkaggkkxh7nad0vhyhtuhw3sqaa3c5";
+        let sub = Subscription::from_client_message(
+            "lease_alerts",
+            &serde_json::json!({"address": owner}),
+        )
+        .unwrap();
+        assert!(matches!(&sub, Subscription::LeaseAlerts { address } if address == owner));
+        assert_eq!(sub.topic_name(), "lease_alerts");
+        assert!(Subscription::from_client_message("lease_alerts", &serde_json::json!({})).is_err());
+        assert!(Subscription::from_client_message(
+            "lease_alerts",
+            &serde_json::json!({"address": "nolus1owner"})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_subscription_parsing_portfolio() {
+        let owner = "nolus1qg5ega6dykkxc307y25pecuufrjkxkaggkkxh7nad0vhyhtuhw3sqaa3c5";
+        let sol = "11111111111111111111111111111111";
+        let sub = Subscription::from_client_message(
+            "portfolio",
+            &serde_json::json!({"address": owner, "solana_addresses": [sol]}),
+        )
+        .unwrap();
+        assert_eq!(
+            sub,
+            Subscription::Portfolio {
+                address: owner.to_string(),
+                solana_addresses: vec![sol.to_string()],
+            }
+        );
+        assert_eq!(sub.topic_name(), "portfolio");
+
+        let other = "So11111111111111111111111111111111111111112";
+        let forward = Subscription::from_client_message(
+            "portfolio",
+            &serde_json::json!({"address": owner, "solana_addresses": [sol, other]}),
+        )
+        .unwrap();
+        let reversed = Subscription::from_client_message(
+            "portfolio",
+            &serde_json::json!({"address": owner, "solana_addresses": [other, sol, other]}),
+        )
+        .unwrap();
+        assert_eq!(forward, reversed);
+
+        assert!(Subscription::from_client_message(
+            "portfolio",
+            &serde_json::json!({"address": "nolus1owner"})
+        )
+        .is_err());
+        assert!(Subscription::from_client_message(
+            "portfolio",
+            &serde_json::json!({"address": owner, "solana_addresses": ["bad!"]})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_subscription_parsing_governance() {
+        let sub = Subscription::from_client_message(
+            "governance",
+            &serde_json::json!({"proposal_ids": ["12", "3", "12"]}),
+        )
+        .unwrap();
+        assert_eq!(
+            sub,
+            Subscription::Governance {
+                proposal_ids: vec!["12".to_string(), "3".to_string()],
+            }
+        );
+        assert_eq!(sub.topic_name(), "governance");
+
+        let all = Subscription::from_client_message("governance", &serde_json::json!({})).unwrap();
+        assert_eq!(
+            all,
+            Subscription::Governance {
+                proposal_ids: vec![]
+            }
+        );
+        assert!(Subscription::from_client_message(
+            "governance",
+            &serde_json::json!({"proposal_ids": ["abc"]})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_subscription_parsing_staking() {
+        let owner = "nolus1qg5ega6dykkxc307y25pecuufrjkxkaggkkxh7nad0vhyhtuhw3sqaa3c5";
+        let sub =
+            Subscription::from_client_message("staking", &serde_json::json!({"address": owner}))
+                .unwrap();
+        assert_eq!(
+            sub,
+            Subscription::Staking {
+                address: owner.to_string()
+            }
+        );
+        assert_eq!(sub.topic_name(), "staking");
+        assert!(Subscription::from_client_message(
+            "staking",
+            &serde_json::json!({"address": "nolus1owner"})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_update_staking_schedule_reports_matured_entries() {
+        let manager = WebSocketManager::new(10);
+        let entry = |completion_time: &str| ScheduleEntry {
+            kind: staking::ScheduleEntryKind::Unbonding,
+            validator_address: "nolusvaloper1abc".to_string(),
+            validator_moniker: None,
+            destination_validator_address: None,
+            destination_moniker: None,
+            creation_height: "100".to_string(),
+            completion_time: completion_time.to_string(),
+            initial_balance: "1000".to_string(),
+            amount: "1000".to_string(),
+            seconds_remaining: 0,
+        };
+        let past = entry("2020-01-01T00:00:00Z");
+        let future = entry("2999-01-01T00:00:00Z");
+
+        // First read only sets the baseline
+        assert!(manager
+            .update_staking_schedule("nolus1owner", vec![past.clone(), future.clone()])
+            .is_empty());
+        // Both gone: only the one past its completion time matured
+        let matured = manager.update_staking_schedule("nolus1owner", vec![]);
+        assert_eq!(matured, vec![past]);
+    }
+
+    #[test]
+    fn test_lease_alerts_ltv_warning_fires_per_new_level() {
+        let lease = alert_lease("opened");
+        let thresholds = alert_thresholds();
+
+        let (state, alerts) = evaluate_lease_alerts(
+            "owner",
+            &lease,
+            Some(&alert_distance(76.0, 15.0)),
+            &thresholds,
+            &LeaseAlertState::default(),
+        );
+        assert_eq!(state.warning_level, 2);
+        assert_eq!(alerts.len(), 1);
+        assert!(matches!(
+            &alerts[0],
+            ServerMessage::LeaseLtvWarning { level: 2, threshold, .. } if (*threshold - 75.0).abs() < f64::EPSILON
+        ));
+
+        // Same level again stays quiet
+        let (state, alerts) = evaluate_lease_alerts(
+            "owner",
+            &lease,
+            Some(&alert_distance(77.0, 14.0)),
+            &thresholds,
+            &state,
+        );
+        assert!(alerts.is_empty());
+
+        // Higher level fires again
+        let (state, alerts) = evaluate_lease_alerts(
+            "owner",
+            &lease,
+            Some(&alert_distance(79.0, 12.0)),
+            &thresholds,
+            &state,
+        );
+        assert_eq!(state.warning_level, 3);
+        assert!(matches!(
+            &alerts[0],
+            ServerMessage::LeaseLtvWarning { level: 3, .. }
+        ));
+
+        // Dropping back below every threshold re-arms the warnings
+        let (state, alerts) = evaluate_lease_alerts(
+            "owner",
+            &lease,
+            Some(&alert_distance(60.0, 30.0)),
+            &thresholds,
+            &state,
+        );
+        assert_eq!(state.warning_level, 0);
+        assert!(alerts.is_empty());
+    }
+
+    #[test]
+    fn test_lease_alerts_liquidation_approaching_on_rising_edge() {
+        let lease = alert_lease("opened");
+        let thresholds = alert_thresholds();
+        let distance = alert_distance(50.0, 4.2);
+
+        let (state, alerts) = evaluate_lease_alerts(
+            "owner",
+            &lease,
+            Some(&distance),
+            &thresholds,
+            &LeaseAlertState::default(),
+        );
+        assert!(state.approaching_liquidation);
+        assert_eq!(alerts.len(), 1);
+        match &alerts[0] {
+            ServerMessage::LeaseLiquidationApproaching {
+                lease_address,
+                distance: d,
+                ..
+            } => {
+                assert_eq!(lease_address, "lease1");
+                assert_eq!(d, &distance);
+            }
+            other => panic!("unexpected alert: {other:?}"),
+        }
+
+        let (_, alerts) =
+            evaluate_lease_alerts("owner", &lease, Some(&distance), &thresholds, &state);
+        assert!(alerts.is_empty());
+    }
+
+    #[test]
+    fn test_lease_alerts_partial_liquidation_only_for_open_lease() {
+        let mut lease = alert_lease("opened");
+        lease.in_progress = Some(crate::handlers::leases::LeaseInProgress::Liquidation {
+            cause: Some("overdue".to_string()),
+        });
+        let thresholds = alert_thresholds();
+
+        let (state, alerts) = evaluate_lease_alerts(
+            "owner",
+            &lease,
+            None,
+            &thresholds,
+            &LeaseAlertState::default(),
+        );
+        assert!(state.liquidation_in_progress);
+        assert!(matches!(
+            &alerts[0],
+            ServerMessage::LeasePartialLiquidation { cause: Some(c), distance: None, .. } if c == "overdue"
+        ));
+
+        lease.status = "liquidated".to_string();
+        let (_, alerts) = evaluate_lease_alerts(
+            "owner",
+            &lease,
+            None,
+            &thresholds,
+            &LeaseAlertState::default(),
+        );
+        assert!(alerts.is_empty());
+    }
+
+    #[test]
+    fn test_lease_alerts_overdue_collection_requires_overdue_amount() {
+        let mut lease = alert_lease("opened");
+        lease.overdue_collect_in = Some(1_800 * 1_000_000_000);
+        let thresholds = alert_thresholds();
+
+        // Nothing overdue: no alert even though collection is near
+        let (state, alerts) = evaluate_lease_alerts(
+            "owner",
+            &lease,
+            None,
+            &thresholds,
+            &LeaseAlertState::default(),
+        );
+        assert!(!state.overdue_collect_imminent);
+        assert!(alerts.is_empty());
+
+        if let Some(debt) = lease.debt.as_mut() {
+            debt.overdue_interest = "12".to_string();
+            debt.overdue_margin = "3".to_string();
+        }
+        let (state, alerts) = evaluate_lease_alerts(
+            "owner",
+            &lease,
+            None,
+            &thresholds,
+            &LeaseAlertState::default(),
+        );
+        assert!(state.overdue_collect_imminent);
+        match &alerts[0] {
+            ServerMessage::LeaseOverdueCollection {
+                collect_in_secs,
+                amount,
+                ticker,
+                ..
+            } => {
+                assert_eq!(*collect_in_secs, 1_800);
+                assert_eq!(amount, "15");
+                assert_eq!(ticker, "USDC_NOBLE");
+            }
+            other => panic!("unexpected alert: {other:?}"),
+        }
+
+        // Collection still far away
+        lease.overdue_collect_in = Some(7_200 * 1_000_000_000);
+        let (_, alerts) = evaluate_lease_alerts(
+            "owner",
+            &lease,
+            None,
+            &thresholds,
+            &LeaseAlertState::default(),
+        );
+        assert!(alerts.is_empty());
+    }
+
+    #[test]
+    fn test_lease_alert_serialization_carries_distance() {
+        let msg = ServerMessage::LeaseLiquidationApproaching {
+            owner: "owner".to_string(),
+            lease_address: "lease1".to_string(),
+            protocol: "P".to_string(),
+            distance: alert_distance(80.0, 4.0),
+        };
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["type"], "lease_liquidation_approaching");
+        assert_eq!(json["distance"]["liquidation_price"], "8.000000");
+        assert_eq!(json["distance"]["distance_percent"], 4.0);
+    }
+
+    #[tokio::test]
+    async fn test_send_lease_alert_targets_alert_subscribers_only() {
+        let m = WebSocketManager::new(16);
+        let mut rx_alerts = register_conn(&m, "alerts");
+        let mut rx_leases = register_conn(&m, "leases");
+        m.add_subscription(
+            "alerts",
+            Subscription::LeaseAlerts {
+                address: "nolus1a".to_string(),
+            },
+        )
+        .unwrap();
+        m.add_subscription(
+            "leases",
+            Subscription::Leases {
+                address: "nolus1a".to_string(),
+            },
+        )
+        .unwrap();
+
+        assert!(m.has_lease_alert_subscriber("nolus1a"));
+        let mut owners = m.get_subscribed_lease_owners();
+        owners.sort();
+        assert_eq!(owners, vec!["nolus1a".to_string()]);
+
+        m.send_lease_alert(
+            "nolus1a",
+            ServerMessage::LeaseLiquidationApproaching {
+                owner: "nolus1a".to_string(),
+                lease_address: "lease1".to_string(),
+                protocol: "P".to_string(),
+                distance: alert_distance(80.0, 4.0),
+            },
+        );
+        assert!(rx_alerts.try_recv().is_ok());
+        assert!(rx_leases.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remove_connection_clears_lease_alert_state() {
+        let m = WebSocketManager::new(16);
+        let _rx = register_conn(&m, "c1");
+        m.add_subscription(
+            "c1",
+            Subscription::LeaseAlerts {
+                address: "owner".to_string(),
+            },
+        )
+        .unwrap();
+        let state = LeaseAlertState {
+            warning_level: 2,
+            ..Default::default()
+        };
+        m.set_lease_alert_state("owner", "lease1", state.clone());
+        assert_eq!(m.lease_alert_state("owner", "lease1"), state);
+
+        m.remove_connection("c1");
+        assert_eq!(
+            m.lease_alert_state("owner", "lease1"),
+            LeaseAlertState::default()
+        );
+    }
+
+    #[test]
+    fn test_lease_alert_draft_is_named_after_message_type() {
+        let alert = ServerMessage::LeaseLiquidationApproaching {
+            owner: "owner".to_string(),
+            lease_address: "lease1".to_string(),
+            protocol: "P".to_string(),
+            distance: alert_distance(80.0, 4.0),
+        };
+        let draft = lease_alert_draft("owner", &alert);
+        assert_eq!(draft.kind, NotificationKind::Lease);
+        assert_eq!(draft.event, "lease_liquidation_approaching");
+        assert_eq!(draft.payload["lease_address"], "lease1");
+    }
+
+    #[tokio::test]
+    async fn test_bank_transfer_drafts_only_for_registered_parties() {
+        let state = crate::test_utils::test_app_state().await;
+        state
+            .notification_store
+            .register_webhook(crate::notifications::WebhookRegistration {
+                id: "w1".to_string(),
+                address: "nolus1a".to_string(),
+                url: "https://hooks.example.com".to_string(),
+                secret: "s".to_string(),
+                kinds: vec![NotificationKind::Transfer],
+                created_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        let drafts = bank_transfer_drafts(&state, &["nolus1a".to_string(), "nolus1b".to_string()]);
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].address, "nolus1a");
+        assert_eq!(
+            drafts[0].payload["counterparties"],
+            serde_json::json!(["nolus1b"])
+        );
+        assert!(bank_transfer_drafts(&state, &["nolus1c".to_string()]).is_empty());
+    }
 }
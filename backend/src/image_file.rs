@@ -0,0 +1,221 @@
+//! Atomic whole-image JSON files for the durable stores.
+//!
+//! Each write goes to a unique temp file, is `sync_all`'d, renamed into place,
+//! and the parent directory is fsync'd, so a crash never leaves a partial
+//! image behind. Loading falls back to `<path>.bak` when the primary image is
+//! unreadable and otherwise fails loudly — a missing or corrupt image never
+//! silently yields an empty store.
+//!
+//! Callers serialize their own writes (each store holds a `write_gate` and
+//! snapshots inside it), so the newest image is always the one renamed last.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::io::AsyncWriteExt as _;
+use tracing::warn;
+
+use crate::error::AppError;
+
+/// Monotonic suffix source for temp-file uniqueness within a persist directory.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A JSON image at a fixed path. `label` names the store in errors and logs.
+#[derive(Debug, Clone)]
+pub struct ImageFile {
+    path: PathBuf,
+    label: &'static str,
+}
+
+impl ImageFile {
+    pub const fn new(path: PathBuf, label: &'static str) -> Self {
+        Self { path, label }
+    }
+
+    /// Read the image, falling back to `<path>.bak` if the primary is
+    /// unreadable. `Err` if neither loads.
+    pub async fn load<T: DeserializeOwned>(&self) -> Result<T, AppError> {
+        match self.read(&self.path).await {
+            Ok(image) => Ok(image),
+            Err(primary_err) => self.read(&self.backup_path()).await.map_err(|_backup_err| {
+                AppError::Internal(format!(
+                    "{} image at {} is unreadable and no valid backup exists: {primary_err}",
+                    self.label,
+                    self.path.display()
+                ))
+            }),
+        }
+    }
+
+    /// Durably replace the image with `image`.
+    pub async fn write<T: Serialize>(&self, image: &T) -> Result<(), AppError> {
+        let bytes = serde_json::to_vec(image)
+            .map_err(|e| AppError::Internal(format!("serialising {}: {e}", self.label)))?;
+        let temp = self.temp_path();
+
+        {
+            let mut file = tokio::fs::File::create(&temp).await.map_err(|e| {
+                AppError::Internal(format!("creating {} temp file: {e}", self.label))
+            })?;
+            file.write_all(&bytes).await.map_err(|e| {
+                AppError::Internal(format!("writing {} temp file: {e}", self.label))
+            })?;
+            file.sync_all().await.map_err(|e| {
+                AppError::Internal(format!("syncing {} temp file: {e}", self.label))
+            })?;
+        }
+
+        tokio::fs::rename(&temp, &self.path)
+            .await
+            .map_err(|e| AppError::Internal(format!("committing {} image: {e}", self.label)))?;
+
+        self.sync_parent_dir().await;
+        Ok(())
+    }
+
+    /// The parent-dir fsync durably records the rename. A failure does not
+    /// lose the written image (already fsync'd), so it is non-fatal, but it
+    /// is surfaced rather than swallowed.
+    async fn sync_parent_dir(&self) {
+        let Some(dir) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) else {
+            return;
+        };
+        match tokio::fs::File::open(dir).await {
+            Ok(handle) => {
+                if let Err(e) = handle.sync_all().await {
+                    warn!(
+                        "{} parent-dir fsync failed ({}): {e}",
+                        self.label,
+                        dir.display()
+                    );
+                }
+            }
+            Err(e) => {
+                warn!(
+                    "{} parent-dir open failed ({}): {e}",
+                    self.label,
+                    dir.display()
+                );
+            }
+        }
+    }
+
+    /// The `<path>.bak` fallback image path.
+    fn backup_path(&self) -> PathBuf {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(".bak");
+        self.path.with_file_name(name)
+    }
+
+    /// A unique temp path in the image's directory for an atomic write.
+    fn temp_path(&self) -> PathBuf {
+        let counter = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".tmp-{}-{counter}", std::process::id()));
+        self.path.with_file_name(name)
+    }
+
+    /// Read and deserialise a whole image. Any I/O or parse failure is an
+    /// `Err`.
+    async fn read<T: DeserializeOwned>(&self, path: &Path) -> Result<T, AppError> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| AppError::Internal(format!("reading {} image: {e}", self.label)))?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| AppError::Internal(format!("parsing {} image: {e}", self.label)))
+    }
+}
+
+/// Open a durable store at the path named by `env_var`, or `default` when it
+/// is unset. The parent directory is created first; an existing image goes
+/// through `load` and a fresh deployment starts from `create`.
+pub async fn open_store<T, Fut>(
+    env_var: &str,
+    default: &str,
+    load: impl FnOnce(PathBuf) -> Fut,
+    create: impl FnOnce(PathBuf) -> T,
+) -> Result<T, AppError>
+where
+    Fut: Future<Output = Result<T, AppError>>,
+{
+    let path = PathBuf::from(std::env::var(env_var).unwrap_or_else(|_err| default.to_string()));
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::Internal(format!("creating {}: {e}", parent.display())))?;
+    }
+    if path.exists() {
+        load(path).await
+    } else {
+        Ok(create(path))
+    }
+}
+
+/// A unique path in the system temp dir for an ephemeral test store.
+#[cfg(test)]
+pub fn ephemeral_path(prefix: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "{prefix}-test-{}-{}.json",
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn write_then_load_round_trips_without_leftover_temp_files() {
+        let dir = TempDir::new().unwrap();
+        let file = ImageFile::new(dir.path().join("image.json"), "test image");
+        let image = HashMap::from([("a".to_string(), 1u32)]);
+        file.write(&image).await.unwrap();
+        file.write(&image).await.unwrap();
+
+        let loaded: HashMap<String, u32> = file.load().await.unwrap();
+        assert_eq!(loaded, image);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn corrupt_image_falls_back_to_backup() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("image.json");
+        tokio::fs::write(&path, b"{not json").await.unwrap();
+        let file = ImageFile::new(path.clone(), "test image");
+        let err = file.load::<HashMap<String, u32>>().await.unwrap_err();
+        assert!(err.to_string().contains("no valid backup"));
+
+        tokio::fs::write(dir.path().join("image.json.bak"), br#"{"b":2}"#)
+            .await
+            .unwrap();
+        let loaded: HashMap<String, u32> = file.load().await.unwrap();
+        assert_eq!(loaded.get("b"), Some(&2));
+    }
+
+    #[tokio::test]
+    async fn open_store_creates_parent_then_loads_existing_image() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("store.json");
+        let default = path.to_str().unwrap();
+        let open = || {
+            open_store(
+                "IMAGE_FILE_TEST_UNSET_STORE_PATH",
+                default,
+                |_path| async { Ok("loaded") },
+                |_path| "created",
+            )
+        };
+
+        assert_eq!(open().await.unwrap(), "created");
+        assert!(dir.path().join("nested").is_dir());
+
+        tokio::fs::write(&path, b"{}").await.unwrap();
+        assert_eq!(open().await.unwrap(), "loaded");
+    }
+}
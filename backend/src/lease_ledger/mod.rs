@@ -0,0 +1,768 @@
+//! Backend-recorded lease ledger for ETL-independent PnL.
+//!
+//! [`start_ledger_task`] listens to `contract_exec` events. A `wasm-ls-open`
+//! event records the downpayment and loan valued at the oracle prices of the
+//! event's block; the post-swap position is filled in once the lease reports
+//! `Opened`. Each `wasm-ls-repay` event appends the payment, priced the same
+//! way, and the principal and interest it covered. A repayment that cannot be
+//! priced is kept but marked, which takes the lease off ledger PnL. With the
+//! current on-chain status and cached prices, this is everything
+//! `handlers::leases` needs to compute PnL without waiting for ETL to index
+//! the lease. Durable state lives in [`store`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, warn};
+
+use crate::chain_events::ContractExecEvent;
+use crate::external::chain::LeaseStatusResponse;
+use crate::handlers::currencies::{
+    calculate_price_with_decimals, CurrenciesResponse, PriceInfo, PricesResponse,
+};
+use crate::AppState;
+
+mod store;
+
+pub use store::LeaseLedgerStore;
+
+/// Leases tracked; the least recently opened are evicted first.
+pub const MAX_TRACKED_LEASES: usize = 50_000;
+
+/// Repayments kept per lease; a lease past this stops being ledger-priced.
+pub const MAX_REPAYMENTS_PER_LEASE: usize = 1_000;
+
+/// Custom event the lease contract emits once the opening swap completes.
+const LEASE_OPEN_EVENT: &str = "wasm-ls-open";
+
+/// Custom event the lease contract emits for every repayment.
+const LEASE_REPAY_EVENT: &str = "wasm-ls-repay";
+
+/// Status checks for a lease to leave `Opening` before its position is
+/// left unrecorded.
+const OPENED_POSITION_ATTEMPTS: u32 = 12;
+
+/// Pause between those checks.
+const OPENED_POSITION_RETRY: Duration = Duration::from_secs(10);
+
+/// An amount in a currency's minor units with its USD value when recorded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerAmount {
+    pub ticker: String,
+    pub amount: String,
+    pub usd: f64,
+}
+
+/// One observed repayment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaseRepayment {
+    pub tx_hash: String,
+    pub at: DateTime<Utc>,
+    pub payment: LedgerAmount,
+    /// No price was available at the repayment's block, so `payment.usd` is
+    /// zero and the lease has no ledger PnL.
+    #[serde(default)]
+    pub unpriced: bool,
+    /// Principal repaid, LPN minor units
+    pub principal: u128,
+    /// Loan interest (due + overdue) repaid, LPN minor units
+    pub loan_interest: u128,
+    /// Margin interest (due + overdue) repaid, LPN minor units
+    pub margin_interest: u128,
+}
+
+/// Everything recorded about one lease since it opened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaseLedgerEntry {
+    pub protocol: String,
+    pub opened_at: DateTime<Utc>,
+    pub downpayment: LedgerAmount,
+    pub loan: LedgerAmount,
+    /// Position right after the opening swap, valued at the prices of the
+    /// open event's block; `None` until the lease reports `Opened`, or if it
+    /// never did within the wait
+    pub opened_position: Option<LedgerAmount>,
+    /// Oldest first
+    pub repayments: Vec<LeaseRepayment>,
+}
+
+impl LeaseLedgerEntry {
+    /// Total USD paid into the lease after opening; `None` if a repayment
+    /// could not be priced.
+    pub fn repaid_usd(&self) -> Option<f64> {
+        if self.repayments.iter().any(|r| r.unpriced) {
+            return None;
+        }
+        Some(self.repayments.iter().map(|r| r.payment.usd).sum())
+    }
+
+    /// Loan and margin interest repaid so far, LPN minor units.
+    pub fn interest_repaid(&self) -> (u128, u128) {
+        self.repayments.iter().fold((0, 0), |(loan, margin), r| {
+            (
+                loan.saturating_add(r.loan_interest),
+                margin.saturating_add(r.margin_interest),
+            )
+        })
+    }
+
+    /// Price lost in the opening swap: what went in minus what came out.
+    pub fn swap_cost_usd(&self) -> Option<f64> {
+        self.opened_position
+            .as_ref()
+            .map(|position| self.downpayment.usd + self.loan.usd - position.usd)
+    }
+}
+
+/// Payload of a `wasm-ls-open` event.
+#[derive(Debug, Clone, PartialEq)]
+struct OpenObservation {
+    lease: String,
+    loan_pool: String,
+    downpayment: (String, String),
+    loan: (String, String),
+}
+
+/// Payload of a `wasm-ls-repay` event.
+#[derive(Debug, Clone, PartialEq)]
+struct RepayObservation {
+    lease: String,
+    payment: (String, String),
+    principal: u128,
+    loan_interest: u128,
+    margin_interest: u128,
+}
+
+fn parse_open_event(event: &ContractExecEvent) -> Option<OpenObservation> {
+    let attr = |key: &str| event.attribute(key).map(str::to_string);
+    Some(OpenObservation {
+        lease: attr("id").unwrap_or_else(|| event.contract_address.clone()),
+        loan_pool: attr("loan-pool-id")?,
+        downpayment: (attr("downpayment-symbol")?, attr("downpayment-amount")?),
+        loan: (attr("loan-symbol")?, attr("loan-amount")?),
+    })
+}
+
+fn parse_repay_event(event: &ContractExecEvent) -> Option<RepayObservation> {
+    // Interest attributes are omitted by older contract versions when zero.
+    let amount = |key: &str| -> u128 {
+        event
+            .attribute(key)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default()
+    };
+    Some(RepayObservation {
+        lease: event
+            .attribute("to")
+            .unwrap_or(&event.contract_address)
+            .to_string(),
+        payment: (
+            event.attribute("payment-symbol")?.to_string(),
+            event.attribute("payment-amount")?.to_string(),
+        ),
+        principal: amount("principal"),
+        loan_interest: amount("overdue-loan-interest")
+            .saturating_add(amount("current-loan-interest")),
+        margin_interest: amount("overdue-margin-interest")
+            .saturating_add(amount("current-margin-interest")),
+    })
+}
+
+/// USD value of `amount` minor units of `ticker` in `protocol`, from the
+/// cached prices and currency decimals.
+pub fn usd_value(
+    prices: &PricesResponse,
+    currencies: &CurrenciesResponse,
+    ticker: &str,
+    protocol: &str,
+    amount: &str,
+) -> Option<f64> {
+    let key = format!("{}@{}", ticker, protocol);
+    let decimals = i32::from(currencies.currencies.get(&key)?.decimal_digits);
+    let price: f64 = prices.prices.get(&key)?.price_usd.parse().ok()?;
+    let amount: f64 = amount.parse().ok()?;
+    Some(amount / 10_f64.powi(decimals) * price)
+}
+
+/// USD price of one whole `ticker` at block `height`, computed like the
+/// price refresh does from the oracle's stable quote.
+async fn stable_price_at(
+    state: &AppState,
+    currencies: &CurrenciesResponse,
+    oracle: &str,
+    protocol: &str,
+    ticker: &str,
+    height: u64,
+) -> Option<String> {
+    let quote = match state
+        .chain_client
+        .get_stable_price_at(oracle, ticker, height)
+        .await
+    {
+        Ok(quote) => quote,
+        Err(e) => {
+            debug!("No {} price at height {}: {}", ticker, height, e);
+            return None;
+        }
+    };
+    let decimals = |ticker: &str| {
+        currencies
+            .currencies
+            .get(&format!("{}@{}", ticker, protocol))
+            .map(|c| c.decimal_digits)
+    };
+    Some(calculate_price_with_decimals(
+        &quote.amount_quote.amount,
+        &quote.amount.amount,
+        "1.0",
+        decimals(ticker)?,
+        decimals(&quote.amount_quote.ticker)?,
+    ))
+}
+
+/// The protocol whose liquidity pool is `loan_pool`.
+fn protocol_of_pool(state: &AppState, loan_pool: &str) -> Option<String> {
+    state
+        .data_cache
+        .protocol_contracts
+        .load()?
+        .into_iter()
+        .find(|(_, info)| info.lpp == loan_pool)
+        .map(|(protocol, _)| protocol)
+}
+
+/// Record a lease's opening, valued at the oracle prices of the event's block.
+/// Returns the lease, its protocol and the height once recorded.
+async fn record_open(state: &AppState, event: &ContractExecEvent) -> Option<(String, String, u64)> {
+    let Some(open) = parse_open_event(event) else {
+        warn!(
+            "Malformed {} event in tx {}",
+            LEASE_OPEN_EVENT, event.tx_hash
+        );
+        return None;
+    };
+    if state.lease_ledger.get(&open.lease).is_some() {
+        return None;
+    }
+    let (Some(protocol), Some(height)) = (protocol_of_pool(state, &open.loan_pool), event.height)
+    else {
+        debug!(
+            "Cannot price opening of lease {}, not recording",
+            open.lease
+        );
+        return None;
+    };
+    let (Some(downpayment), Some(loan)) = (
+        value_at(state, &protocol, height, open.downpayment).await,
+        value_at(state, &protocol, height, open.loan).await,
+    ) else {
+        debug!("Missing prices for opening of lease {}", open.lease);
+        return None;
+    };
+
+    let entry = LeaseLedgerEntry {
+        protocol: protocol.clone(),
+        opened_at: Utc::now(),
+        downpayment,
+        loan,
+        opened_position: None,
+        repayments: Vec::new(),
+    };
+    if let Err(e) = state.lease_ledger.record_opening(&open.lease, entry).await {
+        warn!("Failed to record opening of lease {}: {}", open.lease, e);
+        return None;
+    }
+    Some((open.lease, protocol, height))
+}
+
+/// Wait for a freshly opened lease to leave `Opening`, then record its
+/// position valued at the prices of the open event's block, so the swap cost
+/// compares what went in and came out at the same prices. Gives up after
+/// `attempts` status queries `retry` apart.
+async fn record_opened_position(
+    state: &AppState,
+    lease: &str,
+    protocol: &str,
+    height: u64,
+    attempts: u32,
+    retry: Duration,
+) {
+    for attempt in 1..=attempts {
+        match state.chain_client.get_lease_status(lease, 0).await {
+            Ok(LeaseStatusResponse::Opened(opened)) => {
+                let amount = opened.opened.amount;
+                let Some(position) =
+                    value_at(state, protocol, height, (amount.ticker, amount.amount)).await
+                else {
+                    debug!("Missing price for position of lease {}", lease);
+                    return;
+                };
+                if let Err(e) = state
+                    .lease_ledger
+                    .record_opened_position(lease, position)
+                    .await
+                {
+                    warn!("Failed to record position of lease {}: {}", lease, e);
+                }
+                return;
+            }
+            Ok(LeaseStatusResponse::Opening(_)) => {}
+            Ok(_) => return,
+            Err(e) => debug!("Failed to query opened lease {}: {}", lease, e),
+        }
+        if attempt < attempts {
+            tokio::time::sleep(retry).await;
+        }
+    }
+    debug!(
+        "Lease {} not opened after {} checks, leaving its position unrecorded",
+        lease, attempts
+    );
+}
+
+/// An amount of a ticker valued at the oracle price of block `height`.
+async fn value_at(
+    state: &AppState,
+    protocol: &str,
+    height: u64,
+    (ticker, amount): (String, String),
+) -> Option<LedgerAmount> {
+    let oracle = state
+        .data_cache
+        .protocol_contracts
+        .load()?
+        .get(protocol)?
+        .oracle
+        .clone();
+    let currencies = state.data_cache.currencies.load()?;
+    let price_usd = stable_price_at(state, &currencies, &oracle, protocol, &ticker, height).await?;
+    let key = format!("{}@{}", ticker, protocol);
+    let price = PriceInfo {
+        key: key.clone(),
+        symbol: ticker.clone(),
+        price_usd,
+    };
+    let prices = PricesResponse {
+        prices: HashMap::from([(key, price)]),
+        updated_at: String::new(),
+    };
+    let usd = usd_value(&prices, &currencies, &ticker, protocol, &amount)?;
+    Some(LedgerAmount {
+        ticker,
+        amount,
+        usd,
+    })
+}
+
+async fn on_lease_open(state: &Arc<AppState>, event: &ContractExecEvent) {
+    let Some((lease, protocol, height)) = record_open(state, event).await else {
+        return;
+    };
+    // The opening swap may still be in flight; don't hold up the event loop.
+    let state = state.clone();
+    tokio::spawn(async move {
+        record_opened_position(
+            &state,
+            &lease,
+            &protocol,
+            height,
+            OPENED_POSITION_ATTEMPTS,
+            OPENED_POSITION_RETRY,
+        )
+        .await;
+    });
+}
+
+async fn on_lease_repay(state: &Arc<AppState>, event: &ContractExecEvent) {
+    let Some(repay) = parse_repay_event(event) else {
+        warn!(
+            "Malformed {} event in tx {}",
+            LEASE_REPAY_EVENT, event.tx_hash
+        );
+        return;
+    };
+    // Leases opened before the backend was watching stay on ETL PnL.
+    let Some(entry) = state.lease_ledger.get(&repay.lease) else {
+        return;
+    };
+    let payment = match event.height {
+        Some(height) => value_at(state, &entry.protocol, height, repay.payment.clone()).await,
+        None => None,
+    };
+    let unpriced = payment.is_none();
+    if unpriced {
+        // Kept so the lease's history stays complete; it just has no ledger PnL.
+        warn!(
+            "Cannot price repayment of lease {} in tx {}, recording it unpriced",
+            repay.lease, event.tx_hash
+        );
+    }
+    let repayment = LeaseRepayment {
+        tx_hash: event.tx_hash.clone(),
+        at: Utc::now(),
+        payment: payment.unwrap_or_else(|| LedgerAmount {
+            ticker: repay.payment.0,
+            amount: repay.payment.1,
+            usd: 0.0,
+        }),
+        unpriced,
+        principal: repay.principal,
+        loan_interest: repay.loan_interest,
+        margin_interest: repay.margin_interest,
+    };
+    if let Err(e) = state
+        .lease_ledger
+        .record_repayment(&repay.lease, repayment)
+        .await
+    {
+        warn!("Failed to record repayment of lease {}: {}", repay.lease, e);
+    }
+}
+
+/// Record lease openings and repayments from contract events.
+pub async fn start_ledger_task(
+    state: Arc<AppState>,
+    mut contract_rx: tokio::sync::broadcast::Receiver<ContractExecEvent>,
+) {
+    tokio::spawn(async move {
+        loop {
+            match contract_rx.recv().await {
+                Ok(event) => match event.event_type.as_str() {
+                    LEASE_OPEN_EVENT => on_lease_open(&state, &event).await,
+                    LEASE_REPAY_EVENT => on_lease_repay(&state, &event).await,
+                    _ => {}
+                },
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                    // A missed repayment can't be recovered from the stream and
+                    // would overstate PnL; fall back to ETL for every lease instead.
+                    warn!(
+                        "Lease ledger lagged {} events, forgetting recorded leases",
+                        n
+                    );
+                    state.lease_ledger.forget_all().await;
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                    error!("Contract event channel closed, lease ledger stopping");
+                    return;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::currencies::CurrencyInfo;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn event(event_type: &str, attributes: &[(&str, &str)]) -> ContractExecEvent {
+        ContractExecEvent {
+            contract_address: "nolus1lease".to_string(),
+            action: None,
+            tx_hash: "HASH".to_string(),
+            height: Some(100),
+            event_type: event_type.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect(),
+        }
+    }
+
+    fn currency(ticker: &str, decimals: u8) -> CurrencyInfo {
+        CurrencyInfo {
+            key: format!("{}@P", ticker),
+            ticker: ticker.to_string(),
+            symbol: ticker.to_string(),
+            name: ticker.to_string(),
+            short_name: ticker.to_string(),
+            decimal_digits: decimals,
+            bank_symbol: String::new(),
+            dex_symbol: String::new(),
+            icon: String::new(),
+            native: false,
+            coingecko_id: None,
+            protocol: "P".to_string(),
+            group: "lease".to_string(),
+            is_active: true,
+        }
+    }
+
+    #[test]
+    fn parses_open_event() {
+        let open = parse_open_event(&event(
+            LEASE_OPEN_EVENT,
+            &[
+                ("id", "nolus1opened"),
+                ("currency", "ATOM"),
+                ("loan-pool-id", "nolus1lpp"),
+                ("loan-symbol", "USDC"),
+                ("loan-amount", "2000000"),
+                ("downpayment-symbol", "USDC"),
+                ("downpayment-amount", "1000000"),
+            ],
+        ))
+        .unwrap();
+        assert_eq!(open.lease, "nolus1opened");
+        assert_eq!(open.loan_pool, "nolus1lpp");
+        assert_eq!(
+            open.downpayment,
+            ("USDC".to_string(), "1000000".to_string())
+        );
+
+        assert!(parse_open_event(&event(LEASE_OPEN_EVENT, &[("id", "x")])).is_none());
+    }
+
+    #[test]
+    fn parses_repay_event_and_sums_interest() {
+        let repay = parse_repay_event(&event(
+            LEASE_REPAY_EVENT,
+            &[
+                ("to", "nolus1repaid"),
+                ("payment-symbol", "USDC"),
+                ("payment-amount", "500000"),
+                ("principal", "400000"),
+                ("overdue-loan-interest", "10"),
+                ("current-loan-interest", "90"),
+                ("current-margin-interest", "50"),
+            ],
+        ))
+        .unwrap();
+        assert_eq!(repay.lease, "nolus1repaid");
+        assert_eq!(repay.principal, 400_000);
+        assert_eq!(repay.loan_interest, 100);
+        assert_eq!(repay.margin_interest, 50);
+
+        // Falls back to the emitting contract when `to` is absent.
+        let bare = parse_repay_event(&event(
+            LEASE_REPAY_EVENT,
+            &[("payment-symbol", "USDC"), ("payment-amount", "1")],
+        ))
+        .unwrap();
+        assert_eq!(bare.lease, "nolus1lease");
+    }
+
+    #[test]
+    fn usd_value_applies_decimals_and_price() {
+        let prices = PricesResponse {
+            prices: HashMap::from([(
+                "ATOM@P".to_string(),
+                PriceInfo {
+                    key: "ATOM@P".to_string(),
+                    symbol: "ATOM".to_string(),
+                    price_usd: "8".to_string(),
+                },
+            )]),
+            updated_at: String::new(),
+        };
+        let currencies = CurrenciesResponse {
+            currencies: HashMap::from([("ATOM@P".to_string(), currency("ATOM", 6))]),
+            lpn: Vec::new(),
+            lease_currencies: Vec::new(),
+            map: HashMap::new(),
+        };
+        let usd = usd_value(&prices, &currencies, "ATOM", "P", "2500000").unwrap();
+        assert!((usd - 20.0).abs() < 1e-9);
+        assert!(usd_value(&prices, &currencies, "OSMO", "P", "1").is_none());
+    }
+
+    #[test]
+    fn entry_aggregates_repayments_and_swap_cost() {
+        let amount = |usd| LedgerAmount {
+            ticker: "USDC".to_string(),
+            amount: "0".to_string(),
+            usd,
+        };
+        let repayment = |usd, loan_interest, margin_interest| LeaseRepayment {
+            tx_hash: "T".to_string(),
+            at: Utc::now(),
+            payment: amount(usd),
+            unpriced: false,
+            principal: 0,
+            loan_interest,
+            margin_interest,
+        };
+        let entry = LeaseLedgerEntry {
+            protocol: "P".to_string(),
+            opened_at: Utc::now(),
+            downpayment: amount(100.0),
+            loan: amount(200.0),
+            opened_position: Some(amount(297.0)),
+            repayments: vec![repayment(10.0, 5, 1), repayment(15.0, 7, 2)],
+        };
+        assert!((entry.repaid_usd().unwrap() - 25.0).abs() < 1e-9);
+        assert_eq!(entry.interest_repaid(), (12, 3));
+        assert!((entry.swap_cost_usd().unwrap() - 3.0).abs() < 1e-9);
+
+        let mut unpriced = entry;
+        unpriced.repayments[1].unpriced = true;
+        assert!(unpriced.repaid_usd().is_none());
+    }
+
+    fn smart_path(contract: &str, query: serde_json::Value) -> String {
+        use base64::Engine as _;
+        format!(
+            "/cosmwasm/wasm/v1/contract/{}/smart/{}",
+            contract,
+            base64::engine::general_purpose::STANDARD.encode(serde_json::to_vec(&query).unwrap())
+        )
+    }
+
+    async fn mount_stable_price(chain: &MockServer, ticker: &str, amount: &str, quote: &str) {
+        Mock::given(method("GET"))
+            .and(path(smart_path(
+                "nolus1oracle",
+                serde_json::json!({ "stable_price": { "currency": ticker } }),
+            )))
+            .and(header("x-cosmos-block-height", "100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "amount": { "ticker": ticker, "amount": amount },
+                    "amount_quote": { "ticker": "USDC", "amount": quote }
+                }
+            })))
+            .mount(chain)
+            .await;
+    }
+
+    async fn ledger_state(chain: &MockServer) -> Arc<AppState> {
+        use crate::external::chain::ProtocolContractsInfo;
+
+        let mut config = crate::test_utils::test_config();
+        config.external.nolus_rest_url = chain.uri();
+        let state = crate::test_utils::test_app_state_with_config_and_client(
+            config,
+            reqwest::Client::new(),
+        )
+        .await;
+        state.data_cache.protocol_contracts.store(HashMap::from([(
+            "P".to_string(),
+            ProtocolContractsInfo {
+                oracle: "nolus1oracle".to_string(),
+                lpp: "nolus1lpp".to_string(),
+                leaser: "nolus1leaser".to_string(),
+                profit: "nolus1profit".to_string(),
+                reserve: None,
+            },
+        )]));
+        state.data_cache.currencies.store(CurrenciesResponse {
+            currencies: HashMap::from([
+                ("USDC@P".to_string(), currency("USDC", 6)),
+                ("ATOM@P".to_string(), currency("ATOM", 6)),
+            ]),
+            lpn: Vec::new(),
+            lease_currencies: Vec::new(),
+            map: HashMap::new(),
+        });
+        mount_stable_price(chain, "USDC", "1000000", "1000000").await;
+        mount_stable_price(chain, "ATOM", "1000000", "8000000").await;
+        state
+    }
+
+    fn open_event() -> ContractExecEvent {
+        event(
+            LEASE_OPEN_EVENT,
+            &[
+                ("id", "nolus1opened"),
+                ("loan-pool-id", "nolus1lpp"),
+                ("loan-symbol", "USDC"),
+                ("loan-amount", "200000000"),
+                ("downpayment-symbol", "USDC"),
+                ("downpayment-amount", "100000000"),
+            ],
+        )
+    }
+
+    #[tokio::test]
+    async fn opening_is_valued_at_event_height_and_position_waits_for_opened() {
+        let chain = MockServer::start().await;
+        let state = ledger_state(&chain).await;
+        let lease_state = smart_path(
+            "nolus1opened",
+            serde_json::json!({ "state": { "due_projection_secs": 0 } }),
+        );
+        let debt = || serde_json::json!({ "ticker": "USDC", "amount": "0" });
+        Mock::given(method("GET"))
+            .and(path(lease_state.clone()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "opening": {
+                    "currency": "ATOM",
+                    "downpayment": { "ticker": "USDC", "amount": "100000000" },
+                    "loan": { "ticker": "USDC", "amount": "200000000" },
+                    "loan_interest_rate": 100,
+                    "in_progress": "open_lease"
+                } }
+            })))
+            .up_to_n_times(1)
+            .mount(&chain)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(lease_state))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": { "opened": {
+                    "amount": { "ticker": "ATOM", "amount": "37125000" },
+                    "loan_interest_rate": 100,
+                    "margin_interest_rate": 10,
+                    "principal_due": { "ticker": "USDC", "amount": "200000000" },
+                    "overdue_margin": debt(),
+                    "overdue_interest": debt(),
+                    "due_margin": debt(),
+                    "due_interest": debt(),
+                    "validity": "valid"
+                } }
+            })))
+            .mount(&chain)
+            .await;
+
+        let (lease, protocol, height) = record_open(&state, &open_event()).await.unwrap();
+        assert_eq!(
+            (lease.as_str(), protocol.as_str(), height),
+            ("nolus1opened", "P", 100)
+        );
+        let entry = state.lease_ledger.get("nolus1opened").unwrap();
+        assert!((entry.downpayment.usd - 100.0).abs() < 1e-9);
+        assert!((entry.loan.usd - 200.0).abs() < 1e-9);
+        assert!(entry.opened_position.is_none());
+
+        // The first check still sees `Opening`; the second records the position.
+        record_opened_position(&state, &lease, &protocol, height, 3, Duration::ZERO).await;
+        let entry = state.lease_ledger.get("nolus1opened").unwrap();
+        assert!((entry.opened_position.unwrap().usd - 297.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn unpriced_repayment_is_marked_not_dropped() {
+        let chain = MockServer::start().await;
+        let state = ledger_state(&chain).await;
+        record_open(&state, &open_event()).await.unwrap();
+
+        let repay = |tx_hash: &str, height| {
+            let mut event = event(
+                LEASE_REPAY_EVENT,
+                &[
+                    ("to", "nolus1opened"),
+                    ("payment-symbol", "USDC"),
+                    ("payment-amount", "10000000"),
+                    ("principal", "10000000"),
+                ],
+            );
+            event.tx_hash = tx_hash.to_string();
+            event.height = height;
+            event
+        };
+        on_lease_repay(&state, &repay("PRICED", Some(100))).await;
+        on_lease_repay(&state, &repay("UNPRICED", None)).await;
+
+        let entry = state.lease_ledger.get("nolus1opened").unwrap();
+        assert_eq!(entry.repayments.len(), 2);
+        assert!((entry.repayments[0].payment.usd - 10.0).abs() < 1e-9);
+        assert!(!entry.repayments[0].unpriced);
+        assert!(entry.repayments[1].unpriced);
+        assert!(entry.repaid_usd().is_none());
+    }
+}
@@ -0,0 +1,290 @@
+//! Durable lease ledger store.
+//!
+//! A single locked [`LedgerImage`] of per-lease entries, persisted as a
+//! whole-image JSON file after every change with the same discipline as the
+//! transfer store: unique temp file, `sync_all`, rename into place, parent
+//! directory fsync. A corrupt image on load falls back to `<path>.bak` and
+//! otherwise fails loudly — an empty ledger would silently push every lease
+//! back onto ETL PnL.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::error::AppError;
+use crate::image_file::ImageFile;
+
+use super::{
+    LeaseLedgerEntry, LeaseRepayment, LedgerAmount, MAX_REPAYMENTS_PER_LEASE, MAX_TRACKED_LEASES,
+};
+
+/// Everything the ledger persists, written as one image.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct LedgerImage {
+    /// lease address -> entry
+    leases: HashMap<String, LeaseLedgerEntry>,
+}
+
+/// The durable lease ledger.
+///
+/// The image is guarded by a single std [`Mutex`], never held across an
+/// `.await`. A separate async `write_gate` serializes persist calls so that
+/// the image written to disk is always the newest snapshot.
+pub struct LeaseLedgerStore {
+    file: ImageFile,
+    image: Mutex<LedgerImage>,
+    write_gate: tokio::sync::Mutex<()>,
+}
+
+impl LeaseLedgerStore {
+    /// Bind a store to `path` with an empty ledger — the create path when no
+    /// prior image exists.
+    pub fn create(path: PathBuf) -> Self {
+        Self {
+            file: ImageFile::new(path, "lease ledger"),
+            image: Mutex::new(LedgerImage::default()),
+            write_gate: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Load an existing image from `path`. A corrupt primary image falls back
+    /// to `<path>.bak`; if neither loads, this returns `Err`.
+    pub async fn load(path: PathBuf) -> Result<Self, AppError> {
+        let file = ImageFile::new(path, "lease ledger");
+        let image = file.load().await?;
+        Ok(Self {
+            file,
+            image: Mutex::new(image),
+            write_gate: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Durably write the current ledger to the store's path.
+    pub async fn persist(&self) -> Result<(), AppError> {
+        // Snapshot INSIDE the write gate so snapshot order equals rename order.
+        let _write = self.write_gate.lock().await;
+        let snapshot = self.lock().clone();
+        self.file.write(&snapshot).await
+    }
+
+    /// The recorded entry for `lease`, if the backend saw it open.
+    pub fn get(&self, lease: &str) -> Option<LeaseLedgerEntry> {
+        self.lock().leases.get(lease).cloned()
+    }
+
+    /// Record a lease's opening. A lease already in the ledger is left as is.
+    pub async fn record_opening(
+        &self,
+        lease: &str,
+        entry: LeaseLedgerEntry,
+    ) -> Result<(), AppError> {
+        {
+            let mut image = self.lock();
+            if image.leases.contains_key(lease) {
+                return Ok(());
+            }
+            if image.leases.len() >= MAX_TRACKED_LEASES {
+                let oldest = image
+                    .leases
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.opened_at)
+                    .map(|(address, _)| address.clone());
+                if let Some(oldest) = oldest {
+                    image.leases.remove(&oldest);
+                }
+            }
+            image.leases.insert(lease.to_string(), entry);
+        }
+        self.persist().await
+    }
+
+    /// Append a repayment to a recorded lease. Unknown leases are ignored; a
+    /// lease past [`MAX_REPAYMENTS_PER_LEASE`] is dropped from the ledger.
+    pub async fn record_repayment(
+        &self,
+        lease: &str,
+        repayment: LeaseRepayment,
+    ) -> Result<(), AppError> {
+        {
+            let mut image = self.lock();
+            let Some(entry) = image.leases.get_mut(lease) else {
+                return Ok(());
+            };
+            if entry
+                .repayments
+                .iter()
+                .any(|r| r.tx_hash == repayment.tx_hash)
+            {
+                return Ok(());
+            }
+            if entry.repayments.len() >= MAX_REPAYMENTS_PER_LEASE {
+                image.leases.remove(lease);
+            } else {
+                entry.repayments.push(repayment);
+            }
+        }
+        self.persist().await
+    }
+
+    /// Fill in the post-swap position of a recorded lease, once it is known.
+    /// Unknown leases and leases whose position is already set are ignored.
+    pub async fn record_opened_position(
+        &self,
+        lease: &str,
+        position: LedgerAmount,
+    ) -> Result<(), AppError> {
+        {
+            let mut image = self.lock();
+            let Some(entry) = image.leases.get_mut(lease) else {
+                return Ok(());
+            };
+            if entry.opened_position.is_some() {
+                return Ok(());
+            }
+            entry.opened_position = Some(position);
+        }
+        self.persist().await
+    }
+
+    /// Drop every lease (after missing events whose targets are unknown).
+    pub async fn forget_all(&self) {
+        self.lock().leases.clear();
+        if let Err(e) = self.persist().await {
+            warn!("Failed to persist cleared lease ledger: {}", e);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LedgerImage> {
+        self.image.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Ephemeral store bound to a unique temp file, for tests only.
+    #[cfg(test)]
+    pub fn ephemeral() -> Self {
+        let path = crate::image_file::ephemeral_path("lease-ledger");
+        Self::create(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn usdc(usd: f64) -> LedgerAmount {
+        LedgerAmount {
+            ticker: "USDC".to_string(),
+            amount: "1000000".to_string(),
+            usd,
+        }
+    }
+
+    fn entry() -> LeaseLedgerEntry {
+        LeaseLedgerEntry {
+            protocol: "P".to_string(),
+            opened_at: Utc::now(),
+            downpayment: usdc(100.0),
+            loan: usdc(200.0),
+            opened_position: None,
+            repayments: Vec::new(),
+        }
+    }
+
+    fn repayment(tx_hash: &str) -> LeaseRepayment {
+        LeaseRepayment {
+            tx_hash: tx_hash.to_string(),
+            at: Utc::now(),
+            payment: usdc(10.0),
+            unpriced: false,
+            principal: 9,
+            loan_interest: 1,
+            margin_interest: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn opening_and_repayments_survive_reload() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("lease_ledger.json");
+        let store = LeaseLedgerStore::create(path.clone());
+        store.record_opening("nolus1lease", entry()).await.unwrap();
+        store
+            .record_repayment("nolus1lease", repayment("A"))
+            .await
+            .unwrap();
+
+        let reloaded = LeaseLedgerStore::load(path).await.unwrap();
+        let recorded = reloaded.get("nolus1lease").unwrap();
+        assert_eq!(recorded.repayments.len(), 1);
+        assert_eq!(recorded.downpayment, usdc(100.0));
+    }
+
+    #[tokio::test]
+    async fn duplicate_repayment_tx_is_recorded_once() {
+        let store = LeaseLedgerStore::ephemeral();
+        store.record_opening("nolus1lease", entry()).await.unwrap();
+        for _ in 0..2 {
+            store
+                .record_repayment("nolus1lease", repayment("A"))
+                .await
+                .unwrap();
+        }
+        assert_eq!(store.get("nolus1lease").unwrap().repayments.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn repayment_for_unknown_lease_is_ignored() {
+        let store = LeaseLedgerStore::ephemeral();
+        store
+            .record_repayment("nolus1unknown", repayment("A"))
+            .await
+            .unwrap();
+        assert!(store.get("nolus1unknown").is_none());
+    }
+
+    #[tokio::test]
+    async fn reopening_keeps_first_record() {
+        let store = LeaseLedgerStore::ephemeral();
+        store.record_opening("nolus1lease", entry()).await.unwrap();
+        let mut second = entry();
+        second.downpayment = usdc(999.0);
+        store.record_opening("nolus1lease", second).await.unwrap();
+        assert_eq!(store.get("nolus1lease").unwrap().downpayment, usdc(100.0));
+    }
+
+    #[tokio::test]
+    async fn opened_position_is_set_once() {
+        let store = LeaseLedgerStore::ephemeral();
+        store.record_opening("nolus1lease", entry()).await.unwrap();
+        store
+            .record_opened_position("nolus1lease", usdc(297.0))
+            .await
+            .unwrap();
+        store
+            .record_opened_position("nolus1lease", usdc(1.0))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get("nolus1lease").unwrap().opened_position,
+            Some(usdc(297.0))
+        );
+
+        store
+            .record_opened_position("nolus1unknown", usdc(1.0))
+            .await
+            .unwrap();
+        assert!(store.get("nolus1unknown").is_none());
+    }
+
+    #[tokio::test]
+    async fn corrupt_image_without_backup_fails_loudly() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("lease_ledger.json");
+        tokio::fs::write(&path, b"[").await.unwrap();
+        assert!(LeaseLedgerStore::load(path).await.is_err());
+    }
+}
@@ -17,20 +17,27 @@ use tracing::{info, warn};
 
 use crate::middleware::{
     admin_auth_middleware, cache_control_middleware, create_rate_limit_state,
-    rate_limit_middleware, standard_rate_limit_config, start_cleanup_task,
-    strict_rate_limit_config,
+    rate_limit_middleware, request_id_middleware, require_admin_scope, standard_rate_limit_config,
+    start_cleanup_task, strict_rate_limit_config,
 };
 
+mod admin_keys;
+mod audit;
 pub mod chain_events;
 mod config;
 mod config_store;
 pub mod data_cache;
+mod earn_ledger;
 mod error;
 mod external;
 mod handlers;
 mod http_utils;
+mod image_file;
+mod lease_ledger;
 mod middleware;
+mod notifications;
 mod num_utils;
+mod price_history;
 mod propagation;
 mod query_types;
 pub mod refresh;
@@ -41,6 +48,7 @@ mod validation;
 #[cfg(test)]
 mod test_utils;
 
+use crate::admin_keys::AdminScope;
 use crate::config::{AppConfig, ServerConfig};
 use crate::config_store::ConfigStore;
 use crate::handlers::websocket::WebSocketManager;
@@ -56,6 +64,29 @@ const DEFAULT_TRANSFER_STORE_PATH: &str = "./data/transfers.json";
 /// Retention window (hours) a terminal transfer record is kept before pruning.
 const TRANSFER_RETENTION_HOURS: i64 = 24;
 
+/// Default filesystem path for the durable notification outbox image.
+/// Override with the `NOTIFICATION_STORE_PATH` environment variable.
+const DEFAULT_NOTIFICATION_STORE_PATH: &str = "./data/notifications.json";
+
+/// Retention window (days) for recorded notification events.
+const NOTIFICATION_RETENTION_DAYS: i64 = 30;
+
+/// Default filesystem path for the durable price candle image.
+/// Override with the `PRICE_HISTORY_PATH` environment variable.
+const DEFAULT_PRICE_HISTORY_PATH: &str = "./data/price_history.json";
+
+/// Default filesystem path for the durable lease ledger image.
+/// Override with the `LEASE_LEDGER_PATH` environment variable.
+const DEFAULT_LEASE_LEDGER_PATH: &str = "./data/lease_ledger.json";
+
+/// Default filesystem path for the durable earn ledger image.
+/// Override with the `EARN_LEDGER_PATH` environment variable.
+const DEFAULT_EARN_LEDGER_PATH: &str = "./data/earn_ledger.json";
+
+/// Default filesystem path for the durable admin key image.
+/// Override with the `ADMIN_KEYS_PATH` environment variable.
+const DEFAULT_ADMIN_KEYS_PATH: &str = "./data/admin_keys.json";
+
 /// Application state shared across all handlers
 pub struct AppState {
     pub config: AppConfig,
@@ -74,6 +105,16 @@ pub struct AppState {
     pub llm_client: LlmClient,
     /// Durable tracking set for in-flight Nolus<->Solana transfers.
     pub transfer_store: transfer_tracker::TransferStore,
+    /// Durable per-address notification history and webhook outbox.
+    pub notification_store: notifications::NotificationStore,
+    /// Bounded OHLC candles recorded from oracle prices.
+    pub price_history: price_history::PriceHistoryStore,
+    /// Lease openings and repayments observed on-chain, for ETL-independent PnL.
+    pub lease_ledger: lease_ledger::LeaseLedgerStore,
+    /// LPP deposits, withdrawals and reward claims, for earn position history.
+    pub earn_ledger: earn_ledger::EarnLedgerStore,
+    /// Named, scoped admin API keys (hashed at rest), rotatable at runtime.
+    pub admin_keys: admin_keys::AdminKeyStore,
     /// Server startup time for uptime tracking
     pub startup_time: Instant,
 }
@@ -138,9 +179,11 @@ async fn main() -> anyhow::Result<()> {
         http_client.clone(),
     );
 
-    // Initialize chain client for direct blockchain queries
-    let chain_client = external::chain::ChainClient::new(
-        config.external.nolus_rest_url.clone(),
+    // Initialize chain client for direct blockchain queries, failing over
+    // across the primary and fallback Nolus nodes
+    let chain_client = external::chain::ChainClient::with_endpoints(
+        config.external.nolus_rest_urls(),
+        config.external.nolus_rpc_urls(),
         http_client.clone(),
     );
 
@@ -190,30 +233,78 @@ async fn main() -> anyhow::Result<()> {
     // Initialize the durable transfer tracking set. An existing image is loaded
     // (a corrupt image fails loud rather than silently starting empty); a fresh
     // deployment starts an empty set.
-    let transfer_store_path = std::path::PathBuf::from(
-        std::env::var("TRANSFER_STORE_PATH")
-            .unwrap_or_else(|_err| DEFAULT_TRANSFER_STORE_PATH.to_string()),
-    );
-    if let Some(parent) = transfer_store_path.parent() {
-        if !parent.as_os_str().is_empty() {
-            tokio::fs::create_dir_all(parent).await?;
-        }
-    }
     let transfer_retention = chrono::Duration::hours(TRANSFER_RETENTION_HOURS);
-    let transfer_store = if transfer_store_path.exists() {
-        transfer_tracker::TransferStore::load(
-            transfer_store_path,
-            transfer_tracker::DEFAULT_ACTIVE_SET_CAP,
-            transfer_retention,
-        )
-        .await?
-    } else {
-        transfer_tracker::TransferStore::create(
-            transfer_store_path,
-            transfer_tracker::DEFAULT_ACTIVE_SET_CAP,
-            transfer_retention,
-        )
-    };
+    let transfer_store = image_file::open_store(
+        "TRANSFER_STORE_PATH",
+        DEFAULT_TRANSFER_STORE_PATH,
+        |path| {
+            transfer_tracker::TransferStore::load(
+                path,
+                transfer_tracker::DEFAULT_ACTIVE_SET_CAP,
+                transfer_retention,
+            )
+        },
+        |path| {
+            transfer_tracker::TransferStore::create(
+                path,
+                transfer_tracker::DEFAULT_ACTIVE_SET_CAP,
+                transfer_retention,
+            )
+        },
+    )
+    .await?;
+
+    // Initialize the notification outbox with the same load-or-create policy.
+    let notification_retention = chrono::Duration::days(NOTIFICATION_RETENTION_DAYS);
+    let notification_store = image_file::open_store(
+        "NOTIFICATION_STORE_PATH",
+        DEFAULT_NOTIFICATION_STORE_PATH,
+        |path| notifications::NotificationStore::load(path, notification_retention),
+        |path| notifications::NotificationStore::create(path, notification_retention),
+    )
+    .await?;
+
+    // Initialize the price candle store. Candles are a derived cache that
+    // refills from live prices and ETL backfill, so an unreadable image is
+    // not worth refusing to start over.
+    let price_history = image_file::open_store(
+        "PRICE_HISTORY_PATH",
+        DEFAULT_PRICE_HISTORY_PATH,
+        |path| async move {
+            Ok(price_history::PriceHistoryStore::load(path.clone())
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("{}; starting with empty price history", e);
+                    price_history::PriceHistoryStore::create(path)
+                }))
+        },
+        price_history::PriceHistoryStore::create,
+    )
+    .await?;
+
+    // Initialize the lease ledger, earn ledger and admin key store with the
+    // same load-or-create policy.
+    let lease_ledger = image_file::open_store(
+        "LEASE_LEDGER_PATH",
+        DEFAULT_LEASE_LEDGER_PATH,
+        lease_ledger::LeaseLedgerStore::load,
+        lease_ledger::LeaseLedgerStore::create,
+    )
+    .await?;
+    let earn_ledger = image_file::open_store(
+        "EARN_LEDGER_PATH",
+        DEFAULT_EARN_LEDGER_PATH,
+        earn_ledger::EarnLedgerStore::load,
+        earn_ledger::EarnLedgerStore::create,
+    )
+    .await?;
+    let admin_keys = image_file::open_store(
+        "ADMIN_KEYS_PATH",
+        DEFAULT_ADMIN_KEYS_PATH,
+        admin_keys::AdminKeyStore::load,
+        admin_keys::AdminKeyStore::create,
+    )
+    .await?;
 
     // Create shared application state
     let state = Arc::new(AppState {
@@ -230,6 +321,11 @@ async fn main() -> anyhow::Result<()> {
         translation_storage,
         llm_client,
         transfer_store,
+        notification_store,
+        price_history,
+        lease_ledger,
+        earn_ledger,
+        admin_keys,
         startup_time: Instant::now(),
     });
 
@@ -244,13 +340,19 @@ async fn main() -> anyhow::Result<()> {
     // Create event channels for CometBFT WebSocket events
     let event_channels = chain_events::EventChannels::new();
 
+    // Probe fallback Nolus nodes so failover has current heights and scores
+    state.chain_client.start_endpoint_probes();
+
     // Start CometBFT WebSocket client (connects, subscribes, dispatches events)
-    chain_events::start(&state.config.external.nolus_rpc_url, event_channels.clone());
+    chain_events::start(
+        state.chain_client.rpc_endpoints().clone(),
+        event_channels.clone(),
+    );
 
     // Start background refresh tasks (prices: event-driven, others: timer-driven)
     refresh::start_all(state.clone(), &event_channels);
 
-    // Start WebSocket background tasks (lease/earn/prices: event-driven, skip: timer)
+    // Start WebSocket background tasks (lease/earn/prices/tx: event-driven, skip: timer)
     handlers::websocket::start_price_update_task(
         state.clone(),
         event_channels.new_block.subscribe(),
@@ -262,6 +364,11 @@ async fn main() -> anyhow::Result<()> {
     )
     .await;
     handlers::websocket::start_skip_tracking_task(state.clone()).await;
+    handlers::websocket::start_tx_confirmation_task(
+        state.clone(),
+        event_channels.new_block.subscribe(),
+    )
+    .await;
     handlers::websocket::start_earn_monitor_task(
         state.clone(),
         event_channels.contract_exec.subscribe(),
@@ -272,8 +379,23 @@ async fn main() -> anyhow::Result<()> {
         event_channels.bank_transfer.subscribe(),
     )
     .await;
+    handlers::websocket::start_portfolio_monitor_task(state.clone()).await;
+    handlers::websocket::start_staking_schedule_monitor_task(state.clone()).await;
     handlers::websocket::start_stale_connection_reaper(state.clone()).await;
 
+    // Deliver recorded notifications to registered webhooks
+    notifications::start_delivery_task(state.clone(), notifications::delivery_client()?).await;
+
+    // Backfill price candles from ETL, then persist them periodically
+    price_history::start_price_history_task(state.clone()).await;
+
+    // Record lease openings and repayments for ETL-independent PnL
+    lease_ledger::start_ledger_task(state.clone(), event_channels.contract_exec.subscribe()).await;
+
+    // Record LPP deposits, withdrawals and reward claims for earn history
+    earn_ledger::start_earn_ledger_task(state.clone(), event_channels.contract_exec.subscribe())
+        .await;
+
     // Build router
     let app = create_router(state);
 
@@ -426,6 +548,10 @@ fn create_router(state: Arc<AppState>) -> Router {
         .route("/currencies", get(handlers::currencies::get_currencies))
         .route("/currencies/{key}", get(handlers::currencies::get_currency))
         .route("/prices", get(handlers::currencies::get_prices))
+        .route(
+            "/prices/{ticker}/candles",
+            get(handlers::currencies::get_price_candles),
+        )
         .route("/balances", get(handlers::currencies::get_balances))
         // Solana (read) — balances + fresh transfer-timeout parameters
         .route(
@@ -450,13 +576,29 @@ fn create_router(state: Arc<AppState>) -> Router {
         // Earn (read)
         .route("/earn/pools", get(handlers::earn::get_pools))
         .route("/earn/pools/{pool_id}", get(handlers::earn::get_pool))
+        .route(
+            "/earn/pools/{pool_id}/analytics",
+            get(handlers::earn::get_pool_analytics),
+        )
         .route("/earn/positions", get(handlers::earn::get_positions))
+        .route(
+            "/earn/positions/history",
+            get(handlers::earn::get_positions_history),
+        )
         .route("/earn/stats", get(handlers::earn::get_earn_stats))
         // Staking (read)
         .route(
             "/staking/validators",
             get(handlers::staking::get_validators),
         )
+        .route(
+            "/staking/validators/scores",
+            get(handlers::validator_scoring::get_validator_scores),
+        )
+        .route(
+            "/staking/validators/recommend",
+            get(handlers::validator_scoring::recommend_delegations),
+        )
         .route(
             "/staking/validators/{address}",
             get(handlers::staking::get_validator),
@@ -466,6 +608,16 @@ fn create_router(state: Arc<AppState>) -> Router {
             "/staking/params",
             get(handlers::staking::get_staking_params),
         )
+        .route("/staking/schedule", get(handlers::staking::get_schedule))
+        .route(
+            "/staking/auto-compound/grants",
+            get(handlers::staking::get_auto_compound_grants),
+        )
+        // Portfolio (read) — net worth across balances, leases, earn, staking
+        .route(
+            "/portfolio/{address}",
+            get(handlers::portfolio::get_portfolio),
+        )
         // Swap (read)
         .route("/swap/config", get(handlers::swap::get_swap_config))
         .route("/swap/status/{tx_hash}", get(handlers::swap::get_status))
@@ -475,6 +627,19 @@ fn create_router(state: Arc<AppState>) -> Router {
             "/transfer/status/{id}",
             get(handlers::transfer::get_transfer_status),
         )
+        // Notifications (read) — event history, webhooks, dead letters
+        .route(
+            "/notifications/{address}",
+            get(handlers::notifications::get_notifications),
+        )
+        .route(
+            "/notifications/{address}/webhooks",
+            get(handlers::notifications::list_webhooks),
+        )
+        .route(
+            "/notifications/{address}/dead-letters",
+            get(handlers::notifications::get_dead_letters),
+        )
         // Referral (read)
         .route(
             "/referral/validate/{code}",
@@ -531,6 +696,10 @@ fn create_router(state: Arc<AppState>) -> Router {
             "/governance/proposals",
             get(handlers::governance::get_proposals),
         )
+        .route(
+            "/governance/proposals/{proposal_id}",
+            get(handlers::governance::get_proposal_detail),
+        )
         .route(
             "/governance/proposals/{proposal_id}/tally",
             get(handlers::governance::get_proposal_tally),
@@ -606,6 +775,7 @@ fn create_router(state: Arc<AppState>) -> Router {
     let write_routes = Router::new()
         // Leases (write)
         .route("/leases/quote", post(handlers::leases::get_lease_quote))
+        .route("/leases/simulate", post(handlers::leases::simulate_lease))
         .route("/leases/open", post(handlers::leases::open_lease))
         .route("/leases/repay", post(handlers::leases::repay_lease))
         .route("/leases/close", post(handlers::leases::close_lease))
@@ -613,9 +783,25 @@ fn create_router(state: Arc<AppState>) -> Router {
             "/leases/market-close",
             post(handlers::leases::market_close_lease),
         )
+        .route(
+            "/leases/close-policy",
+            post(handlers::leases::change_close_policy),
+        )
+        .route(
+            "/leases/partial-close",
+            post(handlers::leases::partial_close_lease),
+        )
+        .route(
+            "/leases/repay-with-collateral",
+            post(handlers::leases::repay_with_collateral),
+        )
         // Earn (write)
         .route("/earn/deposit", post(handlers::earn::deposit))
         .route("/earn/withdraw", post(handlers::earn::withdraw))
+        .route(
+            "/earn/withdraw/preview",
+            post(handlers::earn::preview_withdraw),
+        )
         // Staking (write)
         .route("/staking/delegate", post(handlers::staking::delegate))
         .route("/staking/undelegate", post(handlers::staking::undelegate))
@@ -624,12 +810,43 @@ fn create_router(state: Arc<AppState>) -> Router {
             "/staking/claim-rewards",
             post(handlers::staking::claim_rewards),
         )
+        .route(
+            "/staking/auto-compound",
+            post(handlers::staking::build_auto_compound),
+        )
+        .route(
+            "/staking/auto-compound/revoke",
+            post(handlers::staking::revoke_auto_compound),
+        )
+        // Governance (write)
+        .route("/governance/vote", post(handlers::governance::vote))
+        .route(
+            "/governance/vote-weighted",
+            post(handlers::governance::vote_weighted),
+        )
+        .route("/governance/deposit", post(handlers::governance::deposit))
+        .route(
+            "/governance/submit-proposal",
+            post(handlers::governance::submit_proposal),
+        )
         // Swap (write)
         .route("/swap/track", post(handlers::swap::track_transaction))
         .route("/swap/route", post(handlers::swap::get_route))
         .route("/swap/messages", post(handlers::swap::get_messages))
         // Transfer tracker (write) — register an in-flight route for tracking
         .route("/transfer/track", post(handlers::transfer::track_transfer))
+        // Cosmos tx (write) — simulate unsigned messages, relay signed tx bytes
+        .route("/tx/simulate", post(handlers::cosmos_tx::simulate_tx))
+        .route("/tx/broadcast", post(handlers::cosmos_tx::broadcast_tx))
+        // Notifications (write) — webhook registration and removal
+        .route(
+            "/notifications/{address}/webhooks",
+            post(handlers::notifications::register_webhook),
+        )
+        .route(
+            "/notifications/{address}/webhooks/{webhook_id}",
+            delete(handlers::notifications::delete_webhook),
+        )
         // Solana unsigned-tx build (write) — compose + simulate, return base64 v0 tx
         .route(
             "/solana/tx/create-ata",
@@ -663,11 +880,18 @@ fn create_router(state: Arc<AppState>) -> Router {
             async move { rate_limit_middleware(state, connect_info, req, next).await }
         }));
 
-    // Admin routes (protected with authentication middleware)
-    let admin_routes = Router::new()
+    // Admin routes (protected with authentication middleware). Each group is
+    // gated on its own scope so a named key only reaches the groups it holds.
+    let cache_admin_routes = Router::new()
         .route("/cache/stats", get(handlers::admin::get_cache_stats))
         .route("/cache/invalidate", post(handlers::admin::invalidate_cache))
-        // Translation Management
+        .route_layer(axum_middleware::from_fn_with_state(
+            AdminScope::Cache,
+            require_admin_scope,
+        ));
+
+    // Translation Management
+    let translation_admin_routes = Router::new()
         .route(
             "/translations/sync",
             post(handlers::translations::sync_translations),
@@ -728,6 +952,12 @@ fn create_router(state: Arc<AppState>) -> Router {
             "/translations/key-history/{lang}/{key}",
             get(handlers::translations::get_key_history),
         )
+        .route_layer(axum_middleware::from_fn_with_state(
+            AdminScope::Translations,
+            require_admin_scope,
+        ));
+
+    let gated_admin_routes = Router::new()
         // Gated Propagation Admin - Discovery
         .route(
             "/gated/currencies",
@@ -815,6 +1045,66 @@ fn create_router(state: Arc<AppState>) -> Router {
             "/gated/ui-settings/hidden-proposals/{id}",
             delete(handlers::gated_admin::remove_hidden_proposal),
         )
+        // Gated Propagation Admin - Version History
+        .route("/gated/versions", get(handlers::gated_admin::list_versions))
+        .route(
+            "/gated/versions/{id}",
+            get(handlers::gated_admin::get_version),
+        )
+        .route(
+            "/gated/versions/{id}/diff",
+            get(handlers::gated_admin::diff_version),
+        )
+        .route(
+            "/gated/versions/{id}/rollback",
+            post(handlers::gated_admin::rollback_version),
+        )
+        // Gated Propagation Admin - Drafts & Publish
+        .route("/gated/drafts", get(handlers::gated_admin::list_drafts))
+        .route(
+            "/gated/drafts/{resource}",
+            get(handlers::gated_admin::get_draft)
+                .put(handlers::gated_admin::stage_draft)
+                .delete(handlers::gated_admin::discard_draft),
+        )
+        .route(
+            "/gated/drafts/{resource}/publish",
+            post(handlers::gated_admin::publish_draft),
+        )
+        // Gated Propagation Admin - Dry-run Preview
+        .route(
+            "/gated/preview",
+            post(handlers::gated_admin::preview_gated_change),
+        )
+        .route_layer(axum_middleware::from_fn_with_state(
+            AdminScope::GatedConfig,
+            require_admin_scope,
+        ));
+
+    // Admin Key Management
+    let key_admin_routes = Router::new()
+        .route(
+            "/keys",
+            get(handlers::admin_keys::list_keys).post(handlers::admin_keys::create_key),
+        )
+        .route("/keys/{name}", delete(handlers::admin_keys::revoke_key))
+        .route(
+            "/keys/{name}/rotate",
+            post(handlers::admin_keys::rotate_key),
+        )
+        // Unified audit log (config + translations) - query, export, verify
+        .route("/audit", get(handlers::audit::query_audit))
+        .route("/audit/verify", get(handlers::audit::verify_audit))
+        .route_layer(axum_middleware::from_fn_with_state(
+            AdminScope::Keys,
+            require_admin_scope,
+        ));
+
+    let admin_routes = Router::new()
+        .merge(cache_admin_routes)
+        .merge(translation_admin_routes)
+        .merge(gated_admin_routes)
+        .merge(key_admin_routes)
         .layer(axum_middleware::from_fn_with_state(
             state.clone(),
             admin_auth_middleware,
@@ -855,6 +1145,7 @@ fn create_router(state: Arc<AppState>) -> Router {
         .fallback_service(spa_fallback)
         .layer(axum_middleware::from_fn(cache_control_middleware))
         .layer(TraceLayer::new_for_http())
+        .layer(axum_middleware::from_fn(request_id_middleware))
         .layer(CompressionLayer::new())
         .layer(cors)
         .with_state(state)
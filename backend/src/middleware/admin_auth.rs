@@ -1,17 +1,22 @@
 //! Admin authentication middleware
 //!
-//! Provides Bearer token authentication for admin endpoints.
+//! Provides Bearer token authentication for admin endpoints and per-group
+//! scope checks for named admin keys.
 
 use axum::{
     body::Body,
-    extract::State,
+    extract::{ConnectInfo, State},
     http::{Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use tracing::{error, warn};
 
+use super::request_id::RequestId;
+use crate::admin_keys::{AdminIdentity, AdminScope};
+use crate::audit::RequestContext;
 use crate::AppState;
 
 /// Admin authentication error response
@@ -36,6 +41,37 @@ impl IntoResponse for AdminAuthError {
     }
 }
 
+/// The caller's IP for the audit log: the socket peer, or, when the peer is
+/// a trusted proxy, the rightmost `X-Forwarded-For` hop that isn't one.
+/// Hops left of that were supplied by the client and can't be believed.
+fn audit_client_ip<B>(
+    request: &Request<B>,
+    peer: Option<IpAddr>,
+    trusted_proxies: &[IpAddr],
+) -> Option<IpAddr> {
+    let mut client = peer?;
+    if !trusted_proxies.contains(&client) {
+        return Some(client);
+    }
+    let forwarded: Vec<&str> = request
+        .headers()
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .collect();
+    for hop in forwarded.iter().rev() {
+        let Ok(ip) = hop.trim().parse::<IpAddr>() else {
+            break;
+        };
+        client = ip;
+        if !trusted_proxies.contains(&ip) {
+            break;
+        }
+    }
+    Some(client)
+}
+
 /// Extract Bearer token from Authorization header
 fn extract_bearer_token(auth_header: &str) -> Option<&str> {
     auth_header.strip_prefix("Bearer ")
@@ -43,15 +79,17 @@ fn extract_bearer_token(auth_header: &str) -> Option<&str> {
 
 /// Admin authentication middleware
 ///
-/// Validates the Bearer token in the Authorization header against the configured admin API key.
+/// Validates the Bearer token in the Authorization header against the configured
+/// admin API key (the `root` identity) and the named keys in the admin key store,
+/// then attaches the matching [`AdminIdentity`] as a request extension.
 /// Returns 401 Unauthorized if:
 /// - Admin API is disabled
 /// - Authorization header is missing
 /// - Token format is invalid
-/// - Token doesn't match the configured API key
+/// - Token matches neither the configured API key nor a named key
 pub async fn admin_auth_middleware(
     State(state): State<Arc<AppState>>,
-    request: Request<Body>,
+    mut request: Request<Body>,
     next: Next,
 ) -> Response {
     // Check if admin API is enabled
@@ -118,22 +156,79 @@ pub async fn admin_auth_middleware(
     };
 
     // Validate token using constant-time comparison to prevent timing attacks
-    if !constant_time_compare(token.as_bytes(), state.config.admin.api_key.as_bytes()) {
+    let identity = if constant_time_compare(token.as_bytes(), state.config.admin.api_key.as_bytes())
+    {
+        Some(AdminIdentity::root())
+    } else {
+        state.admin_keys.identify(token)
+    };
+    let Some(identity) = identity else {
         warn!("Admin API access attempted with invalid token");
         return AdminAuthError {
             status: StatusCode::UNAUTHORIZED,
             message: "Invalid API key",
         }
         .into_response();
-    }
+    };
+
+    // Token is valid, proceed with the request. Audit entries written while
+    // handling it pick up the caller's IP and request ID from this scope.
+    let context = RequestContext {
+        ip: audit_client_ip(
+            &request,
+            request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|info| info.0.ip()),
+            &state.config.admin.trusted_proxies,
+        )
+        .map(|ip| ip.to_string()),
+        request_id: request
+            .extensions()
+            .get::<RequestId>()
+            .map(|id| id.0.clone()),
+    };
+    request.extensions_mut().insert(identity);
+    crate::audit::scope(context, next.run(request)).await
+}
 
-    // Token is valid, proceed with the request
+/// Admin scope middleware
+///
+/// Layered per route group behind `admin_auth_middleware`, with the group's
+/// [`AdminScope`] as its state. Returns 403 Forbidden if the authenticated
+/// identity is not scoped to the group (or, for `read_only` keys, if the
+/// method is not a read).
+pub async fn require_admin_scope(
+    State(group): State<AdminScope>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let allowed = request
+        .extensions()
+        .get::<AdminIdentity>()
+        .is_some_and(|identity| identity.allows(group, request.method()));
+    if !allowed {
+        warn!(
+            "Admin API access denied for {:?} on {} {}",
+            request
+                .extensions()
+                .get::<AdminIdentity>()
+                .map(|identity| identity.name.as_str()),
+            request.method(),
+            request.uri().path()
+        );
+        return AdminAuthError {
+            status: StatusCode::FORBIDDEN,
+            message: "API key is not scoped for this endpoint",
+        }
+        .into_response();
+    }
     next.run(request).await
 }
 
 /// Constant-time string comparison to prevent timing attacks.
 /// Always iterates over the longer slice to avoid leaking length info via timing.
-fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
+pub fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
     let len_matches = a.len() == b.len();
     let max_len = a.len().max(b.len());
 
@@ -231,6 +326,34 @@ mod tests {
         assert_eq!(response.status(), StatusCode::FORBIDDEN);
     }
 
+    fn forwarded(value: &str) -> Request<()> {
+        Request::builder()
+            .header("x-forwarded-for", value)
+            .body(())
+            .expect("valid request")
+    }
+
+    #[test]
+    fn audit_ip_ignores_forwarded_header_from_untrusted_peer() {
+        let peer: IpAddr = "192.0.2.1".parse().unwrap();
+        let proxy: IpAddr = "10.0.0.2".parse().unwrap();
+        let req = forwarded("203.0.113.9");
+        assert_eq!(audit_client_ip(&req, Some(peer), &[proxy]), Some(peer));
+        assert_eq!(audit_client_ip(&req, Some(peer), &[]), Some(peer));
+    }
+
+    #[test]
+    fn audit_ip_takes_rightmost_untrusted_hop_behind_a_trusted_proxy() {
+        let proxy: IpAddr = "10.0.0.2".parse().unwrap();
+        let inner: IpAddr = "10.0.0.3".parse().unwrap();
+        // The leftmost hop is whatever the client claimed.
+        let req = forwarded("198.51.100.1, 203.0.113.9, 10.0.0.3");
+        assert_eq!(
+            audit_client_ip(&req, Some(proxy), &[proxy, inner]),
+            Some("203.0.113.9".parse().unwrap())
+        );
+    }
+
     // ========================================================================
     // Middleware integration tests — exercise every branch of
     // `admin_auth_middleware` end-to-end through an axum Router.
@@ -410,4 +533,85 @@ mod tests {
             "body must not mention api_key internals, got: {body}"
         );
     }
+
+    /// Router with a gated-config GET and POST behind both middlewares, plus
+    /// a named key minted with `scopes`. Returns the router and the key.
+    async fn scoped_router(scopes: &[AdminScope]) -> (Router, String) {
+        let state = test_app_state_with_config(test_config_with_admin(true, "root-key")).await;
+        let issued = state
+            .admin_keys
+            .create_key("ops", scopes)
+            .await
+            .expect("mint key");
+        let router = Router::new()
+            .route(
+                "/gated",
+                get(|| async { "read" }).post(|| async { "write" }),
+            )
+            .route_layer(from_fn_with_state(
+                AdminScope::GatedConfig,
+                require_admin_scope,
+            ))
+            .layer(from_fn_with_state(state.clone(), admin_auth_middleware))
+            .with_state(state);
+        (router, issued.key)
+    }
+
+    fn scoped_request(method: &str, token: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri("/gated")
+            .header("Authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .expect("valid request")
+    }
+
+    #[tokio::test]
+    async fn named_key_with_group_scope_is_allowed_to_write() {
+        let (app, key) = scoped_router(&[AdminScope::GatedConfig]).await;
+        let resp = app
+            .oneshot(scoped_request("POST", &key))
+            .await
+            .expect("router call");
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(collect_body_str(resp).await, "write");
+    }
+
+    #[tokio::test]
+    async fn named_key_without_group_scope_is_forbidden() {
+        let (app, key) = scoped_router(&[AdminScope::Translations]).await;
+        let resp = app
+            .oneshot(scoped_request("GET", &key))
+            .await
+            .expect("router call");
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        let body = collect_body_str(resp).await;
+        assert!(body.contains("not scoped"), "body: {body}");
+    }
+
+    #[tokio::test]
+    async fn read_only_key_can_read_but_not_write() {
+        let (app, key) = scoped_router(&[AdminScope::ReadOnly]).await;
+        let read = app
+            .clone()
+            .oneshot(scoped_request("GET", &key))
+            .await
+            .expect("router call");
+        assert_eq!(read.status(), StatusCode::OK);
+        let write = app
+            .oneshot(scoped_request("POST", &key))
+            .await
+            .expect("router call");
+        assert_eq!(write.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn root_key_passes_every_scope_check() {
+        let (app, _key) = scoped_router(&[AdminScope::Cache]).await;
+        let resp = app
+            .oneshot(scoped_request("POST", "root-key"))
+            .await
+            .expect("router call");
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
 }
@@ -1,7 +1,9 @@
 pub mod admin_auth;
 pub mod cache_control;
 pub mod rate_limit;
+pub mod request_id;
 
 pub use admin_auth::*;
 pub use cache_control::*;
 pub use rate_limit::*;
+pub use request_id::*;
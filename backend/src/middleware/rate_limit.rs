@@ -194,7 +194,7 @@ pub fn start_cleanup_task(state: Arc<RateLimitState>) {
 }
 
 /// Extract client IP from request
-fn extract_client_ip<B>(
+pub fn extract_client_ip<B>(
     req: &Request<B>,
     connect_info: Option<&ConnectInfo<SocketAddr>>,
 ) -> Option<IpAddr> {
@@ -0,0 +1,109 @@
+//! Request ID middleware
+//!
+//! Tags every request with an `x-request-id`. A well-formed ID sent by the
+//! client or reverse proxy is kept; otherwise a UUID is generated. The ID is
+//! stored as a [`RequestId`] extension, forwarded in the request headers and
+//! echoed on the response so log lines and audit entries can be correlated.
+
+use axum::{
+    body::Body,
+    http::{HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+
+/// Header carrying the request ID in both directions
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied ID that is accepted as-is
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// The ID of the request being handled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// Middleware that assigns and propagates the request ID
+pub async fn request_id_middleware(mut request: Request<Body>, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map_or_else(|| uuid::Uuid::new_v4().to_string(), String::from);
+
+    let header = HeaderValue::from_str(&id).ok();
+    if let Some(ref value) = header {
+        request
+            .headers_mut()
+            .insert(REQUEST_ID_HEADER, value.clone());
+    }
+    request.extensions_mut().insert(RequestId(id));
+
+    let mut response = next.run(request).await;
+    if let Some(value) = header {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Accept only short IDs of URL-safe characters so a client cannot smuggle
+/// arbitrary text into logs and audit exports.
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::Extension, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(|Extension(id): Extension<RequestId>| async move { id.0 }),
+            )
+            .layer(axum::middleware::from_fn(request_id_middleware))
+    }
+
+    async fn send(header: Option<&str>) -> (String, String) {
+        let mut builder = Request::builder().uri("/");
+        if let Some(value) = header {
+            builder = builder.header(REQUEST_ID_HEADER, value);
+        }
+        let resp = app()
+            .oneshot(builder.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let echoed = resp.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (echoed, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn keeps_well_formed_client_id() {
+        let (echoed, seen) = send(Some("edge-42.a:b")).await;
+        assert_eq!(echoed, "edge-42.a:b");
+        assert_eq!(seen, "edge-42.a:b");
+    }
+
+    #[tokio::test]
+    async fn generates_id_when_missing_or_malformed() {
+        let (echoed, seen) = send(None).await;
+        assert!(uuid::Uuid::parse_str(&echoed).is_ok());
+        assert_eq!(echoed, seen);
+
+        let (echoed, _) = send(Some("has spaces, commas")).await;
+        assert!(uuid::Uuid::parse_str(&echoed).is_ok());
+    }
+}
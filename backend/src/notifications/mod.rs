@@ -0,0 +1,509 @@
+//! Per-address notification outbox: event history plus signed webhook
+//! delivery.
+//!
+//! Monitors record lease, earn, transfer and governance events for the
+//! addresses they watch. Each event lands in the address's bounded history
+//! (served by `GET /api/notifications/{address}`) and fans out one pending
+//! delivery per matching webhook. [`start_delivery_task`] POSTs due deliveries
+//! with an HMAC-SHA256 signature, reschedules failures with exponential
+//! backoff, and moves a delivery to the dead-letter list once its attempts are
+//! exhausted. Durable state lives in [`store`]; recorded events are written
+//! with the next delivery pass rather than one image write per event.
+//!
+//! Registering a webhook requires an ADR-036 proof that the caller controls
+//! the address ([`ownership`]). Deliveries go through [`delivery_client`],
+//! which never follows redirects and refuses to connect to hosts that resolve
+//! to internal addresses.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tracing::{debug, info, warn};
+use utoipa::ToSchema;
+
+use crate::error::AppError;
+use crate::AppState;
+
+pub mod ownership;
+mod store;
+
+pub use store::{DueDelivery, NotificationStore};
+
+/// Events kept per address; the oldest are dropped first.
+pub const MAX_EVENTS_PER_ADDRESS: usize = 500;
+
+/// Addresses with history held at once. Past this, the least recently active
+/// address without a webhook is evicted.
+pub const MAX_TRACKED_ADDRESSES: usize = 10_000;
+
+/// Webhooks a single address may register.
+pub const MAX_WEBHOOKS_PER_ADDRESS: usize = 5;
+
+/// Webhooks registered across all addresses.
+pub const MAX_WEBHOOKS: usize = 20_000;
+
+/// Dead letters kept across all addresses; the oldest are dropped first.
+pub const MAX_DEAD_LETTERS: usize = 1_000;
+
+/// Delivery attempts before a delivery is dead-lettered.
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 8;
+
+/// Delay before the first retry; doubles with each further attempt.
+const RETRY_BASE_SECS: i64 = 30;
+
+/// Upper bound on the retry delay.
+const RETRY_MAX_SECS: i64 = 3_600;
+
+/// How often the delivery worker scans for due deliveries.
+const DELIVERY_POLL_INTERVAL: StdDuration = StdDuration::from_secs(5);
+
+/// Deliveries attempted per worker pass.
+const DELIVERY_BATCH_SIZE: usize = 64;
+
+/// Concurrent webhook POSTs per worker pass.
+const DELIVERY_FANOUT_CAP: usize = 8;
+
+/// Per-request timeout for a webhook POST.
+const DELIVERY_TIMEOUT: StdDuration = StdDuration::from_secs(10);
+
+/// Header carrying `t=<unix seconds>,v1=<hex HMAC-SHA256>`.
+pub const SIGNATURE_HEADER: &str = "x-nolus-signature";
+/// Header carrying the event id, stable across retries (for receiver dedup).
+pub const EVENT_ID_HEADER: &str = "x-nolus-event-id";
+/// Header carrying the 1-based attempt number.
+pub const ATTEMPT_HEADER: &str = "x-nolus-delivery-attempt";
+
+/// Event category, used for webhook filtering and history queries.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    Lease,
+    Earn,
+    Transfer,
+    Governance,
+}
+
+/// A recorded event in an address's history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct NotificationEvent {
+    /// Store-wide sequence number (newer events have larger values)
+    pub seq: u64,
+    pub id: String,
+    pub address: String,
+    pub kind: NotificationKind,
+    /// Event name within the kind (e.g. `liquidated`, `lease_ltv_warning`)
+    pub event: String,
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An event to record; the store assigns its id, sequence and timestamp.
+#[derive(Debug, Clone)]
+pub struct NotificationDraft {
+    pub address: String,
+    pub kind: NotificationKind,
+    pub event: String,
+    pub payload: serde_json::Value,
+}
+
+impl NotificationDraft {
+    pub fn new(
+        address: &str,
+        kind: NotificationKind,
+        event: &str,
+        payload: serde_json::Value,
+    ) -> Self {
+        Self {
+            address: address.to_string(),
+            kind,
+            event: event.to_string(),
+            payload,
+        }
+    }
+}
+
+/// A webhook registered by an address. `secret` signs every delivery and is
+/// only returned to the caller at registration time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookRegistration {
+    pub id: String,
+    pub address: String,
+    pub url: String,
+    pub secret: String,
+    /// Kinds delivered to this webhook; empty means every kind
+    pub kinds: Vec<NotificationKind>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookRegistration {
+    pub fn accepts(&self, kind: NotificationKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+}
+
+/// A delivery awaiting its next attempt. Carries the event itself so history
+/// trimming never strands a pending delivery.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingDelivery {
+    pub id: String,
+    pub webhook_id: String,
+    pub address: String,
+    pub event: NotificationEvent,
+    /// Attempts made so far
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+}
+
+/// A delivery that exhausted its attempts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub delivery_id: String,
+    pub webhook_id: String,
+    pub address: String,
+    pub url: String,
+    pub event: NotificationEvent,
+    pub attempts: u32,
+    pub last_error: String,
+    pub dead_at: DateTime<Utc>,
+}
+
+/// Delay before retrying a delivery that has failed `attempts` times.
+pub fn retry_delay(attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    let secs = RETRY_BASE_SECS
+        .saturating_mul(1_i64 << exponent)
+        .min(RETRY_MAX_SECS);
+    Duration::seconds(secs)
+}
+
+/// Signature header value for `body` sent at `timestamp`: HMAC-SHA256 over
+/// `"<timestamp>.<body>"` keyed with the webhook secret, hex-encoded.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret.as_bytes())
+        .unwrap_or_else(|_| unreachable!("HMAC accepts keys of any length"));
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!(
+        "t={timestamp},v1={}",
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+/// Fresh webhook signing secret (256 bits from two v4 UUIDs).
+pub fn generate_secret() -> String {
+    format!(
+        "whsec_{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// Reject webhook URLs the server must not call: anything but `https`, and
+/// hosts that resolve to the server's own network (localhost, loopback,
+/// private, link-local and unspecified IP literals).
+pub fn validate_webhook_url(raw: &str) -> Result<reqwest::Url, AppError> {
+    let invalid = |message: &str| AppError::Validation {
+        message: message.to_string(),
+        field: Some("url".to_string()),
+        details: None,
+    };
+
+    let url = reqwest::Url::parse(raw).map_err(|_| invalid("url is not a valid URL"))?;
+    if url.scheme() != "https" {
+        return Err(invalid("url must use https"));
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| invalid("url must include a host"))?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.eq_ignore_ascii_case("localhost") || host.ends_with(".localhost") {
+        return Err(invalid("url must not target localhost"));
+    }
+    if host.parse::<IpAddr>().is_ok_and(is_internal) {
+        return Err(invalid("url must not target a private or loopback address"));
+    }
+    Ok(url)
+}
+
+/// Whether `ip` belongs to the server's own network: loopback, private,
+/// shared (CGNAT), link-local, unspecified, broadcast and unique-local
+/// addresses, including IPv4 addresses mapped into IPv6.
+pub const fn is_internal(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || (v4.octets()[0] == 100 && (v4.octets()[1] & 0xc0) == 64)
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_internal(IpAddr::V4(v4));
+            }
+            v6.is_loopback()
+                || v6.is_unspecified()
+                || (v6.segments()[0] & 0xfe00) == 0xfc00
+                || (v6.segments()[0] & 0xffc0) == 0xfe80
+        }
+    }
+}
+
+/// DNS resolver for webhook deliveries that drops internal addresses, so a
+/// public hostname re-pointed at the server's network after registration is
+/// refused at connect time.
+struct PublicOnlyResolver;
+
+impl reqwest::dns::Resolve for PublicOnlyResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(resolve_public(name))
+    }
+}
+
+async fn resolve_public(
+    name: reqwest::dns::Name,
+) -> Result<reqwest::dns::Addrs, Box<dyn std::error::Error + Send + Sync>> {
+    let host = name.as_str();
+    let public: Vec<SocketAddr> = tokio::net::lookup_host((host, 0))
+        .await?
+        .filter(|addr| !is_internal(addr.ip()))
+        .collect();
+    if public.is_empty() {
+        return Err(format!("{host} resolves to no public address").into());
+    }
+    Ok(Box::new(public.into_iter()))
+}
+
+/// HTTP client for webhook deliveries: no redirects (a public URL must not
+/// bounce a signed POST into the internal network) and [`PublicOnlyResolver`]
+/// for every lookup.
+pub fn delivery_client() -> reqwest::Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .dns_resolver(Arc::new(PublicOnlyResolver))
+        .timeout(DELIVERY_TIMEOUT)
+        .build()
+}
+
+/// A webhook URL reduced to its host, for listings: the path and query often
+/// carry the receiver's own secret.
+pub fn redact_url(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Record events. They are persisted by the delivery task's next pass, so a
+/// burst of events costs one image write, never a stall in the monitor that
+/// produced them.
+pub fn record(state: &AppState, drafts: Vec<NotificationDraft>) {
+    if drafts.is_empty() {
+        return;
+    }
+    state.notification_store.record(drafts);
+}
+
+/// POST one delivery to its webhook. `Ok` only for a 2xx response.
+pub async fn deliver(http: &reqwest::Client, due: &DueDelivery) -> Result<(), String> {
+    let body =
+        serde_json::to_vec(&due.delivery.event).map_err(|e| format!("serialising event: {e}"))?;
+    let timestamp = Utc::now().timestamp();
+    let attempt = due.delivery.attempts.saturating_add(1);
+
+    let response = http
+        .post(&due.url)
+        .timeout(DELIVERY_TIMEOUT)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(
+            SIGNATURE_HEADER,
+            sign_payload(&due.secret, timestamp, &body),
+        )
+        .header(EVENT_ID_HEADER, &due.delivery.event.id)
+        .header(ATTEMPT_HEADER, attempt.to_string())
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| format!("request failed: {}", e.without_url()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(format!("webhook responded with HTTP {}", status.as_u16()))
+    }
+}
+
+/// Attempt every due delivery once and fold the outcomes back into the store.
+pub async fn run_delivery_pass(state: &AppState, http: &reqwest::Client) {
+    let due = state
+        .notification_store
+        .due_deliveries(Utc::now(), DELIVERY_BATCH_SIZE);
+    if due.is_empty() {
+        return;
+    }
+
+    let outcomes: Vec<(String, Result<(), String>)> = stream::iter(due)
+        .map(|due| async move {
+            let outcome = deliver(http, &due).await;
+            if let Err(e) = &outcome {
+                debug!(
+                    "Webhook delivery {} to {} failed: {}",
+                    due.delivery.id, due.url, e
+                );
+            }
+            (due.delivery.id, outcome)
+        })
+        .buffer_unordered(DELIVERY_FANOUT_CAP)
+        .collect()
+        .await;
+
+    let delivered = outcomes.iter().filter(|(_, o)| o.is_ok()).count();
+    let failed = outcomes.len().saturating_sub(delivered);
+    if let Err(e) = state
+        .notification_store
+        .apply_delivery_outcomes(outcomes, Utc::now())
+        .await
+    {
+        warn!("Failed to persist webhook delivery outcomes: {}", e);
+    }
+    info!(
+        "Webhook delivery pass: delivered={} failed={}",
+        delivered, failed
+    );
+}
+
+/// Background task delivering pending webhook notifications and persisting
+/// events recorded since the previous pass. `http` should come from
+/// [`delivery_client`].
+pub async fn start_delivery_task(state: Arc<AppState>, http: reqwest::Client) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(DELIVERY_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            run_delivery_pass(&state, &http).await;
+            if let Err(e) = state.notification_store.persist_if_dirty().await {
+                warn!("Failed to persist notification events: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_and_caps() {
+        assert_eq!(retry_delay(1), Duration::seconds(30));
+        assert_eq!(retry_delay(2), Duration::seconds(60));
+        assert_eq!(retry_delay(4), Duration::seconds(240));
+        assert_eq!(retry_delay(8), Duration::seconds(3_600));
+        assert_eq!(retry_delay(40), Duration::seconds(3_600));
+    }
+
+    #[test]
+    fn sign_payload_matches_known_vector() {
+        // HMAC-SHA256("key", "1700000000.{}")
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(b"key").unwrap();
+        mac.update(b"1700000000.{}");
+        let expected = format!(
+            "t=1700000000,v1={}",
+            hex::encode(mac.finalize().into_bytes())
+        );
+        assert_eq!(sign_payload("key", 1_700_000_000, b"{}"), expected);
+        assert_ne!(sign_payload("other", 1_700_000_000, b"{}"), expected);
+    }
+
+    #[test]
+    fn generated_secrets_are_unique() {
+        let a = generate_secret();
+        assert!(a.starts_with("whsec_"));
+        assert_eq!(a.len(), "whsec_".len() + 64);
+        assert_ne!(a, generate_secret());
+    }
+
+    #[test]
+    fn webhook_url_validation() {
+        assert!(validate_webhook_url("https://hooks.example.com/nolus").is_ok());
+        for bad in [
+            "not a url",
+            "http://hooks.example.com/nolus",
+            "https://localhost/hook",
+            "https://127.0.0.1/hook",
+            "https://10.0.0.5/hook",
+            "https://192.168.1.1/hook",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]/hook",
+            "https://[fd00::1]/hook",
+            "https://[::ffff:127.0.0.1]/hook",
+            "https://100.64.0.1/hook",
+        ] {
+            assert!(validate_webhook_url(bad).is_err(), "{bad} must be rejected");
+        }
+    }
+
+    #[test]
+    fn redact_url_keeps_only_the_host() {
+        assert_eq!(
+            redact_url("https://hooks.example.com/t/secret-token?key=abc"),
+            "hooks.example.com"
+        );
+        assert_eq!(redact_url("not a url"), "");
+    }
+
+    #[tokio::test]
+    async fn delivery_client_refuses_hosts_resolving_internally() {
+        let due = DueDelivery {
+            delivery: PendingDelivery {
+                id: "d1".to_string(),
+                webhook_id: "w1".to_string(),
+                address: "nolus1a".to_string(),
+                event: NotificationEvent {
+                    seq: 0,
+                    id: "e1".to_string(),
+                    address: "nolus1a".to_string(),
+                    kind: NotificationKind::Lease,
+                    event: "opened".to_string(),
+                    payload: serde_json::Value::Null,
+                    created_at: Utc::now(),
+                },
+                attempts: 0,
+                next_attempt_at: Utc::now(),
+                last_error: None,
+            },
+            url: "https://localhost/hook".to_string(),
+            secret: "secret".to_string(),
+        };
+        let client = delivery_client().unwrap();
+        let error = deliver(&client, &due).await.unwrap_err();
+        assert!(error.contains("request failed"), "{error}");
+    }
+
+    #[test]
+    fn webhook_kind_filter() {
+        let mut hook = WebhookRegistration {
+            id: "w1".to_string(),
+            address: "nolus1a".to_string(),
+            url: "https://hooks.example.com".to_string(),
+            secret: "s".to_string(),
+            kinds: vec![],
+            created_at: Utc::now(),
+        };
+        assert!(hook.accepts(NotificationKind::Governance));
+        hook.kinds = vec![NotificationKind::Lease];
+        assert!(hook.accepts(NotificationKind::Lease));
+        assert!(!hook.accepts(NotificationKind::Earn));
+    }
+}
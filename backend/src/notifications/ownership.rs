@@ -0,0 +1,156 @@
+//! ADR-036 proof that a caller controls a Nolus address.
+//!
+//! Wallets sign arbitrary text (Keplr's `signArbitrary`) as an amino
+//! `StdSignDoc` wrapping a single `sign/MsgSignData` message with an empty
+//! chain id, fee and memo. A proof is accepted when its public key derives the
+//! address, the signature verifies over that document, and the timestamp in
+//! the signed text is within [`PROOF_MAX_AGE_SECS`] of now. The signed text
+//! names the action and its target, so a proof cannot be replayed for another
+//! webhook URL.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use k256::ecdsa::signature::Verifier as _;
+use k256::ecdsa::{Signature, VerifyingKey};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::error::AppError;
+
+/// Bech32 prefix the proof's public key must derive the address under.
+const ADDRESS_PREFIX: &str = "nolus";
+
+/// Largest accepted distance between the signed timestamp and now.
+pub const PROOF_MAX_AGE_SECS: i64 = 300;
+
+/// An ADR-036 signature over the action's text
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct OwnershipProof {
+    /// Base64 compressed secp256k1 public key of the address
+    pub pub_key: String,
+    /// Base64 64-byte `r || s` signature returned by the wallet
+    pub signature: String,
+    /// Unix seconds embedded in the signed text
+    pub timestamp: i64,
+}
+
+/// The text an address signs to register `url` as its webhook.
+pub fn webhook_registration_text(address: &str, url: &str, timestamp: i64) -> String {
+    format!("Register Nolus webhook {url} for {address} at {timestamp}")
+}
+
+/// Amino JSON sign bytes of an ADR-036 document carrying `text` for `signer`.
+/// Keys are in the canonical (sorted) order the wallet signs.
+fn sign_bytes(signer: &str, text: &str) -> Vec<u8> {
+    format!(
+        r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"{}"}}}}],"sequence":"0"}}"#,
+        BASE64.encode(text),
+        signer
+    )
+    .into_bytes()
+}
+
+/// Check that `proof` signs `text` with the key behind `address`, at a
+/// timestamp within [`PROOF_MAX_AGE_SECS`] of `now`.
+pub fn verify_ownership(
+    address: &str,
+    text: &str,
+    proof: &OwnershipProof,
+    now: i64,
+) -> Result<(), AppError> {
+    let invalid = |message: &str| AppError::Validation {
+        message: message.to_string(),
+        field: Some("proof".to_string()),
+        details: None,
+    };
+
+    if now.abs_diff(proof.timestamp) > PROOF_MAX_AGE_SECS.unsigned_abs() {
+        return Err(invalid("proof timestamp is too far from the current time"));
+    }
+    let key_bytes = BASE64
+        .decode(&proof.pub_key)
+        .map_err(|_| invalid("proof pub_key is not valid base64"))?;
+    let signature_bytes = BASE64
+        .decode(&proof.signature)
+        .map_err(|_| invalid("proof signature is not valid base64"))?;
+
+    let derived = cosmrs::crypto::PublicKey::from_raw_secp256k1(&key_bytes)
+        .and_then(|key| key.account_id(ADDRESS_PREFIX).ok())
+        .ok_or_else(|| invalid("proof pub_key is not a secp256k1 public key"))?;
+    if derived.as_ref() != address {
+        return Err(AppError::Forbidden);
+    }
+
+    let key = VerifyingKey::from_sec1_bytes(&key_bytes)
+        .map_err(|_| invalid("proof pub_key is not a secp256k1 public key"))?;
+    let signature = Signature::from_slice(&signature_bytes)
+        .map_err(|_| invalid("proof signature must be 64 bytes"))?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+    key.verify(&sign_bytes(address, text), &signature)
+        .map_err(|_| AppError::Forbidden)
+}
+
+/// Sign `text` with a deterministic test key, returning its address and proof.
+#[cfg(test)]
+pub fn sign_for_test(seed: u8, text: &str, timestamp: i64) -> (String, OwnershipProof) {
+    use k256::ecdsa::signature::Signer as _;
+    use k256::ecdsa::SigningKey;
+
+    let signing_key = SigningKey::from_slice(&[seed; 32]).unwrap();
+    let key_bytes = signing_key
+        .verifying_key()
+        .to_encoded_point(true)
+        .as_bytes()
+        .to_vec();
+    let address = cosmrs::crypto::PublicKey::from_raw_secp256k1(&key_bytes)
+        .unwrap()
+        .account_id(ADDRESS_PREFIX)
+        .unwrap()
+        .to_string();
+    let signature: Signature = signing_key.sign(&sign_bytes(&address, text));
+    let proof = OwnershipProof {
+        pub_key: BASE64.encode(&key_bytes),
+        signature: BASE64.encode(signature.to_bytes()),
+        timestamp,
+    };
+    (address, proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn accepts_a_fresh_signature_from_the_address_key() {
+        let (address, _) = sign_for_test(7, "", NOW);
+        let text = webhook_registration_text(&address, "https://hooks.example.com", NOW);
+        let (_, proof) = sign_for_test(7, &text, NOW);
+        assert!(verify_ownership(&address, &text, &proof, NOW + 10).is_ok());
+    }
+
+    #[test]
+    fn rejects_other_keys_texts_and_stale_timestamps() {
+        let (address, _) = sign_for_test(7, "", NOW);
+        let text = webhook_registration_text(&address, "https://hooks.example.com", NOW);
+        let (_, proof) = sign_for_test(7, &text, NOW);
+
+        let (_, other_key) = sign_for_test(8, &text, NOW);
+        assert!(matches!(
+            verify_ownership(&address, &text, &other_key, NOW),
+            Err(AppError::Forbidden)
+        ));
+
+        let other_url = webhook_registration_text(&address, "https://evil.example.com", NOW);
+        assert!(matches!(
+            verify_ownership(&address, &other_url, &proof, NOW),
+            Err(AppError::Forbidden)
+        ));
+
+        assert!(matches!(
+            verify_ownership(&address, &text, &proof, NOW + PROOF_MAX_AGE_SECS + 1),
+            Err(AppError::Validation { .. })
+        ));
+    }
+}
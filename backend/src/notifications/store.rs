@@ -0,0 +1,813 @@
+//! Durable notification outbox store.
+//!
+//! A single locked [`OutboxImage`] — per-address history, webhook
+//! registrations, pending deliveries and dead letters — persisted as a
+//! whole-image JSON file. Each write goes to a unique temp file, is
+//! `sync_all`'d, renamed into place, and the parent directory is fsync'd. A
+//! corrupt image on load is a loud failure with a `.bak` fallback — never a
+//! silent empty start. Events past the retention window are pruned.
+//! Recording only marks the image dirty; the delivery task writes it once per
+//! pass, so a burst of events never turns into a write per event. Webhook
+//! registration and removal are written before they return.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::image_file::ImageFile;
+use crate::middleware::admin_auth::constant_time_compare;
+
+use super::{
+    retry_delay, DeadLetter, NotificationDraft, NotificationEvent, NotificationKind,
+    PendingDelivery, WebhookRegistration, MAX_DEAD_LETTERS, MAX_DELIVERY_ATTEMPTS,
+    MAX_EVENTS_PER_ADDRESS, MAX_TRACKED_ADDRESSES, MAX_WEBHOOKS, MAX_WEBHOOKS_PER_ADDRESS,
+};
+
+/// Everything the outbox persists, written as one image.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct OutboxImage {
+    /// Sequence number assigned to the next recorded event
+    next_seq: u64,
+    /// address -> events, oldest first
+    events: HashMap<String, VecDeque<NotificationEvent>>,
+    /// address -> registered webhooks
+    webhooks: HashMap<String, Vec<WebhookRegistration>>,
+    deliveries: Vec<PendingDelivery>,
+    /// Oldest first
+    dead_letters: VecDeque<DeadLetter>,
+}
+
+/// A due delivery joined with the webhook it targets.
+#[derive(Debug, Clone)]
+pub struct DueDelivery {
+    pub delivery: PendingDelivery,
+    pub url: String,
+    pub secret: String,
+}
+
+/// One page of an address's history, newest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryPage {
+    pub events: Vec<NotificationEvent>,
+    /// Pass as `before` to fetch the next (older) page; `None` on the last page
+    pub next_before: Option<u64>,
+}
+
+/// The durable notification outbox.
+///
+/// The image is guarded by a single std [`Mutex`], never held across an
+/// `.await`: async methods mutate under it, drop it, then do file I/O. A
+/// separate async `write_gate` serializes persist calls so that the image
+/// written to disk is always the newest snapshot (see [`persist`]).
+pub struct NotificationStore {
+    file: ImageFile,
+    retention: Duration,
+    image: Mutex<OutboxImage>,
+    /// Set by [`record`](Self::record), cleared by the next persist.
+    dirty: AtomicBool,
+    write_gate: tokio::sync::Mutex<()>,
+}
+
+impl NotificationStore {
+    /// Bind a store to `path` with an empty outbox — the create path when no
+    /// prior image exists.
+    pub fn create(path: PathBuf, retention: Duration) -> Self {
+        Self {
+            file: ImageFile::new(path, "notification store"),
+            retention,
+            image: Mutex::new(OutboxImage::default()),
+            dirty: AtomicBool::new(false),
+            write_gate: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Load an existing image from `path`. A corrupt primary image falls back
+    /// to `<path>.bak`; if neither loads, this returns `Err` — it must never
+    /// silently yield an empty outbox (registered webhooks would vanish).
+    pub async fn load(path: PathBuf, retention: Duration) -> Result<Self, AppError> {
+        let file = ImageFile::new(path, "notification store");
+        let image = file.load().await?;
+        let store = Self {
+            file,
+            retention,
+            image: Mutex::new(image),
+            dirty: AtomicBool::new(false),
+            write_gate: tokio::sync::Mutex::new(()),
+        };
+        store.prune(Utc::now());
+        Ok(store)
+    }
+
+    /// Durably write the current outbox to the store's path. Concurrent callers
+    /// are safe: the newest state always lands last, and a crash never leaves a
+    /// partial image behind.
+    pub async fn persist(&self) -> Result<(), AppError> {
+        // Snapshot INSIDE the write gate so snapshot order equals rename order.
+        let _write = self.write_gate.lock().await;
+        self.dirty.store(false, Ordering::Release);
+        let snapshot = self.lock().clone();
+        let result = self.file.write(&snapshot).await;
+        if result.is_err() {
+            // Retry with the next pass instead of losing recorded events.
+            self.dirty.store(true, Ordering::Release);
+        }
+        result
+    }
+
+    /// Persist only if events were recorded since the last write.
+    pub async fn persist_if_dirty(&self) -> Result<(), AppError> {
+        if self.dirty.load(Ordering::Acquire) {
+            self.persist().await
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Append events to their addresses' histories and queue one delivery per
+    /// matching webhook. The events are written with the next persist.
+    pub fn record(&self, drafts: Vec<NotificationDraft>) {
+        let now = Utc::now();
+        self.prune(now);
+        {
+            let mut image = self.lock();
+            for draft in drafts {
+                let event = NotificationEvent {
+                    seq: image.next_seq,
+                    id: Uuid::new_v4().to_string(),
+                    address: draft.address,
+                    kind: draft.kind,
+                    event: draft.event,
+                    payload: draft.payload,
+                    created_at: now,
+                };
+                image.next_seq = image.next_seq.saturating_add(1);
+
+                let hooks: Vec<String> = image
+                    .webhooks
+                    .get(&event.address)
+                    .map(|hooks| {
+                        hooks
+                            .iter()
+                            .filter(|hook| hook.accepts(event.kind))
+                            .map(|hook| hook.id.clone())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                for webhook_id in hooks {
+                    image.deliveries.push(PendingDelivery {
+                        id: Uuid::new_v4().to_string(),
+                        webhook_id,
+                        address: event.address.clone(),
+                        event: event.clone(),
+                        attempts: 0,
+                        next_attempt_at: now,
+                        last_error: None,
+                    });
+                }
+
+                let history = image.events.entry(event.address.clone()).or_default();
+                history.push_back(event);
+                while history.len() > MAX_EVENTS_PER_ADDRESS {
+                    history.pop_front();
+                }
+            }
+            Self::evict_idle_addresses(&mut image);
+        }
+        self.dirty.store(true, Ordering::Release);
+    }
+
+    /// Page through an address's history, newest first. `before` is an
+    /// exclusive upper bound on `seq`.
+    pub fn history(
+        &self,
+        address: &str,
+        kind: Option<NotificationKind>,
+        before: Option<u64>,
+        limit: usize,
+    ) -> HistoryPage {
+        let image = self.lock();
+        let mut matching = image
+            .events
+            .get(address)
+            .into_iter()
+            .flat_map(|events| events.iter().rev())
+            .filter(|event| before.is_none_or(|before| event.seq < before))
+            .filter(|event| kind.is_none_or(|kind| event.kind == kind));
+
+        let events: Vec<NotificationEvent> = matching.by_ref().take(limit).cloned().collect();
+        let next_before = if matching.next().is_some() {
+            events.last().map(|event| event.seq)
+        } else {
+            None
+        };
+        HistoryPage {
+            events,
+            next_before,
+        }
+    }
+
+    /// Whether the address has at least one webhook registered.
+    pub fn is_registered(&self, address: &str) -> bool {
+        self.lock()
+            .webhooks
+            .get(address)
+            .is_some_and(|hooks| !hooks.is_empty())
+    }
+
+    /// Addresses with a webhook accepting `kind`.
+    pub fn registered_addresses(&self, kind: NotificationKind) -> Vec<String> {
+        self.lock()
+            .webhooks
+            .iter()
+            .filter(|(_, hooks)| hooks.iter().any(|hook| hook.accepts(kind)))
+            .map(|(address, _)| address.clone())
+            .collect()
+    }
+
+    /// Webhooks registered by an address.
+    pub fn webhooks(&self, address: &str) -> Vec<WebhookRegistration> {
+        self.lock()
+            .webhooks
+            .get(address)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Register a webhook. Rejects past [`MAX_WEBHOOKS_PER_ADDRESS`] or
+    /// [`MAX_WEBHOOKS`], or for a URL the address already registered. A
+    /// persist failure rolls the registration back.
+    pub async fn register_webhook(
+        &self,
+        registration: WebhookRegistration,
+    ) -> Result<(), AppError> {
+        let address = registration.address.clone();
+        let id = registration.id.clone();
+        {
+            let mut image = self.lock();
+            let total: usize = image.webhooks.values().map(Vec::len).sum();
+            if total >= MAX_WEBHOOKS {
+                return Err(AppError::ServiceUnavailable {
+                    message: "webhook registrations are at capacity".to_string(),
+                });
+            }
+            let hooks = image.webhooks.entry(address.clone()).or_default();
+            if hooks.len() >= MAX_WEBHOOKS_PER_ADDRESS {
+                return Err(AppError::Validation {
+                    message: format!(
+                        "an address may register at most {MAX_WEBHOOKS_PER_ADDRESS} webhooks"
+                    ),
+                    field: Some("url".to_string()),
+                    details: None,
+                });
+            }
+            if hooks.iter().any(|hook| hook.url == registration.url) {
+                return Err(AppError::Validation {
+                    message: "webhook URL is already registered for this address".to_string(),
+                    field: Some("url".to_string()),
+                    details: None,
+                });
+            }
+            hooks.push(registration);
+        }
+        if let Err(e) = self.persist().await {
+            let mut image = self.lock();
+            if let Some(hooks) = image.webhooks.get_mut(&address) {
+                hooks.retain(|hook| hook.id != id);
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Remove a webhook and its pending deliveries. The caller must present the
+    /// webhook's secret.
+    pub async fn remove_webhook(
+        &self,
+        address: &str,
+        webhook_id: &str,
+        secret: &str,
+    ) -> Result<(), AppError> {
+        {
+            let mut image = self.lock();
+            let hooks = image
+                .webhooks
+                .get_mut(address)
+                .ok_or_else(|| AppError::NotFound {
+                    resource: format!("Webhook {webhook_id}"),
+                })?;
+            let position = hooks
+                .iter()
+                .position(|hook| hook.id == webhook_id)
+                .ok_or_else(|| AppError::NotFound {
+                    resource: format!("Webhook {webhook_id}"),
+                })?;
+            if hooks.get(position).is_none_or(|hook| {
+                !constant_time_compare(hook.secret.as_bytes(), secret.as_bytes())
+            }) {
+                return Err(AppError::Forbidden);
+            }
+            hooks.remove(position);
+            if hooks.is_empty() {
+                image.webhooks.remove(address);
+            }
+            image
+                .deliveries
+                .retain(|delivery| delivery.webhook_id != webhook_id);
+        }
+        self.persist().await
+    }
+
+    /// Deliveries whose next attempt is due, oldest first, capped at `limit`.
+    pub fn due_deliveries(&self, now: DateTime<Utc>, limit: usize) -> Vec<DueDelivery> {
+        let image = self.lock();
+        let mut due: Vec<DueDelivery> = image
+            .deliveries
+            .iter()
+            .filter(|delivery| delivery.next_attempt_at <= now)
+            .filter_map(|delivery| {
+                let hook = image
+                    .webhooks
+                    .get(&delivery.address)?
+                    .iter()
+                    .find(|hook| hook.id == delivery.webhook_id)?;
+                Some(DueDelivery {
+                    delivery: delivery.clone(),
+                    url: hook.url.clone(),
+                    secret: hook.secret.clone(),
+                })
+            })
+            .collect();
+        due.sort_by_key(|d| d.delivery.next_attempt_at);
+        due.truncate(limit);
+        due
+    }
+
+    /// Fold delivery results back: successes are dropped, failures are
+    /// rescheduled with backoff or moved to the dead-letter list once
+    /// [`MAX_DELIVERY_ATTEMPTS`] is reached.
+    pub async fn apply_delivery_outcomes(
+        &self,
+        outcomes: Vec<(String, Result<(), String>)>,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        {
+            let mut image = self.lock();
+            for (delivery_id, outcome) in outcomes {
+                let Some(position) = image.deliveries.iter().position(|d| d.id == delivery_id)
+                else {
+                    // Webhook removed while the attempt was in flight
+                    continue;
+                };
+                let error = match outcome {
+                    Ok(()) => {
+                        image.deliveries.swap_remove(position);
+                        continue;
+                    }
+                    Err(error) => error,
+                };
+
+                let attempts = image
+                    .deliveries
+                    .get(position)
+                    .map_or(0, |d| d.attempts)
+                    .saturating_add(1);
+                if attempts >= MAX_DELIVERY_ATTEMPTS {
+                    let delivery = image.deliveries.swap_remove(position);
+                    let url = image
+                        .webhooks
+                        .get(&delivery.address)
+                        .and_then(|hooks| hooks.iter().find(|h| h.id == delivery.webhook_id))
+                        .map(|hook| hook.url.clone())
+                        .unwrap_or_default();
+                    warn!(
+                        "Webhook delivery {} to {} dead-lettered after {} attempts: {}",
+                        delivery.id, url, attempts, error
+                    );
+                    image.dead_letters.push_back(DeadLetter {
+                        delivery_id: delivery.id,
+                        webhook_id: delivery.webhook_id,
+                        address: delivery.address,
+                        url,
+                        event: delivery.event,
+                        attempts,
+                        last_error: error,
+                        dead_at: now,
+                    });
+                    while image.dead_letters.len() > MAX_DEAD_LETTERS {
+                        image.dead_letters.pop_front();
+                    }
+                } else if let Some(delivery) = image.deliveries.get_mut(position) {
+                    delivery.attempts = attempts;
+                    delivery.next_attempt_at = now + retry_delay(attempts);
+                    delivery.last_error = Some(error);
+                }
+            }
+        }
+        self.persist().await
+    }
+
+    /// Dead letters for an address, newest first.
+    pub fn dead_letters(&self, address: &str) -> Vec<DeadLetter> {
+        self.lock()
+            .dead_letters
+            .iter()
+            .rev()
+            .filter(|letter| letter.address == address)
+            .cloned()
+            .collect()
+    }
+
+    /// Drop events older than the retention window relative to `now`, and
+    /// addresses left with neither history nor webhooks.
+    fn prune(&self, now: DateTime<Utc>) {
+        let cutoff = now - self.retention;
+        let mut image = self.lock();
+        let OutboxImage {
+            events, webhooks, ..
+        } = &mut *image;
+        events.retain(|address, history| {
+            history.retain(|event| event.created_at >= cutoff);
+            !history.is_empty() || webhooks.contains_key(address)
+        });
+    }
+
+    /// Keep the number of addresses with history under
+    /// [`MAX_TRACKED_ADDRESSES`], evicting the least recently active addresses
+    /// that have no webhook.
+    fn evict_idle_addresses(image: &mut OutboxImage) {
+        let excess = image.events.len().saturating_sub(MAX_TRACKED_ADDRESSES);
+        if excess == 0 {
+            return;
+        }
+        let mut idle: Vec<(String, DateTime<Utc>)> = image
+            .events
+            .iter()
+            .filter(|(address, _)| !image.webhooks.contains_key(*address))
+            .map(|(address, history)| {
+                let last = history
+                    .back()
+                    .map_or(DateTime::<Utc>::MIN_UTC, |event| event.created_at);
+                (address.clone(), last)
+            })
+            .collect();
+        idle.sort_by_key(|(_, last)| *last);
+        for (address, _) in idle.into_iter().take(excess) {
+            image.events.remove(&address);
+        }
+    }
+
+    /// Lock the image, recovering the inner guard on poisoning rather than
+    /// panicking (a poisoned lock still holds a usable image).
+    fn lock(&self) -> std::sync::MutexGuard<'_, OutboxImage> {
+        self.image.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Ephemeral store bound to a unique temp file, for tests only.
+    #[cfg(test)]
+    pub fn ephemeral() -> Self {
+        let path = crate::image_file::ephemeral_path("notifications");
+        Self::create(path, Duration::days(30))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn store_in(dir: &TempDir) -> NotificationStore {
+        NotificationStore::create(dir.path().join("notifications.json"), Duration::days(30))
+    }
+
+    fn hook(id: &str, address: &str, kinds: Vec<NotificationKind>) -> WebhookRegistration {
+        WebhookRegistration {
+            id: id.to_string(),
+            address: address.to_string(),
+            url: format!("https://hooks.example.com/{id}"),
+            secret: format!("secret-{id}"),
+            kinds,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn lease_event(address: &str, event: &str) -> NotificationDraft {
+        NotificationDraft::new(
+            address,
+            NotificationKind::Lease,
+            event,
+            serde_json::json!({ "lease": "nolus1lease" }),
+        )
+    }
+
+    #[tokio::test]
+    async fn record_then_reload_recovers_history_and_webhooks() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("notifications.json");
+        let store = NotificationStore::create(path.clone(), Duration::days(30));
+        store
+            .register_webhook(hook("w1", "nolus1a", vec![]))
+            .await
+            .expect("register");
+        store.record(vec![lease_event("nolus1a", "opened")]);
+        store.persist_if_dirty().await.expect("persist");
+
+        let reloaded = NotificationStore::load(path, Duration::days(30))
+            .await
+            .expect("reload");
+        let page = reloaded.history("nolus1a", None, None, 10);
+        assert_eq!(page.events.len(), 1);
+        assert_eq!(page.events[0].event, "opened");
+        assert!(reloaded.is_registered("nolus1a"));
+        assert_eq!(reloaded.due_deliveries(Utc::now(), 10).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn corrupt_image_load_errors_rather_than_starting_empty() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("notifications.json");
+        std::fs::write(&path, b"{ not json").expect("seed corrupt file");
+        assert!(NotificationStore::load(path, Duration::days(30))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn history_pages_newest_first_with_kind_filter() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        let mut drafts: Vec<NotificationDraft> = (0..5)
+            .map(|i| lease_event("nolus1a", &format!("e{i}")))
+            .collect();
+        drafts.push(NotificationDraft::new(
+            "nolus1a",
+            NotificationKind::Earn,
+            "deposited",
+            serde_json::Value::Null,
+        ));
+        store.record(drafts);
+
+        let first = store.history("nolus1a", Some(NotificationKind::Lease), None, 2);
+        let names: Vec<&str> = first.events.iter().map(|e| e.event.as_str()).collect();
+        assert_eq!(names, vec!["e4", "e3"]);
+
+        let second = store.history(
+            "nolus1a",
+            Some(NotificationKind::Lease),
+            first.next_before,
+            10,
+        );
+        let names: Vec<&str> = second.events.iter().map(|e| e.event.as_str()).collect();
+        assert_eq!(names, vec!["e2", "e1", "e0"]);
+        assert_eq!(second.next_before, None);
+
+        assert_eq!(store.history("nolus1a", None, None, 10).events.len(), 6);
+        assert!(store.history("nolus1b", None, None, 10).events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn history_is_capped_per_address() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        let drafts = (0..MAX_EVENTS_PER_ADDRESS + 3)
+            .map(|i| lease_event("nolus1a", &format!("e{i}")))
+            .collect();
+        store.record(drafts);
+
+        let page = store.history("nolus1a", None, None, MAX_EVENTS_PER_ADDRESS + 10);
+        assert_eq!(page.events.len(), MAX_EVENTS_PER_ADDRESS);
+        assert_eq!(
+            page.events.last().map(|e| e.event.as_str()),
+            Some("e3"),
+            "the oldest events are dropped first"
+        );
+    }
+
+    #[tokio::test]
+    async fn deliveries_follow_webhook_kind_filters() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        store
+            .register_webhook(hook("lease-only", "nolus1a", vec![NotificationKind::Lease]))
+            .await
+            .expect("register");
+        store
+            .register_webhook(hook("all", "nolus1a", vec![]))
+            .await
+            .expect("register");
+        store.record(vec![
+            lease_event("nolus1a", "opened"),
+            NotificationDraft::new(
+                "nolus1a",
+                NotificationKind::Governance,
+                "proposal_status_changed",
+                serde_json::Value::Null,
+            ),
+            lease_event("nolus1other", "opened"),
+        ]);
+
+        let due = store.due_deliveries(Utc::now(), 10);
+        assert_eq!(due.len(), 3, "lease → both hooks, governance → catch-all");
+        assert_eq!(
+            due.iter()
+                .filter(|d| d.delivery.webhook_id == "lease-only")
+                .count(),
+            1
+        );
+        assert_eq!(
+            store.registered_addresses(NotificationKind::Governance),
+            vec!["nolus1a".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_backs_off_then_dead_letters() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        store
+            .register_webhook(hook("w1", "nolus1a", vec![]))
+            .await
+            .expect("register");
+        store.record(vec![lease_event("nolus1a", "opened")]);
+
+        let mut now = Utc::now();
+        for attempt in 1..MAX_DELIVERY_ATTEMPTS {
+            let due = store.due_deliveries(now, 10);
+            assert_eq!(due.len(), 1, "attempt {attempt} is due");
+            store
+                .apply_delivery_outcomes(
+                    vec![(due[0].delivery.id.clone(), Err("HTTP 500".to_string()))],
+                    now,
+                )
+                .await
+                .expect("apply");
+            assert!(
+                store.due_deliveries(now, 10).is_empty(),
+                "a failed delivery waits for its backoff"
+            );
+            now += retry_delay(attempt);
+        }
+
+        let due = store.due_deliveries(now, 10);
+        store
+            .apply_delivery_outcomes(
+                vec![(due[0].delivery.id.clone(), Err("HTTP 500".to_string()))],
+                now,
+            )
+            .await
+            .expect("apply");
+
+        assert!(store.due_deliveries(now + Duration::days(1), 10).is_empty());
+        let dead = store.dead_letters("nolus1a");
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].attempts, MAX_DELIVERY_ATTEMPTS);
+        assert_eq!(dead[0].last_error, "HTTP 500");
+        assert_eq!(dead[0].url, "https://hooks.example.com/w1");
+    }
+
+    #[tokio::test]
+    async fn successful_delivery_is_removed() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        store
+            .register_webhook(hook("w1", "nolus1a", vec![]))
+            .await
+            .expect("register");
+        store.record(vec![lease_event("nolus1a", "opened")]);
+
+        let due = store.due_deliveries(Utc::now(), 10);
+        store
+            .apply_delivery_outcomes(vec![(due[0].delivery.id.clone(), Ok(()))], Utc::now())
+            .await
+            .expect("apply");
+        assert!(store
+            .due_deliveries(Utc::now() + Duration::days(1), 10)
+            .is_empty());
+        assert!(store.dead_letters("nolus1a").is_empty());
+    }
+
+    #[tokio::test]
+    async fn webhook_registration_cap_and_duplicate_url() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        for i in 0..MAX_WEBHOOKS_PER_ADDRESS {
+            store
+                .register_webhook(hook(&format!("w{i}"), "nolus1a", vec![]))
+                .await
+                .expect("within cap");
+        }
+        assert!(store
+            .register_webhook(hook("extra", "nolus1a", vec![]))
+            .await
+            .is_err());
+
+        let other = store_in(&dir);
+        other
+            .register_webhook(hook("w1", "nolus1b", vec![]))
+            .await
+            .expect("register");
+        let mut duplicate = hook("w2", "nolus1b", vec![]);
+        duplicate.url = "https://hooks.example.com/w1".to_string();
+        assert!(other.register_webhook(duplicate).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn record_defers_the_write_until_persist() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("notifications.json");
+        let store = NotificationStore::create(path.clone(), Duration::days(30));
+        for i in 0..3 {
+            store.record(vec![lease_event("nolus1a", &format!("e{i}"))]);
+        }
+        assert!(!path.exists(), "recording alone writes nothing");
+
+        store.persist_if_dirty().await.expect("persist");
+        let written = std::fs::metadata(&path)
+            .expect("image")
+            .modified()
+            .expect("mtime");
+        store.persist_if_dirty().await.expect("clean persist");
+        assert_eq!(
+            std::fs::metadata(&path)
+                .expect("image")
+                .modified()
+                .expect("mtime"),
+            written,
+            "a clean store is not rewritten"
+        );
+        let reloaded = NotificationStore::load(path, Duration::days(30))
+            .await
+            .expect("reload");
+        assert_eq!(reloaded.history("nolus1a", None, None, 10).events.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn webhook_registrations_are_capped_globally() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        {
+            let mut image = store.lock();
+            for i in 0..MAX_WEBHOOKS {
+                image
+                    .webhooks
+                    .entry(format!("nolus1addr{}", i / MAX_WEBHOOKS_PER_ADDRESS))
+                    .or_default()
+                    .push(hook(&format!("w{i}"), "nolus1addr", vec![]));
+            }
+        }
+        assert!(matches!(
+            store
+                .register_webhook(hook("extra", "nolus1new", vec![]))
+                .await,
+            Err(AppError::ServiceUnavailable { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn remove_webhook_requires_secret_and_drops_deliveries() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        store
+            .register_webhook(hook("w1", "nolus1a", vec![]))
+            .await
+            .expect("register");
+        store.record(vec![lease_event("nolus1a", "opened")]);
+
+        assert!(matches!(
+            store.remove_webhook("nolus1a", "w1", "wrong").await,
+            Err(AppError::Forbidden)
+        ));
+        assert!(matches!(
+            store.remove_webhook("nolus1a", "nope", "secret-w1").await,
+            Err(AppError::NotFound { .. })
+        ));
+        store
+            .remove_webhook("nolus1a", "w1", "secret-w1")
+            .await
+            .expect("remove");
+        assert!(!store.is_registered("nolus1a"));
+        assert!(store.due_deliveries(Utc::now(), 10).is_empty());
+        assert_eq!(
+            store.history("nolus1a", None, None, 10).events.len(),
+            1,
+            "history survives webhook removal"
+        );
+    }
+
+    #[tokio::test]
+    async fn prune_drops_expired_events() {
+        let dir = TempDir::new().expect("tempdir");
+        let store = store_in(&dir);
+        store.record(vec![lease_event("nolus1a", "opened")]);
+
+        store.prune(Utc::now() + Duration::days(31));
+        assert!(store.history("nolus1a", None, None, 10).events.is_empty());
+        assert!(store.lock().events.is_empty());
+    }
+}
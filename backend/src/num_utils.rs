@@ -2,8 +2,9 @@
 //!
 //! `clippy::as_conversions` forbids `as` in this crate. Most casts have a
 //! `From`/`TryFrom` replacement, but `f64` has neither `From<u128>` nor
-//! `TryFrom<u128>` in std (the conversion is lossy above 2^53). This module
-//! holds the one conversion that needs a non-trivial, reviewed strategy.
+//! `TryFrom<u128>` in std (the conversion is lossy above 2^53), and nothing in
+//! std converts `f64` back to an integer without `as`. This module holds the
+//! conversions that need a non-trivial, reviewed strategy.
 
 /// Convert an unsigned 128-bit integer to `f64` without an `as` cast.
 ///
@@ -17,6 +18,18 @@ pub fn u128_to_f64(value: u128) -> f64 {
     value.to_string().parse().unwrap_or(f64::INFINITY)
 }
 
+/// Convert a non-negative `f64` to `u64`, rounding up.
+///
+/// Used for gas limits derived from a float multiplier. Routes through the
+/// decimal string of the ceiled value; NaN and non-positive inputs map to 0
+/// and values beyond `u64::MAX` (including infinity) saturate, mirroring `as`.
+pub fn f64_ceil_to_u64(value: f64) -> u64 {
+    if value.is_nan() || value <= 0.0 {
+        return 0;
+    }
+    format!("{:.0}", value.ceil()).parse().unwrap_or(u64::MAX)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,4 +55,15 @@ mod tests {
         let amount = 1_000_000_u128;
         assert_eq!(u128_to_f64(quote) / u128_to_f64(amount), 1234.56789);
     }
+
+    #[test]
+    fn f64_ceil_to_u64_rounds_up_and_saturates() {
+        assert_eq!(f64_ceil_to_u64(0.0), 0);
+        assert_eq!(f64_ceil_to_u64(-3.2), 0);
+        assert_eq!(f64_ceil_to_u64(f64::NAN), 0);
+        assert_eq!(f64_ceil_to_u64(1.0), 1);
+        assert_eq!(f64_ceil_to_u64(150_000.1), 150_001);
+        assert_eq!(f64_ceil_to_u64(f64::INFINITY), u64::MAX);
+        assert_eq!(f64_ceil_to_u64(1e30), u64::MAX);
+    }
 }
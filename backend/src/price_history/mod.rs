@@ -0,0 +1,233 @@
+//! Backend-recorded oracle price history.
+//!
+//! Every `new_block` price refresh feeds [`record_prices`], which folds each
+//! oracle price into rolling OHLC candles at 1m/5m/1h/1d resolutions. Each
+//! resolution keeps a bounded number of candles per currency, so the on-disk
+//! image stays bounded no matter how long the backend runs. On startup,
+//! [`start_price_history_task`] backfills buckets the store has never seen
+//! from the ETL price series, then periodically persists the image. Charts
+//! therefore keep working through ETL outages. Durable state lives in
+//! [`store`].
+
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use utoipa::ToSchema;
+
+use crate::handlers::currencies::PriceInfo;
+use crate::AppState;
+
+mod store;
+
+pub use store::PriceHistoryStore;
+
+/// Currencies (`TICKER@PROTOCOL` keys) tracked; new keys past this are ignored.
+pub const MAX_SERIES: usize = 512;
+
+/// ETL price-series windows (days) fetched per currency during backfill.
+const BACKFILL_INTERVALS_DAYS: [u32; 3] = [1, 7, 30];
+
+/// Concurrent currencies backfilled at once.
+const BACKFILL_CONCURRENCY: usize = 4;
+
+/// How often a changed image is written to disk.
+const PERSIST_INTERVAL: StdDuration = StdDuration::from_secs(60);
+
+/// Candle width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum Resolution {
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "1d")]
+    OneDay,
+}
+
+impl Resolution {
+    pub const ALL: [Self; 4] = [
+        Self::OneMinute,
+        Self::FiveMinutes,
+        Self::OneHour,
+        Self::OneDay,
+    ];
+
+    /// Bucket width in seconds.
+    pub const fn seconds(self) -> i64 {
+        match self {
+            Self::OneMinute => 60,
+            Self::FiveMinutes => 300,
+            Self::OneHour => 3_600,
+            Self::OneDay => 86_400,
+        }
+    }
+
+    /// Candles retained per currency: 1 day of 1m, 1 week of 5m, 90 days of
+    /// 1h and 2 years of 1d.
+    pub const fn capacity(self) -> usize {
+        match self {
+            Self::OneMinute => 1_440,
+            Self::FiveMinutes => 2_016,
+            Self::OneHour => 2_160,
+            Self::OneDay => 730,
+        }
+    }
+
+    /// Start (unix seconds) of the bucket containing `unix_secs`.
+    pub const fn bucket_start(self, unix_secs: i64) -> i64 {
+        unix_secs - unix_secs.rem_euclid(self.seconds())
+    }
+}
+
+/// One OHLC candle.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Candle {
+    /// Bucket start, unix seconds
+    pub time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl Candle {
+    const fn opening(time: i64, price: f64) -> Self {
+        Self {
+            time,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+        }
+    }
+
+    const fn update(&mut self, price: f64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+    }
+}
+
+/// Parse a cached `price_usd` string, rejecting values a chart can't plot.
+fn parse_price(raw: &str) -> Option<f64> {
+    raw.parse::<f64>()
+        .ok()
+        .filter(|price| price.is_finite() && *price > 0.0)
+}
+
+/// Fold the prices from one oracle refresh into the candle store.
+pub fn record_prices<'a>(
+    store: &PriceHistoryStore,
+    prices: impl IntoIterator<Item = &'a PriceInfo>,
+    at: DateTime<Utc>,
+) {
+    let samples: Vec<(&str, f64)> = prices
+        .into_iter()
+        .filter_map(|info| parse_price(&info.price_usd).map(|price| (info.key.as_str(), price)))
+        .collect();
+    store.record(&samples, at.timestamp());
+}
+
+/// Fill buckets the store has never seen from the ETL price series of every
+/// currently priced currency.
+pub async fn backfill(state: &Arc<AppState>) {
+    let Some(prices) = state.data_cache.prices.load() else {
+        warn!("Skipping price history backfill: prices not loaded");
+        return;
+    };
+    let mut keys: Vec<String> = prices.prices.into_keys().collect();
+    keys.sort();
+
+    let filled: usize = stream::iter(keys)
+        .map(|key| async move {
+            let Some((ticker, protocol)) = key.split_once('@') else {
+                return 0;
+            };
+            let mut points = Vec::new();
+            for interval in BACKFILL_INTERVALS_DAYS {
+                match state
+                    .etl_client
+                    .fetch_price_history(ticker, protocol, interval)
+                    .await
+                {
+                    Ok(series) => points.extend(series.into_iter().map(|p| (p.0, p.1))),
+                    Err(e) => debug!("Price history backfill for {} ({}d): {}", key, interval, e),
+                }
+            }
+            state.price_history.backfill(&key, &points)
+        })
+        .buffer_unordered(BACKFILL_CONCURRENCY)
+        .fold(0, |total, added| async move { total + added })
+        .await;
+
+    info!("Price history backfill added {} candles", filled);
+    if let Err(e) = state.price_history.persist().await {
+        warn!("Failed to persist price history after backfill: {}", e);
+    }
+}
+
+/// Backfill from ETL once, then persist the image whenever it has changed.
+pub async fn start_price_history_task(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        backfill(&state).await;
+        let mut interval = tokio::time::interval(PERSIST_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = state.price_history.persist_if_dirty().await {
+                warn!("Failed to persist price history: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(key: &str, price_usd: &str) -> PriceInfo {
+        PriceInfo {
+            key: key.to_string(),
+            symbol: key.split('@').next().unwrap_or_default().to_string(),
+            price_usd: price_usd.to_string(),
+        }
+    }
+
+    #[test]
+    fn bucket_start_aligns_to_resolution() {
+        let t = 1_700_000_123;
+        assert_eq!(Resolution::OneMinute.bucket_start(t), 1_700_000_100);
+        assert_eq!(Resolution::FiveMinutes.bucket_start(t), 1_700_000_100);
+        assert_eq!(Resolution::OneHour.bucket_start(t), 1_699_999_200);
+        assert_eq!(Resolution::OneDay.bucket_start(t), 1_699_920_000);
+    }
+
+    #[test]
+    fn resolution_serializes_as_short_label() {
+        assert_eq!(
+            serde_json::to_value(Resolution::FiveMinutes).unwrap(),
+            serde_json::json!("5m")
+        );
+        let parsed: Resolution = serde_json::from_value(serde_json::json!("1d")).unwrap();
+        assert_eq!(parsed, Resolution::OneDay);
+    }
+
+    #[test]
+    fn record_prices_skips_unplottable_values() {
+        let store = PriceHistoryStore::ephemeral();
+        let prices = [
+            info("ATOM@P", "7.5"),
+            info("BAD@P", "not-a-number"),
+            info("ZERO@P", "0"),
+        ];
+        let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        record_prices(&store, &prices, at);
+
+        assert_eq!(store.keys(), vec!["ATOM@P".to_string()]);
+    }
+}
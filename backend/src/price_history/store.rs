@@ -0,0 +1,407 @@
+//! Durable candle store.
+//!
+//! A single locked [`HistoryImage`] — per-currency candle series at every
+//! [`Resolution`] — persisted as a whole-image JSON file with the same
+//! discipline as the transfer store: each write goes to a unique temp file, is
+//! `sync_all`'d, renamed into place, and the parent directory is fsync'd. A
+//! corrupt image on load falls back to `<path>.bak` and otherwise fails
+//! loudly. Recording only marks the image dirty; the background task decides
+//! when to write, so a refresh every block never turns into a write every
+//! block.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::error::AppError;
+use crate::image_file::ImageFile;
+
+use super::{Candle, Resolution, MAX_SERIES};
+
+/// Candles for one currency, oldest first within each resolution.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Series {
+    #[serde(rename = "1m", default)]
+    one_minute: VecDeque<Candle>,
+    #[serde(rename = "5m", default)]
+    five_minutes: VecDeque<Candle>,
+    #[serde(rename = "1h", default)]
+    one_hour: VecDeque<Candle>,
+    #[serde(rename = "1d", default)]
+    one_day: VecDeque<Candle>,
+}
+
+impl Series {
+    const fn candles(&self, resolution: Resolution) -> &VecDeque<Candle> {
+        match resolution {
+            Resolution::OneMinute => &self.one_minute,
+            Resolution::FiveMinutes => &self.five_minutes,
+            Resolution::OneHour => &self.one_hour,
+            Resolution::OneDay => &self.one_day,
+        }
+    }
+
+    const fn candles_mut(&mut self, resolution: Resolution) -> &mut VecDeque<Candle> {
+        match resolution {
+            Resolution::OneMinute => &mut self.one_minute,
+            Resolution::FiveMinutes => &mut self.five_minutes,
+            Resolution::OneHour => &mut self.one_hour,
+            Resolution::OneDay => &mut self.one_day,
+        }
+    }
+
+    /// Fold one live sample into every resolution. Samples older than the
+    /// newest candle are dropped — live data only moves forward.
+    fn record(&mut self, unix_secs: i64, price: f64) {
+        for resolution in Resolution::ALL {
+            let bucket = resolution.bucket_start(unix_secs);
+            let candles = self.candles_mut(resolution);
+            match candles.back_mut() {
+                Some(last) if last.time == bucket => last.update(price),
+                Some(last) if last.time > bucket => {}
+                _ => {
+                    candles.push_back(Candle::opening(bucket, price));
+                    while candles.len() > resolution.capacity() {
+                        candles.pop_front();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Merge ETL points (sorted oldest first) into buckets this series has no
+    /// candle for. Existing candles are authoritative and never touched.
+    /// Returns the number of candles added.
+    fn backfill(&mut self, points: &[(i64, f64)]) -> usize {
+        let mut added = 0;
+        for resolution in Resolution::ALL {
+            let candles = self.candles_mut(resolution);
+            let mut merged: BTreeMap<i64, Candle> = BTreeMap::new();
+            for &(unix_secs, price) in points {
+                merged
+                    .entry(resolution.bucket_start(unix_secs))
+                    .and_modify(|candle| candle.update(price))
+                    .or_insert_with_key(|&bucket| Candle::opening(bucket, price));
+            }
+            let before = candles.len();
+            for candle in candles.drain(..) {
+                merged.insert(candle.time, candle);
+            }
+            let skip = merged.len().saturating_sub(resolution.capacity());
+            candles.extend(merged.into_values().skip(skip));
+            added += candles.len().saturating_sub(before);
+        }
+        added
+    }
+}
+
+/// Everything the candle store persists, written as one image.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HistoryImage {
+    /// `TICKER@PROTOCOL` -> candles
+    series: HashMap<String, Series>,
+}
+
+/// The durable candle store.
+///
+/// The image is guarded by a single std [`Mutex`], never held across an
+/// `.await`. A separate async `write_gate` serializes persist calls so that
+/// the image written to disk is always the newest snapshot.
+pub struct PriceHistoryStore {
+    file: ImageFile,
+    image: Mutex<HistoryImage>,
+    dirty: AtomicBool,
+    write_gate: tokio::sync::Mutex<()>,
+}
+
+impl PriceHistoryStore {
+    /// Bind a store to `path` with no history — the create path when no prior
+    /// image exists.
+    pub fn create(path: PathBuf) -> Self {
+        Self {
+            file: ImageFile::new(path, "price history"),
+            image: Mutex::new(HistoryImage::default()),
+            dirty: AtomicBool::new(false),
+            write_gate: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Load an existing image from `path`. A corrupt primary image falls back
+    /// to `<path>.bak`; if neither loads, this returns `Err`.
+    pub async fn load(path: PathBuf) -> Result<Self, AppError> {
+        let file = ImageFile::new(path, "price history");
+        let image = file.load().await?;
+        Ok(Self {
+            file,
+            image: Mutex::new(image),
+            dirty: AtomicBool::new(false),
+            write_gate: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Durably write the current image to the store's path.
+    pub async fn persist(&self) -> Result<(), AppError> {
+        // Snapshot INSIDE the write gate so snapshot order equals rename order.
+        let _write = self.write_gate.lock().await;
+        self.dirty.store(false, Ordering::Release);
+        let snapshot = self.lock().clone();
+        let result = self.file.write(&snapshot).await;
+        if result.is_err() {
+            // Retry with the next tick instead of losing the change.
+            self.dirty.store(true, Ordering::Release);
+        }
+        result
+    }
+
+    /// Persist only if something was recorded since the last write.
+    pub async fn persist_if_dirty(&self) -> Result<(), AppError> {
+        if self.dirty.load(Ordering::Acquire) {
+            self.persist().await
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Fold one refresh's `(key, price_usd)` samples taken at `unix_secs`.
+    pub fn record(&self, samples: &[(&str, f64)], unix_secs: i64) {
+        if samples.is_empty() {
+            return;
+        }
+        let mut image = self.lock();
+        for &(key, price) in samples {
+            if !image.series.contains_key(key) && image.series.len() >= MAX_SERIES {
+                debug!("Price history series cap reached, not tracking {}", key);
+                continue;
+            }
+            image
+                .series
+                .entry(key.to_string())
+                .or_default()
+                .record(unix_secs, price);
+        }
+        self.dirty.store(true, Ordering::Release);
+    }
+
+    /// Merge `(unix_millis, price_usd)` ETL points for `key` into buckets the
+    /// store has no candle for. Returns the number of candles added.
+    pub fn backfill(&self, key: &str, points: &[(i64, f64)]) -> usize {
+        let mut points: Vec<(i64, f64)> = points
+            .iter()
+            .filter(|(_, price)| price.is_finite() && *price > 0.0)
+            .map(|&(millis, price)| (millis.div_euclid(1_000), price))
+            .collect();
+        if points.is_empty() {
+            return 0;
+        }
+        points.sort_by_key(|&(unix_secs, _)| unix_secs);
+        points.dedup_by_key(|&mut (unix_secs, _)| unix_secs);
+
+        let mut image = self.lock();
+        if !image.series.contains_key(key) && image.series.len() >= MAX_SERIES {
+            return 0;
+        }
+        let added = image
+            .series
+            .entry(key.to_string())
+            .or_default()
+            .backfill(&points);
+        if added > 0 {
+            self.dirty.store(true, Ordering::Release);
+        }
+        added
+    }
+
+    /// Candles for `key` at `resolution` with `from <= time <= to`, oldest
+    /// first, keeping the newest `limit`. `None` if the currency is untracked.
+    pub fn candles(
+        &self,
+        key: &str,
+        resolution: Resolution,
+        from: Option<i64>,
+        to: Option<i64>,
+        limit: usize,
+    ) -> Option<Vec<Candle>> {
+        let image = self.lock();
+        let series = image.series.get(key)?;
+        let in_range: Vec<Candle> = series
+            .candles(resolution)
+            .iter()
+            .filter(|c| from.is_none_or(|from| c.time >= from))
+            .filter(|c| to.is_none_or(|to| c.time <= to))
+            .copied()
+            .collect();
+        let skip = in_range.len().saturating_sub(limit);
+        Some(in_range.into_iter().skip(skip).collect())
+    }
+
+    /// Every tracked `TICKER@PROTOCOL` key, sorted.
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.lock().series.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HistoryImage> {
+        self.image.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Ephemeral store bound to a unique temp file, for tests only.
+    #[cfg(test)]
+    pub fn ephemeral() -> Self {
+        let path = crate::image_file::ephemeral_path("price-history");
+        Self::create(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const T0: i64 = 1_700_000_070; // 30s into a minute
+
+    #[test]
+    fn record_builds_ohlc_per_bucket() {
+        let store = PriceHistoryStore::ephemeral();
+        store.record(&[("ATOM@P", 10.0)], T0);
+        store.record(&[("ATOM@P", 12.0)], T0 + 6);
+        store.record(&[("ATOM@P", 9.0)], T0 + 12);
+        store.record(&[("ATOM@P", 11.0)], T0 + 30); // next minute
+
+        let minutes = store
+            .candles("ATOM@P", Resolution::OneMinute, None, None, 100)
+            .unwrap();
+        assert_eq!(minutes.len(), 2);
+        assert_eq!(
+            minutes[0],
+            Candle {
+                time: Resolution::OneMinute.bucket_start(T0),
+                open: 10.0,
+                high: 12.0,
+                low: 9.0,
+                close: 9.0,
+            }
+        );
+        assert_eq!(minutes[1].open, 11.0);
+
+        let hours = store
+            .candles("ATOM@P", Resolution::OneHour, None, None, 100)
+            .unwrap();
+        assert_eq!(hours.len(), 1);
+        assert_eq!(
+            (hours[0].open, hours[0].high, hours[0].low, hours[0].close),
+            (10.0, 12.0, 9.0, 11.0)
+        );
+    }
+
+    #[test]
+    fn record_is_bounded_per_resolution() {
+        let store = PriceHistoryStore::ephemeral();
+        let cap = Resolution::OneMinute.capacity();
+        for i in 0..(cap + 10) {
+            let offset = i64::try_from(i).unwrap() * 60;
+            store.record(&[("ATOM@P", 1.0)], T0 + offset);
+        }
+        let minutes = store
+            .candles("ATOM@P", Resolution::OneMinute, None, None, usize::MAX)
+            .unwrap();
+        assert_eq!(minutes.len(), cap);
+    }
+
+    #[test]
+    fn late_samples_do_not_rewrite_history() {
+        let store = PriceHistoryStore::ephemeral();
+        store.record(&[("ATOM@P", 10.0)], T0 + 120);
+        store.record(&[("ATOM@P", 99.0)], T0);
+        let minutes = store
+            .candles("ATOM@P", Resolution::OneMinute, None, None, 100)
+            .unwrap();
+        assert_eq!(minutes.len(), 1);
+        assert_eq!(minutes[0].high, 10.0);
+    }
+
+    #[test]
+    fn backfill_only_fills_unseen_buckets() {
+        let store = PriceHistoryStore::ephemeral();
+        let live_bucket = Resolution::OneHour.bucket_start(T0);
+        store.record(&[("ATOM@P", 10.0)], T0);
+
+        // One point two hours earlier, one inside the live hour.
+        let points = [((T0 - 7_200) * 1_000, 8.0), (T0 * 1_000, 50.0)];
+        let added = store.backfill("ATOM@P", &points);
+        assert!(added > 0);
+
+        let hours = store
+            .candles("ATOM@P", Resolution::OneHour, None, None, 100)
+            .unwrap();
+        assert_eq!(hours.len(), 2);
+        assert_eq!(hours[0].close, 8.0);
+        assert_eq!(hours[1].time, live_bucket);
+        assert_eq!(hours[1].high, 10.0, "live candle must not be overwritten");
+
+        // Re-running the same backfill adds nothing.
+        assert_eq!(store.backfill("ATOM@P", &points), 0);
+    }
+
+    #[test]
+    fn candles_filters_range_and_keeps_newest() {
+        let store = PriceHistoryStore::ephemeral();
+        for i in 0..10 {
+            store.record(&[("ATOM@P", f64::from(i))], T0 + i64::from(i) * 60);
+        }
+        let start = Resolution::OneMinute.bucket_start(T0);
+        let window = store
+            .candles(
+                "ATOM@P",
+                Resolution::OneMinute,
+                Some(start + 120),
+                Some(start + 480),
+                3,
+            )
+            .unwrap();
+        let closes: Vec<f64> = window.iter().map(|c| c.close).collect();
+        assert_eq!(closes, vec![6.0, 7.0, 8.0]);
+        assert!(store
+            .candles("OSMO@P", Resolution::OneMinute, None, None, 3)
+            .is_none());
+    }
+
+    #[test]
+    fn series_cap_ignores_new_keys() {
+        let store = PriceHistoryStore::ephemeral();
+        for i in 0..MAX_SERIES {
+            store.record(&[(format!("T{i}@P").as_str(), 1.0)], T0);
+        }
+        store.record(&[("EXTRA@P", 1.0)], T0);
+        assert_eq!(store.keys().len(), MAX_SERIES);
+        assert!(!store.keys().contains(&"EXTRA@P".to_string()));
+    }
+
+    #[tokio::test]
+    async fn persisted_image_reloads() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("price_history.json");
+        let store = PriceHistoryStore::create(path.clone());
+        store.record(&[("ATOM@P", 10.0)], T0);
+        store.persist_if_dirty().await.unwrap();
+
+        let reloaded = PriceHistoryStore::load(path).await.unwrap();
+        let days = reloaded
+            .candles("ATOM@P", Resolution::OneDay, None, None, 10)
+            .unwrap();
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].close, 10.0);
+    }
+
+    #[tokio::test]
+    async fn corrupt_image_without_backup_fails_loudly() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("price_history.json");
+        tokio::fs::write(&path, b"{not json").await.unwrap();
+        assert!(PriceHistoryStore::load(path).await.is_err());
+    }
+}
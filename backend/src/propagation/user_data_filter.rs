@@ -298,6 +298,7 @@ mod tests {
                 ignore_short: vec!["RISKY_SHORT".to_string()],
             },
             due_projection_secs: 400,
+            alerts: Default::default(),
         }
     }
 
@@ -117,6 +117,66 @@ impl PropagationValidator {
             .map(|p| p.name.clone())
             .collect()
     }
+
+    /// Items a candidate config would take offline that the live config serves
+    ///
+    /// Compares the unconfigured currencies, unconfigured networks and unready
+    /// protocols of both configs and reports only those the candidate adds,
+    /// so pre-existing gaps never block a publish.
+    pub fn publish_regressions(
+        live_currencies: &CurrencyDisplayConfig,
+        live_networks: &GatedNetworkConfig,
+        candidate_currencies: &CurrencyDisplayConfig,
+        candidate_networks: &GatedNetworkConfig,
+        etl_protocols: &EtlProtocolsResponse,
+        etl_currencies: &EtlCurrenciesResponse,
+    ) -> Vec<String> {
+        fn added(
+            live: Vec<String>,
+            candidate: Vec<String>,
+            kind: &str,
+            state: &str,
+        ) -> Vec<String> {
+            let live: HashSet<String> = live.into_iter().collect();
+            let mut added: Vec<String> = candidate
+                .into_iter()
+                .filter(|item| !live.contains(item))
+                .map(|item| format!("{kind} {item} would become {state}"))
+                .collect();
+            added.sort();
+            added
+        }
+
+        let mut regressions = added(
+            Self::get_unconfigured_currencies(live_currencies, etl_currencies),
+            Self::get_unconfigured_currencies(candidate_currencies, etl_currencies),
+            "currency",
+            "unconfigured",
+        );
+        regressions.extend(added(
+            Self::get_unconfigured_networks(live_networks, etl_protocols),
+            Self::get_unconfigured_networks(candidate_networks, etl_protocols),
+            "network",
+            "unconfigured",
+        ));
+        regressions.extend(added(
+            Self::get_unready_protocols(
+                live_currencies,
+                live_networks,
+                etl_protocols,
+                etl_currencies,
+            ),
+            Self::get_unready_protocols(
+                candidate_currencies,
+                candidate_networks,
+                etl_protocols,
+                etl_currencies,
+            ),
+            "protocol",
+            "unready",
+        ));
+        regressions
+    }
 }
 
 #[cfg(test)]
@@ -365,4 +425,49 @@ mod tests {
         );
         assert!(unready.is_empty());
     }
+
+    /// Only gaps the candidate introduces are regressions; gaps the live
+    /// config already has (OSMO here) are not reported again.
+    #[test]
+    fn test_publish_regressions_reports_only_new_gaps() {
+        let atom = CurrencyDisplay {
+            icon: "/icons/atom.svg".to_string(),
+            display_name: "Cosmos".to_string(),
+            short_name: None,
+            color: None,
+            coingecko_id: None,
+        };
+        let live = CurrencyDisplayConfig {
+            currencies: HashMap::from([("ATOM".to_string(), atom)]),
+        };
+        let candidate = CurrencyDisplayConfig {
+            currencies: HashMap::new(),
+        };
+        let networks = GatedNetworkConfig {
+            networks: HashMap::new(),
+        };
+
+        let regressions = PropagationValidator::publish_regressions(
+            &live,
+            &networks,
+            &candidate,
+            &networks,
+            &mock_etl_protocols(),
+            &mock_etl_currencies(),
+        );
+        assert_eq!(
+            regressions,
+            vec!["currency ATOM would become unconfigured".to_string()]
+        );
+
+        let unchanged = PropagationValidator::publish_regressions(
+            &live,
+            &networks,
+            &live,
+            &networks,
+            &mock_etl_protocols(),
+            &mock_etl_currencies(),
+        );
+        assert!(unchanged.is_empty());
+    }
 }
@@ -35,9 +35,10 @@ where
 }
 
 use crate::chain_events::EventChannels;
-use crate::config_store::gated_types::NetworkSettings;
+use crate::config_store::gated_types::{GatedNetworkConfig, NetworkSettings, SwapSettingsConfig};
 use crate::data_cache::{GatedConfigBundle, ProposalsWithTally};
-use crate::external::chain::{ProtocolContractsInfo, TallyResult};
+use crate::external::chain::{ProtocolContractsInfo, TallyResult, ValidatorInfo};
+use crate::external::etl::{EtlCurrenciesResponse, EtlProtocolsResponse};
 use crate::handlers::config::{
     AppConfigResponse, ContractsInfo, NativeAssetInfo, NetworkInfo, ProtocolInfo,
 };
@@ -48,9 +49,12 @@ use crate::handlers::earn::EarnPool;
 use crate::handlers::etl_proxy::{LoansStatsBatch, StatsOverviewBatch};
 use crate::handlers::gated_assets::{get_price_for_asset, AssetResponse, AssetsResponse};
 use crate::handlers::gated_networks::NetworksResponse;
+use crate::handlers::governance::TallySample;
 use crate::handlers::leases::LeaseConfigResponse;
-use crate::handlers::staking::Validator;
+use crate::handlers::staking::{Validator, ValidatorStatus};
 use crate::handlers::swap::{NetworkTransfers, SwapConfigResponse, TransferCurrency};
+use crate::handlers::validator_scoring;
+use crate::notifications::{NotificationDraft, NotificationKind};
 use crate::propagation::user_data_filter::UserDataFilterContext;
 use crate::propagation::{PropagationFilter, PropagationMerger};
 use crate::AppState;
@@ -113,6 +117,7 @@ pub async fn warm_essential_data(state: Arc<AppState>) {
 ///
 /// **Group 6 — Slow (300s):**
 ///   swap_config — depends on gated_config + ETL, infrequently changing.
+///   validator_scores — one vote lookup per bonded validator and open proposal.
 pub fn start_all(state: Arc<AppState>, event_channels: &EventChannels) {
     // Group 1: Chain data (event-driven)
     spawn_event_refresh(
@@ -170,6 +175,9 @@ pub fn start_all(state: Arc<AppState>, event_channels: &EventChannels) {
     spawn_refresh("swap_config", state.clone(), 300, |s| {
         Box::pin(refresh_swap_config(s))
     });
+    spawn_refresh("validator_scores", state.clone(), 300, |s| {
+        Box::pin(refresh_validator_scores(s))
+    });
 
     info!("All background refresh tasks started (6 groups)");
 }
@@ -687,9 +695,12 @@ pub async fn refresh_prices(state: &Arc<AppState>) {
         }
     }
 
+    let now = chrono::Utc::now();
+    crate::price_history::record_prices(&state.price_history, prices.values(), now);
+
     let response = PricesResponse {
         prices,
-        updated_at: chrono::Utc::now().to_rfc3339(),
+        updated_at: now.to_rfc3339(),
     };
 
     state.data_cache.prices.store(response);
@@ -753,27 +764,178 @@ pub async fn refresh_validators(state: &Arc<AppState>) {
         }
     };
 
-    let result: Vec<Validator> = validators
-        .into_iter()
-        .map(|v| Validator {
-            operator_address: v.operator_address,
-            moniker: v.description.moniker,
-            identity: v.description.identity,
-            website: v.description.website,
-            details: v.description.details,
-            commission_rate: v.commission.commission_rates.rate,
-            max_commission_rate: v.commission.commission_rates.max_rate,
-            max_commission_change_rate: v.commission.commission_rates.max_change_rate,
-            tokens: v.tokens,
-            delegator_shares: v.delegator_shares,
-            unbonding_height: v.unbonding_height,
-            unbonding_time: v.unbonding_time,
-            status: crate::handlers::staking::parse_validator_status(&v.status),
-            jailed: v.jailed,
+    let result: Vec<Validator> = validators.into_iter().map(validator_from_info).collect();
+
+    state.data_cache.validators.store(result);
+}
+
+fn validator_from_info(v: ValidatorInfo) -> Validator {
+    Validator {
+        operator_address: v.operator_address,
+        moniker: v.description.moniker,
+        identity: v.description.identity,
+        website: v.description.website,
+        details: v.description.details,
+        commission_rate: v.commission.commission_rates.rate,
+        max_commission_rate: v.commission.commission_rates.max_rate,
+        max_commission_change_rate: v.commission.commission_rates.max_change_rate,
+        tokens: v.tokens,
+        delegator_shares: v.delegator_shares,
+        unbonding_height: v.unbonding_height,
+        unbonding_time: v.unbonding_time,
+        status: crate::handlers::staking::parse_validator_status(&v.status),
+        jailed: v.jailed,
+    }
+}
+
+/// Maximum concurrent vote lookups while recording validator participation.
+const VALIDATOR_VOTE_FANOUT_CAP: usize = 8;
+
+/// Refresh validator scores.
+///
+/// Fetches validators, slashing signing infos and the liveness window, then
+/// carries the prior snapshot's history forward:
+/// - proposals now in their voting period join the tracked set, and each
+///   bonded validator not yet seen voting on one is looked up (votes are
+///   pruned on-chain once voting ends, so they are only observable now);
+/// - commission rates that differ from the last recorded one are appended.
+///
+/// On any of the three chain calls failing, the cache is left untouched.
+/// Individual vote lookups that fail are retried next cycle.
+pub async fn refresh_validator_scores(state: &Arc<AppState>) {
+    let started = std::time::Instant::now();
+
+    let (validators, signing_infos, slashing_params) = match tokio::try_join!(
+        state.chain_client.get_validators(),
+        state.chain_client.get_signing_infos(),
+        state.chain_client.get_slashing_params(),
+    ) {
+        Ok(r) => r,
+        Err(e) => {
+            warn!("Failed to refresh validator scores: {}", e);
+            return;
+        }
+    };
+
+    let consensus_addresses: HashMap<String, String> = validators
+        .iter()
+        .filter_map(|v| {
+            let address = validator_scoring::consensus_address(v.consensus_pubkey.as_ref()?)?;
+            Some((v.operator_address.clone(), address))
         })
         .collect();
+    let validators: Vec<Validator> = validators.into_iter().map(validator_from_info).collect();
 
-    state.data_cache.validators.store(result);
+    let mut scores = state.data_cache.validator_scores.load().unwrap_or_default();
+    let now = chrono::Utc::now();
+
+    // Governance participation
+    let voting_ids: Vec<String> = state
+        .data_cache
+        .proposals_with_tally
+        .load()
+        .map(|p| {
+            p.proposals
+                .into_iter()
+                .filter(|p| p.status == PROPOSAL_STATUS_VOTING_PERIOD)
+                .map(|p| p.id)
+                .collect()
+        })
+        .unwrap_or_default();
+    validator_scoring::track_proposals(&mut scores, &voting_ids);
+
+    let lookups: Vec<(String, String, String)> = voting_ids
+        .iter()
+        .filter(|id| scores.tracked_proposals.contains(id))
+        .flat_map(|id| {
+            let voted = scores.votes.get(id);
+            validators
+                .iter()
+                .filter(|v| matches!(v.status, ValidatorStatus::Bonded))
+                .filter(move |v| voted.is_none_or(|set| !set.contains(&v.operator_address)))
+                .filter_map(move |v| {
+                    let account = validator_scoring::operator_account_address(&v.operator_address)?;
+                    Some((id.clone(), v.operator_address.clone(), account))
+                })
+        })
+        .collect();
+    let lookup_count = lookups.len();
+
+    let chain_client = state.chain_client.clone();
+    let vote_results: Vec<(String, String, Result<bool, String>)> = stream::iter(lookups)
+        .map(|(id, operator, account)| {
+            let chain_client = chain_client.clone();
+            async move {
+                let res = chain_client
+                    .get_proposal_vote(&id, &account)
+                    .await
+                    .map(|vote| vote.is_some())
+                    .map_err(|e| e.to_string());
+                (id, operator, res)
+            }
+        })
+        .buffer_unordered(VALIDATOR_VOTE_FANOUT_CAP)
+        .collect()
+        .await;
+
+    let mut vote_errors: usize = 0;
+    for (id, operator, result) in vote_results {
+        match result {
+            Ok(true) => {
+                scores.votes.entry(id).or_default().insert(operator);
+            }
+            Ok(false) => {}
+            Err(e) => {
+                debug!("Failed to look up vote of {} on {}: {}", operator, id, e);
+                vote_errors += 1;
+            }
+        }
+    }
+
+    // Commission history, pruned to validators still listed
+    for v in &validators {
+        validator_scoring::record_commission(
+            scores
+                .commission_history
+                .entry(v.operator_address.clone())
+                .or_default(),
+            &v.commission_rate,
+            now,
+        );
+    }
+    let listed: std::collections::HashSet<&str> = validators
+        .iter()
+        .map(|v| v.operator_address.as_str())
+        .collect();
+    scores
+        .commission_history
+        .retain(|address, _| listed.contains(address.as_str()));
+
+    let signed_blocks_window: u64 = slashing_params.signed_blocks_window.parse().unwrap_or(0);
+    let (computed, nakamoto_coefficient) = validator_scoring::compute_scores(
+        &validator_scoring::ScoringInputs {
+            validators: &validators,
+            consensus_addresses: &consensus_addresses,
+            signing_infos: &signing_infos,
+            signed_blocks_window,
+        },
+        &scores,
+    );
+    scores.scores = computed;
+    scores.signed_blocks_window = signed_blocks_window;
+    scores.nakamoto_coefficient = nakamoto_coefficient;
+    scores.updated_at = Some(now);
+
+    let validator_count = scores.scores.len();
+    state.data_cache.validator_scores.store(scores);
+
+    info!(
+        "validator_scores_refresh: validators={} vote_lookups={} vote_errors={} elapsed_ms={}",
+        validator_count,
+        lookup_count,
+        vote_errors,
+        started.elapsed().as_millis(),
+    );
 }
 
 /// Status string the chain reports for proposals currently accepting votes.
@@ -785,6 +947,74 @@ const PROPOSAL_STATUS_VOTING_PERIOD: &str = "PROPOSAL_STATUS_VOTING_PERIOD";
 /// chain-client permits.
 const PROPOSAL_TALLY_FANOUT_CAP: usize = 8;
 
+/// Minimum spacing between two tally history samples of one proposal.
+const TALLY_SAMPLE_INTERVAL_SECS: i64 = 15 * 60;
+
+/// Per-proposal tally history cap. On overflow every other sample is dropped,
+/// so the history keeps spanning the whole voting period at a coarser step.
+const MAX_TALLY_SAMPLES: usize = 672;
+
+/// A proposal that appeared or changed status between two refreshes.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ProposalStatusChange {
+    pub proposal_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// `None` when the proposal is new since the previous refresh
+    pub previous_status: Option<String>,
+    pub status: String,
+}
+
+/// Diff two proposal lists by id. Proposals that dropped out of the list are
+/// ignored (the list is a bounded window, not a full history).
+pub fn proposal_status_changes(
+    prior: &[crate::external::chain::Proposal],
+    current: &[crate::external::chain::Proposal],
+) -> Vec<ProposalStatusChange> {
+    let prior_status: HashMap<&str, &str> = prior
+        .iter()
+        .map(|p| (p.id.as_str(), p.status.as_str()))
+        .collect();
+    current
+        .iter()
+        .filter_map(|p| {
+            let previous = prior_status.get(p.id.as_str()).copied();
+            (previous != Some(p.status.as_str())).then(|| ProposalStatusChange {
+                proposal_id: p.id.clone(),
+                title: p.title.clone(),
+                previous_status: previous.map(str::to_string),
+                status: p.status.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Append `tally` to a proposal's history unless the last sample is younger
+/// than [`TALLY_SAMPLE_INTERVAL_SECS`], halving the resolution at the cap.
+fn record_tally_sample(
+    history: &mut Vec<TallySample>,
+    tally: &TallyResult,
+    at: chrono::DateTime<chrono::Utc>,
+) {
+    if history
+        .last()
+        .is_some_and(|last| (at - last.at).num_seconds() < TALLY_SAMPLE_INTERVAL_SECS)
+    {
+        return;
+    }
+    if history.len() >= MAX_TALLY_SAMPLES {
+        let mut index = 0;
+        history.retain(|_| {
+            index += 1;
+            index % 2 == 1
+        });
+    }
+    history.push(TallySample {
+        at,
+        tally: tally.clone(),
+    });
+}
+
 /// Refresh governance proposals plus per-proposal tallies for those in
 /// `PROPOSAL_STATUS_VOTING_PERIOD`.
 ///
@@ -806,11 +1036,11 @@ pub async fn refresh_governance_proposals(state: &Arc<AppState>) {
     };
     let proposals = proposals_response.proposals;
 
-    let prior_tallies: HashMap<String, TallyResult> = state
+    let (prior_tallies, mut tally_history) = state
         .data_cache
         .proposals_with_tally
         .load()
-        .map(|p| p.tallies)
+        .map(|p| (p.tallies, p.tally_history))
         .unwrap_or_default();
 
     let voting_ids: Vec<String> = proposals
@@ -848,9 +1078,11 @@ pub async fn refresh_governance_proposals(state: &Arc<AppState>) {
 
     let mut tallies_ok: usize = 0;
     let mut tallies_kept_prior: usize = 0;
+    let now = chrono::Utc::now();
     for (id, result) in tally_results {
         match result {
             Ok(tally) => {
+                record_tally_sample(tally_history.entry(id.clone()).or_default(), &tally, now);
                 tallies.insert(id, tally);
                 tallies_ok += 1;
             }
@@ -863,11 +1095,50 @@ pub async fn refresh_governance_proposals(state: &Arc<AppState>) {
         }
     }
 
+    // Status transitions vs the prior cycle (none on the very first refresh)
+    let status_changes = state
+        .data_cache
+        .proposals_with_tally
+        .load()
+        .map(|prior| proposal_status_changes(&prior.proposals, &proposals))
+        .unwrap_or_default();
+
+    // History outlives voting; it only goes once the proposal leaves the list
+    let listed: std::collections::HashSet<&str> = proposals.iter().map(|p| p.id.as_str()).collect();
+    tally_history.retain(|id, _| listed.contains(id.as_str()));
+
     let proposal_count = proposals.len();
     state
         .data_cache
         .proposals_with_tally
-        .store(ProposalsWithTally { proposals, tallies });
+        .store(ProposalsWithTally {
+            proposals,
+            tallies,
+            tally_history,
+        });
+
+    if !status_changes.is_empty() {
+        state
+            .ws_manager
+            .send_proposal_status_changes(&status_changes);
+        let recipients = state
+            .notification_store
+            .registered_addresses(NotificationKind::Governance);
+        let drafts = recipients
+            .iter()
+            .flat_map(|address| {
+                status_changes.iter().map(move |change| {
+                    NotificationDraft::new(
+                        address,
+                        NotificationKind::Governance,
+                        "proposal_status_changed",
+                        serde_json::to_value(change).unwrap_or_default(),
+                    )
+                })
+            })
+            .collect();
+        crate::notifications::record(state, drafts);
+    }
 
     info!(
         "governance_refresh: proposals={} voting={} tallies_ok={} tallies_kept_prior={} elapsed_ms={}",
@@ -921,8 +1192,25 @@ pub async fn refresh_gated_assets(state: &Arc<AppState>) {
         }
     };
 
-    let configured_protocols = PropagationFilter::filter_protocols(
+    state.data_cache.gated_assets.store(build_gated_assets(
+        &gated,
+        &etl_currencies,
         &etl_protocols,
+        &prices.prices,
+    ));
+}
+
+/// Build the gated assets view from a gated config bundle and ETL data.
+///
+/// Shared by the background refresh and the admin dry-run preview.
+pub fn build_gated_assets(
+    gated: &GatedConfigBundle,
+    etl_currencies: &EtlCurrenciesResponse,
+    etl_protocols: &EtlProtocolsResponse,
+    prices: &HashMap<String, PriceInfo>,
+) -> AssetsResponse {
+    let configured_protocols = PropagationFilter::filter_protocols(
+        etl_protocols,
         &gated.currency_display,
         &gated.network_config,
     );
@@ -974,12 +1262,7 @@ pub async fn refresh_gated_assets(state: &Arc<AppState>) {
             .into_iter()
             .collect();
 
-        let price = get_price_for_asset(
-            &currency.ticker,
-            &networks,
-            &gated.network_config,
-            &prices.prices,
-        );
+        let price = get_price_for_asset(&currency.ticker, &networks, &gated.network_config, prices);
 
         assets.push(AssetResponse {
             ticker: currency.ticker.clone(),
@@ -998,20 +1281,14 @@ pub async fn refresh_gated_assets(state: &Arc<AppState>) {
         });
     }
 
-    let response = AssetsResponse {
+    AssetsResponse {
         count: assets.len(),
         assets,
-    };
-
-    state.data_cache.gated_assets.store(response);
+    }
 }
 
 /// Refresh gated protocols
 pub async fn refresh_gated_protocols(state: &Arc<AppState>) {
-    use crate::handlers::common_types::{CurrencyDisplayInfo, ProtocolContracts};
-    use crate::handlers::gated_protocols::ProtocolResponse;
-    use std::collections::HashSet;
-
     let gated = match state.data_cache.gated_config.load() {
         Some(g) => g,
         None => return,
@@ -1025,8 +1302,23 @@ pub async fn refresh_gated_protocols(state: &Arc<AppState>) {
         }
     };
 
+    state
+        .data_cache
+        .gated_protocols
+        .store(build_gated_protocols(&gated, &etl_protocols));
+}
+
+/// Build the gated protocols view from a gated config bundle and ETL protocols.
+pub fn build_gated_protocols(
+    gated: &GatedConfigBundle,
+    etl_protocols: &EtlProtocolsResponse,
+) -> crate::data_cache::GatedProtocolsResponse {
+    use crate::handlers::common_types::{CurrencyDisplayInfo, ProtocolContracts};
+    use crate::handlers::gated_protocols::ProtocolResponse;
+    use std::collections::HashSet;
+
     let filtered_protocols = PropagationFilter::filter_protocols(
-        &etl_protocols,
+        etl_protocols,
         &gated.currency_display,
         &gated.network_config,
     );
@@ -1085,24 +1377,30 @@ pub async fn refresh_gated_protocols(state: &Arc<AppState>) {
         })
         .collect();
 
-    let response = crate::data_cache::GatedProtocolsResponse {
+    crate::data_cache::GatedProtocolsResponse {
         count: protocols.len(),
         protocols,
-    };
-
-    state.data_cache.gated_protocols.store(response);
+    }
 }
 
 /// Refresh gated networks
 pub async fn refresh_gated_networks(state: &Arc<AppState>) {
-    use crate::handlers::gated_networks::NetworkResponse;
-
     let gated = match state.data_cache.gated_config.load() {
         Some(g) => g,
         None => return,
     };
 
-    let merged_networks = PropagationMerger::merge_networks(&gated.network_config);
+    state
+        .data_cache
+        .gated_networks
+        .store(build_gated_networks(&gated.network_config));
+}
+
+/// Build the gated networks view from the network config alone.
+pub fn build_gated_networks(network_config: &GatedNetworkConfig) -> NetworksResponse {
+    use crate::handlers::gated_networks::NetworkResponse;
+
+    let merged_networks = PropagationMerger::merge_networks(network_config);
 
     let networks: Vec<NetworkResponse> = merged_networks
         .into_iter()
@@ -1124,12 +1422,10 @@ pub async fn refresh_gated_networks(state: &Arc<AppState>) {
         })
         .collect();
 
-    let response = NetworksResponse {
+    NetworksResponse {
         count: networks.len(),
         networks,
-    };
-
-    state.data_cache.gated_networks.store(response);
+    }
 }
 
 /// Refresh stats overview batch from ETL
@@ -1210,8 +1506,20 @@ pub async fn refresh_swap_config(state: &Arc<AppState>) {
         }
     };
 
-    let swap_settings = &gated.swap_settings;
+    state.data_cache.swap_config.store(build_swap_config(
+        &gated.swap_settings,
+        &protocols_response,
+        &currencies_response,
+    ));
+}
 
+/// Resolve swap settings against ETL protocols and currencies into the
+/// swap config served to the frontend.
+pub fn build_swap_config(
+    swap_settings: &SwapSettingsConfig,
+    protocols_response: &EtlProtocolsResponse,
+    currencies_response: &EtlCurrenciesResponse,
+) -> SwapConfigResponse {
     // Build protocol -> network lookup
     let mut protocol_to_network: HashMap<String, String> = HashMap::new();
     for protocol in &protocols_response.protocols {
@@ -1292,13 +1600,13 @@ pub async fn refresh_swap_config(state: &Arc<AppState>) {
         swap_currencies.insert(format!("swap_currency_{}", network), denom);
     }
 
-    state.data_cache.swap_config.store(SwapConfigResponse {
+    SwapConfigResponse {
         blacklist: swap_settings.blacklist.clone(),
         fee: swap_settings.fee,
         swap_to_currency,
         transfers,
         swap_currencies,
-    });
+    }
 }
 
 /// Refresh lease configs for all protocols
@@ -1334,6 +1642,7 @@ pub async fn refresh_lease_configs(state: &Arc<AppState>) {
                         downpayment_ranges,
                         min_asset: leaser_config.lease_position_spec.min_asset,
                         min_transaction: leaser_config.lease_position_spec.min_transaction,
+                        liability: leaser_config.lease_position_spec.liability,
                     },
                 );
             }
@@ -1622,6 +1931,7 @@ mod tests {
                 downpayment_ranges: HashMap::new(),
                 asset_restrictions: AssetRestrictions::default(),
                 due_projection_secs: 400,
+                alerts: Default::default(),
             },
             swap_settings: SwapSettingsConfig {
                 api_url: "https://api.skip.build".to_string(),
@@ -1948,27 +2258,27 @@ mod tests {
 
         state
             .config_store
-            .save_currency_display(&bundle.currency_display)
+            .save_currency_display(&bundle.currency_display, None)
             .await
             .expect("seed currency_display");
         state
             .config_store
-            .save_gated_network_config(&bundle.network_config)
+            .save_gated_network_config(&bundle.network_config, None)
             .await
             .expect("seed network_config");
         state
             .config_store
-            .save_lease_rules(&bundle.lease_rules)
+            .save_lease_rules(&bundle.lease_rules, None)
             .await
             .expect("seed lease_rules");
         state
             .config_store
-            .save_swap_settings(&bundle.swap_settings)
+            .save_swap_settings(&bundle.swap_settings, None)
             .await
             .expect("seed swap_settings");
         state
             .config_store
-            .save_ui_settings(&bundle.ui_settings)
+            .save_ui_settings(&bundle.ui_settings, None)
             .await
             .expect("seed ui_settings");
 
@@ -2331,6 +2641,48 @@ mod tests {
         assert_eq!(loaded[0].operator_address, "nolusvaloper1sentinel");
     }
 
+    #[tokio::test]
+    async fn refresh_validator_scores_populates_and_keeps_history() {
+        let (state, _etl, chain) = state_with_wiremock_etl_and_chain().await;
+
+        Mock::given(method("GET"))
+            .and(path("/cosmos/staking/v1beta1/validators"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(validators_body()))
+            .mount(&chain)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/slashing/v1beta1/signing_infos"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "info": [] })))
+            .mount(&chain)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/slashing/v1beta1/params"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "params": {
+                    "signed_blocks_window": "10000",
+                    "min_signed_per_window": "0.050000000000000000"
+                }
+            })))
+            .mount(&chain)
+            .await;
+
+        refresh_validator_scores(&state).await;
+        refresh_validator_scores(&state).await;
+
+        let loaded = state
+            .data_cache
+            .validator_scores
+            .load()
+            .expect("validator scores populated");
+        assert_eq!(loaded.signed_blocks_window, 10_000);
+        assert_eq!(loaded.scores.len(), 3);
+        // No signing record, no tracked proposals: both stay unknown
+        assert!(loaded.scores[0].uptime.is_none());
+        assert!(loaded.scores[0].governance_participation.is_none());
+        // The unchanged rate is recorded once across both refreshes
+        assert_eq!(loaded.commission_history["nolusvaloper1abc"].len(), 1);
+    }
+
     // =======================================================================
     // refresh_annual_inflation / refresh_staking_pool
     // =======================================================================
@@ -2940,6 +3292,15 @@ mod tests {
                     amount: "1".to_string(),
                     ticker: "USDC".to_string(),
                 },
+                liability: crate::external::chain::LiabilitySpec {
+                    initial: 600,
+                    healthy: 830,
+                    first_liq_warn: 850,
+                    second_liq_warn: 865,
+                    third_liq_warn: 880,
+                    max: 900,
+                    recalc_time: 7_200_000_000_000,
+                },
             },
         );
         state.data_cache.lease_configs.store(sentinel);
@@ -3071,6 +3432,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tally_samples_are_spaced_and_thinned_at_the_cap() {
+        let start = chrono::Utc::now();
+        let mut history = Vec::new();
+        record_tally_sample(&mut history, &sample_tally("1"), start);
+        record_tally_sample(
+            &mut history,
+            &sample_tally("2"),
+            start + chrono::Duration::seconds(60),
+        );
+        assert_eq!(
+            history.len(),
+            1,
+            "samples closer than the interval are skipped"
+        );
+
+        for step in 1..=i64::try_from(MAX_TALLY_SAMPLES).unwrap() {
+            record_tally_sample(
+                &mut history,
+                &sample_tally("n"),
+                start + chrono::Duration::seconds(step * TALLY_SAMPLE_INTERVAL_SECS),
+            );
+        }
+        assert_eq!(history.len(), MAX_TALLY_SAMPLES / 2 + 1);
+        assert_eq!(history[0].at, start, "thinning keeps the first sample");
+    }
+
     fn proposals_body(proposals: &[crate::external::chain::Proposal]) -> serde_json::Value {
         json!({
             "proposals": proposals,
@@ -3078,6 +3466,39 @@ mod tests {
         })
     }
 
+    #[test]
+    fn proposal_status_changes_reports_new_and_transitioned_only() {
+        let prior = vec![
+            sample_proposal("1", "PROPOSAL_STATUS_VOTING_PERIOD"),
+            sample_proposal("2", "PROPOSAL_STATUS_PASSED"),
+            sample_proposal("9", "PROPOSAL_STATUS_REJECTED"),
+        ];
+        let current = vec![
+            sample_proposal("1", "PROPOSAL_STATUS_PASSED"),
+            sample_proposal("2", "PROPOSAL_STATUS_PASSED"),
+            sample_proposal("3", "PROPOSAL_STATUS_DEPOSIT_PERIOD"),
+        ];
+
+        let changes = proposal_status_changes(&prior, &current);
+        assert_eq!(
+            changes,
+            vec![
+                ProposalStatusChange {
+                    proposal_id: "1".to_string(),
+                    title: Some("proposal 1".to_string()),
+                    previous_status: Some("PROPOSAL_STATUS_VOTING_PERIOD".to_string()),
+                    status: "PROPOSAL_STATUS_PASSED".to_string(),
+                },
+                ProposalStatusChange {
+                    proposal_id: "3".to_string(),
+                    title: Some("proposal 3".to_string()),
+                    previous_status: None,
+                    status: "PROPOSAL_STATUS_DEPOSIT_PERIOD".to_string(),
+                },
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn refresh_governance_proposals_populates_on_success() {
         let (state, _etl, chain) = state_with_wiremock_etl_and_chain().await;
@@ -3123,6 +3544,9 @@ mod tests {
             .expect("tally for voting proposal 1 stored");
         assert_eq!(tally_a.yes_count, "100");
         assert!(!loaded.tallies.contains_key("2"));
+
+        // The successful tally is also the first history sample.
+        assert_eq!(loaded.tally_history.get("1").map(Vec::len), Some(1));
     }
 
     #[tokio::test]
@@ -3142,6 +3566,7 @@ mod tests {
             .store(crate::data_cache::ProposalsWithTally {
                 proposals: vec![prior_a.clone(), prior_b.clone()],
                 tallies: prior_tallies,
+                tally_history: HashMap::new(),
             });
 
         // Fresh proposals list: A and B both still in voting period.
@@ -3211,6 +3636,7 @@ mod tests {
                     sample_proposal("2", "PROPOSAL_STATUS_VOTING_PERIOD"),
                 ],
                 tallies: prior_tallies,
+                tally_history: HashMap::new(),
             });
 
         // Fresh fetch: A still voting, B passed.
@@ -13,7 +13,9 @@ use std::time::Instant;
 use axum::response::Response;
 use http_body_util::BodyExt;
 
-use crate::config::{AdminConfig, AppConfig, ExternalApiConfig, ProtocolsConfig, ServerConfig};
+use crate::config::{
+    AdminConfig, AppConfig, ExternalApiConfig, ProtocolsConfig, RestakeConfig, ServerConfig,
+};
 use crate::config_store::ConfigStore;
 use crate::data_cache::AppDataCache;
 use crate::external;
@@ -39,6 +41,8 @@ pub fn test_config() -> AppConfig {
             skip_api_key: None,
             nolus_rpc_url: "http://127.0.0.1:1/".to_string(),
             nolus_rest_url: "http://127.0.0.1:1/".to_string(),
+            nolus_rpc_fallback_urls: Vec::new(),
+            nolus_rest_fallback_urls: Vec::new(),
             solana_rpc_url: Some("http://127.0.0.1:1/".to_string()),
             referral_api_url: "http://127.0.0.1:1/".to_string(),
             referral_api_token: "stub".to_string(),
@@ -50,8 +54,10 @@ pub fn test_config() -> AppConfig {
         admin: AdminConfig {
             enabled: false,
             api_key: String::new(),
+            trusted_proxies: Vec::new(),
         },
         protocols: ProtocolsConfig::default(),
+        restake: RestakeConfig::default(),
     }
 }
 
@@ -163,6 +169,11 @@ pub async fn test_app_state_with_config_client_and_store(
         translation_storage,
         llm_client,
         transfer_store,
+        notification_store: crate::notifications::NotificationStore::ephemeral(),
+        price_history: crate::price_history::PriceHistoryStore::ephemeral(),
+        lease_ledger: crate::lease_ledger::LeaseLedgerStore::ephemeral(),
+        earn_ledger: crate::earn_ledger::EarnLedgerStore::ephemeral(),
+        admin_keys: crate::admin_keys::AdminKeyStore::ephemeral(),
         startup_time: Instant::now(),
     })
 }
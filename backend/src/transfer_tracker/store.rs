@@ -7,21 +7,16 @@
 //! silent empty start. Terminal routes are retained for a window, then pruned.
 
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::path::PathBuf;
 use std::sync::{Mutex, PoisonError};
 
 use chrono::{DateTime, Duration, Utc};
-use tokio::io::AsyncWriteExt as _;
-use tracing::warn;
 
 use crate::error::AppError;
+use crate::image_file::ImageFile;
 
 use super::TrackedTransfer;
 
-/// Monotonic suffix source for temp-file uniqueness within a persist directory.
-static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
-
 /// The durable, capped, self-pruning tracking set.
 ///
 /// The canonical map is guarded by a single std [`Mutex`], never held across an
@@ -29,7 +24,7 @@ static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
 /// I/O. A separate async `write_gate` serializes persist calls so that the
 /// image written to disk is always the newest snapshot (see [`persist`]).
 pub struct TransferStore {
-    path: PathBuf,
+    file: ImageFile,
     capacity: usize,
     retention: Duration,
     entries: Mutex<HashMap<String, TrackedTransfer>>,
@@ -41,7 +36,7 @@ impl TransferStore {
     /// when no prior image exists.
     pub fn create(path: PathBuf, capacity: usize, retention: Duration) -> Self {
         Self {
-            path,
+            file: ImageFile::new(path, "transfer store"),
             capacity,
             retention,
             entries: Mutex::new(HashMap::new()),
@@ -57,21 +52,10 @@ impl TransferStore {
         capacity: usize,
         retention: Duration,
     ) -> Result<Self, AppError> {
-        let primary = Self::read_image(&path).await;
-        let entries = match primary {
-            Ok(map) => map,
-            Err(primary_err) => {
-                let backup = Self::backup_path(&path);
-                Self::read_image(&backup).await.map_err(|_backup_err| {
-                    AppError::Internal(format!(
-                        "transfer store image at {} is unreadable and no valid backup exists: {primary_err}",
-                        path.display()
-                    ))
-                })?
-            }
-        };
+        let file = ImageFile::new(path, "transfer store");
+        let entries = file.load().await?;
         let store = Self {
-            path,
+            file,
             capacity,
             retention,
             entries: Mutex::new(entries),
@@ -92,50 +76,7 @@ impl TransferStore {
         // newer one (silent record loss across a restart).
         let _write = self.write_gate.lock().await;
         let snapshot = self.snapshot();
-        let bytes = serde_json::to_vec_pretty(&snapshot)
-            .map_err(|e| AppError::Internal(format!("serialising transfer store: {e}")))?;
-
-        let dir = self.path.parent().filter(|p| !p.as_os_str().is_empty());
-        let temp = self.temp_path();
-
-        {
-            let mut file = tokio::fs::File::create(&temp).await.map_err(|e| {
-                AppError::Internal(format!("creating transfer store temp file: {e}"))
-            })?;
-            file.write_all(&bytes).await.map_err(|e| {
-                AppError::Internal(format!("writing transfer store temp file: {e}"))
-            })?;
-            file.sync_all().await.map_err(|e| {
-                AppError::Internal(format!("syncing transfer store temp file: {e}"))
-            })?;
-        }
-
-        tokio::fs::rename(&temp, &self.path)
-            .await
-            .map_err(|e| AppError::Internal(format!("committing transfer store image: {e}")))?;
-
-        // The parent-dir fsync durably records the rename; a failure here does
-        // not lose the written image (already fsync'd), so it is non-fatal, but
-        // it must be surfaced rather than swallowed.
-        if let Some(dir) = dir {
-            match tokio::fs::File::open(dir).await {
-                Ok(handle) => {
-                    if let Err(e) = handle.sync_all().await {
-                        warn!(
-                            "transfer store parent-dir fsync failed ({}): {e}",
-                            dir.display()
-                        );
-                    }
-                }
-                Err(e) => {
-                    warn!(
-                        "transfer store parent-dir open failed ({}): {e}",
-                        dir.display()
-                    );
-                }
-            }
-        }
-        Ok(())
+        self.file.write(&snapshot).await
     }
 
     /// Insert a new tracked route. Rejects with `Err` once the active set is at
@@ -215,31 +156,6 @@ impl TransferStore {
         self.lock().clone()
     }
 
-    /// The `<path>.bak` fallback image path.
-    fn backup_path(path: &Path) -> PathBuf {
-        let mut name = path.file_name().unwrap_or_default().to_os_string();
-        name.push(".bak");
-        path.with_file_name(name)
-    }
-
-    /// A unique temp path in the image's directory for an atomic write.
-    fn temp_path(&self) -> PathBuf {
-        let counter = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
-        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
-        name.push(format!(".tmp-{}-{counter}", std::process::id()));
-        self.path.with_file_name(name)
-    }
-
-    /// Read and deserialise a whole-map image. Any I/O or parse failure is an
-    /// `Err` — a missing or corrupt image never yields an empty map here.
-    async fn read_image(path: &Path) -> Result<HashMap<String, TrackedTransfer>, AppError> {
-        let bytes = tokio::fs::read(path)
-            .await
-            .map_err(|e| AppError::Internal(format!("reading transfer store image: {e}")))?;
-        serde_json::from_slice(&bytes)
-            .map_err(|e| AppError::Internal(format!("parsing transfer store image: {e}")))
-    }
-
     /// Ephemeral store bound to a unique temp file, for tests only.
     #[cfg(test)]
     pub fn ephemeral() -> Self {
@@ -250,11 +166,7 @@ impl TransferStore {
     /// for tests only.
     #[cfg(test)]
     pub fn ephemeral_with_capacity(capacity: usize) -> Self {
-        let path = std::env::temp_dir().join(format!(
-            "transfers-test-{}-{}.json",
-            std::process::id(),
-            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
-        ));
+        let path = crate::image_file::ephemeral_path("transfers");
         Self::create(path, capacity, Duration::hours(1))
     }
 }
@@ -1,16 +1,17 @@
 //! Translation-specific audit log
 //!
 //! Tracks all translation changes with detailed metadata for compliance
-//! and rollback capabilities.
+//! and rollback capabilities. Entries are appended to a hash-chained
+//! [`AuditChain`] alongside the config audit log.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use tokio::fs;
-use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 
 use super::types::TranslationSource;
+use crate::audit::{AuditChain, AuditKey, AuditRecord, AuditRetention, AuditRow, AuditSource};
 use crate::error::AppError;
 
 /// Audit action types for translations
@@ -28,6 +29,22 @@ pub enum AuditAction {
     DirectEdit,
 }
 
+impl AuditAction {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Approve => "approve",
+            Self::Reject => "reject",
+            Self::Edit => "edit",
+            Self::BulkApprove => "bulk_approve",
+            Self::AddLanguage => "add_language",
+            Self::RemoveLanguage => "remove_language",
+            Self::Generate => "generate",
+            Self::Restore => "restore",
+            Self::DirectEdit => "direct_edit",
+        }
+    }
+}
+
 /// A single audit log entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranslationAuditEntry {
@@ -72,6 +89,56 @@ pub struct TranslationAuditEntry {
     /// Additional metadata
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
+
+    /// Requester IP (filled in by [`TranslationAuditLog::record`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+
+    /// `x-request-id` of the admin request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl AuditRecord for TranslationAuditEntry {
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    fn to_row(&self, seq: u64, hash: &str) -> AuditRow {
+        let resource = match &self.key {
+            Some(key) => format!("translations/{}/{}", self.lang, key),
+            None => format!("translations/{}", self.lang),
+        };
+        let details = [
+            self.reason.as_ref().map(|r| format!("reason: {}", r)),
+            self.count.map(|c| format!("count: {}", c)),
+            self.batch_id.as_ref().map(|b| format!("batch: {}", b)),
+            self.ai_model.as_ref().map(|m| format!("model: {}", m)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        AuditRow {
+            source: AuditSource::Translations,
+            seq,
+            hash: hash.to_string(),
+            id: self.id.clone(),
+            timestamp: self.timestamp,
+            actor: self.admin_user.clone(),
+            action: self.action.as_str().to_string(),
+            resource,
+            details: (!details.is_empty()).then(|| details.join(", ")),
+            before: self.old_value.clone().map(serde_json::Value::String),
+            after: self
+                .new_value
+                .clone()
+                .map(serde_json::Value::String)
+                .or_else(|| self.metadata.clone()),
+            ip: self.ip.clone(),
+            request_id: self.request_id.clone(),
+        }
+    }
 }
 
 /// Audit log file structure
@@ -83,57 +150,56 @@ pub struct AuditLogFile {
 /// Translation audit log manager
 #[derive(Debug)]
 pub struct TranslationAuditLog {
-    /// Path to the audit log file
-    file_path: PathBuf,
-    /// In-memory entries (most recent)
-    entries: RwLock<Vec<TranslationAuditEntry>>,
-    /// Maximum entries to keep in memory
-    max_memory_entries: usize,
+    /// Pre-chain `audit.json`, imported on init
+    legacy_path: PathBuf,
+    /// Hash-chained entries
+    chain: AuditChain<TranslationAuditEntry>,
 }
 
 impl TranslationAuditLog {
-    /// Create a new audit log
-    pub fn new(file_path: PathBuf) -> Self {
+    /// Create a new audit log stored in `chain_dir`
+    pub fn new(chain_dir: PathBuf, legacy_path: PathBuf) -> Self {
         Self {
-            file_path,
-            entries: RwLock::new(Vec::new()),
-            max_memory_entries: 1000,
+            legacy_path,
+            chain: AuditChain::new(chain_dir, AuditRetention::from_env(), AuditKey::from_env()),
         }
     }
 
-    /// Initialize the audit log, loading existing entries
+    /// Initialize the audit log, migrating a legacy `audit.json` if present
     pub async fn init(&self) -> Result<(), AppError> {
-        if self.file_path.exists() {
-            match self.load_from_file().await {
-                Ok(entries) => {
-                    let mut log = self.entries.write().await;
-                    *log = entries;
-                    info!("Loaded {} translation audit entries", log.len());
-                }
+        self.chain.init().await?;
+        if self.legacy_path.exists() {
+            match self.load_legacy().await {
+                Ok(entries) => self.chain.import_legacy(&self.legacy_path, entries).await?,
                 Err(e) => {
-                    warn!("Could not load translation audit log: {}", e);
+                    warn!("Could not migrate translation audit log: {}", e);
                 }
             }
         }
         Ok(())
     }
 
-    /// Record an audit entry
-    pub async fn record(&self, entry: TranslationAuditEntry) {
-        {
-            let mut log = self.entries.write().await;
-            log.push(entry.clone());
-
-            // Trim if exceeds max
-            let len = log.len();
-            if len > self.max_memory_entries {
-                log.drain(0..len - self.max_memory_entries);
-            }
-        }
+    /// Hash-chained translation audit entries
+    pub const fn chain(&self) -> &AuditChain<TranslationAuditEntry> {
+        &self.chain
+    }
 
-        // Persist asynchronously (best effort)
-        if let Err(e) = self.persist().await {
-            error!("Failed to persist translation audit log: {}", e);
+    /// Record an audit entry, tagged with the current admin request
+    ///
+    /// Entries are recorded after the change is applied, so a failed append
+    /// is logged rather than returned: failing the request would report an
+    /// applied change as not made.
+    pub async fn record(&self, mut entry: TranslationAuditEntry) {
+        let request = crate::audit::current_request();
+        entry.ip = request.ip;
+        entry.request_id = request.request_id;
+
+        if let Err(e) = self.chain.append(&entry).await {
+            error!(
+                "Failed to record translation audit entry {:?} - {} - {:?}: {}",
+                entry.action, entry.lang, entry.key, e
+            );
+            return;
         }
 
         info!(
@@ -167,8 +233,10 @@ impl TranslationAuditLog {
             count: None,
             batch_id: None,
             metadata: None,
+            ip: None,
+            request_id: None,
         })
-        .await;
+        .await
     }
 
     /// Record a reject action
@@ -194,8 +262,10 @@ impl TranslationAuditLog {
             count: None,
             batch_id: None,
             metadata: None,
+            ip: None,
+            request_id: None,
         })
-        .await;
+        .await
     }
 
     /// Record an edit action
@@ -222,8 +292,10 @@ impl TranslationAuditLog {
             count: None,
             batch_id: None,
             metadata: None,
+            ip: None,
+            request_id: None,
         })
-        .await;
+        .await
     }
 
     /// Record a bulk approve action
@@ -249,8 +321,10 @@ impl TranslationAuditLog {
             count: Some(count),
             batch_id,
             metadata: None,
+            ip: None,
+            request_id: None,
         })
-        .await;
+        .await
     }
 
     /// Record an add language action
@@ -279,8 +353,10 @@ impl TranslationAuditLog {
                 "label": label,
                 "copied_from": copied_from
             })),
+            ip: None,
+            request_id: None,
         })
-        .await;
+        .await
     }
 
     /// Record a generate action
@@ -307,8 +383,10 @@ impl TranslationAuditLog {
             count: Some(count),
             batch_id: Some(batch_id.to_string()),
             metadata: None,
+            ip: None,
+            request_id: None,
         })
-        .await;
+        .await
     }
 
     /// Query audit entries
@@ -320,11 +398,12 @@ impl TranslationAuditLog {
         from: Option<DateTime<Utc>>,
         limit: usize,
         offset: usize,
-    ) -> Vec<TranslationAuditEntry> {
-        let log = self.entries.read().await;
+    ) -> Result<Vec<TranslationAuditEntry>, AppError> {
+        let log = self.chain.read_since(from).await?;
 
         let filtered: Vec<_> = log
-            .iter()
+            .into_iter()
+            .map(|chained| chained.entry)
             .filter(|entry| {
                 if let Some(l) = lang {
                     if entry.lang != l {
@@ -348,26 +427,29 @@ impl TranslationAuditLog {
                 }
                 true
             })
-            .cloned()
             .collect();
 
         // Return most recent first
-        filtered
+        Ok(filtered
             .into_iter()
             .rev()
             .skip(offset)
             .take(limit.min(100))
-            .collect()
+            .collect())
     }
 
     /// Get history for a specific key
-    pub async fn get_key_history(&self, lang: &str, key: &str) -> Vec<TranslationAuditEntry> {
+    pub async fn get_key_history(
+        &self,
+        lang: &str,
+        key: &str,
+    ) -> Result<Vec<TranslationAuditEntry>, AppError> {
         self.query(Some(lang), None, Some(key), None, 100, 0).await
     }
 
-    /// Load entries from file
-    async fn load_from_file(&self) -> Result<Vec<TranslationAuditEntry>, AppError> {
-        let content = fs::read_to_string(&self.file_path)
+    /// Load entries from the legacy `audit.json`
+    async fn load_legacy(&self) -> Result<Vec<TranslationAuditEntry>, AppError> {
+        let content = fs::read_to_string(&self.legacy_path)
             .await
             .map_err(|e| AppError::Internal(format!("Failed to read audit log: {}", e)))?;
 
@@ -376,41 +458,6 @@ impl TranslationAuditLog {
 
         Ok(file.entries)
     }
-
-    /// Persist entries to file
-    async fn persist(&self) -> Result<(), AppError> {
-        let entries = self.entries.read().await;
-
-        let file = AuditLogFile {
-            entries: entries.clone(),
-        };
-
-        let content = serde_json::to_string_pretty(&file)
-            .map_err(|e| AppError::Internal(format!("Failed to serialize audit log: {}", e)))?;
-
-        // Ensure parent directory exists
-        if let Some(parent) = self.file_path.parent() {
-            fs::create_dir_all(parent).await.map_err(|e| {
-                AppError::Internal(format!(
-                    "Failed to create directory {}: {}",
-                    parent.display(),
-                    e
-                ))
-            })?;
-        }
-
-        // Write to temp file first, then rename (atomic)
-        let temp_path = self.file_path.with_extension("json.tmp");
-        fs::write(&temp_path, &content)
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to write audit log: {}", e)))?;
-
-        fs::rename(&temp_path, &self.file_path)
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to save audit log: {}", e)))?;
-
-        Ok(())
-    }
 }
 
 #[cfg(test)]
@@ -421,8 +468,11 @@ mod tests {
     #[tokio::test]
     async fn test_audit_log_record_and_query() {
         let temp_dir = TempDir::new().unwrap();
-        let file_path = temp_dir.path().join("audit.json");
-        let audit_log = TranslationAuditLog::new(file_path);
+        let audit_log = TranslationAuditLog::new(
+            temp_dir.path().join("audit"),
+            temp_dir.path().join("audit.json"),
+        );
+        audit_log.init().await.unwrap();
 
         audit_log
             .record_approve(
@@ -435,7 +485,10 @@ mod tests {
             )
             .await;
 
-        let entries = audit_log.query(Some("ru"), None, None, None, 10, 0).await;
+        let entries = audit_log
+            .query(Some("ru"), None, None, None, 10, 0)
+            .await
+            .unwrap();
         assert_eq!(entries.len(), 1);
         assert_eq!(entries[0].action, AuditAction::Approve);
         assert_eq!(entries[0].lang, "ru");
@@ -444,8 +497,11 @@ mod tests {
     #[tokio::test]
     async fn test_audit_log_key_history() {
         let temp_dir = TempDir::new().unwrap();
-        let file_path = temp_dir.path().join("audit.json");
-        let audit_log = TranslationAuditLog::new(file_path);
+        let audit_log = TranslationAuditLog::new(
+            temp_dir.path().join("audit"),
+            temp_dir.path().join("audit.json"),
+        );
+        audit_log.init().await.unwrap();
 
         // Record multiple actions for the same key
         audit_log
@@ -469,10 +525,44 @@ mod tests {
             )
             .await;
 
-        let history = audit_log.get_key_history("ru", "message.hello").await;
+        let history = audit_log
+            .get_key_history("ru", "message.hello")
+            .await
+            .unwrap();
         assert_eq!(history.len(), 2);
         // Most recent first
         assert_eq!(history[0].action, AuditAction::Edit);
         assert_eq!(history[1].action, AuditAction::Approve);
     }
+
+    #[tokio::test]
+    async fn test_legacy_audit_file_is_migrated_into_chain() {
+        let temp_dir = TempDir::new().unwrap();
+        let legacy_path = temp_dir.path().join("audit.json");
+        let legacy = serde_json::json!({
+            "entries": [{
+                "id": "old-1",
+                "timestamp": "2026-01-01T00:00:00Z",
+                "action": "edit",
+                "admin_user": "ops",
+                "lang": "de",
+                "key": "message.bye",
+                "old_value": "Tschüs",
+                "new_value": "Auf Wiedersehen"
+            }]
+        });
+        fs::write(&legacy_path, legacy.to_string()).await.unwrap();
+
+        let audit_log =
+            TranslationAuditLog::new(temp_dir.path().join("audit"), legacy_path.clone());
+        audit_log.init().await.unwrap();
+
+        assert!(!legacy_path.exists());
+        let chained = audit_log.chain().read_all().await.unwrap();
+        assert_eq!(chained.len(), 1);
+        let row = chained[0].to_row();
+        assert_eq!(row.resource, "translations/de/message.bye");
+        assert_eq!(row.before, Some(serde_json::json!("Tschüs")));
+        assert_eq!(row.after, Some(serde_json::json!("Auf Wiedersehen")));
+    }
 }
@@ -37,13 +37,14 @@ impl TranslationStorage {
     pub fn new(config_dir: &Path) -> Self {
         let locales_dir = config_dir.join("locales");
         let audit_path = locales_dir.join("audit.json");
+        let audit_dir = config_dir.join("audit").join("translations");
 
         Self {
             locales_dir,
             cached_locales: Arc::new(RwLock::new(HashMap::new())),
             cached_languages: Arc::new(RwLock::new(None)),
             cached_pending: Arc::new(RwLock::new(None)),
-            audit_log: Arc::new(TranslationAuditLog::new(audit_path)),
+            audit_log: Arc::new(TranslationAuditLog::new(audit_dir, audit_path)),
         }
     }
 
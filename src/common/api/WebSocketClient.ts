@@ -77,7 +77,7 @@ interface LeaseUpdateMessage {
 interface TxStatusMessage {
   type: "tx_status";
   tx_hash: string;
-  status: "pending" | "success" | "failed";
+  status: "pending" | "success" | "failed" | "timeout";
   error?: string;
 }
 
@@ -122,7 +122,7 @@ type ServerMessage =
 export type PriceCallback = (prices: Record<string, string>) => void;
 export type BalanceCallback = (address: string, balances: BalanceInfo[], totalValueUsd: string) => void;
 export type LeaseCallback = (lease: Partial<LeaseInfo> & Pick<LeaseInfo, "address" | "status">) => void;
-export type TxStatusCallback = (txHash: string, status: "pending" | "success" | "failed", error?: string) => void;
+export type TxStatusCallback = (txHash: string, status: "pending" | "success" | "failed" | "timeout", error?: string) => void;
 export type SkipTxCallback = (update: {
   tx_hash: string;
   status: "pending" | "success" | "failed";